@@ -6,13 +6,18 @@ use rcgen::{
     RevokedCertParams, SerialNumber, PKCS_ECDSA_P256_SHA256,
 };
 
+use std::collections::HashMap;
 use std::fs::File;
-use std::hint::black_box;
 use std::io::{ErrorKind, Read, Write};
 use std::path::Path;
 use std::sync::Mutex;
+use std::time::Duration;
 
-use webpki::{BorrowedCertRevocationList, CertRevocationList, OwnedCertRevocationList};
+use pki_types::{CertificateDer, Der, ServerName, TrustAnchor, UnixTime};
+use webpki::{
+    anchor_from_trusted_cert, BorrowedCertRevocationList, CertRevocationList, EndEntityCert,
+    KeyUsage, OwnedCertRevocationList, RevocationOptionsBuilder, TrustAnchorSource,
+};
 
 /// Lazy initialized CRL issuer to be used when generating CRL data. Includes
 /// `KeyUsagePurpose::CrlSign` key usage bit.
@@ -143,7 +148,7 @@ fn bench_search_borrowed_crl_small(c: &mut Bencher) {
         .unwrap()
         .into();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None))));
 }
 
 /// Benchmark searching a small CRL file in owned representation for a serial that does not
@@ -154,7 +159,7 @@ fn bench_search_owned_crl_small(c: &mut Bencher) {
         .unwrap()
         .into();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None))));
 }
 
 /// Benchmark searching a medium CRL file in borrowed representation for a serial that does not
@@ -165,7 +170,7 @@ fn bench_search_borrowed_crl_medium(c: &mut Bencher) {
         .unwrap()
         .into();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None))));
 }
 
 /// Benchmark searching a medium CRL file in owned representation for a serial that does not
@@ -176,7 +181,7 @@ fn bench_search_owned_crl_medium(c: &mut Bencher) {
         .unwrap()
         .into();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None))));
 }
 
 /// Benchmark searching a large CRL file in borrowed representation for a serial that does not
@@ -187,7 +192,7 @@ fn bench_search_borrowed_crl_large(c: &mut Bencher) {
         .unwrap()
         .into();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None))));
 }
 
 /// Benchmark searching a large CRL file in owned representation for a serial that does not
@@ -198,9 +203,213 @@ fn bench_search_owned_crl_large(c: &mut Bencher) {
         .unwrap()
         .into();
 
-    c.iter(|| black_box(assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None)))));
+    c.iter(|| assert!(matches!(crl.find_serial(FAKE_SERIAL), Ok(None))));
+}
+
+/// Benchmark verifying an end-entity certificate against the large CRL in owned representation,
+/// which doesn't revoke it, as a TLS server checking client certificates with a CRL loaded at
+/// startup would. With `verified`, the CRL's signature is verified once, outside of the
+/// benchmark task, rather than during each verification.
+fn check_revocation_owned_crl_large(c: &mut Bencher, verified: bool) {
+    let crl_bytes = load_or_generate("./benches/large.crl.der", LARGE_CRL_CERT_COUNT);
+    let (ca, ee) = {
+        let issuer = CRL_ISSUER.lock().unwrap();
+        let mut ee_params = CertificateParams::new(vec!["client.example.com".to_string()]);
+        ee_params.serial_number = Some(SerialNumber::from_slice(FAKE_SERIAL));
+        let ee = Certificate::from_params(ee_params).unwrap();
+        (
+            CertificateDer::from(issuer.serialize_der().unwrap()),
+            CertificateDer::from(ee.serialize_der_with_signer(&issuer).unwrap()),
+        )
+    };
+    let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+
+    let mut crl = OwnedCertRevocationList::from_der(&crl_bytes).unwrap();
+    if verified {
+        let issuer = webpki::Cert::try_from(&ca).unwrap();
+        crl = crl
+            .with_verified_signature(
+                webpki::ALL_VERIFICATION_ALGS,
+                issuer.subject_public_key_info(),
+            )
+            .unwrap();
+    }
+    let crls = [&CertRevocationList::from(crl)];
+    let revocation = RevocationOptionsBuilder::new(&crls).unwrap().build();
+    // 2024-01-01T00:00:00Z, before the CRL's nextUpdate.
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(1_704_067_200));
+
+    c.iter(|| {
+        cert.verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+            .at_time(time)
+            .key_usage(KeyUsage::client_auth())
+            .revocation(revocation)
+            .verify()
+            .unwrap()
+    });
+}
+
+fn bench_check_revocation_owned_crl_large(c: &mut Bencher) {
+    check_revocation_owned_crl_large(c, false);
+}
+
+fn bench_check_revocation_verified_owned_crl_large(c: &mut Bencher) {
+    check_revocation_owned_crl_large(c, true);
 }
 
+/// The certificates of a chain served by www.netflix.com: the end-entity certificate, its
+/// issuer, and the root.
+static NETFLIX_EE: &[u8] = include_bytes!("../tests/netflix/ee.der");
+static NETFLIX_INTER: &[u8] = include_bytes!("../tests/netflix/inter.der");
+static NETFLIX_CA: &[u8] = include_bytes!("../tests/netflix/ca.der");
+
+/// A time at which the Netflix chain is valid: 2017-04-17T15:08:36Z.
+const NETFLIX_TIME: u64 = 1_492_441_716;
+
+/// Benchmark parsing an end-entity certificate.
+fn bench_parse_end_entity(c: &mut Bencher) {
+    let ee = CertificateDer::from(NETFLIX_EE);
+
+    c.iter(|| EndEntityCert::try_from(&ee).unwrap());
+}
+
+/// Benchmark verifying a three certificate chain, with the end-entity certificate parsed
+/// once outside of the benchmark task, as a TLS client verifying a handshake would.
+fn bench_verify_chain(c: &mut Bencher) {
+    let ee = CertificateDer::from(NETFLIX_EE);
+    let intermediates = [CertificateDer::from(NETFLIX_INTER)];
+    let ca = CertificateDer::from(NETFLIX_CA);
+    let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(NETFLIX_TIME));
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+
+    c.iter(|| {
+        cert.verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+            .intermediates(&intermediates)
+            .at_time(time)
+            .key_usage(KeyUsage::server_auth())
+            .verify()
+            .unwrap()
+    });
+}
+
+/// Number of trust anchors in the large trust store benchmarks, about as many as the Mozilla
+/// root program together with a few thousand private CAs.
+const TRUST_STORE_ANCHOR_COUNT: usize = 4_000;
+
+/// The DER-encoded subjects of `TRUST_STORE_ANCHOR_COUNT - 1` distinct trust anchors, each a
+/// single commonName.
+fn trust_store_subjects() -> Vec<Vec<u8>> {
+    (1..TRUST_STORE_ANCHOR_COUNT)
+        .map(|i| {
+            let cn = format!("Benchmark Root CA {i:04}").into_bytes();
+            let len = u8::try_from(cn.len()).unwrap();
+            let mut subject = vec![0x31, len + 9, 0x30, len + 7, 0x06, 0x03, 0x55, 0x04, 0x03];
+            subject.extend([0x0c, len]);
+            subject.extend(cn);
+            subject
+        })
+        .collect()
+}
+
+/// A trust store of `TRUST_STORE_ANCHOR_COUNT` anchors, with the one the Netflix chain ends
+/// at last.
+fn trust_store<'a>(ca: &TrustAnchor<'a>, subjects: &'a [Vec<u8>]) -> Vec<TrustAnchor<'a>> {
+    subjects
+        .iter()
+        .map(|subject| TrustAnchor {
+            subject: Der::from(&subject[..]),
+            subject_public_key_info: ca.subject_public_key_info.clone(),
+            name_constraints: None,
+        })
+        .chain(core::iter::once(ca.clone()))
+        .collect()
+}
+
+/// A trust store indexed by subject.
+struct TrustStoreBySubject<'a>(HashMap<&'a [u8], Vec<TrustAnchor<'a>>>);
+
+impl<'a> TrustStoreBySubject<'a> {
+    fn new(anchors: &'a [TrustAnchor<'a>]) -> Self {
+        let mut map = HashMap::<_, Vec<_>>::new();
+        for anchor in anchors {
+            map.entry(anchor.subject.as_ref())
+                .or_default()
+                .push(anchor.clone());
+        }
+        Self(map)
+    }
+}
+
+impl TrustAnchorSource for TrustStoreBySubject<'_> {
+    fn anchors_for_subject(&self, subject: &[u8]) -> &[TrustAnchor<'_>] {
+        self.0.get(subject).map_or(&[], |anchors| anchors)
+    }
+}
+
+/// Benchmark verifying the Netflix chain against a large trust store passed as a slice, which
+/// is scanned in full for each issuer.
+fn bench_verify_chain_large_trust_store_slice(c: &mut Bencher) {
+    let ee = CertificateDer::from(NETFLIX_EE);
+    let intermediates = [CertificateDer::from(NETFLIX_INTER)];
+    let ca = CertificateDer::from(NETFLIX_CA);
+    let subjects = trust_store_subjects();
+    let anchors = trust_store(&anchor_from_trusted_cert(&ca).unwrap(), &subjects);
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(NETFLIX_TIME));
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+
+    c.iter(|| {
+        cert.verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+            .intermediates(&intermediates)
+            .at_time(time)
+            .key_usage(KeyUsage::server_auth())
+            .verify()
+            .unwrap()
+    });
+}
+
+/// Benchmark verifying the Netflix chain against the same trust store, looked up by subject
+/// in a hash map.
+fn bench_verify_chain_large_trust_store_map(c: &mut Bencher) {
+    let ee = CertificateDer::from(NETFLIX_EE);
+    let intermediates = [CertificateDer::from(NETFLIX_INTER)];
+    let ca = CertificateDer::from(NETFLIX_CA);
+    let subjects = trust_store_subjects();
+    let anchors = trust_store(&anchor_from_trusted_cert(&ca).unwrap(), &subjects);
+    let store = TrustStoreBySubject::new(&anchors);
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(NETFLIX_TIME));
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+
+    c.iter(|| {
+        cert.verifier_with_anchor_source(webpki::ALL_VERIFICATION_ALGS, &store)
+            .intermediates(&intermediates)
+            .at_time(time)
+            .key_usage(KeyUsage::server_auth())
+            .verify()
+            .unwrap()
+    });
+}
+
+/// Benchmark checking an end-entity certificate's subject name, against the last of its
+/// twelve subject alternative names.
+fn bench_verify_subject_name(c: &mut Bencher) {
+    let ee = CertificateDer::from(NETFLIX_EE);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let name = ServerName::try_from("www.netflix.com").unwrap();
+
+    c.iter(|| cert.verify_is_valid_for_subject_name(&name).unwrap());
+}
+
+benchmark_group!(
+    verify_benches,
+    bench_parse_end_entity,
+    bench_verify_chain,
+    bench_verify_chain_large_trust_store_slice,
+    bench_verify_chain_large_trust_store_map,
+    bench_verify_subject_name,
+);
+
 benchmark_group!(
     crl_benches,
     bench_parse_borrowed_crl_small,
@@ -215,6 +424,8 @@ benchmark_group!(
     bench_search_owned_crl_medium,
     bench_search_borrowed_crl_large,
     bench_search_owned_crl_large,
+    bench_check_revocation_owned_crl_large,
+    bench_check_revocation_verified_owned_crl_large,
 );
 
-benchmark_main!(crl_benches);
+benchmark_main!(verify_benches, crl_benches);
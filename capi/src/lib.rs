@@ -0,0 +1,8 @@
+//! The C API of rustls-webpki, in `webpki::capi`, built as a static and a dynamic library.
+//!
+//! The library has no functions of its own. `cbindgen.toml` in this directory generates the C
+//! header for those it exports.
+
+#![deny(missing_docs)]
+
+pub use webpki::capi::*;
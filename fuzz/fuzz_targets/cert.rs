@@ -0,0 +1,28 @@
+#![no_main]
+
+use core::time::Duration;
+
+use libfuzzer_sys::fuzz_target;
+use pki_types::{CertificateDer, UnixTime};
+use webpki::{anchor_from_trusted_cert, EndEntityCert};
+
+fuzz_target!(|data: &[u8]| {
+    let der = CertificateDer::from(data);
+    let cert = match EndEntityCert::try_from(&der) {
+        Ok(cert) => cert,
+        Err(_) => return,
+    };
+
+    // Verify the certificate against itself, so that path building and the checks of the
+    // issuer's extensions also see the input.
+    let anchors = match anchor_from_trusted_cert(&der) {
+        Ok(anchor) => [anchor],
+        Err(_) => return,
+    };
+    let intermediates = [der.clone()];
+    let _ = cert
+        .verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+        .intermediates(&intermediates)
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(1_700_000_000)))
+        .verify();
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use webpki::{BorrowedCertRevocationList, OwnedCertRevocationList};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(crl) = BorrowedCertRevocationList::from_der(data) {
+        for revoked_cert in &crl {
+            let _ = revoked_cert;
+        }
+    }
+    let _ = OwnedCertRevocationList::from_der(data);
+});
@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pki_types::{CertificateDer, ServerName};
+use webpki::EndEntityCert;
+
+// The input is a name, a NUL byte, and a certificate.
+fuzz_target!(|data: &[u8]| {
+    let (name, der) = match data.iter().position(|&byte| byte == 0) {
+        Some(nul) => (&data[..nul], &data[nul + 1..]),
+        None => return,
+    };
+    let name = match core::str::from_utf8(name)
+        .ok()
+        .and_then(|name| ServerName::try_from(name).ok())
+    {
+        Some(name) => name,
+        None => return,
+    };
+
+    let der = CertificateDer::from(der);
+    if let Ok(cert) = EndEntityCert::try_from(&der) {
+        let _ = cert.verify_is_valid_for_subject_name(&name);
+        let _ = cert.valid_dns_names().count();
+    }
+});
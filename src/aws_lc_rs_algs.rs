@@ -7,6 +7,7 @@ use crate::signed_data::alg_id;
 // so this is very similar to ring_algs.rs.
 
 /// A `SignatureVerificationAlgorithm` implemented using aws-lc-rs.
+#[derive(Debug)]
 struct AwsLcRsAlgorithm {
     public_key_alg_id: AlgorithmIdentifier,
     signature_alg_id: AlgorithmIdentifier,
@@ -62,6 +63,42 @@ pub static ECDSA_P384_SHA384: &dyn SignatureVerificationAlgorithm = &AwsLcRsAlgo
     verification_alg: &signature::ECDSA_P384_SHA384_ASN1,
 };
 
+/// ECDSA signatures using the P-521 curve and SHA-256. Deprecated.
+pub static ECDSA_P521_SHA256: &dyn SignatureVerificationAlgorithm = &AwsLcRsAlgorithm {
+    public_key_alg_id: alg_id::ECDSA_P521,
+    signature_alg_id: alg_id::ECDSA_SHA256,
+    verification_alg: &signature::ECDSA_P521_SHA256_ASN1,
+};
+
+/// ECDSA signatures using the P-521 curve and SHA-384. Deprecated.
+pub static ECDSA_P521_SHA384: &dyn SignatureVerificationAlgorithm = &AwsLcRsAlgorithm {
+    public_key_alg_id: alg_id::ECDSA_P521,
+    signature_alg_id: alg_id::ECDSA_SHA384,
+    verification_alg: &signature::ECDSA_P521_SHA384_ASN1,
+};
+
+/// ECDSA signatures using the P-521 curve and SHA-512.
+pub static ECDSA_P521_SHA512: &dyn SignatureVerificationAlgorithm = &AwsLcRsAlgorithm {
+    public_key_alg_id: alg_id::ECDSA_P521,
+    signature_alg_id: alg_id::ECDSA_SHA512,
+    verification_alg: &signature::ECDSA_P521_SHA512_ASN1,
+};
+
+/// RSA PKCS#1 1.5 signatures using SHA-1 for keys of 2048-8192 bits.
+///
+/// **This algorithm is insecure.** SHA-1 collisions are practical, so anyone able to get
+/// a certificate issued by a CA that signs with SHA-1 can forge certificates from it. This
+/// is only for closed networks with legacy PKIs that can't be reissued, and must never be
+/// used to verify certificates from the web PKI. It isn't in `ALL_VERIFICATION_ALGS`, and
+/// is only available with the `legacy_sha1` feature.
+#[cfg(feature = "legacy_sha1")]
+pub static RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY: &dyn SignatureVerificationAlgorithm =
+    &AwsLcRsAlgorithm {
+        public_key_alg_id: alg_id::RSA_ENCRYPTION,
+        signature_alg_id: alg_id::RSA_PKCS1_SHA1,
+        verification_alg: &signature::RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY,
+    };
+
 /// RSA PKCS#1 1.5 signatures using SHA-256 for keys of 2048-8192 bits.
 pub static RSA_PKCS1_2048_8192_SHA256: &dyn SignatureVerificationAlgorithm = &AwsLcRsAlgorithm {
     public_key_alg_id: alg_id::RSA_ENCRYPTION,
@@ -130,6 +167,50 @@ pub static ED25519: &dyn SignatureVerificationAlgorithm = &AwsLcRsAlgorithm {
     verification_alg: &signature::ED25519,
 };
 
+/// All the signature verification algorithms of this module, in the order of the crate-wide
+/// [`crate::ALL_VERIFICATION_ALGS`], except those for SHA-1.
+pub static ALL_VERIFICATION_ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    ECDSA_P256_SHA256,
+    ECDSA_P256_SHA384,
+    ECDSA_P384_SHA256,
+    ECDSA_P384_SHA384,
+    ECDSA_P521_SHA256,
+    ECDSA_P521_SHA384,
+    ECDSA_P521_SHA512,
+    ED25519,
+    RSA_PKCS1_2048_8192_SHA256,
+    RSA_PKCS1_2048_8192_SHA384,
+    RSA_PKCS1_2048_8192_SHA512,
+    RSA_PKCS1_3072_8192_SHA384,
+    RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
+    RSA_PSS_2048_8192_SHA384_LEGACY_KEY,
+    RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
+];
+
+/// The signature verification algorithms of this module that the FIPS 140-3 validated build
+/// of aws-lc accepts in FIPS mode: ECDSA on the NIST curves, and RSA PKCS#1 v1.5 and PSS
+/// with SHA-2.
+///
+/// Ed25519 and SHA-1 are excluded. Use this list, instead of [`ALL_VERIFICATION_ALGS`],
+/// where only approved algorithms may be used. It doesn't itself put aws-lc in FIPS mode:
+/// that is selected by enabling the `fips` feature of the `aws-lc-rs` crate.
+pub static FIPS_VERIFICATION_ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    ECDSA_P256_SHA256,
+    ECDSA_P256_SHA384,
+    ECDSA_P384_SHA256,
+    ECDSA_P384_SHA384,
+    ECDSA_P521_SHA256,
+    ECDSA_P521_SHA384,
+    ECDSA_P521_SHA512,
+    RSA_PKCS1_2048_8192_SHA256,
+    RSA_PKCS1_2048_8192_SHA384,
+    RSA_PKCS1_2048_8192_SHA512,
+    RSA_PKCS1_3072_8192_SHA384,
+    RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
+    RSA_PSS_2048_8192_SHA384_LEGACY_KEY,
+    RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
+];
+
 #[cfg(test)]
 #[path = "."]
 mod tests {
@@ -139,6 +220,7 @@ mod tests {
         // Reasonable algorithms.
         super::ECDSA_P256_SHA256,
         super::ECDSA_P384_SHA384,
+        super::ECDSA_P521_SHA512,
         super::ED25519,
         super::RSA_PKCS1_2048_8192_SHA256,
         super::RSA_PKCS1_2048_8192_SHA384,
@@ -150,6 +232,8 @@ mod tests {
         // Algorithms deprecated because they are nonsensical combinations.
         super::ECDSA_P256_SHA384, // Truncates digest.
         super::ECDSA_P384_SHA256, // Digest is unnecessarily short.
+        super::ECDSA_P521_SHA256, // Digest is unnecessarily short.
+        super::ECDSA_P521_SHA384, // Digest is unnecessarily short.
     ];
 
     const UNSUPPORTED_SIGNATURE_ALGORITHM_FOR_RSA_KEY: Error =
@@ -159,6 +243,10 @@ mod tests {
 
     const OK_IF_RSA_AVAILABLE: Result<(), Error> = Ok(());
 
+    // ecdsa-with-SHA512 is only supported with P-521 keys.
+    const UNSUPPORTED_SIGNATURE_ALGORITHM_FOR_ECDSA_SHA512: Error =
+        Error::UnsupportedSignatureAlgorithmForPublicKey;
+
     #[path = "alg_tests.rs"]
     mod alg_tests;
 }
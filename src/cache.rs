@@ -0,0 +1,404 @@
+use core::fmt;
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+
+use crate::cert::Cert;
+use crate::crl::{
+    ExpirationPolicy, RevocationCheckDepth, RevocationDatePolicy, RevocationOptions,
+    UnknownStatusPolicy,
+};
+use crate::end_entity::EndEntityCert;
+use crate::error::Error;
+use crate::trust_anchor::TrustAnchorSet;
+use crate::verify_cert::{KeyUsage, VerificationPolicy};
+#[cfg(feature = "zeroize")]
+use crate::zeroize::zeroize;
+
+/// A cache of successful verifications, for callers that verify the same chains repeatedly.
+///
+/// Entries are keyed on the exact DER encoding of the end-entity certificate and the set of
+/// intermediates, the [`TrustAnchorSet::generation()`] of the anchors, the
+/// [`crate::CrlSet::generation()`] of any CRLs, and the usage and policy. Each entry is only
+/// used for verification times within the period in which every certificate in the verified
+/// path is valid, and before the `nextUpdate` of every CRL consulted.
+///
+/// Only successful verifications are cached: a failure is always re-verified, since it may be
+/// due to a time-dependent condition. If revocation checking is requested with
+/// [`RevocationOptions`] not built by [`crate::RevocationOptionsBuilder::from_crl_set()`],
+/// changes to the CRLs cannot be detected, so the verification is not cached.
+///
+/// A cache must only be used with a single `supported_sig_algs` list.
+pub struct VerificationCache {
+    entries: HashMap<CacheKey, ValidityBounds>,
+    capacity: usize,
+}
+
+impl VerificationCache {
+    /// Construct a cache holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity,
+        }
+    }
+
+    /// Verify `end_entity` as [`EndEntityCert::verify_for_usage()`] would, first consulting
+    /// the cache.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify(
+        &mut self,
+        end_entity: &CertificateDer<'_>,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        trust_anchors: &TrustAnchorSet<'_>,
+        intermediate_certs: &[CertificateDer<'_>],
+        time: UnixTime,
+        usage: KeyUsage,
+        revocation: Option<RevocationOptions<'_>>,
+        policy: VerificationPolicy,
+    ) -> Result<(), Error> {
+        let key = CacheKey::new(
+            end_entity,
+            trust_anchors,
+            intermediate_certs,
+            usage,
+            revocation,
+            policy,
+        );
+        if let Some(bounds) = key.as_ref().and_then(|key| self.entries.get(key)) {
+            if bounds.contains(time) {
+                return Ok(());
+            }
+        }
+
+        let cert = EndEntityCert::try_from(end_entity)?;
+        let verifier = cert
+            .verifier(supported_sig_algs, trust_anchors.as_slice())
+            .intermediates(intermediate_certs)
+            .at_time(time)
+            .key_usage(usage)
+            .policy(policy);
+        let path = match revocation {
+            Some(revocation) => verifier.revocation(revocation),
+            None => verifier,
+        }
+        .verify()?;
+
+        let key = match key {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+
+        let mut bounds = ValidityBounds {
+            not_before: 0,
+            not_after: u64::MAX,
+        };
+        for cert in core::iter::once(&**path.end_entity()).chain(path.intermediate_certificates()) {
+            bounds.restrict_to_cert(cert)?;
+        }
+        if let Some(revocation) = revocation {
+            for crl in revocation.crls {
+                bounds.not_after = bounds.not_after.min(crl.next_update().as_secs());
+            }
+        }
+
+        if self.entries.len() >= self.capacity {
+            self.entries
+                .retain(|_, bounds| bounds.not_after >= time.as_secs());
+        }
+        if self.entries.len() >= self.capacity {
+            self.entries.clear();
+        }
+        if self.capacity > 0 {
+            self.entries.insert(key, bounds);
+        }
+
+        Ok(())
+    }
+
+    /// The number of cached verifications.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no verifications are cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all cached verifications.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+impl fmt::Debug for VerificationCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerificationCache")
+            .field("len", &self.entries.len())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct CacheKey {
+    end_entity: Vec<u8>,
+    /// Sorted and deduplicated, since neither affects the outcome of path building.
+    intermediates: Vec<Vec<u8>>,
+    anchors_generation: usize,
+    revocation: Option<(
+        usize,
+        RevocationCheckDepth,
+        UnknownStatusPolicy,
+        ExpirationPolicy,
+        RevocationDatePolicy,
+    )>,
+    usage: KeyUsage,
+    policy: VerificationPolicy,
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for CacheKey {
+    fn drop(&mut self) {
+        zeroize(&mut self.end_entity);
+        for intermediate in &mut self.intermediates {
+            zeroize(intermediate);
+        }
+    }
+}
+
+impl CacheKey {
+    /// Returns `None` if the verification can't be cached.
+    fn new(
+        end_entity: &CertificateDer<'_>,
+        trust_anchors: &TrustAnchorSet<'_>,
+        intermediate_certs: &[CertificateDer<'_>],
+        usage: KeyUsage,
+        revocation: Option<RevocationOptions<'_>>,
+        policy: VerificationPolicy,
+    ) -> Option<Self> {
+        let revocation = match revocation {
+            Some(revocation) => Some((
+                revocation.generation?,
+                revocation.depth,
+                revocation.status_policy,
+                revocation.expiration_policy,
+                revocation.revocation_date_policy,
+            )),
+            None => None,
+        };
+
+        let mut intermediates = intermediate_certs
+            .iter()
+            .map(|cert| cert.as_ref().to_vec())
+            .collect::<Vec<_>>();
+        intermediates.sort();
+        intermediates.dedup();
+
+        Some(Self {
+            end_entity: end_entity.as_ref().to_vec(),
+            intermediates,
+            anchors_generation: trust_anchors.generation(),
+            revocation,
+            usage,
+            policy,
+        })
+    }
+}
+
+/// In seconds since the Unix epoch, since `UnixTime` is not `Ord`.
+struct ValidityBounds {
+    not_before: u64,
+    not_after: u64,
+}
+
+impl ValidityBounds {
+    fn restrict_to_cert(&mut self, cert: &Cert<'_>) -> Result<(), Error> {
+        let (not_before, not_after) = cert.validity()?;
+        self.not_before = self.not_before.max(not_before.as_secs());
+        self.not_after = self.not_after.min(not_after.as_secs());
+        Ok(())
+    }
+
+    fn contains(&self, time: UnixTime) -> bool {
+        (self.not_before..=self.not_after).contains(&time.as_secs())
+    }
+}
+
+#[cfg(all(test, any(feature = "ring", feature = "aws_lc_rs")))]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::crl::RevocationOptionsBuilder;
+    use crate::crl::{CertRevocationList, CrlSet, OwnedCertRevocationList};
+    use crate::test_utils::{make_end_entity, make_issuer};
+    use crate::trust_anchor::anchor_from_trusted_cert;
+
+    fn crl(
+        issuer: &rcgen::Certificate,
+        revoked: Option<&CertificateDer<'_>>,
+    ) -> CertRevocationList<'static> {
+        let revoked_certs = match revoked {
+            Some(der) => {
+                let cert = Cert::from_der(untrusted::Input::from(der.as_ref())).unwrap();
+                vec![rcgen::RevokedCertParams {
+                    serial_number: rcgen::SerialNumber::from_slice(cert.serial()),
+                    revocation_time: rcgen::date_time_ymd(1980, 1, 1),
+                    reason_code: None,
+                    invalidity_date: None,
+                }]
+            }
+            None => Vec::new(),
+        };
+        let params = rcgen::CertificateRevocationListParams {
+            this_update: rcgen::date_time_ymd(1980, 1, 1),
+            next_update: rcgen::date_time_ymd(2030, 1, 1),
+            crl_number: rcgen::SerialNumber::from(1),
+            issuing_distribution_point: None,
+            revoked_certs,
+            alg: &rcgen::PKCS_ECDSA_P256_SHA256,
+            key_identifier_method: rcgen::KeyIdMethod::Sha256,
+        };
+        let der = rcgen::CertificateRevocationList::from_params(params)
+            .unwrap()
+            .serialize_der_with_signer(issuer)
+            .unwrap();
+        OwnedCertRevocationList::from_der(&der).unwrap().into()
+    }
+
+    #[test]
+    fn caches_successful_verification() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchor = [anchor_from_trusted_cert(&ca_der).unwrap()];
+        let anchors = TrustAnchorSet::from(&anchor[..]);
+        let ee = make_end_entity(&ca);
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+
+        let mut cache = VerificationCache::new(8);
+        let verify = |cache: &mut VerificationCache, anchors: &TrustAnchorSet<'_>, time| {
+            cache.verify(
+                &ee,
+                crate::ALL_VERIFICATION_ALGS,
+                anchors,
+                &[],
+                time,
+                KeyUsage::server_auth(),
+                None,
+                VerificationPolicy::default(),
+            )
+        };
+
+        assert_eq!(verify(&mut cache, &anchors, time), Ok(()));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(verify(&mut cache, &anchors, time), Ok(()));
+        assert_eq!(cache.len(), 1);
+
+        // Outside the EE's validity period the entry is not used.
+        let before = UnixTime::since_unix_epoch(Duration::from_secs(0));
+        assert!(matches!(
+            verify(&mut cache, &anchors, before),
+            Err(Error::CertNotValidYet { time, .. }) if time == before
+        ));
+
+        // A different anchor set is a different entry, even with the same contents.
+        let other = TrustAnchorSet::from(&anchor[..]);
+        assert_ne!(other.generation(), anchors.generation());
+        assert_eq!(verify(&mut cache, &other, time), Ok(()));
+        assert_eq!(cache.len(), 2);
+
+        // Failures aren't cached.
+        assert_eq!(
+            verify(&mut cache, &TrustAnchorSet::new(), time),
+            Err(Error::UnknownIssuer)
+        );
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn changed_crl_set_is_not_served_from_cache() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchor = [anchor_from_trusted_cert(&ca_der).unwrap()];
+        let anchors = TrustAnchorSet::from(&anchor[..]);
+        let ee = make_end_entity(&ca);
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+
+        let empty_crl = crl(&ca, None);
+        let revoking_crl = crl(&ca, Some(&ee));
+
+        let mut cache = VerificationCache::new(8);
+        let verify = |cache: &mut VerificationCache, crls: &CrlSet<'_>| {
+            let revocation = RevocationOptionsBuilder::from_crl_set(crls)
+                .unwrap()
+                .build();
+            cache.verify(
+                &ee,
+                crate::ALL_VERIFICATION_ALGS,
+                &anchors,
+                &[],
+                time,
+                KeyUsage::server_auth(),
+                Some(revocation),
+                VerificationPolicy::default(),
+            )
+        };
+
+        let mut crls = CrlSet::new();
+        crls.push(&empty_crl);
+        assert_eq!(verify(&mut cache, &crls), Ok(()));
+        assert_eq!(cache.len(), 1);
+
+        crls.clear();
+        crls.push(&revoking_crl);
+        assert_eq!(verify(&mut cache, &crls), Err(Error::CertRevoked));
+
+        // Revocation options not built from a `CrlSet` are never cached.
+        let slice = [&empty_crl];
+        let revocation = RevocationOptionsBuilder::new(&slice).unwrap().build();
+        cache.clear();
+        assert_eq!(
+            cache.verify(
+                &ee,
+                crate::ALL_VERIFICATION_ALGS,
+                &anchors,
+                &[],
+                time,
+                KeyUsage::server_auth(),
+                Some(revocation),
+                VerificationPolicy::default(),
+            ),
+            Ok(())
+        );
+        assert!(cache.is_empty());
+    }
+
+    #[cfg(all(feature = "zeroize", feature = "std"))]
+    #[test]
+    fn dropped_keys_are_zeroized() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchor = [anchor_from_trusted_cert(&ca_der).unwrap()];
+        let anchors = TrustAnchorSet::from(&anchor[..]);
+        let ee = make_end_entity(&ca);
+        let intermediate = make_end_entity(&ca);
+
+        let mut cache = VerificationCache::new(8);
+        let _ = cache.verify(
+            &ee,
+            crate::ALL_VERIFICATION_ALGS,
+            &anchors,
+            &[intermediate],
+            UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)),
+            KeyUsage::server_auth(),
+            None,
+            VerificationPolicy::default(),
+        );
+        assert_eq!(cache.len(), 1);
+        // The end-entity certificate and the intermediate.
+        assert_eq!(crate::zeroize::count_zeroized(|| drop(cache)), 2);
+    }
+}
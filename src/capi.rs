@@ -0,0 +1,893 @@
+//! A small C API over the core verification entry points.
+//!
+//! Every function reports failure through a [`c_int`] error code: [`WEBPKI_OK`] on success,
+//! a negative value for misuse of the API itself, and a positive value identifying the
+//! [`Error`] that caused verification to fail. The positive codes are stable: a variant
+//! keeps its code across releases, and new variants are assigned new codes.
+//!
+//! No function in this module unwinds across the FFI boundary; a panic is reported as
+//! [`WEBPKI_ERROR_PANIC`].
+//!
+//! Trust anchors are given to [`webpki_verify_tls_server_cert()`] as an anchor store, loaded
+//! once with [`webpki_anchor_store_add_pem()`] or [`webpki_anchor_store_add_der()`], rather
+//! than as PEM to be parsed again by every call.
+//!
+//! This module is built into static and dynamic libraries by the `capi` crate in the
+//! repository, whose `cbindgen.toml` generates the C header:
+//!
+//! ```text
+//! cd capi && cbindgen --config cbindgen.toml --output webpki.h
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::{fmt, ptr, slice};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use base64::{engine::general_purpose, Engine as _};
+use pki_types::{CertificateDer, ServerName, TrustAnchor, UnixTime};
+
+use crate::crl::{CertRevocationList, OwnedCertRevocationList, RevocationOptionsBuilder};
+use crate::end_entity::EndEntityCert;
+use crate::error::Error;
+use crate::trust_anchor::anchor_from_trusted_cert;
+use crate::verify_cert::KeyUsage;
+#[cfg(feature = "zeroize")]
+use crate::zeroize::zeroize;
+use crate::zeroize::Redacted;
+use crate::ALL_VERIFICATION_ALGS;
+
+/// A borrowed, length-delimited DER buffer.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct WebpkiBuffer {
+    /// Pointer to the first byte of the buffer. May be null if `len` is zero.
+    pub data: *const u8,
+    /// Length of the buffer in bytes.
+    pub len: usize,
+}
+
+/// An opaque set of trust anchors, created with [`webpki_anchor_store_new()`].
+#[derive(Debug, Default)]
+pub struct WebpkiAnchorStore {
+    anchors: Vec<OwnedAnchor>,
+}
+
+/// A trust anchor whose contents are held in buffers of its own, rather than in those of
+/// `TrustAnchor::to_owned()`, so that they can be zeroized when it is dropped.
+struct OwnedAnchor {
+    subject: Vec<u8>,
+    subject_public_key_info: Vec<u8>,
+    name_constraints: Option<Vec<u8>>,
+}
+
+impl OwnedAnchor {
+    fn new(anchor: &TrustAnchor<'_>) -> Self {
+        Self {
+            subject: anchor.subject.to_vec(),
+            subject_public_key_info: anchor.subject_public_key_info.to_vec(),
+            name_constraints: anchor.name_constraints.as_ref().map(|nc| nc.to_vec()),
+        }
+    }
+
+    fn anchor(&self) -> TrustAnchor<'_> {
+        TrustAnchor {
+            subject: self.subject[..].into(),
+            subject_public_key_info: self.subject_public_key_info[..].into(),
+            name_constraints: self.name_constraints.as_deref().map(Into::into),
+        }
+    }
+}
+
+impl fmt::Debug for OwnedAnchor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedAnchor")
+            .field("subject", &Redacted(&self.subject))
+            .field(
+                "subject_public_key_info",
+                &Redacted(&self.subject_public_key_info),
+            )
+            .field(
+                "name_constraints",
+                &self.name_constraints.as_deref().map(Redacted),
+            )
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for OwnedAnchor {
+    fn drop(&mut self) {
+        zeroize(&mut self.subject);
+        zeroize(&mut self.subject_public_key_info);
+        if let Some(name_constraints) = &mut self.name_constraints {
+            zeroize(name_constraints);
+        }
+    }
+}
+
+/// An opaque set of certificate revocation lists, created with [`webpki_crl_set_new()`].
+#[derive(Debug, Default)]
+pub struct WebpkiCrlSet {
+    crls: Vec<CertRevocationList<'static>>,
+}
+
+/// The operation succeeded.
+pub const WEBPKI_OK: c_int = 0;
+/// A required pointer was null, or a length was out of range.
+pub const WEBPKI_ERROR_INVALID_ARGUMENT: c_int = -1;
+/// The call panicked. This indicates a bug in webpki.
+pub const WEBPKI_ERROR_PANIC: c_int = -2;
+
+/// [`Error::BadDer`]
+pub const WEBPKI_ERROR_BAD_DER: c_int = 1;
+/// [`Error::BadDerTime`]
+pub const WEBPKI_ERROR_BAD_DER_TIME: c_int = 2;
+/// [`Error::BerEncodingNotAllowed`]
+pub const WEBPKI_ERROR_BER_ENCODING_NOT_ALLOWED: c_int = 3;
+/// [`Error::CaConstraintsNotCritical`]
+pub const WEBPKI_ERROR_CA_CONSTRAINTS_NOT_CRITICAL: c_int = 4;
+/// [`Error::CaUsedAsEndEntity`]
+pub const WEBPKI_ERROR_CA_USED_AS_END_ENTITY: c_int = 5;
+/// [`Error::CertExpired`]
+pub const WEBPKI_ERROR_CERT_EXPIRED: c_int = 6;
+/// [`Error::CertNotValidForName`]
+pub const WEBPKI_ERROR_CERT_NOT_VALID_FOR_NAME: c_int = 7;
+/// [`Error::CertNotValidYet`]
+pub const WEBPKI_ERROR_CERT_NOT_VALID_YET: c_int = 8;
+/// [`Error::CertRevoked`]
+pub const WEBPKI_ERROR_CERT_REVOKED: c_int = 9;
+/// [`Error::DeprecatedUniqueIdentifiersPresent`]
+pub const WEBPKI_ERROR_DEPRECATED_UNIQUE_IDENTIFIERS_PRESENT: c_int = 10;
+/// [`Error::EkuCriticalityMismatch`]
+pub const WEBPKI_ERROR_EKU_CRITICALITY_MISMATCH: c_int = 11;
+/// [`Error::EmptyIssuer`]
+pub const WEBPKI_ERROR_EMPTY_ISSUER: c_int = 12;
+/// [`Error::EmptySubjectWithoutCriticalSan`]
+pub const WEBPKI_ERROR_EMPTY_SUBJECT_WITHOUT_CRITICAL_SAN: c_int = 13;
+/// [`Error::EndEntityUsedAsCa`]
+pub const WEBPKI_ERROR_END_ENTITY_USED_AS_CA: c_int = 14;
+/// [`Error::ExtensionValueInvalid`]
+pub const WEBPKI_ERROR_EXTENSION_VALUE_INVALID: c_int = 15;
+/// [`Error::ImproperTimeEncoding`]
+pub const WEBPKI_ERROR_IMPROPER_TIME_ENCODING: c_int = 16;
+/// [`Error::InvalidCertValidity`]
+pub const WEBPKI_ERROR_INVALID_CERT_VALIDITY: c_int = 17;
+/// [`Error::InvalidCrlNumber`]
+pub const WEBPKI_ERROR_INVALID_CRL_NUMBER: c_int = 18;
+/// [`Error::InvalidCrlValidityPeriod`]
+pub const WEBPKI_ERROR_INVALID_CRL_VALIDITY_PERIOD: c_int = 19;
+/// [`Error::InvalidNetworkMaskConstraint`]
+pub const WEBPKI_ERROR_INVALID_NETWORK_MASK_CONSTRAINT: c_int = 20;
+/// [`Error::InvalidSerialNumber`]
+pub const WEBPKI_ERROR_INVALID_SERIAL_NUMBER: c_int = 21;
+/// [`Error::InvalidCrlSignatureForPublicKey`]
+pub const WEBPKI_ERROR_INVALID_CRL_SIGNATURE_FOR_PUBLIC_KEY: c_int = 22;
+/// [`Error::InvalidSignatureForPublicKey`]
+pub const WEBPKI_ERROR_INVALID_SIGNATURE_FOR_PUBLIC_KEY: c_int = 23;
+/// [`Error::IssuerKeyUsageInvalid`]
+pub const WEBPKI_ERROR_ISSUER_KEY_USAGE_INVALID: c_int = 24;
+/// [`Error::IssuerNotCrlSigner`]
+pub const WEBPKI_ERROR_ISSUER_NOT_CRL_SIGNER: c_int = 25;
+/// [`Error::KeyIdentifierMismatch`]
+pub const WEBPKI_ERROR_KEY_IDENTIFIER_MISMATCH: c_int = 26;
+/// [`Error::MalformedDnsIdentifier`]
+pub const WEBPKI_ERROR_MALFORMED_DNS_IDENTIFIER: c_int = 27;
+/// [`Error::MalformedDnsNameInSan`]
+pub const WEBPKI_ERROR_MALFORMED_DNS_NAME_IN_SAN: c_int = 28;
+/// [`Error::MalformedExtensions`]
+pub const WEBPKI_ERROR_MALFORMED_EXTENSIONS: c_int = 29;
+/// [`Error::MalformedIpAddress`]
+pub const WEBPKI_ERROR_MALFORMED_IP_ADDRESS: c_int = 30;
+/// [`Error::MalformedNameConstraint`]
+pub const WEBPKI_ERROR_MALFORMED_NAME_CONSTRAINT: c_int = 31;
+/// [`Error::MaximumNameConstraintComparisonsExceeded`]
+pub const WEBPKI_ERROR_MAXIMUM_NAME_CONSTRAINT_COMPARISONS_EXCEEDED: c_int = 32;
+/// [`Error::MaximumPathBuildCallsExceeded`]
+pub const WEBPKI_ERROR_MAXIMUM_PATH_BUILD_CALLS_EXCEEDED: c_int = 33;
+/// [`Error::MaximumPathDepthExceeded`]
+pub const WEBPKI_ERROR_MAXIMUM_PATH_DEPTH_EXCEEDED: c_int = 34;
+/// [`Error::MaximumSignatureChecksExceeded`]
+pub const WEBPKI_ERROR_MAXIMUM_SIGNATURE_CHECKS_EXCEEDED: c_int = 35;
+/// [`Error::NameConstraintViolation`]
+pub const WEBPKI_ERROR_NAME_CONSTRAINT_VIOLATION: c_int = 36;
+/// [`Error::PathLenConstraintViolated`]
+pub const WEBPKI_ERROR_PATH_LEN_CONSTRAINT_VIOLATED: c_int = 37;
+/// [`Error::RequiredEkuNotFound`]
+pub const WEBPKI_ERROR_REQUIRED_EKU_NOT_FOUND: c_int = 38;
+/// [`Error::SignatureAlgorithmMismatch`]
+pub const WEBPKI_ERROR_SIGNATURE_ALGORITHM_MISMATCH: c_int = 39;
+/// [`Error::TrailingData`]
+pub const WEBPKI_ERROR_TRAILING_DATA: c_int = 40;
+/// [`Error::UnknownIssuer`]
+pub const WEBPKI_ERROR_UNKNOWN_ISSUER: c_int = 41;
+/// [`Error::UnknownRevocationStatus`]
+pub const WEBPKI_ERROR_UNKNOWN_REVOCATION_STATUS: c_int = 42;
+/// [`Error::UnsupportedCertVersion`]
+pub const WEBPKI_ERROR_UNSUPPORTED_CERT_VERSION: c_int = 43;
+/// [`Error::UnsupportedCertVersionForCa`]
+pub const WEBPKI_ERROR_UNSUPPORTED_CERT_VERSION_FOR_CA: c_int = 44;
+/// [`Error::UnsupportedCriticalExtension`]
+pub const WEBPKI_ERROR_UNSUPPORTED_CRITICAL_EXTENSION: c_int = 45;
+/// [`Error::UnsupportedCrlIssuingDistributionPoint`]
+pub const WEBPKI_ERROR_UNSUPPORTED_CRL_ISSUING_DISTRIBUTION_POINT: c_int = 46;
+/// [`Error::UnsupportedCrlVersion`]
+pub const WEBPKI_ERROR_UNSUPPORTED_CRL_VERSION: c_int = 47;
+/// [`Error::UnsupportedDeltaCrl`]
+pub const WEBPKI_ERROR_UNSUPPORTED_DELTA_CRL: c_int = 48;
+/// [`Error::UnsupportedIndirectCrl`]
+pub const WEBPKI_ERROR_UNSUPPORTED_INDIRECT_CRL: c_int = 49;
+/// [`Error::UnsupportedNameType`]
+pub const WEBPKI_ERROR_UNSUPPORTED_NAME_TYPE: c_int = 50;
+/// [`Error::UnsupportedRevocationReason`]
+pub const WEBPKI_ERROR_UNSUPPORTED_REVOCATION_REASON: c_int = 51;
+/// [`Error::UnsupportedRevocationReasonsPartitioning`]
+pub const WEBPKI_ERROR_UNSUPPORTED_REVOCATION_REASONS_PARTITIONING: c_int = 52;
+/// [`Error::UnsupportedCrlSignatureAlgorithm`]
+pub const WEBPKI_ERROR_UNSUPPORTED_CRL_SIGNATURE_ALGORITHM: c_int = 53;
+/// [`Error::UnsupportedSignatureAlgorithm`]
+pub const WEBPKI_ERROR_UNSUPPORTED_SIGNATURE_ALGORITHM: c_int = 54;
+/// [`Error::UnsupportedCrlSignatureAlgorithmForPublicKey`]
+pub const WEBPKI_ERROR_UNSUPPORTED_CRL_SIGNATURE_ALGORITHM_FOR_PUBLIC_KEY: c_int = 55;
+/// [`Error::UnsupportedSignatureAlgorithmForPublicKey`]
+pub const WEBPKI_ERROR_UNSUPPORTED_SIGNATURE_ALGORITHM_FOR_PUBLIC_KEY: c_int = 56;
+/// [`Error::CrlExpired`]
+pub const WEBPKI_ERROR_CRL_EXPIRED: c_int = 57;
+/// [`Error::MaximumCrlSizeExceeded`]
+pub const WEBPKI_ERROR_MAXIMUM_CRL_SIZE_EXCEEDED: c_int = 58;
+/// [`Error::MalformedOtherNameInSan`]
+pub const WEBPKI_ERROR_MALFORMED_OTHER_NAME_IN_SAN: c_int = 59;
+/// [`Error::ValidityPeriodTooLong`]
+pub const WEBPKI_ERROR_VALIDITY_PERIOD_TOO_LONG: c_int = 60;
+/// [`Error::DeltaCrlMismatch`]
+pub const WEBPKI_ERROR_DELTA_CRL_MISMATCH: c_int = 61;
+/// [`Error::MalformedOcspResponse`]
+pub const WEBPKI_ERROR_MALFORMED_OCSP_RESPONSE: c_int = 62;
+/// [`Error::OcspResponderNotAuthorized`]
+pub const WEBPKI_ERROR_OCSP_RESPONDER_NOT_AUTHORIZED: c_int = 63;
+/// [`Error::OcspResponseCertMismatch`]
+pub const WEBPKI_ERROR_OCSP_RESPONSE_CERT_MISMATCH: c_int = 64;
+/// [`Error::OcspResponseExpired`]
+pub const WEBPKI_ERROR_OCSP_RESPONSE_EXPIRED: c_int = 65;
+/// [`Error::OcspResponseNotValidYet`]
+pub const WEBPKI_ERROR_OCSP_RESPONSE_NOT_VALID_YET: c_int = 66;
+/// [`Error::UnsuccessfulOcspResponse`]
+pub const WEBPKI_ERROR_UNSUCCESSFUL_OCSP_RESPONSE: c_int = 67;
+/// [`Error::UnsupportedOcspResponseType`]
+pub const WEBPKI_ERROR_UNSUPPORTED_OCSP_RESPONSE_TYPE: c_int = 68;
+/// [`Error::EndEntityKeyUsageInvalid`]
+pub const WEBPKI_ERROR_END_ENTITY_KEY_USAGE_INVALID: c_int = 69;
+/// [`Error::MalformedBundleCert`]
+pub const WEBPKI_ERROR_MALFORMED_BUNDLE_CERT: c_int = 70;
+/// [`Error::BundleCertNotCa`]
+pub const WEBPKI_ERROR_BUNDLE_CERT_NOT_CA: c_int = 71;
+/// [`Error::RequiredPolicyNotMet`]
+pub const WEBPKI_ERROR_REQUIRED_POLICY_NOT_MET: c_int = 72;
+/// [`Error::MalformedEmailIdentifier`]
+pub const WEBPKI_ERROR_MALFORMED_EMAIL_IDENTIFIER: c_int = 73;
+/// [`Error::MalformedSctList`]
+pub const WEBPKI_ERROR_MALFORMED_SCT_LIST: c_int = 74;
+/// [`Error::UnsupportedSctVersion`]
+pub const WEBPKI_ERROR_UNSUPPORTED_SCT_VERSION: c_int = 75;
+/// [`Error::TrustAnchorKeyUsageInvalid`]
+pub const WEBPKI_ERROR_TRUST_ANCHOR_KEY_USAGE_INVALID: c_int = 76;
+/// [`Error::TrustAnchorNotCa`]
+pub const WEBPKI_ERROR_TRUST_ANCHOR_NOT_CA: c_int = 77;
+/// [`Error::TrustAnchorNotSelfIssued`]
+pub const WEBPKI_ERROR_TRUST_ANCHOR_NOT_SELF_ISSUED: c_int = 78;
+/// [`Error::TrustAnchorNotSelfSigned`]
+pub const WEBPKI_ERROR_TRUST_ANCHOR_NOT_SELF_SIGNED: c_int = 79;
+/// [`Error::NonPositiveSerialNumber`]
+pub const WEBPKI_ERROR_NON_POSITIVE_SERIAL_NUMBER: c_int = 80;
+/// [`Error::SerialNumberTooLong`]
+pub const WEBPKI_ERROR_SERIAL_NUMBER_TOO_LONG: c_int = 81;
+/// [`Error::IntermediatesOutOfOrder`]
+pub const WEBPKI_ERROR_INTERMEDIATES_OUT_OF_ORDER: c_int = 82;
+/// [`Error::PublicKeyTooSmall`]
+pub const WEBPKI_ERROR_PUBLIC_KEY_TOO_SMALL: c_int = 83;
+/// [`Error::EmptySubjectAltName`]
+pub const WEBPKI_ERROR_EMPTY_SUBJECT_ALT_NAME: c_int = 84;
+/// [`Error::InvalidValidityPeriod`]
+pub const WEBPKI_ERROR_INVALID_VALIDITY_PERIOD: c_int = 85;
+/// [`Error::MisalignedOcspResponse`]
+pub const WEBPKI_ERROR_MISALIGNED_OCSP_RESPONSE: c_int = 86;
+/// [`Error::SurplusOcspResponses`]
+pub const WEBPKI_ERROR_SURPLUS_OCSP_RESPONSES: c_int = 87;
+
+/// Returns the stable C error code for `error`.
+pub fn error_code(error: Error) -> c_int {
+    match error {
+        Error::BadDer => WEBPKI_ERROR_BAD_DER,
+        Error::BadDerTime => WEBPKI_ERROR_BAD_DER_TIME,
+        Error::BerEncodingNotAllowed { .. } => WEBPKI_ERROR_BER_ENCODING_NOT_ALLOWED,
+        Error::CaConstraintsNotCritical { .. } => WEBPKI_ERROR_CA_CONSTRAINTS_NOT_CRITICAL,
+        Error::CaUsedAsEndEntity => WEBPKI_ERROR_CA_USED_AS_END_ENTITY,
+        Error::CertExpired { .. } => WEBPKI_ERROR_CERT_EXPIRED,
+        Error::CertNotValidForName => WEBPKI_ERROR_CERT_NOT_VALID_FOR_NAME,
+        Error::CertNotValidYet { .. } => WEBPKI_ERROR_CERT_NOT_VALID_YET,
+        Error::CertRevoked => WEBPKI_ERROR_CERT_REVOKED,
+        Error::DeprecatedUniqueIdentifiersPresent => {
+            WEBPKI_ERROR_DEPRECATED_UNIQUE_IDENTIFIERS_PRESENT
+        }
+        Error::EkuCriticalityMismatch => WEBPKI_ERROR_EKU_CRITICALITY_MISMATCH,
+        Error::EmptyIssuer => WEBPKI_ERROR_EMPTY_ISSUER,
+        Error::EmptySubjectWithoutCriticalSan => WEBPKI_ERROR_EMPTY_SUBJECT_WITHOUT_CRITICAL_SAN,
+        Error::EndEntityUsedAsCa => WEBPKI_ERROR_END_ENTITY_USED_AS_CA,
+        Error::ExtensionValueInvalid => WEBPKI_ERROR_EXTENSION_VALUE_INVALID,
+        Error::ImproperTimeEncoding { .. } => WEBPKI_ERROR_IMPROPER_TIME_ENCODING,
+        Error::InvalidCertValidity => WEBPKI_ERROR_INVALID_CERT_VALIDITY,
+        Error::InvalidCrlNumber => WEBPKI_ERROR_INVALID_CRL_NUMBER,
+        Error::InvalidCrlValidityPeriod => WEBPKI_ERROR_INVALID_CRL_VALIDITY_PERIOD,
+        Error::InvalidNetworkMaskConstraint => WEBPKI_ERROR_INVALID_NETWORK_MASK_CONSTRAINT,
+        Error::InvalidSerialNumber => WEBPKI_ERROR_INVALID_SERIAL_NUMBER,
+        Error::InvalidCrlSignatureForPublicKey => WEBPKI_ERROR_INVALID_CRL_SIGNATURE_FOR_PUBLIC_KEY,
+        Error::InvalidSignatureForPublicKey => WEBPKI_ERROR_INVALID_SIGNATURE_FOR_PUBLIC_KEY,
+        Error::IssuerKeyUsageInvalid => WEBPKI_ERROR_ISSUER_KEY_USAGE_INVALID,
+        Error::IssuerNotCrlSigner => WEBPKI_ERROR_ISSUER_NOT_CRL_SIGNER,
+        Error::KeyIdentifierMismatch => WEBPKI_ERROR_KEY_IDENTIFIER_MISMATCH,
+        Error::MalformedDnsIdentifier => WEBPKI_ERROR_MALFORMED_DNS_IDENTIFIER,
+        Error::MalformedDnsNameInSan => WEBPKI_ERROR_MALFORMED_DNS_NAME_IN_SAN,
+        Error::MalformedExtensions => WEBPKI_ERROR_MALFORMED_EXTENSIONS,
+        Error::MalformedIpAddress => WEBPKI_ERROR_MALFORMED_IP_ADDRESS,
+        Error::MalformedNameConstraint => WEBPKI_ERROR_MALFORMED_NAME_CONSTRAINT,
+        Error::MaximumNameConstraintComparisonsExceeded => {
+            WEBPKI_ERROR_MAXIMUM_NAME_CONSTRAINT_COMPARISONS_EXCEEDED
+        }
+        Error::MaximumPathBuildCallsExceeded => WEBPKI_ERROR_MAXIMUM_PATH_BUILD_CALLS_EXCEEDED,
+        Error::MaximumPathDepthExceeded => WEBPKI_ERROR_MAXIMUM_PATH_DEPTH_EXCEEDED,
+        Error::MaximumSignatureChecksExceeded => WEBPKI_ERROR_MAXIMUM_SIGNATURE_CHECKS_EXCEEDED,
+        Error::NameConstraintViolation => WEBPKI_ERROR_NAME_CONSTRAINT_VIOLATION,
+        Error::PathLenConstraintViolated => WEBPKI_ERROR_PATH_LEN_CONSTRAINT_VIOLATED,
+        Error::RequiredEkuNotFound => WEBPKI_ERROR_REQUIRED_EKU_NOT_FOUND,
+        Error::SignatureAlgorithmMismatch => WEBPKI_ERROR_SIGNATURE_ALGORITHM_MISMATCH,
+        Error::TrailingData(_) => WEBPKI_ERROR_TRAILING_DATA,
+        Error::UnknownIssuer => WEBPKI_ERROR_UNKNOWN_ISSUER,
+        Error::UnknownRevocationStatus => WEBPKI_ERROR_UNKNOWN_REVOCATION_STATUS,
+        Error::UnsupportedCertVersion => WEBPKI_ERROR_UNSUPPORTED_CERT_VERSION,
+        Error::UnsupportedCertVersionForCa => WEBPKI_ERROR_UNSUPPORTED_CERT_VERSION_FOR_CA,
+        Error::UnsupportedCriticalExtension => WEBPKI_ERROR_UNSUPPORTED_CRITICAL_EXTENSION,
+        Error::UnsupportedCrlIssuingDistributionPoint => {
+            WEBPKI_ERROR_UNSUPPORTED_CRL_ISSUING_DISTRIBUTION_POINT
+        }
+        Error::UnsupportedCrlVersion => WEBPKI_ERROR_UNSUPPORTED_CRL_VERSION,
+        Error::UnsupportedDeltaCrl => WEBPKI_ERROR_UNSUPPORTED_DELTA_CRL,
+        Error::UnsupportedIndirectCrl => WEBPKI_ERROR_UNSUPPORTED_INDIRECT_CRL,
+        Error::UnsupportedNameType => WEBPKI_ERROR_UNSUPPORTED_NAME_TYPE,
+        Error::UnsupportedRevocationReason => WEBPKI_ERROR_UNSUPPORTED_REVOCATION_REASON,
+        Error::UnsupportedRevocationReasonsPartitioning => {
+            WEBPKI_ERROR_UNSUPPORTED_REVOCATION_REASONS_PARTITIONING
+        }
+        Error::UnsupportedCrlSignatureAlgorithm => WEBPKI_ERROR_UNSUPPORTED_CRL_SIGNATURE_ALGORITHM,
+        Error::UnsupportedSignatureAlgorithm => WEBPKI_ERROR_UNSUPPORTED_SIGNATURE_ALGORITHM,
+        Error::UnsupportedCrlSignatureAlgorithmForPublicKey => {
+            WEBPKI_ERROR_UNSUPPORTED_CRL_SIGNATURE_ALGORITHM_FOR_PUBLIC_KEY
+        }
+        Error::UnsupportedSignatureAlgorithmForPublicKey => {
+            WEBPKI_ERROR_UNSUPPORTED_SIGNATURE_ALGORITHM_FOR_PUBLIC_KEY
+        }
+        Error::CrlExpired => WEBPKI_ERROR_CRL_EXPIRED,
+        Error::MaximumCrlSizeExceeded => WEBPKI_ERROR_MAXIMUM_CRL_SIZE_EXCEEDED,
+        Error::MalformedOtherNameInSan => WEBPKI_ERROR_MALFORMED_OTHER_NAME_IN_SAN,
+        Error::ValidityPeriodTooLong { .. } => WEBPKI_ERROR_VALIDITY_PERIOD_TOO_LONG,
+        Error::DeltaCrlMismatch => WEBPKI_ERROR_DELTA_CRL_MISMATCH,
+        Error::MalformedOcspResponse => WEBPKI_ERROR_MALFORMED_OCSP_RESPONSE,
+        Error::OcspResponderNotAuthorized => WEBPKI_ERROR_OCSP_RESPONDER_NOT_AUTHORIZED,
+        Error::OcspResponseCertMismatch => WEBPKI_ERROR_OCSP_RESPONSE_CERT_MISMATCH,
+        Error::OcspResponseExpired => WEBPKI_ERROR_OCSP_RESPONSE_EXPIRED,
+        Error::OcspResponseNotValidYet => WEBPKI_ERROR_OCSP_RESPONSE_NOT_VALID_YET,
+        Error::UnsuccessfulOcspResponse => WEBPKI_ERROR_UNSUCCESSFUL_OCSP_RESPONSE,
+        Error::UnsupportedOcspResponseType => WEBPKI_ERROR_UNSUPPORTED_OCSP_RESPONSE_TYPE,
+        Error::EndEntityKeyUsageInvalid => WEBPKI_ERROR_END_ENTITY_KEY_USAGE_INVALID,
+        Error::MalformedBundleCert { .. } => WEBPKI_ERROR_MALFORMED_BUNDLE_CERT,
+        Error::BundleCertNotCa { .. } => WEBPKI_ERROR_BUNDLE_CERT_NOT_CA,
+        Error::RequiredPolicyNotMet => WEBPKI_ERROR_REQUIRED_POLICY_NOT_MET,
+        Error::MalformedEmailIdentifier => WEBPKI_ERROR_MALFORMED_EMAIL_IDENTIFIER,
+        Error::MalformedSctList => WEBPKI_ERROR_MALFORMED_SCT_LIST,
+        Error::UnsupportedSctVersion => WEBPKI_ERROR_UNSUPPORTED_SCT_VERSION,
+        Error::TrustAnchorKeyUsageInvalid => WEBPKI_ERROR_TRUST_ANCHOR_KEY_USAGE_INVALID,
+        Error::TrustAnchorNotCa => WEBPKI_ERROR_TRUST_ANCHOR_NOT_CA,
+        Error::TrustAnchorNotSelfIssued => WEBPKI_ERROR_TRUST_ANCHOR_NOT_SELF_ISSUED,
+        Error::TrustAnchorNotSelfSigned => WEBPKI_ERROR_TRUST_ANCHOR_NOT_SELF_SIGNED,
+        Error::NonPositiveSerialNumber => WEBPKI_ERROR_NON_POSITIVE_SERIAL_NUMBER,
+        Error::SerialNumberTooLong => WEBPKI_ERROR_SERIAL_NUMBER_TOO_LONG,
+        Error::IntermediatesOutOfOrder => WEBPKI_ERROR_INTERMEDIATES_OUT_OF_ORDER,
+        Error::PublicKeyTooSmall => WEBPKI_ERROR_PUBLIC_KEY_TOO_SMALL,
+        Error::EmptySubjectAltName => WEBPKI_ERROR_EMPTY_SUBJECT_ALT_NAME,
+        Error::InvalidValidityPeriod => WEBPKI_ERROR_INVALID_VALIDITY_PERIOD,
+        Error::MisalignedOcspResponse { .. } => WEBPKI_ERROR_MISALIGNED_OCSP_RESPONSE,
+        Error::SurplusOcspResponses => WEBPKI_ERROR_SURPLUS_OCSP_RESPONSES,
+    }
+}
+
+/// Allocates an empty trust anchor store.
+///
+/// Returns null on failure. The store must be released with [`webpki_anchor_store_free()`].
+#[no_mangle]
+pub extern "C" fn webpki_anchor_store_new() -> *mut WebpkiAnchorStore {
+    catch_unwind(|| Box::into_raw(Box::default())).unwrap_or(ptr::null_mut())
+}
+
+/// Parses the DER-encoded X.509 certificate at `der` and adds it to `store` as a trust anchor.
+///
+/// # Safety
+///
+/// `store` must be null or a pointer returned by [`webpki_anchor_store_new()`] that has not
+/// been freed, and `der` must be null or valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn webpki_anchor_store_add_der(
+    store: *mut WebpkiAnchorStore,
+    der: *const u8,
+    len: usize,
+) -> c_int {
+    guard(|| {
+        let store = store.as_mut().ok_or(WEBPKI_ERROR_INVALID_ARGUMENT)?;
+        let cert = CertificateDer::from(bytes(der, len)?);
+        let anchor = anchor_from_trusted_cert(&cert).map_err(error_code)?;
+        store.anchors.push(OwnedAnchor::new(&anchor));
+        Ok(())
+    })
+}
+
+/// Parses the PEM-encoded X.509 certificates at `pem` and adds each to `store` as a trust
+/// anchor.
+///
+/// Every `CERTIFICATE` block (RFC 7468) is read, and any text between blocks is ignored. If
+/// `pem` has no such block, or any of them can't be decoded or used as a trust anchor, none
+/// are added.
+///
+/// # Safety
+///
+/// `store` must be null or a pointer returned by [`webpki_anchor_store_new()`] that has not
+/// been freed, and `pem` must be null or valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn webpki_anchor_store_add_pem(
+    store: *mut WebpkiAnchorStore,
+    pem: *const u8,
+    len: usize,
+) -> c_int {
+    guard(|| {
+        let store = store.as_mut().ok_or(WEBPKI_ERROR_INVALID_ARGUMENT)?;
+        let certs = pem_certificates(bytes(pem, len)?)?;
+        if certs.is_empty() {
+            return Err(WEBPKI_ERROR_INVALID_ARGUMENT);
+        }
+        let anchors = certs
+            .iter()
+            .map(|der| {
+                anchor_from_trusted_cert(&CertificateDer::from(&der[..]))
+                    .map(|anchor| OwnedAnchor::new(&anchor))
+                    .map_err(error_code)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        store.anchors.extend(anchors);
+        Ok(())
+    })
+}
+
+/// Releases a store returned by [`webpki_anchor_store_new()`]. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `store` must be null or a pointer returned by [`webpki_anchor_store_new()`] that has not
+/// already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn webpki_anchor_store_free(store: *mut WebpkiAnchorStore) {
+    if !store.is_null() {
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(store))));
+    }
+}
+
+/// Allocates an empty CRL set.
+///
+/// Returns null on failure. The set must be released with [`webpki_crl_set_free()`].
+#[no_mangle]
+pub extern "C" fn webpki_crl_set_new() -> *mut WebpkiCrlSet {
+    catch_unwind(|| Box::into_raw(Box::default())).unwrap_or(ptr::null_mut())
+}
+
+/// Parses the DER-encoded CRL at `der` and adds it to `set`.
+///
+/// # Safety
+///
+/// `set` must be null or a pointer returned by [`webpki_crl_set_new()`] that has not been
+/// freed, and `der` must be null or valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn webpki_crl_set_add_der(
+    set: *mut WebpkiCrlSet,
+    der: *const u8,
+    len: usize,
+) -> c_int {
+    guard(|| {
+        let set = set.as_mut().ok_or(WEBPKI_ERROR_INVALID_ARGUMENT)?;
+        let crl = OwnedCertRevocationList::from_der(bytes(der, len)?).map_err(error_code)?;
+        set.crls.push(crl.into());
+        Ok(())
+    })
+}
+
+/// Releases a set returned by [`webpki_crl_set_new()`]. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `set` must be null or a pointer returned by [`webpki_crl_set_new()`] that has not already
+/// been freed.
+#[no_mangle]
+pub unsafe extern "C" fn webpki_crl_set_free(set: *mut WebpkiCrlSet) {
+    if !set.is_null() {
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(set))));
+    }
+}
+
+/// Verifies that the DER-encoded end-entity certificate at `ee_der` is valid for TLS server
+/// authentication of `hostname` at `time_secs` (seconds since the Unix epoch).
+///
+/// The certificate must chain to one of the anchors in `anchors`, optionally through the
+/// `intermediates_len` certificates in `intermediates`. If `crls` is non-null the chain's
+/// revocation status is checked against it, and certificates whose status cannot be
+/// determined are rejected. `hostname` is a NUL-terminated DNS name or IP address.
+///
+/// Returns [`WEBPKI_OK`] if the certificate is valid, or another error code otherwise.
+/// The same code is also written to `out_error_code` if it is non-null.
+///
+/// # Safety
+///
+/// * `ee_der` must be null or valid for reads of `ee_len` bytes.
+/// * `intermediates` must be null or valid for reads of `intermediates_len` buffers, each of
+///   which satisfies the requirements of [`WebpkiBuffer`].
+/// * `anchors` and `crls` must be null or live pointers returned by
+///   [`webpki_anchor_store_new()`] and [`webpki_crl_set_new()`] respectively.
+/// * `hostname` must be null or point to a NUL-terminated string.
+/// * `out_error_code` must be null or valid for a write of a `c_int`.
+#[no_mangle]
+pub unsafe extern "C" fn webpki_verify_tls_server_cert(
+    ee_der: *const u8,
+    ee_len: usize,
+    intermediates: *const WebpkiBuffer,
+    intermediates_len: usize,
+    anchors: *const WebpkiAnchorStore,
+    crls: *const WebpkiCrlSet,
+    time_secs: u64,
+    hostname: *const c_char,
+    out_error_code: *mut c_int,
+) -> c_int {
+    let code = guard(|| {
+        let ee = CertificateDer::from(bytes(ee_der, ee_len)?);
+        let intermediates = slice_from_raw(intermediates, intermediates_len)?
+            .iter()
+            .map(|buf| bytes(buf.data, buf.len).map(CertificateDer::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        let anchors = anchors
+            .as_ref()
+            .ok_or(WEBPKI_ERROR_INVALID_ARGUMENT)?
+            .anchors
+            .iter()
+            .map(OwnedAnchor::anchor)
+            .collect::<Vec<_>>();
+        let crls = crls.as_ref().map(|set| set.crls.iter().collect::<Vec<_>>());
+        if hostname.is_null() {
+            return Err(WEBPKI_ERROR_INVALID_ARGUMENT);
+        }
+        let hostname = CStr::from_ptr(hostname)
+            .to_str()
+            .map_err(|_| WEBPKI_ERROR_INVALID_ARGUMENT)?;
+        let server_name =
+            ServerName::try_from(hostname).map_err(|_| WEBPKI_ERROR_INVALID_ARGUMENT)?;
+
+        let revocation = match &crls {
+            Some(crls) => Some(
+                RevocationOptionsBuilder::new(crls)
+                    .map_err(|_| WEBPKI_ERROR_INVALID_ARGUMENT)?
+                    .build(),
+            ),
+            None => None,
+        };
+
+        let cert = EndEntityCert::try_from(&ee).map_err(error_code)?;
+        cert.verify_for_usage(
+            ALL_VERIFICATION_ALGS,
+            &anchors,
+            &intermediates,
+            UnixTime::since_unix_epoch(core::time::Duration::from_secs(time_secs)),
+            KeyUsage::server_auth(),
+            revocation,
+            None,
+        )
+        .map_err(error_code)?;
+        cert.verify_is_valid_for_subject_name(&server_name)
+            .map_err(error_code)
+    });
+
+    if let Some(out) = out_error_code.as_mut() {
+        *out = code;
+    }
+    code
+}
+
+/// The DER encodings of the `CERTIFICATE` blocks of `pem` (RFC 7468), in order.
+fn pem_certificates(pem: &[u8]) -> Result<Vec<Vec<u8>>, c_int> {
+    const BEGIN: &[u8] = b"-----BEGIN CERTIFICATE-----";
+    const END: &[u8] = b"-----END CERTIFICATE-----";
+
+    let find = |haystack: &[u8], needle: &[u8]| {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    };
+    let mut certs = Vec::new();
+    let mut rest = pem;
+    while let Some(start) = find(rest, BEGIN) {
+        let body = &rest[start + BEGIN.len()..];
+        let end = find(body, END).ok_or(WEBPKI_ERROR_INVALID_ARGUMENT)?;
+        let base64 = body[..end]
+            .iter()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .copied()
+            .collect::<Vec<_>>();
+        let der = general_purpose::STANDARD
+            .decode(base64)
+            .map_err(|_| WEBPKI_ERROR_INVALID_ARGUMENT)?;
+        certs.push(der);
+        rest = &body[end + END.len()..];
+    }
+    Ok(certs)
+}
+
+/// Runs `f`, converting its result and any panic into an error code.
+fn guard(f: impl FnOnce() -> Result<(), c_int>) -> c_int {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => WEBPKI_OK,
+        Ok(Err(code)) => code,
+        Err(_) => WEBPKI_ERROR_PANIC,
+    }
+}
+
+/// Converts a C buffer into a byte slice, treating a null pointer with zero length as empty.
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> Result<&'a [u8], c_int> {
+    slice_from_raw(data, len)
+}
+
+unsafe fn slice_from_raw<'a, T>(data: *const T, len: usize) -> Result<&'a [T], c_int> {
+    match (data.is_null(), len) {
+        (true, 0) => Ok(&[]),
+        (true, _) => Err(WEBPKI_ERROR_INVALID_ARGUMENT),
+        (false, _) if len > isize::MAX.unsigned_abs() / core::mem::size_of::<T>().max(1) => {
+            Err(WEBPKI_ERROR_INVALID_ARGUMENT)
+        }
+        (false, _) => Ok(slice::from_raw_parts(data, len)),
+    }
+}
+
+/// cbindgen:ignore
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EE: &[u8] = include_bytes!("../tests/netflix/ee.der");
+    const INTER: &[u8] = include_bytes!("../tests/netflix/inter.der");
+    const CA: &[u8] = include_bytes!("../tests/netflix/ca.der");
+    const TIME: u64 = 1_492_441_716; // 2017-04-17T15:08:36Z
+
+    unsafe fn verify(store: *const WebpkiAnchorStore, hostname: &CStr, time: u64) -> c_int {
+        let intermediates = [WebpkiBuffer {
+            data: INTER.as_ptr(),
+            len: INTER.len(),
+        }];
+        let mut out = WEBPKI_ERROR_PANIC;
+        let code = webpki_verify_tls_server_cert(
+            EE.as_ptr(),
+            EE.len(),
+            intermediates.as_ptr(),
+            intermediates.len(),
+            store,
+            ptr::null(),
+            time,
+            hostname.as_ptr(),
+            &mut out,
+        );
+        assert_eq!(code, out);
+        code
+    }
+
+    #[test]
+    fn verify_tls_server_cert() {
+        unsafe {
+            let store = webpki_anchor_store_new();
+            assert_eq!(
+                webpki_anchor_store_add_der(store, CA.as_ptr(), CA.len()),
+                WEBPKI_OK
+            );
+
+            let name = CStr::from_bytes_with_nul(b"account.netflix.com\0").unwrap();
+            assert_eq!(verify(store, name, TIME), WEBPKI_OK);
+            assert_eq!(verify(store, name, 0), WEBPKI_ERROR_CERT_NOT_VALID_YET);
+
+            let other = CStr::from_bytes_with_nul(b"example.com\0").unwrap();
+            assert_eq!(
+                verify(store, other, TIME),
+                WEBPKI_ERROR_CERT_NOT_VALID_FOR_NAME
+            );
+
+            webpki_anchor_store_free(store);
+        }
+    }
+
+    /// `ders` PEM-encoded as certificates, with text before and after each.
+    fn pem(ders: &[&[u8]]) -> Vec<u8> {
+        let mut pem = String::new();
+        for der in ders {
+            pem.push_str("# A trust anchor.\n-----BEGIN CERTIFICATE-----\n");
+            let base64 = general_purpose::STANDARD.encode(der);
+            for line in base64.as_bytes().chunks(64) {
+                pem.push_str(core::str::from_utf8(line).unwrap());
+                pem.push('\n');
+            }
+            pem.push_str("-----END CERTIFICATE-----\n\n");
+        }
+        pem.into_bytes()
+    }
+
+    #[test]
+    fn anchors_from_pem() {
+        unsafe {
+            let store = webpki_anchor_store_new();
+            let pem = pem(&[INTER, CA]);
+            assert_eq!(
+                webpki_anchor_store_add_pem(store, pem.as_ptr(), pem.len()),
+                WEBPKI_OK
+            );
+            assert_eq!((*store).anchors.len(), 2);
+
+            let name = CStr::from_bytes_with_nul(b"account.netflix.com\0").unwrap();
+            assert_eq!(verify(store, name, TIME), WEBPKI_OK);
+            webpki_anchor_store_free(store);
+        }
+    }
+
+    #[test]
+    fn invalid_pem() {
+        unsafe {
+            let store = webpki_anchor_store_new();
+            let add = |pem: &[u8]| webpki_anchor_store_add_pem(store, pem.as_ptr(), pem.len());
+
+            // No certificates at all.
+            assert_eq!(add(b""), WEBPKI_ERROR_INVALID_ARGUMENT);
+            assert_eq!(
+                add(b"-----BEGIN X509 CRL-----\nAAAA\n-----END X509 CRL-----\n"),
+                WEBPKI_ERROR_INVALID_ARGUMENT
+            );
+
+            // A block that isn't closed, or isn't base64.
+            let valid = pem(&[CA]);
+            assert_eq!(
+                add(&valid[..valid.len() - 10]),
+                WEBPKI_ERROR_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                add(b"-----BEGIN CERTIFICATE-----\n*\n-----END CERTIFICATE-----\n"),
+                WEBPKI_ERROR_INVALID_ARGUMENT
+            );
+
+            // A certificate that can't be used as a trust anchor, after one that can: neither
+            // is added.
+            let mut invalid = valid.clone();
+            invalid.extend_from_slice(&pem(&[b"\x30\x00"]));
+            assert_eq!(add(&invalid), WEBPKI_ERROR_BAD_DER);
+            assert!((*store).anchors.is_empty());
+
+            assert_eq!(add(&valid), WEBPKI_OK);
+            assert_eq!(
+                webpki_anchor_store_add_pem(ptr::null_mut(), valid.as_ptr(), valid.len()),
+                WEBPKI_ERROR_INVALID_ARGUMENT
+            );
+            webpki_anchor_store_free(store);
+        }
+    }
+
+    #[test]
+    fn unknown_issuer() {
+        unsafe {
+            let store = webpki_anchor_store_new();
+            let name = CStr::from_bytes_with_nul(b"account.netflix.com\0").unwrap();
+            assert_eq!(verify(store, name, TIME), WEBPKI_ERROR_UNKNOWN_ISSUER);
+            webpki_anchor_store_free(store);
+        }
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        unsafe {
+            let store = webpki_anchor_store_new();
+            assert_eq!(
+                webpki_anchor_store_add_der(store, ptr::null(), 1),
+                WEBPKI_ERROR_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                webpki_anchor_store_add_der(store, CA.as_ptr(), usize::MAX),
+                WEBPKI_ERROR_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                webpki_anchor_store_add_der(store, ptr::null(), 0),
+                WEBPKI_ERROR_TRAILING_DATA
+            );
+            assert_eq!(
+                webpki_anchor_store_add_der(ptr::null_mut(), CA.as_ptr(), CA.len()),
+                WEBPKI_ERROR_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                webpki_verify_tls_server_cert(
+                    EE.as_ptr(),
+                    EE.len(),
+                    ptr::null(),
+                    0,
+                    store,
+                    ptr::null(),
+                    TIME,
+                    ptr::null(),
+                    ptr::null_mut(),
+                ),
+                WEBPKI_ERROR_INVALID_ARGUMENT
+            );
+            webpki_anchor_store_free(store);
+            webpki_anchor_store_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn crl_set() {
+        let crl: &[u8] = include_bytes!("../tests/crls/crl.valid.der");
+        unsafe {
+            let set = webpki_crl_set_new();
+            assert_eq!(
+                webpki_crl_set_add_der(set, crl.as_ptr(), crl.len()),
+                WEBPKI_OK
+            );
+            assert_eq!(
+                webpki_crl_set_add_der(set, crl.as_ptr(), 1),
+                WEBPKI_ERROR_TRAILING_DATA
+            );
+            assert_eq!((*set).crls.len(), 1);
+            webpki_crl_set_free(set);
+        }
+    }
+
+    #[test]
+    fn error_codes_are_distinct() {
+        let codes = [
+            error_code(Error::BadDer),
+            error_code(Error::CaConstraintsNotCritical { depth: 0 }),
+            error_code(Error::UnsupportedSignatureAlgorithmForPublicKey),
+        ];
+        assert_eq!(codes, [1, 4, 56]);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn freed_anchors_are_zeroized() {
+        unsafe {
+            let store = webpki_anchor_store_new();
+            assert_eq!(
+                webpki_anchor_store_add_der(store, CA.as_ptr(), CA.len()),
+                WEBPKI_OK
+            );
+            // The anchor's subject and subjectPublicKeyInfo.
+            let zeroized = crate::zeroize::count_zeroized(|| webpki_anchor_store_free(store));
+            assert_eq!(zeroized, 2);
+        }
+    }
+
+    #[test]
+    fn anchor_store_debug_omits_contents() {
+        let mut store = WebpkiAnchorStore::default();
+        let cert = CertificateDer::from(CA);
+        store
+            .anchors
+            .push(OwnedAnchor::new(&anchor_from_trusted_cert(&cert).unwrap()));
+        assert_eq!(
+            format!("{:?}", store),
+            "WebpkiAnchorStore { anchors: [OwnedAnchor { subject: [202 bytes], \
+             subject_public_key_info: [290 bytes], name_constraints: None }] }"
+        );
+    }
+}
@@ -12,16 +12,42 @@
 // ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
 // OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
+#[cfg(all(feature = "aws_lc_rs", not(feature = "ring")))]
+use aws_lc_rs::digest;
+#[cfg(feature = "ring")]
+use ring::digest;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use pki_types::{CertificateDer, DnsName};
 
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+use crate::constant_time;
 use crate::der::{self, DerIterator, FromDer, Tag, CONSTRUCTED, CONTEXT_SPECIFIC};
 use crate::error::{DerTypeId, Error};
+use crate::lint::{self, Lint};
 use crate::public_values_eq;
-use crate::signed_data::SignedData;
-use crate::subject_name::{GeneralName, NameIterator, WildcardDnsNameRef};
-use crate::x509::{remember_extension, set_extension_once, DistributionPointName, Extension};
+use crate::sct::{self, SignedCertificateTimestamp};
+use crate::signed_data::{algorithm_identifiers_match, PublicKeyInfo, SignedData};
+use crate::subject_name::{
+    other_name, GeneralName, NameIterator, SubjectAltName, WildcardDnsNameRef,
+};
+#[cfg(feature = "alloc")]
+use crate::subject_name::{ConstraintSource, EnforcedNameConstraints};
+use crate::x509::{
+    remember_extension, set_extension_once, DistributionPointName, Extension, Extensions,
+};
 
 /// A parsed X509 certificate.
+///
+/// The extensions webpki processes are located once, when the certificate is parsed, and are
+/// kept as slices of its DER encoding, so verifying a certificate doesn't parse it again.
+///
+/// Parsing a certificate doesn't presume its role, so CA certificates can be inspected, and
+/// supplied to `Verifier::parsed_intermediates()`, as they are. [`crate::EndEntityCert`]
+/// parses a certificate to be verified as an end-entity.
+#[derive(Clone)]
 pub struct Cert<'a> {
     pub(crate) serial: untrusted::Input<'a>,
     pub(crate) signed_data: SignedData<'a>,
@@ -29,23 +55,74 @@ pub struct Cert<'a> {
     pub(crate) validity: untrusted::Input<'a>,
     pub(crate) subject: untrusted::Input<'a>,
     pub(crate) spki: untrusted::Input<'a>,
+    /// The whole subjectPublicKeyInfo field, including its tag and length.
+    pub(crate) spki_der: untrusted::Input<'a>,
+    pub(crate) issuer_unique_id: Option<untrusted::Input<'a>>,
+    pub(crate) subject_unique_id: Option<untrusted::Input<'a>>,
+    // Whether this is a v1 certificate, which has no extensions. Only end-entity certificates
+    // are parsed as such (see `Cert::from_der_allowing_v1()`).
+    pub(crate) v1: bool,
 
     pub(crate) basic_constraints: Option<untrusted::Input<'a>>,
+    // Whether the basic constraints extension (if any) is marked critical.
+    pub(crate) basic_constraints_critical: bool,
     // key usage (KU) extension (if any). When validating certificate revocation lists (CRLs) this
     // field will be consulted to determine if the cert is allowed to sign CRLs. For cert validation
-    // this field is ignored (for more detail see in `verify_cert.rs` and
-    // `check_issuer_independent_properties`).
+    // this field is only consulted for issuers, which must assert keyCertSign if it is present
+    // (for more detail see in `verify_cert.rs` and `check_issuer_independent_properties`).
     pub(crate) key_usage: Option<untrusted::Input<'a>>,
     pub(crate) eku: Option<untrusted::Input<'a>>,
+    // Whether the extended key usage extension (if any) is marked critical.
+    pub(crate) eku_critical: bool,
     pub(crate) name_constraints: Option<untrusted::Input<'a>>,
     pub(crate) subject_alt_name: Option<untrusted::Input<'a>>,
+    // Whether the subject alternative name extension (if any) is marked critical. RFC 5280
+    // requires this when the subject is empty.
+    pub(crate) subject_alt_name_critical: bool,
     pub(crate) crl_distribution_points: Option<untrusted::Input<'a>>,
+    pub(crate) authority_key_identifier: Option<untrusted::Input<'a>>,
+    pub(crate) subject_key_identifier: Option<untrusted::Input<'a>>,
+    pub(crate) sct_list: Option<untrusted::Input<'a>>,
+    pub(crate) authority_info_access: Option<untrusted::Input<'a>>,
+    // The certificate policy extensions, only processed when the caller requires policies (see
+    // `policy::check_policies()`).
+    pub(crate) certificate_policies: Option<untrusted::Input<'a>>,
+    pub(crate) policy_mappings: Option<untrusted::Input<'a>>,
+    pub(crate) policy_constraints: Option<untrusted::Input<'a>>,
+    pub(crate) inhibit_any_policy: Option<untrusted::Input<'a>>,
+    // Whether any of the certificate policy extensions is marked critical.
+    pub(crate) policy_extensions_critical: bool,
+    // The extensions, and whether any of them is a critical extension not understood by
+    // webpki. Such extensions are rejected at verification, not while parsing the certificate,
+    // so that the verifier can be told to ignore them (see `Cert::check_critical_extensions()`).
+    pub(crate) extensions: Option<untrusted::Input<'a>>,
+    pub(crate) unknown_critical_extensions: bool,
+
+    pub(crate) der: CertificateDer<'a>,
+}
 
-    der: CertificateDer<'a>,
+impl<'a> TryFrom<&'a CertificateDer<'a>> for Cert<'a> {
+    type Error = Error;
+
+    /// Parse the ASN.1 DER-encoded X.509 encoding of the certificate
+    /// `cert_der`.
+    fn try_from(cert: &'a CertificateDer<'a>) -> Result<Self, Self::Error> {
+        Self::from_der(untrusted::Input::from(cert.as_ref()))
+    }
 }
 
 impl<'a> Cert<'a> {
     pub(crate) fn from_der(cert_der: untrusted::Input<'a>) -> Result<Self, Error> {
+        Self::parse(cert_der, false)
+    }
+
+    /// Like `from_der()`, but also accepts a v1 certificate, for `EndEntityCert`. Whether a
+    /// v1 certificate may be verified is decided later, by `Verifier::allow_v1_certificates()`.
+    pub(crate) fn from_der_allowing_v1(cert_der: untrusted::Input<'a>) -> Result<Self, Error> {
+        Self::parse(cert_der, true)
+    }
+
+    fn parse(cert_der: untrusted::Input<'a>, allow_v1: bool) -> Result<Self, Error> {
         let (tbs, signed_data) =
             cert_der.read_all(Error::TrailingData(DerTypeId::Certificate), |cert_der| {
                 der::nested(
@@ -62,27 +139,41 @@ impl<'a> Cert<'a> {
         tbs.read_all(
             Error::TrailingData(DerTypeId::CertificateTbsCertificate),
             |tbs| {
-                version3(tbs)?;
+                // A v1 certificate omits the version, which is DEFAULT v1.
+                let v1 = match tbs.peek(der::Tag::ContextSpecificConstructed0.into()) {
+                    false if allow_v1 => true,
+                    _ => {
+                        version3(tbs)?;
+                        false
+                    }
+                };
 
                 let serial = lenient_certificate_serial_number(tbs)?;
 
                 let signature = der::expect_tag(tbs, der::Tag::Sequence)?;
-                // TODO: In mozilla::pkix, the comparison is done based on the
-                // normalized value (ignoring whether or not there is an optional NULL
-                // parameter for RSA-based algorithms), so this may be too strict.
-                if !public_values_eq(signature, signed_data.algorithm) {
+                if !algorithm_identifiers_match(signature, signed_data.algorithm) {
                     return Err(Error::SignatureAlgorithmMismatch);
                 }
 
                 let issuer = der::expect_tag(tbs, der::Tag::Sequence)?;
+                // https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.4:
+                //   "The issuer field MUST contain a non-empty distinguished name (DN)."
+                if issuer.is_empty() {
+                    return Err(Error::EmptyIssuer);
+                }
                 let validity = der::expect_tag(tbs, der::Tag::Sequence)?;
                 let subject = der::expect_tag(tbs, der::Tag::Sequence)?;
-                let spki = der::expect_tag(tbs, der::Tag::Sequence)?;
-
-                // In theory there could be fields [1] issuerUniqueID and [2]
-                // subjectUniqueID, but in practice there never are, and to keep the
-                // code small and simple we don't accept any certificates that do
-                // contain them.
+                let (spki_der, spki) =
+                    tbs.read_partial(|tbs| der::expect_tag(tbs, der::Tag::Sequence))?;
+
+                // The deprecated [1] issuerUniqueID and [2] subjectUniqueID fields are
+                // IMPLICIT BIT STRINGs. They are rare in practice, and only recorded so
+                // that strict verification can reject them.
+                let issuer_unique_id = optional_unique_id(tbs, der::Tag::ContextSpecific1)?;
+                let subject_unique_id = optional_unique_id(tbs, der::Tag::ContextSpecific2)?;
+                if v1 && (issuer_unique_id.is_some() || subject_unique_id.is_some()) {
+                    return Err(Error::UnsupportedCertVersion);
+                }
 
                 let mut cert = Cert {
                     signed_data,
@@ -91,21 +182,42 @@ impl<'a> Cert<'a> {
                     validity,
                     subject,
                     spki,
+                    spki_der,
+                    issuer_unique_id,
+                    subject_unique_id,
+                    v1,
 
                     basic_constraints: None,
+                    basic_constraints_critical: false,
                     key_usage: None,
                     eku: None,
+                    eku_critical: false,
                     name_constraints: None,
                     subject_alt_name: None,
+                    subject_alt_name_critical: false,
                     crl_distribution_points: None,
+                    authority_key_identifier: None,
+                    subject_key_identifier: None,
+                    sct_list: None,
+                    authority_info_access: None,
+                    certificate_policies: None,
+                    policy_mappings: None,
+                    policy_constraints: None,
+                    inhibit_any_policy: None,
+                    policy_extensions_critical: false,
+                    extensions: None,
+                    unknown_critical_extensions: false,
 
                     der: CertificateDer::from(cert_der.as_slice_less_safe()),
                 };
 
                 if !tbs.at_end() {
-                    der::nested(
-                        tbs,
-                        der::Tag::ContextSpecificConstructed3,
+                    if v1 {
+                        return Err(Error::UnsupportedCertVersion);
+                    }
+                    let extensions = der::expect_tag(tbs, der::Tag::ContextSpecificConstructed3)?;
+                    cert.extensions = Some(extensions);
+                    extensions.read_all(
                         Error::TrailingData(DerTypeId::CertificateExtensions),
                         |tagged| {
                             der::nested_of_mut(
@@ -158,7 +270,29 @@ impl<'a> Cert<'a> {
         })
     }
 
+    /// Returns the entries of the certificate's subjectAltName extension, in order.
+    ///
+    /// The iterator is empty if the certificate has no such extension. If the extension is
+    /// malformed, it yields an error for the first malformed entry and then ends.
+    pub fn subject_alternative_names(
+        &self,
+    ) -> impl Iterator<Item = Result<SubjectAltName<'a>, Error>> {
+        let mut names = self.subject_alt_name.map(untrusted::Reader::new);
+        core::iter::from_fn(move || {
+            let reader = names.as_mut().filter(|reader| !reader.at_end())?;
+            let result = SubjectAltName::from_der(reader);
+            if result.is_err() {
+                names = None;
+            }
+            Some(result)
+        })
+    }
+
     /// Raw DER encoded certificate serial number.
+    ///
+    /// This is the contents of the serialNumber INTEGER, as a big-endian two's complement
+    /// number. A positive serial number whose most significant bit is set has a leading zero
+    /// byte, which is left for the caller to keep or strip.
     pub fn serial(&self) -> &[u8] {
         self.serial.as_slice_less_safe()
     }
@@ -173,6 +307,471 @@ impl<'a> Cert<'a> {
         self.subject.as_slice_less_safe()
     }
 
+    /// Raw DER encoded certificate subjectPublicKeyInfo, including its outer SEQUENCE tag and
+    /// length, as hashed for public key pinning.
+    pub fn subject_public_key_info(&self) -> &[u8] {
+        self.spki_der.as_slice_less_safe()
+    }
+
+    /// The algorithm and size of the certificate's public key.
+    ///
+    /// The key is parsed only as far as needed to tell its size, so this may succeed for a key
+    /// that can't verify any signature. Use `VerifiedPath::intermediate_certificates()` to
+    /// check the keys of the rest of a path.
+    pub fn public_key_info(&self) -> Result<PublicKeyInfo<'a>, Error> {
+        PublicKeyInfo::from_spki(self.spki)
+    }
+
+    /// Returns whether the SHA-256 digest of the certificate's subjectPublicKeyInfo is one of
+    /// `pins`, as with the pin-sha256 directive of HPKP (RFC 7469).
+    ///
+    /// To accept a path if any of its certificates is pinned, see
+    /// `VerifiedPath::matches_spki_pins()`.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub fn matches_spki_pins(&self, pins: &[[u8; 32]]) -> bool {
+        spki_pin_matches(&[], self.spki_der.as_slice_less_safe(), pins)
+    }
+
+    /// Returns the SHA-256 digest of the DER encoding the certificate was parsed from, which is
+    /// its fingerprint as shown by `openssl x509 -fingerprint -sha256`.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub fn sha256_fingerprint(&self) -> [u8; 32] {
+        let mut fingerprint = [0; 32];
+        fingerprint.copy_from_slice(digest::digest(&digest::SHA256, self.der.as_ref()).as_ref());
+        fingerprint
+    }
+
+    /// Returns whether [`Self::sha256_fingerprint()`] is `expected`, comparing them in constant
+    /// time.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub fn fingerprint_matches(&self, expected: &[u8; 32]) -> bool {
+        constant_time::slices_eq(&self.sha256_fingerprint(), expected)
+    }
+
+    /// The certificate's basicConstraints extension, or `None` if it has none, in which case
+    /// the certificate is not a CA.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.9>.
+    pub fn basic_constraints(&self) -> Result<Option<BasicConstraints>, Error> {
+        let value = match self.basic_constraints {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        value.read_all(Error::BadDer, |input| {
+            let is_ca = bool::from_der(input)?;
+
+            // https://bugzilla.mozilla.org/show_bug.cgi?id=985025: RFC 5280
+            // says that a certificate must not have pathLenConstraint unless
+            // it is a CA certificate, but some real-world end-entity
+            // certificates have pathLenConstraint.
+            let path_len_constraint = if !input.at_end() {
+                Some(u8::from_der(input)?)
+            } else {
+                None
+            };
+
+            Ok(Some(BasicConstraints {
+                is_ca,
+                path_len_constraint,
+                critical: self.basic_constraints_critical,
+            }))
+        })
+    }
+
+    /// The keyIdentifier of the certificate's subjectKeyIdentifier extension, or `None` if it
+    /// has none.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.2>.
+    pub fn subject_key_identifier(&self) -> Option<&'a [u8]> {
+        self.subject_key_identifier
+            .map(|ski| ski.as_slice_less_safe())
+    }
+
+    /// The certificate's authorityKeyIdentifier extension, or `None` if it has none.
+    ///
+    /// Path building tries first the issuer whose key the keyIdentifier identifies, but
+    /// doesn't require one to: see [`crate::VerificationPolicy::strict()`].
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.1>.
+    pub fn authority_key_identifier(&self) -> Result<Option<AuthorityKeyIdentifier<'a>>, Error> {
+        let value = match self.authority_key_identifier {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        value.read_all(Error::BadDer, |input| {
+            let mut optional = |tag: Tag| -> Result<Option<&'a [u8]>, Error> {
+                match input.peek(tag.into()) {
+                    true => Ok(Some(der::expect_tag(input, tag)?.as_slice_less_safe())),
+                    false => Ok(None),
+                }
+            };
+            Ok(Some(AuthorityKeyIdentifier {
+                key_identifier: optional(Tag::ContextSpecific0)?,
+                authority_cert_issuer: optional(Tag::ContextSpecificConstructed1)?,
+                authority_cert_serial_number: optional(Tag::ContextSpecific2)?,
+            }))
+        })
+    }
+
+    /// The bits asserted by the certificate's keyUsage extension, or `None` if it has none.
+    ///
+    /// Bit `n` of the result is the bit numbered `n` by RFC 5280, so `digitalSignature` is
+    /// `1 << 0`, `keyCertSign` is `1 << 5` and `cRLSign` is `1 << 6`. Bits beyond the last
+    /// defined one, `decipherOnly`, are ignored.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.3>.
+    pub fn key_usage(&self) -> Result<Option<u16>, Error> {
+        let value = match self.key_usage {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let bit_string = value.read_all(Error::BadDer, |input| {
+            der::expect_tag(input, Tag::BitString)
+        })?;
+        let flags = der::bit_string_flags(bit_string)?;
+        Ok(Some((0..9).fold(0, |bits, bit| match flags.bit_set(bit) {
+            true => bits | 1 << bit,
+            false => bits,
+        })))
+    }
+
+    /// The key purpose OIDs of the certificate's extendedKeyUsage extension, as the values of
+    /// the OBJECT IDENTIFIERs, or `None` if it has none.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.12>.
+    pub fn extended_key_usages(
+        &self,
+    ) -> Option<impl Iterator<Item = Result<&'a [u8], Error>> + 'a> {
+        self.eku.map(|eku| {
+            let mut oids = Some(untrusted::Reader::new(eku));
+            core::iter::from_fn(move || {
+                let reader = oids.as_mut().filter(|reader| !reader.at_end())?;
+                let result = der::expect_tag(reader, Tag::OID);
+                if result.is_err() {
+                    oids = None;
+                }
+                Some(result.map(|oid| oid.as_slice_less_safe()))
+            })
+        })
+    }
+
+    /// Returns whether the certificate's extendedKeyUsage extension is marked critical, or
+    /// `None` if the certificate has no such extension.
+    pub fn extended_key_usage_critical(&self) -> Option<bool> {
+        self.eku.map(|_| self.eku_critical)
+    }
+
+    /// The subtrees of the certificate's nameConstraints extension, which are enforced on the
+    /// certificates it issues when it is an intermediate, attributed to
+    /// `ConstraintSource::Certificate`.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.10>.
+    #[cfg(feature = "alloc")]
+    pub fn name_constraints(&self) -> Result<EnforcedNameConstraints<'a>, Error> {
+        let mut constraints = EnforcedNameConstraints::default();
+        if let Some(value) = self.name_constraints {
+            constraints.extend(value, ConstraintSource::Certificate)?;
+        }
+        Ok(constraints)
+    }
+
+    /// Returns the TLS-encoded SignedCertificateTimestampList embedded in the certificate's
+    /// signedCertificateTimestampList extension, or `None` if there is no such extension.
+    ///
+    /// The list is returned uninterpreted. An extension containing an empty list yields
+    /// `Some(&[])`.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc6962#section-3.3>.
+    pub fn sct_list(&self) -> Option<&[u8]> {
+        self.sct_list.map(|list| list.as_slice_less_safe())
+    }
+
+    /// Returns the signed certificate timestamps in the certificate's
+    /// signedCertificateTimestampList extension, in order.
+    ///
+    /// The iterator is empty if the certificate has no such extension. An SCT that is
+    /// malformed, or not version 1, is yielded as an error and iteration continues; if the
+    /// list itself is malformed, the iterator yields [`Error::MalformedSctList`] and then ends.
+    /// The SCTs' signatures are not checked.
+    pub fn signed_certificate_timestamps(
+        &self,
+    ) -> impl Iterator<Item = Result<SignedCertificateTimestamp<'a>, Error>> {
+        sct::signed_certificate_timestamps(self.sct_list)
+    }
+
+    /// Returns the DER-encoded tbsCertificate over which a log signed the certificate's
+    /// embedded SCTs: the certificate's own, with the signedCertificateTimestampList
+    /// extension removed.
+    ///
+    /// To check an SCT's signature, this is preceded in the signed `PreCert` by the SHA-256
+    /// hash of the issuer's subjectPublicKeyInfo, such as from
+    /// [`Cert::subject_public_key_info()`] of the issuer. Precertificates issued by a
+    /// dedicated precertificate signing certificate are not supported, since the log signed
+    /// a tbsCertificate with a different issuer.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc6962#section-3.2>.
+    #[cfg(feature = "alloc")]
+    pub fn precert_tbs_certificate(&self) -> Result<Vec<u8>, Error> {
+        sct::precert_tbs_certificate(self.signed_data.data, &ID_PE_SCT_LIST)
+    }
+
+    /// Returns the OCSP responder URIs in the certificate's authorityInfoAccess extension.
+    ///
+    /// Access descriptions whose location is not a URI, or not valid UTF-8, are skipped. If the
+    /// extension is malformed, iteration stops at the first malformed access description.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.2.1>.
+    pub fn ocsp_uris(&self) -> impl Iterator<Item = &'a str> {
+        self.access_locations(&ID_AD_OCSP)
+    }
+
+    /// Returns the caIssuers URIs in the certificate's authorityInfoAccess extension, from
+    /// which certificates for its issuer may be fetched.
+    ///
+    /// Skips the same access descriptions as [`Self::ocsp_uris()`].
+    pub fn ca_issuers_uris(&self) -> impl Iterator<Item = &'a str> {
+        self.access_locations(&ID_AD_CA_ISSUERS)
+    }
+
+    /// Returns the URI locations of the access descriptions with the given `method` in the
+    /// authorityInfoAccess extension.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.2.1>:
+    ///   AccessDescription  ::=  SEQUENCE {
+    ///           accessMethod          OBJECT IDENTIFIER,
+    ///           accessLocation        GeneralName  }
+    fn access_locations(&self, method: &'static [u8]) -> impl Iterator<Item = &'a str> {
+        let mut descriptions = self.authority_info_access.map(untrusted::Reader::new);
+        core::iter::from_fn(move || {
+            let descriptions = descriptions.as_mut()?;
+            while !descriptions.at_end() {
+                let description = der::expect_tag(descriptions, Tag::Sequence).ok()?;
+                let (id, location) = description
+                    .read_all(Error::BadDer, |description| {
+                        let id = der::expect_tag(description, Tag::OID)?;
+                        Ok((id, GeneralName::from_der(description)?))
+                    })
+                    .ok()?;
+                if id.as_slice_less_safe() != method {
+                    continue;
+                }
+                if let GeneralName::UniformResourceIdentifier(uri) = location {
+                    if let Ok(uri) = core::str::from_utf8(uri.as_slice_less_safe()) {
+                        return Some(uri);
+                    }
+                }
+            }
+            None
+        })
+    }
+
+    /// Returns the URIs in the full names of the certificate's cRLDistributionPoints
+    /// extension, skipping anything else in the same way as [`Self::ocsp_uris()`].
+    ///
+    /// These are where to fetch CRLs covering the certificate from. A CRL with an issuing
+    /// distribution point only covers the certificate if that names one of these URIs.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.13>.
+    pub fn crl_distribution_point_uris(&self) -> impl Iterator<Item = &'a str> {
+        self.crl_distribution_points()
+            .into_iter()
+            .flatten()
+            .map_while(Result::ok)
+            .filter_map(|point| match point.names() {
+                Ok(Some(DistributionPointName::FullName(names))) => Some(names),
+                _ => None,
+            })
+            .flat_map(|names| names.map_while(Result::ok))
+            .filter_map(|name| match name {
+                GeneralName::UniformResourceIdentifier(uri) => {
+                    core::str::from_utf8(uri.as_slice_less_safe()).ok()
+                }
+                _ => None,
+            })
+    }
+
+    /// Returns the certificate's notBefore and notAfter times.
+    ///
+    /// Both UTCTime and GeneralizedTime encodings are accepted. As RFC 5280 requires, a
+    /// UTCTime year below 50 is in the 21st century, and any other in the 20th. A time before
+    /// the Unix epoch, such as a UTCTime in 1950-1969, can't be represented as a `UnixTime`
+    /// and fails with [`Error::BadDerTime`]. Times are only parsed when this is called, so
+    /// any other encoding error is reported here too.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.5>.
+    pub fn validity(&self) -> Result<(pki_types::UnixTime, pki_types::UnixTime), Error> {
+        self.validity.read_all(Error::BadDer, |input| {
+            Ok((
+                pki_types::UnixTime::from_der(input)?,
+                pki_types::UnixTime::from_der(input)?,
+            ))
+        })
+    }
+
+    /// Returns true if the certificate has a deprecated issuerUniqueID or subjectUniqueID field.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.8>.
+    pub fn has_unique_ids(&self) -> bool {
+        self.issuer_unique_id.is_some() || self.subject_unique_id.is_some()
+    }
+
+    /// Checks that every critical extension of the certificate is one webpki understands, or
+    /// one of the `ignored` extensions, given as the values of their OIDs.
+    ///
+    /// RFC 5280 section 4.2: "A certificate-using system MUST reject the certificate if it
+    /// encounters a critical extension it does not recognize".
+    pub(crate) fn check_critical_extensions(&self, ignored: &[&[u8]]) -> Result<(), Error> {
+        let extensions = match (self.unknown_critical_extensions, self.extensions) {
+            (true, Some(extensions)) => extensions,
+            _ => return Ok(()),
+        };
+
+        extensions.read_all(
+            Error::TrailingData(DerTypeId::CertificateExtensions),
+            |tagged| {
+                der::nested_of_mut(
+                    tagged,
+                    der::Tag::Sequence,
+                    der::Tag::Sequence,
+                    Error::TrailingData(DerTypeId::Extension),
+                    |extension| {
+                        let extension = Extension::from_der(extension)?;
+                        let id = extension.id.as_slice_less_safe();
+                        match extension.critical
+                            && !understood_extension(id)
+                            && !ignored.contains(&id)
+                        {
+                            true => Err(Error::UnsupportedCriticalExtension),
+                            false => Ok(()),
+                        }
+                    },
+                )
+            },
+        )
+    }
+
+    /// Returns an iterator over all the certificate's extensions, in order, including those
+    /// webpki doesn't understand.
+    ///
+    /// This is for applications that look for extensions themselves, such as the qcStatements
+    /// of EU qualified certificates. An application that enforces a critical extension itself
+    /// can have verification accept it with `Verifier::ignore_critical_extensions()`. Since a
+    /// certificate with malformed extensions fails to parse, no error is expected here.
+    pub fn extensions(&self) -> Extensions<'a> {
+        Extensions::new(self.extensions)
+    }
+
+    /// Returns an iterator over the problems found in this certificate that webpki tolerates
+    /// by default.
+    ///
+    /// See [`Lint`] for details.
+    pub fn lints(&self) -> impl Iterator<Item = Lint> + '_ {
+        lint::check(self)
+    }
+
+    /// Returns true if the certificate has an empty subject and lacks a critical
+    /// subjectAltName extension.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.6>: "If the subject field
+    /// contains an empty sequence, then the issuing CA MUST include a subjectAltName
+    /// extension that is marked as critical."
+    pub(crate) fn has_empty_subject_without_critical_san(&self) -> bool {
+        self.subject.is_empty()
+            && (self.subject_alt_name.is_none() || !self.subject_alt_name_critical)
+    }
+
+    /// Returns true if the certificate has a subjectAltName extension with no names.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.6>: "SubjectAltName ::=
+    /// GeneralNames", where "GeneralNames ::= SEQUENCE SIZE (1..MAX) OF GeneralName".
+    pub(crate) fn has_empty_subject_alt_name(&self) -> bool {
+        self.subject_alt_name.map_or(false, |san| san.is_empty())
+    }
+
+    /// Returns true if the certificate's subjectAltName extension contains a dNSName that is
+    /// empty or consists only of whitespace.
+    ///
+    /// Such names can never match a reference identifier, and usually indicate a bug in the
+    /// tooling used to request the certificate.
+    pub(crate) fn has_malformed_dns_name_in_san(&self) -> bool {
+        NameIterator::new(None, self.subject_alt_name).any(|result| match result {
+            Ok(GeneralName::DnsName(name)) => name
+                .as_slice_less_safe()
+                .iter()
+                .all(|b| b.is_ascii_whitespace()),
+            _ => false,
+        })
+    }
+
+    /// Returns true if the certificate's subjectAltName extension contains an otherName whose
+    /// contents are not a `type-id` OID followed by a `[0] EXPLICIT` value holding a single
+    /// DER value.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.6>:
+    ///
+    /// ```ASN.1
+    /// OtherName ::= SEQUENCE {
+    ///    type-id    OBJECT IDENTIFIER,
+    ///    value      [0] EXPLICIT ANY DEFINED BY type-id }
+    /// ```
+    ///
+    /// A subjectAltName that can't be split into names at all is reported as an error by
+    /// whichever operation needs its names, rather than here.
+    pub(crate) fn has_malformed_other_name_in_san(&self) -> bool {
+        const OTHER_NAME_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED;
+
+        let mut san = match self.subject_alt_name {
+            Some(san) => untrusted::Reader::new(san),
+            None => return false,
+        };
+        while !san.at_end() {
+            let (tag, value) = match der::read_tag_and_get_value(&mut san) {
+                Ok(name) => name,
+                Err(_) => return false,
+            };
+            if tag != OTHER_NAME_TAG {
+                continue;
+            }
+
+            if other_name(value).is_err() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Whether the certificate's issuer and subject are the same, as for a self-signed
+    /// certificate or a certificate issued by a CA to its rolled-over key.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc5280#section-6.1>
+    pub(crate) fn is_self_issued(&self) -> bool {
+        public_values_eq(self.issuer, self.subject)
+    }
+
+    /// Returns the keyIdentifier of the certificate's authorityKeyIdentifier extension, if any.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.1>:
+    ///
+    /// ```ASN.1
+    /// AuthorityKeyIdentifier ::= SEQUENCE {
+    ///    keyIdentifier             [0] KeyIdentifier           OPTIONAL,
+    ///    authorityCertIssuer       [1] GeneralNames            OPTIONAL,
+    ///    authorityCertSerialNumber [2] CertificateSerialNumber OPTIONAL  }
+    /// ```
+    pub(crate) fn authority_key_id(&self) -> Result<Option<untrusted::Input<'a>>, Error> {
+        let mut aki = match self.authority_key_identifier {
+            Some(aki) => untrusted::Reader::new(aki),
+            None => return Ok(None),
+        };
+        if !aki.peek(Tag::ContextSpecific0.into()) {
+            return Ok(None);
+        }
+        der::expect_tag(&mut aki, Tag::ContextSpecific0).map(Some)
+    }
+
     /// Returns an iterator over the certificate's cRLDistributionPoints extension values, if any.
     pub(crate) fn crl_distribution_points(
         &self,
@@ -186,8 +785,73 @@ impl<'a> Cert<'a> {
     }
 }
 
+/// The basicConstraints extension of a certificate, as returned by [`Cert::basic_constraints()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BasicConstraints {
+    /// Whether the certificate's subject is a CA.
+    pub is_ca: bool,
+    /// The most intermediate certificates that may follow this one in a path, if limited.
+    pub path_len_constraint: Option<u8>,
+    /// Whether the extension is marked critical.
+    pub critical: bool,
+}
+
+/// The authorityKeyIdentifier extension of a certificate, as returned by
+/// [`Cert::authority_key_identifier()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuthorityKeyIdentifier<'a> {
+    /// The keyIdentifier, identifying the issuer's key, usually by a digest of it.
+    pub key_identifier: Option<&'a [u8]>,
+    /// The contents of the authorityCertIssuer GeneralNames, naming the issuer of the issuer's
+    /// certificate.
+    pub authority_cert_issuer: Option<&'a [u8]>,
+    /// The contents of the authorityCertSerialNumber INTEGER, the serial number of the
+    /// issuer's certificate.
+    pub authority_cert_serial_number: Option<&'a [u8]>,
+}
+
+/// Returns whether the SHA-256 digest of `header` followed by `spki` is one of `pins`.
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+pub(crate) fn spki_pin_matches(header: &[u8], spki: &[u8], pins: &[[u8; 32]]) -> bool {
+    let mut context = digest::Context::new(&digest::SHA256);
+    context.update(header);
+    context.update(spki);
+    let digest = context.finish();
+    // Compare with every pin, so that the time taken doesn't reveal which one matched.
+    pins.iter().fold(false, |found, pin| {
+        found | constant_time::slices_eq(pin, digest.as_ref())
+    })
+}
+
+/// Splits `bytes`, a concatenation of DER-encoded certificates, into the individual
+/// certificates.
+///
+/// Each item borrows one top-level SEQUENCE from `bytes`; the certificates themselves are not
+/// parsed. If the remaining bytes do not begin with a complete SEQUENCE, for example because
+/// of trailing garbage after the last certificate, an error is yielded and iteration stops.
+pub fn split_der_certificates(
+    bytes: &[u8],
+) -> impl Iterator<Item = Result<CertificateDer<'_>, Error>> {
+    let mut reader = untrusted::Reader::new(untrusted::Input::from(bytes));
+    let mut failed = false;
+    core::iter::from_fn(move || {
+        if failed || reader.at_end() {
+            return None;
+        }
+
+        match reader.read_partial(|input| der::expect_tag(input, Tag::Sequence)) {
+            Ok((cert, _)) => Some(Ok(CertificateDer::from(cert.as_slice_less_safe()))),
+            Err(err) => {
+                failed = true;
+                Some(Err(err))
+            }
+        }
+    })
+}
+
 // mozilla::pkix supports v1, v2, v3, and v4, including both the implicit
-// (correct) and explicit (incorrect) encoding of v1. We allow only v3.
+// (correct) and explicit (incorrect) encoding of v1. We allow only v3, and the implicit
+// encoding of v1 for end-entity certificates (see `Cert::from_der_allowing_v1()`).
 fn version3(input: &mut untrusted::Reader) -> Result<(), Error> {
     der::nested(
         input,
@@ -217,27 +881,105 @@ pub(crate) fn lenient_certificate_serial_number<'a>(
     //   Note: Non-conforming CAs may issue certificates with serial numbers
     //   that are negative or zero.  Certificate users SHOULD be prepared to
     //   gracefully handle such certificates.
+    //
+    // `SerialNumberPolicy` enforces them during verification, when asked to.
     der::expect_tag(input, Tag::Integer)
 }
 
+// https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.8:
+//   issuerUniqueID  [1]  IMPLICIT UniqueIdentifier OPTIONAL
+//   subjectUniqueID [2]  IMPLICIT UniqueIdentifier OPTIONAL
+//   UniqueIdentifier  ::=  BIT STRING
+fn optional_unique_id<'a>(
+    input: &mut untrusted::Reader<'a>,
+    tag: der::Tag,
+) -> Result<Option<untrusted::Input<'a>>, Error> {
+    if !input.peek(tag.into()) {
+        return Ok(None);
+    }
+    let value = der::expect_tag(input, tag)?;
+    der::bit_string_flags(value)?;
+    Ok(Some(value))
+}
+
+// id-pe-signedCertificateTimestampList 1.3.6.1.4.1.11129.2.4.2
+// https://www.rfc-editor.org/rfc/rfc6962#section-3.3
+static ID_PE_SCT_LIST: [u8; 10] = oid![1, 3, 6, 1, 4, 1, 0xd6, 0x79, 2, 4, 2];
+
+// id-pe-authorityInfoAccess 1.3.6.1.5.5.7.1.1
+static ID_PE_AUTHORITY_INFO_ACCESS: [u8; 8] = oid![1, 3, 6, 1, 5, 5, 7, 1, 1];
+
+// id-ad-ocsp 1.3.6.1.5.5.7.48.1
+static ID_AD_OCSP: [u8; 8] = oid![1, 3, 6, 1, 5, 5, 7, 48, 1];
+
+// id-ad-caIssuers 1.3.6.1.5.5.7.48.2
+static ID_AD_CA_ISSUERS: [u8; 8] = oid![1, 3, 6, 1, 5, 5, 7, 48, 2];
+
+/// Returns true if `remember_cert_extension()` remembers the certificate extension with OID
+/// `id`, so that webpki processes it.
+fn understood_extension(id: &[u8]) -> bool {
+    // ISO arc for standard certificate extensions, as in `x509::remember_extension()`.
+    static ID_CE: [u8; 2] = oid![2, 5, 29];
+
+    match id {
+        [prefix @ .., last] if prefix == ID_CE => {
+            matches!(
+                last,
+                14 | 15 | 17 | 19 | 30 | 31 | 32 | 33 | 35 | 36 | 37 | 54
+            )
+        }
+        _ => id == ID_PE_SCT_LIST || id == ID_PE_AUTHORITY_INFO_ACCESS,
+    }
+}
+
 fn remember_cert_extension<'a>(
     cert: &mut Cert<'a>,
     extension: &Extension<'a>,
 ) -> Result<(), Error> {
-    // We don't do anything with certificate policies so we can safely ignore
-    // all policy-related stuff. We assume that the policy-related extensions
-    // are not marked critical.
+    // Extensions that aren't understood are only remembered to be rejected at verification if
+    // they are critical.
+    if !understood_extension(extension.id.as_slice_less_safe()) {
+        cert.unknown_critical_extensions |= extension.critical;
+        return Ok(());
+    }
+
+    // The SCT list and authority information access are the only remembered extensions
+    // outside the id-ce arc. The SCT list's value is an OctetString holding the TLS-encoded
+    // list.
+    if extension.id.as_slice_less_safe() == ID_PE_SCT_LIST {
+        return set_extension_once(&mut cert.sct_list, || {
+            extension.value.read_all(Error::BadDer, |value| {
+                der::expect_tag(value, Tag::OctetString)
+            })
+        });
+    }
+    if extension.id.as_slice_less_safe() == ID_PE_AUTHORITY_INFO_ACCESS {
+        return set_extension_once(&mut cert.authority_info_access, || {
+            extension
+                .value
+                .read_all(Error::BadDer, |value| der::expect_tag(value, Tag::Sequence))
+        });
+    }
 
     remember_extension(extension, |id| {
         let out = match id {
+            // id-ce-subjectKeyIdentifier 2.5.29.14
+            14 => &mut cert.subject_key_identifier,
+
             // id-ce-keyUsage 2.5.29.15.
             15 => &mut cert.key_usage,
 
             // id-ce-subjectAltName 2.5.29.17
-            17 => &mut cert.subject_alt_name,
+            17 => {
+                cert.subject_alt_name_critical = extension.critical;
+                &mut cert.subject_alt_name
+            }
 
             // id-ce-basicConstraints 2.5.29.19
-            19 => &mut cert.basic_constraints,
+            19 => {
+                cert.basic_constraints_critical = extension.critical;
+                &mut cert.basic_constraints
+            }
 
             // id-ce-nameConstraints 2.5.29.30
             30 => &mut cert.name_constraints,
@@ -245,8 +987,38 @@ fn remember_cert_extension<'a>(
             // id-ce-cRLDistributionPoints 2.5.29.31
             31 => &mut cert.crl_distribution_points,
 
+            // id-ce-authorityKeyIdentifier 2.5.29.35
+            35 => &mut cert.authority_key_identifier,
+
+            // id-ce-certificatePolicies 2.5.29.32
+            32 => {
+                cert.policy_extensions_critical |= extension.critical;
+                &mut cert.certificate_policies
+            }
+
+            // id-ce-policyMappings 2.5.29.33
+            33 => {
+                cert.policy_extensions_critical |= extension.critical;
+                &mut cert.policy_mappings
+            }
+
+            // id-ce-policyConstraints 2.5.29.36
+            36 => {
+                cert.policy_extensions_critical |= extension.critical;
+                &mut cert.policy_constraints
+            }
+
             // id-ce-extKeyUsage 2.5.29.37
-            37 => &mut cert.eku,
+            37 => {
+                cert.eku_critical = extension.critical;
+                &mut cert.eku
+            }
+
+            // id-ce-inhibitAnyPolicy 2.5.29.54
+            54 => {
+                cert.policy_extensions_critical |= extension.critical;
+                &mut cert.inhibit_any_policy
+            }
 
             // Unsupported extension
             _ => return extension.unsupported(),
@@ -257,6 +1029,11 @@ fn remember_cert_extension<'a>(
                 // Unlike the other extensions we remember KU is a BitString and not a Sequence. We
                 // read the raw bytes here and parse at the time of use.
                 15 => Ok(value.read_bytes_to_end()),
+                // inhibitAnyPolicy is an Integer, likewise kept whole and parsed at the time of
+                // use.
+                54 => Ok(value.read_bytes_to_end()),
+                // The subject key identifier is an OctetString.
+                14 => der::expect_tag(value, Tag::OctetString),
                 // All other remembered certificate extensions are wrapped in a Sequence.
                 _ => der::expect_tag(value, Tag::Sequence),
             })
@@ -349,9 +1126,6 @@ mod tests {
     use crate::subject_name::GeneralName;
 
     #[test]
-    // Note: cert::parse_cert is crate-local visibility, and EndEntityCert doesn't expose the
-    //       inner Cert, or the serial number. As a result we test that the raw serial value
-    //       is read correctly here instead of in tests/integration.rs.
     fn test_serial_read() {
         let ee = include_bytes!("../tests/misc/serial_neg_ee.der");
         let cert = Cert::from_der(untrusted::Input::from(ee)).expect("failed to parse certificate");
@@ -368,6 +1142,437 @@ mod tests {
         )
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_validity_read() {
+        use core::time::Duration;
+        use pki_types::UnixTime;
+
+        fn validity(
+            not_before: (i32, u8, u8),
+            not_after: (i32, u8, u8),
+        ) -> Result<(u64, u64), Error> {
+            let issuer = crate::test_utils::make_issuer("Test CA");
+            let mut params = crate::test_utils::end_entity_params(vec!["example.com".into()]);
+            params.not_before = rcgen::date_time_ymd(not_before.0, not_before.1, not_before.2);
+            params.not_after = rcgen::date_time_ymd(not_after.0, not_after.1, not_after.2);
+            let der = rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der_with_signer(&issuer)
+                .unwrap();
+            let cert = Cert::from_der(untrusted::Input::from(&der)).unwrap();
+            cert.validity()
+                .map(|(not_before, not_after)| (not_before.as_secs(), not_after.as_secs()))
+        }
+
+        // rcgen encodes years before 2050 as UTCTime, and later ones as GeneralizedTime.
+        assert_eq!(
+            validity((2049, 12, 31), (2050, 1, 1)),
+            Ok((2_524_521_600, 2_524_608_000))
+        );
+        assert_eq!(
+            validity((1970, 1, 1), (9999, 12, 31)),
+            Ok((0, 253_402_214_400))
+        );
+        // 1969 and 1950 can be encoded as UTCTime, but not represented as a `UnixTime`.
+        assert_eq!(
+            validity((1969, 12, 31), (2030, 1, 1)),
+            Err(Error::BadDerTime)
+        );
+        assert_eq!(validity((1950, 1, 1), (2030, 1, 1)), Err(Error::BadDerTime));
+
+        let ee = include_bytes!("../tests/misc/serial_neg_ee.der");
+        let cert = Cert::from_der(untrusted::Input::from(ee)).unwrap();
+        let (not_before, not_after) = cert.validity().unwrap();
+        assert!(not_before < not_after);
+        assert!(not_before > UnixTime::since_unix_epoch(Duration::ZERO));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_sct_list_read() {
+        fn ee_with_sct_extension(value: Option<&[u8]>) -> Vec<u8> {
+            let issuer = crate::test_utils::make_issuer("Test CA");
+            let mut params = crate::test_utils::end_entity_params(vec!["example.com".into()]);
+            if let Some(value) = value {
+                params
+                    .custom_extensions
+                    .push(rcgen::CustomExtension::from_oid_content(
+                        &[1, 3, 6, 1, 4, 1, 11129, 2, 4, 2],
+                        value.to_vec(),
+                    ));
+            }
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der_with_signer(&issuer)
+                .unwrap()
+        }
+
+        let der = ee_with_sct_extension(None);
+        let cert = Cert::from_der(untrusted::Input::from(&der)).unwrap();
+        assert_eq!(cert.sct_list(), None);
+        assert_eq!(cert.signed_certificate_timestamps().next(), None);
+
+        let der = ee_with_sct_extension(Some(&[0x04, 0x00]));
+        let cert = Cert::from_der(untrusted::Input::from(&der)).unwrap();
+        assert_eq!(cert.sct_list(), Some(&[][..]));
+        assert_eq!(
+            cert.signed_certificate_timestamps().collect::<Vec<_>>(),
+            [Err(Error::MalformedSctList)]
+        );
+
+        let der = ee_with_sct_extension(Some(&[0x04, 0x04, 0x00, 0x02, 0xab, 0xcd]));
+        let cert = Cert::from_der(untrusted::Input::from(&der)).unwrap();
+        assert_eq!(cert.sct_list(), Some(&[0x00, 0x02, 0xab, 0xcd][..]));
+        assert_eq!(
+            cert.signed_certificate_timestamps().collect::<Vec<_>>(),
+            [Err(Error::MalformedSctList)]
+        );
+
+        // The list must be wrapped in an OctetString.
+        let der = ee_with_sct_extension(Some(&[0x30, 0x00]));
+        assert_eq!(
+            Cert::from_der(untrusted::Input::from(&der)).err(),
+            Some(Error::BadDer)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_precert_tbs_certificate() {
+        let issuer = crate::test_utils::make_issuer("Test CA");
+        let key = rcgen::KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256)
+            .unwrap()
+            .serialize_der();
+        // Certificates differing only in their extensions, and so in their signatures.
+        let ee = |extensions: &[(&[u64], &[u8])]| {
+            let mut params = crate::test_utils::end_entity_params(vec!["example.com".into()]);
+            params.serial_number = Some(rcgen::SerialNumber::from(42u64));
+            params.key_pair = Some(rcgen::KeyPair::from_der(&key).unwrap());
+            params.custom_extensions = extensions
+                .iter()
+                .map(|(oid, value)| rcgen::CustomExtension::from_oid_content(oid, value.to_vec()))
+                .collect();
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der_with_signer(&issuer)
+                .unwrap()
+        };
+        let tbs = |der: &[u8]| {
+            let cert = Cert::from_der(untrusted::Input::from(der)).unwrap();
+            (
+                cert.signed_data.data.as_slice_less_safe().to_vec(),
+                cert.precert_tbs_certificate().unwrap(),
+            )
+        };
+
+        let sct: (&[u64], &[u8]) = (&[1, 3, 6, 1, 4, 1, 11129, 2, 4, 2], &[0x04, 0x00]);
+        let other: (&[u64], &[u8]) = (&[1, 2, 3, 4], &[0x05, 0x00]);
+
+        // Without the SCT list, the tbsCertificate is unchanged.
+        let (plain, precert) = tbs(&ee(&[other]));
+        assert_eq!(precert, plain);
+
+        for extensions in [&[sct, other][..], &[other, sct][..]] {
+            let (with_sct, precert) = tbs(&ee(extensions));
+            assert_ne!(precert, with_sct);
+            assert_eq!(precert, plain);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_subject_alternative_names() {
+        fn ee_with_san(value: Option<&[u8]>) -> Vec<u8> {
+            let issuer = crate::test_utils::make_issuer("Test CA");
+            let mut params = crate::test_utils::end_entity_params(vec![]);
+            if let Some(value) = value {
+                params
+                    .custom_extensions
+                    .push(rcgen::CustomExtension::from_oid_content(
+                        &[2, 5, 29, 17],
+                        value.to_vec(),
+                    ));
+            }
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der_with_signer(&issuer)
+                .unwrap()
+        }
+
+        let der = ee_with_san(None);
+        let cert = Cert::from_der(untrusted::Input::from(&der)).unwrap();
+        assert_eq!(cert.subject_alternative_names().next(), None);
+
+        let der = ee_with_san(Some(&[
+            0x30, 0x2c, // GeneralNames
+            0xa0, 0x0a, 0x06, 0x03, 0x2a, 0x03, 0x04, 0xa0, 0x03, 0x02, 0x01,
+            0x05, // otherName
+            0x82, 0x03, b'a', b'.', b'b', // dNSName
+            0x87, 0x04, 0x01, 0x02, 0x03, 0x04, // iPAddress
+            0x81, 0x03, b'a', b'@', b'b', // rfc822Name
+            0x86, 0x05, b'h', b't', b't', b'p', b':', // uniformResourceIdentifier
+            0xa4, 0x02, 0x30, 0x00, // directoryName
+            0x88, 0x03, 0x2a, 0x03, 0x04, // registeredID
+        ]));
+        let cert = Cert::from_der(untrusted::Input::from(&der)).unwrap();
+        assert_eq!(
+            cert.subject_alternative_names()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            [
+                SubjectAltName::OtherName {
+                    type_id: &[0x2a, 0x03, 0x04],
+                    value: &[0x02, 0x01, 0x05],
+                },
+                SubjectAltName::DnsName("a.b"),
+                SubjectAltName::IpAddress(&[1, 2, 3, 4]),
+                SubjectAltName::Rfc822Name("a@b"),
+                SubjectAltName::Uri("http:"),
+                SubjectAltName::Other {
+                    tag: 4,
+                    value: &[0x30, 0x00],
+                },
+                SubjectAltName::Other {
+                    tag: 8,
+                    value: &[0x2a, 0x03, 0x04],
+                },
+            ]
+        );
+
+        // A malformed entry is yielded as an error, after which iteration ends.
+        for (entry, expected) in [
+            (
+                &[0x87, 0x03, 0x01, 0x02, 0x03][..],
+                Error::MalformedIpAddress,
+            ),
+            (&[0x82, 0x01, 0xff], Error::MalformedDnsIdentifier),
+            (&[0x81, 0x01, 0xff], Error::BadDer),
+            (&[0x89, 0x00], Error::BadDer),
+            (
+                &[0xa0, 0x05, 0x06, 0x03, 0x2a, 0x03, 0x04],
+                Error::MalformedOtherNameInSan,
+            ),
+        ] {
+            let mut san = vec![0x30, u8::try_from(entry.len() + 2).unwrap(), 0x82, 0x00];
+            san.extend_from_slice(entry);
+            let der = ee_with_san(Some(&san));
+            let cert = Cert::from_der(untrusted::Input::from(&der)).unwrap();
+            let mut names = cert.subject_alternative_names();
+            assert_eq!(names.next(), Some(Ok(SubjectAltName::DnsName(""))));
+            assert_eq!(names.next(), Some(Err(expected)));
+            assert_eq!(names.next(), None);
+        }
+    }
+
+    #[test]
+    fn test_unique_ids_read() {
+        let ee = include_bytes!("../tests/misc/unique_ids_ee.der");
+        let cert = Cert::from_der(untrusted::Input::from(ee)).expect("failed to parse certificate");
+        assert!(cert.has_unique_ids());
+        assert_eq!(
+            cert.issuer_unique_id.map(|id| id.as_slice_less_safe()),
+            Some(&[0x00, 0x01, 0x02, 0x03, 0x04][..])
+        );
+        assert_eq!(
+            cert.subject_unique_id.map(|id| id.as_slice_less_safe()),
+            Some(&[0x00, 0x05, 0x06, 0x07, 0x08][..])
+        );
+        // The fields that follow the unique IDs are still parsed.
+        assert!(cert.subject_alt_name.is_some());
+
+        let ca = include_bytes!("../tests/misc/unique_ids_ca.der");
+        let cert = Cert::from_der(untrusted::Input::from(ca)).expect("failed to parse certificate");
+        assert!(!cert.has_unique_ids());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn ca_extensions() {
+        use crate::subject_name::{ConstraintBase, SubtreeKind};
+
+        let mut params = crate::test_utils::issuer_params("CA");
+        params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Constrained(2));
+        params.extended_key_usages = vec![
+            rcgen::ExtendedKeyUsagePurpose::ServerAuth,
+            rcgen::ExtendedKeyUsagePurpose::ClientAuth,
+        ];
+        params.name_constraints = Some(rcgen::NameConstraints {
+            permitted_subtrees: vec![rcgen::GeneralSubtree::DnsName("example.com".into())],
+            excluded_subtrees: vec![rcgen::GeneralSubtree::DnsName("secret.example.com".into())],
+        });
+        let der = rcgen::Certificate::from_params(params)
+            .unwrap()
+            .serialize_der()
+            .unwrap();
+        let cert = Cert::from_der(untrusted::Input::from(&der)).unwrap();
+
+        assert_eq!(
+            cert.basic_constraints(),
+            Ok(Some(BasicConstraints {
+                is_ca: true,
+                path_len_constraint: Some(2),
+                critical: true,
+            }))
+        );
+        // digitalSignature, keyCertSign and cRLSign.
+        assert_eq!(cert.key_usage(), Ok(Some(1 << 0 | 1 << 5 | 1 << 6)));
+        assert_eq!(
+            cert.extended_key_usages()
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(vec![
+                &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x01][..],
+                &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x02][..],
+            ])
+        );
+
+        let constraints = cert.name_constraints().unwrap();
+        assert_eq!(
+            constraints
+                .subtrees()
+                .iter()
+                .map(|subtree| (subtree.kind, subtree.source, subtree.base))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    SubtreeKind::Permitted,
+                    ConstraintSource::Certificate,
+                    ConstraintBase::DnsName(b"example.com"),
+                ),
+                (
+                    SubtreeKind::Excluded,
+                    ConstraintSource::Certificate,
+                    ConstraintBase::DnsName(b"secret.example.com"),
+                ),
+            ]
+        );
+
+        // An end-entity certificate asserting digitalSignature, contentCommitment,
+        // keyEncipherment and dataEncipherment, with no other of these extensions.
+        let ee = include_bytes!("../tests/misc/serial_neg_ee.der");
+        let cert = Cert::from_der(untrusted::Input::from(ee)).unwrap();
+        assert_eq!(
+            cert.basic_constraints(),
+            Ok(Some(BasicConstraints {
+                is_ca: false,
+                path_len_constraint: None,
+                critical: false,
+            }))
+        );
+        assert_eq!(cert.key_usage(), Ok(Some(0b1111)));
+        assert!(cert.extended_key_usages().is_none());
+        assert!(cert.name_constraints().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn key_identifiers() {
+        let issuer = crate::test_utils::make_issuer("CA");
+        let issuer_der = issuer.serialize_der().unwrap();
+        let issuer_cert = Cert::from_der(untrusted::Input::from(&issuer_der)).unwrap();
+        let ski = issuer_cert.subject_key_identifier().unwrap();
+        assert_eq!(ski.len(), 20);
+        assert_eq!(issuer_cert.authority_key_identifier(), Ok(None));
+
+        let mut params = crate::test_utils::end_entity_params(vec!["example.com".into()]);
+        params.use_authority_key_identifier_extension = true;
+        let ee_der = rcgen::Certificate::from_params(params)
+            .unwrap()
+            .serialize_der_with_signer(&issuer)
+            .unwrap();
+        let ee = Cert::from_der(untrusted::Input::from(&ee_der)).unwrap();
+        assert_eq!(
+            ee.authority_key_identifier(),
+            Ok(Some(AuthorityKeyIdentifier {
+                key_identifier: Some(ski),
+                authority_cert_issuer: None,
+                authority_cert_serial_number: None,
+            }))
+        );
+
+        // An authorityKeyIdentifier with every field: keyIdentifier 0x0102, an
+        // authorityCertIssuer of dNSName "ca", and authorityCertSerialNumber 5.
+        let mut params = crate::test_utils::end_entity_params(vec!["example.com".into()]);
+        params
+            .custom_extensions
+            .push(rcgen::CustomExtension::from_oid_content(
+                &[2, 5, 29, 35],
+                vec![
+                    0x30, 0x0d, 0x80, 0x02, 0x01, 0x02, 0xa1, 0x04, 0x82, 0x02, b'c', b'a', 0x82,
+                    0x01, 0x05,
+                ],
+            ));
+        let ee_der = rcgen::Certificate::from_params(params)
+            .unwrap()
+            .serialize_der_with_signer(&issuer)
+            .unwrap();
+        let ee = Cert::from_der(untrusted::Input::from(&ee_der)).unwrap();
+        assert_eq!(
+            ee.authority_key_identifier(),
+            Ok(Some(AuthorityKeyIdentifier {
+                key_identifier: Some(&[0x01, 0x02]),
+                authority_cert_issuer: Some(&[0x82, 0x02, b'c', b'a']),
+                authority_cert_serial_number: Some(&[0x05]),
+            }))
+        );
+        assert!(ee.subject_key_identifier().is_some());
+    }
+
+    #[test]
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    fn sha256_fingerprint() {
+        // From `openssl x509 -inform DER -noout -fingerprint -sha256`.
+        for (der, fingerprint) in [
+            (
+                &include_bytes!("../tests/misc/serial_neg_ee.der")[..],
+                "9C:A5:1E:EF:51:22:DF:AA:AE:65:75:57:B6:66:6E:11:0B:95:95:86:10:0A:27:A2:93:0C:98:B9:5B:FB:C8:89",
+            ),
+            (
+                &include_bytes!("../tests/misc/unique_ids_ca.der")[..],
+                "C5:A8:62:1E:81:82:8F:5B:87:72:D5:EF:DD:14:A9:E8:7E:EF:7E:95:9D:74:21:C6:D9:3D:76:97:1D:EC:44:B6",
+            ),
+        ] {
+            let mut expected = [0; 32];
+            for (byte, hex) in expected.iter_mut().zip(fingerprint.split(':')) {
+                *byte = u8::from_str_radix(hex, 16).unwrap();
+            }
+
+            let cert = Cert::from_der(untrusted::Input::from(der)).unwrap();
+            assert_eq!(cert.sha256_fingerprint(), expected);
+            assert!(cert.fingerprint_matches(&expected));
+            expected[31] ^= 1;
+            assert!(!cert.fingerprint_matches(&expected));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_ber_indefinite_length() {
+        // Re-encode the outer Certificate SEQUENCE with a BER indefinite length:
+        // `30 80 <contents> 00 00` in place of `30 82 03 13 <contents>`.
+        let ee = include_bytes!("../tests/misc/serial_neg_ee.der");
+        let ber = [&[0x30, 0x80], &ee[4..], &[0x00, 0x00]].concat();
+        assert!(matches!(
+            Cert::from_der(untrusted::Input::from(&ber)),
+            Err(Error::BerEncodingNotAllowed { offset: 1 })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_tbs_signature_algorithm_mismatch() {
+        // Change the TBSCertificate signature algorithm from sha256WithRSAEncryption to
+        // sha384WithRSAEncryption, leaving the outer signatureAlgorithm unchanged.
+        let mut ee = include_bytes!("../tests/misc/serial_neg_ee.der").to_vec();
+        assert_eq!(ee[0x20], 0x0b);
+        ee[0x20] = 0x0c;
+        assert!(matches!(
+            Cert::from_der(untrusted::Input::from(&ee)),
+            Err(Error::SignatureAlgorithmMismatch)
+        ));
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     fn test_crl_distribution_point_netflix() {
@@ -618,7 +1823,7 @@ mod tests {
         // There should be two distribution points present.
         let (point_a, point_b): (&CrlDistributionPoint, &CrlDistributionPoint) = (
             crl_distribution_points
-                .get(0)
+                .first()
                 .expect("missing first distribution point"),
             crl_distribution_points
                 .get(1)
@@ -0,0 +1,81 @@
+//! Comparisons of attacker-influenced values whose running time should not depend on where
+//! the values first differ.
+//!
+//! These are used where an early-exit comparison could leak a matching prefix to a remote
+//! observer measuring many verifications: CRL serial number matching in
+//! [`crate::BorrowedCertRevocationList`], authority/subject key identifier matching during
+//! path building, SPKI pin checks, as by `crate::Cert::matches_spki_pins()`, and OCSP CertID
+//! and responder key hash matching. Lengths are not hidden.
+//!
+//! Other comparisons, such as of DNS names and distinguished names, are of public values and
+//! are not covered. Neither is serial number lookup in [`crate::OwnedCertRevocationList`],
+//! which uses an ordered map.
+
+/// Returns `true` if `a` and `b` are equal, in time dependent only on their lengths.
+pub(crate) fn slices_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equivalent_to_eq() {
+        let values: &[&[u8]] = &[
+            b"",
+            b"\x00",
+            b"\x01",
+            b"\x00\x01",
+            b"\x01\x00",
+            b"\x01\x02\x03",
+            b"\x01\x02\x04",
+            b"\xff\x02\x03",
+        ];
+        for a in values {
+            for b in values {
+                assert_eq!(slices_eq(a, b), a == b, "{:?} vs {:?}", a, b);
+            }
+        }
+    }
+
+    /// Best-effort: compares the time taken by a mismatch in the first byte against one in the
+    /// last byte. Too noisy to run in CI.
+    #[cfg(feature = "std")]
+    #[test]
+    #[ignore]
+    fn timing_is_independent_of_mismatch_position() {
+        use std::time::{Duration, Instant};
+
+        const ITERATIONS: usize = 200_000;
+
+        fn time(a: &[u8], b: &[u8]) -> Duration {
+            let start = Instant::now();
+            for _ in 0..ITERATIONS {
+                assert!(!slices_eq(a, b));
+            }
+            start.elapsed()
+        }
+
+        let reference = [0x5a; 64];
+        let mut first = reference;
+        first[0] ^= 1;
+        let mut last = reference;
+        last[63] ^= 1;
+
+        // Take the best of several runs of each, to reduce scheduling noise.
+        let first = (0..5).map(|_| time(&reference, &first)).min().unwrap();
+        let last = (0..5).map(|_| time(&reference, &last)).min().unwrap();
+        let ratio = first.as_secs_f64() / last.as_secs_f64();
+        assert!(
+            (0.8..1.25).contains(&ratio),
+            "first-byte mismatch took {:?}, last-byte mismatch took {:?}",
+            first,
+            last
+        );
+    }
+}
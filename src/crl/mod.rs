@@ -12,21 +12,28 @@
 // ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
 // OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
-use pki_types::SignatureVerificationAlgorithm;
+use pki_types::{SignatureVerificationAlgorithm, UnixTime};
 
 use crate::error::Error;
-use crate::verify_cert::{Budget, PathNode, Role};
-use crate::{der, public_values_eq};
+use crate::public_values_eq;
+use crate::time::skewed;
+use crate::verify_cert::{Budget, KeyUsageMode, PathNode, Role};
 
 use core::fmt::Debug;
+use core::time::Duration;
 
 mod types;
+#[cfg(feature = "std")]
+pub use types::CrlLimits;
 pub use types::{
     BorrowedCertRevocationList, BorrowedRevokedCert, CertRevocationList, RevocationReason,
 };
 #[cfg(feature = "alloc")]
 pub use types::{OwnedCertRevocationList, OwnedRevokedCert};
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 /// Builds a RevocationOptions instance to control how revocation checking is performed.
 #[derive(Debug, Copy, Clone)]
 pub struct RevocationOptionsBuilder<'a> {
@@ -35,6 +42,12 @@ pub struct RevocationOptionsBuilder<'a> {
     depth: RevocationCheckDepth,
 
     status_policy: UnknownStatusPolicy,
+
+    expiration_policy: ExpirationPolicy,
+
+    revocation_date_policy: RevocationDatePolicy,
+
+    generation: Option<usize>,
 }
 
 impl<'a> RevocationOptionsBuilder<'a> {
@@ -50,6 +63,13 @@ impl<'a> RevocationOptionsBuilder<'a> {
     /// By default revocation checking will fail if the revocation status of a certificate cannot
     /// be determined. This can be customized using the
     /// [RevocationOptionsBuilder::with_status_policy] method.
+    ///
+    /// By default a CRL is used even if its `nextUpdate` is before the verification time. This
+    /// can be customized using the [RevocationOptionsBuilder::with_expiration_policy] method.
+    ///
+    /// By default a certificate listed in a CRL is revoked regardless of its revocation date.
+    /// This can be customized using the [RevocationOptionsBuilder::with_revocation_date_policy]
+    /// method.
     pub fn new(crls: &'a [&'a CertRevocationList<'a>]) -> Result<Self, CrlsRequired> {
         if crls.is_empty() {
             return Err(CrlsRequired(()));
@@ -59,9 +79,23 @@ impl<'a> RevocationOptionsBuilder<'a> {
             crls,
             depth: RevocationCheckDepth::Chain,
             status_policy: UnknownStatusPolicy::Deny,
+            expiration_policy: ExpirationPolicy::Ignore,
+            revocation_date_policy: RevocationDatePolicy::Ignore,
+            generation: None,
         })
     }
 
+    /// Like [RevocationOptionsBuilder::new], using the CRLs in `crls`.
+    ///
+    /// Unlike a plain slice, a [CrlSet] records when its contents change, so the resulting
+    /// [RevocationOptions] can be used with a [crate::VerificationCache].
+    #[cfg(feature = "alloc")]
+    pub fn from_crl_set(crls: &'a CrlSet<'a>) -> Result<Self, CrlsRequired> {
+        let mut builder = Self::new(crls.as_slice())?;
+        builder.generation = Some(crls.generation());
+        Ok(builder)
+    }
+
     /// Customize the depth at which revocation checking will be performed, controlling
     /// whether only the end-entity (leaf) certificate in the chain to a trust anchor will
     /// have its revocation status checked, or whether the intermediate certificates will as well.
@@ -76,12 +110,36 @@ impl<'a> RevocationOptionsBuilder<'a> {
         self
     }
 
+    /// Customize whether a CRL whose `nextUpdate` is before the verification time is an error.
+    ///
+    /// The comparison is against the time passed to verification, not the current time, so a
+    /// historical verification accepts CRLs that were current at that time. A freshness time
+    /// set with [`crate::Verifier::freshness_time()`] is used instead, if any.
+    pub fn with_expiration_policy(mut self, policy: ExpirationPolicy) -> Self {
+        self.expiration_policy = policy;
+        self
+    }
+
+    /// Customize whether a certificate listed in a CRL is treated as revoked at verification
+    /// times before its revocation date.
+    ///
+    /// Enforcing the revocation date is appropriate when verifying at a trusted past time, such
+    /// as the time of a code signature's timestamp: a signature made before the signing
+    /// certificate was revoked remains valid.
+    pub fn with_revocation_date_policy(mut self, policy: RevocationDatePolicy) -> Self {
+        self.revocation_date_policy = policy;
+        self
+    }
+
     /// Construct a [RevocationOptions] instance based on the builder's configuration.
     pub fn build(self) -> RevocationOptions<'a> {
         RevocationOptions {
             crls: self.crls,
             depth: self.depth,
             status_policy: self.status_policy,
+            expiration_policy: self.expiration_policy,
+            revocation_date_policy: self.revocation_date_policy,
+            generation: self.generation,
         }
     }
 }
@@ -93,9 +151,15 @@ pub struct RevocationOptions<'a> {
     pub(crate) crls: &'a [&'a CertRevocationList<'a>],
     pub(crate) depth: RevocationCheckDepth,
     pub(crate) status_policy: UnknownStatusPolicy,
+    pub(crate) expiration_policy: ExpirationPolicy,
+    pub(crate) revocation_date_policy: RevocationDatePolicy,
+    /// The [CrlSet::generation] of the CRLs, if they came from a [CrlSet].
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) generation: Option<usize>,
 }
 
 impl<'a> RevocationOptions<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn check(
         &self,
         path: &PathNode<'_>,
@@ -103,6 +167,9 @@ impl<'a> RevocationOptions<'a> {
         issuer_spki: untrusted::Input,
         issuer_ku: Option<untrusted::Input>,
         supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        time: UnixTime,
+        freshness_time: UnixTime,
+        clock_skew: Duration,
         budget: &mut Budget,
     ) -> Result<Option<CertNotRevoked>, Error> {
         assert!(public_values_eq(path.cert.issuer, issuer_subject));
@@ -113,13 +180,8 @@ impl<'a> RevocationOptions<'a> {
             return Ok(None);
         }
 
-        let crl = self
-            .crls
-            .iter()
-            .find(|candidate_crl| candidate_crl.authoritative(path));
-
         use UnknownStatusPolicy::*;
-        let crl = match (crl, self.status_policy) {
+        let crl = match (self.authoritative_crl(path), self.status_policy) {
             (Some(crl), _) => crl,
             // If the policy allows unknown, return Ok(None) to indicate that the certificate
             // was not confirmed as CertNotRevoked, but that this isn't an error condition.
@@ -128,6 +190,11 @@ impl<'a> RevocationOptions<'a> {
             (None, _) => return Err(Error::UnknownRevocationStatus),
         };
 
+        // Verify that if the issuer has a KeyUsage bitstring it asserts cRLSign. This is checked
+        // before the signature so an issuer that may not sign CRLs can't consume signature budget.
+        // Trust anchors have no KeyUsage available (`issuer_ku` is `None`), so they are exempt.
+        KeyUsageMode::CrlSign.check(issuer_ku)?;
+
         // Verify the CRL signature with the issuer SPKI.
         // TODO(XXX): consider whether we can refactor so this happens once up-front, instead
         //            of per-lookup.
@@ -135,55 +202,76 @@ impl<'a> RevocationOptions<'a> {
         crl.verify_signature(supported_sig_algs, issuer_spki, budget)
             .map_err(crl_signature_err)?;
 
-        // Verify that if the issuer has a KeyUsage bitstring it asserts cRLSign.
-        KeyUsageMode::CrlSign.check(issuer_ku)?;
+        let (earliest, _) = skewed(freshness_time, clock_skew);
+        if self.expiration_policy == ExpirationPolicy::Enforce && earliest > crl.next_update() {
+            return Err(Error::CrlExpired);
+        }
 
         // Try to find the cert serial in the verified CRL contents.
         let cert_serial = path.cert.serial.as_slice_less_safe();
         match crl.find_serial(cert_serial)? {
             None => Ok(Some(CertNotRevoked::assertion())),
+            Some(revoked)
+                if self.revocation_date_policy == RevocationDatePolicy::Enforce
+                    && time < revoked.revocation_date =>
+            {
+                Ok(Some(CertNotRevoked::assertion()))
+            }
             Some(_) => Err(Error::CertRevoked),
         }
     }
-}
 
-// https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.3
-#[repr(u8)]
-#[derive(Clone, Copy)]
-enum KeyUsageMode {
-    // DigitalSignature = 0,
-    // ContentCommitment = 1,
-    // KeyEncipherment = 2,
-    // DataEncipherment = 3,
-    // KeyAgreement = 4,
-    // CertSign = 5,
-    CrlSign = 6,
-    // EncipherOnly = 7,
-    // DecipherOnly = 8,
-}
+    /// The status of the certificate at `path`, once `check()` has succeeded for it.
+    pub(crate) fn status(&self, path: &PathNode<'_>) -> RevocationStatus {
+        if let (RevocationCheckDepth::EndEntity, Role::Issuer) = (self.depth, path.role()) {
+            return RevocationStatus::NotChecked;
+        }
 
-impl KeyUsageMode {
-    // https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.3
-    fn check(self, input: Option<untrusted::Input>) -> Result<(), Error> {
-        let bit_string = match input {
-            Some(input) => {
-                der::expect_tag(&mut untrusted::Reader::new(input), der::Tag::BitString)?
-            }
-            // While RFC 5280 requires KeyUsage be present, historically the absence of a KeyUsage
-            // has been treated as "Any Usage". We follow that convention here and assume the absence
-            // of KeyUsage implies the required_ku_bit_if_present we're checking for.
-            None => return Ok(()),
-        };
+        match self.authoritative_crl(path) {
+            Some(_) => RevocationStatus::NotRevoked,
+            None => RevocationStatus::Unknown,
+        }
+    }
 
-        let flags = der::bit_string_flags(bit_string)?;
-        #[allow(clippy::as_conversions)] // u8 always fits in usize.
-        match flags.bit_set(self as usize) {
-            true => Ok(()),
-            false => Err(Error::IssuerNotCrlSigner),
+    /// The revocation status of the certificate at `path`, whose issuer is unknown, so that no
+    /// CRL can be verified for it. See
+    /// `Verifier::dangerous_verify_allowing_unknown_issuer()`.
+    pub(crate) fn status_without_issuer(
+        &self,
+        path: &PathNode<'_>,
+    ) -> Result<RevocationStatus, Error> {
+        if let (RevocationCheckDepth::EndEntity, Role::Issuer) = (self.depth, path.role()) {
+            return Ok(RevocationStatus::NotChecked);
         }
+
+        match self.status_policy {
+            UnknownStatusPolicy::Allow => Ok(RevocationStatus::Unknown),
+            UnknownStatusPolicy::Deny => Err(Error::UnknownRevocationStatus),
+        }
+    }
+
+    fn authoritative_crl(&self, path: &PathNode<'_>) -> Option<&'a CertRevocationList<'a>> {
+        self.crls
+            .iter()
+            .find(|candidate_crl| candidate_crl.authoritative(path))
+            .copied()
     }
 }
 
+/// The revocation status of a certificate in a verified path, as reported by
+/// `VerifiedPath::revocation_statuses()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RevocationStatus {
+    /// The certificate's revocation status wasn't checked: no [RevocationOptions] were given,
+    /// or their [RevocationCheckDepth] excludes the certificate.
+    NotChecked,
+    /// An authoritative CRL was found, and doesn't list the certificate as revoked.
+    NotRevoked,
+    /// No authoritative CRL was found, and the certificate was accepted because of
+    /// [UnknownStatusPolicy::Allow].
+    Unknown,
+}
+
 // When verifying CRL signed data we want to disambiguate the context of possible errors by mapping
 // them to CRL specific variants that a consumer can use to tell the issue was with the CRL's
 // signature, not a certificate.
@@ -198,8 +286,73 @@ fn crl_signature_err(err: Error) -> Error {
     }
 }
 
+/// A collection of certificate revocation lists, for use with
+/// [RevocationOptionsBuilder::from_crl_set].
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct CrlSet<'a> {
+    crls: Vec<&'a CertRevocationList<'a>>,
+    generation: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> CrlSet<'a> {
+    /// Construct an empty set.
+    pub fn new() -> Self {
+        Self {
+            crls: Vec::new(),
+            generation: crate::next_generation(),
+        }
+    }
+
+    /// Add `crl` to the set.
+    pub fn push(&mut self, crl: &'a CertRevocationList<'a>) {
+        self.generation = crate::next_generation();
+        self.crls.push(crl);
+    }
+
+    /// Remove all CRLs from the set.
+    pub fn clear(&mut self) {
+        self.generation = crate::next_generation();
+        self.crls.clear();
+    }
+
+    /// The CRLs in the set.
+    pub fn as_slice(&self) -> &[&'a CertRevocationList<'a>] {
+        &self.crls
+    }
+
+    /// The number of CRLs in the set.
+    pub fn len(&self) -> usize {
+        self.crls.len()
+    }
+
+    /// Returns true if the set contains no CRLs.
+    pub fn is_empty(&self) -> bool {
+        self.crls.is_empty()
+    }
+
+    /// Identifies the current contents of the set, as for
+    /// [crate::TrustAnchorSet::generation].
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for CrlSet<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Describes how much of a certificate chain is checked for revocation status.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum RevocationCheckDepth {
     /// Only check the end entity (leaf) certificate's revocation status.
     EndEntity,
@@ -208,7 +361,12 @@ pub enum RevocationCheckDepth {
 }
 
 /// Describes how to handle the case where a certificate's revocation status is unknown.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum UnknownStatusPolicy {
     /// Treat unknown revocation status permissively, acting as if the certificate were
     /// not revoked.
@@ -218,6 +376,35 @@ pub enum UnknownStatusPolicy {
     Deny,
 }
 
+/// Describes how to handle a CRL whose `nextUpdate` is before the verification time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ExpirationPolicy {
+    /// Treat an expired CRL as an error condition, yielding [Error::CrlExpired].
+    Enforce,
+    /// Use an expired CRL as if it were current.
+    Ignore,
+}
+
+/// Describes how the `revocationDate` of a certificate listed in a CRL is treated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum RevocationDatePolicy {
+    /// Treat the certificate as revoked only at verification times at or after its
+    /// revocation date.
+    Enforce,
+    /// Treat the certificate as revoked whatever the verification time.
+    Ignore,
+}
+
 // Zero-sized marker type representing positive assertion that revocation status was checked
 // for a certificate and the result was that the certificate is not revoked.
 pub(crate) struct CertNotRevoked(());
@@ -269,6 +456,8 @@ mod tests {
         let opts = builder.build();
         assert_eq!(opts.depth, RevocationCheckDepth::Chain);
         assert_eq!(opts.status_policy, UnknownStatusPolicy::Deny);
+        assert_eq!(opts.expiration_policy, ExpirationPolicy::Ignore);
+        assert_eq!(opts.revocation_date_policy, RevocationDatePolicy::Ignore);
         assert_eq!(opts.crls.len(), 1);
 
         // It should be possible to build a revocation options builder with custom depth.
@@ -2,19 +2,27 @@
 use alloc::collections::BTreeMap;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use core::fmt;
 use core::fmt::Debug;
 
 use pki_types::{SignatureVerificationAlgorithm, UnixTime};
 
 use crate::cert::lenient_certificate_serial_number;
+use crate::constant_time;
 use crate::crl::crl_signature_err;
 use crate::der::{self, DerIterator, FromDer, Tag, CONSTRUCTED, CONTEXT_SPECIFIC};
 use crate::error::{DerTypeId, Error};
-use crate::public_values_eq;
 use crate::signed_data::{self, SignedData};
 use crate::subject_name::GeneralName;
 use crate::verify_cert::{Budget, PathNode, Role};
 use crate::x509::{remember_extension, set_extension_once, DistributionPointName, Extension};
+#[cfg(feature = "zeroize")]
+use crate::zeroize::zeroize;
+#[cfg(feature = "alloc")]
+use crate::zeroize::Redacted;
 
 /// A RFC 5280[^1] profile Certificate Revocation List (CRL).
 ///
@@ -53,6 +61,15 @@ impl<'a> CertRevocationList<'a> {
         }
     }
 
+    /// Return the date by which the next CRL will be issued.
+    pub fn next_update(&self) -> UnixTime {
+        match self {
+            #[cfg(feature = "alloc")]
+            CertRevocationList::Owned(crl) => crl.next_update,
+            CertRevocationList::Borrowed(crl) => crl.next_update,
+        }
+    }
+
     /// Return the DER encoded issuing distribution point of the CRL, if any.
     pub fn issuing_distribution_point(&self) -> Option<&[u8]> {
         match self {
@@ -64,9 +81,33 @@ impl<'a> CertRevocationList<'a> {
         }
     }
 
+    /// Return the big-endian encoding of the CRL's number, from its cRLNumber extension, if
+    /// any.
+    pub fn crl_number(&self) -> Option<&[u8]> {
+        match self {
+            #[cfg(feature = "alloc")]
+            CertRevocationList::Owned(crl) => crl.crl_number.as_deref(),
+            CertRevocationList::Borrowed(crl) => {
+                crl.crl_number.map(|number| number.as_slice_less_safe())
+            }
+        }
+    }
+
+    /// For a delta CRL, return the big-endian encoding of the number of the base CRL it
+    /// updates, from its deltaCRLIndicator extension. Returns `None` for a complete CRL.
+    pub fn base_crl_number(&self) -> Option<&[u8]> {
+        match self {
+            #[cfg(feature = "alloc")]
+            CertRevocationList::Owned(crl) => crl.base_crl_number.as_deref(),
+            CertRevocationList::Borrowed(crl) => crl
+                .base_crl_number
+                .map(|number| number.as_slice_less_safe()),
+        }
+    }
+
     /// Try to find a revoked certificate in the CRL by DER encoded serial number. This
     /// may yield an error if the CRL has malformed revoked certificates.
-    pub fn find_serial(&self, serial: &[u8]) -> Result<Option<BorrowedRevokedCert>, Error> {
+    pub fn find_serial(&self, serial: &[u8]) -> Result<Option<BorrowedRevokedCert<'_>>, Error> {
         match self {
             #[cfg(feature = "alloc")]
             CertRevocationList::Owned(crl) => crl.find_serial(serial),
@@ -74,6 +115,88 @@ impl<'a> CertRevocationList<'a> {
         }
     }
 
+    /// Merge `delta`, a delta CRL, into this complete CRL, producing a complete CRL that can be
+    /// used for revocation checking in the same way as either.
+    ///
+    /// The result lists the certificates revoked by this CRL or by `delta`, except those that
+    /// `delta` lists with [`RevocationReason::RemoveFromCrl`]. It has the number and
+    /// `nextUpdate` of `delta`, and the signatures of both CRLs are verified when it is used.
+    /// See RFC 5280 §5.2.4[^1].
+    ///
+    /// Returns [`Error::UnsupportedDeltaCrl`] if this CRL is itself a delta CRL, and
+    /// [`Error::DeltaCrlMismatch`] if `delta` cannot be applied to this CRL: if it is not a
+    /// delta CRL, if the issuers or issuing distribution points of the two CRLs differ, or if
+    /// this CRL's number is lower than the base CRL number of `delta`, or not lower than the
+    /// number of `delta` itself.
+    ///
+    /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5.2.4>
+    #[cfg(feature = "alloc")]
+    pub fn with_delta(
+        &self,
+        delta: &CertRevocationList<'_>,
+    ) -> Result<OwnedCertRevocationList, Error> {
+        if self.base_crl_number().is_some() {
+            return Err(Error::UnsupportedDeltaCrl);
+        }
+
+        let (crl_number, delta_crl_number, delta_base_crl_number) = match (
+            self.crl_number(),
+            delta.crl_number(),
+            delta.base_crl_number(),
+        ) {
+            (Some(crl_number), Some(delta_crl_number), Some(delta_base_crl_number)) => {
+                (crl_number, delta_crl_number, delta_base_crl_number)
+            }
+            _ => return Err(Error::DeltaCrlMismatch),
+        };
+        if self.issuer() != delta.issuer()
+            || self.issuing_distribution_point() != delta.issuing_distribution_point()
+            || compare_crl_numbers(crl_number, delta_base_crl_number) == Ordering::Less
+            || compare_crl_numbers(crl_number, delta_crl_number) != Ordering::Less
+        {
+            return Err(Error::DeltaCrlMismatch);
+        }
+
+        let mut revoked_certs = self.owned_revoked_certs()?;
+        for (serial, revoked_cert) in delta.owned_revoked_certs()? {
+            match revoked_cert.reason_code {
+                Some(RevocationReason::RemoveFromCrl) => revoked_certs.remove(&serial),
+                _ => revoked_certs.insert(serial, revoked_cert),
+            };
+        }
+
+        let (signed_data, mut delta_signed_data) = match self {
+            CertRevocationList::Owned(crl) => {
+                (crl.signed_data.clone(), crl.delta_signed_data.clone())
+            }
+            CertRevocationList::Borrowed(crl) => (crl.signed_data.to_owned(), Vec::new()),
+        };
+        delta_signed_data.push(match delta {
+            CertRevocationList::Owned(crl) => crl.signed_data.clone(),
+            CertRevocationList::Borrowed(crl) => crl.signed_data.to_owned(),
+        });
+
+        Ok(OwnedCertRevocationList {
+            revoked_certs,
+            issuer: self.issuer().to_vec(),
+            issuing_distribution_point: self.issuing_distribution_point().map(|idp| idp.to_vec()),
+            crl_number: Some(delta_crl_number.to_vec()),
+            base_crl_number: None,
+            next_update: delta.next_update(),
+            signed_data,
+            delta_signed_data,
+            verified_spki: None,
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn owned_revoked_certs(&self) -> Result<BTreeMap<Vec<u8>, OwnedRevokedCert>, Error> {
+        match self {
+            CertRevocationList::Owned(crl) => Ok(crl.revoked_certs.clone()),
+            CertRevocationList::Borrowed(crl) => crl.owned_revoked_certs(usize::MAX),
+        }
+    }
+
     /// Returns true if the CRL can be considered authoritative for the given certificate.
     ///
     /// A CRL is considered authoritative for a certificate when:
@@ -87,8 +210,15 @@ impl<'a> CertRevocationList<'a> {
     ///       one distribution point full name is a URI type general name that can also be found in
     ///       the CRL issuing distribution point full name general name sequence.
     ///
-    /// In all other circumstances the CRL is not considered authoritative.
+    /// In all other circumstances the CRL is not considered authoritative. In particular, a
+    /// delta CRL is never authoritative until it is merged with its base CRL by
+    /// [`CertRevocationList::with_delta()`].
     pub(crate) fn authoritative(&self, path: &PathNode<'_>) -> bool {
+        // A delta CRL only lists the changes since its base CRL.
+        if self.base_crl_number().is_some() {
+            return false;
+        }
+
         // In all cases we require that the authoritative CRL have the same issuer
         // as the certificate. Recall we do not support indirect CRLs.
         if self.issuer() != path.cert.issuer() {
@@ -118,35 +248,46 @@ impl<'a> CertRevocationList<'a> {
 
     /// Verify the CRL signature using the issuer certificate and a list of supported signature
     /// verification algorithms, consuming signature operations from the [`Budget`].
+    ///
+    /// For a CRL produced by [`CertRevocationList::with_delta()`], the signatures of the base
+    /// CRL and of each merged delta CRL are verified.
     pub(crate) fn verify_signature(
         &self,
         supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
         issuer_spki: untrusted::Input,
         budget: &mut Budget,
     ) -> Result<(), Error> {
-        signed_data::verify_signed_data(
-            supported_sig_algs,
-            issuer_spki,
-            &match self {
-                #[cfg(feature = "alloc")]
-                CertRevocationList::Owned(crl) => crl.signed_data.borrow(),
-                CertRevocationList::Borrowed(crl) => SignedData {
+        match self {
+            #[cfg(feature = "alloc")]
+            CertRevocationList::Owned(crl) => {
+                crl.verify_signature(supported_sig_algs, issuer_spki, budget)
+            }
+            CertRevocationList::Borrowed(crl) => signed_data::verify_signed_data(
+                supported_sig_algs,
+                issuer_spki,
+                &SignedData {
                     data: crl.signed_data.data,
                     algorithm: crl.signed_data.algorithm,
                     signature: crl.signed_data.signature,
                 },
-            },
-            budget,
-        )
-        .map_err(crl_signature_err)
+                budget,
+            )
+            .map_err(crl_signature_err),
+        }
     }
 }
 
 /// Owned representation of a RFC 5280[^1] profile Certificate Revocation List (CRL).
 ///
+/// Unlike [`BorrowedCertRevocationList`], which walks its entries for each lookup, the revoked
+/// certificates are indexed by serial number when the CRL is parsed, and looked up in
+/// logarithmic time. A large CRL is meant to be parsed once, verified once with
+/// [`OwnedCertRevocationList::with_verified_signature()`], and then shared by every
+/// verification, such as behind an `Arc<CertRevocationList<'static>>`.
+///
 /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
 #[cfg(feature = "alloc")]
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct OwnedCertRevocationList {
     /// A map of the revoked certificates contained in then CRL, keyed by the DER encoding
     /// of the revoked cert's serial number.
@@ -156,7 +297,22 @@ pub struct OwnedCertRevocationList {
 
     issuing_distribution_point: Option<Vec<u8>>,
 
+    crl_number: Option<Vec<u8>>,
+
+    base_crl_number: Option<Vec<u8>>,
+
+    next_update: UnixTime,
+
     signed_data: signed_data::OwnedSignedData,
+
+    /// The signatures of the delta CRLs merged into this CRL by
+    /// [`CertRevocationList::with_delta()`], if any.
+    delta_signed_data: Vec<signed_data::OwnedSignedData>,
+
+    /// The subjectPublicKeyInfo, without its outer SEQUENCE tag and length, that the
+    /// signatures were verified with by [`OwnedCertRevocationList::with_verified_signature()`],
+    /// if any. They aren't verified again when the CRL is used with an issuer with this key.
+    verified_spki: Option<Vec<u8>>,
 }
 
 #[cfg(feature = "alloc")]
@@ -167,9 +323,11 @@ impl OwnedCertRevocationList {
     ///   * CRL versions other than version 2.
     ///   * CRLs missing the next update field.
     ///   * CRLs missing certificate revocation list extensions.
-    ///   * Delta CRLs.
     ///   * CRLs larger than (2^32)-1 bytes in size.
     ///
+    /// A delta CRL can be parsed, but must be merged into its base CRL with
+    /// [`CertRevocationList::with_delta()`] before use.
+    ///
     /// See [BorrowedCertRevocationList::from_der] for more details.
     ///
     /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
@@ -177,7 +335,151 @@ impl OwnedCertRevocationList {
         BorrowedCertRevocationList::from_der(crl_der)?.to_owned()
     }
 
-    fn find_serial(&self, serial: &[u8]) -> Result<Option<BorrowedRevokedCert>, Error> {
+    /// Like [OwnedCertRevocationList::from_der], reading the DER encoding of the CRL from
+    /// `reader`, subject to `limits`.
+    ///
+    /// The CRL is read into a single buffer, which is then reused to hold the `tbsCertList`
+    /// the signature is verified over, so that the CRL is not held in memory twice. Only the
+    /// bytes of the CRL are read from `reader`; anything following it is left unread.
+    ///
+    /// Returns [Error::MaximumCrlSizeExceeded] if the CRL exceeds `limits`, and
+    /// [Error::BadDer] if `reader` ends before the CRL does or fails.
+    #[cfg(feature = "std")]
+    pub fn from_reader(mut reader: impl std::io::Read, limits: CrlLimits) -> Result<Self, Error> {
+        use std::io::Read;
+
+        // Read the tag and length of the outer SEQUENCE, to learn how much to read without
+        // relying on the reader to end.
+        let mut der = alloc::vec![0; 2];
+        reader.read_exact(&mut der).map_err(|_| Error::BadDer)?;
+        if der[0] != u8::from(Tag::Sequence) {
+            return Err(Error::BadDer);
+        }
+        let length_bytes = match der[1] {
+            n if n < 0x80 => 0,
+            0x80 => return Err(Error::BerEncodingNotAllowed { offset: 1 }),
+            n if n <= 0x84 => usize::from(n & 0x7f),
+            _ => return Err(Error::BadDer),
+        };
+        der.resize(2 + length_bytes, 0);
+        reader
+            .read_exact(&mut der[2..])
+            .map_err(|_| Error::BadDer)?;
+        let length = match length_bytes {
+            0 => usize::from(der[1]),
+            _ => der[2..]
+                .iter()
+                .fold(0, |length, byte| (length << 8) | usize::from(*byte)),
+        };
+
+        let header_len = der.len();
+        let total_len = header_len.checked_add(length).ok_or(Error::BadDer)?;
+        if total_len > limits.max_size {
+            return Err(Error::MaximumCrlSizeExceeded);
+        }
+
+        // The buffer grows as the reader supplies data, rather than being allocated up-front
+        // based on the untrusted length.
+        let remaining = u64::try_from(length).map_err(|_| Error::BadDer)?;
+        reader
+            .take(remaining)
+            .read_to_end(&mut der)
+            .map_err(|_| Error::BadDer)?;
+        if der.len() != total_len {
+            return Err(Error::BadDer);
+        }
+
+        let (mut crl, tbs_len) = {
+            let crl = BorrowedCertRevocationList::from_der(&der)?;
+            let owned = OwnedCertRevocationList {
+                revoked_certs: crl.owned_revoked_certs(limits.max_entries)?,
+                issuer: crl.issuer.as_slice_less_safe().to_vec(),
+                issuing_distribution_point: crl
+                    .issuing_distribution_point
+                    .map(|idp| idp.as_slice_less_safe().to_vec()),
+                crl_number: crl
+                    .crl_number
+                    .map(|number| number.as_slice_less_safe().to_vec()),
+                base_crl_number: crl
+                    .base_crl_number
+                    .map(|number| number.as_slice_less_safe().to_vec()),
+                next_update: crl.next_update,
+                signed_data: signed_data::OwnedSignedData {
+                    data: Vec::new(),
+                    algorithm: crl.signed_data.algorithm.as_slice_less_safe().to_vec(),
+                    signature: crl.signed_data.signature.as_slice_less_safe().to_vec(),
+                },
+                delta_signed_data: Vec::new(),
+                verified_spki: None,
+            };
+            (owned, crl.signed_data.data.len())
+        };
+
+        // The `tbsCertList` is the first element of the outer SEQUENCE.
+        der.truncate(header_len + tbs_len);
+        der.drain(..header_len);
+        crl.signed_data.data = der;
+        Ok(crl)
+    }
+
+    /// Verifies the CRL's signatures with `issuer_spki`, the DER-encoded subjectPublicKeyInfo of
+    /// the CRL issuer including its outer SEQUENCE tag and length, as returned by
+    /// [`crate::Cert::subject_public_key_info()`].
+    ///
+    /// On success, the signatures aren't verified again during path building if the CRL is
+    /// used for an issuer with the same key, so checking revocation with a large CRL costs
+    /// only the lookup of each certificate. For a CRL produced by
+    /// [`CertRevocationList::with_delta()`], the signatures of the base CRL and of each merged
+    /// delta CRL are verified. The signature algorithm must be one of `supported_sig_algs`
+    /// then; it isn't checked against those of later verifications.
+    ///
+    /// Fails as revocation checking would for a signature that doesn't verify, with
+    /// [`Error::InvalidCrlSignatureForPublicKey`] for example.
+    pub fn with_verified_signature(
+        mut self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        issuer_spki: &[u8],
+    ) -> Result<Self, Error> {
+        let issuer_spki = untrusted::Input::from(issuer_spki).read_all(
+            Error::TrailingData(DerTypeId::SubjectPublicKeyInfo),
+            |reader| der::expect_tag(reader, Tag::Sequence),
+        )?;
+        self.verified_spki = None;
+        self.verify_signature(supported_sig_algs, issuer_spki, &mut Budget::default())?;
+        self.verified_spki = Some(issuer_spki.as_slice_less_safe().to_vec());
+        Ok(self)
+    }
+
+    fn verify_signature(
+        &self,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        issuer_spki: untrusted::Input,
+        budget: &mut Budget,
+    ) -> Result<(), Error> {
+        if self.verified_spki.as_deref() == Some(issuer_spki.as_slice_less_safe()) {
+            return Ok(());
+        }
+
+        for delta_signed_data in &self.delta_signed_data {
+            signed_data::verify_signed_data(
+                supported_sig_algs,
+                issuer_spki,
+                &delta_signed_data.borrow(),
+                budget,
+            )
+            .map_err(crl_signature_err)?;
+        }
+
+        signed_data::verify_signed_data(
+            supported_sig_algs,
+            issuer_spki,
+            &self.signed_data.borrow(),
+            budget,
+        )
+        .map_err(crl_signature_err)
+    }
+
+    fn find_serial(&self, serial: &[u8]) -> Result<Option<BorrowedRevokedCert<'_>>, Error> {
         // note: this is infallible for the owned representation because we process all
         // revoked certificates at the time of construction to build the `revoked_certs` map,
         // returning any encountered errors at that time.
@@ -188,6 +490,91 @@ impl OwnedCertRevocationList {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl fmt::Debug for OwnedCertRevocationList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedCertRevocationList")
+            .field("revoked_certs", &self.revoked_certs.len())
+            .field("issuer", &Redacted(&self.issuer))
+            .field(
+                "issuing_distribution_point",
+                &self.issuing_distribution_point.as_deref().map(Redacted),
+            )
+            .field("crl_number", &self.crl_number.as_deref().map(Redacted))
+            .field(
+                "base_crl_number",
+                &self.base_crl_number.as_deref().map(Redacted),
+            )
+            .field("next_update", &self.next_update)
+            .field("signed_data", &self.signed_data)
+            .field("delta_signed_data", &self.delta_signed_data)
+            .field("signature_verified", &self.verified_spki.is_some())
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for OwnedCertRevocationList {
+    fn drop(&mut self) {
+        for (mut serial, mut revoked_cert) in core::mem::take(&mut self.revoked_certs) {
+            zeroize(&mut serial);
+            zeroize(&mut revoked_cert.serial_number);
+        }
+        zeroize(&mut self.issuer);
+        for buf in [
+            &mut self.issuing_distribution_point,
+            &mut self.crl_number,
+            &mut self.base_crl_number,
+            &mut self.verified_spki,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            zeroize(buf);
+        }
+    }
+}
+
+/// Limits on the CRLs accepted by [OwnedCertRevocationList::from_reader], bounding the memory
+/// used to load them.
+///
+/// By default, the only limits are those of [OwnedCertRevocationList::from_der].
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrlLimits {
+    max_size: usize,
+    max_entries: usize,
+}
+
+#[cfg(feature = "std")]
+impl CrlLimits {
+    /// Reject CRLs whose DER encoding is longer than `bytes`.
+    pub const fn with_max_size(self, bytes: usize) -> Self {
+        Self {
+            max_size: bytes,
+            ..self
+        }
+    }
+
+    /// Reject CRLs listing more than `entries` revoked certificates.
+    pub const fn with_max_entries(self, entries: usize) -> Self {
+        Self {
+            max_entries: entries,
+            ..self
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for CrlLimits {
+    fn default() -> Self {
+        Self {
+            max_size: usize::MAX,
+            max_entries: usize::MAX,
+        }
+    }
+}
+
 /// Borrowed representation of a RFC 5280[^1] profile Certificate Revocation List (CRL).
 ///
 /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
@@ -203,6 +590,16 @@ pub struct BorrowedCertRevocationList<'a> {
     /// An optional CRL extension that identifies the CRL distribution point and scope for the CRL.
     issuing_distribution_point: Option<untrusted::Input<'a>>,
 
+    /// The number of this CRL, from the cRLNumber extension.
+    crl_number: Option<untrusted::Input<'a>>,
+
+    /// For a delta CRL, the number of the base CRL it updates, from the deltaCRLIndicator
+    /// extension.
+    base_crl_number: Option<untrusted::Input<'a>>,
+
+    /// The date by which the next CRL will be issued.
+    next_update: UnixTime,
+
     /// List of certificates revoked by the issuer in this CRL.
     revoked_certs: untrusted::Input<'a>,
 }
@@ -214,9 +611,11 @@ impl<'a> BorrowedCertRevocationList<'a> {
     ///   * CRL versions other than version 2.
     ///   * CRLs missing the next update field.
     ///   * CRLs missing certificate revocation list extensions.
-    ///   * Delta CRLs.
     ///   * CRLs larger than (2^32)-1 bytes in size.
     ///
+    /// A delta CRL can be parsed, but must be merged into its base CRL with
+    /// [`CertRevocationList::with_delta()`] before use.
+    ///
     /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
     pub fn from_der(crl_der: &'a [u8]) -> Result<Self, Error> {
         der::read_all(untrusted::Input::from(crl_der))
@@ -228,12 +627,7 @@ impl<'a> BorrowedCertRevocationList<'a> {
     pub fn to_owned(&self) -> Result<OwnedCertRevocationList, Error> {
         // Parse and collect the CRL's revoked cert entries, ensuring there are no errors. With
         // the full set in-hand, create a lookup map by serial number for fast revocation checking.
-        let revoked_certs = self
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?
-            .iter()
-            .map(|revoked_cert| (revoked_cert.serial_number.to_vec(), revoked_cert.to_owned()))
-            .collect::<BTreeMap<_, _>>();
+        let revoked_certs = self.owned_revoked_certs(usize::MAX)?;
 
         Ok(OwnedCertRevocationList {
             signed_data: self.signed_data.to_owned(),
@@ -241,37 +635,47 @@ impl<'a> BorrowedCertRevocationList<'a> {
             issuing_distribution_point: self
                 .issuing_distribution_point
                 .map(|idp| idp.as_slice_less_safe().to_vec()),
+            crl_number: self
+                .crl_number
+                .map(|number| number.as_slice_less_safe().to_vec()),
+            base_crl_number: self
+                .base_crl_number
+                .map(|number| number.as_slice_less_safe().to_vec()),
+            next_update: self.next_update,
             revoked_certs,
+            delta_signed_data: Vec::new(),
+            verified_spki: None,
         })
     }
 
+    /// Collect the revoked certificates into a map keyed by serial number, failing with
+    /// [Error::MaximumCrlSizeExceeded] if there are more than `max_entries`.
+    #[cfg(feature = "alloc")]
+    fn owned_revoked_certs(
+        &self,
+        max_entries: usize,
+    ) -> Result<BTreeMap<Vec<u8>, OwnedRevokedCert>, Error> {
+        let mut revoked_certs = BTreeMap::new();
+        for (i, revoked_cert) in self.into_iter().enumerate() {
+            if i >= max_entries {
+                return Err(Error::MaximumCrlSizeExceeded);
+            }
+            let revoked_cert = revoked_cert?;
+            revoked_certs.insert(revoked_cert.serial_number.to_vec(), revoked_cert.to_owned());
+        }
+        Ok(revoked_certs)
+    }
+
     fn remember_extension(&mut self, extension: &Extension<'a>) -> Result<(), Error> {
         remember_extension(extension, |id| {
             match id {
                 // id-ce-cRLNumber 2.5.29.20 - RFC 5280 §5.2.3
-                20 => {
-                    // RFC 5280 §5.2.3:
-                    //   CRL verifiers MUST be able to handle CRLNumber values
-                    //   up to 20 octets.  Conforming CRL issuers MUST NOT use CRLNumber
-                    //   values longer than 20 octets.
-                    //
-                    extension.value.read_all(Error::InvalidCrlNumber, |der| {
-                        let crl_number = der::nonnegative_integer(der)
-                            .map_err(|_| Error::InvalidCrlNumber)?
-                            .as_slice_less_safe();
-                        if crl_number.len() <= 20 {
-                            Ok(crl_number)
-                        } else {
-                            Err(Error::InvalidCrlNumber)
-                        }
-                    })?;
-                    // We enforce the cRLNumber is sensible, but don't retain the value for use.
-                    Ok(())
-                }
+                // We recognize the extension and retain its value for use.
+                20 => set_extension_once(&mut self.crl_number, || crl_number(extension)),
 
                 // id-ce-deltaCRLIndicator 2.5.29.27 - RFC 5280 §5.2.4
-                // We explicitly do not support delta CRLs.
-                27 => Err(Error::UnsupportedDeltaCrl),
+                // We recognize the extension and retain the base CRL number for use.
+                27 => set_extension_once(&mut self.base_crl_number, || crl_number(extension)),
 
                 // id-ce-issuingDistributionPoint 2.5.29.28 - RFC 5280 §5.2.4
                 // We recognize the extension and retain its value for use.
@@ -283,18 +687,23 @@ impl<'a> BorrowedCertRevocationList<'a> {
                 // We recognize the extension but don't retain its value for use.
                 35 => Ok(()),
 
+                // id-ce-freshestCRL 2.5.29.46 - RFC 5280 §5.2.6
+                // We recognize the extension but don't retain its value for use: locating
+                // delta CRLs is left to the caller.
+                46 => Ok(()),
+
                 // Unsupported extension
                 _ => extension.unsupported(),
             }
         })
     }
 
-    fn find_serial(&self, serial: &[u8]) -> Result<Option<BorrowedRevokedCert>, Error> {
+    fn find_serial(&self, serial: &[u8]) -> Result<Option<BorrowedRevokedCert<'_>>, Error> {
         for revoked_cert_result in self {
             match revoked_cert_result {
                 Err(e) => return Err(e),
                 Ok(revoked_cert) => {
-                    if revoked_cert.serial_number.eq(serial) {
+                    if constant_time::slices_eq(revoked_cert.serial_number, serial) {
                         return Ok(Some(revoked_cert));
                     }
                 }
@@ -305,6 +714,30 @@ impl<'a> BorrowedCertRevocationList<'a> {
     }
 }
 
+/// Parse the value of a cRLNumber or deltaCRLIndicator extension, both of which are a CRLNumber.
+fn crl_number<'a>(extension: &Extension<'a>) -> Result<untrusted::Input<'a>, Error> {
+    // RFC 5280 §5.2.3:
+    //   CRL verifiers MUST be able to handle CRLNumber values
+    //   up to 20 octets.  Conforming CRL issuers MUST NOT use CRLNumber
+    //   values longer than 20 octets.
+    //
+    extension.value.read_all(Error::InvalidCrlNumber, |der| {
+        let crl_number = der::nonnegative_integer(der).map_err(|_| Error::InvalidCrlNumber)?;
+        if crl_number.len() <= 20 {
+            Ok(crl_number)
+        } else {
+            Err(Error::InvalidCrlNumber)
+        }
+    })
+}
+
+/// Compare two CRL numbers, as returned by [`crl_number()`].
+#[cfg(feature = "alloc")]
+fn compare_crl_numbers(a: &[u8], b: &[u8]) -> Ordering {
+    // Neither has a leading zero unless it is zero, so the longer number is the greater.
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
 impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
     /// Try to parse the given bytes as a RFC 5280[^1] profile Certificate Revocation List (CRL).
     ///
@@ -312,9 +745,11 @@ impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
     ///   * CRL versions other than version 2.
     ///   * CRLs missing the next update field.
     ///   * CRLs missing certificate revocation list extensions.
-    ///   * Delta CRLs.
     ///   * CRLs larger than (2^32)-1 bytes in size.
     ///
+    /// A delta CRL can be parsed, but must be merged into its base CRL with
+    /// [`CertRevocationList::with_delta()`] before use.
+    ///
     /// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5>
     fn from_der(reader: &mut untrusted::Reader<'a>) -> Result<Self, Error> {
         let (tbs_cert_list, signed_data) = der::nested_limited(
@@ -344,7 +779,7 @@ impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
             //   This field MUST contain the same algorithm identifier as the
             //   signatureAlgorithm field in the sequence CertificateList
             let signature = der::expect_tag(tbs_cert_list, Tag::Sequence)?;
-            if !public_values_eq(signature, signed_data.algorithm) {
+            if !signed_data::algorithm_identifiers_match(signature, signed_data.algorithm) {
                 return Err(Error::SignatureAlgorithmMismatch);
             }
 
@@ -357,18 +792,20 @@ impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
             //    encoded as UTCTime or GeneralizedTime.
             // We do not presently enforce the correct choice of UTCTime or GeneralizedTime based on
             // whether the date is post 2050.
-            UnixTime::from_der(tbs_cert_list)?;
+            let this_update = UnixTime::from_der(tbs_cert_list)?;
 
             // While OPTIONAL in the ASN.1 module, RFC 5280 §5.1.2.5 says:
             //   Conforming CRL issuers MUST include the nextUpdate field in all CRLs.
             // We do not presently enforce the correct choice of UTCTime or GeneralizedTime based on
             // whether the date is post 2050.
-            UnixTime::from_der(tbs_cert_list)?;
+            let next_update = UnixTime::from_der(tbs_cert_list)?;
+            if next_update < this_update {
+                return Err(Error::InvalidCrlValidityPeriod);
+            }
 
             // RFC 5280 §5.1.2.6:
             //   When there are no revoked certificates, the revoked certificates list
             //   MUST be absent
-            // TODO(@cpu): Do we care to support empty CRLs if we don't support delta CRLs?
             let revoked_certs = if tbs_cert_list.peek(Tag::Sequence.into()) {
                 der::expect_tag_and_get_value_limited(
                     tbs_cert_list,
@@ -384,6 +821,9 @@ impl<'a> FromDer<'a> for BorrowedCertRevocationList<'a> {
                 issuer,
                 revoked_certs,
                 issuing_distribution_point: None,
+                crl_number: None,
+                base_crl_number: None,
+                next_update,
             };
 
             // RFC 5280 §5.1.2.7:
@@ -450,7 +890,9 @@ pub(crate) struct IssuingDistributionPoint<'a> {
 }
 
 impl<'a> IssuingDistributionPoint<'a> {
-    pub(crate) fn from_der(der: untrusted::Input<'a>) -> Result<IssuingDistributionPoint, Error> {
+    pub(crate) fn from_der(
+        der: untrusted::Input<'a>,
+    ) -> Result<IssuingDistributionPoint<'a>, Error> {
         const DISTRIBUTION_POINT_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED;
         const ONLY_CONTAINS_USER_CERTS_TAG: u8 = CONTEXT_SPECIFIC | 1;
         const ONLY_CONTAINS_CA_CERTS_TAG: u8 = CONTEXT_SPECIFIC | 2;
@@ -672,7 +1114,7 @@ pub struct OwnedRevokedCert {
 #[cfg(feature = "alloc")]
 impl OwnedRevokedCert {
     /// Convert the owned representation of this revoked cert to a borrowed version.
-    pub fn borrow(&self) -> BorrowedRevokedCert {
+    pub fn borrow(&self) -> BorrowedRevokedCert<'_> {
         BorrowedRevokedCert {
             serial_number: &self.serial_number,
             revocation_date: self.revocation_date,
@@ -822,7 +1264,7 @@ impl<'a> FromDer<'a> for BorrowedRevokedCert<'a> {
 /// Identifies the reason a certificate was revoked.
 /// See RFC 5280 §5.3.1[^1]
 ///
-/// [^1] <https://www.rfc-editor.org/rfc/rfc5280#section-5.3.1>
+/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5.3.1>
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[allow(missing_docs)] // Not much to add above the code name.
 pub enum RevocationReason {
@@ -836,7 +1278,8 @@ pub enum RevocationReason {
     CessationOfOperation = 5,
     CertificateHold = 6,
     // 7 is not used.
-    /// RemoveFromCrl only appears in delta CRLs that are unsupported.
+    /// RemoveFromCrl only appears in delta CRLs, and removes a certificate listed by the base
+    /// CRL when the delta is merged with [`CertRevocationList::with_delta()`].
     RemoveFromCrl = 8,
     PrivilegeWithdrawn = 9,
     AaCompromise = 10,
@@ -1235,4 +1678,190 @@ mod tests {
             include_bytes!("../../tests/client_auth_revocation/ee_revoked_crl_ku_ee_depth.crl.der");
         assert!(OwnedCertRevocationList::from_der(crl).is_ok())
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn owned_crl_debug_omits_contents() {
+        let der = include_bytes!("../../tests/crls/crl.idp.valid.der");
+        let crl = OwnedCertRevocationList::from_der(der).unwrap();
+        assert_eq!(
+            format!("{:?}", crl),
+            format!(
+                "OwnedCertRevocationList {{ revoked_certs: {}, issuer: [{} bytes], \
+                 issuing_distribution_point: Some([{} bytes]), crl_number: Some([{} bytes]), \
+                 base_crl_number: None, next_update: {:?}, \
+                 signed_data: OwnedSignedData {{ data: [{} bytes], algorithm: [{} bytes], \
+                 signature: [{} bytes] }}, delta_signed_data: [], signature_verified: false }}",
+                crl.revoked_certs.len(),
+                crl.issuer.len(),
+                crl.issuing_distribution_point.as_ref().unwrap().len(),
+                crl.crl_number.as_ref().unwrap().len(),
+                crl.next_update,
+                crl.signed_data.data.len(),
+                crl.signed_data.algorithm.len(),
+                crl.signed_data.signature.len(),
+            )
+        );
+    }
+
+    #[cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+    #[test]
+    fn owned_crl_signature_verified_once() {
+        let issuer = CertificateDer::from(
+            &include_bytes!("../../tests/client_auth_revocation/ku_chain.int.a.ca.der")[..],
+        );
+        let issuer = Cert::try_from(&issuer).unwrap();
+        let other = CertificateDer::from(
+            &include_bytes!("../../tests/client_auth_revocation/ku_chain.int.b.ca.der")[..],
+        );
+        let other = Cert::try_from(&other).unwrap();
+        let crl = OwnedCertRevocationList::from_der(include_bytes!(
+            "../../tests/client_auth_revocation/ee_revoked_crl_ku_ee_depth.crl.der"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            crl.clone()
+                .with_verified_signature(
+                    crate::ALL_VERIFICATION_ALGS,
+                    other.subject_public_key_info()
+                )
+                .unwrap_err(),
+            Error::InvalidCrlSignatureForPublicKey
+        );
+        let crl = CertRevocationList::from(
+            crl.with_verified_signature(
+                crate::ALL_VERIFICATION_ALGS,
+                issuer.subject_public_key_info(),
+            )
+            .unwrap(),
+        );
+        assert!(format!("{:?}", crl).contains("signature_verified: true"));
+
+        // For the issuer's key, nothing is verified again: no algorithm is needed, and no
+        // signature check is consumed.
+        let mut budget = Budget::default();
+        let signatures = budget.signatures;
+        assert_eq!(crl.verify_signature(&[], issuer.spki, &mut budget), Ok(()));
+        assert_eq!(budget.signatures, signatures);
+
+        // Any other key is checked as usual.
+        assert_eq!(
+            crl.verify_signature(&[], other.spki, &mut budget),
+            Err(Error::UnsupportedCrlSignatureAlgorithm)
+        );
+    }
+
+    /// Every field of `crl`, for comparison.
+    #[cfg(feature = "std")]
+    #[allow(clippy::type_complexity)]
+    fn fields(
+        crl: &OwnedCertRevocationList,
+    ) -> (
+        String,
+        &[u8],
+        Option<&[u8]>,
+        Option<&[u8]>,
+        Option<&[u8]>,
+        u64,
+        &[u8],
+        &[u8],
+        &[u8],
+    ) {
+        (
+            format!("{:?}", crl.revoked_certs),
+            &crl.issuer,
+            crl.issuing_distribution_point.as_deref(),
+            crl.crl_number.as_deref(),
+            crl.base_crl_number.as_deref(),
+            crl.next_update.as_secs(),
+            &crl.signed_data.data,
+            &crl.signed_data.algorithm,
+            &crl.signed_data.signature,
+        )
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_reader_matches_from_der() {
+        let mut paths = Vec::new();
+        for dir in ["tests/crls", "tests/client_auth_revocation"] {
+            let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(dir);
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                let name = path.file_name().unwrap().to_str().unwrap();
+                if name.starts_with("crl.") && name.ends_with(".der") || name.ends_with(".crl.der")
+                {
+                    paths.push(path);
+                }
+            }
+        }
+        assert!(paths.len() > 50);
+
+        for path in paths {
+            let der = std::fs::read(&path).unwrap();
+            let expected = OwnedCertRevocationList::from_der(&der);
+            let actual = OwnedCertRevocationList::from_reader(&der[..], CrlLimits::default());
+            assert_eq!(
+                actual.as_ref().map(fields),
+                expected.as_ref().map(fields),
+                "{}",
+                path.display()
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_reader_limits() {
+        // This CRL lists one revoked certificate.
+        let der =
+            include_bytes!("../../tests/client_auth_revocation/ee_revoked_crl_ku_ee_depth.crl.der");
+
+        let limits = CrlLimits::default().with_max_size(der.len());
+        assert!(OwnedCertRevocationList::from_reader(&der[..], limits).is_ok());
+        let limits = CrlLimits::default().with_max_size(der.len() - 1);
+        assert!(matches!(
+            OwnedCertRevocationList::from_reader(&der[..], limits),
+            Err(Error::MaximumCrlSizeExceeded)
+        ));
+
+        let limits = CrlLimits::default().with_max_entries(1);
+        assert!(OwnedCertRevocationList::from_reader(&der[..], limits).is_ok());
+        let limits = CrlLimits::default().with_max_entries(0);
+        assert!(matches!(
+            OwnedCertRevocationList::from_reader(&der[..], limits),
+            Err(Error::MaximumCrlSizeExceeded)
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_reader_reads_only_the_crl() {
+        let der =
+            include_bytes!("../../tests/client_auth_revocation/ee_revoked_crl_ku_ee_depth.crl.der");
+
+        let mut input = der.to_vec();
+        input.extend_from_slice(b"trailing");
+        let mut reader = &input[..];
+        assert!(OwnedCertRevocationList::from_reader(&mut reader, CrlLimits::default()).is_ok());
+        assert_eq!(reader, b"trailing");
+
+        for len in [0, 1, 2, der.len() / 2, der.len() - 1] {
+            assert!(matches!(
+                OwnedCertRevocationList::from_reader(&der[..len], CrlLimits::default()),
+                Err(Error::BadDer)
+            ));
+        }
+    }
+
+    #[cfg(all(feature = "zeroize", feature = "std"))]
+    #[test]
+    fn drop_zeroizes_every_buffer() {
+        let crl = include_bytes!("../../tests/crls/crl.idp.valid.der");
+        let crl = OwnedCertRevocationList::from_der(&crl[..]).unwrap();
+        // Six serial numbers, each held as a key and in its entry, the issuer, the issuing
+        // distribution point, the CRL number, and the three buffers of the signed data.
+        assert_eq!(crate::zeroize::count_zeroized(|| drop(crl)), 18);
+    }
 }
@@ -0,0 +1,303 @@
+//! Options that weaken certificate verification, for applications that let a user accept a
+//! certificate despite an error.
+//!
+//! Nothing here is needed to verify certificates correctly. A browser-like application can
+//! verify with [`AllowedErrors`], show the user `VerifiedPath::suppressed_errors()`, and, if
+//! the user accepts them, remember the end-entity certificate.
+//!
+//! Only errors that concern the validity period of a certificate can be suppressed. Signature,
+//! name constraint, key usage and revocation checks are always enforced, for every certificate
+//! in the path. A path whose issuer is unknown is never a `VerifiedPath`: it is only returned,
+//! as an [`UnanchoredPath`], by [`crate::Verifier::dangerous_verify_allowing_unknown_issuer()`].
+//!
+//! For analyzing a path after the fact, such as one captured from a TLS handshake,
+//! [`crate::Verifier::dangerous_ignore_validity_periods()`] skips the validity period checks
+//! altogether, rather than trying them first.
+//!
+//! For interoperating with signers that can't be fixed, [`BerTolerantEcdsa`] accepts ECDSA
+//! signatures that are BER rather than DER encoded.
+
+use pki_types::{AlgorithmIdentifier, InvalidSignature, SignatureVerificationAlgorithm};
+
+use crate::error::Error;
+use crate::signed_data::alg_id;
+pub use crate::verify_cert::UnanchoredPath;
+use crate::verify_cert::VerifiedPath;
+
+/// The errors that verification may suppress, as passed to
+/// [`crate::Verifier::dangerous_allow_errors()`]. None are by default.
+///
+/// A path that needs no errors suppressed is always preferred: path building is only tried
+/// again with the suppressed errors allowed if verification fails without them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllowedErrors {
+    cert_expired: bool,
+    cert_not_valid_yet: bool,
+}
+
+impl AllowedErrors {
+    /// Suppresses no errors.
+    pub const fn new() -> Self {
+        Self {
+            cert_expired: false,
+            cert_not_valid_yet: false,
+        }
+    }
+
+    /// Suppresses [`Error::CertExpired`] for any certificate in the path.
+    pub const fn cert_expired(mut self) -> Self {
+        self.cert_expired = true;
+        self
+    }
+
+    /// Suppresses [`Error::CertNotValidYet`] for any certificate in the path.
+    pub const fn cert_not_valid_yet(mut self) -> Self {
+        self.cert_not_valid_yet = true;
+        self
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        *self == Self::new()
+    }
+
+    pub(crate) fn allows(&self, err: Error) -> bool {
+        match err {
+            Error::CertExpired { .. } => self.cert_expired,
+            Error::CertNotValidYet { .. } => self.cert_not_valid_yet,
+            _ => false,
+        }
+    }
+}
+
+/// A path accepted by [`crate::Verifier::dangerous_verify_allowing_unknown_issuer()`].
+pub enum AcceptedPath<'p> {
+    /// A path ending at one of the trust anchors, as [`crate::Verifier::verify()`] returns.
+    Verified(VerifiedPath<'p>),
+    /// A path ending at a certificate for which no issuer was found.
+    UnknownIssuer(UnanchoredPath<'p>),
+}
+
+/// An ECDSA `SignatureVerificationAlgorithm` that also accepts signatures whose Ecdsa-Sig-Value
+/// is BER rather than DER encoded, as some HSMs produce. **Off unless used**, and only for
+/// signers that can't be fixed.
+///
+/// RFC 5480 requires the DER encoding, which the algorithms of this crate insist on. This
+/// tolerates two deviations in it, re-encoding the signature as DER for `inner`:
+///
+/// * INTEGERs with unnecessary leading zero octets, and
+/// * lengths, of the SEQUENCE or of either INTEGER, in a longer form than needed.
+///
+/// Anything else that isn't DER is still rejected, including indefinite lengths, data after
+/// either INTEGER or after the SEQUENCE, negative values, and values longer than the largest
+/// curve order. Whether `r` and `s` are in range for the curve is checked by `inner`. Only
+/// the signature value is relaxed: the signed data, such as a certificate's
+/// `tbsCertificate`, is parsed as strictly as ever.
+///
+/// It is used in place of `inner` in the list of supported algorithms:
+///
+/// ```
+/// # #[cfg(feature = "ring")]
+/// # {
+/// use webpki::dangerous::BerTolerantEcdsa;
+///
+/// static P256_SHA256: BerTolerantEcdsa<'static> = BerTolerantEcdsa::new(
+///     webpki::ring::ECDSA_P256_SHA256,
+/// );
+/// let supported_sig_algs: &[&dyn pki_types::SignatureVerificationAlgorithm] = &[&P256_SHA256];
+/// # }
+/// ```
+///
+/// For an `inner` algorithm whose public keys are not ECDSA keys, signatures are passed on
+/// unchanged.
+#[derive(Clone, Copy, Debug)]
+pub struct BerTolerantEcdsa<'a> {
+    inner: &'a dyn SignatureVerificationAlgorithm,
+}
+
+impl<'a> BerTolerantEcdsa<'a> {
+    /// Accepts the BER encodings described above of signatures verified by `inner`, such as
+    /// `webpki::ring::ECDSA_P256_SHA256`.
+    pub const fn new(inner: &'a dyn SignatureVerificationAlgorithm) -> Self {
+        Self { inner }
+    }
+}
+
+impl SignatureVerificationAlgorithm for BerTolerantEcdsa<'_> {
+    fn verify_signature(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), InvalidSignature> {
+        let public_key_alg_id = self.inner.public_key_alg_id();
+        if ![alg_id::ECDSA_P256, alg_id::ECDSA_P384, alg_id::ECDSA_P521]
+            .iter()
+            .any(|alg_id| alg_id.as_ref() == public_key_alg_id.as_ref())
+        {
+            return self.inner.verify_signature(public_key, message, signature);
+        }
+
+        let mut der = [0; MAX_ECDSA_SIG_LEN];
+        let len = ecdsa_sig_value_to_der(signature, &mut der).ok_or(InvalidSignature)?;
+        self.inner
+            .verify_signature(public_key, message, &der[..len])
+    }
+
+    fn public_key_alg_id(&self) -> AlgorithmIdentifier {
+        self.inner.public_key_alg_id()
+    }
+
+    fn signature_alg_id(&self) -> AlgorithmIdentifier {
+        self.inner.signature_alg_id()
+    }
+}
+
+/// Re-encodes the BER `Ecdsa-Sig-Value ::= SEQUENCE { r INTEGER, s INTEGER }` of `signature`
+/// into `out` as DER, returning its length, or `None` if it is not such a SEQUENCE with the
+/// deviations tolerated by [`BerTolerantEcdsa`].
+fn ecdsa_sig_value_to_der(signature: &[u8], out: &mut [u8; MAX_ECDSA_SIG_LEN]) -> Option<usize> {
+    let (sequence, rest) = ber_tlv(signature, 0x30)?;
+    if !rest.is_empty() {
+        return None;
+    }
+    let (r, rest) = ber_tlv(sequence, 0x02)?;
+    let (s, rest) = ber_tlv(rest, 0x02)?;
+    if !rest.is_empty() {
+        return None;
+    }
+
+    // Each INTEGER is the minimal, positive, encoding of its value.
+    let mut integers = [[0; MAX_SCALAR_LEN + 3]; 2];
+    let mut lens = [0; 2];
+    for ((value, integer), len) in [r, s].iter().zip(&mut integers).zip(&mut lens) {
+        let value = match value.iter().position(|&byte| byte != 0) {
+            Some(first) if value[0] & 0x80 == 0 => &value[first..],
+            // A negative value, or zero.
+            _ => return None,
+        };
+        if value.len() > MAX_SCALAR_LEN {
+            return None;
+        }
+        let pad = usize::from(value[0] & 0x80 != 0);
+        integer[0] = 0x02;
+        integer[1] = u8::try_from(pad + value.len()).ok()?;
+        integer[2 + pad..2 + pad + value.len()].copy_from_slice(value);
+        *len = 2 + pad + value.len();
+    }
+
+    let content_len = lens[0] + lens[1];
+    let header: &[u8] = match u8::try_from(content_len).ok()? {
+        len @ 0..=0x7f => &[0x30, len],
+        len => &[0x30, 0x81, len],
+    };
+    let mut len = 0;
+    for part in [header, &integers[0][..lens[0]], &integers[1][..lens[1]]] {
+        out[len..len + part.len()].copy_from_slice(part);
+        len += part.len();
+    }
+    Some(len)
+}
+
+/// Splits the BER encoding of a value with tag `tag` and a definite length off the front of
+/// `input`, returning its contents and what follows it.
+fn ber_tlv(input: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    let (&actual, rest) = input.split_first()?;
+    if actual != tag {
+        return None;
+    }
+    let (&first, mut rest) = rest.split_first()?;
+    let len = match first {
+        0..=0x7f => usize::from(first),
+        // Long form, possibly with leading zero octets, but not indefinite.
+        0x81..=0x84 => {
+            let len_len = usize::from(first & 0x7f);
+            if rest.len() < len_len {
+                return None;
+            }
+            let (octets, after) = rest.split_at(len_len);
+            rest = after;
+            octets.iter().try_fold(0usize, |len, &octet| {
+                len.checked_mul(0x100)?.checked_add(usize::from(octet))
+            })?
+        }
+        _ => return None,
+    };
+    match len <= rest.len() {
+        true => Some(rest.split_at(len)),
+        false => None,
+    }
+}
+
+/// The length of the largest curve order supported, that of P-521.
+const MAX_SCALAR_LEN: usize = 66;
+
+/// The length of the largest DER-encoded Ecdsa-Sig-Value: a SEQUENCE with a two-octet length,
+/// of two INTEGERs each with a sign octet.
+const MAX_ECDSA_SIG_LEN: usize = 3 + 2 * (MAX_SCALAR_LEN + 3);
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    fn to_der(signature: &[u8]) -> Option<Vec<u8>> {
+        let mut out = [0; MAX_ECDSA_SIG_LEN];
+        ecdsa_sig_value_to_der(signature, &mut out).map(|len| out[..len].to_vec())
+    }
+
+    #[test]
+    fn ecdsa_sig_value_to_der_tolerates() {
+        let der = [0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80];
+        assert_eq!(to_der(&der).as_deref(), Some(&der[..]));
+
+        for ber in [
+            // Unnecessary leading zeros.
+            &[
+                0x30, 0x09, 0x02, 0x02, 0x00, 0x01, 0x02, 0x03, 0x00, 0x00, 0x80,
+            ][..],
+            // Long form lengths.
+            &[0x30, 0x81, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80],
+            &[
+                0x30, 0x09, 0x02, 0x82, 0x00, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80,
+            ],
+        ] {
+            assert_eq!(to_der(ber).as_deref(), Some(&der[..]), "{ber:02x?}");
+        }
+
+        // Values as long as the order of P-521 need a long form SEQUENCE length.
+        let mut long = vec![0x30, 0x81, 0x88, 0x02, 0x42, 0x01];
+        long.extend([0xff; 65]);
+        long.extend([0x02, 0x42, 0x00, 0x80]);
+        long.extend([0x00; 64]);
+        assert_eq!(to_der(&long), Some(long.clone()));
+    }
+
+    #[test]
+    fn ecdsa_sig_value_to_der_rejects() {
+        for ber in [
+            // Trailing data, after s and after the SEQUENCE.
+            &[0x30, 0x08, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00, 0x00][..],
+            &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00],
+            // Negative and zero values.
+            &[0x30, 0x06, 0x02, 0x01, 0x81, 0x02, 0x01, 0x01],
+            &[0x30, 0x06, 0x02, 0x01, 0x00, 0x02, 0x01, 0x01],
+            &[0x30, 0x05, 0x02, 0x00, 0x02, 0x01, 0x01],
+            // An indefinite length.
+            &[0x30, 0x80, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00, 0x00],
+            // A truncated long form length.
+            &[0x30, 0x82, 0x00],
+            // A missing INTEGER, and another tag.
+            &[0x30, 0x03, 0x02, 0x01, 0x01],
+            &[0x30, 0x06, 0x02, 0x01, 0x01, 0x04, 0x01, 0x01],
+        ] {
+            assert_eq!(to_der(ber), None, "{ber:02x?}");
+        }
+
+        // Longer than the order of P-521.
+        let mut long = vec![0x30, 0x81, 0x49, 0x02, 0x44];
+        long.extend([0x01; 68]);
+        long.extend([0x02, 0x01, 0x01]);
+        assert_eq!(to_der(&long), None);
+    }
+}
@@ -14,6 +14,9 @@
 
 use core::marker::PhantomData;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use crate::{error::DerTypeId, Error};
 
 #[derive(Debug)]
@@ -60,15 +63,24 @@ pub(crate) enum Tag {
     Integer = 0x02,
     BitString = 0x03,
     OctetString = 0x04,
+    Null = 0x05,
     OID = 0x06,
     Enum = 0x0A,
     Sequence = CONSTRUCTED | 0x10, // 0x30
+    Set = CONSTRUCTED | 0x11,      // 0x31
     UTCTime = 0x17,
     GeneralizedTime = 0x18,
 
+    #[allow(clippy::identity_op)]
+    ContextSpecific0 = CONTEXT_SPECIFIC | 0,
+    ContextSpecific1 = CONTEXT_SPECIFIC | 1,
+    ContextSpecific2 = CONTEXT_SPECIFIC | 2,
+
     #[allow(clippy::identity_op)]
     ContextSpecificConstructed0 = CONTEXT_SPECIFIC | CONSTRUCTED | 0,
     ContextSpecificConstructed1 = CONTEXT_SPECIFIC | CONSTRUCTED | 1,
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    ContextSpecificConstructed2 = CONTEXT_SPECIFIC | CONSTRUCTED | 2,
     ContextSpecificConstructed3 = CONTEXT_SPECIFIC | CONSTRUCTED | 3,
 }
 
@@ -110,7 +122,11 @@ pub(crate) fn nested_limited<'a, R>(
     size_limit: usize,
 ) -> Result<R, Error> {
     expect_tag_and_get_value_limited(input, tag, size_limit)
-        .map_err(|_| error)?
+        .map_err(|err| match err {
+            // Preserve the more precise diagnosis for BER-only constructs.
+            Error::BerEncodingNotAllowed { .. } => err,
+            _ => error,
+        })?
         .read_all(error, decoder)
 }
 
@@ -153,12 +169,20 @@ pub(crate) fn read_tag_and_get_value_limited<'a>(
     if (tag & HIGH_TAG_RANGE_START) == HIGH_TAG_RANGE_START {
         return Err(Error::BadDer); // High tag number form is not allowed.
     }
+    if is_constructed_string(tag) {
+        // DER requires the primitive form.
+        return Err(Error::BerEncodingNotAllowed { offset: 0 });
+    }
 
     // If the high order bit of the first byte is set to zero then the length
     // is encoded in the seven remaining bits of that byte. Otherwise, those
     // seven bits represent the number of bytes used to encode the length.
     let length = match input.read_byte().map_err(end_of_input_err)? {
         n if (n & SHORT_FORM_LEN_MAX) == 0 => usize::from(n),
+        LONG_FORM_LEN_INDEFINITE => {
+            // DER requires a definite length.
+            return Err(Error::BerEncodingNotAllowed { offset: 1 });
+        }
         LONG_FORM_LEN_ONE_BYTE => {
             let length_byte = input.read_byte().map_err(end_of_input_err)?;
             if length_byte < SHORT_FORM_LEN_MAX {
@@ -212,6 +236,24 @@ pub(crate) fn read_tag_and_get_value_limited<'a>(
     Ok((tag, inner))
 }
 
+// BER permits the constructed form for string types, splitting the value into segments that
+// must be concatenated. DER (ITU X690-0207 section 10.2) requires the primitive form.
+fn is_constructed_string(tag: u8) -> bool {
+    // Universal class tags with the constructed bit set.
+    const BIT_STRING: u8 = CONSTRUCTED | 0x03;
+    const OCTET_STRING: u8 = CONSTRUCTED | 0x04;
+    const UTF8_STRING: u8 = CONSTRUCTED | 0x0c;
+    const NUMERIC_STRING_TO_GENERAL_STRING: core::ops::RangeInclusive<u8> =
+        CONSTRUCTED | 0x12..=CONSTRUCTED | 0x1b;
+    const UNIVERSAL_STRING: u8 = CONSTRUCTED | 0x1c;
+    const BMP_STRING: u8 = CONSTRUCTED | 0x1e;
+
+    matches!(
+        tag,
+        BIT_STRING | OCTET_STRING | UTF8_STRING | UNIVERSAL_STRING | BMP_STRING
+    ) || NUMERIC_STRING_TO_GENERAL_STRING.contains(&tag)
+}
+
 // Long-form DER encoded lengths of two bytes can express lengths up to the following limit.
 //
 // The upstream ring::io::der::read_tag_and_get_value() function limits itself to up to two byte
@@ -236,6 +278,10 @@ const HIGH_TAG_RANGE_START: u8 = 31;
 // * Long definite form: 2 to 127 octets, number of octets encoded into first octet.
 const SHORT_FORM_LEN_MAX: u8 = 128;
 
+// Length octet used by BER to indicate an indefinite length, terminated by an end-of-contents
+// marker. Not permitted in DER.
+const LONG_FORM_LEN_INDEFINITE: u8 = 0x80;
+
 // Leading octet for long definite form DER length expressed in second byte.
 const LONG_FORM_LEN_ONE_BYTE: u8 = 0x81;
 
@@ -321,7 +367,7 @@ impl<'a> BitStringFlags<'a> {
 //
 // [0]: https://security.stackexchange.com/a/10396
 // [1]: https://www.itu.int/ITU-T/studygroups/com17/languages/X.690-0207.pdf
-pub(crate) fn bit_string_flags(input: untrusted::Input) -> Result<BitStringFlags<'_>, Error> {
+pub(crate) fn bit_string_flags(input: untrusted::Input<'_>) -> Result<BitStringFlags<'_>, Error> {
     input.read_all(Error::BadDer, |bit_string| {
         // ITU X690-0207 11.2:
         //   "The initial octet shall encode, as an unsigned binary integer with bit 1 as the least
@@ -338,7 +384,10 @@ pub(crate) fn bit_string_flags(input: untrusted::Input) -> Result<BitStringFlags
 
         // If there are padding bits then the last bit of the last raw byte must be 0 or the
         // distinguished encoding rules are not being followed.
-        let last_byte = raw_bits[raw_bits.len() - 1];
+        let last_byte = match raw_bits.last() {
+            Some(last_byte) => *last_byte,
+            None => return Ok(BitStringFlags { raw_bits }),
+        };
         let padding_mask = (1 << padding_bits) - 1;
 
         match padding_bits > 0 && (last_byte & padding_mask) != 0 {
@@ -390,6 +439,24 @@ pub(crate) fn end_of_input_err(_: untrusted::EndOfInput) -> Error {
     Error::BadDer
 }
 
+/// Append the DER encoding of `value`, with `tag`, to `out`.
+#[cfg(feature = "alloc")]
+pub(crate) fn push_tlv(out: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    // Certificates are limited to 2^16 bytes when parsed, so the contents of any of their
+    // fields are shorter than that, as is anything webpki encodes from them.
+    let len = u16::try_from(value.len()).unwrap();
+    out.push(tag);
+    match u8::try_from(len) {
+        Ok(len) if len < 0x80 => out.push(len),
+        Ok(len) => out.extend_from_slice(&[0x81, len]),
+        Err(_) => {
+            out.push(0x82);
+            out.extend_from_slice(&len.to_be_bytes());
+        }
+    }
+    out.extend_from_slice(value);
+}
+
 // Like mozilla::pkix, we accept the nonconformant explicit encoding of
 // the default value (false) for compatibility with real-world certificates.
 impl<'a> FromDer<'a> for bool {
@@ -485,8 +552,8 @@ mod tests {
         );
     }
 
-    fn bytes_reader(bytes: &[u8]) -> untrusted::Reader {
-        return untrusted::Reader::new(untrusted::Input::from(bytes));
+    fn bytes_reader(bytes: &[u8]) -> untrusted::Reader<'_> {
+        untrusted::Reader::new(untrusted::Input::from(bytes))
     }
 
     #[test]
@@ -612,6 +679,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn read_tag_and_get_value_ber_constructs() {
+        use super::{read_tag_and_get_value, Error};
+
+        let inputs: &[(&[u8], usize)] = &[
+            // SEQUENCE with indefinite length, terminated by end-of-contents octets.
+            (&[EXAMPLE_TAG, 0x80, 0x02, 0x01, 0x00, 0x00, 0x00], 1),
+            // Constructed OCTET STRING made of two primitive segments.
+            (&[0x24, 0x06, 0x04, 0x01, 0xaa, 0x04, 0x01, 0xbb], 0),
+            // Constructed BIT STRING made of one primitive segment.
+            (&[0x23, 0x04, 0x03, 0x02, 0x00, 0xaa], 0),
+            // Constructed PrintableString made of one primitive segment.
+            (&[0x33, 0x03, 0x13, 0x01, 0x61], 0),
+        ];
+
+        for &(input, offset) in inputs {
+            let mut bytes = untrusted::Reader::new(untrusted::Input::from(input));
+            assert_eq!(
+                read_tag_and_get_value(&mut bytes).err(),
+                Some(Error::BerEncodingNotAllowed { offset })
+            );
+        }
+    }
+
+    #[test]
+    fn nested_preserves_ber_error() {
+        use super::{nested, Error, Tag};
+
+        let mut bytes = bytes_reader(&[EXAMPLE_TAG, 0x80, 0x00, 0x00]);
+        assert_eq!(
+            nested(&mut bytes, Tag::Sequence, Error::BadDer, |_| Ok(())),
+            Err(Error::BerEncodingNotAllowed { offset: 1 })
+        );
+    }
+
     #[allow(clippy::as_conversions)] // infallible.
     const EXAMPLE_TAG: u8 = super::Tag::Sequence as u8;
 
@@ -684,6 +786,18 @@ mod tests {
         assert!(!res.bit_set(256));
     }
 
+    #[test]
+    fn empty_bit_string_flags() {
+        use super::bit_string_flags;
+
+        let empty = untrusted::Input::from(&[
+            0x00, // No padding.
+                 // No flags value.
+        ]);
+        let res = bit_string_flags(empty).unwrap();
+        assert!(!res.bit_set(0));
+    }
+
     #[test]
     fn test_small_nonnegative_integer() {
         use super::{Error, FromDer, Tag};
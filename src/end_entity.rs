@@ -12,17 +12,42 @@
 // ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
 // OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
+use core::cell::Cell;
 use core::ops::Deref;
+use core::time::Duration;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 use pki_types::{
     CertificateDer, ServerName, SignatureVerificationAlgorithm, TrustAnchor, UnixTime,
 };
 
 use crate::crl::RevocationOptions;
-use crate::error::Error;
-use crate::subject_name::{verify_dns_names, verify_ip_address_names, NameIterator};
-use crate::verify_cert::{self, KeyUsage, VerifiedPath};
-use crate::{cert, signed_data};
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+use crate::crl::UnknownStatusPolicy;
+use crate::dangerous::{AcceptedPath, AllowedErrors};
+use crate::error::{DerTypeId, Error};
+#[cfg(feature = "alloc")]
+use crate::lint::{self, SanLint};
+#[cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+use crate::ocsp::OcspHash;
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+use crate::ocsp::{self, OcspCertStatus};
+use crate::subject_name::{
+    dns_names_cover_pattern, verify_dns_names, verify_email_addresses, verify_ip_address_names,
+    NameIterator, SubjectAltName,
+};
+#[cfg(feature = "alloc")]
+use crate::subject_name::{presented_names, InvalidNameContext};
+use crate::trust_anchor::{Anchors, TrustAnchorSource};
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+use crate::verify_cert::FailedCert;
+use crate::verify_cert::{
+    self, Budget, DetailedError, InspectionHook, KeyUsage, SuppliedIntermediates,
+    VerificationPolicy, VerifiedPath,
+};
+use crate::{cert, der, signed_data};
 
 /// An end-entity certificate.
 ///
@@ -64,9 +89,13 @@ impl<'a> TryFrom<&'a CertificateDer<'a>> for EndEntityCert<'a> {
 
     /// Parse the ASN.1 DER-encoded X.509 encoding of the certificate
     /// `cert_der`.
+    ///
+    /// A v1 certificate is parsed, but only verifies if
+    /// [`Verifier::allow_v1_certificates()`] is given; other versions than v1 and v3 fail with
+    /// [`Error::UnsupportedCertVersion`].
     fn try_from(cert: &'a CertificateDer<'a>) -> Result<Self, Self::Error> {
         Ok(Self {
-            inner: cert::Cert::from_der(untrusted::Input::from(cert.as_ref()))?,
+            inner: cert::Cert::from_der_allowing_v1(untrusted::Input::from(cert.as_ref()))?,
         })
     }
 }
@@ -78,7 +107,11 @@ impl<'a> EndEntityCert<'a> {
     /// * `supported_sig_algs` is the list of signature algorithms that are
     ///   trusted for use in certificate signatures; the end-entity certificate's
     ///   public key is not validated against this list.
-    /// * `trust_anchors` is the list of root CAs to trust in the built path.
+    /// * `trust_anchors` is the list of root CAs to trust in the built path. For each
+    ///   certificate in a candidate path, the trust anchors are tried as its issuer in the
+    ///   order given, before any intermediate certificate is. So the path terminates at the
+    ///   nearest trust anchor that issued it and, among those, the earliest in
+    ///   `trust_anchors`.
     /// * `intermediate_certs` is the sequence of intermediate certificates that
     ///   a peer sent for the purpose of path building.
     /// * `time` is the time for which the validation is effective (usually the
@@ -97,6 +130,9 @@ impl<'a> EndEntityCert<'a> {
     /// verify a path that doesn't satisfy the constraints listed above; it can only be used to
     /// reject a path that does satisfy the aforementioned constraints. If `verify_path` returns
     /// an error, path building will continue in order to try other options.
+    ///
+    /// [`EndEntityCert::verifier()`] builds the same verification without positional
+    /// arguments, and supports further options.
     #[allow(clippy::too_many_arguments)]
     pub fn verify_for_usage<'p>(
         &'p self,
@@ -108,17 +144,127 @@ impl<'a> EndEntityCert<'a> {
         revocation: Option<RevocationOptions<'_>>,
         verify_path: Option<&dyn Fn(&VerifiedPath<'_>) -> Result<(), Error>>,
     ) -> Result<VerifiedPath<'p>, Error> {
-        verify_cert::ChainOptions {
-            eku: usage,
+        Verifier {
+            revocation,
+            verify_path,
+            ..self
+                .verifier(supported_sig_algs, trust_anchors)
+                .intermediates(intermediate_certs)
+                .at_time(time)
+                .key_usage(usage)
+        }
+        .verify()
+    }
+
+    /// Starts building a verification of this certificate, like
+    /// [`EndEntityCert::verify_for_usage()`], with paths built to `trust_anchors` and
+    /// certificate signatures verified using `supported_sig_algs`.
+    ///
+    /// Options are set on the returned [`Verifier`], and [`Verifier::verify()`] performs the
+    /// verification:
+    ///
+    /// ```
+    /// # use webpki::types::{CertificateDer, SignatureVerificationAlgorithm, TrustAnchor, UnixTime};
+    /// # use webpki::{EndEntityCert, Error, KeyUsage};
+    /// # fn verify(
+    /// #     cert: &EndEntityCert<'_>,
+    /// #     supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+    /// #     trust_anchors: &[TrustAnchor<'_>],
+    /// #     intermediates: &[CertificateDer<'_>],
+    /// #     time: UnixTime,
+    /// # ) -> Result<(), Error> {
+    /// let path = cert
+    ///     .verifier(supported_sig_algs, trust_anchors)
+    ///     .intermediates(intermediates)
+    ///     .at_time(time)
+    ///     .key_usage(KeyUsage::client_auth())
+    ///     .verify()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verifier<'v, 'p>(
+        &'p self,
+        supported_sig_algs: &'v [&'v dyn SignatureVerificationAlgorithm],
+        trust_anchors: &'p [TrustAnchor<'p>],
+    ) -> Verifier<'v, 'p> {
+        self.verifier_for(supported_sig_algs, Anchors::Slice(trust_anchors))
+    }
+
+    /// Returns a [`Verifier`] as [`EndEntityCert::verifier()`] does, with paths built to the
+    /// trust anchors that `trust_anchors` returns for each issuer.
+    ///
+    /// This avoids scanning every trust anchor for every certificate in the path when there
+    /// are many of them, since the source can be indexed by subject:
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use webpki::types::TrustAnchor;
+    /// # use webpki::TrustAnchorSource;
+    /// struct BySubject<'a>(HashMap<Vec<u8>, Vec<TrustAnchor<'a>>>);
+    ///
+    /// impl TrustAnchorSource for BySubject<'_> {
+    ///     fn anchors_for_subject(&self, subject: &[u8]) -> &[TrustAnchor<'_>] {
+    ///         self.0.get(subject).map_or(&[], |anchors| anchors)
+    ///     }
+    /// }
+    /// ```
+    pub fn verifier_with_anchor_source<'v, 'p>(
+        &'p self,
+        supported_sig_algs: &'v [&'v dyn SignatureVerificationAlgorithm],
+        trust_anchors: &'p dyn TrustAnchorSource,
+    ) -> Verifier<'v, 'p> {
+        self.verifier_for(supported_sig_algs, Anchors::Source(trust_anchors))
+    }
+
+    fn verifier_for<'v, 'p>(
+        &'p self,
+        supported_sig_algs: &'v [&'v dyn SignatureVerificationAlgorithm],
+        trust_anchors: Anchors<'p>,
+    ) -> Verifier<'v, 'p> {
+        Verifier {
+            end_entity: self,
             supported_sig_algs,
             trust_anchors,
-            intermediate_certs,
-            revocation,
+            intermediate_certs: SuppliedIntermediates::Der(&[]),
+            time: None,
+            usage: KeyUsage::server_auth(),
+            revocation: None,
+            policy: VerificationPolicy::default(),
+            verify_path: None,
+            anchor_filter: None,
+            hook: None,
+            max_path_length: verify_cert::MAX_SUB_CA_COUNT,
+            budget: Budget::default(),
+            clock_skew: Duration::ZERO,
+            freshness_time: None,
+            required_policies: &[],
+            ignored_extensions: &[],
+            allowed_errors: AllowedErrors::new(),
+            ignore_validity_periods: false,
+            allow_v1: false,
+            #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+            ocsp_response: None,
+            #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+            ocsp_responses: None,
         }
-        .build_chain(self, time, verify_path)
     }
 
     /// Verifies that the certificate is valid for the given Subject Name.
+    ///
+    /// An IP address is compared byte-wise against the certificate's iPAddress subject
+    /// alternative names only, never against its dNSNames or subject common name. An address
+    /// that is already parsed, such as a `std::net::IpAddr`, can be converted to a
+    /// `ServerName::IpAddress` without formatting it as text.
+    ///
+    /// A dNSName that is not a syntactically valid DNS name, such as one containing a NUL or a
+    /// label starting with a hyphen, never matches. If no name matches a DNS name and there
+    /// was such a dNSName, the error is [`Error::MalformedDnsIdentifier`] rather than
+    /// [`Error::CertNotValidForName`], telling a malformed certificate from one for another
+    /// name.
+    ///
+    /// A v1 certificate has no subject alternative names, so a DNS name is instead compared
+    /// against each commonName in its subject, which must be exactly that name, ignoring
+    /// ASCII case. See [`Verifier::allow_v1_certificates()`].
     pub fn verify_is_valid_for_subject_name(
         &self,
         server_name: &ServerName<'_>,
@@ -127,6 +273,10 @@ impl<'a> EndEntityCert<'a> {
             ServerName::DnsName(dns_name) => verify_dns_names(
                 dns_name,
                 NameIterator::new(Some(self.inner.subject), self.inner.subject_alt_name),
+                match self.inner.v1 {
+                    true => Some(self.inner.subject),
+                    false => None,
+                },
             ),
             // IP addresses are not compared against the subject field;
             // only against Subject Alternative Names.
@@ -138,12 +288,128 @@ impl<'a> EndEntityCert<'a> {
         }
     }
 
+    /// Like [`EndEntityCert::verify_is_valid_for_subject_name()`], but on failure also
+    /// returns the names the certificate presented, so that a caller can explain why it was
+    /// rejected, such as by saying that it is only valid for `*.example.org`.
+    #[cfg(feature = "alloc")]
+    pub fn verify_is_valid_for_subject_name_verbose(
+        &self,
+        server_name: &ServerName<'_>,
+    ) -> Result<(), InvalidNameContext> {
+        self.verify_is_valid_for_subject_name(server_name)
+            .map_err(|error| InvalidNameContext {
+                error,
+                expected: server_name.to_owned(),
+                presented: presented_names(self.inner.subject_alt_name),
+            })
+    }
+
+    /// Verifies that the certificate is valid for the given email address, such as the
+    /// sender of an S/MIME message.
+    ///
+    /// The address is compared against the certificate's rfc822Name subject alternative
+    /// names: the local part exactly, and the host case-insensitively. The subject's
+    /// emailAddress attributes are not considered; see
+    /// [`EndEntityCert::verify_is_valid_for_legacy_email()`]. An address that is not ASCII,
+    /// or lacks a local part or host, fails with `Error::MalformedEmailIdentifier`.
+    pub fn verify_is_valid_for_email(&self, email: &str) -> Result<(), Error> {
+        verify_email_addresses(
+            email,
+            NameIterator::new(None, self.inner.subject_alt_name),
+            None,
+        )
+    }
+
+    /// Like [`EndEntityCert::verify_is_valid_for_email()`], but for a certificate with no
+    /// rfc822Name subject alternative names, also accepts a matching emailAddress attribute
+    /// in its subject.
+    ///
+    /// RFC 5280 requires new certificates to carry email addresses in rfc822Name subject
+    /// alternative names, so this is only for legacy certificates.
+    pub fn verify_is_valid_for_legacy_email(&self, email: &str) -> Result<(), Error> {
+        verify_email_addresses(
+            email,
+            NameIterator::new(None, self.inner.subject_alt_name),
+            Some(self.inner.subject),
+        )
+    }
+
+    /// Returns the problems with the names in the certificate's subjectAltName extension, for
+    /// compliance tooling that logs misissued certificates. See [`SanLint`] for details.
+    ///
+    /// Names after a malformed one are not checked. The result is empty if the certificate has
+    /// no subjectAltName extension.
+    #[cfg(feature = "alloc")]
+    pub fn lint_subject_alt_names(&self) -> Vec<SanLint<'a>> {
+        lint::check_subject_alt_names(&self.inner)
+    }
+
+    /// Returns the first Microsoft user principal name (UPN) in the certificate's
+    /// subjectAltName extension, such as the user identity of an Active Directory smartcard
+    /// logon certificate, or `None` if it has none.
+    ///
+    /// A UPN is an otherName of type 1.3.6.1.4.1.311.20.2.3 whose value is a UTF8String.
+    /// Fails with [`Error::MalformedOtherNameInSan`] if it is not, and with the error yielded
+    /// by `Cert::subject_alternative_names()` if the extension is malformed before the UPN.
+    ///
+    /// The UPN is only read, not verified against a reference name.
+    pub fn microsoft_upn(&self) -> Result<Option<&'a str>, Error> {
+        // 1.3.6.1.4.1.311.20.2.3
+        const UPN: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x14, 0x02, 0x03];
+        const UTF8_STRING: u8 = 0x0c;
+
+        for name in self.inner.subject_alternative_names() {
+            let value = match name? {
+                SubjectAltName::OtherName { type_id, value } if type_id == UPN => value,
+                _ => continue,
+            };
+            let upn = untrusted::Input::from(value).read_all(
+                Error::MalformedOtherNameInSan,
+                |reader| match der::read_tag_and_get_value(reader)? {
+                    (UTF8_STRING, upn) => Ok(upn.as_slice_less_safe()),
+                    _ => Err(Error::MalformedOtherNameInSan),
+                },
+            )?;
+            return core::str::from_utf8(upn)
+                .map(Some)
+                .map_err(|_| Error::MalformedOtherNameInSan);
+        }
+        Ok(None)
+    }
+
+    /// Returns whether the certificate's dNSName subject alternative names cover every name
+    /// matched by `pattern`, a DNS name that may have a wildcard left-most label.
+    ///
+    /// A pattern without a wildcard is covered exactly when
+    /// [`EndEntityCert::verify_is_valid_for_subject_name()`] would accept it as a name. A
+    /// pattern such as `*.example.com` is only covered by a `*.example.com` name (compared
+    /// case-insensitively): not by any concrete name such as `www.example.com`, and not by
+    /// `*.com`, since a wildcard label never matches across a label boundary.
+    ///
+    /// Patterns are held to the same syntax as wildcard names in certificates, failing with
+    /// `Error::MalformedDnsIdentifier` otherwise: the wildcard must be the whole left-most
+    /// label, so `f*.example.com` and `www.*.example.com` are rejected, and it must be
+    /// followed by at least two labels, so `*.com` is rejected. Beyond that, whether a
+    /// pattern is too broad, such as `*.co.uk`, is for the caller to decide: this crate has
+    /// no knowledge of public suffixes.
+    pub fn covers_dns_pattern(&self, pattern: &str) -> Result<bool, Error> {
+        dns_names_cover_pattern(
+            pattern.as_bytes(),
+            NameIterator::new(None, self.inner.subject_alt_name),
+        )
+    }
+
     /// Verifies the signature `signature` of message `msg` using the
     /// certificate's public key.
     ///
     /// `signature_alg` is the algorithm to use to
     /// verify the signature; the certificate's public key is verified to be
-    /// compatible with this algorithm.
+    /// compatible with this algorithm, failing with
+    /// `Error::UnsupportedSignatureAlgorithmForPublicKey` otherwise. A signature
+    /// that does not verify fails with `Error::InvalidSignatureForPublicKey`.
+    ///
+    /// Only the public key is used: the certificate is not checked to chain to
+    /// a trust anchor, or to be valid at any time.
     ///
     /// For TLS 1.2, `signature` corresponds to TLS's
     /// `DigitallySigned.signature` and `signature_alg` corresponds to TLS's
@@ -171,6 +437,619 @@ impl<'a> EndEntityCert<'a> {
             untrusted::Input::from(signature),
         )
     }
+
+    /// Verifies `response`, a DER-encoded OCSP response such as one stapled to a TLS handshake,
+    /// and returns the status it gives for this certificate.
+    ///
+    /// `issuer` must be the certificate that issued this one. The response must be a basic
+    /// OCSP response, signed either by `issuer` or by a delegated responder certificate that
+    /// `issuer` issued for the id-kp-OCSPSigning extended key usage, included in the response,
+    /// and valid at `time`. It must also be current at `time`. See RFC 6960[^1].
+    ///
+    /// This does not verify this certificate or `issuer`: that is done by
+    /// [`EndEntityCert::verify_for_usage()`].
+    ///
+    /// [^1]: <https://www.rfc-editor.org/rfc/rfc6960>
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub fn verify_ocsp_stapled_response(
+        &self,
+        issuer: &cert::Cert<'_>,
+        response: &[u8],
+        time: UnixTime,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+    ) -> Result<OcspCertStatus, Error> {
+        ocsp::verify_response(
+            &self.inner,
+            issuer.into(),
+            response,
+            time,
+            Duration::ZERO,
+            supported_sig_algs,
+            &mut Budget::default(),
+        )
+    }
+
+    /// Returns a DER-encoded OCSP request for the status of this certificate, to be sent to an
+    /// OCSP responder such as one named by [`crate::Cert::ocsp_uris()`].
+    ///
+    /// `issuer` must be the certificate that issued this one. The certificate is identified by
+    /// a CertID whose issuer name and key hashes are computed with `hash`. The request is
+    /// unsigned and has no extensions, as is usual. See RFC 6960[^1]. A response to it is
+    /// verified by [`EndEntityCert::verify_ocsp_stapled_response()`].
+    ///
+    /// Returns [`Error::UnknownIssuer`] if `issuer`'s subject is not this certificate's issuer.
+    ///
+    /// [^1]: <https://www.rfc-editor.org/rfc/rfc6960>
+    #[cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+    pub fn ocsp_request_for(
+        &self,
+        issuer: &cert::Cert<'_>,
+        hash: OcspHash,
+    ) -> Result<Vec<u8>, Error> {
+        ocsp::request(&self.inner, issuer.into(), hash, None)
+    }
+
+    /// Returns a DER-encoded OCSP request as [`EndEntityCert::ocsp_request_for()`] does, with
+    /// the nonce extension of RFC 8954[^1] containing `nonce`.
+    ///
+    /// A responder that supports nonces echoes `nonce` in its response, proving that the
+    /// response is fresh, but many responders ignore them. `nonce` should be random, and must
+    /// be from 1 to 32 octets long, or [`Error::ExtensionValueInvalid`] is returned.
+    ///
+    /// [^1]: <https://www.rfc-editor.org/rfc/rfc8954>
+    #[cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+    pub fn ocsp_request_with_nonce_for(
+        &self,
+        issuer: &cert::Cert<'_>,
+        hash: OcspHash,
+        nonce: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        ocsp::request(&self.inner, issuer.into(), hash, Some(nonce))
+    }
+}
+
+/// Options for verifying an [`EndEntityCert`], returned by [`EndEntityCert::verifier()`].
+///
+/// Every option has a default, so that options can be added without affecting existing
+/// callers. The defaults are those of [`EndEntityCert::verify_for_usage()`] called with no
+/// intermediates, revocation checking or `verify_path`, `KeyUsage::server_auth()`, and
+/// `VerificationPolicy::default()`.
+///
+/// Use `'v` for lifetimes that only need to last for the verification, and `'p` for those that
+/// become part of the verified path.
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct Verifier<'v, 'p> {
+    end_entity: &'p EndEntityCert<'p>,
+    supported_sig_algs: &'v [&'v dyn SignatureVerificationAlgorithm],
+    trust_anchors: Anchors<'p>,
+    intermediate_certs: SuppliedIntermediates<'p>,
+    time: Option<UnixTime>,
+    usage: KeyUsage,
+    revocation: Option<RevocationOptions<'v>>,
+    policy: VerificationPolicy,
+    verify_path: Option<&'v dyn Fn(&VerifiedPath<'_>) -> Result<(), Error>>,
+    anchor_filter: Option<&'v dyn Fn(&TrustAnchor<'_>) -> bool>,
+    hook: Option<&'v dyn InspectionHook>,
+    max_path_length: usize,
+    budget: Budget,
+    clock_skew: Duration,
+    freshness_time: Option<UnixTime>,
+    required_policies: &'v [&'v [u8]],
+    ignored_extensions: &'v [&'v [u8]],
+    allowed_errors: AllowedErrors,
+    ignore_validity_periods: bool,
+    allow_v1: bool,
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    ocsp_response: Option<&'v [u8]>,
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    ocsp_responses: Option<(&'v [&'v [u8]], UnknownStatusPolicy)>,
+}
+
+impl<'v, 'p: 'v> Verifier<'v, 'p> {
+    /// Uses `intermediate_certs`, the certificates that a peer sent, for path building.
+    ///
+    /// They may be in any order: the issuer of each certificate is looked for among all of
+    /// them. Certificates that are unrelated to the path, or malformed, are ignored unless no
+    /// path can be built.
+    pub fn intermediates(mut self, intermediate_certs: &'p [CertificateDer<'p>]) -> Self {
+        self.intermediate_certs = SuppliedIntermediates::Der(intermediate_certs);
+        self
+    }
+
+    /// Uses `intermediate_certs`, the certificates that a peer sent, for path building, without
+    /// first collecting them into a slice.
+    ///
+    /// The iterator is consumed once, here, and each certificate is parsed once. Without the
+    /// `alloc` feature there's nowhere to keep more than 6 certificates, the limit built into
+    /// path building: if `intermediate_certs` yields more, verification fails with
+    /// [`Error::MaximumPathDepthExceeded`]. Use [`Self::intermediates()`] to supply more.
+    pub fn intermediates_from_iter(
+        mut self,
+        intermediate_certs: impl IntoIterator<Item = &'p CertificateDer<'p>>,
+    ) -> Self {
+        self.intermediate_certs = SuppliedIntermediates::parse(intermediate_certs);
+        self
+    }
+
+    /// Uses `intermediate_certs`, already parsed as [`crate::Cert`]s, for path building, as
+    /// [`Self::intermediates()`] does, without parsing them again.
+    pub fn parsed_intermediates(mut self, intermediate_certs: &'p [cert::Cert<'p>]) -> Self {
+        self.intermediate_certs = SuppliedIntermediates::Certs(intermediate_certs);
+        self
+    }
+
+    /// Verifies the certificates as of `time`.
+    ///
+    /// With the `std` feature enabled this defaults to the current time. Otherwise there's no
+    /// clock to read, and it defaults to the Unix epoch, at which no certificate is valid.
+    pub fn at_time(mut self, time: UnixTime) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Checks that revocation data is fresh as of `time`, rather than the verification time set
+    /// by [`Self::at_time()`].
+    ///
+    /// This is for verifying a certificate chain again long after it was received, as with a
+    /// stored exported authenticator (RFC 9261): the certificates are checked against their
+    /// validity periods at the original verification time, while the nextUpdate of each CRL,
+    /// and the times of an OCSP response given to `Self::ocsp_response()`, are checked against
+    /// the current time. A certificate listed in a CRL is still compared against its
+    /// revocation date at the verification time. Defaults to the verification time.
+    ///
+    /// Both times are recorded in the `VerifiedPath`.
+    pub fn freshness_time(mut self, time: UnixTime) -> Self {
+        self.freshness_time = Some(time);
+        self
+    }
+
+    /// Verifies the end-entity certificate for `usage`. Defaults to `KeyUsage::server_auth()`.
+    pub fn key_usage(mut self, usage: KeyUsage) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Checks the certificates in the path against `revocation`. By default, no revocation
+    /// checking is performed.
+    pub fn revocation(mut self, revocation: RevocationOptions<'v>) -> Self {
+        self.revocation = Some(revocation);
+        self
+    }
+
+    /// Performs the additional checks selected by `policy`.
+    pub fn policy(mut self, policy: VerificationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Calls `verify_path` for each potentially verified path, which it can reject. See
+    /// [`EndEntityCert::verify_for_usage()`].
+    pub fn verify_path(
+        mut self,
+        verify_path: &'v dyn Fn(&VerifiedPath<'_>) -> Result<(), Error>,
+    ) -> Self {
+        self.verify_path = Some(verify_path);
+        self
+    }
+
+    /// Only lets the trust anchors for which `anchor_filter` returns `true` terminate a path.
+    ///
+    /// `anchor_filter` is called for each trust anchor named as the issuer of a certificate in
+    /// a candidate path, before its signature is checked. Rejected trust anchors are skipped,
+    /// and path building continues as if they had not been supplied.
+    ///
+    /// To prefer some trust anchors over others even when the preferred ones are more distant,
+    /// verify first with a filter that only accepts the preferred trust anchors, then again
+    /// without one.
+    pub fn anchor_filter(mut self, anchor_filter: &'v dyn Fn(&TrustAnchor<'_>) -> bool) -> Self {
+        self.anchor_filter = Some(anchor_filter);
+        self
+    }
+
+    /// Calls `hook` for each certificate considered during path building.
+    pub fn hook(mut self, hook: &'v dyn InspectionHook) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    /// Accepts paths with at most `max` intermediate certificates.
+    ///
+    /// This can only lower the limit built into path building, which is 6, and is used by
+    /// default.
+    pub fn max_path_length(mut self, max: usize) -> Self {
+        self.max_path_length = max.min(verify_cert::MAX_SUB_CA_COUNT);
+        self
+    }
+
+    /// Fails with [`Error::MaximumSignatureChecksExceeded`] if more than `max` signatures
+    /// would be verified while building paths, or while checking the response given to
+    /// `Verifier::ocsp_response()`. Defaults to 100.
+    pub fn max_signature_checks(mut self, max: usize) -> Self {
+        self.budget.signatures = max;
+        self
+    }
+
+    /// Fails with [`Error::MaximumPathBuildCallsExceeded`] if more than `max` candidate
+    /// issuers would be considered while building paths. Defaults to 200,000.
+    ///
+    /// Path building explores every combination of the intermediates supplied, so this bounds
+    /// the work done for a peer that sends many cross-signed certificates.
+    pub fn max_path_build_calls(mut self, max: usize) -> Self {
+        self.budget.build_chain_calls = max;
+        self
+    }
+
+    /// Fails with [`Error::MaximumNameConstraintComparisonsExceeded`] if more than `max` name
+    /// constraint subtrees would be compared against names while building paths. Defaults to
+    /// 250,000.
+    ///
+    /// Each name of a certificate is compared against every subtree of the name constraints
+    /// above it, so this bounds the work done for a peer that sends an intermediate with many
+    /// subtrees and an end-entity certificate with many names.
+    pub fn max_name_constraint_comparisons(mut self, max: usize) -> Self {
+        self.budget.name_constraint_comparisons = max;
+        self
+    }
+
+    /// Tolerates the verification time being off by up to `skew` in either direction.
+    ///
+    /// Every comparison with the verification time is widened by `skew`: a certificate, or a
+    /// delegated OCSP responder certificate, is accepted if its notBefore is no later than the
+    /// time plus `skew` and its notAfter no earlier than the time minus `skew`. CRL nextUpdate
+    /// and OCSP thisUpdate and nextUpdate times are compared in the same way. The revocation
+    /// date of a certificate listed in a CRL is not a freshness check, and is compared against
+    /// the verification time itself.
+    ///
+    /// Only whole seconds of `skew` are used. Defaults to zero.
+    pub fn allow_clock_skew(mut self, skew: Duration) -> Self {
+        self.clock_skew = skew;
+        self
+    }
+
+    /// Requires the verified path to be valid for each of the certificate policies in `oids`.
+    ///
+    /// Each OID is given as the DER-encoded value of an OBJECT IDENTIFIER, without its tag and
+    /// length, and is in the policy domain of the trust anchor. The certificate policies,
+    /// policy mappings, policy constraints and inhibit anyPolicy extensions along the path are
+    /// processed as described in RFC 5280 section 6.1, and a path that isn't valid for one of
+    /// the policies fails with [`Error::RequiredPolicyNotMet`].
+    ///
+    /// By default no policies are required, and a path including a critical certificate
+    /// policy extension is rejected with [`Error::UnsupportedCriticalExtension`].
+    #[cfg(feature = "alloc")]
+    pub fn required_policies(mut self, oids: &'v [&'v [u8]]) -> Self {
+        self.required_policies = oids;
+        self
+    }
+
+    /// Accepts certificates in the path with the critical extensions in `oids`, without
+    /// processing them.
+    ///
+    /// By default, a certificate with a critical extension that webpki doesn't understand is
+    /// rejected with [`Error::UnsupportedCriticalExtension`], as RFC 5280 requires. Each OID is
+    /// given as the DER-encoded value of an OBJECT IDENTIFIER, without its tag and length.
+    /// Ignoring an extension is only safe if the application enforces whatever it means
+    /// itself, or it's known to be irrelevant to the application.
+    pub fn ignore_critical_extensions(mut self, oids: &'v [&'v [u8]]) -> Self {
+        self.ignored_extensions = oids;
+        self
+    }
+
+    /// Accepts certificates despite the errors in `allowed`, reporting them in
+    /// `VerifiedPath::suppressed_errors()` instead of failing.
+    ///
+    /// This is for applications that let a user accept a certificate anyway. See
+    /// [`crate::dangerous`] for which errors can be suppressed.
+    pub fn dangerous_allow_errors(mut self, allowed: AllowedErrors) -> Self {
+        self.allowed_errors = allowed;
+        self
+    }
+
+    /// Skips checking that certificates are valid at the verification time, for analyzing
+    /// a path regardless of when it was used.
+    ///
+    /// Every other check is still made, including that every validity period is well-formed
+    /// and not inverted. `VerifiedPath::validity_periods()` yields them, to be evaluated
+    /// separately. CRLs and OCSP responses are still checked at the verification time.
+    pub fn dangerous_ignore_validity_periods(mut self) -> Self {
+        self.ignore_validity_periods = true;
+        self
+    }
+
+    /// Accepts a v1 end-entity certificate, as some old devices still use.
+    ///
+    /// A v1 certificate has no extensions, so nothing constrains its key usage, and its
+    /// identity is its subject: [`EndEntityCert::verify_is_valid_for_subject_name()`]
+    /// compares DNS names against the subject commonName instead. Only enable this where that
+    /// has been agreed with the issuer. By default a v1 end-entity certificate fails with
+    /// [`Error::UnsupportedCertVersion`]. Intermediates must always be v3 certificates; trust
+    /// anchors may be v1 certificates regardless (see [`crate::anchor_from_trusted_cert()`]).
+    pub fn allow_v1_certificates(mut self) -> Self {
+        self.allow_v1 = true;
+        self
+    }
+
+    /// Requires `response`, a DER-encoded OCSP response such as one stapled to a TLS
+    /// handshake, to report that the end-entity certificate is good.
+    ///
+    /// The response is verified as by [`EndEntityCert::verify_ocsp_stapled_response()`],
+    /// against the issuer in the verified path. A response that reports the certificate as
+    /// revoked fails with [`Error::CertRevoked`], and one that doesn't know its status fails
+    /// with [`Error::UnknownRevocationStatus`]. So does any response if the end-entity
+    /// certificate's issuer is unknown, as [`Self::dangerous_verify_allowing_unknown_issuer()`]
+    /// allows: there's no key to verify the response with.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub fn ocsp_response(mut self, response: &'v [u8]) -> Self {
+        self.ocsp_response = Some(response);
+        self
+    }
+
+    /// Requires `responses`, DER-encoded OCSP responses aligned with the verified path, to
+    /// report that no certificate in it is revoked. This is for lists of stapled responses,
+    /// such as the OCSPResponseList of the status_request_v2 TLS extension (RFC 6961[^1]).
+    ///
+    /// The first response is for the end-entity certificate, the next for its issuer, and so
+    /// on up the path; there is none for the trust anchor. Each is verified as by
+    /// [`EndEntityCert::verify_ocsp_stapled_response()`], against the next certificate up the
+    /// path. A response that reports its certificate as revoked fails with
+    /// [`Error::CertRevoked`].
+    ///
+    /// There may be fewer responses than certificates, and a response may be empty. Those
+    /// certificates have no response, as has the topmost certificate of a path accepted by
+    /// [`Self::dangerous_verify_allowing_unknown_issuer()`]. `status_policy` says whether such
+    /// certificates, and those whose response doesn't know their status, are accepted or fail
+    /// with [`Error::UnknownRevocationStatus`]. The statuses are available from
+    /// `VerifiedPath::ocsp_statuses()`.
+    ///
+    /// A response for another certificate of the path fails with
+    /// [`Error::MisalignedOcspResponse`], and more responses than certificates with
+    /// [`Error::SurplusOcspResponses`]. This is checked in addition to [`Self::ocsp_response()`].
+    ///
+    /// [^1]: <https://www.rfc-editor.org/rfc/rfc6961>
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub fn ocsp_responses(
+        mut self,
+        responses: &'v [&'v [u8]],
+        status_policy: UnknownStatusPolicy,
+    ) -> Self {
+        self.ocsp_responses = Some((responses, status_policy));
+        self
+    }
+
+    /// Verifies the end-entity certificate with these options.
+    ///
+    /// If successful, yields the verified path. See [`EndEntityCert::verify_for_usage()`].
+    pub fn verify(self) -> Result<VerifiedPath<'p>, Error> {
+        self.verify_detailed().map_err(Error::from)
+    }
+
+    /// Verifies the end-entity certificate with these options, as [`Self::verify()`] does.
+    ///
+    /// On failure, also reports which certificate the error was found at, for example which
+    /// certificate in the path expired or has a bad signature. Errors from checking the OCSP
+    /// response given to `Self::ocsp_response()` are reported at the end-entity certificate,
+    /// and those given to `Self::ocsp_responses()` at the certificate they were given for.
+    pub fn verify_detailed(self) -> Result<VerifiedPath<'p>, DetailedError<'p>> {
+        match self.verify_accepting(false)? {
+            AcceptedPath::Verified(path) => Ok(path),
+            // Only accepted by `dangerous_verify_allowing_unknown_issuer()`.
+            AcceptedPath::UnknownIssuer(_) => Err(DetailedError {
+                error: Error::UnknownIssuer,
+                cert: None,
+            }),
+        }
+    }
+
+    /// Verifies the end-entity certificate as [`Self::verify_detailed()`] does, but also accepts
+    /// a path ending at a certificate for which no issuer was found among the trust anchors and
+    /// intermediate certificates. **Only** for letting a user accept such a certificate.
+    ///
+    /// That path is verified as far as it goes: every certificate but the topmost must be
+    /// signed by the one above it, and the name constraints of each must be satisfied. The
+    /// topmost certificate's signature can't be checked, and neither can its revocation status,
+    /// which is [`crate::RevocationStatus::Unknown`] if checking it is required and
+    /// [`crate::UnknownStatusPolicy::Allow`] is set, and an error otherwise. It is never passed
+    /// to the function set by [`Self::verify_path()`], which only sees paths that end at a
+    /// trust anchor. The errors allowed by [`Self::dangerous_allow_errors()`] are suppressed for
+    /// it as for any path.
+    ///
+    /// A path ending at a trust anchor is always preferred. An issuer that was found but
+    /// rejected, for example because its signature is invalid, is not unknown, so the error for
+    /// it is reported as usual.
+    pub fn dangerous_verify_allowing_unknown_issuer(
+        self,
+    ) -> Result<AcceptedPath<'p>, DetailedError<'p>> {
+        self.verify_accepting(true)
+    }
+
+    fn verify_accepting(
+        self,
+        allow_unknown_issuer: bool,
+    ) -> Result<AcceptedPath<'p>, DetailedError<'p>> {
+        let time = match self.time {
+            Some(time) => time,
+            #[cfg(feature = "std")]
+            None => UnixTime::now(),
+            #[cfg(not(feature = "std"))]
+            None => UnixTime::since_unix_epoch(core::time::Duration::ZERO),
+        };
+
+        let options = |allowed_errors, allow_unknown_issuer| verify_cert::ChainOptions {
+            eku: self.usage,
+            supported_sig_algs: self.supported_sig_algs,
+            trust_anchors: self.trust_anchors,
+            intermediate_certs: &self.intermediate_certs,
+            revocation: self.revocation,
+            policy: self.policy,
+            anchor_filter: self.anchor_filter,
+            hook: self.hook,
+            max_sub_ca_count: self.max_path_length,
+            budget: self.budget,
+            clock_skew: self.clock_skew,
+            freshness_time: self.freshness_time,
+            required_policies: self.required_policies,
+            ignored_extensions: self.ignored_extensions,
+            allowed_errors,
+            allow_unknown_issuer,
+            ignore_validity_periods: self.ignore_validity_periods,
+            allow_v1: self.allow_v1,
+            failure: Cell::new(None),
+        };
+        // Prefer a path for which no errors need to be suppressed, then one that ends at a
+        // trust anchor.
+        let build_chain = |allowed, allow_unknown_issuer| {
+            options(allowed, allow_unknown_issuer).build_chain(
+                self.end_entity,
+                time,
+                self.verify_path,
+            )
+        };
+        let mut result = build_chain(AllowedErrors::new(), false);
+        if result.is_err() && !self.allowed_errors.is_empty() {
+            result = build_chain(self.allowed_errors, false);
+        }
+        if result.is_err() && allow_unknown_issuer {
+            result = build_chain(self.allowed_errors, true);
+        }
+        #[cfg_attr(not(any(feature = "ring", feature = "aws_lc_rs")), allow(unused_mut))]
+        let mut path = result?;
+
+        // The responses are checked within the limits set for path building.
+        #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+        let mut budget = self.budget;
+        #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+        if let Some(response) = self.ocsp_response {
+            let issuer = match &path {
+                AcceptedPath::Verified(path) => Some(match path.end_entity_issuer() {
+                    Some(cert) => ocsp::Issuer::from(cert),
+                    None => ocsp::Issuer::from(path.anchor()),
+                }),
+                // Without the issuer, the response can't be verified.
+                AcceptedPath::UnknownIssuer(path) => {
+                    path.end_entity_issuer().map(ocsp::Issuer::from)
+                }
+            };
+            let result = match issuer.map(|issuer| {
+                ocsp::verify_response(
+                    &self.end_entity.inner,
+                    issuer,
+                    response,
+                    self.freshness_time.unwrap_or(time),
+                    self.clock_skew,
+                    self.supported_sig_algs,
+                    &mut budget,
+                )
+            }) {
+                Some(Ok(OcspCertStatus::Good)) => Ok(()),
+                Some(Ok(OcspCertStatus::Revoked { .. })) => Err(Error::CertRevoked),
+                Some(Ok(OcspCertStatus::Unknown)) | None => Err(Error::UnknownRevocationStatus),
+                Some(Err(err)) => Err(err),
+            };
+            result.map_err(|error| DetailedError {
+                error,
+                cert: Some(FailedCert {
+                    depth: 0,
+                    der: self.end_entity.der.clone(),
+                }),
+            })?;
+        }
+
+        #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+        if let Some((responses, status_policy)) = self.ocsp_responses {
+            let responses = verify_cert::OcspResponses {
+                responses,
+                status_policy,
+                time: self.freshness_time.unwrap_or(time),
+                clock_skew: self.clock_skew,
+            };
+            match &mut path {
+                AcceptedPath::Verified(path) => {
+                    path.verify_ocsp_responses(responses, self.supported_sig_algs, &mut budget)
+                }
+                AcceptedPath::UnknownIssuer(path) => {
+                    path.verify_ocsp_responses(responses, self.supported_sig_algs, &mut budget)
+                }
+            }?;
+        }
+
+        Ok(path)
+    }
+}
+
+/// Verifies that `end_entity` was valid for code signing at `signing_time`.
+///
+/// `signing_time` is the time at which the signature was made, usually taken from a trusted
+/// timestamp token; timestamp tokens themselves are not processed. Every certificate in the path
+/// must have been valid at that time, whether or not it has since expired, and be valid for the
+/// codeSigning extended key usage. See [`KeyUsage::code_signing()`].
+///
+/// CRLs are consulted as of `signing_time` too. To accept a signature made before its
+/// certificate was revoked, as Authenticode does, build `revocation` with
+/// [`crate::RevocationDatePolicy::Enforce`].
+pub fn verify_code_signing_cert(
+    end_entity: &CertificateDer<'_>,
+    intermediate_certs: &[CertificateDer<'_>],
+    trust_anchors: &[TrustAnchor<'_>],
+    signing_time: UnixTime,
+    supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+    revocation: Option<RevocationOptions<'_>>,
+) -> Result<(), Error> {
+    EndEntityCert::try_from(end_entity)?
+        .verify_for_usage(
+            supported_sig_algs,
+            trust_anchors,
+            intermediate_certs,
+            signing_time,
+            KeyUsage::code_signing(),
+            revocation,
+            None,
+        )
+        .map(|_| ())
+}
+
+/// Verifies `signature` over `message` using the key in `spki`, with the signature algorithm
+/// identified by `algorithm_id`, as the signatures in a certificate chain are verified.
+///
+/// This is for signatures over other data made with a certificate's key, such as a detached
+/// signature over a firmware manifest made with the key of a path's end-entity certificate.
+/// `spki` is a DER-encoded subjectPublicKeyInfo, as returned by
+/// [`crate::Cert::subject_public_key_info()`], and `algorithm_id` a DER-encoded
+/// AlgorithmIdentifier, both including their outer SEQUENCE tag and length.
+///
+/// The algorithm is chosen from `supported_sig_algs` as for a certificate signature: its
+/// signature algorithm identifier must be exactly `algorithm_id`, parameters included, and its
+/// public key algorithm identifier, including any named curve, that of `spki`. Fails with
+/// [`Error::UnsupportedSignatureAlgorithm`] if no algorithm has that signature algorithm
+/// identifier, [`Error::UnsupportedSignatureAlgorithmForPublicKey`] if none of those is for the
+/// key in `spki`, and [`Error::InvalidSignatureForPublicKey`] if the signature is not valid.
+pub fn verify_signed_data(
+    spki: &[u8],
+    algorithm_id: &[u8],
+    message: &[u8],
+    signature: &[u8],
+    supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+) -> Result<(), Error> {
+    let spki = untrusted::Input::from(spki).read_all(
+        Error::TrailingData(DerTypeId::SubjectPublicKeyInfo),
+        |reader| der::expect_tag(reader, der::Tag::Sequence),
+    )?;
+    let algorithm = untrusted::Input::from(algorithm_id).read_all(
+        Error::TrailingData(DerTypeId::SignatureAlgorithm),
+        |reader| der::expect_tag(reader, der::Tag::Sequence),
+    )?;
+
+    let signed_data = signed_data::SignedData {
+        data: untrusted::Input::from(message),
+        algorithm,
+        signature: untrusted::Input::from(signature),
+    };
+    signed_data::verify_signed_data(
+        supported_sig_algs,
+        spki,
+        &signed_data,
+        &mut Budget::default(),
+    )
 }
 
 impl<'a> Deref for EndEntityCert<'a> {
@@ -185,6 +1064,7 @@ impl<'a> Deref for EndEntityCert<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lint::Lint;
     use crate::test_utils;
 
     // This test reproduces https://github.com/rustls/webpki/issues/167 --- an
@@ -228,6 +1108,451 @@ mod tests {
         expect_dns_name(&ee_cert_der, "example.com");
     }
 
+    #[test]
+    fn malformed_ip_address_san() {
+        // SEQUENCE { [7] 01 02 03 04 05 } and SEQUENCE { [7] {} }
+        for san in [
+            &[0x30, 0x07, 0x87, 0x05, 0x01, 0x02, 0x03, 0x04, 0x05][..],
+            &[0x30, 0x02, 0x87, 0x00][..],
+        ] {
+            let ee_cert_der = end_entity_with_san(san);
+            let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+            assert_eq!(
+                cert.verify_is_valid_for_subject_name(&ServerName::try_from("1.2.3.4").unwrap()),
+                Err(Error::MalformedIpAddress)
+            );
+        }
+    }
+
+    #[test]
+    fn ip_address_family_mismatch() {
+        // SEQUENCE { [7] 1.2.3.4 }
+        let v4_san = [0x30, 0x06, 0x87, 0x04, 0x01, 0x02, 0x03, 0x04];
+        // SEQUENCE { [7] ::ffff:1.2.3.4 }
+        let v6_san = [
+            0x30, 0x12, 0x87, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0xff, 0xff, 0x01, 0x02, 0x03, 0x04,
+        ];
+        let v4 = ServerName::try_from("1.2.3.4").unwrap();
+        let v6 = ServerName::try_from("::ffff:1.2.3.4").unwrap();
+
+        // An address only matches a presented address of the same family, even when
+        // the IPv6 address is the IPv4-mapped form of the IPv4 one.
+        let ee_cert_der = end_entity_with_san(&v4_san);
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+        assert_eq!(cert.verify_is_valid_for_subject_name(&v4), Ok(()));
+        assert_eq!(
+            cert.verify_is_valid_for_subject_name(&v6),
+            Err(Error::CertNotValidForName)
+        );
+
+        let ee_cert_der = end_entity_with_san(&v6_san);
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+        assert_eq!(cert.verify_is_valid_for_subject_name(&v6), Ok(()));
+        assert_eq!(
+            cert.verify_is_valid_for_subject_name(&v4),
+            Err(Error::CertNotValidForName)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn ip_address_not_matched_as_dns_name() {
+        // SEQUENCE { [2] "10.0.0.1" [7] ::ffff:10.0.0.2 }
+        let san = [
+            0x30, 0x1c, 0x82, 0x08, b'1', b'0', b'.', b'0', b'.', b'0', b'.', b'1', 0x87, 0x10,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0x0a, 0x00,
+            0x00, 0x02,
+        ];
+        let ee_cert_der = end_entity_with_san(&san);
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+
+        let name = |addr: std::net::IpAddr| ServerName::IpAddress(addr.into());
+        assert_eq!(
+            cert.verify_is_valid_for_subject_name(&name("10.0.0.1".parse().unwrap())),
+            Err(Error::CertNotValidForName)
+        );
+        // Textual forms of the same IPv6 address all match.
+        for text in [
+            "::ffff:10.0.0.2",
+            "::ffff:a00:2",
+            "0:0:0:0:0:ffff:0a00:0002",
+            "0000:0000:0000:0000:0000:FFFF:0A00:0002",
+        ] {
+            assert_eq!(
+                cert.verify_is_valid_for_subject_name(&name(text.parse().unwrap())),
+                Ok(())
+            );
+            assert_eq!(
+                cert.verify_is_valid_for_subject_name(&ServerName::try_from(text).unwrap()),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn covers_dns_pattern() {
+        let issuer = test_utils::make_issuer("Test");
+        let names = ["*.internal.example.com", "www.example.com"];
+        let params = test_utils::end_entity_params(names.iter().map(|n| n.to_string()).collect());
+        let ee_cert_der = CertificateDer::from(
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der_with_signer(&issuer)
+                .unwrap(),
+        );
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+
+        for (pattern, expected) in [
+            // Concrete patterns match as subject names do.
+            ("www.example.com", true),
+            ("WWW.Example.COM.", true),
+            ("db.internal.example.com", true),
+            ("internal.example.com", false),
+            ("a.db.internal.example.com", false),
+            // Wildcard patterns need a wildcard name with the same suffix.
+            ("*.internal.example.com", true),
+            ("*.INTERNAL.example.com.", true),
+            ("*.example.com", false),
+            ("*.db.internal.example.com", false),
+            ("*.ternal.example.com", false),
+        ] {
+            assert_eq!(cert.covers_dns_pattern(pattern), Ok(expected), "{pattern}");
+        }
+
+        for pattern in [
+            "",
+            "*",
+            "*.com",
+            "f*.example.com",
+            "*www.example.com",
+            "www.*.example.com",
+            "**.example.com",
+            "*.*.example.com",
+        ] {
+            assert_eq!(
+                cert.covers_dns_pattern(pattern),
+                Err(Error::MalformedDnsIdentifier),
+                "{pattern}"
+            );
+        }
+
+        // Public-suffix-wide patterns are syntactically valid; judging them is up to the caller.
+        assert_eq!(cert.covers_dns_pattern("*.co.uk"), Ok(false));
+    }
+
+    #[test]
+    fn absolute_reference_names() {
+        use crate::subject_name::parse_dns_name;
+
+        // None of the presented names is absolute.
+        let issuer = test_utils::make_issuer("Test");
+        let names = ["*.example.com", "example.com"];
+        let params = test_utils::end_entity_params(names.iter().map(|n| n.to_string()).collect());
+        let ee_cert_der = CertificateDer::from(
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der_with_signer(&issuer)
+                .unwrap(),
+        );
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+
+        for (name, expected) in [
+            ("example.com", Ok(())),
+            ("example.com.", Ok(())),
+            ("WWW.Example.COM.", Ok(())),
+            ("a.b.example.com.", Err(Error::CertNotValidForName)),
+            ("example.org.", Err(Error::CertNotValidForName)),
+        ] {
+            let dns_name = parse_dns_name(name).unwrap();
+            assert!(!dns_name.as_ref().ends_with('.'));
+            let server_name = ServerName::DnsName(dns_name);
+            assert_eq!(
+                cert.verify_is_valid_for_subject_name(&server_name),
+                expected,
+                "{name}"
+            );
+            // The absolute name itself matches the same presented names.
+            let server_name = ServerName::try_from(name).unwrap();
+            assert_eq!(
+                cert.verify_is_valid_for_subject_name(&server_name),
+                expected,
+                "{name}"
+            );
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn subject_name_verbose() {
+        use crate::subject_name::PresentedName;
+
+        // SEQUENCE { [2] "*.example.org" [2] "a..b" [1] "a@b" [7] 1.2.3.4 }
+        let mut san = vec![0x30, 0x20, 0x82, 0x0d];
+        san.extend_from_slice(b"*.example.org");
+        san.extend_from_slice(&[0x82, 0x04]);
+        san.extend_from_slice(b"a..b");
+        san.extend_from_slice(&[0x81, 0x03]);
+        san.extend_from_slice(b"a@b");
+        san.extend_from_slice(&[0x87, 0x04, 0x01, 0x02, 0x03, 0x04]);
+        let ee_cert_der = end_entity_with_san(&san);
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+
+        let name = ServerName::try_from("www.example.org").unwrap();
+        assert_eq!(cert.verify_is_valid_for_subject_name_verbose(&name), Ok(()));
+
+        let presented = vec![
+            PresentedName::DnsName("*.example.org".to_string()),
+            PresentedName::MalformedDnsName(b"a..b".to_vec()),
+            PresentedName::IpAddress(vec![0x01, 0x02, 0x03, 0x04]),
+        ];
+        // Names of either kind are listed, whichever kind of name is expected. The malformed
+        // dNSName is only reported for a DNS name.
+        for (expected, error) in [
+            ("www.example.com", Error::MalformedDnsIdentifier),
+            ("5.6.7.8", Error::CertNotValidForName),
+        ] {
+            let name = ServerName::try_from(expected).unwrap();
+            let err = cert
+                .verify_is_valid_for_subject_name_verbose(&name)
+                .unwrap_err();
+            assert_eq!(err.error, error);
+            assert_eq!(err.expected, name);
+            assert_eq!(err.presented, presented);
+            assert_eq!(Error::from(err), error);
+        }
+
+        // SEQUENCE { [2] "ok.example.com" [7] 01 02 03 04 05 }
+        let mut san = vec![0x30, 0x17, 0x82, 0x0e];
+        san.extend_from_slice(b"ok.example.com");
+        san.extend_from_slice(&[0x87, 0x05, 0x01, 0x02, 0x03, 0x04, 0x05]);
+        let ee_cert_der = end_entity_with_san(&san);
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+        let err = cert
+            .verify_is_valid_for_subject_name_verbose(&ServerName::try_from("1.2.3.4").unwrap())
+            .unwrap_err();
+        assert_eq!(err.error, Error::MalformedIpAddress);
+        assert_eq!(
+            err.presented,
+            vec![
+                PresentedName::DnsName("ok.example.com".to_string()),
+                PresentedName::MalformedIpAddress(vec![0x01, 0x02, 0x03, 0x04, 0x05]),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_dns_names() {
+        for malformed in [
+            &b"www.example.com\0.evil.example"[..],
+            b"-www.example.com",
+            b"www-.example.com",
+            b"www.*.example.com",
+            b"w*.*.example.com",
+        ] {
+            // SEQUENCE { [2] malformed }
+            #[allow(clippy::as_conversions)] // The names are short.
+            let mut san = vec![0x30, malformed.len() as u8 + 2, 0x82, malformed.len() as u8];
+            san.extend_from_slice(malformed);
+            let ee_cert_der = end_entity_with_san(&san);
+            let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+
+            let name = ServerName::try_from("www.example.com").unwrap();
+            assert_eq!(
+                cert.verify_is_valid_for_subject_name(&name),
+                Err(Error::MalformedDnsIdentifier),
+                "{malformed:?}"
+            );
+        }
+
+        // Upper case is not malformed: names are compared ignoring ASCII case.
+        // SEQUENCE { [2] "WWW.Example.COM" }
+        let mut san = vec![0x30, 0x11, 0x82, 0x0f];
+        san.extend_from_slice(b"WWW.Example.COM");
+        let ee_cert_der = end_entity_with_san(&san);
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+        for (name, expected) in [
+            ("www.example.com", Ok(())),
+            ("other.example.com", Err(Error::CertNotValidForName)),
+        ] {
+            let name = ServerName::try_from(name).unwrap();
+            assert_eq!(cert.verify_is_valid_for_subject_name(&name), expected);
+        }
+    }
+
+    // rcgen only encodes well-formed iPAddress names, so the SAN extension value is
+    // given directly.
+    #[test]
+    fn lint_subject_alt_names() {
+        // SEQUENCE of short-form GeneralNames, each a (tag, contents) pair.
+        let encode = |names: &[(u8, &[u8])]| {
+            let mut san = vec![0x30, 0x00];
+            for (tag, value) in names {
+                san.extend_from_slice(&[*tag, u8::try_from(value.len()).unwrap()]);
+                san.extend_from_slice(value);
+            }
+            san[1] = u8::try_from(san.len() - 2).unwrap();
+            san
+        };
+
+        let ee_cert_der = end_entity_with_san(&encode(&[
+            (0x82, b"example.com"),
+            (0x82, b"www.example.com"),
+            (0x82, b"example.com"),
+            (0x82, b"EXAMPLE.com"),
+            (0x82, b"192.0.2.1"),
+            (0x87, &[192, 0, 2, 1]),
+            (0x87, &[192, 0, 2, 1]),
+        ]));
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+        assert_eq!(
+            cert.lint_subject_alt_names(),
+            [
+                SanLint::DuplicateName(SubjectAltName::DnsName("example.com")),
+                SanLint::CaseVariantDnsName("EXAMPLE.com"),
+                SanLint::IpAddressInDnsName("192.0.2.1"),
+                SanLint::DuplicateName(SubjectAltName::IpAddress(&[192, 0, 2, 1])),
+            ]
+        );
+        assert_eq!(cert.lints().next(), None);
+
+        // Names are only checked up to a malformed one.
+        let ee_cert_der = end_entity_with_san(&encode(&[
+            (0x82, b"example.com"),
+            (0x87, &[192, 0, 2]),
+            (0x82, b"example.com"),
+        ]));
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+        assert_eq!(cert.lint_subject_alt_names(), []);
+
+        let ee_cert_der = end_entity_with_san(&encode(&[]));
+        let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+        assert_eq!(
+            cert.lint_subject_alt_names(),
+            [SanLint::EmptySubjectAltName]
+        );
+        assert_eq!(
+            cert.lints().collect::<Vec<_>>(),
+            [Lint::EmptySubjectAltName]
+        );
+    }
+
+    #[test]
+    fn lint_subject_alt_name_criticality() {
+        let issuer = test_utils::make_issuer("Test");
+        let issue = |empty_subject: bool, critical: bool| {
+            let mut params = test_utils::end_entity_params(vec![]);
+            if empty_subject {
+                params.distinguished_name = rcgen::DistinguishedName::new();
+            }
+            // SEQUENCE { [2] "example.com" }
+            let mut san = vec![0x30, 0x0d, 0x82, 0x0b];
+            san.extend_from_slice(b"example.com");
+            let mut san = rcgen::CustomExtension::from_oid_content(&[2, 5, 29, 17], san);
+            san.set_criticality(critical);
+            params.custom_extensions = vec![san];
+            let cert = rcgen::Certificate::from_params(params).unwrap();
+            CertificateDer::from(cert.serialize_der_with_signer(&issuer).unwrap())
+        };
+
+        for (empty_subject, critical, expected) in [
+            (false, false, None),
+            (false, true, Some(SanLint::CriticalWithSubject)),
+            (true, false, Some(SanLint::NotCriticalWithoutSubject)),
+            (true, true, None),
+        ] {
+            let ee_cert_der = issue(empty_subject, critical);
+            let cert = EndEntityCert::try_from(&ee_cert_der).unwrap();
+            assert_eq!(
+                cert.lint_subject_alt_names(),
+                expected.into_iter().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ring")]
+    fn verify_signed_data_with_end_entity_key() {
+        use ring::rand::SystemRandom;
+        use ring::signature::{EcdsaKeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+
+        let issuer = test_utils::make_issuer("Test");
+        let issuer_der = CertificateDer::from(issuer.serialize_der().unwrap());
+        let anchors = [crate::anchor_from_trusted_cert(&issuer_der).unwrap()];
+        let ee = rcgen::Certificate::from_params(test_utils::end_entity_params(vec![
+            "example.com".into(),
+        ]))
+        .unwrap();
+        let ee_der = CertificateDer::from(ee.serialize_der_with_signer(&issuer).unwrap());
+        let cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let path = cert
+            .verifier(crate::ALL_VERIFICATION_ALGS, &anchors)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+            .verify()
+            .unwrap();
+        let spki = path.end_entity().subject_public_key_info();
+
+        let rng = SystemRandom::new();
+        let key = EcdsaKeyPair::from_pkcs8(
+            &ECDSA_P256_SHA256_ASN1_SIGNING,
+            &ee.get_key_pair().serialize_der(),
+            &rng,
+        )
+        .unwrap();
+        let signature = key.sign(&rng, b"manifest").unwrap();
+        let signature = signature.as_ref();
+
+        // A DER-encoded AlgorithmIdentifier, with its outer SEQUENCE.
+        let alg_id = |value: &[u8]| {
+            let mut der = vec![0x30, u8::try_from(value.len()).unwrap()];
+            der.extend_from_slice(value);
+            der
+        };
+        let ecdsa_sha256 = alg_id(crate::alg_id::ECDSA_SHA256.as_ref());
+        let verify = |alg_id: &[u8], message: &[u8]| {
+            verify_signed_data(
+                spki,
+                alg_id,
+                message,
+                signature,
+                crate::ALL_VERIFICATION_ALGS,
+            )
+        };
+
+        assert_eq!(verify(&ecdsa_sha256, b"manifest"), Ok(()));
+        assert_eq!(
+            verify(&ecdsa_sha256, b"manifesto"),
+            Err(Error::InvalidSignatureForPublicKey)
+        );
+        assert_eq!(
+            verify(&alg_id(crate::alg_id::ED25519.as_ref()), b"manifest"),
+            Err(Error::UnsupportedSignatureAlgorithmForPublicKey)
+        );
+        // 1.3.6.1.4.1.32473.1, an OID reserved for documentation.
+        assert_eq!(
+            verify(
+                &alg_id(&[0x06, 0x09, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x81, 0xfd, 0x59, 0x01]),
+                b"manifest"
+            ),
+            Err(Error::UnsupportedSignatureAlgorithm)
+        );
+        assert_eq!(
+            verify(&[&ecdsa_sha256[..], &[0x00]].concat(), b"manifest"),
+            Err(Error::TrailingData(DerTypeId::SignatureAlgorithm))
+        );
+    }
+
+    fn end_entity_with_san(san: &[u8]) -> CertificateDer<'static> {
+        let issuer = test_utils::make_issuer("Test");
+        let mut params = test_utils::end_entity_params(vec![]);
+        params.custom_extensions = vec![rcgen::CustomExtension::from_oid_content(
+            &[2, 5, 29, 17],
+            san.to_vec(),
+        )];
+        let cert = rcgen::Certificate::from_params(params).unwrap();
+        CertificateDer::from(cert.serialize_der_with_signer(&issuer).unwrap())
+    }
+
     fn expect_dns_name(der: &CertificateDer<'_>, name: &str) {
         let cert =
             EndEntityCert::try_from(der).expect("should parse end entity certificate correctly");
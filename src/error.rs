@@ -15,8 +15,18 @@
 use core::fmt;
 use core::ops::ControlFlow;
 
+use pki_types::UnixTime;
+
 /// An error that occurs during certificate validation or name validation.
+///
+/// With the `serde` feature, this serializes in snake case, with times in seconds since the
+/// Unix epoch: for example `"bad_der"`, or `{"cert_expired":{"time":..,"not_after":..}}`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
 #[non_exhaustive]
 pub enum Error {
     /// The encoding of some ASN.1 DER-encoded item is invalid.
@@ -25,31 +35,141 @@ pub enum Error {
     /// The encoding of an ASN.1 DER-encoded time is invalid.
     BadDerTime,
 
+    /// The input uses a BER encoding construct that is not allowed in DER, such as an
+    /// indefinite length or the constructed form of a string type.
+    ///
+    /// `offset` is the position within the offending element of the first byte DER does not
+    /// allow: 0 for the identifier octet of a constructed string, and 1 for an indefinite
+    /// length octet.
+    BerEncodingNotAllowed {
+        /// The position of the first byte DER does not allow.
+        offset: usize,
+    },
+
+    /// A CA certificate's basicConstraints extension is not marked critical.
+    ///
+    /// This is only reported when verifying with a strict [`crate::VerificationPolicy`].
+    CaConstraintsNotCritical {
+        /// The position of the offending certificate in the path, where the end-entity
+        /// certificate is at depth 0.
+        depth: usize,
+    },
+
     /// A CA certificate is being used as an end-entity certificate.
     CaUsedAsEndEntity,
 
+    /// A certificate in a trust anchor bundle is not a CA certificate, and the
+    /// [`crate::NonCaCertPolicy`] rejects it.
+    BundleCertNotCa {
+        /// The position of the certificate in the bundle, starting from 0.
+        index: usize,
+    },
+
     /// The certificate is expired; i.e. the time it is being validated for is
     /// later than the certificate's notAfter time.
-    CertExpired,
+    CertExpired {
+        /// The time the certificate was validated for.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::report::serialize_time")
+        )]
+        time: UnixTime,
+        /// The certificate's notAfter time.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::report::serialize_time")
+        )]
+        not_after: UnixTime,
+    },
 
     /// The certificate is not valid for the name it is being validated for.
     CertNotValidForName,
 
     /// The certificate is not valid yet; i.e. the time it is being validated
     /// for is earlier than the certificate's notBefore time.
-    CertNotValidYet,
+    CertNotValidYet {
+        /// The time the certificate was validated for.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::report::serialize_time")
+        )]
+        time: UnixTime,
+        /// The certificate's notBefore time.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::report::serialize_time")
+        )]
+        not_before: UnixTime,
+    },
 
     /// The certificate, or one of its issuers, has been revoked.
     CertRevoked,
 
+    /// The CRL consulted for a certificate's revocation status had expired at the
+    /// verification time, and [`crate::ExpirationPolicy::Enforce`] was requested.
+    CrlExpired,
+
+    /// A delta CRL could not be merged into a base CRL, because their issuers or scopes
+    /// differ, or the base CRL does not satisfy the delta's base CRL number.
+    ///
+    /// A caller seeing this error should fetch a fresh base CRL.
+    DeltaCrlMismatch,
+
+    /// A certificate contains the deprecated issuerUniqueID or subjectUniqueID fields, and
+    /// strict verification was requested.
+    DeprecatedUniqueIdentifiersPresent,
+
+    /// The end-entity certificate's extendedKeyUsage extension does not have the criticality
+    /// required by the [`crate::VerificationPolicy`].
+    EkuCriticalityMismatch,
+
+    /// The end-entity certificate has a KeyUsage bitstring that does not include the
+    /// digitalSignature key usage bit, and the [`crate::VerificationPolicy`] requires it.
+    EndEntityKeyUsageInvalid,
+
+    /// The certificate's issuer is an empty distinguished name.
+    ///
+    /// An empty issuer can never be matched to the subject of an issuing certificate, and is
+    /// forbidden by <https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.4>.
+    EmptyIssuer,
+
+    /// The certificate's subjectAltName extension contains no names, although RFC 5280
+    /// requires at least one.
+    ///
+    /// This is only reported when verifying with a strict [`crate::VerificationPolicy`]. See
+    /// <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.6>.
+    EmptySubjectAltName,
+
+    /// The certificate's subject is an empty distinguished name, but the certificate does
+    /// not have a critical subjectAltName extension.
+    ///
+    /// This is only reported when verifying with a strict [`crate::VerificationPolicy`]. See
+    /// <https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.6>.
+    EmptySubjectWithoutCriticalSan,
+
     /// An end-entity certificate is being used as a CA certificate.
     EndEntityUsedAsCa,
 
     /// An X.509 extension is invalid.
     ExtensionValueInvalid,
 
-    /// The certificate validity period (notBefore, notAfter) is invalid; e.g.
-    /// the notAfter time is earlier than the notBefore time.
+    /// A time (e.g. a certificate's notBefore) was not encoded as DER requires: in UTC, with
+    /// seconds, and with a trailing 'Z' but no fractional seconds or timezone offset.
+    ///
+    /// `offset` is the position within the time value of the first improperly encoded byte.
+    ImproperTimeEncoding {
+        /// The position of the first improperly encoded byte.
+        offset: usize,
+    },
+
+    /// The intermediates given to `crate::lint_chain()` verify, but not in the order of the
+    /// path: each certificate should be followed by its issuer.
+    IntermediatesOutOfOrder,
+
+    /// The certificate validity period (notBefore, notAfter) is invalid.
+    ///
+    /// This is no longer returned for an inverted validity period, which is reported as
+    /// [`Error::InvalidValidityPeriod`] instead.
     InvalidCertValidity,
 
     /// A CRL number extension was invalid:
@@ -58,6 +178,10 @@ pub enum Error {
     ///  - it was too long
     InvalidCrlNumber,
 
+    /// The CRL validity period (thisUpdate, nextUpdate) is invalid; e.g.
+    /// the nextUpdate time is earlier than the thisUpdate time.
+    InvalidCrlValidityPeriod,
+
     /// A iPAddress name constraint was invalid:
     /// - it had a sparse network mask (ie, cannot be written in CIDR form).
     /// - it was too long or short
@@ -75,14 +199,42 @@ pub enum Error {
     /// The signature is invalid for the given public key.
     InvalidSignatureForPublicKey,
 
+    /// The notAfter time of a certificate's validity period is earlier than its notBefore
+    /// time, so the certificate is not valid at any verification time.
+    InvalidValidityPeriod,
+
+    /// A certificate used as an issuer has a KeyUsage bitstring that does not include the
+    /// keyCertSign key usage bit.
+    IssuerKeyUsageInvalid,
+
     /// A CRL was signed by an issuer that has a KeyUsage bitstring that does not include
     /// the cRLSign key usage bit.
     IssuerNotCrlSigner,
 
+    /// The keyIdentifier in a certificate's authorityKeyIdentifier extension does not match the
+    /// subjectKeyIdentifier of its issuer, and strict verification was requested.
+    KeyIdentifierMismatch,
+
     /// A presented or reference DNS identifier was malformed, potentially
     /// containing invalid characters or invalid labels.
     MalformedDnsIdentifier,
 
+    /// A reference email address was malformed: it was not ASCII, or lacked a local part or
+    /// host.
+    MalformedEmailIdentifier,
+
+    /// A certificate's subjectAltName extension contains a dNSName that is empty or consists
+    /// only of whitespace, and strict verification was requested.
+    MalformedDnsNameInSan,
+
+    /// A certificate's subjectAltName extension contains an otherName whose contents are not
+    /// a valid `type-id` and `value`, and strict verification was requested.
+    MalformedOtherNameInSan,
+
+    /// A certificate's signedCertificateTimestampList extension, or a signed certificate
+    /// timestamp within it, is not validly encoded.
+    MalformedSctList,
+
     /// The certificate extensions are malformed.
     ///
     /// In particular, webpki requires the DNS name(s) be in the subjectAltName
@@ -90,10 +242,29 @@ pub enum Error {
     /// and as recommended by RFC6125.
     MalformedExtensions,
 
+    /// An iPAddress name presented by a certificate was malformed: it was
+    /// neither four (IPv4) nor sixteen (IPv6) octets long.
+    ///
+    /// Malformed iPAddress name constraints are reported as
+    /// [`Error::InvalidNetworkMaskConstraint`] instead.
+    MalformedIpAddress,
+
+    /// A certificate in a trust anchor bundle could not be parsed.
+    MalformedBundleCert {
+        /// The position of the certificate in the bundle, starting from 0.
+        index: usize,
+    },
+
     /// A name constraint was malformed, potentially containing invalid characters or
     /// invalid labels.
     MalformedNameConstraint,
 
+    /// An OCSP response is malformed.
+    MalformedOcspResponse,
+
+    /// A CRL is larger, or has more entries, than its [`crate::CrlLimits`] permit.
+    MaximumCrlSizeExceeded,
+
     /// The maximum number of name constraint comparisons has been reached.
     MaximumNameConstraintComparisonsExceeded,
 
@@ -106,38 +277,114 @@ pub enum Error {
     /// The maximum number of signature checks has been reached. Path complexity is too great.
     MaximumSignatureChecksExceeded,
 
+    /// The OCSP response given by `crate::Verifier::ocsp_responses()` for the certificate at
+    /// `depth` of the path is for another certificate of the path.
+    MisalignedOcspResponse {
+        /// The position of the certificate the response was given for, where the end-entity
+        /// certificate is at depth 0.
+        depth: usize,
+    },
+
     /// The certificate violates one or more name constraints.
     NameConstraintViolation,
 
+    /// A certificate's serial number is negative or zero, and the
+    /// [`crate::SerialNumberPolicy`] requires it to be positive.
+    NonPositiveSerialNumber,
+
+    /// An OCSP response was not signed by the certificate's issuer, nor by a delegated
+    /// responder certificate issued by it for OCSP signing and valid at the verification time.
+    OcspResponderNotAuthorized,
+
+    /// An OCSP response does not give the status of the certificate being checked.
+    OcspResponseCertMismatch,
+
+    /// An OCSP response had expired at the verification time; i.e. the verification time is
+    /// later than the response's nextUpdate time.
+    OcspResponseExpired,
+
+    /// An OCSP response was not valid yet at the verification time; i.e. the verification
+    /// time is earlier than the response's thisUpdate time.
+    OcspResponseNotValidYet,
+
     /// The certificate violates one or more path length constraints.
     PathLenConstraintViolated,
 
+    /// A certificate's public key is too small for the algorithms webpki provides, as reported
+    /// by `crate::lint_chain()`: an RSA modulus shorter than 2048 bits.
+    PublicKeyTooSmall,
+
     /// The certificate is not valid for the Extended Key Usage for which it is
     /// being validated.
     RequiredEkuNotFound,
 
+    /// The path is not valid for the certificate policies required by
+    /// `Verifier::required_policies()`.
+    RequiredPolicyNotMet,
+
+    /// A certificate's serial number is longer than 20 octets, and the
+    /// [`crate::SerialNumberPolicy`] limits its length.
+    SerialNumberTooLong,
+
     /// The algorithm in the TBSCertificate "signature" field of a certificate
     /// does not match the algorithm in the signature of the certificate.
     SignatureAlgorithmMismatch,
 
+    /// More OCSP responses were given by `crate::Verifier::ocsp_responses()` than there are
+    /// certificates in the path.
+    SurplusOcspResponses,
+
     /// Trailing data was found while parsing DER-encoded input for the named type.
     TrailingData(DerTypeId),
 
+    /// A certificate checked by [`crate::anchor_from_trusted_cert_checked()`] has a keyUsage
+    /// extension that does not assert keyCertSign.
+    TrustAnchorKeyUsageInvalid,
+
+    /// A certificate checked by [`crate::anchor_from_trusted_cert_checked()`] has no
+    /// basicConstraints extension with `cA` set.
+    TrustAnchorNotCa,
+
+    /// A certificate checked by [`crate::anchor_from_trusted_cert_checked()`] has an issuer
+    /// different from its subject.
+    TrustAnchorNotSelfIssued,
+
+    /// A certificate checked by [`crate::anchor_from_trusted_cert_checked()`] has a signature
+    /// that does not verify with its own public key.
+    TrustAnchorNotSelfSigned,
+
     /// A valid issuer for the certificate could not be found.
+    ///
+    /// This means that, for some certificate in every path considered, none of the supplied
+    /// trust anchors or intermediate certificates has its issuer's name. If a candidate with the
+    /// right name was found but could not be used, for example because of an invalid signature
+    /// or validity period, that more specific error is returned instead.
     UnknownIssuer,
 
     /// The certificate's revocation status could not be determined.
     UnknownRevocationStatus,
 
+    /// An OCSP response reports that the responder could not answer the request, such as
+    /// `tryLater` or `internalError`.
+    UnsuccessfulOcspResponse,
+
     /// The certificate is not a v3 X.509 certificate.
     ///
     /// This error may be also reported if the certificate version field
     /// is malformed.
     UnsupportedCertVersion,
 
+    /// A certificate supplied as an intermediate is not a version 3 certificate, and strict
+    /// verification was requested.
+    UnsupportedCertVersionForCa,
+
     /// The certificate contains an unsupported critical extension.
     UnsupportedCriticalExtension,
 
+    /// A signed certificate timestamp is not version 1, the only version defined by
+    /// RFC 6962.
+    UnsupportedSctVersion,
+
     /// The CRL contains an issuing distribution point with no distribution point name,
     /// or a distribution point name relative to an issuer.
     UnsupportedCrlIssuingDistributionPoint,
@@ -150,7 +397,8 @@ pub enum Error {
     /// This error may also be reported if the CRL version field is malformed.
     UnsupportedCrlVersion,
 
-    /// The CRL is an unsupported "delta" CRL.
+    /// A delta CRL was used where a complete CRL is required, such as the base CRL given to
+    /// [`crate::CertRevocationList::with_delta()`].
     UnsupportedDeltaCrl,
 
     /// The CRL contains unsupported "indirect" entries.
@@ -159,6 +407,9 @@ pub enum Error {
     /// The `ServerName` contained an unsupported type of value.
     UnsupportedNameType,
 
+    /// An OCSP response is of a type other than the basic OCSP response type.
+    UnsupportedOcspResponseType,
+
     /// The revocation reason is not in the set of supported revocation reasons.
     UnsupportedRevocationReason,
 
@@ -190,6 +441,16 @@ pub enum Error {
     /// algorithm and the signature algorithm simply don't match (e.g.
     /// verifying an RSA signature with an ECC public key).
     UnsupportedSignatureAlgorithmForPublicKey,
+
+    /// A certificate's validity period is longer than the [`crate::MaxValidity`] of the
+    /// [`crate::VerificationPolicy`] permits for its position in the path.
+    ValidityPeriodTooLong {
+        /// The position of the offending certificate in the path, where the end-entity
+        /// certificate is at depth 0.
+        depth: usize,
+        /// Whether the offending certificate is the end-entity or an intermediate.
+        role: CertRole,
+    },
 }
 
 impl Error {
@@ -211,32 +472,54 @@ impl Error {
     pub(crate) fn rank(&self) -> u32 {
         match &self {
             // Errors related to certificate validity
-            Error::CertNotValidYet | Error::CertExpired => 290,
+            Error::CertNotValidYet { .. } | Error::CertExpired { .. } => 290,
             Error::CertNotValidForName => 280,
-            Error::CertRevoked | Error::UnknownRevocationStatus => 270,
+            Error::CertRevoked | Error::UnknownRevocationStatus | Error::CrlExpired => 270,
+            Error::OcspResponseExpired | Error::OcspResponseNotValidYet => 270,
             Error::InvalidCrlSignatureForPublicKey | Error::InvalidSignatureForPublicKey => 260,
+            Error::TrustAnchorNotSelfSigned => 260,
             Error::SignatureAlgorithmMismatch => 250,
-            Error::RequiredEkuNotFound => 240,
+            Error::EkuCriticalityMismatch | Error::RequiredEkuNotFound => 240,
             Error::NameConstraintViolation => 230,
+            Error::RequiredPolicyNotMet => 225,
             Error::PathLenConstraintViolated => 220,
             Error::CaUsedAsEndEntity | Error::EndEntityUsedAsCa => 210,
-            Error::IssuerNotCrlSigner => 200,
+            Error::BundleCertNotCa { .. } | Error::TrustAnchorNotCa => 210,
+            Error::CaConstraintsNotCritical { .. } | Error::KeyIdentifierMismatch => 205,
+            Error::TrustAnchorNotSelfIssued | Error::IntermediatesOutOfOrder => 205,
+            Error::IssuerKeyUsageInvalid | Error::IssuerNotCrlSigner => 200,
+            Error::EndEntityKeyUsageInvalid | Error::TrustAnchorKeyUsageInvalid => 200,
+            Error::MisalignedOcspResponse { .. }
+            | Error::OcspResponderNotAuthorized
+            | Error::OcspResponseCertMismatch
+            | Error::SurplusOcspResponses => 200,
 
             // Errors related to supported features used in an invalid way.
-            Error::InvalidCertValidity => 190,
+            Error::InvalidCertValidity
+            | Error::InvalidCrlValidityPeriod
+            | Error::InvalidValidityPeriod
+            | Error::ValidityPeriodTooLong { .. } => 190,
+            Error::DeprecatedUniqueIdentifiersPresent
+            | Error::EmptyIssuer
+            | Error::EmptySubjectAltName
+            | Error::EmptySubjectWithoutCriticalSan
+            | Error::NonPositiveSerialNumber
+            | Error::SerialNumberTooLong => 185,
             Error::InvalidNetworkMaskConstraint => 180,
             Error::InvalidSerialNumber => 170,
             Error::InvalidCrlNumber => 160,
 
             // Errors related to unsupported features.
             Error::UnsupportedCrlSignatureAlgorithmForPublicKey
-            | Error::UnsupportedSignatureAlgorithmForPublicKey => 150,
+            | Error::UnsupportedSignatureAlgorithmForPublicKey
+            | Error::PublicKeyTooSmall => 150,
             Error::UnsupportedCrlSignatureAlgorithm | Error::UnsupportedSignatureAlgorithm => 140,
             Error::UnsupportedCriticalExtension => 130,
-            Error::UnsupportedCertVersion => 130,
-            Error::UnsupportedCrlVersion => 120,
-            Error::UnsupportedDeltaCrl => 110,
+            Error::UnsupportedCertVersion | Error::UnsupportedCertVersionForCa => 130,
+            Error::UnsupportedCrlVersion | Error::UnsupportedSctVersion => 120,
+            Error::UnsupportedDeltaCrl | Error::DeltaCrlMismatch => 110,
             Error::UnsupportedIndirectCrl => 100,
+            Error::UnsuccessfulOcspResponse | Error::UnsupportedOcspResponseType => 100,
             Error::UnsupportedNameType => 95,
             Error::UnsupportedRevocationReason => 90,
             Error::UnsupportedRevocationReasonsPartitioning => 80,
@@ -244,25 +527,128 @@ impl Error {
             Error::MaximumPathDepthExceeded => 61,
 
             // Errors related to malformed data.
-            Error::MalformedDnsIdentifier => 60,
-            Error::MalformedNameConstraint => 50,
-            Error::MalformedExtensions | Error::TrailingData(_) => 40,
+            Error::MalformedDnsIdentifier
+            | Error::MalformedDnsNameInSan
+            | Error::MalformedEmailIdentifier
+            | Error::MalformedIpAddress
+            | Error::MalformedOtherNameInSan => 60,
+            Error::MalformedNameConstraint | Error::MalformedOcspResponse => 50,
+            Error::MalformedBundleCert { .. } => 50,
+            Error::MalformedExtensions | Error::MalformedSctList | Error::TrailingData(_) => 40,
             Error::ExtensionValueInvalid => 30,
 
             // Generic DER errors.
-            Error::BadDerTime => 20,
+            Error::BadDerTime | Error::ImproperTimeEncoding { .. } => 20,
+            Error::BerEncodingNotAllowed { .. } => 15,
             Error::BadDer => 10,
 
             // Special case errors - not subject to ranking.
             Error::MaximumSignatureChecksExceeded => 0,
             Error::MaximumPathBuildCallsExceeded => 0,
             Error::MaximumNameConstraintComparisonsExceeded => 0,
+            Error::MaximumCrlSizeExceeded => 0,
 
             // Default catch all error - should be renamed in the future.
             Error::UnknownIssuer => 0,
         }
     }
 
+    /// Returns the category of validation failure this error belongs to.
+    ///
+    /// See [`FailureCategory`] for the mapping, which is stable: an error's category will
+    /// only change in a semver-incompatible release.
+    pub fn category(&self) -> FailureCategory {
+        match self {
+            Error::BadDer
+            | Error::BadDerTime
+            | Error::BerEncodingNotAllowed { .. }
+            | Error::DeprecatedUniqueIdentifiersPresent
+            | Error::EmptySubjectAltName
+            | Error::EmptySubjectWithoutCriticalSan
+            | Error::ExtensionValueInvalid
+            | Error::ImproperTimeEncoding { .. }
+            | Error::InvalidSerialNumber
+            | Error::MalformedBundleCert { .. }
+            | Error::MalformedDnsIdentifier
+            | Error::MalformedDnsNameInSan
+            | Error::MalformedEmailIdentifier
+            | Error::MalformedExtensions
+            | Error::MalformedIpAddress
+            | Error::MalformedOtherNameInSan
+            | Error::MalformedSctList
+            | Error::NonPositiveSerialNumber
+            | Error::SerialNumberTooLong
+            | Error::TrailingData(_)
+            | Error::UnsupportedCertVersion
+            | Error::UnsupportedCertVersionForCa
+            | Error::UnsupportedCriticalExtension
+            | Error::UnsupportedSctVersion => FailureCategory::Format,
+            Error::InvalidCrlSignatureForPublicKey
+            | Error::InvalidSignatureForPublicKey
+            | Error::PublicKeyTooSmall
+            | Error::SignatureAlgorithmMismatch
+            | Error::TrustAnchorNotSelfSigned
+            | Error::UnsupportedCrlSignatureAlgorithm
+            | Error::UnsupportedCrlSignatureAlgorithmForPublicKey
+            | Error::UnsupportedSignatureAlgorithm
+            | Error::UnsupportedSignatureAlgorithmForPublicKey => FailureCategory::Signature,
+            Error::CertExpired { .. }
+            | Error::CertNotValidYet { .. }
+            | Error::InvalidCertValidity
+            | Error::InvalidValidityPeriod
+            | Error::ValidityPeriodTooLong { .. } => FailureCategory::ValidityInterval,
+            Error::CertRevoked
+            | Error::CrlExpired
+            | Error::DeltaCrlMismatch
+            | Error::InvalidCrlNumber
+            | Error::InvalidCrlValidityPeriod
+            | Error::IssuerNotCrlSigner
+            | Error::MalformedOcspResponse
+            | Error::MisalignedOcspResponse { .. }
+            | Error::OcspResponderNotAuthorized
+            | Error::OcspResponseCertMismatch
+            | Error::OcspResponseExpired
+            | Error::OcspResponseNotValidYet
+            | Error::SurplusOcspResponses
+            | Error::UnknownRevocationStatus
+            | Error::UnsuccessfulOcspResponse
+            | Error::UnsupportedCrlIssuingDistributionPoint
+            | Error::UnsupportedCrlVersion
+            | Error::UnsupportedDeltaCrl
+            | Error::UnsupportedIndirectCrl
+            | Error::UnsupportedOcspResponseType
+            | Error::UnsupportedRevocationReason
+            | Error::UnsupportedRevocationReasonsPartitioning => FailureCategory::Revocation,
+            Error::EmptyIssuer
+            | Error::IntermediatesOutOfOrder
+            | Error::KeyIdentifierMismatch
+            | Error::TrustAnchorNotSelfIssued
+            | Error::UnknownIssuer => FailureCategory::NameChaining,
+            Error::BundleCertNotCa { .. }
+            | Error::CaConstraintsNotCritical { .. }
+            | Error::CaUsedAsEndEntity
+            | Error::CertNotValidForName
+            | Error::EkuCriticalityMismatch
+            | Error::EndEntityKeyUsageInvalid
+            | Error::EndEntityUsedAsCa
+            | Error::InvalidNetworkMaskConstraint
+            | Error::IssuerKeyUsageInvalid
+            | Error::MalformedNameConstraint
+            | Error::MaximumPathDepthExceeded
+            | Error::NameConstraintViolation
+            | Error::PathLenConstraintViolated
+            | Error::RequiredEkuNotFound
+            | Error::RequiredPolicyNotMet
+            | Error::TrustAnchorKeyUsageInvalid
+            | Error::TrustAnchorNotCa
+            | Error::UnsupportedNameType => FailureCategory::Constraints,
+            Error::MaximumCrlSizeExceeded
+            | Error::MaximumNameConstraintComparisonsExceeded
+            | Error::MaximumPathBuildCallsExceeded
+            | Error::MaximumSignatureChecksExceeded => FailureCategory::ResourceLimit,
+        }
+    }
+
     /// Returns true for errors that should be considered fatal during path building. Errors of
     /// this class should halt any further path building and be returned immediately.
     #[inline]
@@ -276,6 +662,44 @@ impl Error {
     }
 }
 
+/// A category of validation failure, loosely following those used by RFC 5280 section 6 and
+/// by ETSI validation reports, as returned by [`Error::category()`].
+///
+/// Each [`Error`] belongs to exactly one category:
+///
+/// | Category | Errors |
+/// | -------- | ------ |
+/// | [`Format`][FailureCategory::Format] | `BadDer`, `BadDerTime`, `BerEncodingNotAllowed`, `DeprecatedUniqueIdentifiersPresent`, `EmptySubjectAltName`, `EmptySubjectWithoutCriticalSan`, `ExtensionValueInvalid`, `ImproperTimeEncoding`, `InvalidSerialNumber`, `MalformedBundleCert`, `MalformedDnsIdentifier`, `MalformedDnsNameInSan`, `MalformedEmailIdentifier`, `MalformedExtensions`, `MalformedIpAddress`, `MalformedOtherNameInSan`, `MalformedSctList`, `NonPositiveSerialNumber`, `SerialNumberTooLong`, `TrailingData`, `UnsupportedCertVersion`, `UnsupportedCertVersionForCa`, `UnsupportedCriticalExtension`, `UnsupportedSctVersion` |
+/// | [`Signature`][FailureCategory::Signature] | `InvalidCrlSignatureForPublicKey`, `InvalidSignatureForPublicKey`, `PublicKeyTooSmall`, `SignatureAlgorithmMismatch`, `TrustAnchorNotSelfSigned`, `UnsupportedCrlSignatureAlgorithm`, `UnsupportedCrlSignatureAlgorithmForPublicKey`, `UnsupportedSignatureAlgorithm`, `UnsupportedSignatureAlgorithmForPublicKey` |
+/// | [`ValidityInterval`][FailureCategory::ValidityInterval] | `CertExpired`, `CertNotValidYet`, `InvalidCertValidity`, `InvalidValidityPeriod`, `ValidityPeriodTooLong` |
+/// | [`Revocation`][FailureCategory::Revocation] | `CertRevoked`, `CrlExpired`, `DeltaCrlMismatch`, `InvalidCrlNumber`, `InvalidCrlValidityPeriod`, `IssuerNotCrlSigner`, `MalformedOcspResponse`, `MisalignedOcspResponse`, `OcspResponderNotAuthorized`, `OcspResponseCertMismatch`, `OcspResponseExpired`, `OcspResponseNotValidYet`, `SurplusOcspResponses`, `UnknownRevocationStatus`, `UnsuccessfulOcspResponse`, `UnsupportedCrlIssuingDistributionPoint`, `UnsupportedCrlVersion`, `UnsupportedDeltaCrl`, `UnsupportedIndirectCrl`, `UnsupportedOcspResponseType`, `UnsupportedRevocationReason`, `UnsupportedRevocationReasonsPartitioning` |
+/// | [`NameChaining`][FailureCategory::NameChaining] | `EmptyIssuer`, `IntermediatesOutOfOrder`, `KeyIdentifierMismatch`, `TrustAnchorNotSelfIssued`, `UnknownIssuer` |
+/// | [`Constraints`][FailureCategory::Constraints] | `BundleCertNotCa`, `CaConstraintsNotCritical`, `CaUsedAsEndEntity`, `CertNotValidForName`, `EkuCriticalityMismatch`, `EndEntityKeyUsageInvalid`, `EndEntityUsedAsCa`, `InvalidNetworkMaskConstraint`, `IssuerKeyUsageInvalid`, `MalformedNameConstraint`, `MaximumPathDepthExceeded`, `NameConstraintViolation`, `PathLenConstraintViolated`, `RequiredEkuNotFound`, `RequiredPolicyNotMet`, `TrustAnchorKeyUsageInvalid`, `TrustAnchorNotCa`, `UnsupportedNameType` |
+/// | [`ResourceLimit`][FailureCategory::ResourceLimit] | `MaximumCrlSizeExceeded`, `MaximumNameConstraintComparisonsExceeded`, `MaximumPathBuildCallsExceeded`, `MaximumSignatureChecksExceeded` |
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum FailureCategory {
+    /// The input is malformed, or uses an encoding or feature webpki does not support.
+    Format,
+    /// A signature is invalid, or made with an unsupported or mismatched algorithm.
+    Signature,
+    /// A certificate is outside, or has an invalid, validity period.
+    ValidityInterval,
+    /// A certificate is revoked, or its revocation status could not be established.
+    Revocation,
+    /// No path from the certificate to a trust anchor could be built by issuer name.
+    NameChaining,
+    /// A basic, name, key usage, extended key usage or path length constraint is violated, including the requirement that the end-entity be valid for the requested name.
+    Constraints,
+    /// Verification was abandoned after exceeding one of webpki's resource limits.
+    ResourceLimit,
+}
+
 impl From<Error> for ControlFlow<Error, Error> {
     fn from(value: Error) -> Self {
         match value {
@@ -289,17 +713,199 @@ impl From<Error> for ControlFlow<Error, Error> {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        let msg = match self {
+            Error::BadDer => "invalid DER encoding",
+            Error::BadDerTime => "invalid DER encoding of a time",
+            Error::BerEncodingNotAllowed { offset } => {
+                return write!(
+                    f,
+                    "BER encoding construct not allowed in DER at offset {offset}"
+                )
+            }
+            Error::CaConstraintsNotCritical { depth } => {
+                return write!(
+                    f,
+                    "basicConstraints of the CA certificate at depth {depth} is not critical"
+                )
+            }
+            Error::CaUsedAsEndEntity => "CA certificate used as an end-entity certificate",
+            Error::BundleCertNotCa { index } => {
+                return write!(
+                    f,
+                    "certificate {index} of the trust anchor bundle is not a CA certificate"
+                )
+            }
+            Error::CertExpired { time, not_after } => {
+                return write!(
+                    f,
+                    "certificate expired: verified at {} but notAfter is {} (Unix time)",
+                    time.as_secs(),
+                    not_after.as_secs()
+                )
+            }
+            Error::CertNotValidForName => "certificate not valid for the name",
+            Error::CertNotValidYet { time, not_before } => {
+                return write!(
+                    f,
+                    "certificate not valid yet: verified at {} but notBefore is {} (Unix time)",
+                    time.as_secs(),
+                    not_before.as_secs()
+                )
+            }
+            Error::CertRevoked => "certificate revoked",
+            Error::CrlExpired => "CRL expired",
+            Error::DeltaCrlMismatch => "delta CRL does not apply to the base CRL",
+            Error::DeprecatedUniqueIdentifiersPresent => {
+                "certificate has deprecated issuer or subject unique identifiers"
+            }
+            Error::EkuCriticalityMismatch => {
+                "extendedKeyUsage criticality of the end-entity certificate is not as required"
+            }
+            Error::EndEntityKeyUsageInvalid => {
+                "keyUsage of the end-entity certificate lacks digitalSignature"
+            }
+            Error::EmptyIssuer => "certificate has an empty issuer",
+            Error::EmptySubjectAltName => "certificate has an empty subjectAltName",
+            Error::EmptySubjectWithoutCriticalSan => {
+                "certificate has an empty subject but no critical subjectAltName"
+            }
+            Error::EndEntityUsedAsCa => "end-entity certificate used as a CA certificate",
+            Error::ExtensionValueInvalid => "invalid extension value",
+            Error::ImproperTimeEncoding { offset } => {
+                return write!(f, "improperly encoded time at offset {offset}")
+            }
+            Error::IntermediatesOutOfOrder => "intermediates are not in path order",
+            Error::InvalidCertValidity => "certificate validity period is invalid",
+            Error::InvalidCrlNumber => "invalid CRL number",
+            Error::InvalidCrlValidityPeriod => "CRL validity period is invalid",
+            Error::InvalidNetworkMaskConstraint => "invalid iPAddress name constraint",
+            Error::InvalidSerialNumber => "invalid serial number",
+            Error::InvalidCrlSignatureForPublicKey => "invalid CRL signature for the public key",
+            Error::InvalidSignatureForPublicKey => "invalid signature for the public key",
+            Error::InvalidValidityPeriod => "certificate notAfter is earlier than its notBefore",
+            Error::IssuerKeyUsageInvalid => "keyUsage of the issuer lacks keyCertSign",
+            Error::IssuerNotCrlSigner => "keyUsage of the CRL issuer lacks cRLSign",
+            Error::KeyIdentifierMismatch => {
+                "authorityKeyIdentifier does not match the issuer's subjectKeyIdentifier"
+            }
+            Error::MalformedDnsIdentifier => "malformed DNS identifier",
+            Error::MalformedEmailIdentifier => "malformed email address",
+            Error::MalformedDnsNameInSan => "empty dNSName in subjectAltName",
+            Error::MalformedOtherNameInSan => "malformed otherName in subjectAltName",
+            Error::MalformedSctList => "malformed signed certificate timestamp list",
+            Error::MalformedExtensions => "malformed certificate extensions",
+            Error::MalformedIpAddress => "malformed iPAddress name",
+            Error::MalformedBundleCert { index } => {
+                return write!(
+                    f,
+                    "certificate {index} of the trust anchor bundle is malformed"
+                )
+            }
+            Error::MalformedNameConstraint => "malformed name constraint",
+            Error::MalformedOcspResponse => "malformed OCSP response",
+            Error::MaximumCrlSizeExceeded => "CRL exceeds the size limits",
+            Error::MaximumNameConstraintComparisonsExceeded => {
+                "too many name constraint comparisons"
+            }
+            Error::MaximumPathBuildCallsExceeded => "too many candidate issuers considered",
+            Error::MaximumPathDepthExceeded => "certificate path too long",
+            Error::MaximumSignatureChecksExceeded => "too many signatures checked",
+            Error::MisalignedOcspResponse { depth } => {
+                return write!(
+                    f,
+                    "OCSP response given for depth {depth} is for another certificate"
+                )
+            }
+            Error::NameConstraintViolation => "name constraint violated",
+            Error::NonPositiveSerialNumber => "certificate serial number not positive",
+            Error::OcspResponderNotAuthorized => "OCSP responder not authorized",
+            Error::OcspResponseCertMismatch => "OCSP response is for another certificate",
+            Error::OcspResponseExpired => "OCSP response expired",
+            Error::OcspResponseNotValidYet => "OCSP response not valid yet",
+            Error::PathLenConstraintViolated => "path length constraint violated",
+            Error::PublicKeyTooSmall => "public key is too small",
+            Error::RequiredEkuNotFound => "certificate not valid for the required key usage",
+            Error::RequiredPolicyNotMet => "path not valid for the required certificate policies",
+            Error::SerialNumberTooLong => "certificate serial number longer than 20 octets",
+            Error::SignatureAlgorithmMismatch => {
+                "certificate signature algorithms in tbsCertificate and signature differ"
+            }
+            Error::SurplusOcspResponses => "more OCSP responses than certificates in the path",
+            Error::TrailingData(type_id) => {
+                return write!(f, "trailing data after DER-encoded {type_id:?}")
+            }
+            Error::TrustAnchorKeyUsageInvalid => "keyUsage of the trust anchor lacks keyCertSign",
+            Error::TrustAnchorNotCa => "trust anchor is not a CA certificate",
+            Error::TrustAnchorNotSelfIssued => "trust anchor is not self-issued",
+            Error::TrustAnchorNotSelfSigned => "trust anchor is not self-signed",
+            Error::UnknownIssuer => "unknown issuer",
+            Error::UnknownRevocationStatus => "unknown revocation status",
+            Error::UnsuccessfulOcspResponse => "OCSP responder could not answer",
+            Error::UnsupportedCertVersion => "unsupported certificate version",
+            Error::UnsupportedCertVersionForCa => "unsupported version for a CA certificate",
+            Error::UnsupportedCriticalExtension => "unsupported critical extension",
+            Error::UnsupportedSctVersion => "unsupported signed certificate timestamp version",
+            Error::UnsupportedCrlIssuingDistributionPoint => {
+                "unsupported CRL issuing distribution point"
+            }
+            Error::UnsupportedCrlVersion => "unsupported CRL version",
+            Error::UnsupportedDeltaCrl => "delta CRL used where a complete CRL is required",
+            Error::UnsupportedIndirectCrl => "unsupported indirect CRL",
+            Error::UnsupportedNameType => "unsupported name type",
+            Error::UnsupportedOcspResponseType => "unsupported OCSP response type",
+            Error::UnsupportedRevocationReason => "unsupported revocation reason",
+            Error::UnsupportedRevocationReasonsPartitioning => {
+                "unsupported CRL partitioning by revocation reasons"
+            }
+            Error::UnsupportedCrlSignatureAlgorithm => "unsupported CRL signature algorithm",
+            Error::UnsupportedSignatureAlgorithm => "unsupported signature algorithm",
+            Error::UnsupportedCrlSignatureAlgorithmForPublicKey => {
+                "CRL signature algorithm unsupported for the public key"
+            }
+            Error::UnsupportedSignatureAlgorithmForPublicKey => {
+                "signature algorithm unsupported for the public key"
+            }
+            Error::ValidityPeriodTooLong { depth, role } => {
+                let role = match role {
+                    CertRole::EndEntity => "end-entity",
+                    CertRole::Intermediate => "intermediate",
+                };
+                return write!(
+                    f,
+                    "validity period of the {role} certificate at depth {depth} is too long"
+                );
+            }
+        };
+        f.write_str(msg)
     }
 }
 
 #[cfg(feature = "std")]
 impl ::std::error::Error for Error {}
 
+/// The position of a certificate in a path, as reported by [`Error::ValidityPeriodTooLong`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CertRole {
+    /// The end-entity certificate being verified.
+    EndEntity,
+    /// An intermediate certificate.
+    Intermediate,
+}
+
 /// Trailing data was found while parsing DER-encoded input for the named type.
 #[allow(missing_docs)]
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum DerTypeId {
     BitString,
     Bool,
@@ -328,3 +934,168 @@ pub enum DerTypeId {
     RevokedCertEntry,
     IssuingDistributionPoint,
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::format;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn categories_match_documented_table() {
+        // Every row of the table in `FailureCategory`'s documentation.
+        let mut documented = Vec::<(String, String)>::new();
+        for row in include_str!("error.rs")
+            .lines()
+            .filter_map(|line| line.strip_prefix("/// | [`"))
+        {
+            let (category, errors) = row.split_once('`').unwrap();
+            let errors = errors.split_once("] | ").unwrap().1.trim_end_matches(" |");
+            for err in errors.split(", ") {
+                documented.push((err.trim_matches('`').to_string(), category.to_string()));
+            }
+        }
+        let all_errors = all_errors();
+        assert_eq!(documented.len(), all_errors.len());
+
+        for err in all_errors {
+            let debug = format!("{:?}", err);
+            let name = debug.split([' ', '(']).next().unwrap();
+            let category = match documented.iter().find(|(documented, _)| documented == name) {
+                Some((_, category)) => category,
+                None => panic!("{} is not documented", name),
+            };
+            assert_eq!(&format!("{:?}", err.category()), category, "{}", name);
+        }
+    }
+
+    #[test]
+    fn display_messages_are_distinct() {
+        let messages = all_errors()
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+        for (i, message) in messages.iter().enumerate() {
+            assert!(!messages[..i].contains(message), "{message}");
+        }
+
+        let time = UnixTime::since_unix_epoch(core::time::Duration::from_secs(1_700_000_000));
+        let not_after = UnixTime::since_unix_epoch(core::time::Duration::from_secs(1_600_000_000));
+        assert_eq!(
+            Error::CertExpired { time, not_after }.to_string(),
+            "certificate expired: verified at 1700000000 but notAfter is 1600000000 (Unix time)"
+        );
+        assert_eq!(
+            Error::ValidityPeriodTooLong {
+                depth: 1,
+                role: CertRole::Intermediate
+            }
+            .to_string(),
+            "validity period of the intermediate certificate at depth 1 is too long"
+        );
+    }
+
+    fn all_errors() -> Vec<Error> {
+        let time = UnixTime::since_unix_epoch(core::time::Duration::from_secs(0));
+        vec![
+            Error::BadDer,
+            Error::BadDerTime,
+            Error::BerEncodingNotAllowed { offset: 0 },
+            Error::CaConstraintsNotCritical { depth: 1 },
+            Error::CaUsedAsEndEntity,
+            Error::CertExpired {
+                time,
+                not_after: time,
+            },
+            Error::CertNotValidForName,
+            Error::CertNotValidYet {
+                time,
+                not_before: time,
+            },
+            Error::CertRevoked,
+            Error::CrlExpired,
+            Error::DeltaCrlMismatch,
+            Error::DeprecatedUniqueIdentifiersPresent,
+            Error::EkuCriticalityMismatch,
+            Error::EmptyIssuer,
+            Error::EmptySubjectAltName,
+            Error::EmptySubjectWithoutCriticalSan,
+            Error::EndEntityKeyUsageInvalid,
+            Error::MalformedBundleCert { index: 1 },
+            Error::BundleCertNotCa { index: 2 },
+            Error::EndEntityUsedAsCa,
+            Error::ExtensionValueInvalid,
+            Error::ImproperTimeEncoding { offset: 0 },
+            Error::IntermediatesOutOfOrder,
+            Error::InvalidCertValidity,
+            Error::InvalidCrlNumber,
+            Error::InvalidCrlValidityPeriod,
+            Error::InvalidNetworkMaskConstraint,
+            Error::InvalidSerialNumber,
+            Error::InvalidCrlSignatureForPublicKey,
+            Error::InvalidSignatureForPublicKey,
+            Error::InvalidValidityPeriod,
+            Error::IssuerKeyUsageInvalid,
+            Error::IssuerNotCrlSigner,
+            Error::KeyIdentifierMismatch,
+            Error::MalformedDnsIdentifier,
+            Error::MalformedDnsNameInSan,
+            Error::MalformedEmailIdentifier,
+            Error::MalformedExtensions,
+            Error::MalformedIpAddress,
+            Error::MalformedOtherNameInSan,
+            Error::MalformedSctList,
+            Error::MalformedNameConstraint,
+            Error::MalformedOcspResponse,
+            Error::MaximumCrlSizeExceeded,
+            Error::MaximumNameConstraintComparisonsExceeded,
+            Error::MaximumPathBuildCallsExceeded,
+            Error::MaximumPathDepthExceeded,
+            Error::MaximumSignatureChecksExceeded,
+            Error::MisalignedOcspResponse { depth: 0 },
+            Error::NameConstraintViolation,
+            Error::NonPositiveSerialNumber,
+            Error::OcspResponderNotAuthorized,
+            Error::OcspResponseCertMismatch,
+            Error::OcspResponseExpired,
+            Error::OcspResponseNotValidYet,
+            Error::PathLenConstraintViolated,
+            Error::PublicKeyTooSmall,
+            Error::RequiredEkuNotFound,
+            Error::RequiredPolicyNotMet,
+            Error::SerialNumberTooLong,
+            Error::SignatureAlgorithmMismatch,
+            Error::SurplusOcspResponses,
+            Error::TrailingData(DerTypeId::Certificate),
+            Error::TrustAnchorKeyUsageInvalid,
+            Error::TrustAnchorNotCa,
+            Error::TrustAnchorNotSelfIssued,
+            Error::TrustAnchorNotSelfSigned,
+            Error::UnknownIssuer,
+            Error::UnknownRevocationStatus,
+            Error::UnsuccessfulOcspResponse,
+            Error::UnsupportedCertVersion,
+            Error::UnsupportedCertVersionForCa,
+            Error::UnsupportedCriticalExtension,
+            Error::UnsupportedSctVersion,
+            Error::UnsupportedCrlIssuingDistributionPoint,
+            Error::UnsupportedCrlVersion,
+            Error::UnsupportedDeltaCrl,
+            Error::UnsupportedIndirectCrl,
+            Error::UnsupportedNameType,
+            Error::UnsupportedOcspResponseType,
+            Error::UnsupportedRevocationReason,
+            Error::UnsupportedRevocationReasonsPartitioning,
+            Error::UnsupportedCrlSignatureAlgorithm,
+            Error::UnsupportedSignatureAlgorithm,
+            Error::UnsupportedCrlSignatureAlgorithmForPublicKey,
+            Error::UnsupportedSignatureAlgorithmForPublicKey,
+            Error::ValidityPeriodTooLong {
+                depth: 0,
+                role: CertRole::EndEntity,
+            },
+        ]
+    }
+}
@@ -25,6 +25,27 @@
 //! | `std` | Enable features that require libstd. Implies `alloc`. |
 //! | `ring` | Enable use of the *ring* crate for cryptography. |
 //! | `aws_lc_rs` | Enable use of the aws-lc-rs crate for cryptography. |
+//! | `legacy_sha1` | Enable the SHA-1 signature algorithms, for closed networks with legacy PKIs only. They are never in `ALL_VERIFICATION_ALGS`. |
+//! | `capi` | Enable the C API in the `capi` module. Implies `std`. |
+//! | `serde` | Enable the serializable verification reports in the `report` module, and implement `Serialize` for inspection types such as `Error` and `SubjectAltName`. Implies `alloc`. |
+//! | `testing` | Enable the JSON test vector runner in the `testing` module. Implies `std` and `serde`. |
+//! | `zeroize` | Overwrite owned buffers copied from certificates and CRLs with zeros when they are dropped. Implies `alloc`. |
+//!
+//! Neither `ring` nor `aws_lc_rs` is required: every verification entry point takes the
+//! supported algorithms as `&[&dyn SignatureVerificationAlgorithm]`, so callers can supply
+//! their own implementations of the `pki_types` trait. An algorithm is chosen only by comparing
+//! its `signature_alg_id()` and `public_key_alg_id()` with the encoded algorithm identifiers of
+//! the signature and public key, so an implementation of an algorithm webpki doesn't know, such
+//! as ML-DSA, can be used without changes to webpki.
+//!
+//! Parsing an end-entity certificate, verifying its path through a slice of intermediates and
+//! checking its subject name need neither `alloc` nor the heap, so webpki can be used without
+//! an allocator with non-RSA signature algorithms. Path building uses a fixed-capacity stack,
+//! bounded by the maximum path length.
+//!
+//! No input makes parsing or verification panic. The DER parser doesn't recurse: each
+//! structure is read by code written for it, so the nesting depth of the input can't exhaust
+//! the stack. The fuzz targets in the repository's `fuzz` directory exercise this.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(unreachable_pub)]
@@ -49,15 +70,39 @@ mod der;
 
 #[cfg(feature = "aws_lc_rs")]
 mod aws_lc_rs_algs;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "capi")]
+pub mod capi;
 mod cert;
+mod constant_time;
+pub mod dangerous;
 mod end_entity;
 mod error;
+mod lint;
+#[cfg(feature = "alloc")]
+pub mod name;
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+mod ocsp;
+mod policy;
+#[cfg(feature = "alloc")]
+mod precheck;
+pub mod raw_public_key;
+#[cfg(feature = "serde")]
+pub mod report;
+#[cfg(feature = "alloc")]
+mod resumable;
 #[cfg(feature = "ring")]
 mod ring_algs;
+mod sct;
 mod signed_data;
 mod subject_name;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod time;
 mod trust_anchor;
+#[cfg(feature = "alloc")]
+mod zeroize;
 
 mod crl;
 mod verify_cert;
@@ -67,28 +112,59 @@ mod x509;
 pub(crate) mod test_utils;
 
 pub use {
-    cert::Cert,
+    cert::{split_der_certificates, AuthorityKeyIdentifier, BasicConstraints, Cert},
     crl::{
-        BorrowedCertRevocationList, BorrowedRevokedCert, CertRevocationList, RevocationCheckDepth,
-        RevocationOptions, RevocationOptionsBuilder, RevocationReason, UnknownStatusPolicy,
+        BorrowedCertRevocationList, BorrowedRevokedCert, CertRevocationList, ExpirationPolicy,
+        RevocationCheckDepth, RevocationDatePolicy, RevocationOptions, RevocationOptionsBuilder,
+        RevocationReason, RevocationStatus, UnknownStatusPolicy,
     },
-    end_entity::EndEntityCert,
-    error::{DerTypeId, Error},
-    signed_data::alg_id,
-    trust_anchor::anchor_from_trusted_cert,
-    verify_cert::KeyUsage,
+    end_entity::{verify_code_signing_cert, verify_signed_data, EndEntityCert, Verifier},
+    error::{CertRole, DerTypeId, Error, FailureCategory},
+    lint::{Lint, SanLint},
+    sct::SignedCertificateTimestamp,
+    signed_data::{alg_id, EcCurve, PublicKeyInfo},
+    subject_name::{parse_dns_name, DnsNameError, SubjectAltName},
+    trust_anchor::{
+        anchor_from_trusted_cert, anchor_from_trusted_cert_checked, AnchorPolicy, TrustAnchorSource,
+    },
+    verify_cert::{
+        DetailedError, EkuCriticality, EndEntityKeyUsage, FailedCert, HookDecision, InspectionHook,
+        KeyUsage, MaxValidity, SerialNumberPolicy, VerificationPolicy,
+    },
+    x509::{Extensions, RawExtension},
 };
 
 pub use pki_types as types;
 
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+pub use ocsp::OcspCertStatus;
+#[cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+pub use ocsp::OcspHash;
+
 #[cfg(feature = "alloc")]
-pub use crl::{OwnedCertRevocationList, OwnedRevokedCert};
+pub use {
+    crl::{CrlSet, OwnedCertRevocationList, OwnedRevokedCert},
+    precheck::{lint_chain, precheck_end_entity, ChainReport, Finding, PrecheckPolicy, Severity},
+    resumable::{
+        NeededResource, Resource, ResumableVerifier, VerificationOutcome, VerificationSuspended,
+    },
+    subject_name::{
+        ConstraintBase, ConstraintSource, EnforcedNameConstraints, InvalidNameContext, NameSubtree,
+        PresentedName, SubtreeKind,
+    },
+    trust_anchor::{extract_trust_anchors_from_pkcs7, NonCaCertPolicy, TrustAnchorSet},
+    verify_cert::RevocationEndpoints,
+};
+
+#[cfg(feature = "std")]
+pub use {cache::VerificationCache, crl::CrlLimits};
 
 #[cfg(feature = "ring")]
 /// Signature verification algorithm implementations using the *ring* crypto library.
 pub mod ring {
     pub use super::ring_algs::{
-        ECDSA_P256_SHA256, ECDSA_P256_SHA384, ECDSA_P384_SHA256, ECDSA_P384_SHA384, ED25519,
+        ALL_VERIFICATION_ALGS, ECDSA_P256_SHA256, ECDSA_P256_SHA384, ECDSA_P384_SHA256,
+        ECDSA_P384_SHA384, ED25519,
     };
 
     #[cfg(feature = "alloc")]
@@ -97,22 +173,32 @@ pub mod ring {
         RSA_PKCS1_3072_8192_SHA384, RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
         RSA_PSS_2048_8192_SHA384_LEGACY_KEY, RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
     };
+
+    #[cfg(all(feature = "alloc", feature = "legacy_sha1"))]
+    pub use super::ring_algs::RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY;
 }
 
 #[cfg(feature = "aws_lc_rs")]
 /// Signature verification algorithm implementations using the aws-lc-rs crypto library.
 pub mod aws_lc_rs {
     pub use super::aws_lc_rs_algs::{
-        ECDSA_P256_SHA256, ECDSA_P256_SHA384, ECDSA_P384_SHA256, ECDSA_P384_SHA384, ED25519,
-        RSA_PKCS1_2048_8192_SHA256, RSA_PKCS1_2048_8192_SHA384, RSA_PKCS1_2048_8192_SHA512,
-        RSA_PKCS1_3072_8192_SHA384, RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
-        RSA_PSS_2048_8192_SHA384_LEGACY_KEY, RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
+        ALL_VERIFICATION_ALGS, ECDSA_P256_SHA256, ECDSA_P256_SHA384, ECDSA_P384_SHA256,
+        ECDSA_P384_SHA384, ECDSA_P521_SHA256, ECDSA_P521_SHA384, ECDSA_P521_SHA512, ED25519,
+        FIPS_VERIFICATION_ALGS, RSA_PKCS1_2048_8192_SHA256, RSA_PKCS1_2048_8192_SHA384,
+        RSA_PKCS1_2048_8192_SHA512, RSA_PKCS1_3072_8192_SHA384,
+        RSA_PSS_2048_8192_SHA256_LEGACY_KEY, RSA_PSS_2048_8192_SHA384_LEGACY_KEY,
+        RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
     };
+
+    #[cfg(feature = "legacy_sha1")]
+    pub use super::aws_lc_rs_algs::RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY;
 }
 
 /// An array of all the verification algorithms exported by this crate.
 ///
-/// This will be empty if the crate is built without the `ring` and `aws_lc_rs` features.
+/// This will be empty if the crate is built without the `ring` and `aws_lc_rs` features. It
+/// is `ring::ALL_VERIFICATION_ALGS` followed by `aws_lc_rs::ALL_VERIFICATION_ALGS`, for
+/// the backends enabled; use one of those to verify with only one backend.
 pub static ALL_VERIFICATION_ALGS: &[&dyn types::SignatureVerificationAlgorithm] = &[
     #[cfg(feature = "ring")]
     ring::ECDSA_P256_SHA256,
@@ -147,6 +233,12 @@ pub static ALL_VERIFICATION_ALGS: &[&dyn types::SignatureVerificationAlgorithm]
     #[cfg(feature = "aws_lc_rs")]
     aws_lc_rs::ECDSA_P384_SHA384,
     #[cfg(feature = "aws_lc_rs")]
+    aws_lc_rs::ECDSA_P521_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    aws_lc_rs::ECDSA_P521_SHA384,
+    #[cfg(feature = "aws_lc_rs")]
+    aws_lc_rs::ECDSA_P521_SHA512,
+    #[cfg(feature = "aws_lc_rs")]
     aws_lc_rs::ED25519,
     #[cfg(feature = "aws_lc_rs")]
     aws_lc_rs::RSA_PKCS1_2048_8192_SHA256,
@@ -167,3 +259,13 @@ pub static ALL_VERIFICATION_ALGS: &[&dyn types::SignatureVerificationAlgorithm]
 fn public_values_eq(a: untrusted::Input<'_>, b: untrusted::Input<'_>) -> bool {
     a.as_slice_less_safe() == b.as_slice_less_safe()
 }
+
+/// Returns a value never before returned in this process, identifying one version of the
+/// contents of a [`TrustAnchorSet`] or [`CrlSet`].
+#[cfg(feature = "alloc")]
+fn next_generation() -> usize {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
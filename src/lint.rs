@@ -0,0 +1,156 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use pki_types::IpAddr;
+
+use crate::cert::Cert;
+use crate::subject_name::SubjectAltName;
+
+/// A problem with a certificate that webpki tolerates by default, but which indicates
+/// the certificate was likely misissued.
+///
+/// Lints for a certificate are available from [`Cert::lints()`]. Verifying with a
+/// [`crate::VerificationPolicy::strict()`] policy turns each of these into an [`crate::Error`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Lint {
+    /// The certificate has an empty subject, but no critical subjectAltName extension.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.6>.
+    EmptySubjectWithoutCriticalSan,
+
+    /// The certificate's subjectAltName extension contains a dNSName that is empty or
+    /// consists only of whitespace.
+    MalformedDnsNameInSan,
+
+    /// The certificate's subjectAltName extension contains an otherName whose contents are not
+    /// a valid `type-id` and `value`.
+    ///
+    /// webpki never matches otherNames, so this doesn't prevent matching names of other types.
+    MalformedOtherNameInSan,
+
+    /// The certificate has a subjectAltName extension that contains no names.
+    ///
+    /// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.6>.
+    EmptySubjectAltName,
+}
+
+/// A problem with the names in a certificate's subjectAltName extension, as reported by
+/// `EndEntityCert::lint_subject_alt_names()`, following the CA/Browser Forum's certificate
+/// linters.
+///
+/// webpki tolerates all of these. Only [`SanLint::EmptySubjectAltName`] is also a [`Lint`], and
+/// so rejected by a [`crate::VerificationPolicy::strict()`] policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SanLint<'a> {
+    /// The extension contains no names, although RFC 5280 requires at least one.
+    EmptySubjectAltName,
+
+    /// The name is identical to one earlier in the extension.
+    DuplicateName(SubjectAltName<'a>),
+
+    /// The dNSName differs only in ASCII case from one earlier in the extension.
+    CaseVariantDnsName(&'a str),
+
+    /// The dNSName is an IP address, which should have been encoded as an iPAddress.
+    IpAddressInDnsName(&'a str),
+
+    /// The extension is critical, although the certificate's subject is not empty.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.6>: "When including the
+    /// subjectAltName extension in a certificate that has a non-empty subject distinguished
+    /// name, conforming CAs SHOULD mark the subjectAltName extension as non-critical."
+    CriticalWithSubject,
+
+    /// The extension is not critical, although the certificate's subject is empty. This is
+    /// also reported as [`Lint::EmptySubjectWithoutCriticalSan`].
+    NotCriticalWithoutSubject,
+}
+
+pub(crate) fn check<'a>(cert: &'a Cert<'_>) -> impl Iterator<Item = Lint> + 'a {
+    CHECKS.iter().filter_map(move |check| check(cert))
+}
+
+static CHECKS: &[fn(&Cert<'_>) -> Option<Lint>] = &[
+    empty_subject_without_critical_san,
+    malformed_dns_name_in_san,
+    malformed_other_name_in_san,
+    empty_subject_alt_name,
+];
+
+fn empty_subject_without_critical_san(cert: &Cert<'_>) -> Option<Lint> {
+    match cert.has_empty_subject_without_critical_san() {
+        true => Some(Lint::EmptySubjectWithoutCriticalSan),
+        false => None,
+    }
+}
+
+fn malformed_dns_name_in_san(cert: &Cert<'_>) -> Option<Lint> {
+    match cert.has_malformed_dns_name_in_san() {
+        true => Some(Lint::MalformedDnsNameInSan),
+        false => None,
+    }
+}
+
+fn malformed_other_name_in_san(cert: &Cert<'_>) -> Option<Lint> {
+    match cert.has_malformed_other_name_in_san() {
+        true => Some(Lint::MalformedOtherNameInSan),
+        false => None,
+    }
+}
+
+fn empty_subject_alt_name(cert: &Cert<'_>) -> Option<Lint> {
+    match cert.has_empty_subject_alt_name() {
+        true => Some(Lint::EmptySubjectAltName),
+        false => None,
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn check_subject_alt_names<'a>(cert: &Cert<'a>) -> Vec<SanLint<'a>> {
+    let mut lints = Vec::new();
+    if cert.subject_alt_name.is_none() {
+        return lints;
+    }
+
+    match (cert.subject.is_empty(), cert.subject_alt_name_critical) {
+        (false, true) => lints.push(SanLint::CriticalWithSubject),
+        (true, false) => lints.push(SanLint::NotCriticalWithoutSubject),
+        _ => {}
+    }
+    if cert.has_empty_subject_alt_name() {
+        lints.push(SanLint::EmptySubjectAltName);
+    }
+
+    // A malformed extension is reported by whichever operation needs its names: only the
+    // names before the malformed one are checked here.
+    let names = cert
+        .subject_alternative_names()
+        .map_while(Result::ok)
+        .collect::<Vec<_>>();
+    for (i, name) in names.iter().enumerate() {
+        let earlier = &names[..i];
+        if earlier.contains(name) {
+            lints.push(SanLint::DuplicateName(*name));
+            continue;
+        }
+
+        let dns_name = match name {
+            SubjectAltName::DnsName(dns_name) => *dns_name,
+            _ => continue,
+        };
+        if earlier.iter().any(|earlier| match earlier {
+            SubjectAltName::DnsName(earlier) => earlier.eq_ignore_ascii_case(dns_name),
+            _ => false,
+        }) {
+            lints.push(SanLint::CaseVariantDnsName(dns_name));
+        }
+        if IpAddr::try_from(dns_name).is_ok() {
+            lints.push(SanLint::IpAddressInDnsName(dns_name));
+        }
+    }
+
+    lints
+}
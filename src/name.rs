@@ -0,0 +1,482 @@
+//! Parsing of distinguished names, and rendering them as strings.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::der::{self, Tag};
+use crate::error::Error;
+
+/// Renders the DER-encoded distinguished name `der` as a string, as described in
+/// [RFC 4514](https://www.rfc-editor.org/rfc/rfc4514).
+///
+/// `der` may be either a complete `Name`, or the contents of one as returned by
+/// [`crate::Cert::subject()`] and [`crate::Cert::issuer()`]. The relative distinguished
+/// names are rendered last first and separated by `,`; the attributes of a multi-valued
+/// RDN are separated by `+`.
+///
+/// The attribute types in RFC 4514 section 3 are rendered by their short names (`CN`, `O`,
+/// and so on); others are rendered as dotted OIDs. A value that is a PrintableString,
+/// UTF8String, IA5String or BMPString of a named attribute type is rendered as an escaped
+/// string, and any other value as `#` followed by its hex-encoded DER encoding.
+///
+/// Returns [`Error::BadDer`] if `der` is not a valid name, or contains a string that is
+/// not valid for its type.
+pub fn format_dn(der: &[u8]) -> Result<String, Error> {
+    let name = name_contents(der)?;
+    let mut rdns = Vec::new();
+    name.read_all(Error::BadDer, |reader| {
+        while !reader.at_end() {
+            let rdn = der::expect_tag(reader, Tag::Set)?;
+            let mut out = String::new();
+            rdn.read_all(Error::BadDer, |rdn| {
+                loop {
+                    let attribute = der::expect_tag(rdn, Tag::Sequence)?;
+                    format_attribute(attribute, &mut out)?;
+                    if rdn.at_end() {
+                        break;
+                    }
+                    out.push('+');
+                }
+                Ok(())
+            })?;
+            rdns.push(out);
+        }
+        Ok(())
+    })?;
+
+    rdns.reverse();
+    Ok(rdns.join(","))
+}
+
+/// Iterates over the attributes of the DER-encoded distinguished name `der`, in the order they
+/// are encoded: the most significant relative distinguished name (RDN) first, and the
+/// attributes of a multi-valued RDN in the order they appear within it.
+///
+/// `der` may be either a complete `Name`, or the contents of one as returned by
+/// [`crate::Cert::subject()`] and [`crate::Cert::issuer()`]. Once `der` is found to be
+/// malformed, the iterator yields [`Error::BadDer`] and then ends.
+pub fn attributes(der: &[u8]) -> NameAttributes<'_> {
+    NameAttributes {
+        rdns: name_contents(der).map(untrusted::Reader::new),
+        rdn: None,
+        rdn_count: 0,
+    }
+}
+
+/// An iterator over the attributes of a distinguished name, returned by [`attributes()`].
+pub struct NameAttributes<'a> {
+    /// The RDNs not yet read, or the error to yield if the name is not a valid SEQUENCE.
+    rdns: Result<untrusted::Reader<'a>, Error>,
+    /// The attributes not yet read of the current RDN.
+    rdn: Option<untrusted::Reader<'a>>,
+    /// The number of RDNs read so far.
+    rdn_count: usize,
+}
+
+impl<'a> NameAttributes<'a> {
+    fn read(&mut self) -> Result<Option<NameAttribute<'a>>, Error> {
+        let rdns = match &mut self.rdns {
+            Ok(rdns) => rdns,
+            Err(err) => return Err(*err),
+        };
+        let rdn = match &mut self.rdn {
+            Some(rdn) if !rdn.at_end() => rdn,
+            _ if rdns.at_end() => return Ok(None),
+            _ => {
+                let rdn = der::expect_tag(rdns, Tag::Set)?;
+                // An RDN has at least one attribute.
+                if rdn.is_empty() {
+                    return Err(Error::BadDer);
+                }
+                self.rdn_count += 1;
+                self.rdn.insert(untrusted::Reader::new(rdn))
+            }
+        };
+
+        der::nested(rdn, Tag::Sequence, Error::BadDer, |attribute| {
+            let oid = der::expect_tag(attribute, Tag::OID)?;
+            let (tag, value) = der::read_tag_and_get_value(attribute)?;
+            Ok(Some(NameAttribute {
+                rdn: self.rdn_count - 1,
+                oid: oid.as_slice_less_safe(),
+                tag,
+                value: value.as_slice_less_safe(),
+            }))
+        })
+    }
+}
+
+impl<'a> Iterator for NameAttributes<'a> {
+    type Item = Result<NameAttribute<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.read();
+        if result.is_err() {
+            // Stop after the first error.
+            self.rdns = Ok(untrusted::Reader::new(untrusted::Input::from(&[])));
+            self.rdn = None;
+        }
+        result.transpose()
+    }
+}
+
+/// An attribute of a distinguished name, as yielded by [`attributes()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NameAttribute<'a> {
+    rdn: usize,
+    oid: &'a [u8],
+    tag: u8,
+    value: &'a [u8],
+}
+
+impl<'a> NameAttribute<'a> {
+    /// The index of the RDN this attribute belongs to, counting from 0 for the first encoded.
+    /// Consecutive attributes with the same index belong to the same multi-valued RDN.
+    pub fn rdn(&self) -> usize {
+        self.rdn
+    }
+
+    /// The DER-encoded contents of the attribute type OID, such as those of 2.5.4.3 for the
+    /// common name.
+    pub fn oid(&self) -> &'a [u8] {
+        self.oid
+    }
+
+    /// The ASN.1 tag of the value, such as 0x0c for a UTF8String.
+    pub fn tag(&self) -> u8 {
+        self.tag
+    }
+
+    /// The contents of the DER-encoded value.
+    pub fn value(&self) -> &'a [u8] {
+        self.value
+    }
+
+    /// Decodes the value as a string, if it is a UTF8String, PrintableString, IA5String or
+    /// BMPString; otherwise, returns `Ok(None)`.
+    ///
+    /// Returns [`Error::BadDer`] if the value is not valid for its string type.
+    pub fn decode_string(&self) -> Result<Option<String>, Error> {
+        decode_string(self.tag, self.value)
+    }
+}
+
+/// Returns the contents of `der`, if it is a complete `Name`, or else `der` itself.
+fn name_contents(der: &[u8]) -> Result<untrusted::Input<'_>, Error> {
+    let name = untrusted::Input::from(der);
+    match der.first() == Some(&u8::from(Tag::Sequence)) {
+        true => name.read_all(Error::BadDer, |reader| {
+            der::expect_tag(reader, Tag::Sequence)
+        }),
+        false => Ok(name),
+    }
+}
+
+fn format_attribute(attribute: untrusted::Input<'_>, out: &mut String) -> Result<(), Error> {
+    attribute.read_all(Error::BadDer, |reader| {
+        let oid = der::expect_tag(reader, Tag::OID)?;
+        let (encoded, (tag, value)) = reader.read_partial(der::read_tag_and_get_value)?;
+
+        let short_name = SHORT_NAMES
+            .iter()
+            .find(|(known, _)| *known == oid.as_slice_less_safe())
+            .map(|(_, short_name)| *short_name);
+        let string = match short_name {
+            Some(_) => decode_string(tag, value.as_slice_less_safe())?,
+            None => None,
+        };
+
+        match short_name {
+            Some(short_name) => out.push_str(short_name),
+            None => write_dotted_oid(oid.as_slice_less_safe(), out)?,
+        }
+        out.push('=');
+        match string {
+            Some(string) => escape(&string, out),
+            None => {
+                out.push('#');
+                for byte in encoded.as_slice_less_safe() {
+                    let _ = write!(out, "{:02x}", byte);
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Returns `None` if `tag` is not a string type that is rendered as a string.
+fn decode_string(tag: u8, value: &[u8]) -> Result<Option<String>, Error> {
+    match tag {
+        UTF8_STRING => core::str::from_utf8(value)
+            .map(|value| Some(String::from(value)))
+            .map_err(|_| Error::BadDer),
+        PRINTABLE_STRING | IA5_STRING => match value.is_ascii() {
+            // ASCII is a subset of UTF-8.
+            true => Ok(core::str::from_utf8(value).ok().map(String::from)),
+            false => Err(Error::BadDer),
+        },
+        BMP_STRING => {
+            if value.len() % 2 != 0 {
+                return Err(Error::BadDer);
+            }
+            let units = value
+                .chunks(2)
+                .map(|unit| u16::from_be_bytes([unit[0], unit[1]]));
+            core::char::decode_utf16(units)
+                .collect::<Result<String, _>>()
+                .map(Some)
+                .map_err(|_| Error::BadDer)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Escapes `value` as described in RFC 4514 section 2.4, also escaping control characters.
+fn escape(value: &str, out: &mut String) {
+    for (i, c) in value.char_indices() {
+        let first = i == 0;
+        let last = i + c.len_utf8() == value.len();
+        match c {
+            '"' | '+' | ',' | ';' | '<' | '>' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '#' if first => out.push_str("\\#"),
+            ' ' if first || last => out.push_str("\\ "),
+            '\u{0}'..='\u{1f}' | '\u{7f}' => {
+                let _ = write!(out, "\\{:02X}", u32::from(c));
+            }
+            _ => out.push(c),
+        }
+    }
+}
+
+pub(crate) fn write_dotted_oid(oid: &[u8], out: &mut String) -> Result<(), Error> {
+    if oid.last().map_or(true, |last| last & 0x80 != 0) {
+        return Err(Error::BadDer);
+    }
+
+    let mut arc = 0u64;
+    let mut arc_start = true;
+    let mut first = true;
+    for byte in oid {
+        // Arcs must be minimally encoded.
+        if arc_start && *byte == 0x80 {
+            return Err(Error::BadDer);
+        }
+        arc = arc
+            .checked_mul(128)
+            .ok_or(Error::BadDer)?
+            .checked_add(u64::from(byte & 0x7f))
+            .ok_or(Error::BadDer)?;
+        arc_start = byte & 0x80 == 0;
+        if !arc_start {
+            continue;
+        }
+
+        if first {
+            // The first encoded arc combines the first two.
+            let top = match arc {
+                0..=39 => 0,
+                40..=79 => 1,
+                _ => 2,
+            };
+            let _ = write!(out, "{}.{}", top, arc - 40 * top);
+            first = false;
+        } else {
+            let _ = write!(out, ".{}", arc);
+        }
+        arc = 0;
+    }
+    Ok(())
+}
+
+/// The attribute types of RFC 4514 section 3.
+static SHORT_NAMES: &[(&[u8], &str)] = &[
+    (&oid![2, 5, 4, 3], "CN"),
+    (&oid![2, 5, 4, 7], "L"),
+    (&oid![2, 5, 4, 8], "ST"),
+    (&oid![2, 5, 4, 10], "O"),
+    (&oid![2, 5, 4, 11], "OU"),
+    (&oid![2, 5, 4, 6], "C"),
+    (&oid![2, 5, 4, 9], "STREET"),
+    (
+        &oid![0, 9, 0x92, 0x26, 0x89, 0x93, 0xf2, 0x2c, 0x64, 1, 25],
+        "DC",
+    ),
+    (
+        &oid![0, 9, 0x92, 0x26, 0x89, 0x93, 0xf2, 0x2c, 0x64, 1, 1],
+        "UID",
+    ),
+];
+
+const UTF8_STRING: u8 = 0x0c;
+const PRINTABLE_STRING: u8 = 0x13;
+const IA5_STRING: u8 = 0x16;
+const BMP_STRING: u8 = 0x1e;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag, u8::try_from(contents.len()).unwrap()];
+        out.extend_from_slice(contents);
+        out
+    }
+
+    fn attribute(oid: &[u8], tag: u8, value: &[u8]) -> Vec<u8> {
+        let mut contents = tlv(0x06, oid);
+        contents.extend(tlv(tag, value));
+        tlv(0x30, &contents)
+    }
+
+    fn rdn(attributes: &[Vec<u8>]) -> Vec<u8> {
+        tlv(0x31, &attributes.concat())
+    }
+
+    const CN: &[u8] = &oid![2, 5, 4, 3];
+    const O: &[u8] = &oid![2, 5, 4, 10];
+
+    #[test]
+    fn rdns_are_reversed() {
+        let name = [
+            rdn(&[attribute(O, PRINTABLE_STRING, b"Example")]),
+            rdn(&[attribute(CN, UTF8_STRING, b"foo")]),
+        ]
+        .concat();
+        assert_eq!(format_dn(&name).unwrap(), "CN=foo,O=Example");
+        // A complete `Name` is accepted too.
+        assert_eq!(format_dn(&tlv(0x30, &name)).unwrap(), "CN=foo,O=Example");
+        assert_eq!(format_dn(&[]).unwrap(), "");
+    }
+
+    #[test]
+    fn multi_valued_rdn() {
+        let name = rdn(&[
+            attribute(CN, UTF8_STRING, b"foo"),
+            attribute(O, UTF8_STRING, b"bar"),
+        ]);
+        assert_eq!(format_dn(&name).unwrap(), "CN=foo+O=bar");
+    }
+
+    #[test]
+    fn escaping() {
+        for (value, expected) in [
+            (&b"#foo"[..], "CN=\\#foo"),
+            (b"f#oo", "CN=f#oo"),
+            (b"a,b+c;d", "CN=a\\,b\\+c\\;d"),
+            (b"\"<a\\b>\"", "CN=\\\"\\<a\\\\b\\>\\\""),
+            (b" a b ", "CN=\\ a b\\ "),
+            (b"a\x00b\x1f\x7f", "CN=a\\00b\\1F\\7F"),
+            ("é".as_bytes(), "CN=é"),
+        ] {
+            let name = rdn(&[attribute(CN, UTF8_STRING, value)]);
+            assert_eq!(format_dn(&name).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn string_types() {
+        let name = rdn(&[attribute(CN, BMP_STRING, &[0x00, b'h', 0x00, 0xe9])]);
+        assert_eq!(format_dn(&name).unwrap(), "CN=hé");
+        let name = rdn(&[attribute(CN, IA5_STRING, b"a@b")]);
+        assert_eq!(format_dn(&name).unwrap(), "CN=a@b");
+
+        // A TeletexString is rendered as hex.
+        let name = rdn(&[attribute(CN, 0x14, b"ab")]);
+        assert_eq!(format_dn(&name).unwrap(), "CN=#14026162");
+
+        for (tag, value) in [
+            (UTF8_STRING, &b"\xff"[..]),
+            (PRINTABLE_STRING, b"\xc3\xa9"),
+            (BMP_STRING, b"\x00"),
+            (BMP_STRING, b"\xd8\x00"),
+        ] {
+            let name = rdn(&[attribute(CN, tag, value)]);
+            assert_eq!(format_dn(&name), Err(Error::BadDer));
+        }
+    }
+
+    #[test]
+    fn unknown_attribute_type() {
+        // emailAddress, 1.2.840.113549.1.9.1
+        let oid = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x01];
+        let name = rdn(&[attribute(&oid, IA5_STRING, b"a@b")]);
+        assert_eq!(
+            format_dn(&name).unwrap(),
+            "1.2.840.113549.1.9.1=#1603614062"
+        );
+
+        for oid in [&[][..], &[0x2a, 0x86], &[0x2a, 0x80, 0x01]] {
+            let name = rdn(&[attribute(oid, UTF8_STRING, b"a")]);
+            assert_eq!(format_dn(&name), Err(Error::BadDer));
+        }
+    }
+
+    #[test]
+    fn attributes_in_order() {
+        let name = [
+            rdn(&[attribute(O, PRINTABLE_STRING, b"Example")]),
+            rdn(&[
+                attribute(CN, UTF8_STRING, b"foo"),
+                attribute(O, BMP_STRING, &[0x00, b'h', 0x00, 0xe9]),
+            ]),
+        ]
+        .concat();
+        let expected = [
+            (0, O, PRINTABLE_STRING, &b"Example"[..]),
+            (1, CN, UTF8_STRING, b"foo"),
+            (1, O, BMP_STRING, &[0x00, b'h', 0x00, 0xe9]),
+        ];
+        for der in [name.clone(), tlv(0x30, &name)] {
+            let attributes = attributes(&der).collect::<Result<Vec<_>, _>>().unwrap();
+            assert_eq!(attributes.len(), expected.len());
+            for (attribute, (rdn, oid, tag, value)) in attributes.iter().zip(expected) {
+                assert_eq!(attribute.rdn(), rdn);
+                assert_eq!(attribute.oid(), oid);
+                assert_eq!(attribute.tag(), tag);
+                assert_eq!(attribute.value(), value);
+            }
+            assert_eq!(attributes[2].decode_string(), Ok(Some("hé".into())));
+        }
+        assert_eq!(attributes(&[]).next(), None);
+
+        // A TeletexString isn't decoded.
+        let teletex = rdn(&[attribute(CN, 0x14, b"ab")]);
+        let parsed = attributes(&teletex).next().unwrap().unwrap();
+        assert_eq!(parsed.decode_string(), Ok(None));
+        let invalid = rdn(&[attribute(CN, UTF8_STRING, b"\xff")]);
+        let parsed = attributes(&invalid).next().unwrap().unwrap();
+        assert_eq!(parsed.decode_string(), Err(Error::BadDer));
+    }
+
+    #[test]
+    fn attributes_malformed() {
+        // The error is yielded after the attributes that precede it, and then iteration ends.
+        let name = [
+            rdn(&[attribute(CN, UTF8_STRING, b"foo")]),
+            tlv(0x30, &attribute(CN, UTF8_STRING, b"bar")),
+        ]
+        .concat();
+        let mut iter = attributes(&name);
+        assert!(matches!(iter.next(), Some(Ok(_))));
+        assert_eq!(iter.next(), Some(Err(Error::BadDer)));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = attributes(&[0x30, 0x01]);
+        assert_eq!(iter.next(), Some(Err(Error::BadDer)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(attributes(&rdn(&[])).next(), Some(Err(Error::BadDer)));
+    }
+
+    #[test]
+    fn malformed() {
+        // An RDN must be a SET.
+        let name = tlv(0x30, &attribute(CN, UTF8_STRING, b"foo"));
+        assert_eq!(format_dn(&tlv(0x30, &name)), Err(Error::BadDer));
+        // An empty RDN is not valid.
+        assert_eq!(format_dn(&rdn(&[])), Err(Error::BadDer));
+    }
+}
@@ -0,0 +1,766 @@
+//! Verification of OCSP responses, such as those stapled to a TLS handshake, and encoding of
+//! the requests for them.
+//!
+//! Only the basic OCSP response type of RFC 6960[^1] is supported.
+//!
+//! [^1]: <https://www.rfc-editor.org/rfc/rfc6960>
+
+use core::time::Duration;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(all(feature = "aws_lc_rs", not(feature = "ring")))]
+use aws_lc_rs::digest;
+#[cfg(feature = "ring")]
+use ring::digest;
+
+use pki_types::{SignatureVerificationAlgorithm, TrustAnchor, UnixTime};
+
+use crate::cert::{lenient_certificate_serial_number, Cert};
+use crate::constant_time;
+use crate::crl::{RevocationReason, UnknownStatusPolicy};
+use crate::der::{self, FromDer, Tag};
+use crate::error::Error;
+use crate::public_values_eq;
+use crate::signed_data::{self, SignedData};
+use crate::time::skewed;
+use crate::verify_cert::{check_validity, Budget, KeyUsage, MAX_SUB_CA_COUNT};
+use crate::x509::Extension;
+
+/// The status of a certificate, as reported by a verified OCSP response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OcspCertStatus {
+    /// The certificate is not revoked.
+    Good,
+    /// The certificate is revoked.
+    Revoked {
+        /// The time at which the certificate was revoked.
+        time: UnixTime,
+        /// Why the certificate was revoked, if the responder said.
+        reason: Option<RevocationReason>,
+    },
+    /// The responder does not know about the certificate.
+    Unknown,
+}
+
+/// The hash algorithm used to identify a certificate in an OCSP request.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OcspHash {
+    /// SHA-1, which RFC 6960 requires responders to support, and the most widely supported.
+    Sha1,
+    /// SHA-256.
+    Sha256,
+}
+
+#[cfg(feature = "alloc")]
+impl OcspHash {
+    fn oid(self) -> &'static [u8] {
+        match self {
+            Self::Sha1 => &ID_SHA1,
+            Self::Sha256 => &ID_SHA256,
+        }
+    }
+
+    fn algorithm(self) -> &'static digest::Algorithm {
+        match self {
+            Self::Sha1 => &digest::SHA1_FOR_LEGACY_USE_ONLY,
+            Self::Sha256 => &digest::SHA256,
+        }
+    }
+}
+
+/// The name and public key of the issuer of the certificate whose status is wanted, which may
+/// be an intermediate certificate or a trust anchor.
+#[derive(Clone, Copy)]
+pub(crate) struct Issuer<'a> {
+    subject: untrusted::Input<'a>,
+    spki: untrusted::Input<'a>,
+}
+
+impl<'a> From<&Cert<'a>> for Issuer<'a> {
+    fn from(cert: &Cert<'a>) -> Self {
+        Self {
+            subject: cert.subject,
+            spki: cert.spki,
+        }
+    }
+}
+
+impl<'a> From<&'a TrustAnchor<'_>> for Issuer<'a> {
+    fn from(anchor: &'a TrustAnchor<'_>) -> Self {
+        Self {
+            subject: untrusted::Input::from(anchor.subject.as_ref()),
+            spki: untrusted::Input::from(anchor.subject_public_key_info.as_ref()),
+        }
+    }
+}
+
+/// Encode an unsigned OCSPRequest for the status of `cert`, issued by `issuer`, identified by
+/// a CertID hashed with `hash`, with the nonce extension (RFC 8954) if `nonce` is given.
+#[cfg(feature = "alloc")]
+pub(crate) fn request(
+    cert: &Cert<'_>,
+    issuer: Issuer<'_>,
+    hash: OcspHash,
+    nonce: Option<&[u8]>,
+) -> Result<Vec<u8>, Error> {
+    if !public_values_eq(cert.issuer, issuer.subject) {
+        return Err(Error::UnknownIssuer);
+    }
+
+    // CertID ::= SEQUENCE {
+    //    hashAlgorithm       AlgorithmIdentifier,
+    //    issuerNameHash      OCTET STRING,
+    //    issuerKeyHash       OCTET STRING,
+    //    serialNumber        CertificateSerialNumber }
+    let algorithm = hash.algorithm();
+    let mut hash_algorithm = Vec::new();
+    der::push_tlv(&mut hash_algorithm, Tag::OID.into(), hash.oid());
+    der::push_tlv(&mut hash_algorithm, Tag::Null.into(), &[]);
+    let mut cert_id = Vec::new();
+    der::push_tlv(&mut cert_id, Tag::Sequence.into(), &hash_algorithm);
+    der::push_tlv(
+        &mut cert_id,
+        Tag::OctetString.into(),
+        issuer_name_hash(algorithm, cert).as_ref(),
+    );
+    der::push_tlv(
+        &mut cert_id,
+        Tag::OctetString.into(),
+        digest::digest(algorithm, issuer_key(issuer.spki)?).as_ref(),
+    );
+    der::push_tlv(
+        &mut cert_id,
+        Tag::Integer.into(),
+        cert.serial.as_slice_less_safe(),
+    );
+
+    // Request ::= SEQUENCE {
+    //    reqCert                     CertID,
+    //    singleRequestExtensions     [0] EXPLICIT Extensions OPTIONAL }
+    let mut request = Vec::new();
+    der::push_tlv(&mut request, Tag::Sequence.into(), &cert_id);
+    let mut request_list = Vec::new();
+    der::push_tlv(&mut request_list, Tag::Sequence.into(), &request);
+
+    // TBSRequest ::= SEQUENCE {
+    //    version             [0] EXPLICIT Version DEFAULT v1,
+    //    requestorName       [1] EXPLICIT GeneralName OPTIONAL,
+    //    requestList             SEQUENCE OF Request,
+    //    requestExtensions   [2] EXPLICIT Extensions OPTIONAL }
+    let mut tbs_request = Vec::new();
+    der::push_tlv(&mut tbs_request, Tag::Sequence.into(), &request_list);
+    if let Some(nonce) = nonce {
+        // RFC 8954 §2.1: "The minimum nonce length is 1 octet, and the maximum nonce length
+        // is 32 octets."
+        if nonce.is_empty() || nonce.len() > 32 {
+            return Err(Error::ExtensionValueInvalid);
+        }
+        let mut value = Vec::new();
+        der::push_tlv(&mut value, Tag::OctetString.into(), nonce);
+        let mut extension = Vec::new();
+        der::push_tlv(&mut extension, Tag::OID.into(), &ID_PKIX_OCSP_NONCE);
+        der::push_tlv(&mut extension, Tag::OctetString.into(), &value);
+        let mut extensions = Vec::new();
+        der::push_tlv(&mut extensions, Tag::Sequence.into(), &extension);
+        let mut explicit = Vec::new();
+        der::push_tlv(&mut explicit, Tag::Sequence.into(), &extensions);
+        der::push_tlv(
+            &mut tbs_request,
+            Tag::ContextSpecificConstructed2.into(),
+            &explicit,
+        );
+    }
+
+    // OCSPRequest ::= SEQUENCE {
+    //    tbsRequest                  TBSRequest,
+    //    optionalSignature   [0] EXPLICIT Signature OPTIONAL }
+    let mut ocsp_request = Vec::new();
+    der::push_tlv(&mut ocsp_request, Tag::Sequence.into(), &tbs_request);
+    let mut out = Vec::new();
+    der::push_tlv(&mut out, Tag::Sequence.into(), &ocsp_request);
+    Ok(out)
+}
+
+/// Verify `response`, a DER-encoded OCSPResponse, and return the status it gives for `cert`,
+/// issued by `issuer`. The signature checks are charged to `budget`.
+pub(crate) fn verify_response(
+    cert: &Cert<'_>,
+    issuer: Issuer<'_>,
+    response: &[u8],
+    time: UnixTime,
+    clock_skew: Duration,
+    supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+    budget: &mut Budget,
+) -> Result<OcspCertStatus, Error> {
+    let basic = BasicResponse::from_response(untrusted::Input::from(response))?;
+
+    let single = basic.single_response_for(cert, &issuer)?;
+    let (earliest, latest) = skewed(time, clock_skew);
+    if latest < single.this_update {
+        return Err(Error::OcspResponseNotValidYet);
+    }
+    // RFC 6960 §4.2.2.1: "If nextUpdate is not set, the responder is indicating that newer
+    // revocation information is available all the time."
+    if matches!(single.next_update, Some(next_update) if earliest > next_update) {
+        return Err(Error::OcspResponseExpired);
+    }
+
+    let responder_spki =
+        basic.responder_spki(&issuer, time, clock_skew, supported_sig_algs, budget)?;
+    signed_data::verify_signed_data(
+        supported_sig_algs,
+        responder_spki,
+        &basic.signed_data,
+        budget,
+    )?;
+
+    Ok(single.status)
+}
+
+/// Verify `responses`, a list of DER-encoded OCSPResponses aligned with the path from
+/// `end_entity` up through `intermediates`, and return the status each gives for the
+/// certificate at its depth. `top_issuer` issued the topmost certificate, if it is known.
+///
+/// A depth with no response, or an empty one, has no status; so does one whose issuer is
+/// unknown, since its response can't be verified. Those and responses giving an unknown
+/// status are accepted only with [`UnknownStatusPolicy::Allow`]. Errors are returned with the
+/// depth of the certificate they concern, which is `None` for surplus responses.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify_responses(
+    end_entity: &Cert<'_>,
+    intermediates: &[Option<Cert<'_>>],
+    top_issuer: Option<Issuer<'_>>,
+    responses: &[&[u8]],
+    status_policy: UnknownStatusPolicy,
+    time: UnixTime,
+    clock_skew: Duration,
+    supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+    budget: &mut Budget,
+) -> Result<[Option<OcspCertStatus>; MAX_SUB_CA_COUNT + 1], (Option<usize>, Error)> {
+    let len = intermediates.len() + 1;
+    if responses.len() > len {
+        return Err((None, Error::SurplusOcspResponses));
+    }
+
+    let cert_at = |depth: usize| match depth {
+        0 => Some(end_entity),
+        _ => intermediates.get(depth - 1).and_then(Option::as_ref),
+    };
+    let issuer_at = |depth: usize| match depth + 1 == len {
+        true => top_issuer,
+        false => cert_at(depth + 1).map(Issuer::from),
+    };
+    let unknown = |depth: usize, status| match status_policy {
+        UnknownStatusPolicy::Allow => Ok(status),
+        UnknownStatusPolicy::Deny => Err((Some(depth), Error::UnknownRevocationStatus)),
+    };
+
+    // Whether the response given for `depth` is for another certificate of the path.
+    let misaligned = |depth: usize, response: &[u8]| {
+        (0..len).filter(|&other| other != depth).any(|other| {
+            match (cert_at(other), issuer_at(other)) {
+                (Some(cert), Some(issuer)) => is_for(cert, &issuer, response),
+                _ => false,
+            }
+        })
+    };
+
+    let mut statuses = [None; MAX_SUB_CA_COUNT + 1];
+    for (depth, status) in statuses.iter_mut().enumerate().take(len) {
+        let response = responses.get(depth).filter(|response| !response.is_empty());
+        let (cert, response, issuer) = match (cert_at(depth), response, issuer_at(depth)) {
+            (Some(cert), Some(response), Some(issuer)) => (cert, response, issuer),
+            _ => {
+                *status = unknown(depth, None)?;
+                continue;
+            }
+        };
+
+        *status = match verify_response(
+            cert,
+            issuer,
+            response,
+            time,
+            clock_skew,
+            supported_sig_algs,
+            budget,
+        ) {
+            Ok(OcspCertStatus::Good) => Some(OcspCertStatus::Good),
+            Ok(OcspCertStatus::Revoked { .. }) => {
+                return Err((Some(depth), Error::CertRevoked));
+            }
+            Ok(OcspCertStatus::Unknown) => unknown(depth, Some(OcspCertStatus::Unknown))?,
+            Err(Error::OcspResponseCertMismatch) if misaligned(depth, response) => {
+                return Err((Some(depth), Error::MisalignedOcspResponse { depth }));
+            }
+            Err(err) => return Err((Some(depth), err)),
+        };
+    }
+
+    Ok(statuses)
+}
+
+/// Whether `response` has a SingleResponse for `cert`, issued by `issuer`. Nothing else about
+/// the response is checked.
+fn is_for(cert: &Cert<'_>, issuer: &Issuer<'_>, response: &[u8]) -> bool {
+    BasicResponse::from_response(untrusted::Input::from(response))
+        .and_then(|basic| basic.single_response_for(cert, issuer))
+        .is_ok()
+}
+
+/// A BasicOCSPResponse. See RFC 6960 §4.2.1.
+struct BasicResponse<'a> {
+    signed_data: SignedData<'a>,
+    responder_id: ResponderId<'a>,
+    /// The contents of the `responses` SEQUENCE OF SingleResponse.
+    responses: untrusted::Input<'a>,
+    /// The contents of the `certs` SEQUENCE OF Certificate, if present.
+    certs: Option<untrusted::Input<'a>>,
+}
+
+impl<'a> BasicResponse<'a> {
+    /// Parse an OCSPResponse, and the BasicOCSPResponse within it.
+    fn from_response(response: untrusted::Input<'a>) -> Result<Self, Error> {
+        // OCSPResponse ::= SEQUENCE {
+        //    responseStatus         OCSPResponseStatus,
+        //    responseBytes          [0] EXPLICIT ResponseBytes OPTIONAL }
+        // ResponseBytes ::=       SEQUENCE {
+        //    responseType   OBJECT IDENTIFIER,
+        //    response       OCTET STRING }
+        let (status, response_bytes) = response
+            .read_all(Error::BadDer, |reader| {
+                der::nested(reader, Tag::Sequence, Error::BadDer, |response| {
+                    let status = der::expect_tag(response, Tag::Enum)?
+                        .read_all(Error::BadDer, |status| {
+                            status.read_byte().map_err(|_| Error::BadDer)
+                        })?;
+                    let response_bytes = match response.at_end() {
+                        true => None,
+                        false => Some(der::nested(
+                            response,
+                            Tag::ContextSpecificConstructed0,
+                            Error::BadDer,
+                            |tagged| {
+                                der::nested(tagged, Tag::Sequence, Error::BadDer, |bytes| {
+                                    let response_type = der::expect_tag(bytes, Tag::OID)?;
+                                    let response = der::expect_tag(bytes, Tag::OctetString)?;
+                                    Ok((response_type, response))
+                                })
+                            },
+                        )?),
+                    };
+                    Ok((status, response_bytes))
+                })
+            })
+            .map_err(malformed)?;
+
+        // OCSPResponseStatus ::= ENUMERATED { successful (0), ... }
+        if status != 0 {
+            return Err(Error::UnsuccessfulOcspResponse);
+        }
+        let (response_type, response) = response_bytes.ok_or(Error::MalformedOcspResponse)?;
+        if !public_values_eq(response_type, untrusted::Input::from(&ID_PKIX_OCSP_BASIC)) {
+            return Err(Error::UnsupportedOcspResponseType);
+        }
+
+        // BasicOCSPResponse ::= SEQUENCE {
+        //    tbsResponseData      ResponseData,
+        //    signatureAlgorithm   AlgorithmIdentifier,
+        //    signature            BIT STRING,
+        //    certs            [0] EXPLICIT SEQUENCE OF Certificate OPTIONAL }
+        response
+            .read_all(Error::BadDer, |reader| {
+                der::nested(reader, Tag::Sequence, Error::BadDer, |basic| {
+                    let (tbs, signed_data) = SignedData::from_der(basic, der::MAX_DER_SIZE)?;
+                    let certs = match basic.at_end() {
+                        true => None,
+                        false => Some(der::nested(
+                            basic,
+                            Tag::ContextSpecificConstructed0,
+                            Error::BadDer,
+                            |tagged| der::expect_tag(tagged, Tag::Sequence),
+                        )?),
+                    };
+
+                    let (responder_id, responses) =
+                        tbs.read_all(Error::BadDer, Self::read_response_data)?;
+                    Ok(Self {
+                        signed_data,
+                        responder_id,
+                        responses,
+                        certs,
+                    })
+                })
+            })
+            .map_err(malformed)
+    }
+
+    fn read_response_data(
+        tbs: &mut untrusted::Reader<'a>,
+    ) -> Result<(ResponderId<'a>, untrusted::Input<'a>), Error> {
+        // ResponseData ::= SEQUENCE {
+        //    version              [0] EXPLICIT Version DEFAULT v1,
+        //    responderID              ResponderID,
+        //    producedAt               GeneralizedTime,
+        //    responses                SEQUENCE OF SingleResponse,
+        //    responseExtensions   [1] EXPLICIT Extensions OPTIONAL }
+        if tbs.peek(Tag::ContextSpecificConstructed0.into()) {
+            // v1 is the only version, and DER forbids encoding a DEFAULT value.
+            return Err(Error::BadDer);
+        }
+
+        // ResponderID ::= CHOICE {
+        //    byName   [1] Name,
+        //    byKey    [2] KeyHash }
+        let responder_id = if tbs.peek(Tag::ContextSpecificConstructed1.into()) {
+            ResponderId::ByName(der::nested(
+                tbs,
+                Tag::ContextSpecificConstructed1,
+                Error::BadDer,
+                |name| der::expect_tag(name, Tag::Sequence),
+            )?)
+        } else {
+            ResponderId::ByKey(der::nested(
+                tbs,
+                Tag::ContextSpecificConstructed2,
+                Error::BadDer,
+                |key_hash| der::expect_tag(key_hash, Tag::OctetString),
+            )?)
+        };
+
+        UnixTime::from_der(tbs)?; // producedAt
+        let responses = der::expect_tag(tbs, Tag::Sequence)?;
+        read_extensions(tbs, Tag::ContextSpecificConstructed1)?;
+        Ok((responder_id, responses))
+    }
+
+    /// Find the SingleResponse giving the status of `cert`.
+    fn single_response_for(
+        &self,
+        cert: &Cert<'_>,
+        issuer: &Issuer<'_>,
+    ) -> Result<SingleResponse, Error> {
+        let issuer_key = issuer_key(issuer.spki)?;
+        self.responses
+            .read_all(Error::BadDer, |responses| {
+                while !responses.at_end() {
+                    let single = der::nested(
+                        responses,
+                        Tag::Sequence,
+                        Error::BadDer,
+                        IdentifiedSingleResponse::from_der,
+                    )?;
+                    if single.cert_id.matches(cert, issuer_key) {
+                        responses.skip_to_end();
+                        return Ok(Some(single.response));
+                    }
+                }
+                Ok(None)
+            })
+            .map_err(malformed)?
+            .ok_or(Error::OcspResponseCertMismatch)
+    }
+
+    /// Find the public key the response must be signed with: either that of `issuer`, or of a
+    /// delegated responder certificate included in the response. See RFC 6960 §4.2.2.2.
+    fn responder_spki(
+        &self,
+        issuer: &Issuer<'a>,
+        time: UnixTime,
+        clock_skew: Duration,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        budget: &mut Budget,
+    ) -> Result<untrusted::Input<'a>, Error> {
+        if self.responder_id.matches(issuer)? {
+            return Ok(issuer.spki);
+        }
+
+        let certs = match self.certs {
+            Some(certs) => certs,
+            None => return Err(Error::OcspResponderNotAuthorized),
+        };
+        let mut reader = untrusted::Reader::new(certs);
+        while !reader.at_end() {
+            let (der, _) = reader
+                .read_partial(|certs| der::expect_tag(certs, Tag::Sequence))
+                .map_err(malformed)?;
+            let responder = Cert::from_der(der).map_err(malformed)?;
+            responder
+                .check_critical_extensions(&[])
+                .map_err(malformed)?;
+            if !self.responder_id.matches(&Issuer::from(&responder))? {
+                continue;
+            }
+
+            // The delegated responder must be issued by the certificate's issuer, for OCSP
+            // signing, and be valid at the verification time.
+            if !public_values_eq(responder.issuer, issuer.subject) {
+                return Err(Error::OcspResponderNotAuthorized);
+            }
+            signed_data::verify_signed_data(
+                supported_sig_algs,
+                issuer.spki,
+                &responder.signed_data,
+                budget,
+            )?;
+            KeyUsage::ocsp_signing()
+                .check(&responder)
+                .map_err(|_| Error::OcspResponderNotAuthorized)?;
+            responder
+                .validity
+                .read_all(Error::BadDer, |value| {
+                    check_validity(value, time, clock_skew)
+                })
+                .map_err(|_| Error::OcspResponderNotAuthorized)?;
+            return Ok(responder.spki);
+        }
+
+        Err(Error::OcspResponderNotAuthorized)
+    }
+}
+
+enum ResponderId<'a> {
+    /// The contents of the responder's subject Name.
+    ByName(untrusted::Input<'a>),
+    /// The SHA-1 hash of the responder's public key.
+    ByKey(untrusted::Input<'a>),
+}
+
+impl ResponderId<'_> {
+    fn matches(&self, responder: &Issuer<'_>) -> Result<bool, Error> {
+        Ok(match self {
+            ResponderId::ByName(name) => public_values_eq(*name, responder.subject),
+            ResponderId::ByKey(key_hash) => {
+                let key = issuer_key(responder.spki)?;
+                let digest = digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, key);
+                constant_time::slices_eq(key_hash.as_slice_less_safe(), digest.as_ref())
+            }
+        })
+    }
+}
+
+struct SingleResponse {
+    status: OcspCertStatus,
+    this_update: UnixTime,
+    next_update: Option<UnixTime>,
+}
+
+/// A SingleResponse, and the CertID it gives the status of.
+struct IdentifiedSingleResponse<'a> {
+    cert_id: CertId<'a>,
+    response: SingleResponse,
+}
+
+impl<'a> IdentifiedSingleResponse<'a> {
+    fn from_der(reader: &mut untrusted::Reader<'a>) -> Result<Self, Error> {
+        // SingleResponse ::= SEQUENCE {
+        //    certID                       CertID,
+        //    certStatus                   CertStatus,
+        //    thisUpdate                   GeneralizedTime,
+        //    nextUpdate         [0]       EXPLICIT GeneralizedTime OPTIONAL,
+        //    singleExtensions   [1]       EXPLICIT Extensions OPTIONAL }
+        let cert_id = der::nested(reader, Tag::Sequence, Error::BadDer, CertId::from_der)?;
+
+        // CertStatus ::= CHOICE {
+        //    good        [0]     IMPLICIT NULL,
+        //    revoked     [1]     IMPLICIT RevokedInfo,
+        //    unknown     [2]     IMPLICIT UnknownInfo }
+        let (tag, value) = der::read_tag_and_get_value(reader)?;
+        let status = match tag {
+            tag if tag == u8::from(Tag::ContextSpecific0) && value.is_empty() => {
+                OcspCertStatus::Good
+            }
+            tag if tag == u8::from(Tag::ContextSpecificConstructed1) => {
+                value.read_all(Error::BadDer, |revoked_info| {
+                    // RevokedInfo ::= SEQUENCE {
+                    //    revocationTime              GeneralizedTime,
+                    //    revocationReason    [0]     EXPLICIT CRLReason OPTIONAL }
+                    let time = UnixTime::from_der(revoked_info)?;
+                    let reason = match revoked_info.at_end() {
+                        true => None,
+                        false => Some(der::nested(
+                            revoked_info,
+                            Tag::ContextSpecificConstructed0,
+                            Error::BadDer,
+                            RevocationReason::from_der,
+                        )?),
+                    };
+                    Ok(OcspCertStatus::Revoked { time, reason })
+                })?
+            }
+            tag if tag == u8::from(Tag::ContextSpecific2) && value.is_empty() => {
+                OcspCertStatus::Unknown
+            }
+            _ => return Err(Error::BadDer),
+        };
+
+        let this_update = UnixTime::from_der(reader)?;
+        let next_update = match reader.peek(Tag::ContextSpecificConstructed0.into()) {
+            true => Some(der::nested(
+                reader,
+                Tag::ContextSpecificConstructed0,
+                Error::BadDer,
+                UnixTime::from_der,
+            )?),
+            false => None,
+        };
+        read_extensions(reader, Tag::ContextSpecificConstructed1)?;
+
+        Ok(Self {
+            cert_id,
+            response: SingleResponse {
+                status,
+                this_update,
+                next_update,
+            },
+        })
+    }
+}
+
+struct CertId<'a> {
+    hash_algorithm: untrusted::Input<'a>,
+    issuer_name_hash: untrusted::Input<'a>,
+    issuer_key_hash: untrusted::Input<'a>,
+    serial: untrusted::Input<'a>,
+}
+
+impl<'a> CertId<'a> {
+    fn from_der(reader: &mut untrusted::Reader<'a>) -> Result<Self, Error> {
+        // CertID ::= SEQUENCE {
+        //    hashAlgorithm       AlgorithmIdentifier,
+        //    issuerNameHash      OCTET STRING,
+        //    issuerKeyHash       OCTET STRING,
+        //    serialNumber        CertificateSerialNumber }
+        let hash_algorithm = der::nested(reader, Tag::Sequence, Error::BadDer, |algorithm| {
+            let oid = der::expect_tag(algorithm, Tag::OID)?;
+            // The parameters are absent, or NULL.
+            if !algorithm.at_end() {
+                der::expect_tag(algorithm, Tag::Null)?;
+            }
+            Ok(oid)
+        })?;
+        Ok(Self {
+            hash_algorithm,
+            issuer_name_hash: der::expect_tag(reader, Tag::OctetString)?,
+            issuer_key_hash: der::expect_tag(reader, Tag::OctetString)?,
+            serial: lenient_certificate_serial_number(reader)?,
+        })
+    }
+
+    /// Returns true if this identifies `cert`, issued by the holder of `issuer_key`. CertIDs
+    /// using unsupported hash algorithms match nothing.
+    fn matches(&self, cert: &Cert<'_>, issuer_key: &[u8]) -> bool {
+        let algorithm = match HASH_ALGORITHMS
+            .iter()
+            .find(|(oid, _)| self.hash_algorithm.as_slice_less_safe() == *oid)
+        {
+            Some((_, algorithm)) => *algorithm,
+            None => return false,
+        };
+
+        // Every field is compared, so that the time taken doesn't reveal which one differed.
+        constant_time::slices_eq(
+            self.serial.as_slice_less_safe(),
+            cert.serial.as_slice_less_safe(),
+        ) & constant_time::slices_eq(
+            self.issuer_name_hash.as_slice_less_safe(),
+            issuer_name_hash(algorithm, cert).as_ref(),
+        ) & constant_time::slices_eq(
+            self.issuer_key_hash.as_slice_less_safe(),
+            digest::digest(algorithm, issuer_key).as_ref(),
+        )
+    }
+}
+
+/// The hash of the issuer name of `cert`, as in the issuerNameHash of a CertID.
+fn issuer_name_hash(algorithm: &'static digest::Algorithm, cert: &Cert<'_>) -> digest::Digest {
+    // RFC 6960 §4.1.1: "issuerNameHash is the hash of the issuer's distinguished name
+    // (DN). The hash shall be calculated over the DER encoding of the issuer's name field
+    // in the certificate being checked."
+    let mut name = digest::Context::new(algorithm);
+    name.update(&[u8::from(Tag::Sequence)]);
+    let length = cert.issuer.len().to_be_bytes();
+    let length = &length[length.iter().take_while(|byte| **byte == 0).count()..];
+    match length {
+        [] => name.update(&[0]),
+        [short] if *short < 0x80 => name.update(&[*short]),
+        long => {
+            name.update(&[0x80 | u8::try_from(long.len()).unwrap_or(0x7f)]);
+            name.update(long);
+        }
+    }
+    name.update(cert.issuer.as_slice_less_safe());
+    name.finish()
+}
+
+/// The value of the subjectPublicKey BIT STRING of `spki`, excluding the number of unused bits,
+/// over which the issuerKeyHash of a CertID and the KeyHash of a ResponderID are computed.
+fn issuer_key(spki: untrusted::Input<'_>) -> Result<&[u8], Error> {
+    spki.read_all(Error::BadDer, |spki| {
+        der::expect_tag(spki, Tag::Sequence)?; // algorithm
+        Ok(der::bit_string_with_no_unused_bits(spki)?.as_slice_less_safe())
+    })
+}
+
+/// Skip an optional, EXPLICITly tagged Extensions field, rejecting any critical extension
+/// since none are supported.
+fn read_extensions(reader: &mut untrusted::Reader<'_>, tag: Tag) -> Result<(), Error> {
+    if !reader.peek(tag.into()) {
+        return Ok(());
+    }
+    der::nested(reader, tag, Error::BadDer, |tagged| {
+        der::nested_of_mut(
+            tagged,
+            Tag::Sequence,
+            Tag::Sequence,
+            Error::BadDer,
+            |extension| Extension::from_der(extension)?.unsupported(),
+        )
+    })
+}
+
+/// DER errors anywhere within a response all mean the response is malformed.
+fn malformed(error: Error) -> Error {
+    match error {
+        Error::BadDer
+        | Error::BadDerTime
+        | Error::BerEncodingNotAllowed { .. }
+        | Error::ImproperTimeEncoding { .. }
+        | Error::TrailingData(_)
+        | Error::UnsupportedRevocationReason => Error::MalformedOcspResponse,
+        error => error,
+    }
+}
+
+// id-pkix-ocsp-basic OBJECT IDENTIFIER ::= { id-pkix-ocsp 1 }
+const ID_PKIX_OCSP_BASIC: [u8; 9] = oid!(1, 3, 6, 1, 5, 5, 7, 48, 1, 1);
+
+// id-pkix-ocsp-nonce OBJECT IDENTIFIER ::= { id-pkix-ocsp 2 }
+#[cfg(feature = "alloc")]
+const ID_PKIX_OCSP_NONCE: [u8; 9] = oid!(1, 3, 6, 1, 5, 5, 7, 48, 1, 2);
+
+// id-sha1 1.3.14.3.2.26
+const ID_SHA1: [u8; 5] = oid!(1, 3, 14, 3, 2, 26);
+
+// id-sha256 2.16.840.1.101.3.4.2.1
+const ID_SHA256: [u8; 9] = [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+
+/// The hash algorithms supported in CertIDs, by OID.
+static HASH_ALGORITHMS: &[(&[u8], &digest::Algorithm)] = &[
+    (&ID_SHA1, &digest::SHA1_FOR_LEGACY_USE_ONLY),
+    (&ID_SHA256, &digest::SHA256),
+    // id-sha384 2.16.840.1.101.3.4.2.2
+    (
+        &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02],
+        &digest::SHA384,
+    ),
+    // id-sha512 2.16.840.1.101.3.4.2.3
+    (
+        &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03],
+        &digest::SHA512,
+    ),
+];
@@ -0,0 +1,321 @@
+//! Certificate policy processing, as described in RFC 5280 section 6.1.
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "alloc")]
+use crate::cert::Cert;
+#[cfg(feature = "alloc")]
+use crate::der::{self, Tag};
+use crate::error::Error;
+use crate::verify_cert::PathNode;
+
+/// Checks that the path ending at `path` is valid for each of the `required` certificate
+/// policies, given as the values of their OIDs.
+///
+/// On failure, yields the error and the index in the path of the certificate it was found at.
+pub(crate) fn check_policies(
+    path: &PathNode<'_>,
+    required: &[&[u8]],
+) -> Result<(), (usize, Error)> {
+    if required.is_empty() {
+        // The policy extensions are only processed when policies are required. A path whose
+        // validity depends on them being understood must be rejected otherwise.
+        return match path
+            .iter()
+            .find(|node| node.cert.policy_extensions_critical)
+        {
+            Some(node) => Err((node.index, Error::UnsupportedCriticalExtension)),
+            None => Ok(()),
+        };
+    }
+
+    #[cfg(feature = "alloc")]
+    return process(path, required);
+
+    // Policies can only be required with the alloc feature.
+    #[cfg(not(feature = "alloc"))]
+    Err((0, Error::RequiredPolicyNotMet))
+}
+
+/// Runs the policy processing steps of RFC 5280 section 6.1, with the `required` policies as
+/// the user-initial-policy-set.
+///
+/// The valid_policy_tree is kept as one level per certificate, in which nodes with the same
+/// policy are merged into one with several parents. Their expected_policy_sets depend only on
+/// the policy and the certificate, so this doesn't change the result, and it keeps the tree
+/// from growing exponentially with the path length. Policy qualifiers are not kept.
+///
+/// Since at least one policy is required, the valid_policy_tree must not end up empty, as
+/// initial-explicit-policy or a requireExplicitPolicy constraint would require. The
+/// explicit_policy state variable is therefore not tracked.
+#[cfg(feature = "alloc")]
+fn process(path: &PathNode<'_>, required: &[&[u8]]) -> Result<(), (usize, Error)> {
+    let n = path.index + 1;
+    let mut levels = vec![vec![Node::new(&ANY_POLICY, Vec::new())]];
+    let mut policy_mapping = n + 1;
+    let mut inhibit_any_policy = n + 1;
+
+    // `path.iter()` starts at the certificate issued by the trust anchor.
+    for (i, node) in (1..=n).zip(path.iter()) {
+        let cert = node.cert;
+        let at = |err| (node.index, err);
+        let self_issued = crate::public_values_eq(cert.issuer, cert.subject);
+
+        // Step (d), or (e) if there are no certificate policies.
+        let allow_any = inhibit_any_policy > 0 || (i < n && self_issued);
+        let mut level = next_level(levels.last().unwrap(), cert, allow_any).map_err(at)?;
+        if level.is_empty() {
+            return Err(at(Error::RequiredPolicyNotMet));
+        }
+
+        // The remaining steps prepare for the next certificate.
+        if i < n {
+            map_policies(&mut level, cert, policy_mapping > 0).map_err(at)?;
+            if level.is_empty() {
+                return Err(at(Error::RequiredPolicyNotMet));
+            }
+
+            if !self_issued {
+                policy_mapping = policy_mapping.saturating_sub(1);
+                inhibit_any_policy = inhibit_any_policy.saturating_sub(1);
+            }
+
+            if let Some(constraints) = cert.policy_constraints {
+                constraints
+                    .read_all(Error::BadDer, |value| {
+                        // requireExplicitPolicy is checked, but has no effect: see above.
+                        if value.peek(Tag::ContextSpecific0.into()) {
+                            skip_certs(der::expect_tag(value, Tag::ContextSpecific0)?)?;
+                        }
+                        if value.peek(Tag::ContextSpecific1.into()) {
+                            let skip = skip_certs(der::expect_tag(value, Tag::ContextSpecific1)?)?;
+                            policy_mapping = policy_mapping.min(skip);
+                        }
+                        Ok(())
+                    })
+                    .map_err(at)?;
+            }
+
+            if let Some(inhibit) = cert.inhibit_any_policy {
+                let skip = inhibit
+                    .read_all(Error::BadDer, |value| {
+                        skip_certs(der::expect_tag(value, Tag::Integer)?)
+                    })
+                    .map_err(at)?;
+                inhibit_any_policy = inhibit_any_policy.min(skip);
+            }
+        }
+
+        levels.push(level);
+    }
+
+    // Step (g): the path is valid for a required policy if the tree has a node for it whose
+    // parent is an anyPolicy node, or an anyPolicy node at the last level. Only nodes from
+    // which the last level can be reached count, so walk the levels back from it.
+    let mut valid = vec![true; levels.last().unwrap().len()];
+    let mut valid_policies = Vec::new();
+    let any_policy = levels
+        .last()
+        .unwrap()
+        .iter()
+        .any(|node| node.policy == ANY_POLICY);
+    for depth in (1..levels.len()).rev() {
+        let parents = &levels[depth - 1];
+        let mut parents_valid = vec![false; parents.len()];
+        for (node, _) in levels[depth]
+            .iter()
+            .zip(&valid)
+            .filter(|(_, valid)| **valid)
+        {
+            for &parent in &node.parents {
+                parents_valid[parent] = true;
+                if parents[parent].policy == ANY_POLICY {
+                    valid_policies.push(node.policy);
+                }
+            }
+        }
+        valid = parents_valid;
+    }
+
+    match required
+        .iter()
+        .all(|policy| any_policy || valid_policies.contains(policy))
+    {
+        true => Ok(()),
+        false => Err((0, Error::RequiredPolicyNotMet)),
+    }
+}
+
+/// Builds the level of the valid_policy_tree for `cert` from the `previous` level, as in step
+/// (d) of RFC 5280 section 6.1.3. anyPolicy in `cert` is only processed if `allow_any`.
+#[cfg(feature = "alloc")]
+fn next_level<'a>(
+    previous: &[Node<'a>],
+    cert: &Cert<'a>,
+    allow_any: bool,
+) -> Result<Vec<Node<'a>>, Error> {
+    let mut level = Vec::new();
+    let policies = match cert.certificate_policies {
+        Some(policies) => policies,
+        None => return Ok(level),
+    };
+
+    let mut any_policy = false;
+    policies.read_all(Error::BadDer, |value| loop {
+        let policy = der::nested(value, Tag::Sequence, Error::BadDer, |info| {
+            let policy = der::expect_tag(info, Tag::OID)?;
+            // The policy qualifiers are only informational.
+            info.skip_to_end();
+            Ok(policy.as_slice_less_safe())
+        })?;
+
+        match policy == ANY_POLICY {
+            true => any_policy = true,
+            false if level.iter().any(|node: &Node<'_>| node.policy == policy) => {}
+            false => {
+                // Step (d)(1): the policy is a child of each node that expects it, or if
+                // there are none, of the anyPolicy node.
+                let mut parents = previous
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, node)| node.expected.contains(&policy))
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+                if parents.is_empty() {
+                    parents.extend(previous.iter().position(|node| node.policy == ANY_POLICY));
+                }
+                if !parents.is_empty() {
+                    level.push(Node::new(policy, parents));
+                }
+            }
+        }
+
+        if value.at_end() {
+            break Ok(());
+        }
+    })?;
+
+    // Step (d)(2): anyPolicy stands for each expected policy that isn't already asserted.
+    if any_policy && allow_any {
+        for (i, parent) in previous.iter().enumerate() {
+            for &policy in &parent.expected {
+                match level.iter_mut().find(|node| node.policy == policy) {
+                    Some(node) if node.parents.contains(&i) => {}
+                    Some(node) => node.parents.push(i),
+                    None => level.push(Node::new(policy, vec![i])),
+                }
+            }
+        }
+    }
+
+    Ok(level)
+}
+
+/// Applies the policy mappings in `cert` to `level`, as in step (b) of RFC 5280 section 6.1.4:
+/// the mapped nodes expect the subject domain policies if `allow_mapping`, and are removed
+/// otherwise.
+#[cfg(feature = "alloc")]
+fn map_policies<'a>(
+    level: &mut Vec<Node<'a>>,
+    cert: &Cert<'a>,
+    allow_mapping: bool,
+) -> Result<(), Error> {
+    let mappings = match cert.policy_mappings {
+        Some(mappings) => mappings,
+        None => return Ok(()),
+    };
+
+    let mappings = mappings.read_all(Error::BadDer, |value| {
+        let mut mappings = Vec::new();
+        loop {
+            mappings.push(der::nested(
+                value,
+                Tag::Sequence,
+                Error::BadDer,
+                |mapping| {
+                    let issuer = der::expect_tag(mapping, Tag::OID)?.as_slice_less_safe();
+                    let subject = der::expect_tag(mapping, Tag::OID)?.as_slice_less_safe();
+                    // Step (a): anyPolicy can't be mapped.
+                    match issuer == ANY_POLICY || subject == ANY_POLICY {
+                        true => Err(Error::ExtensionValueInvalid),
+                        false => Ok((issuer, subject)),
+                    }
+                },
+            )?);
+            if value.at_end() {
+                break Ok(mappings);
+            }
+        }
+    })?;
+
+    if !allow_mapping {
+        level.retain(|node| !mappings.iter().any(|(issuer, _)| *issuer == node.policy));
+        return Ok(());
+    }
+
+    for (i, &(issuer, _)) in mappings.iter().enumerate() {
+        if mappings[..i].iter().any(|(earlier, _)| *earlier == issuer) {
+            continue;
+        }
+
+        let expected = mappings
+            .iter()
+            .filter(|(from, _)| *from == issuer)
+            .map(|(_, to)| *to)
+            .collect();
+        if let Some(node) = level.iter_mut().find(|node| node.policy == issuer) {
+            node.expected = expected;
+        } else if let Some(any) = level.iter().find(|node| node.policy == ANY_POLICY) {
+            let mut node = Node::new(issuer, any.parents.clone());
+            node.expected = expected;
+            level.push(node);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses the value of a `SkipCerts ::= INTEGER (0..MAX)`, saturating at `usize::MAX`.
+#[cfg(feature = "alloc")]
+fn skip_certs(value: untrusted::Input<'_>) -> Result<usize, Error> {
+    match value.as_slice_less_safe() {
+        [] => Err(Error::BadDer),
+        // Negative.
+        [first, ..] if first & 0x80 != 0 => Err(Error::BadDer),
+        // Unnecessary leading zero.
+        [0, second, ..] if second & 0x80 == 0 => Err(Error::BadDer),
+        bytes => Ok(bytes.iter().fold(0usize, |skip, &byte| {
+            skip.checked_mul(256)
+                .and_then(|skip| skip.checked_add(usize::from(byte)))
+                .unwrap_or(usize::MAX)
+        })),
+    }
+}
+
+/// A node of the valid_policy_tree, merged with the other nodes at its depth with the same
+/// policy.
+#[cfg(feature = "alloc")]
+struct Node<'a> {
+    /// The value of the valid_policy OID.
+    policy: &'a [u8],
+    /// The indices of the node's parents in the previous level.
+    parents: Vec<usize>,
+    /// The expected_policy_set: the policies the node's children may assert.
+    expected: Vec<&'a [u8]>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Node<'a> {
+    fn new(policy: &'a [u8], parents: Vec<usize>) -> Self {
+        Self {
+            policy,
+            parents,
+            expected: vec![policy],
+        }
+    }
+}
+
+// anyPolicy 2.5.29.32.0
+#[cfg(feature = "alloc")]
+static ANY_POLICY: [u8; 4] = oid![2, 5, 29, 32, 0];
@@ -0,0 +1,544 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, TrustAnchor, UnixTime};
+
+use crate::cert::Cert;
+use crate::dangerous::AllowedErrors;
+use crate::end_entity::EndEntityCert;
+use crate::error::Error;
+use crate::lint::Lint;
+use crate::signed_data::{self, PublicKeyInfo};
+use crate::subject_name::NameIterator;
+use crate::verify_cert::{self, KeyUsage, VerificationPolicy};
+
+/// The checks made by [`precheck_end_entity()`].
+#[derive(Clone, Copy)]
+pub struct PrecheckPolicy<'a> {
+    supported_sig_algs: &'a [&'a dyn SignatureVerificationAlgorithm],
+    usage: KeyUsage,
+    policy: VerificationPolicy,
+}
+
+impl<'a> PrecheckPolicy<'a> {
+    /// Check a certificate to be used for `usage`, and verified with `supported_sig_algs`.
+    pub fn new(
+        supported_sig_algs: &'a [&'a dyn SignatureVerificationAlgorithm],
+        usage: KeyUsage,
+    ) -> Self {
+        Self {
+            supported_sig_algs,
+            usage,
+            policy: VerificationPolicy::default(),
+        }
+    }
+
+    /// Make the additional checks of `policy`, instead of those of
+    /// [`VerificationPolicy::default()`].
+    pub fn with_policy(mut self, policy: VerificationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+/// Runs every check webpki makes of an end-entity certificate that depends on neither the
+/// verification time nor the trust anchors, returning all of the errors found.
+///
+/// This is intended for certificate issuers, to check before issuance that nothing about the
+/// certificate itself will make [`EndEntityCert::verify_for_usage()`] reject it with the same
+/// usage and policy. The errors are the ones verification would fail with, in the order
+/// verification would check them, except that signature algorithm and public key problems
+/// come last.
+///
+/// The checks are: that the certificate can be parsed; that it has no critical extensions
+/// webpki doesn't understand; that its validity period isn't inverted; its basicConstraints,
+/// extendedKeyUsage, keyUsage and the policy's additional checks; the syntax of its
+/// subjectAltName extension; that its signature algorithm is among `supported_sig_algs`; and
+/// that some algorithm in `supported_sig_algs` accepts its public key. If the certificate can't be parsed, that is the only error returned.
+pub fn precheck_end_entity(
+    der: &CertificateDer<'_>,
+    policy: &PrecheckPolicy<'_>,
+) -> Result<(), Vec<Error>> {
+    let cert = EndEntityCert::try_from(der).map_err(|err| alloc::vec![err])?;
+
+    let mut errors = verify_cert::end_entity_structural_checks(&cert, policy.usage, &policy.policy)
+        .iter()
+        .filter_map(|result| result.err())
+        .collect::<Vec<_>>();
+
+    if let Some(err) = NameIterator::new(None, cert.subject_alt_name).find_map(Result::err) {
+        errors.push(err);
+    }
+
+    if !policy.supported_sig_algs.iter().any(|alg| {
+        alg.signature_alg_id().as_ref() == cert.signed_data.algorithm.as_slice_less_safe()
+    }) {
+        errors.push(Error::UnsupportedSignatureAlgorithm);
+    }
+
+    if let Err(err) = signed_data::check_public_key_supported(cert.spki, policy.supported_sig_algs)
+    {
+        errors.push(err);
+    }
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
+/// Checks a certificate chain as a server would present it, reporting every problem found
+/// rather than only the first.
+///
+/// This is intended for tooling that checks a chain when it is configured, instead of when
+/// clients fail to verify it. `ee` is the end-entity certificate and `intermediates` the
+/// certificates sent with it, in the order they are sent. The chain is verified with
+/// [`crate::ALL_VERIFICATION_ALGS`] for [`KeyUsage::server_auth()`] at `time`, against
+/// `anchors`, with the default [`VerificationPolicy`].
+///
+/// Each certificate is checked on its own: that it can be parsed, which includes having no
+/// critical extensions webpki doesn't understand; that it is valid at `time`; that its
+/// signature algorithm is supported, which excludes SHA-1; that
+/// its public key is supported and, for RSA, at least 2048 bits; and for the problems
+/// [`Cert::lints()`] reports. Then a path is built, as [`crate::Verifier::verify_detailed()`]
+/// does but regardless of the validity periods already reported: an intermediate missing
+/// from the chain shows as [`Error::UnknownIssuer`] at the certificate whose issuer can't be
+/// found. If a path is found but its intermediates aren't sent first and in path order,
+/// that is reported as [`Error::IntermediatesOutOfOrder`].
+pub fn lint_chain(
+    ee: &CertificateDer<'_>,
+    intermediates: &[CertificateDer<'_>],
+    anchors: &[TrustAnchor<'_>],
+    time: UnixTime,
+) -> ChainReport {
+    let mut findings = Vec::new();
+    let end_entity = match EndEntityCert::try_from(ee) {
+        Ok(cert) => cert,
+        Err(error) => {
+            findings.push(Finding::error(0, error));
+            return ChainReport { findings };
+        }
+    };
+
+    lint_cert(&end_entity, 0, time, &mut findings);
+    for (i, der) in intermediates.iter().enumerate() {
+        match Cert::from_der(untrusted::Input::from(der.as_ref())) {
+            Ok(cert) => lint_cert(&cert, i + 1, time, &mut findings),
+            Err(error) => findings.push(Finding::error(i + 1, error)),
+        }
+    }
+
+    // The index of a certificate of the path among those given.
+    let index_of = |der: &[u8]| {
+        core::iter::once(ee)
+            .chain(intermediates)
+            .position(|cert| cert.as_ref() == der)
+    };
+
+    let result = end_entity
+        .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+        .intermediates(intermediates)
+        .at_time(time)
+        .key_usage(KeyUsage::server_auth())
+        .dangerous_allow_errors(AllowedErrors::new().cert_expired().cert_not_valid_yet())
+        .verify_detailed();
+    match result {
+        Ok(path) => {
+            let out_of_order = path
+                .intermediate_certificates()
+                .enumerate()
+                .find(|(i, cert)| {
+                    intermediates.get(*i).map(|der| der.as_ref()) != Some(cert.der().as_ref())
+                });
+            if let Some((_, cert)) = out_of_order {
+                findings.push(Finding {
+                    severity: Severity::Warning,
+                    index: index_of(cert.der().as_ref()),
+                    error: Error::IntermediatesOutOfOrder,
+                });
+            }
+        }
+        Err(err) => {
+            let finding = Finding {
+                severity: Severity::Error,
+                index: err.cert.and_then(|cert| index_of(cert.der.as_ref())),
+                error: err.error,
+            };
+            if !findings.contains(&finding) {
+                findings.push(finding);
+            }
+        }
+    }
+
+    ChainReport { findings }
+}
+
+/// The checks [`lint_chain()`] makes of each certificate independently of the path.
+fn lint_cert(cert: &Cert<'_>, index: usize, time: UnixTime, findings: &mut Vec<Finding>) {
+    if let Err(error) = cert.validity.read_all(Error::BadDer, |reader| {
+        verify_cert::check_validity(reader, time, Duration::ZERO)
+    }) {
+        findings.push(Finding::error(index, error));
+    }
+
+    if !crate::ALL_VERIFICATION_ALGS.iter().any(|alg| {
+        alg.signature_alg_id().as_ref() == cert.signed_data.algorithm.as_slice_less_safe()
+    }) {
+        findings.push(Finding::error(index, Error::UnsupportedSignatureAlgorithm));
+    }
+
+    if let Err(error) =
+        signed_data::check_public_key_supported(cert.spki, crate::ALL_VERIFICATION_ALGS)
+    {
+        findings.push(Finding::error(index, error));
+    } else if let Ok(PublicKeyInfo::Rsa { modulus_bits }) = cert.public_key_info() {
+        if modulus_bits < 2048 {
+            findings.push(Finding::error(index, Error::PublicKeyTooSmall));
+        }
+    }
+
+    findings.extend(cert.lints().map(|lint| Finding {
+        severity: Severity::Warning,
+        index: Some(index),
+        error: match lint {
+            Lint::EmptySubjectWithoutCriticalSan => Error::EmptySubjectWithoutCriticalSan,
+            Lint::MalformedDnsNameInSan => Error::MalformedDnsNameInSan,
+            Lint::MalformedOtherNameInSan => Error::MalformedOtherNameInSan,
+            Lint::EmptySubjectAltName => Error::EmptySubjectAltName,
+        },
+    }));
+}
+
+/// The problems [`lint_chain()`] found with a certificate chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ChainReport {
+    /// The problems found, those with each certificate in the order the certificates were
+    /// given, followed by those with the path.
+    pub findings: Vec<Finding>,
+}
+
+impl ChainReport {
+    /// Returns whether the chain verified, and no [`Severity::Error`] problems were found.
+    pub fn is_valid(&self) -> bool {
+        !self
+            .findings
+            .iter()
+            .any(|finding| finding.severity == Severity::Error)
+    }
+}
+
+/// A problem found by [`lint_chain()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Finding {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// The certificate the problem is with: 0 for the end-entity certificate, and `i + 1` for
+    /// `intermediates[i]`.
+    ///
+    /// This is `None` for problems that don't concern one of the certificates given, such as
+    /// an error at a trust anchor, or one concerning a pair of certificates.
+    pub index: Option<usize>,
+    /// The problem.
+    pub error: Error,
+}
+
+impl Finding {
+    fn error(index: usize, error: Error) -> Self {
+        Self {
+            severity: Severity::Error,
+            index: Some(index),
+            error,
+        }
+    }
+}
+
+/// How serious a [`Finding`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Severity {
+    /// Clients will fail to verify the chain.
+    Error,
+    /// Clients verify the chain, but some may not, or it was likely misissued.
+    Warning,
+}
+
+#[cfg(all(test, any(feature = "ring", feature = "aws_lc_rs")))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{end_entity_params, issuer_params, make_issuer};
+    use crate::verify_cert::EkuCriticality;
+
+    fn issue(params: rcgen::CertificateParams) -> CertificateDer<'static> {
+        let ca = make_issuer("Test CA");
+        let ee = rcgen::Certificate::from_params(params).unwrap();
+        CertificateDer::from(ee.serialize_der_with_signer(&ca).unwrap())
+    }
+
+    fn params(san: &str) -> rcgen::CertificateParams {
+        let mut params = end_entity_params(vec![san.into()]);
+        params.extended_key_usages = vec![rcgen::ExtendedKeyUsagePurpose::ServerAuth];
+        params
+    }
+
+    fn server_auth() -> PrecheckPolicy<'static> {
+        PrecheckPolicy::new(crate::ALL_VERIFICATION_ALGS, KeyUsage::server_auth())
+    }
+
+    #[test]
+    fn valid_certificate_passes() {
+        let ee = issue(params("example.com"));
+        assert_eq!(precheck_end_entity(&ee, &server_auth()), Ok(()));
+        assert_eq!(
+            precheck_end_entity(
+                &ee,
+                &server_auth().with_policy(VerificationPolicy::strict())
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn unparseable_certificate() {
+        let ee = CertificateDer::from(&[0x30, 0x00][..]);
+        assert_eq!(
+            precheck_end_entity(&ee, &server_auth()),
+            Err(vec![Error::BadDer])
+        );
+    }
+
+    #[test]
+    fn unknown_critical_extension() {
+        let mut params = params("example.com");
+        let mut extension =
+            rcgen::CustomExtension::from_oid_content(&[1, 3, 6, 1, 4, 1, 55555, 2, 1], vec![5, 0]);
+        extension.set_criticality(true);
+        params.custom_extensions.push(extension);
+        assert_eq!(
+            precheck_end_entity(&issue(params), &server_auth()),
+            Err(vec![Error::UnsupportedCriticalExtension])
+        );
+    }
+
+    #[test]
+    fn inverted_validity() {
+        let mut params = params("example.com");
+        params.not_before = rcgen::date_time_ymd(2030, 1, 1);
+        params.not_after = rcgen::date_time_ymd(2020, 1, 1);
+        assert_eq!(
+            precheck_end_entity(&issue(params), &server_auth()),
+            Err(vec![Error::InvalidValidityPeriod])
+        );
+    }
+
+    #[test]
+    fn ca_used_as_end_entity() {
+        let mut params = params("example.com");
+        params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        assert_eq!(
+            precheck_end_entity(&issue(params), &server_auth()),
+            Err(vec![Error::CaUsedAsEndEntity])
+        );
+    }
+
+    #[test]
+    fn wrong_eku() {
+        let ee = issue(params("example.com"));
+        let policy = PrecheckPolicy::new(crate::ALL_VERIFICATION_ALGS, KeyUsage::client_auth());
+        assert_eq!(
+            precheck_end_entity(&ee, &policy),
+            Err(vec![Error::RequiredEkuNotFound])
+        );
+    }
+
+    #[test]
+    fn eku_criticality() {
+        let ee = issue(params("example.com"));
+        let policy = server_auth().with_policy(
+            VerificationPolicy::default().with_eku_criticality(EkuCriticality::RequireCritical),
+        );
+        assert_eq!(
+            precheck_end_entity(&ee, &policy),
+            Err(vec![Error::EkuCriticalityMismatch])
+        );
+    }
+
+    #[test]
+    fn strict_policy_checks() {
+        let ee = issue(params(" "));
+        assert_eq!(precheck_end_entity(&ee, &server_auth()), Ok(()));
+        assert_eq!(
+            precheck_end_entity(
+                &ee,
+                &server_auth().with_policy(VerificationPolicy::strict())
+            ),
+            Err(vec![Error::MalformedDnsNameInSan])
+        );
+    }
+
+    #[test]
+    fn unsupported_algorithms() {
+        let ee = issue(params("example.com"));
+        #[cfg(feature = "ring")]
+        let algs = &[crate::ring::ED25519][..];
+        #[cfg(all(feature = "aws_lc_rs", not(feature = "ring")))]
+        let algs = &[crate::aws_lc_rs::ED25519][..];
+        let policy = PrecheckPolicy::new(algs, KeyUsage::server_auth());
+        assert_eq!(
+            precheck_end_entity(&ee, &policy),
+            Err(vec![
+                Error::UnsupportedSignatureAlgorithm,
+                Error::UnsupportedSignatureAlgorithmForPublicKey,
+            ])
+        );
+    }
+
+    #[test]
+    fn all_errors_are_reported() {
+        let mut params = params("example.com");
+        params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        let policy = PrecheckPolicy::new(crate::ALL_VERIFICATION_ALGS, KeyUsage::client_auth());
+        assert_eq!(
+            precheck_end_entity(&issue(params), &policy),
+            Err(vec![Error::CaUsedAsEndEntity, Error::RequiredEkuNotFound])
+        );
+    }
+
+    struct Chain {
+        root: CertificateDer<'static>,
+        intermediates: Vec<CertificateDer<'static>>,
+        ee: rcgen::CertificateParams,
+        issuer: rcgen::Certificate,
+    }
+
+    /// A root, issuing `depth` intermediates in turn, the last of which issues an end-entity
+    /// certificate for server authentication.
+    fn chain(depth: usize) -> Chain {
+        let root = make_issuer("Root");
+        let root_der = CertificateDer::from(root.serialize_der().unwrap());
+        let mut issuer = root;
+        let mut intermediates = Vec::new();
+        for i in 0..depth {
+            let cert = rcgen::Certificate::from_params(issuer_params(format!("Int {i}"))).unwrap();
+            intermediates.push(CertificateDer::from(
+                cert.serialize_der_with_signer(&issuer).unwrap(),
+            ));
+            issuer = cert;
+        }
+        Chain {
+            root: root_der,
+            intermediates,
+            ee: params("example.com"),
+            issuer,
+        }
+    }
+
+    fn lint(chain: Chain, intermediates: &[CertificateDer<'_>]) -> ChainReport {
+        let ee = rcgen::Certificate::from_params(chain.ee).unwrap();
+        let ee = CertificateDer::from(ee.serialize_der_with_signer(&chain.issuer).unwrap());
+        let anchors = [crate::anchor_from_trusted_cert(&chain.root).unwrap()];
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+        lint_chain(&ee, intermediates, &anchors, time)
+    }
+
+    #[test]
+    fn valid_chain() {
+        let chain = chain(2);
+        let intermediates = chain
+            .intermediates
+            .iter()
+            .rev()
+            .cloned()
+            .collect::<Vec<_>>();
+        let report = lint(chain, &intermediates);
+        assert_eq!(report.findings, []);
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn missing_intermediate() {
+        let chain = chain(2);
+        let intermediates = [chain.intermediates[1].clone()];
+        let report = lint(chain, &intermediates);
+        assert_eq!(
+            report.findings,
+            [Finding {
+                severity: Severity::Error,
+                index: Some(1),
+                error: Error::UnknownIssuer,
+            }]
+        );
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn intermediates_out_of_order() {
+        let chain = chain(2);
+        let intermediates = chain.intermediates.clone();
+        let report = lint(chain, &intermediates);
+        assert_eq!(
+            report.findings,
+            [Finding {
+                severity: Severity::Warning,
+                index: Some(2),
+                error: Error::IntermediatesOutOfOrder,
+            }]
+        );
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn all_problems_are_reported() {
+        let mut chain = chain(1);
+        chain.ee = params(" ");
+        chain.ee.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        let intermediates = [
+            CertificateDer::from(&[0x30, 0x00][..]),
+            chain.intermediates[0].clone(),
+        ];
+        let report = lint(chain, &intermediates);
+        assert_eq!(
+            report.findings,
+            [
+                Finding::error(
+                    0,
+                    Error::CertExpired {
+                        time: UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)),
+                        not_after: UnixTime::since_unix_epoch(Duration::from_secs(315_532_800)),
+                    }
+                ),
+                Finding {
+                    severity: Severity::Warning,
+                    index: Some(0),
+                    error: Error::MalformedDnsNameInSan,
+                },
+                Finding::error(1, Error::BadDer),
+                // The unparseable certificate is sent before the intermediate.
+                Finding {
+                    severity: Severity::Warning,
+                    index: Some(2),
+                    error: Error::IntermediatesOutOfOrder,
+                },
+            ]
+        );
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn unparseable_end_entity() {
+        let anchors = [];
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+        let report = lint_chain(
+            &CertificateDer::from(&[0x30, 0x00][..]),
+            &[],
+            &anchors,
+            time,
+        );
+        assert_eq!(report.findings, [Finding::error(0, Error::BadDer)]);
+    }
+}
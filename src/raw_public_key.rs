@@ -0,0 +1,184 @@
+//! Peers authenticated by a raw public key, as with TLS raw public keys (RFC 7250), instead of
+//! by a certificate.
+//!
+//! A raw public key is a bare subjectPublicKeyInfo. There is no path to build, so the key is
+//! trusted only if it is one of a set of known keys, or matches one of a set of pins. Signatures
+//! made with it are verified as with [`crate::EndEntityCert::verify_signature()`], so a verifier
+//! can handle certificates and raw public keys with the same algorithms and errors.
+
+use pki_types::SignatureVerificationAlgorithm;
+
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+use crate::cert::spki_pin_matches;
+use crate::der::{self, Tag};
+use crate::error::{DerTypeId, Error};
+use crate::public_values_eq;
+use crate::signed_data::{self, PublicKeyInfo, SubjectPublicKeyInfo};
+
+/// A peer's DER-encoded subjectPublicKeyInfo, such as the certificate of a TLS
+/// `RawPublicKey` certificate type (RFC 7250 section 3).
+///
+/// It is parsed from the complete encoding, including the outer SEQUENCE tag and length,
+/// using `RawPublicKeyEntity::try_from()`. The algorithm identifier and the public key must
+/// be well-formed, and nothing may follow them.
+#[derive(Clone, Copy, Debug)]
+pub struct RawPublicKeyEntity<'a> {
+    spki_der: untrusted::Input<'a>,
+    spki: untrusted::Input<'a>,
+}
+
+impl<'a> TryFrom<&'a [u8]> for RawPublicKeyEntity<'a> {
+    type Error = Error;
+
+    /// Parse the DER-encoded subjectPublicKeyInfo `der`.
+    fn try_from(der: &'a [u8]) -> Result<Self, Self::Error> {
+        let spki_der = untrusted::Input::from(der);
+        let spki = spki_der.read_all(
+            Error::TrailingData(DerTypeId::SubjectPublicKeyInfo),
+            |reader| der::expect_tag(reader, Tag::Sequence),
+        )?;
+        der::read_all::<SubjectPublicKeyInfo>(spki)?;
+        Ok(Self { spki_der, spki })
+    }
+}
+
+impl<'a> RawPublicKeyEntity<'a> {
+    /// Raw DER encoded subjectPublicKeyInfo, including its outer SEQUENCE tag and length, as
+    /// hashed for public key pinning.
+    pub fn subject_public_key_info(&self) -> &'a [u8] {
+        self.spki_der.as_slice_less_safe()
+    }
+
+    /// The algorithm and size of the key, as with `crate::Cert::public_key_info()`.
+    pub fn public_key_info(&self) -> Result<PublicKeyInfo<'a>, Error> {
+        PublicKeyInfo::from_spki(self.spki)
+    }
+
+    /// Returns whether the key is one of `trusted`, DER-encoded subjectPublicKeyInfos
+    /// including their outer SEQUENCE tag and length.
+    pub fn is_one_of(&self, trusted: &[&[u8]]) -> bool {
+        trusted
+            .iter()
+            .any(|spki| public_values_eq(self.spki_der, untrusted::Input::from(spki)))
+    }
+
+    /// Returns whether the SHA-256 digest of the subjectPublicKeyInfo is one of `pins`, as
+    /// with the pin-sha256 directive of HPKP (RFC 7469) and `crate::Cert::matches_spki_pins()`.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub fn matches_spki_pins(&self, pins: &[[u8; 32]]) -> bool {
+        spki_pin_matches(&[], self.spki_der.as_slice_less_safe(), pins)
+    }
+
+    /// Verifies the signature `signature` of message `msg` using the key, as
+    /// [`crate::EndEntityCert::verify_signature()`] does using a certificate's key.
+    ///
+    /// Returns [`Error::UnsupportedSignatureAlgorithmForPublicKey`] if `signature_alg` is not
+    /// for the key's algorithm, and [`Error::InvalidSignatureForPublicKey`] if the signature
+    /// is not valid.
+    pub fn verify_signature(
+        &self,
+        signature_alg: &dyn SignatureVerificationAlgorithm,
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        signed_data::verify_signature(
+            signature_alg,
+            self.spki,
+            untrusted::Input::from(msg),
+            untrusted::Input::from(signature),
+        )
+    }
+}
+
+#[cfg(all(test, feature = "ring"))]
+mod tests {
+    use alloc::vec::Vec;
+
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    use super::*;
+
+    /// The DER-encoded subjectPublicKeyInfo of an Ed25519 key (RFC 8410), and the key.
+    fn ed25519_spki(seed: u8) -> (Vec<u8>, Ed25519KeyPair) {
+        let key = Ed25519KeyPair::from_seed_unchecked(&[seed; 32]).unwrap();
+        let mut spki = vec![
+            0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+        ];
+        spki.extend_from_slice(key.public_key().as_ref());
+        (spki, key)
+    }
+
+    #[test]
+    fn verify_signature() {
+        let (spki, key) = ed25519_spki(1);
+        let rpk = RawPublicKeyEntity::try_from(&spki[..]).unwrap();
+        assert_eq!(rpk.subject_public_key_info(), &spki[..]);
+
+        let signature = key.sign(b"message");
+        assert_eq!(
+            rpk.verify_signature(crate::ring::ED25519, b"message", signature.as_ref()),
+            Ok(())
+        );
+        assert_eq!(
+            rpk.verify_signature(crate::ring::ED25519, b"massage", signature.as_ref()),
+            Err(Error::InvalidSignatureForPublicKey)
+        );
+        assert_eq!(
+            rpk.verify_signature(
+                crate::ring::ECDSA_P256_SHA256,
+                b"message",
+                signature.as_ref()
+            ),
+            Err(Error::UnsupportedSignatureAlgorithmForPublicKey)
+        );
+    }
+
+    #[test]
+    fn trusted_keys() {
+        let (spki, _) = ed25519_spki(1);
+        let (other, _) = ed25519_spki(2);
+        let rpk = RawPublicKeyEntity::try_from(&spki[..]).unwrap();
+        assert!(rpk.is_one_of(&[&other, &spki]));
+        assert!(!rpk.is_one_of(&[&other]));
+        assert!(!rpk.is_one_of(&[]));
+
+        let pin = |spki: &[u8]| {
+            let digest = ring::digest::digest(&ring::digest::SHA256, spki);
+            <[u8; 32]>::try_from(digest.as_ref()).unwrap()
+        };
+        assert!(rpk.matches_spki_pins(&[pin(&other), pin(&spki)]));
+        assert!(!rpk.matches_spki_pins(&[pin(&other)]));
+    }
+
+    #[test]
+    fn malformed() {
+        let (spki, _) = ed25519_spki(1);
+        assert_eq!(
+            RawPublicKeyEntity::try_from(&[&spki[..], &[0x00]].concat()[..]).unwrap_err(),
+            Error::TrailingData(DerTypeId::SubjectPublicKeyInfo)
+        );
+        assert_eq!(
+            RawPublicKeyEntity::try_from(&spki[..spki.len() - 1]).unwrap_err(),
+            Error::BadDer
+        );
+
+        // A BIT STRING with unused bits.
+        let mut unused_bits = spki.clone();
+        unused_bits[11] = 0x01;
+        assert!(RawPublicKeyEntity::try_from(&unused_bits[..]).is_err());
+
+        // An OCTET STRING instead of a BIT STRING.
+        let mut octet_string = spki.clone();
+        octet_string[9] = 0x04;
+        assert!(RawPublicKeyEntity::try_from(&octet_string[..]).is_err());
+
+        // Trailing data inside the SEQUENCE.
+        let mut inner = spki;
+        inner[1] += 1;
+        inner.push(0x00);
+        assert_eq!(
+            RawPublicKeyEntity::try_from(&inner[..]).unwrap_err(),
+            Error::TrailingData(DerTypeId::SubjectPublicKeyInfo)
+        );
+    }
+}
@@ -0,0 +1,419 @@
+//! A serializable record of a single verification attempt, for tooling that wants to show
+//! its users what webpki did.
+//!
+//! The report never contains key material: certificates are described by their subject,
+//! serial number, fingerprint and validity period only.
+//!
+//! The `serde` feature also implements `Serialize` for webpki's inspection types: [`Error`],
+//! [`crate::SubjectAltName`], [`crate::PublicKeyInfo`], [`crate::ChainReport`] and
+//! `VerifiedPath`, which serializes as the `chain` of a [`VerificationReport`] would.
+//! Throughout, byte strings serialize as lowercase hex, times as seconds since the Unix epoch
+//! and OIDs in dotted-decimal form.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, TrustAnchor, UnixTime};
+use serde::{Serialize, Serializer};
+
+use crate::cert::Cert;
+use crate::crl::{
+    ExpirationPolicy, RevocationCheckDepth, RevocationDatePolicy, RevocationOptions,
+    UnknownStatusPolicy,
+};
+use crate::end_entity::EndEntityCert;
+use crate::error::Error;
+use crate::name::write_dotted_oid;
+use crate::verify_cert::{KeyUsage, PartialPath, PathNode, Role, VerificationPolicy, VerifiedPath};
+
+/// The outcome of a verification attempt, as produced by [`verify_with_report()`].
+///
+/// This serializes to a JSON object of the following shape:
+///
+/// ```json
+/// {
+///   "time": 1492441716,
+///   "chain": [ /* CertificateReport, from end-entity to trust anchor */ ],
+///   "error": null,
+///   "inputs": { /* VerificationInputs */ }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct VerificationReport {
+    /// The verification time, in seconds since the Unix epoch.
+    pub time: u64,
+    /// The certificates in the verified path, from end-entity to trust anchor.
+    ///
+    /// If verification failed no path was built, and this contains only the end-entity
+    /// certificate (or nothing, if it could not be parsed).
+    pub chain: Vec<CertificateReport>,
+    /// The `Debug` representation of the [`crate::Error`] verification failed with, if any.
+    pub error: Option<String>,
+    /// The inputs the verification was performed with, so that the outcome can be
+    /// reproduced later.
+    pub inputs: VerificationInputs,
+}
+
+impl VerificationReport {
+    /// Returns `true` if verification succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// The inputs to a verification attempt, as recorded in a [`VerificationReport`].
+///
+/// Together with [`VerificationReport::time`] these identify everything the outcome depends
+/// on, other than the `supported_sig_algs`, usage and [`VerificationPolicy`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct VerificationInputs {
+    /// The hex-encoded SHA-256 digest of each supplied intermediate certificate, in the order
+    /// supplied. Each is `None` if the crate was built without a cryptography provider.
+    pub intermediates: Vec<Option<String>>,
+    /// The hex-encoded DER subject name of each trust anchor, in the order supplied.
+    pub trust_anchors: Vec<String>,
+    /// The revocation options, if revocation checking was requested.
+    pub revocation: Option<RevocationInputs>,
+}
+
+/// The revocation options of a [`VerificationInputs`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RevocationInputs {
+    /// The CRLs available, in the order supplied.
+    pub crls: Vec<CrlInput>,
+    /// The configured [`RevocationCheckDepth`].
+    pub depth: RevocationCheckDepth,
+    /// The configured [`UnknownStatusPolicy`].
+    pub unknown_status: UnknownStatusPolicy,
+    /// The configured [`ExpirationPolicy`].
+    pub expiration: ExpirationPolicy,
+    /// The configured [`RevocationDatePolicy`].
+    pub revocation_date: RevocationDatePolicy,
+}
+
+/// A description of one CRL in a [`RevocationInputs`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CrlInput {
+    /// The hex-encoded DER issuer name.
+    pub issuer: String,
+    /// The `nextUpdate` time, in seconds since the Unix epoch.
+    pub next_update: u64,
+}
+
+/// A description of one certificate in a [`VerificationReport`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CertificateReport {
+    /// The position of this certificate in the path.
+    pub role: CertificateRole,
+    /// The hex-encoded DER subject name.
+    pub subject: String,
+    /// The hex-encoded serial number. Trust anchors have none.
+    pub serial: Option<String>,
+    /// The hex-encoded SHA-256 digest of the certificate's DER encoding.
+    ///
+    /// Trust anchors have none, and this is also `None` if the crate was built without a
+    /// cryptography provider.
+    pub sha256_fingerprint: Option<String>,
+    /// The start of the validity period, in seconds since the Unix epoch.
+    pub not_before: Option<u64>,
+    /// The end of the validity period, in seconds since the Unix epoch.
+    pub not_after: Option<u64>,
+    /// The checks that passed for this certificate. Empty if verification failed.
+    pub checks: Vec<Check>,
+    /// The revocation status established for this certificate.
+    pub revocation: RevocationOutcome,
+}
+
+/// The position of a certificate in a verified path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum CertificateRole {
+    /// The end-entity certificate being verified.
+    EndEntity,
+    /// An intermediate certificate.
+    Intermediate,
+    /// The trust anchor the path terminates at.
+    TrustAnchor,
+}
+
+/// A check performed on a certificate in a verified path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Check {
+    /// The certificate was valid at the verification time.
+    Validity,
+    /// The certificate's basic constraints permit its position in the path.
+    BasicConstraints,
+    /// The certificate's extended key usage permits the requested usage.
+    ExtendedKeyUsage,
+    /// The certificate's signature was verified using its issuer's public key.
+    Signature,
+    /// The names of all subordinate certificates conform to this certificate's name
+    /// constraints.
+    NameConstraints,
+    /// The certificate was not revoked by an authoritative CRL.
+    Revocation,
+}
+
+/// The revocation status established for a certificate in a [`VerificationReport`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum RevocationOutcome {
+    /// Revocation status was not checked for this certificate.
+    NotChecked,
+    /// An authoritative CRL was consulted and did not list the certificate.
+    NotRevoked,
+    /// No authoritative CRL was available, and the [`UnknownStatusPolicy`] allowed it.
+    Unknown,
+}
+
+/// Verify `end_entity` as [`EndEntityCert::verify_for_usage()`] would, recording the outcome
+/// in a [`VerificationReport`].
+///
+/// A report is produced whether or not verification succeeds.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_with_report(
+    end_entity: &CertificateDer<'_>,
+    supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+    trust_anchors: &[TrustAnchor<'_>],
+    intermediate_certs: &[CertificateDer<'_>],
+    time: UnixTime,
+    usage: KeyUsage,
+    revocation: Option<RevocationOptions<'_>>,
+    policy: VerificationPolicy,
+) -> VerificationReport {
+    let mut report = VerificationReport {
+        time: time.as_secs(),
+        chain: Vec::new(),
+        error: None,
+        inputs: describe_inputs(trust_anchors, intermediate_certs, revocation),
+    };
+
+    let cert = match EndEntityCert::try_from(end_entity) {
+        Ok(cert) => cert,
+        Err(err) => {
+            report.error = Some(format!("{:?}", err));
+            return report;
+        }
+    };
+
+    let verifier = cert
+        .verifier(supported_sig_algs, trust_anchors)
+        .intermediates(intermediate_certs)
+        .at_time(time)
+        .key_usage(usage)
+        .policy(policy);
+    let verifier = match revocation {
+        Some(revocation) => verifier.revocation(revocation),
+        None => verifier,
+    };
+    match verifier.verify() {
+        Ok(path) => report.chain = describe_path(&path, revocation),
+        Err(err) => {
+            report
+                .chain
+                .push(describe_cert(&cert, CertificateRole::EndEntity));
+            report.error = Some(format!("{:?}", err));
+        }
+    }
+
+    report
+}
+
+impl Serialize for VerifiedPath<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        describe_path(self, None).serialize(serializer)
+    }
+}
+
+fn describe_inputs(
+    trust_anchors: &[TrustAnchor<'_>],
+    intermediate_certs: &[CertificateDer<'_>],
+    revocation: Option<RevocationOptions<'_>>,
+) -> VerificationInputs {
+    VerificationInputs {
+        intermediates: intermediate_certs
+            .iter()
+            .map(|cert| fingerprint(cert.as_ref()))
+            .collect(),
+        trust_anchors: trust_anchors
+            .iter()
+            .map(|anchor| hex(anchor.subject.as_ref()))
+            .collect(),
+        revocation: revocation.map(|revocation| RevocationInputs {
+            crls: revocation
+                .crls
+                .iter()
+                .map(|crl| CrlInput {
+                    issuer: hex(crl.issuer()),
+                    next_update: crl.next_update().as_secs(),
+                })
+                .collect(),
+            depth: revocation.depth,
+            unknown_status: revocation.status_policy,
+            expiration: revocation.expiration_policy,
+            revocation_date: revocation.revocation_date_policy,
+        }),
+    }
+}
+
+fn describe_path(
+    path: &VerifiedPath<'_>,
+    revocation: Option<RevocationOptions<'_>>,
+) -> Vec<CertificateReport> {
+    // Rebuild the path so its nodes can be matched against the CRLs that were consulted.
+    let mut partial = PartialPath::new(path.end_entity());
+    for cert in path.intermediate_certificates() {
+        // Neither of these can fail: the path was already built from the same certificates.
+        let cert = match Cert::from_der(untrusted::Input::from(cert.der.as_ref())) {
+            Ok(cert) => cert,
+            Err(_) => continue,
+        };
+        let _ = partial.push(cert);
+    }
+
+    let mut chain = Vec::new();
+    let head = partial.node();
+    for node in head.iter() {
+        let role = match node.role() {
+            Role::EndEntity => CertificateRole::EndEntity,
+            Role::Issuer => CertificateRole::Intermediate,
+        };
+        let mut checks = alloc::vec![
+            Check::Validity,
+            Check::BasicConstraints,
+            Check::ExtendedKeyUsage,
+            Check::Signature,
+        ];
+        if node.cert.name_constraints.is_some() {
+            checks.push(Check::NameConstraints);
+        }
+
+        let outcome = match revocation {
+            Some(revocation) => revocation_outcome(&revocation, &node),
+            None => RevocationOutcome::NotChecked,
+        };
+        if outcome == RevocationOutcome::NotRevoked {
+            checks.push(Check::Revocation);
+        }
+
+        let mut report = describe_cert(node.cert, role);
+        report.checks = checks;
+        report.revocation = outcome;
+        chain.push(report);
+    }
+
+    // `PathIter` walks from the head of the path towards the end-entity.
+    chain.reverse();
+
+    let anchor = path.anchor();
+    let mut checks = Vec::new();
+    if anchor.name_constraints.is_some() {
+        checks.push(Check::NameConstraints);
+    }
+    chain.push(CertificateReport {
+        role: CertificateRole::TrustAnchor,
+        subject: hex(anchor.subject.as_ref()),
+        serial: None,
+        sha256_fingerprint: None,
+        not_before: None,
+        not_after: None,
+        checks,
+        revocation: RevocationOutcome::NotChecked,
+    });
+
+    chain
+}
+
+fn revocation_outcome(
+    revocation: &RevocationOptions<'_>,
+    node: &PathNode<'_>,
+) -> RevocationOutcome {
+    if let (RevocationCheckDepth::EndEntity, Role::Issuer) = (revocation.depth, node.role()) {
+        return RevocationOutcome::NotChecked;
+    }
+
+    match (
+        revocation.crls.iter().any(|crl| crl.authoritative(node)),
+        revocation.status_policy,
+    ) {
+        (true, _) => RevocationOutcome::NotRevoked,
+        (false, UnknownStatusPolicy::Allow) => RevocationOutcome::Unknown,
+        // Verification would have failed with `Error::UnknownRevocationStatus`.
+        (false, UnknownStatusPolicy::Deny) => RevocationOutcome::NotChecked,
+    }
+}
+
+fn describe_cert(cert: &Cert<'_>, role: CertificateRole) -> CertificateReport {
+    let (not_before, not_after) = cert
+        .validity()
+        .map(|(not_before, not_after)| (Some(not_before.as_secs()), Some(not_after.as_secs())))
+        .unwrap_or((None, None));
+
+    CertificateReport {
+        role,
+        subject: hex(cert.subject()),
+        serial: Some(hex(cert.serial())),
+        sha256_fingerprint: fingerprint(cert.der().as_ref()),
+        not_before,
+        not_after,
+        checks: Vec::new(),
+        revocation: RevocationOutcome::NotChecked,
+    }
+}
+
+#[cfg(feature = "ring")]
+fn fingerprint(der: &[u8]) -> Option<String> {
+    Some(hex(
+        ring::digest::digest(&ring::digest::SHA256, der).as_ref()
+    ))
+}
+
+#[cfg(all(feature = "aws_lc_rs", not(feature = "ring")))]
+fn fingerprint(der: &[u8]) -> Option<String> {
+    Some(hex(aws_lc_rs::digest::digest(
+        &aws_lc_rs::digest::SHA256,
+        der,
+    )
+    .as_ref()))
+}
+
+#[cfg(not(any(feature = "ring", feature = "aws_lc_rs")))]
+fn fingerprint(_: &[u8]) -> Option<String> {
+    None
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(out, "{:02x}", byte);
+    }
+    out
+}
+
+pub(crate) fn serialize_hex<S: Serializer>(
+    bytes: &&[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&hex(bytes))
+}
+
+pub(crate) fn serialize_oid<S: Serializer>(oid: &&[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    let mut dotted = String::new();
+    match write_dotted_oid(oid, &mut dotted) {
+        Ok(()) => serializer.serialize_str(&dotted),
+        Err(_) => Err(serde::ser::Error::custom(Error::BadDer)),
+    }
+}
+
+pub(crate) fn serialize_time<S: Serializer>(
+    time: &UnixTime,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(time.as_secs())
+}
@@ -0,0 +1,274 @@
+use alloc::vec::Vec;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, TrustAnchor, UnixTime};
+
+use crate::cert::Cert;
+use crate::crl::RevocationOptions;
+use crate::end_entity::EndEntityCert;
+use crate::error::Error;
+use crate::verify_cert::{KeyUsage, VerificationPolicy};
+
+/// A verifier that, instead of failing when an issuer is missing, suspends and reports
+/// what it needs so that the caller can fetch it (for example, asynchronously via the
+/// authorityInfoAccess extension) and then [`resume()`][ResumableVerifier::resume].
+///
+/// The verifier owns the intermediates supplied so far, so it can be kept across `await`
+/// points without borrowing from the fetching code.
+pub struct ResumableVerifier<'a> {
+    end_entity: CertificateDer<'a>,
+    intermediates: Vec<CertificateDer<'static>>,
+    trust_anchors: &'a [TrustAnchor<'a>],
+    supported_sig_algs: &'a [&'a dyn SignatureVerificationAlgorithm],
+    time: UnixTime,
+    usage: KeyUsage,
+    revocation: Option<RevocationOptions<'a>>,
+    policy: VerificationPolicy,
+    last_needed: Vec<NeededResource>,
+}
+
+impl<'a> ResumableVerifier<'a> {
+    /// Create a verifier for `end_entity`, to be verified for `usage` at `time` against
+    /// `trust_anchors`.
+    pub fn new(
+        end_entity: CertificateDer<'a>,
+        supported_sig_algs: &'a [&'a dyn SignatureVerificationAlgorithm],
+        trust_anchors: &'a [TrustAnchor<'a>],
+        time: UnixTime,
+        usage: KeyUsage,
+    ) -> Self {
+        Self {
+            end_entity,
+            intermediates: Vec::new(),
+            trust_anchors,
+            supported_sig_algs,
+            time,
+            usage,
+            revocation: None,
+            policy: VerificationPolicy::default(),
+            last_needed: Vec::new(),
+        }
+    }
+
+    /// Supply intermediate certificates that are known up-front.
+    pub fn with_intermediates(
+        mut self,
+        intermediates: impl IntoIterator<Item = CertificateDer<'static>>,
+    ) -> Self {
+        self.intermediates.extend(intermediates);
+        self
+    }
+
+    /// Perform revocation checking with `revocation`.
+    pub fn with_revocation(mut self, revocation: RevocationOptions<'a>) -> Self {
+        self.revocation = Some(revocation);
+        self
+    }
+
+    /// Verify using `policy` instead of [`VerificationPolicy::default()`].
+    pub fn with_policy(mut self, policy: VerificationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Attempt verification with the certificates supplied so far.
+    pub fn verify(&mut self) -> VerificationOutcome {
+        let result = self.attempt();
+        self.outcome(result)
+    }
+
+    /// Add the fetched `resources`, then attempt verification again.
+    ///
+    /// If verification would suspend again needing exactly the same resources, the
+    /// original error is returned instead, so a caller that cannot fetch anything new
+    /// does not loop forever.
+    pub fn resume(&mut self, resources: impl IntoIterator<Item = Resource>) -> VerificationOutcome {
+        for resource in resources {
+            match resource {
+                Resource::Certificate(cert) => self.intermediates.push(cert),
+            }
+        }
+        self.verify()
+    }
+
+    fn attempt(&self) -> Result<(), Error> {
+        let cert = EndEntityCert::try_from(&self.end_entity)?;
+        let verifier = cert
+            .verifier(self.supported_sig_algs, self.trust_anchors)
+            .intermediates(&self.intermediates)
+            .at_time(self.time)
+            .key_usage(self.usage)
+            .policy(self.policy);
+        match self.revocation {
+            Some(revocation) => verifier.revocation(revocation),
+            None => verifier,
+        }
+        .verify()
+        .map(|_| ())
+    }
+
+    fn outcome(&mut self, result: Result<(), Error>) -> VerificationOutcome {
+        let err = match result {
+            Ok(()) => return VerificationOutcome::Verified,
+            Err(err @ Error::UnknownIssuer) => err,
+            Err(err) => return VerificationOutcome::Failed(err),
+        };
+
+        let needed = self.missing_issuers();
+        if needed.is_empty() || needed == self.last_needed {
+            return VerificationOutcome::Failed(err);
+        }
+
+        self.last_needed = needed.clone();
+        VerificationOutcome::Suspended(VerificationSuspended { needed })
+    }
+
+    /// Returns the issuers of supplied certificates that are neither trust anchors nor
+    /// among the supplied intermediates.
+    fn missing_issuers(&self) -> Vec<NeededResource> {
+        let certs = core::iter::once(&self.end_entity)
+            .chain(self.intermediates.iter())
+            .filter_map(|der| Cert::from_der(untrusted::Input::from(der.as_ref())).ok())
+            .collect::<Vec<_>>();
+
+        let mut needed = Vec::new();
+        for cert in &certs {
+            let issuer = cert.issuer();
+            let known = self
+                .trust_anchors
+                .iter()
+                .any(|anchor| anchor.subject.as_ref() == issuer)
+                || certs.iter().any(|other| other.subject() == issuer);
+            if known {
+                continue;
+            }
+
+            let resource = NeededResource::Issuer {
+                name: issuer.to_vec(),
+                key_id: cert
+                    .authority_key_id()
+                    .ok()
+                    .flatten()
+                    .map(|id| id.as_slice_less_safe().to_vec()),
+            };
+            if !needed.contains(&resource) {
+                needed.push(resource);
+            }
+        }
+        needed
+    }
+}
+
+/// The result of [`ResumableVerifier::verify()`] or [`ResumableVerifier::resume()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationOutcome {
+    /// The end-entity certificate was verified.
+    Verified,
+    /// Verification cannot complete without further resources.
+    Suspended(VerificationSuspended),
+    /// Verification failed.
+    Failed(Error),
+}
+
+/// The resources a [`ResumableVerifier`] needs before it can complete verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationSuspended {
+    /// The resources needed. Supplying any of them may allow verification to progress.
+    pub needed: Vec<NeededResource>,
+}
+
+/// A resource needed to complete verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NeededResource {
+    /// A certificate issuing a certificate in the path.
+    Issuer {
+        /// The DER encoded subject name the issuer must have.
+        name: Vec<u8>,
+        /// The keyIdentifier from the issued certificate's authorityKeyIdentifier
+        /// extension, if present.
+        key_id: Option<Vec<u8>>,
+    },
+}
+
+/// A resource fetched in response to a [`VerificationSuspended`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Resource {
+    /// A DER encoded certificate, used as a candidate intermediate.
+    Certificate(CertificateDer<'static>),
+}
+
+#[cfg(all(test, any(feature = "ring", feature = "aws_lc_rs")))]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::test_utils::{end_entity_params, make_end_entity, make_issuer};
+    use crate::trust_anchor::anchor_from_trusted_cert;
+
+    #[test]
+    fn resume_with_missing_intermediate() {
+        let root = make_issuer("Root");
+        let root_der = CertificateDer::from(root.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&root_der).unwrap()];
+
+        let inter = make_issuer("Intermediate");
+        let inter_der = CertificateDer::from(inter.serialize_der_with_signer(&root).unwrap());
+        let mut ee_params = end_entity_params(vec!["example.com".into()]);
+        ee_params.use_authority_key_identifier_extension = true;
+        let ee = rcgen::Certificate::from_params(ee_params).unwrap();
+        let ee = CertificateDer::from(ee.serialize_der_with_signer(&inter).unwrap());
+
+        let mut verifier = ResumableVerifier::new(
+            ee,
+            crate::ALL_VERIFICATION_ALGS,
+            anchors,
+            UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)),
+            KeyUsage::server_auth(),
+        );
+
+        let needed = match verifier.verify() {
+            VerificationOutcome::Suspended(suspended) => suspended.needed,
+            outcome => panic!("unexpected outcome {:?}", outcome),
+        };
+        let inter_cert = Cert::from_der(untrusted::Input::from(inter_der.as_ref())).unwrap();
+        assert_eq!(
+            needed,
+            vec![NeededResource::Issuer {
+                name: inter_cert.subject().to_vec(),
+                key_id: Some(inter.get_key_identifier()),
+            }]
+        );
+
+        // Resuming without the issuer fails rather than suspending forever.
+        assert_eq!(
+            verifier.resume(None),
+            VerificationOutcome::Failed(Error::UnknownIssuer)
+        );
+
+        assert_eq!(
+            verifier.resume(Some(Resource::Certificate(inter_der))),
+            VerificationOutcome::Verified
+        );
+    }
+
+    #[test]
+    fn other_errors_are_not_suspended() {
+        let root = make_issuer("Root");
+        let root_der = CertificateDer::from(root.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&root_der).unwrap()];
+        let ee = make_end_entity(&root);
+
+        let mut verifier = ResumableVerifier::new(
+            ee,
+            crate::ALL_VERIFICATION_ALGS,
+            anchors,
+            UnixTime::since_unix_epoch(Duration::from_secs(0)),
+            KeyUsage::server_auth(),
+        );
+        assert!(matches!(
+            verifier.verify(),
+            VerificationOutcome::Failed(Error::CertNotValidYet { .. })
+        ));
+    }
+}
@@ -18,6 +18,7 @@ use ring::signature;
 use crate::signed_data::alg_id;
 
 /// A `SignatureVerificationAlgorithm` implemented using *ring*.
+#[derive(Debug)]
 struct RingAlgorithm {
     public_key_alg_id: AlgorithmIdentifier,
     signature_alg_id: AlgorithmIdentifier,
@@ -73,6 +74,21 @@ pub static ECDSA_P384_SHA384: &dyn SignatureVerificationAlgorithm = &RingAlgorit
     verification_alg: &signature::ECDSA_P384_SHA384_ASN1,
 };
 
+/// RSA PKCS#1 1.5 signatures using SHA-1 for keys of 2048-8192 bits.
+///
+/// **This algorithm is insecure.** SHA-1 collisions are practical, so anyone able to get
+/// a certificate issued by a CA that signs with SHA-1 can forge certificates from it. This
+/// is only for closed networks with legacy PKIs that can't be reissued, and must never be
+/// used to verify certificates from the web PKI. It isn't in `ALL_VERIFICATION_ALGS`, and
+/// is only available with the `legacy_sha1` feature.
+#[cfg(all(feature = "alloc", feature = "legacy_sha1"))]
+pub static RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY: &dyn SignatureVerificationAlgorithm =
+    &RingAlgorithm {
+        public_key_alg_id: alg_id::RSA_ENCRYPTION,
+        signature_alg_id: alg_id::RSA_PKCS1_SHA1,
+        verification_alg: &signature::RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY,
+    };
+
 /// RSA PKCS#1 1.5 signatures using SHA-256 for keys of 2048-8192 bits.
 #[cfg(feature = "alloc")]
 pub static RSA_PKCS1_2048_8192_SHA256: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
@@ -148,6 +164,32 @@ pub static ED25519: &dyn SignatureVerificationAlgorithm = &RingAlgorithm {
     verification_alg: &signature::ED25519,
 };
 
+/// All the signature verification algorithms of this module, in the order of the crate-wide
+/// [`crate::ALL_VERIFICATION_ALGS`], except those for SHA-1.
+///
+/// The RSA algorithms are only included with the `alloc` feature.
+pub static ALL_VERIFICATION_ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    ECDSA_P256_SHA256,
+    ECDSA_P256_SHA384,
+    ECDSA_P384_SHA256,
+    ECDSA_P384_SHA384,
+    ED25519,
+    #[cfg(feature = "alloc")]
+    RSA_PKCS1_2048_8192_SHA256,
+    #[cfg(feature = "alloc")]
+    RSA_PKCS1_2048_8192_SHA384,
+    #[cfg(feature = "alloc")]
+    RSA_PKCS1_2048_8192_SHA512,
+    #[cfg(feature = "alloc")]
+    RSA_PKCS1_3072_8192_SHA384,
+    #[cfg(feature = "alloc")]
+    RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
+    #[cfg(feature = "alloc")]
+    RSA_PSS_2048_8192_SHA384_LEGACY_KEY,
+    #[cfg(feature = "alloc")]
+    RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
+];
+
 #[cfg(test)]
 #[path = "."]
 mod tests {
@@ -195,6 +237,9 @@ mod tests {
         Err(Error::UnsupportedSignatureAlgorithm)
     };
 
+    const UNSUPPORTED_SIGNATURE_ALGORITHM_FOR_ECDSA_SHA512: Error =
+        Error::UnsupportedSignatureAlgorithm;
+
     #[path = "alg_tests.rs"]
     mod alg_tests;
 }
@@ -0,0 +1,270 @@
+//! Signed certificate timestamps embedded in certificates, as described in RFC 6962.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::der::{self, FromDer, Tag};
+use crate::error::Error;
+#[cfg(feature = "alloc")]
+use crate::x509::Extension;
+
+/// A signed certificate timestamp (SCT), as yielded by
+/// [`crate::Cert::signed_certificate_timestamps()`].
+///
+/// The SCT is only decoded. Checking its signature needs the public key of the log
+/// identified by [`SignedCertificateTimestamp::log_id`], and the data it was made over,
+/// for which see `Cert::precert_tbs_certificate()`.
+///
+/// See <https://www.rfc-editor.org/rfc/rfc6962#section-3.2>.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignedCertificateTimestamp<'a> {
+    /// The SCT version. This is always 0, for version 1: SCTs of other versions are
+    /// yielded as [`Error::UnsupportedSctVersion`].
+    pub version: u8,
+    /// The SHA-256 hash of the log's public key.
+    pub log_id: [u8; 32],
+    /// When the log issued the SCT, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The encoded `CtExtensions`, without their length prefix.
+    pub extensions: &'a [u8],
+    /// The TLS `HashAlgorithm` of the signature, such as 4 for SHA-256.
+    pub hash_algorithm: u8,
+    /// The TLS `SignatureAlgorithm` of the signature, such as 3 for ECDSA.
+    pub signature_algorithm: u8,
+    /// The signature, such as a DER-encoded ECDSA signature, without its length prefix.
+    pub signature: &'a [u8],
+}
+
+impl<'a> SignedCertificateTimestamp<'a> {
+    fn from_tls(sct: untrusted::Input<'a>) -> Result<Self, Error> {
+        sct.read_all(Error::MalformedSctList, |reader| {
+            // https://www.rfc-editor.org/rfc/rfc6962#section-3.2: `enum { v1(0), (255) }`
+            let version = reader.read_byte().map_err(|_| Error::MalformedSctList)?;
+            if version != 0 {
+                return Err(Error::UnsupportedSctVersion);
+            }
+
+            let log_id = read_bytes(reader, 32)?;
+            let timestamp = read_bytes(reader, 8)?;
+            let extensions = read_u16_prefixed(reader)?;
+            let hash_algorithm = reader.read_byte().map_err(|_| Error::MalformedSctList)?;
+            let signature_algorithm = reader.read_byte().map_err(|_| Error::MalformedSctList)?;
+            let signature = read_u16_prefixed(reader)?;
+
+            Ok(Self {
+                version,
+                // Both lengths are checked by `read_bytes()`.
+                log_id: log_id.try_into().unwrap(),
+                timestamp: u64::from_be_bytes(timestamp.try_into().unwrap()),
+                extensions,
+                hash_algorithm,
+                signature_algorithm,
+                signature,
+            })
+        })
+    }
+}
+
+/// Iterates over the SCTs of the TLS-encoded `SignedCertificateTimestampList` `list`.
+///
+/// Each SCT is length-prefixed, so one that is malformed or of an unsupported version is
+/// yielded as an error, and iteration continues. If the list itself is malformed, the error
+/// is yielded and iteration ends.
+pub(crate) fn signed_certificate_timestamps(
+    list: Option<untrusted::Input<'_>>,
+) -> impl Iterator<Item = Result<SignedCertificateTimestamp<'_>, Error>> {
+    // https://www.rfc-editor.org/rfc/rfc6962#section-3.3:
+    //   `struct { SerializedSCT sct_list <1..2^16-1>; } SignedCertificateTimestampList;`
+    let mut scts = list.map(|list| {
+        list.read_all(Error::MalformedSctList, read_u16_prefixed)
+            .and_then(|scts| match scts.is_empty() {
+                true => Err(Error::MalformedSctList),
+                false => Ok(untrusted::Reader::new(untrusted::Input::from(scts))),
+            })
+    });
+
+    core::iter::from_fn(move || {
+        let mut reader = match scts.take()? {
+            Ok(reader) => reader,
+            Err(err) => return Some(Err(err)),
+        };
+        if reader.at_end() {
+            return None;
+        }
+
+        // `opaque SerializedSCT<1..2^16-1>;`
+        Some(match read_u16_prefixed(&mut reader) {
+            Ok(sct) if !sct.is_empty() => {
+                scts = Some(Ok(reader));
+                SignedCertificateTimestamp::from_tls(untrusted::Input::from(sct))
+            }
+            _ => Err(Error::MalformedSctList),
+        })
+    })
+}
+
+fn read_u16_prefixed<'a>(reader: &mut untrusted::Reader<'a>) -> Result<&'a [u8], Error> {
+    let len = read_bytes(reader, 2)?;
+    read_bytes(reader, usize::from(u16::from_be_bytes([len[0], len[1]])))
+}
+
+fn read_bytes<'a>(reader: &mut untrusted::Reader<'a>, len: usize) -> Result<&'a [u8], Error> {
+    reader
+        .read_bytes(len)
+        .map(|bytes| bytes.as_slice_less_safe())
+        .map_err(|_| Error::MalformedSctList)
+}
+
+/// Returns the DER encoding of the certificate's TBSCertificate `tbs`, including its tag and
+/// length, with the signedCertificateTimestampList extension `sct_list_id` removed.
+///
+/// If that leaves no extensions, the extensions field is removed too.
+#[cfg(feature = "alloc")]
+pub(crate) fn precert_tbs_certificate(
+    tbs: untrusted::Input<'_>,
+    sct_list_id: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let tbs = tbs.read_all(Error::BadDer, |reader| {
+        der::expect_tag(reader, Tag::Sequence)
+    })?;
+
+    let mut fields = Vec::with_capacity(tbs.len());
+    let mut reader = untrusted::Reader::new(tbs);
+    while !reader.at_end() {
+        let (field, (tag, value)) = reader.read_partial(der::read_tag_and_get_value)?;
+        if tag != u8::from(Tag::ContextSpecificConstructed3) {
+            fields.extend_from_slice(field.as_slice_less_safe());
+            continue;
+        }
+
+        let mut kept = Vec::with_capacity(value.len());
+        let extensions = value.read_all(Error::BadDer, |reader| {
+            der::expect_tag(reader, Tag::Sequence)
+        })?;
+        let mut extensions = untrusted::Reader::new(extensions);
+        while !extensions.at_end() {
+            let (extension, id) = extensions.read_partial(|reader| {
+                der::nested(reader, Tag::Sequence, Error::BadDer, Extension::from_der)
+                    .map(|extension| extension.id)
+            })?;
+            if id.as_slice_less_safe() != sct_list_id {
+                kept.extend_from_slice(extension.as_slice_less_safe());
+            }
+        }
+
+        if !kept.is_empty() {
+            let mut sequence = Vec::with_capacity(kept.len() + 4);
+            der::push_tlv(&mut sequence, Tag::Sequence.into(), &kept);
+            der::push_tlv(&mut fields, tag, &sequence);
+        }
+    }
+
+    let mut out = Vec::with_capacity(fields.len() + 4);
+    der::push_tlv(&mut out, Tag::Sequence.into(), &fields);
+    Ok(out)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    fn scts(list: &[u8]) -> Vec<Result<SignedCertificateTimestamp<'_>, Error>> {
+        signed_certificate_timestamps(Some(untrusted::Input::from(list))).collect()
+    }
+
+    fn sct(version: u8, signature: &[u8]) -> Vec<u8> {
+        let mut sct = vec![version];
+        sct.extend_from_slice(&[0xab; 32]);
+        sct.extend_from_slice(&0x0000_0180_0000_0001u64.to_be_bytes());
+        sct.extend_from_slice(&[0x00, 0x02, 0xee, 0xff]);
+        sct.extend_from_slice(&[0x04, 0x03]);
+        sct.extend_from_slice(&u16::try_from(signature.len()).unwrap().to_be_bytes());
+        sct.extend_from_slice(signature);
+        sct
+    }
+
+    fn list(scts: &[&[u8]]) -> Vec<u8> {
+        let mut contents = Vec::new();
+        for sct in scts {
+            contents.extend_from_slice(&u16::try_from(sct.len()).unwrap().to_be_bytes());
+            contents.extend_from_slice(sct);
+        }
+        let mut list = u16::try_from(contents.len())
+            .unwrap()
+            .to_be_bytes()
+            .to_vec();
+        list.extend_from_slice(&contents);
+        list
+    }
+
+    #[test]
+    fn absent() {
+        assert_eq!(signed_certificate_timestamps(None).count(), 0);
+    }
+
+    #[test]
+    fn decodes_v1() {
+        let (first, second) = (sct(0, &[0x30, 0x00]), sct(0, &[]));
+        let list = list(&[&first, &second]);
+        let scts = scts(&list);
+        assert_eq!(scts.len(), 2);
+
+        let sct = scts[0].unwrap();
+        assert_eq!(sct.version, 0);
+        assert_eq!(sct.log_id, [0xab; 32]);
+        assert_eq!(sct.timestamp, 0x0000_0180_0000_0001);
+        assert_eq!(sct.extensions, &[0xee, 0xff]);
+        assert_eq!(sct.hash_algorithm, 4);
+        assert_eq!(sct.signature_algorithm, 3);
+        assert_eq!(sct.signature, &[0x30, 0x00]);
+        assert!(scts[1].unwrap().signature.is_empty());
+    }
+
+    #[test]
+    fn malformed_sct_continues() {
+        let good = sct(0, &[0x01]);
+        let v2 = sct(1, &[0x01]);
+        let mut trailing = good.clone();
+        trailing.push(0x00);
+        let truncated = &good[..good.len() - 1];
+
+        let list = list(&[&v2, &trailing, truncated, &good]);
+        let scts = scts(&list);
+        assert_eq!(
+            scts[..3],
+            [
+                Err(Error::UnsupportedSctVersion),
+                Err(Error::MalformedSctList),
+                Err(Error::MalformedSctList),
+            ]
+        );
+        assert_eq!(scts[3].unwrap().signature, &[0x01]);
+    }
+
+    #[test]
+    fn malformed_list_ends() {
+        let good = sct(0, &[0x01]);
+        for list in [
+            // No length prefix, or an empty list.
+            vec![],
+            vec![0x00],
+            vec![0x00, 0x00],
+            // The list length disagrees with the extension.
+            list(&[&good])[1..].to_vec(),
+            [list(&[&good]), vec![0x00]].concat(),
+            // An empty SCT.
+            vec![0x00, 0x02, 0x00, 0x00],
+        ] {
+            assert_eq!(scts(&list), [Err(Error::MalformedSctList)], "{list:x?}");
+        }
+
+        // An SCT overrunning the list ends iteration after the SCTs before it.
+        let mut list = list(&[&good, &good]);
+        list[good.len() + 5] += 1;
+        let scts = scts(&list);
+        assert_eq!(scts.len(), 2);
+        assert!(scts[0].is_ok());
+        assert_eq!(scts[1], Err(Error::MalformedSctList));
+    }
+}
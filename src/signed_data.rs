@@ -15,6 +15,10 @@
 use crate::der::{self, FromDer};
 use crate::error::{DerTypeId, Error};
 use crate::verify_cert::Budget;
+#[cfg(feature = "zeroize")]
+use crate::zeroize::zeroize;
+#[cfg(feature = "alloc")]
+use crate::zeroize::Redacted;
 
 use pki_types::{AlgorithmIdentifier, SignatureVerificationAlgorithm};
 
@@ -25,7 +29,7 @@ use alloc::vec::Vec;
 /// encoded in the format "tbs||signatureAlgorithm||signature". This structure
 /// captures this pattern as an owned data type.
 #[cfg(feature = "alloc")]
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub(crate) struct OwnedSignedData {
     /// The signed data. This would be `tbsCertificate` in the case of an X.509
     /// certificate, `tbsResponseData` in the case of an OCSP response, `tbsCertList`
@@ -57,10 +61,30 @@ impl OwnedSignedData {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl core::fmt::Debug for OwnedSignedData {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OwnedSignedData")
+            .field("data", &Redacted(&self.data))
+            .field("algorithm", &Redacted(&self.algorithm))
+            .field("signature", &Redacted(&self.signature))
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for OwnedSignedData {
+    fn drop(&mut self) {
+        zeroize(&mut self.data);
+        zeroize(&mut self.algorithm);
+        zeroize(&mut self.signature);
+    }
+}
+
 /// X.509 certificates and related items that are signed are almost always
 /// encoded in the format "tbs||signatureAlgorithm||signature". This structure
 /// captures this pattern.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct SignedData<'a> {
     /// The signed data. This would be `tbsCertificate` in the case of an X.509
     /// certificate, `tbsResponseData` in the case of an OCSP response, `tbsCertList`
@@ -208,6 +232,64 @@ pub(crate) fn verify_signed_data(
     }
 }
 
+/// Returns true if the `AlgorithmIdentifier` values `a` and `b` identify the same algorithm.
+///
+/// RFC 5280 requires the `signature` field of a TBSCertificate (section 4.1.2.3) or TBSCertList
+/// (section 5.1.2.2) to contain the same algorithm identifier as the outer `signatureAlgorithm`.
+/// We compare the DER encodings, with one documented equivalence: for the PKCS#1 RSA algorithms,
+/// whose parameters must be NULL (RFC 4055 section 5), some encoders omit the parameters
+/// entirely. Like mozilla::pkix, we treat absent and NULL parameters as equal for those OIDs.
+pub(crate) fn algorithm_identifiers_match(a: untrusted::Input, b: untrusted::Input) -> bool {
+    if a.as_slice_less_safe() == b.as_slice_less_safe() {
+        return true;
+    }
+
+    match (rsa_pkcs1_oid(a), rsa_pkcs1_oid(b)) {
+        (Some(a), Some(b)) => a.as_slice_less_safe() == b.as_slice_less_safe(),
+        _ => false,
+    }
+}
+
+/// If `alg_id` is a PKCS#1 RSA `AlgorithmIdentifier` with NULL or absent parameters, returns
+/// its OID.
+fn rsa_pkcs1_oid(alg_id: untrusted::Input) -> Option<untrusted::Input> {
+    // pkcs-1 OBJECT IDENTIFIER ::= { iso(1) member-body(2) us(840) rsadsi(113549) pkcs(1) 1 }
+    static PKCS1: [u8; 8] = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01];
+    // id-RSASSA-PSS OBJECT IDENTIFIER ::= { pkcs-1 10 }, whose parameters are not NULL.
+    static RSASSA_PSS: [u8; 9] = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0a];
+
+    alg_id
+        .read_all(Error::BadDer, |reader| {
+            let oid = der::expect_tag(reader, der::Tag::OID)?;
+            match reader.at_end() || der::expect_tag(reader, der::Tag::Null)?.is_empty() {
+                true => Ok(oid),
+                false => Err(Error::BadDer),
+            }
+        })
+        .ok()
+        .filter(|oid| {
+            let oid = oid.as_slice_less_safe();
+            oid.len() == PKCS1.len() + 1 && oid.starts_with(&PKCS1) && oid != RSASSA_PSS
+        })
+}
+
+/// Returns an error if none of `supported_algorithms` verify signatures made with the key in
+/// `spki_value`.
+#[cfg(feature = "alloc")]
+pub(crate) fn check_public_key_supported(
+    spki_value: untrusted::Input,
+    supported_algorithms: &[&dyn SignatureVerificationAlgorithm],
+) -> Result<(), Error> {
+    let spki = der::read_all::<SubjectPublicKeyInfo>(spki_value)?;
+    match supported_algorithms
+        .iter()
+        .any(|alg| alg.public_key_alg_id().as_ref() == spki.algorithm_id_value.as_slice_less_safe())
+    {
+        true => Ok(()),
+        false => Err(Error::UnsupportedSignatureAlgorithmForPublicKey),
+    }
+}
+
 pub(crate) fn verify_signature(
     signature_alg: &dyn SignatureVerificationAlgorithm,
     spki_value: untrusted::Input,
@@ -228,7 +310,7 @@ pub(crate) fn verify_signature(
         .map_err(|_| Error::InvalidSignatureForPublicKey)
 }
 
-struct SubjectPublicKeyInfo<'a> {
+pub(crate) struct SubjectPublicKeyInfo<'a> {
     algorithm_id_value: untrusted::Input<'a>,
     key_value: untrusted::Input<'a>,
 }
@@ -250,6 +332,133 @@ impl<'a> FromDer<'a> for SubjectPublicKeyInfo<'a> {
     const TYPE_ID: DerTypeId = DerTypeId::SubjectPublicKeyInfo;
 }
 
+/// The algorithm and size of a public key, as described by its subjectPublicKeyInfo.
+///
+/// This is for enforcing policies on keys beyond what the supported signature algorithms
+/// require, such as a larger minimum RSA modulus, or rejecting keys on some curves.
+///
+/// With the `serde` feature, OIDs serialize in dotted-decimal form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum PublicKeyInfo<'a> {
+    /// An `rsaEncryption` key (RFC 3279 section 2.3.1).
+    Rsa {
+        /// The length of the modulus in bits, not counting leading zeros.
+        modulus_bits: usize,
+    },
+    /// An `id-ecPublicKey` key on a named curve (RFC 5480 section 2.1.1).
+    Ec {
+        /// The named curve.
+        curve: EcCurve<'a>,
+    },
+    /// An Ed25519 key (RFC 8410).
+    Ed25519,
+    /// A key of any other algorithm.
+    Unknown {
+        /// The value of the algorithm OID, excluding its tag and length.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::report::serialize_oid")
+        )]
+        alg_oid: &'a [u8],
+    },
+}
+
+impl<'a> PublicKeyInfo<'a> {
+    /// Parse the value of a subjectPublicKeyInfo: its contents, excluding the outer SEQUENCE
+    /// tag and length.
+    pub(crate) fn from_spki(spki: untrusted::Input<'a>) -> Result<Self, Error> {
+        let spki = der::read_all::<SubjectPublicKeyInfo>(spki)?;
+        let (alg_oid, params) = spki.algorithm_id_value.read_all(Error::BadDer, |reader| {
+            let alg_oid = der::expect_tag(reader, der::Tag::OID)?;
+            Ok((alg_oid, reader.read_bytes_to_end()))
+        })?;
+
+        Ok(match alg_oid.as_slice_less_safe() {
+            RSA_ENCRYPTION => {
+                // RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+                let modulus = spki.key_value.read_all(Error::BadDer, |reader| {
+                    der::nested(reader, der::Tag::Sequence, Error::BadDer, |reader| {
+                        let modulus = der::nonnegative_integer(reader)?;
+                        der::nonnegative_integer(reader)?;
+                        Ok(modulus)
+                    })
+                })?;
+                Self::Rsa {
+                    modulus_bits: bit_length(modulus.as_slice_less_safe()),
+                }
+            }
+            EC_PUBLIC_KEY => {
+                // Only named curves are permitted (RFC 5480 section 2.1.1).
+                let curve = params.read_all(Error::BadDer, |reader| {
+                    der::expect_tag(reader, der::Tag::OID)
+                })?;
+                Self::Ec {
+                    curve: match curve.as_slice_less_safe() {
+                        SECP256R1 => EcCurve::P256,
+                        SECP384R1 => EcCurve::P384,
+                        SECP521R1 => EcCurve::P521,
+                        SECP256K1 => EcCurve::Secp256k1,
+                        curve_oid => EcCurve::Unknown { curve_oid },
+                    },
+                }
+            }
+            ED25519 => Self::Ed25519,
+            alg_oid => Self::Unknown { alg_oid },
+        })
+    }
+}
+
+/// A named elliptic curve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum EcCurve<'a> {
+    /// NIST P-256, also known as `secp256r1` and `prime256v1`.
+    P256,
+    /// NIST P-384, also known as `secp384r1`.
+    P384,
+    /// NIST P-521, also known as `secp521r1`.
+    P521,
+    /// `secp256k1` (SEC 2 section 2.4.1).
+    Secp256k1,
+    /// Any other named curve.
+    Unknown {
+        /// The value of the curve OID, excluding its tag and length.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::report::serialize_oid")
+        )]
+        curve_oid: &'a [u8],
+    },
+}
+
+/// The number of bits in the big-endian unsigned integer `value`, which has no leading zero
+/// bytes.
+fn bit_length(value: &[u8]) -> usize {
+    match value.split_first() {
+        Some((&first, rest)) => 8 * rest.len() + (0..8).filter(|bit| first >> bit != 0).count(),
+        None => 0,
+    }
+}
+
+const RSA_ENCRYPTION: &[u8] = &oid![1, 2, 0x86, 0x48, 0x86, 0xf7, 0x0d, 1, 1, 1];
+const EC_PUBLIC_KEY: &[u8] = &oid![1, 2, 0x86, 0x48, 0xce, 0x3d, 2, 1];
+const ED25519: &[u8] = &oid![1, 3, 101, 112];
+const SECP256R1: &[u8] = &oid![1, 2, 0x86, 0x48, 0xce, 0x3d, 3, 1, 7];
+const SECP384R1: &[u8] = &oid![1, 3, 0x81, 0x04, 0, 34];
+const SECP521R1: &[u8] = &oid![1, 3, 0x81, 0x04, 0, 35];
+const SECP256K1: &[u8] = &oid![1, 3, 0x81, 0x04, 0, 10];
+
 /// Encodings of the PKIX AlgorithmIdentifier type.
 ///
 /// This module contains a set of common values, and exists to keep the
@@ -275,10 +484,32 @@ pub mod alg_id {
     pub const ECDSA_SHA384: AlgorithmIdentifier =
         AlgorithmIdentifier::from_slice(include_bytes!("data/alg-ecdsa-sha384.der"));
 
+    /// AlgorithmIdentifier for `ecdsa-with-SHA512`.
+    pub const ECDSA_SHA512: AlgorithmIdentifier =
+        AlgorithmIdentifier::from_slice(include_bytes!("data/alg-ecdsa-sha512.der"));
+
+    /// AlgorithmIdentifier for `id-ecPublicKey` with named curve `secp521r1`.
+    pub const ECDSA_P521: AlgorithmIdentifier =
+        AlgorithmIdentifier::from_slice(include_bytes!("data/alg-ecdsa-p521.der"));
+
+    /// AlgorithmIdentifier for `ecdsa-with-SHA1`.
+    ///
+    /// SHA-1 is not collision resistant: see the `legacy_sha1` feature.
+    #[cfg(feature = "legacy_sha1")]
+    pub const ECDSA_SHA1: AlgorithmIdentifier =
+        AlgorithmIdentifier::from_slice(include_bytes!("data/alg-ecdsa-sha1.der"));
+
     /// AlgorithmIdentifier for `rsaEncryption`.
     pub const RSA_ENCRYPTION: AlgorithmIdentifier =
         AlgorithmIdentifier::from_slice(include_bytes!("data/alg-rsa-encryption.der"));
 
+    /// AlgorithmIdentifier for `sha1WithRSAEncryption`.
+    ///
+    /// SHA-1 is not collision resistant: see the `legacy_sha1` feature.
+    #[cfg(feature = "legacy_sha1")]
+    pub const RSA_PKCS1_SHA1: AlgorithmIdentifier =
+        AlgorithmIdentifier::from_slice(include_bytes!("data/alg-rsa-pkcs1-sha1.der"));
+
     /// AlgorithmIdentifier for `sha256WithRSAEncryption`.
     pub const RSA_PKCS1_SHA256: AlgorithmIdentifier =
         AlgorithmIdentifier::from_slice(include_bytes!("data/alg-rsa-pkcs1-sha256.der"));
@@ -319,3 +550,152 @@ pub mod alg_id {
     pub const ED25519: AlgorithmIdentifier =
         AlgorithmIdentifier::from_slice(include_bytes!("data/alg-ed25519.der"));
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn algorithm_identifiers_rsa_null_params_equivalent() {
+        let with_null = include_bytes!("data/alg-rsa-pkcs1-sha256.der");
+        // The same AlgorithmIdentifier, with the trailing NULL parameters omitted.
+        let without_params = &with_null[..with_null.len() - 2];
+
+        let with_null = untrusted::Input::from(with_null);
+        let without_params = untrusted::Input::from(without_params);
+        assert!(algorithm_identifiers_match(with_null, without_params));
+        assert!(algorithm_identifiers_match(without_params, with_null));
+    }
+
+    #[test]
+    fn algorithm_identifiers_mismatch() {
+        let rsa_sha256 = untrusted::Input::from(include_bytes!("data/alg-rsa-pkcs1-sha256.der"));
+        let rsa_sha384 = untrusted::Input::from(include_bytes!("data/alg-rsa-pkcs1-sha384.der"));
+        let ecdsa_sha256 = untrusted::Input::from(include_bytes!("data/alg-ecdsa-sha256.der"));
+        assert!(!algorithm_identifiers_match(rsa_sha256, rsa_sha384));
+        assert!(!algorithm_identifiers_match(rsa_sha256, ecdsa_sha256));
+
+        // Parameters other than NULL are never considered equivalent to absent parameters.
+        let pss = include_bytes!("data/alg-rsa-pss-sha256.der");
+        let pss_oid_only = &pss[..11];
+        assert!(!algorithm_identifiers_match(
+            untrusted::Input::from(pss),
+            untrusted::Input::from(pss_oid_only)
+        ));
+
+        // Non-RSA algorithms are compared exactly.
+        let ecdsa_with_null = [
+            include_bytes!("data/alg-ecdsa-sha256.der").as_slice(),
+            &[0x05, 0x00],
+        ]
+        .concat();
+        assert!(!algorithm_identifiers_match(
+            ecdsa_sha256,
+            untrusted::Input::from(&ecdsa_with_null)
+        ));
+    }
+
+    /// DER-encode `value` with `tag`.
+    fn tlv(tag: u8, value: &[u8]) -> Vec<u8> {
+        let [high, low] = u16::try_from(value.len()).unwrap().to_be_bytes();
+        let mut der = match (high, low) {
+            (0, 0..=0x7f) => vec![tag, low],
+            (0, _) => vec![tag, 0x81, low],
+            _ => vec![tag, 0x82, high, low],
+        };
+        der.extend_from_slice(value);
+        der
+    }
+
+    /// The value of a subjectPublicKeyInfo with the algorithm `alg_oid` and `params`, and the
+    /// key `key`.
+    fn spki(alg_oid: &[u8], params: &[u8], key: &[u8]) -> Vec<u8> {
+        let alg = [&tlv(0x06, alg_oid)[..], params].concat();
+        [tlv(0x30, &alg), tlv(0x03, &[&[0x00], key].concat())].concat()
+    }
+
+    /// The value of an `rsaEncryption` subjectPublicKeyInfo, with the encoded modulus `modulus`.
+    fn rsa_spki(modulus: &[u8]) -> Vec<u8> {
+        let key = [tlv(0x02, modulus), tlv(0x02, &[0x01, 0x00, 0x01])].concat();
+        spki(RSA_ENCRYPTION, &[0x05, 0x00], &tlv(0x30, &key))
+    }
+
+    fn public_key_info(spki: &[u8]) -> Result<PublicKeyInfo<'_>, Error> {
+        PublicKeyInfo::from_spki(untrusted::Input::from(spki))
+    }
+
+    #[test]
+    fn rsa_modulus_bits() {
+        for bytes in [256, 384, 512] {
+            // A leading zero byte, as the top bit of the modulus is set.
+            let mut modulus = vec![0xff; bytes + 1];
+            modulus[0] = 0x00;
+            modulus[1] = 0x80;
+            assert_eq!(
+                public_key_info(&rsa_spki(&modulus)),
+                Ok(PublicKeyInfo::Rsa {
+                    modulus_bits: bytes * 8
+                })
+            );
+
+            // Without a leading zero byte, as the top bit is clear.
+            for (top, unused_bits) in [(0x7f, 1), (0x40, 1), (0x01, 7)] {
+                let mut modulus = vec![0xff; bytes];
+                modulus[0] = top;
+                assert_eq!(
+                    public_key_info(&rsa_spki(&modulus)),
+                    Ok(PublicKeyInfo::Rsa {
+                        modulus_bits: bytes * 8 - unused_bits
+                    })
+                );
+            }
+        }
+
+        // An unnecessary leading zero byte.
+        assert_eq!(
+            public_key_info(&rsa_spki(&[0x00, 0x7f, 0xff])),
+            Err(Error::BadDer)
+        );
+        // A negative modulus.
+        assert_eq!(
+            public_key_info(&rsa_spki(&[0x80, 0xff])),
+            Err(Error::BadDer)
+        );
+    }
+
+    #[test]
+    fn ec_curves() {
+        let ec = |params: &[u8]| public_key_info(&spki(EC_PUBLIC_KEY, params, &[0x04])).map(|_| ());
+        for (curve_oid, curve) in [
+            (SECP256K1, EcCurve::Secp256k1),
+            (
+                &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x07][..],
+                EcCurve::Unknown {
+                    curve_oid: &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x07],
+                },
+            ),
+        ] {
+            let spki = spki(EC_PUBLIC_KEY, &tlv(0x06, curve_oid), &[0x04]);
+            assert_eq!(public_key_info(&spki), Ok(PublicKeyInfo::Ec { curve }));
+        }
+
+        // Curves must be named.
+        assert_eq!(ec(&[]), Err(Error::BadDer));
+        assert_eq!(ec(&[0x05, 0x00]), Err(Error::BadDer));
+        assert_eq!(ec(&tlv(0x30, &tlv(0x02, &[0x01]))), Err(Error::BadDer));
+    }
+
+    #[test]
+    fn unknown_algorithm() {
+        // id-X25519, which is for key agreement.
+        let spki = spki(&[0x2b, 0x65, 0x6e], &[], &[0x00; 32]);
+        assert_eq!(
+            public_key_info(&spki),
+            Ok(PublicKeyInfo::Unknown {
+                alg_oid: &[0x2b, 0x65, 0x6e]
+            })
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use alloc::vec::Vec;
+
+use super::verify::GeneralName;
+use crate::der::{self, FromDer};
+use crate::error::Error;
+
+/// The name constraints enforced on a verified path, as returned by
+/// `VerifiedPath::name_constraints()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EnforcedNameConstraints<'a> {
+    subtrees: Vec<NameSubtree<'a>>,
+}
+
+impl<'a> EnforcedNameConstraints<'a> {
+    /// All the enforced subtrees, those of the trust anchor first, then those of each
+    /// intermediate certificate from the end-entity's issuer upwards.
+    pub fn subtrees(&self) -> &[NameSubtree<'a>] {
+        &self.subtrees
+    }
+
+    /// The enforced permitted subtrees.
+    pub fn permitted(&self) -> impl Iterator<Item = &NameSubtree<'a>> {
+        self.subtrees
+            .iter()
+            .filter(|subtree| subtree.kind == SubtreeKind::Permitted)
+    }
+
+    /// The enforced excluded subtrees.
+    pub fn excluded(&self) -> impl Iterator<Item = &NameSubtree<'a>> {
+        self.subtrees
+            .iter()
+            .filter(|subtree| subtree.kind == SubtreeKind::Excluded)
+    }
+
+    /// Returns `true` if no name constraints were enforced.
+    pub fn is_empty(&self) -> bool {
+        self.subtrees.is_empty()
+    }
+
+    /// Adds the subtrees of a DER-encoded NameConstraints value (without the outer SEQUENCE
+    /// tag), imposed by `source`.
+    pub(crate) fn extend(
+        &mut self,
+        constraints: untrusted::Input<'a>,
+        source: ConstraintSource,
+    ) -> Result<(), Error> {
+        constraints.read_all(Error::BadDer, |reader| {
+            for (kind, tag) in [
+                (
+                    SubtreeKind::Permitted,
+                    der::Tag::ContextSpecificConstructed0,
+                ),
+                (SubtreeKind::Excluded, der::Tag::ContextSpecificConstructed1),
+            ] {
+                if !reader.peek(tag.into()) {
+                    continue;
+                }
+
+                let subtrees = der::expect_tag(reader, tag)?;
+                subtrees.read_all(Error::BadDer, |subtrees| {
+                    while !subtrees.at_end() {
+                        let subtree = der::expect_tag(subtrees, der::Tag::Sequence)?;
+                        let (der, base) = subtree.read_all(Error::BadDer, |subtree| {
+                            subtree.read_partial(GeneralName::from_der)
+                        })?;
+                        self.subtrees.push(NameSubtree {
+                            kind,
+                            source,
+                            base: ConstraintBase::try_from(base)?,
+                            der: der.as_slice_less_safe(),
+                        });
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// One permitted or excluded subtree of an [`EnforcedNameConstraints`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NameSubtree<'a> {
+    /// Whether names must be within this subtree, or must not be.
+    pub kind: SubtreeKind,
+    /// The certificate or trust anchor imposing this subtree.
+    pub source: ConstraintSource,
+    /// The subtree's base name.
+    pub base: ConstraintBase<'a>,
+    /// The DER encoding of the base `GeneralName`.
+    pub der: &'a [u8],
+}
+
+/// Whether a [`NameSubtree`] is permitted or excluded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubtreeKind {
+    /// From the `permittedSubtrees` field.
+    Permitted,
+    /// From the `excludedSubtrees` field.
+    Excluded,
+}
+
+/// Where a [`NameSubtree`] came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConstraintSource {
+    /// The name constraints of the path's trust anchor.
+    TrustAnchor,
+    /// The name constraints extension of the intermediate certificate at this index in
+    /// `VerifiedPath::intermediate_certificates()`.
+    Intermediate(usize),
+    /// The name constraints extension of a certificate, as returned by
+    /// `Cert::name_constraints()`.
+    Certificate,
+}
+
+/// The base name of a [`NameSubtree`], by name type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConstraintBase<'a> {
+    /// A DNS name constraint, such as `example.com` or `.example.com`.
+    DnsName(&'a [u8]),
+    /// An IP address range.
+    IpAddress {
+        /// The network address: four octets for IPv4, sixteen for IPv6.
+        address: &'a [u8],
+        /// The network mask, of the same length as `address`.
+        mask: &'a [u8],
+    },
+    /// A DER-encoded directory name.
+    DirectoryName(&'a [u8]),
+    /// An email address constraint: a mailbox such as `joe@example.com`, a host such as
+    /// `example.com`, or a domain such as `.example.com`.
+    Rfc822Name(&'a [u8]),
+    /// A URI constraint on the host of a URI, such as `host.example.com` or `.example.com`.
+    UniformResourceIdentifier(&'a [u8]),
+    /// Another name type, identified by its `GeneralName` tag number. Constraints on these
+    /// are not implemented, and any path containing a name of the same type is rejected.
+    Unsupported(u8),
+}
+
+impl<'a> TryFrom<GeneralName<'a>> for ConstraintBase<'a> {
+    type Error = Error;
+
+    fn try_from(name: GeneralName<'a>) -> Result<Self, Self::Error> {
+        Ok(match name {
+            GeneralName::DnsName(name) => Self::DnsName(name.as_slice_less_safe()),
+            GeneralName::IpAddress(range) => {
+                let range = range.as_slice_less_safe();
+                if !matches!(range.len(), 8 | 32) {
+                    return Err(Error::InvalidNetworkMaskConstraint);
+                }
+                let (address, mask) = range.split_at(range.len() / 2);
+                Self::IpAddress { address, mask }
+            }
+            GeneralName::DirectoryName(name) => Self::DirectoryName(name.as_slice_less_safe()),
+            GeneralName::Rfc822Name(name) => Self::Rfc822Name(name.as_slice_less_safe()),
+            GeneralName::UniformResourceIdentifier(uri) => {
+                Self::UniformResourceIdentifier(uri.as_slice_less_safe())
+            }
+            GeneralName::Unsupported(tag) => Self::Unsupported(tag),
+        })
+    }
+}
@@ -0,0 +1,244 @@
+use crate::der::{self, Tag};
+use crate::error::Error;
+
+pub(super) const UTF8_STRING_TAG: u8 = 0x0c;
+pub(super) const PRINTABLE_STRING_TAG: u8 = 0x13;
+
+// https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.10 says:
+//
+//     Restrictions of the form directoryName MUST be applied to the subject field in the
+//     certificate (when the certificate includes a non-empty subject field) and to any
+//     names of type directoryName in the subjectAltName extension.
+//
+// and https://www.rfc-editor.org/rfc/rfc5280#section-7.1 describes the comparison. A name
+// is within a subtree if the subtree's base is a prefix of it, comparing RDN by RDN. RDNs
+// match if they have the same attributes in any order. Attribute values that are both
+// PrintableString or UTF8String are compared after removing leading and trailing spaces,
+// collapsing internal runs of spaces to one, and folding ASCII case; this simplifies the
+// caseIgnoreMatch rule of RFC 4518 by comparing non-ASCII characters exactly. Other values
+// must be identical.
+//
+// `name` and `constraint` may each be either a DER-encoded Name, or the contents of one.
+pub(super) fn presented_id_matches_constraint(
+    name: untrusted::Input,
+    constraint: untrusted::Input,
+) -> Result<bool, Error> {
+    let mut names = untrusted::Reader::new(rdn_sequence(name)?);
+    let mut bases = untrusted::Reader::new(rdn_sequence(constraint)?);
+
+    while !bases.at_end() {
+        let base = der::expect_tag(&mut bases, Tag::Set)?;
+        if names.at_end() {
+            return Ok(false);
+        }
+        let name = der::expect_tag(&mut names, Tag::Set)?;
+        if !rdn_matches(name, base)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+pub(super) fn rdn_sequence(name: untrusted::Input) -> Result<untrusted::Input, Error> {
+    match name.as_slice_less_safe().first() == Some(&u8::from(Tag::Sequence)) {
+        true => name.read_all(Error::BadDer, |reader| {
+            der::expect_tag(reader, Tag::Sequence)
+        }),
+        false => Ok(name),
+    }
+}
+
+fn rdn_matches(name: untrusted::Input, base: untrusted::Input) -> Result<bool, Error> {
+    let mut name_count = 0;
+    let mut attributes = untrusted::Reader::new(name);
+    while !attributes.at_end() {
+        der::expect_tag(&mut attributes, Tag::Sequence)?;
+        name_count += 1;
+    }
+
+    let mut base_count = 0;
+    let mut bases = untrusted::Reader::new(base);
+    while !bases.at_end() {
+        let base = der::expect_tag(&mut bases, Tag::Sequence)?;
+        base_count += 1;
+
+        let mut found = false;
+        let mut attributes = untrusted::Reader::new(name);
+        while !attributes.at_end() {
+            let attribute = der::expect_tag(&mut attributes, Tag::Sequence)?;
+            if attribute_matches(attribute, base)? {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            return Ok(false);
+        }
+    }
+
+    Ok(name_count == base_count)
+}
+
+fn attribute_matches(name: untrusted::Input, base: untrusted::Input) -> Result<bool, Error> {
+    let (name_type, name_tag, name_value) = attribute(name)?;
+    let (base_type, base_tag, base_value) = attribute(base)?;
+    if name_type != base_type {
+        return Ok(false);
+    }
+
+    let is_string = |tag| matches!(tag, UTF8_STRING_TAG | PRINTABLE_STRING_TAG);
+    Ok(match is_string(name_tag) && is_string(base_tag) {
+        true => normalized(name_value).eq(normalized(base_value)),
+        false => name_tag == base_tag && name_value == base_value,
+    })
+}
+
+pub(super) fn attribute<'a>(
+    attribute: untrusted::Input<'a>,
+) -> Result<(&'a [u8], u8, &'a [u8]), Error> {
+    attribute.read_all(Error::BadDer, |reader| {
+        let attribute_type = der::expect_tag(reader, Tag::OID)?;
+        let (tag, value) = der::read_tag_and_get_value(reader)?;
+        Ok((
+            attribute_type.as_slice_less_safe(),
+            tag,
+            value.as_slice_less_safe(),
+        ))
+    })
+}
+
+fn normalized(value: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    let start = value.iter().take_while(|b| **b == b' ').count();
+    let end = value.len()
+        - value[start..]
+            .iter()
+            .rev()
+            .take_while(|b| **b == b' ')
+            .count();
+    let value = &value[start..end];
+    value
+        .iter()
+        .enumerate()
+        // The first byte isn't a space, so `i` is nonzero for any space.
+        .filter(move |(i, b)| !(**b == b' ' && value[i - 1] == b' '))
+        .map(|(_, b)| b.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    // Name { RDN { commonName }, ... }, with each RDN holding one attribute, or an RDN
+    // holding several if its attributes are given as a slice.
+    fn name(rdns: &[&[(u8, &str)]]) -> Vec<u8> {
+        let mut name = Vec::new();
+        for rdn in rdns {
+            let mut set = Vec::new();
+            for (tag, value) in *rdn {
+                let mut attribute = vec![0x06, 0x03, 0x55, 0x04, 0x03, *tag];
+                attribute.push(u8::try_from(value.len()).unwrap());
+                attribute.extend_from_slice(value.as_bytes());
+                set.extend([0x30, u8::try_from(attribute.len()).unwrap()]);
+                set.extend(attribute);
+            }
+            name.extend([0x31, u8::try_from(set.len()).unwrap()]);
+            name.extend(set);
+        }
+        let mut der = vec![0x30, u8::try_from(name.len()).unwrap()];
+        der.extend(name);
+        der
+    }
+
+    fn matches(name: &[u8], constraint: &[u8]) -> Result<bool, Error> {
+        presented_id_matches_constraint(
+            untrusted::Input::from(name),
+            untrusted::Input::from(constraint),
+        )
+    }
+
+    const P: u8 = PRINTABLE_STRING_TAG;
+    const U: u8 = UTF8_STRING_TAG;
+    const IA5: u8 = 0x16;
+
+    #[test]
+    fn prefix_of_rdns() {
+        let constraint = name(&[&[(P, "US")], &[(P, "Gov")]]);
+        assert_eq!(
+            matches(
+                &name(&[&[(P, "US")], &[(P, "Gov")], &[(P, "DoD")]]),
+                &constraint
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            matches(&name(&[&[(P, "US")], &[(P, "Gov")]]), &constraint),
+            Ok(true)
+        );
+        assert_eq!(matches(&name(&[&[(P, "US")]]), &constraint), Ok(false));
+        assert_eq!(
+            matches(&name(&[&[(P, "Gov")], &[(P, "US")]]), &constraint),
+            Ok(false)
+        );
+        // An empty base is a prefix of every name.
+        assert_eq!(matches(&name(&[&[(P, "US")]]), &name(&[])), Ok(true));
+    }
+
+    #[test]
+    fn contents_of_name() {
+        let der = name(&[&[(P, "US")], &[(P, "Gov")]]);
+        assert_eq!(matches(&der[2..], &name(&[&[(P, "US")]])), Ok(true));
+        assert_eq!(matches(&der[2..], &name(&[&[(P, "CA")]])), Ok(false));
+    }
+
+    #[test]
+    fn multi_valued_rdns() {
+        let constraint = name(&[&[(P, "A"), (P, "B")]]);
+        assert_eq!(
+            matches(&name(&[&[(P, "B"), (P, "A")]]), &constraint),
+            Ok(true)
+        );
+        assert_eq!(matches(&name(&[&[(P, "A")]]), &constraint), Ok(false));
+        assert_eq!(
+            matches(&name(&[&[(P, "A"), (P, "B"), (P, "C")]]), &constraint),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn string_normalization() {
+        let constraint = name(&[&[(P, "Example Org")]]);
+        for matching in ["example org", "  EXAMPLE   ORG ", "Example Org"] {
+            assert_eq!(matches(&name(&[&[(U, matching)]]), &constraint), Ok(true));
+            assert_eq!(matches(&name(&[&[(P, matching)]]), &constraint), Ok(true));
+        }
+        assert_eq!(
+            matches(&name(&[&[(P, "ExampleOrg")]]), &constraint),
+            Ok(false)
+        );
+        // Other string types are compared exactly.
+        assert_eq!(
+            matches(
+                &name(&[&[(IA5, "example org")]]),
+                &name(&[&[(IA5, "Example Org")]])
+            ),
+            Ok(false)
+        );
+        assert_eq!(
+            matches(&name(&[&[(IA5, "Example Org")]]), &constraint),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(matches(&[0x30, 0x02, 0x31], &name(&[])), Err(Error::BadDer));
+        let der = name(&[&[(P, "US")]]);
+        assert_eq!(
+            matches(&der, &[0x30, 0x04, 0x31, 0x02, 0x04, 0x00]),
+            Err(Error::BadDer)
+        );
+    }
+}
@@ -12,18 +12,101 @@
 // ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
 // OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
-use core::fmt::Write;
+use core::fmt::{self, Write};
 
-use pki_types::{DnsName, InvalidDnsNameError};
+use pki_types::{DnsName, InvalidDnsNameError, IpAddr};
 
-use super::verify::{GeneralName, NameIterator};
+use super::directory_name::{self, PRINTABLE_STRING_TAG, UTF8_STRING_TAG};
+use super::verify::{GeneralName, NameIterator, Subtrees, COMMON_NAME, IA5_STRING_TAG};
+use crate::der;
 use crate::Error;
 
+/// Verifies that `reference` matches one of the dNSName subject alternative names in `names`.
+///
+/// A dNSName that is not a syntactically valid DNS name never matches. If nothing matches and
+/// there was such a name, [`Error::MalformedDnsIdentifier`] is returned instead of
+/// [`Error::CertNotValidForName`], as the certificate is malformed rather than for another
+/// name.
+///
+/// When `subject` is given and there are no dNSName subject alternative names at all, the
+/// commonName attributes of the subject are considered instead, as for v1 certificates, which
+/// can't have extensions (see `Verifier::allow_v1_certificates()`). A commonName must then be
+/// the reference name exactly, ignoring ASCII case: wildcards are not expanded.
 pub(crate) fn verify_dns_names(
     reference: &DnsName<'_>,
     mut names: NameIterator<'_>,
+    subject: Option<untrusted::Input>,
 ) -> Result<(), Error> {
     let dns_name = untrusted::Input::from(reference.as_ref().as_bytes());
+    let mut presented_any = false;
+    let mut malformed_any = false;
+    let result = names.find_map(|result| {
+        let name = match result {
+            Ok(name) => name,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let presented_id = match name {
+            GeneralName::DnsName(presented) => presented,
+            _ => return None,
+        };
+
+        presented_any = true;
+        match presented_id_matches_reference_id(presented_id, IdRole::Reference, dns_name) {
+            Ok(true) => Some(Ok(())),
+            Ok(false) => None,
+            Err(Error::MalformedDnsIdentifier) => {
+                malformed_any = true;
+                None
+            }
+            Err(e) => Some(Err(e)),
+        }
+    });
+    match result {
+        Some(result) => return result,
+        None if malformed_any => return Err(Error::MalformedDnsIdentifier),
+        None => {}
+    }
+
+    let subject = match subject {
+        Some(subject) if !presented_any => subject,
+        _ => return Err(Error::CertNotValidForName),
+    };
+
+    let mut rdns = untrusted::Reader::new(directory_name::rdn_sequence(subject)?);
+    while !rdns.at_end() {
+        let mut attributes = untrusted::Reader::new(der::expect_tag(&mut rdns, der::Tag::Set)?);
+        while !attributes.at_end() {
+            let (attribute_type, tag, value) =
+                directory_name::attribute(der::expect_tag(&mut attributes, der::Tag::Sequence)?)?;
+            let value = untrusted::Input::from(value);
+            if attribute_type == COMMON_NAME
+                && matches!(tag, UTF8_STRING_TAG | PRINTABLE_STRING_TAG | IA5_STRING_TAG)
+                && is_valid_dns_id(value, IdRole::Presented, Wildcards::Deny)
+                && presented_id_matches_reference_id(value, IdRole::Reference, dns_name)?
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(Error::CertNotValidForName)
+}
+
+pub(crate) fn dns_names_cover_pattern(
+    pattern: &[u8],
+    mut names: NameIterator<'_>,
+) -> Result<bool, Error> {
+    let pattern_id = untrusted::Input::from(pattern);
+    if !is_valid_dns_id(pattern_id, IdRole::Reference, Wildcards::Allow) {
+        return Err(Error::MalformedDnsIdentifier);
+    }
+
+    // Like the reference IDs of `verify_dns_names`, the pattern may be absolute.
+    let wildcard_suffix = pattern
+        .strip_prefix(b"*")
+        .map(|suffix| suffix.strip_suffix(b".").unwrap_or(suffix));
+
     names
         .find_map(|result| {
             let name = match result {
@@ -36,15 +119,148 @@ pub(crate) fn verify_dns_names(
                 _ => return None,
             };
 
-            match presented_id_matches_reference_id(presented_id, IdRole::Reference, dns_name) {
-                Ok(true) => Some(Ok(())),
-                Ok(false) | Err(Error::MalformedDnsIdentifier) => None,
-                Err(e) => Some(Err(e)),
+            let suffix = match wildcard_suffix {
+                Some(suffix) => suffix,
+                None => {
+                    return match presented_id_matches_reference_id(
+                        presented_id,
+                        IdRole::Reference,
+                        pattern_id,
+                    ) {
+                        Ok(true) => Some(Ok(true)),
+                        Ok(false) | Err(Error::MalformedDnsIdentifier) => None,
+                        Err(e) => Some(Err(e)),
+                    };
+                }
+            };
+
+            // A wildcard pattern is covered only by a wildcard name for the same labels:
+            // no single concrete name covers every name the pattern stands for, and a
+            // wildcard label never stands for more than one label.
+            if !is_valid_dns_id(presented_id, IdRole::Presented, Wildcards::Allow) {
+                return None;
+            }
+            match presented_id.as_slice_less_safe().strip_prefix(b"*") {
+                Some(presented_suffix) if presented_suffix.eq_ignore_ascii_case(suffix) => {
+                    Some(Ok(true))
+                }
+                _ => None,
             }
         })
-        .unwrap_or(Err(Error::CertNotValidForName))
+        .unwrap_or(Ok(false))
+}
+
+/// Parses `name` as a DNS name to verify a certificate for, explaining why if it isn't one.
+///
+/// An absolute name, with a trailing dot, is accepted and the dot removed, as browsers do.
+/// Presented names are never absolute, so "example.com." matches what "example.com" does.
+/// IP addresses are rejected with [`DnsNameError::LooksLikeIpAddress`]: use
+/// `ServerName::IpAddress` for them.
+pub fn parse_dns_name(name: &str) -> Result<DnsName<'_>, DnsNameError> {
+    if IpAddr::try_from(name).is_ok() {
+        return Err(DnsNameError::LooksLikeIpAddress);
+    }
+
+    let relative = name.strip_suffix('.').unwrap_or(name);
+    match DnsName::try_from(relative) {
+        Ok(dns_name) if !relative.ends_with('.') => Ok(dns_name),
+        _ => Err(DnsNameError::diagnose(relative)),
+    }
 }
 
+/// Why [`parse_dns_name()`] rejected a name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DnsNameError {
+    /// The name is empty.
+    Empty,
+    /// The name is longer than 253 characters.
+    TooLong,
+    /// The name has an empty label, as in "example..com" or ".example.com".
+    EmptyLabel,
+    /// A label is longer than 63 characters.
+    LabelTooLong,
+    /// The character at byte offset `position` can't be there: labels consist of ASCII
+    /// letters, digits, hyphens and underscores, and don't start or end with a hyphen.
+    InvalidCharacter {
+        /// The byte offset of the character in the name.
+        position: usize,
+    },
+    /// The name is an IP address.
+    LooksLikeIpAddress,
+    /// The last label is all digits, as in "example.123".
+    NumericLastLabel,
+}
+
+impl DnsNameError {
+    /// Finds why `name`, with any trailing dot already removed, isn't a valid DNS name.
+    fn diagnose(name: &str) -> Self {
+        if name.is_empty() {
+            return Self::Empty;
+        }
+        if name.len() > 253 {
+            return Self::TooLong;
+        }
+
+        let mut start = 0;
+        for label in name.split('.') {
+            if label.is_empty() {
+                return Self::EmptyLabel;
+            }
+            if label.len() > 63 {
+                return Self::LabelTooLong;
+            }
+
+            let last = label.len() - 1;
+            for (i, byte) in label.bytes().enumerate() {
+                let valid = match byte {
+                    b'-' => i != 0 && i != last,
+                    _ => byte.is_ascii_alphanumeric() || byte == b'_',
+                };
+                if !valid {
+                    return Self::InvalidCharacter {
+                        position: start + i,
+                    };
+                }
+            }
+            start += label.len() + 1;
+        }
+
+        // Every other requirement of a DNS name is met.
+        Self::NumericLastLabel
+    }
+}
+
+impl fmt::Display for DnsNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("the DNS name is empty"),
+            Self::TooLong => f.write_str("the DNS name is longer than 253 characters"),
+            Self::EmptyLabel => f.write_str(
+                "the DNS name has an empty label; check for a leading dot or consecutive dots",
+            ),
+            Self::LabelTooLong => {
+                f.write_str("a label of the DNS name is longer than 63 characters")
+            }
+            Self::InvalidCharacter { position } => write!(
+                f,
+                "invalid character at position {position} of the DNS name; labels may only \
+                 contain ASCII letters, digits, hyphens and underscores, and may not start or \
+                 end with a hyphen"
+            ),
+            Self::LooksLikeIpAddress => {
+                f.write_str("the name is an IP address; verify it as ServerName::IpAddress")
+            }
+            Self::NumericLastLabel => {
+                f.write_str("the last label of the DNS name is all digits, like an IP address")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for DnsNameError {}
+
 /// A reference to a DNS Name presented by a server that may include a wildcard.
 ///
 /// A `WildcardDnsNameRef` is guaranteed to be syntactically valid. The validity rules
@@ -98,6 +314,65 @@ impl core::fmt::Debug for WildcardDnsNameRef<'_> {
     }
 }
 
+/// Whether `presented_id` is a dNSName that [`verify_dns_names`] would compare, rather than
+/// skip as malformed.
+#[cfg(feature = "alloc")]
+pub(super) fn is_valid_presented_dns_id(presented_id: untrusted::Input) -> bool {
+    is_valid_dns_id(presented_id, IdRole::Presented, Wildcards::Allow)
+}
+
+/// Whether `presented_id` is within `constraint`, a dNSName of `subtrees`.
+///
+/// A wildcard `presented_id` is matched as the set of names it stands for, as described in the
+/// notes on name constraints below: all of them must be in a permitted subtree, and any of them
+/// in an excluded subtree.
+pub(super) fn presented_id_matches_constraint(
+    presented_id: untrusted::Input,
+    constraint: untrusted::Input,
+    subtrees: Subtrees,
+) -> Result<bool, Error> {
+    let suffix = match presented_id.as_slice_less_safe().strip_prefix(b"*.") {
+        Some(suffix) => untrusted::Input::from(suffix),
+        None => {
+            return presented_id_matches_reference_id(
+                presented_id,
+                IdRole::NameConstraint,
+                constraint,
+            )
+        }
+    };
+    if !is_valid_dns_id(presented_id, IdRole::Presented, Wildcards::Allow) {
+        return Err(Error::MalformedDnsIdentifier);
+    }
+    if !is_valid_dns_id(constraint, IdRole::NameConstraint, Wildcards::Deny) {
+        return Err(Error::MalformedNameConstraint);
+    }
+
+    // The names the wildcard stands for are strict subdomains of `suffix`, so they are all
+    // within "example.com" and ".example.com" alike if `suffix` is "example.com" or one of its
+    // subdomains, and otherwise some of them can only be within a permitted subtree.
+    let constraint_bytes = constraint.as_slice_less_safe();
+    let (leading_dot, without_dot) = match constraint_bytes.strip_prefix(b".") {
+        Some(without_dot) => (true, without_dot),
+        None => (false, constraint_bytes),
+    };
+    let within = presented_id_matches_reference_id(
+        suffix,
+        IdRole::NameConstraint,
+        untrusted::Input::from(without_dot),
+    )?;
+    if within || leading_dot || matches!(subtrees, Subtrees::PermittedSubtrees) {
+        return Ok(within);
+    }
+
+    // Otherwise, one of the names is within "secret.example.com" if the constraint is a single
+    // label prepended to `suffix`.
+    Ok(match constraint_bytes.iter().position(|b| *b == b'.') {
+        Some(dot) => constraint_bytes[dot + 1..].eq_ignore_ascii_case(suffix.as_slice_less_safe()),
+        None => false,
+    })
+}
+
 // We assume that both presented_dns_id and reference_dns_id are encoded in
 // such a way that US-ASCII (7-bit) characters are encoded in one byte and no
 // encoding of a non-US-ASCII character contains a code point in the range
@@ -202,6 +477,22 @@ impl core::fmt::Debug for WildcardDnsNameRef<'_> {
 //     Q: Is "." a valid dNSName constraint? If so, what does it mean?
 //     A: No, because absolute names are not allowed (see above).
 //
+//     Q: How are wildcard presented identifiers matched? For example, does
+//        "*.example.com" match constraints of "example.com", ".example.com",
+//        "sub.example.com" or "secret.example.com"?
+//     A: A wildcard stands for every name formed by replacing the "*" with a
+//        single label, and is matched as the set of those names. In
+//        permittedSubtrees, it matches only if every name it stands for does: its
+//        suffix ("example.com") must be the constraint or one of its subdomains,
+//        whether or not the constraint starts with a ".", since a wildcard never
+//        stands for its suffix itself. So "*.example.com" is permitted by
+//        "example.com" and ".example.com", but not by "sub.example.com". In
+//        excludedSubtrees, it matches if any name it stands for does: also when
+//        the constraint, without a leading ".", is one label prepended to the
+//        suffix. So "*.example.com" is excluded by "example.com", ".example.com"
+//        and "secret.example.com", but not by ".secret.example.com" or
+//        "a.secret.example.com". See `presented_id_matches_constraint`.
+//
 // [0] RFC 6265 (Cookies) Domain Matching rules:
 //     http://tools.ietf.org/html/rfc6265#section-5.1.3
 // [1] NSS source code:
@@ -353,6 +644,14 @@ fn ascii_lower(b: u8) -> u8 {
     }
 }
 
+/// Returns true if `name`, the value of a subject commonName, looks like a DNS name rather than
+/// a name meant for people: it has the syntax of a presented DNS identifier, and more than one
+/// label.
+pub(super) fn looks_like_dns_name(name: untrusted::Input) -> bool {
+    name.as_slice_less_safe().contains(&b'.')
+        && is_valid_dns_id(name, IdRole::Presented, Wildcards::Allow)
+}
+
 #[derive(Clone, Copy, PartialEq)]
 enum Wildcards {
     Deny,
@@ -910,11 +1209,16 @@ mod tests {
             b"*.example.com",
             Err(Error::MalformedNameConstraint),
         ),
-        // No empty presented IDs allowed
+        // No empty or whitespace-only presented IDs allowed
         (b"", b"", Err(Error::MalformedDnsIdentifier)),
+        (b" ", b"", Err(Error::MalformedDnsIdentifier)),
+        (b"", b"example.com", Err(Error::MalformedDnsIdentifier)),
         // Empty constraints match everything allowed
         (b"example.com", b"", Ok(true)),
         (b"*.example.com", b"", Ok(true)),
+        // ... but whitespace-only constraints are malformed, not empty
+        (b"example.com", b" ", Err(Error::MalformedNameConstraint)),
+        (b"example.com", b"  ", Err(Error::MalformedNameConstraint)),
         // Constraints that start with a dot
         (b"www.example.com", b".example.com", Ok(true)),
         (b"www.example.com", b".EXAMPLE.COM", Ok(true)),
@@ -943,6 +1247,131 @@ mod tests {
         (b"www.example.com", b"www.example.com", Ok(true)),
     ];
 
+    #[test]
+    fn wildcard_matches_constraint() {
+        for (presented, constraint, permitted, excluded) in [
+            (&b"*.example.com"[..], &b""[..], true, true),
+            (b"*.example.com", b"example.com", true, true),
+            (b"*.example.com", b".example.com", true, true),
+            (b"*.example.com", b"EXAMPLE.COM", true, true),
+            (b"*.example.com", b"com", true, true),
+            (b"*.example.com", b".com", true, true),
+            (b"*.sub.example.com", b".example.com", true, true),
+            // Only some of the names are within the subtree.
+            (b"*.example.com", b"sub.example.com", false, true),
+            (b"*.example.com", b"SUB.example.COM", false, true),
+            // None of the names are.
+            (b"*.example.com", b".sub.example.com", false, false),
+            (b"*.example.com", b"a.sub.example.com", false, false),
+            (b"*.example.com", b"sub.axample.com", false, false),
+            (b"*.example.com", b"badexample.com", false, false),
+            (b"*.example.com", b"other.com", false, false),
+        ] {
+            for (subtrees, expected) in [
+                (Subtrees::PermittedSubtrees, permitted),
+                (Subtrees::ExcludedSubtrees, excluded),
+            ] {
+                assert_eq!(
+                    presented_id_matches_constraint(
+                        untrusted::Input::from(presented),
+                        untrusted::Input::from(constraint),
+                        subtrees,
+                    ),
+                    Ok(expected),
+                    "{:?} {:?}",
+                    core::str::from_utf8(presented),
+                    core::str::from_utf8(constraint),
+                );
+            }
+        }
+
+        for (presented, constraint, error) in [
+            (
+                &b"*.example.com."[..],
+                &b"example.com"[..],
+                Error::MalformedDnsIdentifier,
+            ),
+            (b"*.com", b"com", Error::MalformedDnsIdentifier),
+            (
+                b"*.example.com",
+                b"..example.com",
+                Error::MalformedNameConstraint,
+            ),
+            (
+                b"*.example.com",
+                b"*.example.com",
+                Error::MalformedNameConstraint,
+            ),
+            (
+                b"*.example.com",
+                b"example.com.",
+                Error::MalformedNameConstraint,
+            ),
+        ] {
+            assert_eq!(
+                presented_id_matches_constraint(
+                    untrusted::Input::from(presented),
+                    untrusted::Input::from(constraint),
+                    Subtrees::ExcludedSubtrees,
+                ),
+                Err(error)
+            );
+        }
+    }
+
+    #[test]
+    fn parse_dns_name_errors() {
+        let long_label = "a".repeat(64);
+        let long_name = ["a"; 128].join(".");
+        for (name, expected) in [
+            ("", DnsNameError::Empty),
+            (".", DnsNameError::Empty),
+            (long_name.as_str(), DnsNameError::TooLong),
+            ("example..com", DnsNameError::EmptyLabel),
+            (".example.com", DnsNameError::EmptyLabel),
+            ("example.com..", DnsNameError::EmptyLabel),
+            (long_label.as_str(), DnsNameError::LabelTooLong),
+            (
+                "exa mple.com",
+                DnsNameError::InvalidCharacter { position: 3 },
+            ),
+            (
+                "www.-example.com",
+                DnsNameError::InvalidCharacter { position: 4 },
+            ),
+            (
+                "www.example-.com",
+                DnsNameError::InvalidCharacter { position: 11 },
+            ),
+            (
+                "caf\u{e9}.example",
+                DnsNameError::InvalidCharacter { position: 3 },
+            ),
+            (
+                "*.example.com",
+                DnsNameError::InvalidCharacter { position: 0 },
+            ),
+            ("192.0.2.1", DnsNameError::LooksLikeIpAddress),
+            ("2001:db8::1", DnsNameError::LooksLikeIpAddress),
+            ("example.123", DnsNameError::NumericLastLabel),
+            ("1.2.3", DnsNameError::NumericLastLabel),
+        ] {
+            assert_eq!(parse_dns_name(name), Err(expected), "{name:?}");
+        }
+    }
+
+    #[test]
+    fn parse_dns_name_strips_trailing_dot() {
+        for (name, expected) in [
+            ("example.com", "example.com"),
+            ("example.com.", "example.com"),
+            ("Example.COM.", "Example.COM"),
+            ("_srv.a-b.example", "_srv.a-b.example"),
+        ] {
+            assert_eq!(parse_dns_name(name).unwrap().as_ref(), expected);
+        }
+    }
+
     #[test]
     fn presented_matches_constraint_test() {
         for &(presented, constraint, expected_result) in PRESENTED_MATCHES_CONSTRAINT {
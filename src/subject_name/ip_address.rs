@@ -106,9 +106,9 @@ pub(super) fn presented_id_matches_constraint(
             return Err(Error::InvalidNetworkMaskConstraint);
         }
 
-        // invalid name length, or anything else
+        // invalid name length
         _ => {
-            return Err(Error::BadDer);
+            return Err(Error::MalformedIpAddress);
         }
     };
 
@@ -218,7 +218,7 @@ mod tests {
                 untrusted::Input::from(&[0xC0, 0x00, 0x02]),
                 untrusted::Input::from(&[0xC0, 0x00, 0x02, 0x00, 0xFF, 0xFF, 0xFF, 0x00]),
             ),
-            Err(Error::BadDer),
+            Err(Error::MalformedIpAddress),
         );
 
         // Invalid name length (longer)
@@ -227,7 +227,7 @@ mod tests {
                 untrusted::Input::from(&[0xC0, 0x00, 0x02, 0x00, 0x00]),
                 untrusted::Input::from(&[0xC0, 0x00, 0x02, 0x00, 0xFF, 0xFF, 0xFF, 0x00]),
             ),
-            Err(Error::BadDer),
+            Err(Error::MalformedIpAddress),
         );
 
         // Unmatching constraint size (shorter)
@@ -359,7 +359,7 @@ mod tests {
                     0x00, 0x00, 0x00, 0x00, 0x00, 0x00
                 ]),
             ),
-            Err(Error::BadDer),
+            Err(Error::MalformedIpAddress),
         );
 
         // Invalid name length (longer)
@@ -375,7 +375,7 @@ mod tests {
                     0x00, 0x00, 0x00, 0x00, 0x00, 0x00
                 ]),
             ),
-            Err(Error::BadDer),
+            Err(Error::MalformedIpAddress),
         );
 
         // Unmatching constraint size (shorter)
@@ -469,14 +469,14 @@ mod tests {
                 untrusted::Input::from(b"\x00\x00\x00"),
                 untrusted::Input::from(b"")
             ),
-            Err(Error::BadDer)
+            Err(Error::MalformedIpAddress)
         );
         assert_eq!(
             presented_id_matches_constraint(
                 untrusted::Input::from(b"\x00\x00\x00\x00\x00"),
                 untrusted::Input::from(b"")
             ),
-            Err(Error::BadDer)
+            Err(Error::MalformedIpAddress)
         );
 
         assert_eq!(
@@ -486,7 +486,7 @@ mod tests {
                 ),
                 untrusted::Input::from(b"")
             ),
-            Err(Error::BadDer)
+            Err(Error::MalformedIpAddress)
         );
         assert_eq!(
             presented_id_matches_constraint(
@@ -495,7 +495,7 @@ mod tests {
                 ),
                 untrusted::Input::from(b"")
             ),
-            Err(Error::BadDer)
+            Err(Error::MalformedIpAddress)
         );
 
         // wrong length constraints
@@ -12,11 +12,34 @@
 // ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
 // OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
+#[cfg(feature = "alloc")]
+mod constraints;
+#[cfg(feature = "alloc")]
+pub use constraints::{
+    ConstraintBase, ConstraintSource, EnforcedNameConstraints, NameSubtree, SubtreeKind,
+};
+
+mod directory_name;
+
+#[cfg(feature = "alloc")]
+mod presented;
+#[cfg(feature = "alloc")]
+pub(crate) use presented::presented_names;
+#[cfg(feature = "alloc")]
+pub use presented::{InvalidNameContext, PresentedName};
+
 mod dns_name;
-pub(crate) use dns_name::{verify_dns_names, WildcardDnsNameRef};
+pub(crate) use dns_name::{dns_names_cover_pattern, verify_dns_names, WildcardDnsNameRef};
+pub use dns_name::{parse_dns_name, DnsNameError};
 
 mod ip_address;
 pub(crate) use ip_address::verify_ip_address_names;
 
+mod rfc822_name;
+pub(crate) use rfc822_name::verify_email_addresses;
+
+mod uri;
+
 mod verify;
-pub(super) use verify::{check_name_constraints, GeneralName, NameIterator};
+pub use verify::SubjectAltName;
+pub(super) use verify::{check_name_constraints, other_name, GeneralName, NameIterator};
@@ -0,0 +1,82 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use pki_types::ServerName;
+
+use super::dns_name::is_valid_presented_dns_id;
+use super::verify::GeneralName;
+use crate::der::FromDer;
+use crate::error::Error;
+
+/// A name verification error, with the names the certificate presented, as returned by
+/// [`crate::EndEntityCert::verify_is_valid_for_subject_name_verbose()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidNameContext {
+    /// The error, as [`crate::EndEntityCert::verify_is_valid_for_subject_name()`] would have
+    /// returned it.
+    pub error: Error,
+    /// The name the certificate was verified for.
+    pub expected: ServerName<'static>,
+    /// The dNSName and iPAddress subject alternative names of the certificate, in order,
+    /// including those that were skipped as malformed.
+    ///
+    /// Both kinds are listed whatever the kind of [`InvalidNameContext::expected`], although
+    /// only names of the same kind are compared with it. If the subjectAltName extension is
+    /// not valid DER, this lists the names before the first undecodable entry.
+    pub presented: Vec<PresentedName>,
+}
+
+impl From<InvalidNameContext> for Error {
+    fn from(err: InvalidNameContext) -> Self {
+        err.error
+    }
+}
+
+/// A dNSName or iPAddress subject alternative name, as listed by
+/// [`InvalidNameContext::presented`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PresentedName {
+    /// A dNSName, which may have a wildcard left-most label.
+    DnsName(String),
+    /// An iPAddress: four octets for IPv4, sixteen for IPv6.
+    IpAddress(Vec<u8>),
+    /// A dNSName that is not a syntactically valid DNS name, which never matches.
+    MalformedDnsName(Vec<u8>),
+    /// An iPAddress that is neither four nor sixteen octets long.
+    ///
+    /// This ends name verification with [`Error::MalformedIpAddress`].
+    MalformedIpAddress(Vec<u8>),
+}
+
+pub(crate) fn presented_names(subject_alt_name: Option<untrusted::Input>) -> Vec<PresentedName> {
+    let mut presented = Vec::new();
+    let mut names = match subject_alt_name {
+        Some(subject_alt_name) => untrusted::Reader::new(subject_alt_name),
+        None => return presented,
+    };
+
+    while !names.at_end() {
+        let name = match GeneralName::from_der(&mut names) {
+            Ok(name) => name,
+            Err(_) => break,
+        };
+
+        presented.push(match name {
+            GeneralName::DnsName(name) => match is_valid_presented_dns_id(name) {
+                // A valid DNS name is ASCII.
+                true => PresentedName::DnsName(
+                    String::from_utf8_lossy(name.as_slice_less_safe()).into_owned(),
+                ),
+                false => PresentedName::MalformedDnsName(name.as_slice_less_safe().to_vec()),
+            },
+            GeneralName::IpAddress(ip) => match ip.len() {
+                4 | 16 => PresentedName::IpAddress(ip.as_slice_less_safe().to_vec()),
+                _ => PresentedName::MalformedIpAddress(ip.as_slice_less_safe().to_vec()),
+            },
+            _ => continue,
+        });
+    }
+
+    presented
+}
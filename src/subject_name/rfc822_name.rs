@@ -0,0 +1,193 @@
+use super::directory_name;
+use super::verify::{GeneralName, NameIterator, EMAIL_ADDRESS, IA5_STRING_TAG};
+use crate::der;
+use crate::error::Error;
+
+/// Verifies that `reference` matches one of the rfc822Name subject alternative names in
+/// `names`, comparing the local part exactly and the host case-insensitively.
+///
+/// When `subject` is given and there are no rfc822Name subject alternative names at all,
+/// the emailAddress attributes of the subject are considered instead, as for existing
+/// certificates that predate RFC 5280 (<https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.6>).
+pub(crate) fn verify_email_addresses(
+    reference: &str,
+    names: NameIterator<'_>,
+    subject: Option<untrusted::Input>,
+) -> Result<(), Error> {
+    let reference = split_mailbox(reference.as_bytes()).ok_or(Error::MalformedEmailIdentifier)?;
+
+    let mut presented_any = false;
+    for name in names {
+        let presented_id = match name? {
+            GeneralName::Rfc822Name(presented) => presented,
+            _ => continue,
+        };
+
+        presented_any = true;
+        if presented_id_matches_reference_id(presented_id.as_slice_less_safe(), reference) {
+            return Ok(());
+        }
+    }
+
+    let subject = match subject {
+        Some(subject) if !presented_any => subject,
+        _ => return Err(Error::CertNotValidForName),
+    };
+
+    let mut rdns = untrusted::Reader::new(directory_name::rdn_sequence(subject)?);
+    while !rdns.at_end() {
+        let mut attributes = untrusted::Reader::new(der::expect_tag(&mut rdns, der::Tag::Set)?);
+        while !attributes.at_end() {
+            let (attribute_type, tag, value) =
+                directory_name::attribute(der::expect_tag(&mut attributes, der::Tag::Sequence)?)?;
+            if (attribute_type, tag) == (EMAIL_ADDRESS, IA5_STRING_TAG)
+                && presented_id_matches_reference_id(value, reference)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(Error::CertNotValidForName)
+}
+
+// A presented mailbox that is not well-formed never matches.
+fn presented_id_matches_reference_id(
+    presented_id: &[u8],
+    (reference_local_part, reference_host): (&[u8], &[u8]),
+) -> bool {
+    match split_mailbox(presented_id) {
+        Some((local_part, host)) => {
+            local_part == reference_local_part && host.eq_ignore_ascii_case(reference_host)
+        }
+        None => false,
+    }
+}
+
+// https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.10 says:
+//
+//     A name constraint for Internet mail addresses MAY specify a particular mailbox, all
+//     addresses at a particular host, or all mailboxes in a domain.  To indicate a
+//     particular mailbox, the constraint is the complete mail address.  For example,
+//     "root@example.com" indicates the root mailbox on the host "example.com".  To
+//     indicate all Internet mail addresses on a particular host, the constraint is
+//     specified as the host name.  For example, the constraint "example.com" is satisfied
+//     by any mail address at the host "example.com".  To specify any address within a
+//     domain, the constraint is specified with a leading period (as with URIs).  For
+//     example, ".example.com" indicates all the Internet mail addresses in the domain
+//     "example.com", but not Internet mail addresses on the host "example.com".
+//
+// Following https://www.rfc-editor.org/rfc/rfc5280#section-7.5, the local part of a mailbox
+// is compared exactly, and the host case-insensitively.
+pub(super) fn presented_id_matches_constraint(
+    name: untrusted::Input,
+    constraint: untrusted::Input,
+) -> Result<bool, Error> {
+    let (local_part, host) = match split_mailbox(name.as_slice_less_safe()) {
+        Some(mailbox) => mailbox,
+        None => return Err(Error::BadDer),
+    };
+
+    let constraint = constraint.as_slice_less_safe();
+    if !constraint.is_ascii() {
+        return Err(Error::BadDer);
+    }
+
+    Ok(match split_mailbox(constraint) {
+        Some((constraint_local_part, constraint_host)) => {
+            local_part == constraint_local_part && host.eq_ignore_ascii_case(constraint_host)
+        }
+        None if constraint.starts_with(b".") => {
+            host.len() > constraint.len()
+                && host[host.len() - constraint.len()..].eq_ignore_ascii_case(constraint)
+        }
+        None => host.eq_ignore_ascii_case(constraint),
+    })
+}
+
+/// Splits an ASCII mailbox into its local part and host, at the last `@`.
+fn split_mailbox(mailbox: &[u8]) -> Option<(&[u8], &[u8])> {
+    if !mailbox.is_ascii() {
+        return None;
+    }
+
+    let at = mailbox.iter().rposition(|b| *b == b'@')?;
+    match (&mailbox[..at], &mailbox[at + 1..]) {
+        (local_part, host) if !local_part.is_empty() && !host.is_empty() => {
+            Some((local_part, host))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mailbox_matches(presented: &str, reference: &str) -> bool {
+        presented_id_matches_reference_id(
+            presented.as_bytes(),
+            split_mailbox(reference.as_bytes()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn mailbox_reference() {
+        assert!(mailbox_matches("joe@example.com", "joe@example.com"));
+        assert!(mailbox_matches("joe@EXAMPLE.com", "joe@example.COM"));
+        assert!(!mailbox_matches("Joe@example.com", "joe@example.com"));
+        assert!(!mailbox_matches("joe@sub.example.com", "joe@example.com"));
+        assert!(!mailbox_matches("example.com", "joe@example.com"));
+        assert!(!mailbox_matches("jo\u{e9}@example.com", "joe@example.com"));
+    }
+
+    fn matches(name: &str, constraint: &str) -> Result<bool, Error> {
+        presented_id_matches_constraint(
+            untrusted::Input::from(name.as_bytes()),
+            untrusted::Input::from(constraint.as_bytes()),
+        )
+    }
+
+    #[test]
+    fn host_constraint() {
+        assert_eq!(matches("joe@example.com", "example.com"), Ok(true));
+        assert_eq!(matches("joe@EXAMPLE.com", "example.COM"), Ok(true));
+        assert_eq!(matches("joe@sub.example.com", "example.com"), Ok(false));
+        assert_eq!(matches("joe@notexample.com", "example.com"), Ok(false));
+    }
+
+    #[test]
+    fn domain_constraint() {
+        assert_eq!(matches("joe@sub.example.com", ".example.com"), Ok(true));
+        assert_eq!(matches("joe@a.b.EXAMPLE.com", ".example.com"), Ok(true));
+        assert_eq!(matches("joe@example.com", ".example.com"), Ok(false));
+        assert_eq!(matches("joe@subexample.com", ".example.com"), Ok(false));
+    }
+
+    #[test]
+    fn mailbox_constraint() {
+        assert_eq!(matches("joe@example.com", "joe@example.com"), Ok(true));
+        assert_eq!(matches("joe@Example.Com", "joe@example.com"), Ok(true));
+        assert_eq!(matches("Joe@example.com", "joe@example.com"), Ok(false));
+        assert_eq!(matches("bob@example.com", "joe@example.com"), Ok(false));
+        assert_eq!(matches("joe@sub.example.com", "joe@example.com"), Ok(false));
+        // The host is split from the local part at the last `@`.
+        assert_eq!(matches("\"a@b\"@example.com", "example.com"), Ok(true));
+    }
+
+    #[test]
+    fn malformed() {
+        for name in [
+            "example.com",
+            "@example.com",
+            "joe@",
+            "jo\u{e9}@example.com",
+        ] {
+            assert_eq!(matches(name, "example.com"), Err(Error::BadDer));
+        }
+        assert_eq!(
+            matches("joe@example.com", "ex\u{e4}mple.com"),
+            Err(Error::BadDer)
+        );
+    }
+}
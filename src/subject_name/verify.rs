@@ -12,16 +12,26 @@
 // ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
 // OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
-use super::dns_name::{self, IdRole};
+use pki_types::IpAddr;
+
+use super::directory_name::{self, PRINTABLE_STRING_TAG, UTF8_STRING_TAG};
+use super::dns_name;
 use super::ip_address;
+use super::rfc822_name;
+use super::uri;
 use crate::der::{self, FromDer};
 use crate::error::{DerTypeId, Error};
-use crate::verify_cert::{Budget, PathNode};
+use crate::verify_cert::{Budget, PathNode, Role};
 
 // https://tools.ietf.org/html/rfc5280#section-4.2.1.10
+//
+// If `subject_identities`, the constraints also apply to the names in the end-entity
+// certificate's subject that other validators might take for its identity (see
+// `check_subject_identities()`).
 pub(crate) fn check_name_constraints(
     constraints: Option<&mut untrusted::Reader>,
     path: &PathNode<'_>,
+    subject_identities: bool,
     budget: &mut Budget,
 ) -> Result<(), Error> {
     let constraints = match constraints {
@@ -61,11 +71,103 @@ pub(crate) fn check_name_constraints(
         if let Some(Err(err)) = result {
             return Err(err);
         }
+
+        if subject_identities && path.role() == Role::EndEntity {
+            if let Some(Err(err)) = check_subject_identities(
+                path.cert.subject,
+                permitted_subtrees,
+                excluded_subtrees,
+                budget,
+            ) {
+                return Err(err);
+            }
+        }
     }
 
     Ok(())
 }
 
+// Applies the constraints to the names in `subject` that another validator might take for an
+// identity of the subject: a commonName that is an IP address or looks like a DNS name, as an
+// iPAddress or dNSName, and each emailAddress, as an rfc822Name.
+//
+// https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.10: "When constraints are imposed on
+// the rfc822Name name form, but the certificate does not include a subject alternative name,
+// the rfc822Name constraint MUST be applied to the attribute of type emailAddress in the
+// subject distinguished name." webpki never takes a commonName for a DNS name or IP address.
+fn check_subject_identities(
+    subject: untrusted::Input,
+    permitted_subtrees: Option<untrusted::Input>,
+    excluded_subtrees: Option<untrusted::Input>,
+    budget: &mut Budget,
+) -> Option<Result<(), Error>> {
+    let rdns = match directory_name::rdn_sequence(subject) {
+        Ok(rdns) => rdns,
+        Err(err) => return Some(Err(err)),
+    };
+
+    let mut rdns = untrusted::Reader::new(rdns);
+    while !rdns.at_end() {
+        let rdn = match der::expect_tag(&mut rdns, der::Tag::Set) {
+            Ok(rdn) => rdn,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let mut attributes = untrusted::Reader::new(rdn);
+        while !attributes.at_end() {
+            let (attribute_type, tag, value) =
+                match der::expect_tag(&mut attributes, der::Tag::Sequence)
+                    .and_then(directory_name::attribute)
+                {
+                    Ok(attribute) => attribute,
+                    Err(err) => return Some(Err(err)),
+                };
+
+            let value = untrusted::Input::from(value);
+            let ip;
+            let name = match (attribute_type, tag) {
+                (COMMON_NAME, UTF8_STRING_TAG | PRINTABLE_STRING_TAG | IA5_STRING_TAG) => {
+                    match core::str::from_utf8(value.as_slice_less_safe())
+                        .ok()
+                        .and_then(|value| IpAddr::try_from(value).ok())
+                    {
+                        Some(addr) => {
+                            ip = addr;
+                            GeneralName::IpAddress(untrusted::Input::from(match &ip {
+                                IpAddr::V4(ip) => ip.as_ref(),
+                                IpAddr::V6(ip) => ip.as_ref(),
+                            }))
+                        }
+                        None if dns_name::looks_like_dns_name(value) => GeneralName::DnsName(value),
+                        None => continue,
+                    }
+                }
+                (EMAIL_ADDRESS, IA5_STRING_TAG) => GeneralName::Rfc822Name(value),
+                _ => continue,
+            };
+
+            let result = check_presented_id_conforms_to_constraints(
+                name,
+                permitted_subtrees,
+                excluded_subtrees,
+                budget,
+            );
+            if result.is_some() {
+                return result;
+            }
+        }
+    }
+
+    None
+}
+
+// id-at-commonName 2.5.4.3
+pub(super) const COMMON_NAME: &[u8] = &oid![2, 5, 4, 3];
+// id-emailAddress 1.2.840.113549.1.9.1
+pub(super) const EMAIL_ADDRESS: &[u8] = &oid![1, 2, 0x86, 0x48, 0x86, 0xf7, 0x0d, 1, 9, 1];
+
+pub(super) const IA5_STRING_TAG: u8 = 0x16;
+
 fn check_presented_id_conforms_to_constraints(
     name: GeneralName,
     permitted_subtrees: Option<untrusted::Input>,
@@ -81,12 +183,37 @@ fn check_presented_id_conforms_to_constraints(
         der::read_all(der::expect_tag(input, der::Tag::Sequence)?)
     }
 
+    // https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.10: directoryName constraints apply
+    // to the subject "when the certificate includes a non-empty subject field".
+    if let GeneralName::DirectoryName(name) = name {
+        if name.is_empty() {
+            return None;
+        }
+    }
+
     for (subtrees, constraints) in subtrees {
         let mut constraints = match constraints {
             Some(constraints) => untrusted::Reader::new(constraints),
             None => continue,
         };
 
+        // A present but empty permittedSubtrees permits nothing, rather than imposing no
+        // constraint. Only apply this to the name types whose constraints we implement. That
+        // includes a non-empty subject `DirectoryName`: an empty one was skipped above.
+        if let (
+            Subtrees::PermittedSubtrees,
+            GeneralName::DirectoryName(_)
+            | GeneralName::DnsName(_)
+            | GeneralName::IpAddress(_)
+            | GeneralName::Rfc822Name(_)
+            | GeneralName::UniformResourceIdentifier(_),
+        ) = (subtrees, name)
+        {
+            if constraints.at_end() {
+                return Some(Err(Error::NameConstraintViolation));
+            }
+        }
+
         let mut has_permitted_subtrees_match = false;
         let mut has_permitted_subtrees_mismatch = false;
         while !constraints.at_end() {
@@ -108,33 +235,26 @@ fn check_presented_id_conforms_to_constraints(
 
             let matches = match (name, base) {
                 (GeneralName::DnsName(name), GeneralName::DnsName(base)) => {
-                    dns_name::presented_id_matches_reference_id(name, IdRole::NameConstraint, base)
+                    dns_name::presented_id_matches_constraint(name, base, subtrees)
                 }
 
-                (GeneralName::DirectoryName(_), GeneralName::DirectoryName(_)) => Ok(
-                    // Reject any uses of directory name constraints; we don't implement this.
-                    //
-                    // Rejecting everything technically confirms to RFC5280:
-                    //
-                    //   "If a name constraints extension that is marked as critical imposes constraints
-                    //    on a particular name form, and an instance of that name form appears in the
-                    //    subject field or subjectAltName extension of a subsequent certificate, then
-                    //    the application MUST either process the constraint or _reject the certificate_."
-                    //
-                    // TODO: rustls/webpki#19
-                    //
-                    // Rejection is achieved by not matching any PermittedSubtrees, and matching all
-                    // ExcludedSubtrees.
-                    match subtrees {
-                        Subtrees::PermittedSubtrees => false,
-                        Subtrees::ExcludedSubtrees => true,
-                    },
-                ),
+                (GeneralName::DirectoryName(name), GeneralName::DirectoryName(base)) => {
+                    directory_name::presented_id_matches_constraint(name, base)
+                }
 
                 (GeneralName::IpAddress(name), GeneralName::IpAddress(base)) => {
                     ip_address::presented_id_matches_constraint(name, base)
                 }
 
+                (GeneralName::Rfc822Name(name), GeneralName::Rfc822Name(base)) => {
+                    rfc822_name::presented_id_matches_constraint(name, base)
+                }
+
+                (
+                    GeneralName::UniformResourceIdentifier(name),
+                    GeneralName::UniformResourceIdentifier(base),
+                ) => uri::presented_id_matches_constraint(name, base),
+
                 // RFC 4280 says "If a name constraints extension that is marked as
                 // critical imposes constraints on a particular name form, and an
                 // instance of that name form appears in the subject field or
@@ -186,7 +306,7 @@ fn check_presented_id_conforms_to_constraints(
 }
 
 #[derive(Clone, Copy)]
-enum Subtrees {
+pub(super) enum Subtrees {
     PermittedSubtrees,
     ExcludedSubtrees,
 }
@@ -224,6 +344,11 @@ impl<'a> Iterator for NameIterator<'a> {
 
             if !subject_alt_name.at_end() {
                 let err = match GeneralName::from_der(subject_alt_name) {
+                    // https://tools.ietf.org/html/rfc5280#section-4.2.1.6: an iPAddress
+                    // must contain exactly four (IPv4) or sixteen (IPv6) octets.
+                    Ok(GeneralName::IpAddress(ip)) if !matches!(ip.len(), 4 | 16) => {
+                        Error::MalformedIpAddress
+                    }
                     Ok(name) => return Some(Ok(name)),
                     Err(err) => err,
                 };
@@ -255,6 +380,7 @@ pub(crate) enum GeneralName<'a> {
     DnsName(untrusted::Input<'a>),
     DirectoryName(untrusted::Input<'a>),
     IpAddress(untrusted::Input<'a>),
+    Rfc822Name(untrusted::Input<'a>),
     UniformResourceIdentifier(untrusted::Input<'a>),
 
     // The value is the `tag & ~(der::CONTEXT_SPECIFIC | der::CONSTRUCTED)` so
@@ -265,29 +391,19 @@ pub(crate) enum GeneralName<'a> {
 
 impl<'a> FromDer<'a> for GeneralName<'a> {
     fn from_der(reader: &mut untrusted::Reader<'a>) -> Result<Self, Error> {
-        use der::{CONSTRUCTED, CONTEXT_SPECIFIC};
         use GeneralName::*;
 
-        #[allow(clippy::identity_op)]
-        const OTHER_NAME_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED | 0;
-        const RFC822_NAME_TAG: u8 = CONTEXT_SPECIFIC | 1;
-        const DNS_NAME_TAG: u8 = CONTEXT_SPECIFIC | 2;
-        const X400_ADDRESS_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED | 3;
-        const DIRECTORY_NAME_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED | 4;
-        const EDI_PARTY_NAME_TAG: u8 = CONTEXT_SPECIFIC | CONSTRUCTED | 5;
-        const UNIFORM_RESOURCE_IDENTIFIER_TAG: u8 = CONTEXT_SPECIFIC | 6;
-        const IP_ADDRESS_TAG: u8 = CONTEXT_SPECIFIC | 7;
-        const REGISTERED_ID_TAG: u8 = CONTEXT_SPECIFIC | 8;
-
         let (tag, value) = der::read_tag_and_get_value(reader)?;
         Ok(match tag {
             DNS_NAME_TAG => DnsName(value),
             DIRECTORY_NAME_TAG => DirectoryName(value),
             IP_ADDRESS_TAG => IpAddress(value),
+            RFC822_NAME_TAG => Rfc822Name(value),
             UNIFORM_RESOURCE_IDENTIFIER_TAG => UniformResourceIdentifier(value),
 
-            OTHER_NAME_TAG | RFC822_NAME_TAG | X400_ADDRESS_TAG | EDI_PARTY_NAME_TAG
-            | REGISTERED_ID_TAG => Unsupported(tag & !(CONTEXT_SPECIFIC | CONSTRUCTED)),
+            OTHER_NAME_TAG | X400_ADDRESS_TAG | EDI_PARTY_NAME_TAG | REGISTERED_ID_TAG => {
+                Unsupported(tag & !(der::CONTEXT_SPECIFIC | der::CONSTRUCTED))
+            }
 
             _ => return Err(Error::BadDer),
         })
@@ -295,3 +411,138 @@ impl<'a> FromDer<'a> for GeneralName<'a> {
 
     const TYPE_ID: DerTypeId = DerTypeId::GeneralName;
 }
+
+/// An entry of a certificate's subjectAltName extension, as yielded by
+/// [`crate::Cert::subject_alternative_names()`].
+///
+/// With the `serde` feature, byte values serialize as lowercase hex, and the `type_id` of an
+/// otherName as a dotted-decimal OID.
+///
+/// See <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.6>.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum SubjectAltName<'a> {
+    /// A dNSName. This is not checked to be a valid DNS name, only to be ASCII.
+    DnsName(&'a str),
+    /// An iPAddress: four octets for IPv4, sixteen for IPv6.
+    IpAddress(
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::report::serialize_hex")
+        )]
+        &'a [u8],
+    ),
+    /// An rfc822Name, that is, an email address. This is only checked to be ASCII.
+    Rfc822Name(&'a str),
+    /// A uniformResourceIdentifier. This is only checked to be ASCII.
+    Uri(&'a str),
+    /// An otherName, such as the Microsoft user principal name returned by
+    /// [`crate::EndEntityCert::microsoft_upn()`].
+    OtherName {
+        /// The value of the `type-id` OID, without its tag and length.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::report::serialize_oid")
+        )]
+        type_id: &'a [u8],
+        /// The DER encoding of the `value`, including its tag and length.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::report::serialize_hex")
+        )]
+        value: &'a [u8],
+    },
+    /// Another name type, identified by its `GeneralName` tag number, with the contents of its
+    /// DER encoding.
+    Other {
+        /// The tag number, such as 4 for a directoryName.
+        tag: u8,
+        /// The contents of the name's DER encoding.
+        #[cfg_attr(
+            feature = "serde",
+            serde(serialize_with = "crate::report::serialize_hex")
+        )]
+        value: &'a [u8],
+    },
+}
+
+impl<'a> SubjectAltName<'a> {
+    pub(crate) fn from_der(reader: &mut untrusted::Reader<'a>) -> Result<Self, Error> {
+        use der::{CONSTRUCTED, CONTEXT_SPECIFIC};
+
+        let (tag, value) = der::read_tag_and_get_value(reader)?;
+        let value = value.as_slice_less_safe();
+        let ascii = |err| match value.is_ascii() {
+            // ASCII is a subset of UTF-8.
+            true => core::str::from_utf8(value).map_err(|_| err),
+            false => Err(err),
+        };
+
+        Ok(match tag {
+            DNS_NAME_TAG => Self::DnsName(ascii(Error::MalformedDnsIdentifier)?),
+            // https://tools.ietf.org/html/rfc5280#section-4.2.1.6: an iPAddress
+            // must contain exactly four (IPv4) or sixteen (IPv6) octets.
+            IP_ADDRESS_TAG => match value.len() {
+                4 | 16 => Self::IpAddress(value),
+                _ => return Err(Error::MalformedIpAddress),
+            },
+            RFC822_NAME_TAG => Self::Rfc822Name(ascii(Error::BadDer)?),
+            UNIFORM_RESOURCE_IDENTIFIER_TAG => Self::Uri(ascii(Error::BadDer)?),
+
+            OTHER_NAME_TAG => {
+                let (type_id, value) = other_name(untrusted::Input::from(value))?;
+                Self::OtherName {
+                    type_id: type_id.as_slice_less_safe(),
+                    value: value.as_slice_less_safe(),
+                }
+            }
+            X400_ADDRESS_TAG | DIRECTORY_NAME_TAG | EDI_PARTY_NAME_TAG | REGISTERED_ID_TAG => {
+                Self::Other {
+                    tag: tag & !(CONTEXT_SPECIFIC | CONSTRUCTED),
+                    value,
+                }
+            }
+
+            _ => return Err(Error::BadDer),
+        })
+    }
+}
+
+/// Splits the contents of an otherName into the value of its `type-id` OID and the DER
+/// encoding of its value, which must be a single DER value of any type.
+///
+/// <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.6>:
+///
+/// ```ASN.1
+/// OtherName ::= SEQUENCE {
+///    type-id    OBJECT IDENTIFIER,
+///    value      [0] EXPLICIT ANY DEFINED BY type-id }
+/// ```
+pub(crate) fn other_name(
+    contents: untrusted::Input<'_>,
+) -> Result<(untrusted::Input<'_>, untrusted::Input<'_>), Error> {
+    contents
+        .read_all(Error::MalformedOtherNameInSan, |reader| {
+            let type_id = der::expect_tag(reader, der::Tag::OID)?;
+            let value = der::expect_tag(reader, der::Tag::ContextSpecificConstructed0)?;
+            value.read_all(Error::MalformedOtherNameInSan, der::read_tag_and_get_value)?;
+            Ok((type_id, value))
+        })
+        .map_err(|_| Error::MalformedOtherNameInSan)
+}
+
+#[allow(clippy::identity_op)]
+const OTHER_NAME_TAG: u8 = der::CONTEXT_SPECIFIC | der::CONSTRUCTED | 0;
+const RFC822_NAME_TAG: u8 = der::CONTEXT_SPECIFIC | 1;
+const DNS_NAME_TAG: u8 = der::CONTEXT_SPECIFIC | 2;
+const X400_ADDRESS_TAG: u8 = der::CONTEXT_SPECIFIC | der::CONSTRUCTED | 3;
+const DIRECTORY_NAME_TAG: u8 = der::CONTEXT_SPECIFIC | der::CONSTRUCTED | 4;
+const EDI_PARTY_NAME_TAG: u8 = der::CONTEXT_SPECIFIC | der::CONSTRUCTED | 5;
+const UNIFORM_RESOURCE_IDENTIFIER_TAG: u8 = der::CONTEXT_SPECIFIC | 6;
+const IP_ADDRESS_TAG: u8 = der::CONTEXT_SPECIFIC | 7;
+const REGISTERED_ID_TAG: u8 = der::CONTEXT_SPECIFIC | 8;
@@ -0,0 +1,259 @@
+//! A runner for JSON-encoded verification test vectors, so that external conformance suites
+//! can drive webpki without writing Rust for each case.
+//!
+//! A vector is a JSON object of the following shape. Certificates and CRLs are base64-encoded
+//! DER; fields marked optional may be omitted.
+//!
+//! ```json
+//! {
+//!   "description": "EE excluded by an IP address name constraint", // optional
+//!   "end_entity": "MIIB...",
+//!   "intermediates": ["MIIB..."],                                  // optional
+//!   "anchors": ["MIIB..."],
+//!   "time": 535752717,
+//!   "usage": "server_auth",                                        // or "client_auth"; optional
+//!   "hostname": "example.com",                                     // optional
+//!   "crls": ["MIIB..."],                                           // optional
+//!   "revocation_depth": "chain",                                   // or "end_entity"; optional
+//!   "unknown_status": "deny",                                      // or "allow"; optional
+//!   "expected_error": "NameConstraintViolation"                    // or null
+//! }
+//! ```
+//!
+//! `expected_error` is compared against the `Debug` representation of the [`Error`] that
+//! verification fails with. The chain is verified first; if `hostname` is present, the
+//! end-entity certificate is then checked against it. Revocation checking is performed only
+//! if `crls` is non-empty.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use base64::{engine::general_purpose, Engine as _};
+use pki_types::{CertificateDer, ServerName, UnixTime};
+use serde::Deserialize;
+
+use crate::crl::{
+    CertRevocationList, OwnedCertRevocationList, RevocationCheckDepth, RevocationOptionsBuilder,
+    UnknownStatusPolicy,
+};
+use crate::end_entity::EndEntityCert;
+use crate::error::Error;
+use crate::trust_anchor::anchor_from_trusted_cert;
+use crate::verify_cert::KeyUsage;
+use crate::ALL_VERIFICATION_ALGS;
+
+/// The result of [`run_vector()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VectorOutcome {
+    /// Verification produced the expected result.
+    Passed,
+    /// Verification produced a different result from the one expected. `None` means success.
+    Mismatch {
+        /// The expected error, from the vector's `expected_error` field.
+        expected: Option<String>,
+        /// The error verification actually failed with.
+        actual: Option<String>,
+    },
+    /// The vector could not be run, for example because it was not valid JSON, or because a
+    /// trust anchor or CRL in it could not be parsed.
+    Invalid(String),
+}
+
+impl VectorOutcome {
+    /// Returns `true` if the vector passed.
+    pub fn is_passed(&self) -> bool {
+        matches!(self, Self::Passed)
+    }
+}
+
+/// Runs the JSON test vector `json`, returning whether webpki produced the expected result.
+pub fn run_vector(json: &str) -> VectorOutcome {
+    let vector = match serde_json::from_str::<Vector>(json) {
+        Ok(vector) => vector,
+        Err(err) => return VectorOutcome::Invalid(format!("invalid vector: {}", err)),
+    };
+
+    let actual = match vector.run() {
+        Ok(result) => result.err().map(|err| format!("{:?}", err)),
+        Err(reason) => return VectorOutcome::Invalid(reason),
+    };
+
+    match actual == vector.expected_error {
+        true => VectorOutcome::Passed,
+        false => VectorOutcome::Mismatch {
+            expected: vector.expected_error,
+            actual,
+        },
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Vector {
+    #[serde(default)]
+    #[allow(dead_code)]
+    description: Option<String>,
+    end_entity: String,
+    #[serde(default)]
+    intermediates: Vec<String>,
+    anchors: Vec<String>,
+    time: u64,
+    #[serde(default)]
+    usage: Usage,
+    #[serde(default)]
+    hostname: Option<String>,
+    #[serde(default)]
+    crls: Vec<String>,
+    #[serde(default)]
+    revocation_depth: Depth,
+    #[serde(default)]
+    unknown_status: StatusPolicy,
+    expected_error: Option<String>,
+}
+
+impl Vector {
+    /// Returns the verification result, or a reason the vector is invalid.
+    fn run(&self) -> Result<Result<(), Error>, String> {
+        let ee = decode(&self.end_entity)?;
+        let intermediates = self
+            .intermediates
+            .iter()
+            .map(|der| decode(der).map(CertificateDer::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        let anchor_ders = self
+            .anchors
+            .iter()
+            .map(|der| decode(der).map(CertificateDer::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        let anchors = anchor_ders
+            .iter()
+            .map(|der| {
+                anchor_from_trusted_cert(der).map_err(|err| format!("invalid anchor: {:?}", err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let crls = self
+            .crls
+            .iter()
+            .map(|der| {
+                OwnedCertRevocationList::from_der(&decode(der)?)
+                    .map(CertRevocationList::from)
+                    .map_err(|err| format!("invalid CRL: {:?}", err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let server_name = match &self.hostname {
+            Some(hostname) => Some(
+                ServerName::try_from(hostname.as_str())
+                    .map_err(|_| format!("invalid hostname: {}", hostname))?,
+            ),
+            None => None,
+        };
+
+        let crl_refs = crls.iter().collect::<Vec<_>>();
+        let revocation = match RevocationOptionsBuilder::new(&crl_refs) {
+            Ok(builder) => Some(
+                builder
+                    .with_depth(self.revocation_depth.into())
+                    .with_status_policy(self.unknown_status.into())
+                    .build(),
+            ),
+            Err(_) => None,
+        };
+
+        let ee = CertificateDer::from(ee);
+        let cert = match EndEntityCert::try_from(&ee) {
+            Ok(cert) => cert,
+            Err(err) => return Ok(Err(err)),
+        };
+
+        let result = cert
+            .verify_for_usage(
+                ALL_VERIFICATION_ALGS,
+                &anchors,
+                &intermediates,
+                UnixTime::since_unix_epoch(Duration::from_secs(self.time)),
+                self.usage.into(),
+                revocation,
+                None,
+            )
+            .and_then(|_| match &server_name {
+                Some(server_name) => cert.verify_is_valid_for_subject_name(server_name),
+                None => Ok(()),
+            });
+        Ok(result)
+    }
+}
+
+fn decode(base64: &str) -> Result<Vec<u8>, String> {
+    general_purpose::STANDARD
+        .decode(base64)
+        .map_err(|err| err.to_string())
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Usage {
+    ServerAuth,
+    ClientAuth,
+}
+
+impl Default for Usage {
+    fn default() -> Self {
+        Self::ServerAuth
+    }
+}
+
+impl From<Usage> for KeyUsage {
+    fn from(usage: Usage) -> Self {
+        match usage {
+            Usage::ServerAuth => Self::server_auth(),
+            Usage::ClientAuth => Self::client_auth(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Depth {
+    Chain,
+    EndEntity,
+}
+
+impl Default for Depth {
+    fn default() -> Self {
+        Self::Chain
+    }
+}
+
+impl From<Depth> for RevocationCheckDepth {
+    fn from(depth: Depth) -> Self {
+        match depth {
+            Depth::Chain => Self::Chain,
+            Depth::EndEntity => Self::EndEntity,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StatusPolicy {
+    Deny,
+    Allow,
+}
+
+impl Default for StatusPolicy {
+    fn default() -> Self {
+        Self::Deny
+    }
+}
+
+impl From<StatusPolicy> for UnknownStatusPolicy {
+    fn from(policy: StatusPolicy) -> Self {
+        match policy {
+            StatusPolicy::Deny => Self::Deny,
+            StatusPolicy::Allow => Self::Allow,
+        }
+    }
+}
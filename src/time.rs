@@ -30,63 +30,108 @@ impl<'a> FromDer<'a> for UnixTime {
             Tag::GeneralizedTime
         };
 
-        fn read_digit(inner: &mut untrusted::Reader) -> Result<u64, Error> {
-            const DIGIT: core::ops::RangeInclusive<u8> = b'0'..=b'9';
-            let b = inner.read_byte().map_err(|_| Error::BadDerTime)?;
-            if DIGIT.contains(&b) {
-                return Ok(u64::from(b - DIGIT.start()));
+        let value = der::nested(
+            input,
+            expected_tag,
+            Error::TrailingData(Self::TYPE_ID),
+            |value| Ok(value.read_bytes_to_end()),
+        )?;
+        let mut value = TimeReader {
+            bytes: value.as_slice_less_safe(),
+            offset: 0,
+        };
+
+        let (year_hi, year_lo) = if is_utc_time {
+            let lo = value.read_two_digits(0, 99)?;
+            let hi = if lo >= 50 { 19 } else { 20 };
+            (hi, lo)
+        } else {
+            let hi = value.read_two_digits(0, 99)?;
+            let lo = value.read_two_digits(0, 99)?;
+            (hi, lo)
+        };
+
+        let year = (year_hi * 100) + year_lo;
+        let month = value.read_two_digits(1, 12)?;
+        let days_in_month = days_in_month(year, month);
+        let day_of_month = value.read_two_digits(1, days_in_month)?;
+        let hours = value.read_two_digits(0, 23)?;
+        let minutes = value.read_two_digits(0, 59)?;
+        let seconds = value.read_two_digits(0, 59)?;
+
+        // DER requires times to be expressed in UTC, with seconds and a trailing 'Z' and
+        // without fractional seconds or a timezone offset (X.690 section 11.7 and 11.8).
+        value.expect(b'Z')?;
+        value.expect_end()?;
+
+        time_from_ymdhms_utc(year, month, day_of_month, hours, minutes, seconds)
+    }
+
+    const TYPE_ID: DerTypeId = DerTypeId::Time;
+}
+
+// Tracks the offset into the time value so that encoding errors can report where they are.
+struct TimeReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl TimeReader<'_> {
+    fn read_two_digits(&mut self, min: u64, max: u64) -> Result<u64, Error> {
+        let hi = self.read_digit()?;
+        let lo = self.read_digit()?;
+        let value = (hi * 10) + lo;
+        if value < min || value > max {
+            return Err(Error::BadDerTime);
+        }
+        Ok(value)
+    }
+
+    fn read_digit(&mut self) -> Result<u64, Error> {
+        match self.bytes.get(self.offset) {
+            Some(b @ b'0'..=b'9') => {
+                self.offset += 1;
+                Ok(u64::from(b - b'0'))
             }
-            Err(Error::BadDerTime)
+            _ => Err(self.improper()),
         }
+    }
 
-        fn read_two_digits(
-            inner: &mut untrusted::Reader,
-            min: u64,
-            max: u64,
-        ) -> Result<u64, Error> {
-            let hi = read_digit(inner)?;
-            let lo = read_digit(inner)?;
-            let value = (hi * 10) + lo;
-            if value < min || value > max {
-                return Err(Error::BadDerTime);
+    fn expect(&mut self, expected: u8) -> Result<(), Error> {
+        match self.bytes.get(self.offset) {
+            Some(b) if *b == expected => {
+                self.offset += 1;
+                Ok(())
             }
-            Ok(value)
+            _ => Err(self.improper()),
         }
+    }
 
-        der::nested(
-            input,
-            expected_tag,
-            Error::TrailingData(Self::TYPE_ID),
-            |value| {
-                let (year_hi, year_lo) = if is_utc_time {
-                    let lo = read_two_digits(value, 0, 99)?;
-                    let hi = if lo >= 50 { 19 } else { 20 };
-                    (hi, lo)
-                } else {
-                    let hi = read_two_digits(value, 0, 99)?;
-                    let lo = read_two_digits(value, 0, 99)?;
-                    (hi, lo)
-                };
-
-                let year = (year_hi * 100) + year_lo;
-                let month = read_two_digits(value, 1, 12)?;
-                let days_in_month = days_in_month(year, month);
-                let day_of_month = read_two_digits(value, 1, days_in_month)?;
-                let hours = read_two_digits(value, 0, 23)?;
-                let minutes = read_two_digits(value, 0, 59)?;
-                let seconds = read_two_digits(value, 0, 59)?;
-
-                let time_zone = value.read_byte().map_err(|_| Error::BadDerTime)?;
-                if time_zone != b'Z' {
-                    return Err(Error::BadDerTime);
-                }
-
-                time_from_ymdhms_utc(year, month, day_of_month, hours, minutes, seconds)
-            },
-        )
+    fn expect_end(&self) -> Result<(), Error> {
+        match self.offset == self.bytes.len() {
+            true => Ok(()),
+            false => Err(self.improper()),
+        }
     }
 
-    const TYPE_ID: DerTypeId = DerTypeId::Time;
+    fn improper(&self) -> Error {
+        Error::ImproperTimeEncoding {
+            offset: self.offset,
+        }
+    }
+}
+
+/// Returns the earliest and latest times that a clock reading `time` may stand for, if it may be
+/// off by up to `skew` in either direction.
+///
+/// Only whole seconds of `skew` are used, since `UnixTime` has no finer resolution. With no
+/// skew, both are `time`.
+pub(crate) fn skewed(time: UnixTime, skew: Duration) -> (UnixTime, UnixTime) {
+    let (time, skew) = (time.as_secs(), skew.as_secs());
+    (
+        UnixTime::since_unix_epoch(Duration::from_secs(time.saturating_sub(skew))),
+        UnixTime::since_unix_epoch(Duration::from_secs(time.saturating_add(skew))),
+    )
 }
 
 pub(crate) fn time_from_ymdhms_utc(
@@ -183,6 +228,99 @@ const DAYS_BEFORE_UNIX_EPOCH_AD: u64 = 1969 * 365 + 477;
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_der() {
+        // (tag, value, expected)
+        #[allow(clippy::type_complexity)]
+        let cases: &[(Tag, &[u8], Result<UnixTime, Error>)] = &[
+            (
+                Tag::UTCTime,
+                b"230101000000Z",
+                Ok(UnixTime::since_unix_epoch(Duration::from_secs(
+                    1_672_531_200,
+                ))),
+            ),
+            (
+                Tag::GeneralizedTime,
+                b"20230101000000Z",
+                Ok(UnixTime::since_unix_epoch(Duration::from_secs(
+                    1_672_531_200,
+                ))),
+            ),
+            // Timezone offset instead of seconds.
+            (
+                Tag::UTCTime,
+                b"2301010000+0100",
+                Err(Error::ImproperTimeEncoding { offset: 10 }),
+            ),
+            // Timezone offset instead of 'Z'.
+            (
+                Tag::UTCTime,
+                b"230101000000+0100",
+                Err(Error::ImproperTimeEncoding { offset: 12 }),
+            ),
+            (
+                Tag::GeneralizedTime,
+                b"20230101000000-0500",
+                Err(Error::ImproperTimeEncoding { offset: 14 }),
+            ),
+            // Missing 'Z'.
+            (
+                Tag::UTCTime,
+                b"230101000000",
+                Err(Error::ImproperTimeEncoding { offset: 12 }),
+            ),
+            (
+                Tag::GeneralizedTime,
+                b"20230101000000",
+                Err(Error::ImproperTimeEncoding { offset: 14 }),
+            ),
+            // Missing seconds.
+            (
+                Tag::UTCTime,
+                b"2301010000Z",
+                Err(Error::ImproperTimeEncoding { offset: 10 }),
+            ),
+            (
+                Tag::GeneralizedTime,
+                b"202301010000Z",
+                Err(Error::ImproperTimeEncoding { offset: 12 }),
+            ),
+            // 13-digit UTCTime.
+            (
+                Tag::UTCTime,
+                b"2301010000000Z",
+                Err(Error::ImproperTimeEncoding { offset: 12 }),
+            ),
+            // Fractional seconds.
+            (
+                Tag::GeneralizedTime,
+                b"20230101000000.5Z",
+                Err(Error::ImproperTimeEncoding { offset: 14 }),
+            ),
+            // Data after 'Z'.
+            (
+                Tag::UTCTime,
+                b"230101000000ZZ",
+                Err(Error::ImproperTimeEncoding { offset: 13 }),
+            ),
+            // Well-formed, but out of range values are still reported as BadDerTime.
+            (Tag::UTCTime, b"231301000000Z", Err(Error::BadDerTime)),
+            (Tag::UTCTime, b"230101000060Z", Err(Error::BadDerTime)),
+        ];
+
+        for (tag, value, expected) in cases {
+            let len = u8::try_from(value.len()).unwrap();
+            let der = [&[u8::from(*tag), len][..], value].concat();
+            assert_eq!(
+                untrusted::Input::from(&der).read_all(Error::BadDer, UnixTime::from_der),
+                *expected,
+                "{:?}",
+                core::str::from_utf8(value)
+            );
+        }
+    }
+
     #[test]
     fn test_days_before_unix_epoch() {
         assert_eq!(
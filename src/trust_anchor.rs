@@ -1,8 +1,15 @@
-use pki_types::{CertificateDer, TrustAnchor};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use pki_types::Der;
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, TrustAnchor};
 
 use crate::cert::{lenient_certificate_serial_number, Cert};
 use crate::der;
 use crate::error::{DerTypeId, Error};
+use crate::signed_data::{self, SignedData};
+use crate::verify_cert::{Budget, KeyUsageMode};
 
 /// Interprets the given pre-validated DER-encoded certificate as a `TrustAnchor`.
 ///
@@ -22,6 +29,17 @@ use crate::error::{DerTypeId, Error};
 /// end-entity certificate as a `TrustAnchor` in an effort to validate the same end-entity
 /// certificate during path building. Webpki has no support for self-signed certificates.
 ///
+/// The certificate may be an intermediate CA that is trusted directly, such as a cluster CA.
+/// Paths then end at it: if a peer also sends it, and the root above it, among its
+/// intermediates, nothing above the anchor is considered, so the root's validity doesn't
+/// matter.
+///
+/// The returned `TrustAnchor` borrows from `cert`. With the `alloc` feature,
+/// `TrustAnchor::to_owned()` copies it into a `TrustAnchor<'static>`, so that a trust store
+/// loaded at startup can be kept as a `Vec<TrustAnchor<'static>>` after the certificate
+/// buffers are dropped. The verification entry points take anchors by slice, so passing such
+/// a store to them copies nothing.
+///
 /// [RFC 5280 6.1.1]: <https://datatracker.ietf.org/doc/html/rfc5280#section-6.1.1>
 /// [RFC 5280 6.2]: <https://www.rfc-editor.org/rfc/rfc5280#section-6.2>
 pub fn anchor_from_trusted_cert<'a>(
@@ -35,7 +53,10 @@ pub fn anchor_from_trusted_cert<'a>(
     // parser doesn't allow extensions, so there's no need to worry about
     // embedded name constraints in a v1 certificate.
     match Cert::from_der(cert_der) {
-        Ok(cert) => Ok(TrustAnchor::from(cert)),
+        Ok(cert) => {
+            cert.check_critical_extensions(&[])?;
+            Ok(TrustAnchor::from(cert))
+        }
         Err(Error::UnsupportedCertVersion) => {
             extract_trust_anchor_from_v1_cert_der(cert_der).or(Err(Error::BadDer))
         }
@@ -43,47 +64,321 @@ pub fn anchor_from_trusted_cert<'a>(
     }
 }
 
+/// Like [`anchor_from_trusted_cert()`], but first checks that the certificate is plausible as
+/// a trust anchor, as configured by `policy`.
+///
+/// This is for loading trust stores whose entries may include certificates that are not CA
+/// certificates, so that each one unfit to be an anchor can be reported, instead of causing
+/// confusing errors when verifying against it later. The checks are, in order:
+///
+/// * The certificate must be a v3 certificate with no unsupported critical extensions, or,
+///   if [`AnchorPolicy::allow_v1()`] was given, a v1 certificate, as some long-lived roots
+///   are. Other v1 certificates fail with [`Error::UnsupportedCertVersion`].
+/// * A v3 certificate must have a basicConstraints extension with `cA` set, or fail with
+///   [`Error::TrustAnchorNotCa`].
+/// * If the certificate has a keyUsage extension, it must assert keyCertSign, or fail with
+///   [`Error::TrustAnchorKeyUsageInvalid`].
+/// * Unless [`AnchorPolicy::allow_not_self_issued()`] was given, the certificate's issuer
+///   must be its subject, or it fails with [`Error::TrustAnchorNotSelfIssued`].
+/// * If [`AnchorPolicy::require_self_signed()`] was given, the certificate's signature must
+///   verify with its own public key, or it fails with [`Error::TrustAnchorNotSelfSigned`].
+///   An unsupported signature algorithm fails with the error signature verification gives.
+///
+/// The certificate's validity period is not checked.
+pub fn anchor_from_trusted_cert_checked<'a>(
+    cert: &'a CertificateDer<'a>,
+    policy: &AnchorPolicy<'_>,
+) -> Result<TrustAnchor<'a>, Error> {
+    let cert_der = untrusted::Input::from(cert.as_ref());
+
+    let (anchor, issuer, signed_data) = match Cert::from_der(cert_der) {
+        Ok(cert) => {
+            cert.check_critical_extensions(&[])?;
+            if !cert
+                .basic_constraints()?
+                .map_or(false, |constraints| constraints.is_ca)
+            {
+                return Err(Error::TrustAnchorNotCa);
+            }
+            KeyUsageMode::CertSign
+                .check(cert.key_usage)
+                .map_err(|err| match err {
+                    Error::IssuerKeyUsageInvalid => Error::TrustAnchorKeyUsageInvalid,
+                    err => err,
+                })?;
+            let issuer = cert.issuer;
+            let signed_data = SignedData {
+                data: cert.signed_data.data,
+                algorithm: cert.signed_data.algorithm,
+                signature: cert.signed_data.signature,
+            };
+            (TrustAnchor::from(cert), issuer, signed_data)
+        }
+        Err(Error::UnsupportedCertVersion) if policy.allow_v1 => {
+            let cert = V1Cert::from_der(cert_der).or(Err(Error::BadDer))?;
+            let signed_data = SignedData {
+                data: cert.tbs,
+                algorithm: cert.signature_algorithm,
+                signature: untrusted::Input::from(cert.signature.as_slice_less_safe())
+                    .read_all(Error::BadDer, der::bit_string_with_no_unused_bits)?,
+            };
+            (cert.anchor, cert.issuer, signed_data)
+        }
+        Err(err) => return Err(err),
+    };
+
+    if !policy.not_self_issued && issuer.as_slice_less_safe() != anchor.subject.as_ref() {
+        return Err(Error::TrustAnchorNotSelfIssued);
+    }
+
+    if let Some(supported_sig_algs) = policy.self_signed {
+        signed_data::verify_signed_data(
+            supported_sig_algs,
+            untrusted::Input::from(anchor.subject_public_key_info.as_ref()),
+            &signed_data,
+            &mut Budget::default(),
+        )
+        .map_err(|err| match err {
+            Error::InvalidSignatureForPublicKey => Error::TrustAnchorNotSelfSigned,
+            err => err,
+        })?;
+    }
+
+    Ok(anchor)
+}
+
+/// The checks made by [`anchor_from_trusted_cert_checked()`].
+#[derive(Clone, Copy, Default)]
+pub struct AnchorPolicy<'a> {
+    allow_v1: bool,
+    not_self_issued: bool,
+    self_signed: Option<&'a [&'a dyn SignatureVerificationAlgorithm]>,
+}
+
+impl<'a> AnchorPolicy<'a> {
+    /// Require a self-issued v3 CA certificate, without checking its signature.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also accept v1 certificates, which can't assert that they are CA certificates.
+    pub fn allow_v1(mut self) -> Self {
+        self.allow_v1 = true;
+        self
+    }
+
+    /// Also accept certificates whose issuer is not their subject, such as intermediate
+    /// certificates trusted directly.
+    pub fn allow_not_self_issued(mut self) -> Self {
+        self.not_self_issued = true;
+        self
+    }
+
+    /// Require the certificate's signature to verify with its own public key, using one of
+    /// `supported_sig_algs`.
+    pub fn require_self_signed(
+        mut self,
+        supported_sig_algs: &'a [&'a dyn SignatureVerificationAlgorithm],
+    ) -> Self {
+        self.self_signed = Some(supported_sig_algs);
+        self
+    }
+}
+
+/// Extracts a trust anchor from each certificate in `der`, a DER-encoded PKCS#7 (CMS)
+/// SignedData structure, such as the "certs-only" bundles that trust stores are often
+/// exported as.
+///
+/// Only the `certificates` of the SignedData are read: any signatures over its content are
+/// not verified, and any CRLs are ignored. As with [`anchor_from_trusted_cert()`], the bundle
+/// must come from a source trusted to have validated its contents.
+///
+/// A CA certificate is a v3 certificate whose basicConstraints extension has `cA` set, or
+/// a v1 certificate, as some long-lived roots are. `non_ca` selects whether other
+/// certificates are skipped or rejected with [`Error::BundleCertNotCa`]. A certificate that
+/// can't be parsed is rejected with [`Error::MalformedBundleCert`]. Both errors give the
+/// position of the certificate in the bundle. A bundle that is not a SignedData structure
+/// is rejected with [`Error::BadDer`].
+///
+/// For a bundle of concatenated DER-encoded certificates, use
+/// [`crate::split_der_certificates()`] with [`anchor_from_trusted_cert()`].
+#[cfg(feature = "alloc")]
+pub fn extract_trust_anchors_from_pkcs7(
+    der: &[u8],
+    non_ca: NonCaCertPolicy,
+) -> Result<Vec<TrustAnchor<'_>>, Error> {
+    let certs = untrusted::Input::from(der).read_all(Error::BadDer, |content_info| {
+        // ContentInfo: https://www.rfc-editor.org/rfc/rfc5652#section-3
+        der::nested_limited(
+            content_info,
+            der::Tag::Sequence,
+            Error::BadDer,
+            |content_info| {
+                let content_type = der::expect_tag(content_info, der::Tag::OID)?;
+                if content_type.as_slice_less_safe() != ID_SIGNED_DATA {
+                    return Err(Error::BadDer);
+                }
+                der::nested_limited(
+                    content_info,
+                    der::Tag::ContextSpecificConstructed0,
+                    Error::BadDer,
+                    |content| {
+                        der::nested_limited(
+                            content,
+                            der::Tag::Sequence,
+                            Error::BadDer,
+                            signed_data_certificates,
+                            der::MAX_DER_SIZE,
+                        )
+                    },
+                    der::MAX_DER_SIZE,
+                )
+            },
+            der::MAX_DER_SIZE,
+        )
+    })?;
+
+    let mut anchors = Vec::new();
+    let mut reader = untrusted::Reader::new(certs);
+    let mut index = 0;
+    while !reader.at_end() {
+        let malformed = Error::MalformedBundleCert { index };
+        let (cert_der, _) = reader
+            .read_partial(|input| der::expect_tag(input, der::Tag::Sequence))
+            .map_err(|_| malformed)?;
+
+        let is_ca = match Cert::from_der(cert_der) {
+            Ok(cert) => {
+                cert.check_critical_extensions(&[]).map_err(|_| malformed)?;
+                let is_ca = cert
+                    .basic_constraints()
+                    .map_err(|_| malformed)?
+                    .map_or(false, |constraints| constraints.is_ca);
+                if is_ca {
+                    anchors.push(TrustAnchor::from(cert));
+                }
+                is_ca
+            }
+            Err(Error::UnsupportedCertVersion) => {
+                anchors.push(extract_trust_anchor_from_v1_cert_der(cert_der).or(Err(malformed))?);
+                true
+            }
+            Err(_) => return Err(malformed),
+        };
+
+        if !is_ca && non_ca == NonCaCertPolicy::Reject {
+            return Err(Error::BundleCertNotCa { index });
+        }
+        index += 1;
+    }
+
+    Ok(anchors)
+}
+
+/// Reads the contents of the `certificates` of a SignedData, or nothing if it has none.
+///
+/// SignedData: <https://www.rfc-editor.org/rfc/rfc5652#section-5.1>
+#[cfg(feature = "alloc")]
+fn signed_data_certificates<'a>(
+    signed_data: &mut untrusted::Reader<'a>,
+) -> Result<untrusted::Input<'a>, Error> {
+    skip(signed_data, der::Tag::Integer)?; // version.
+    skip(signed_data, der::Tag::Set)?; // digestAlgorithms.
+    skip(signed_data, der::Tag::Sequence)?; // encapContentInfo.
+    let certificates = match signed_data.peek(der::Tag::ContextSpecificConstructed0.into()) {
+        true => der::expect_tag_and_get_value_limited(
+            signed_data,
+            der::Tag::ContextSpecificConstructed0,
+            der::MAX_DER_SIZE,
+        )?,
+        false => untrusted::Input::from(&[]),
+    };
+    // The crls and signerInfos are not used.
+    signed_data.skip_to_end();
+    Ok(certificates)
+}
+
+/// How [`extract_trust_anchors_from_pkcs7()`] treats certificates that are not CA certificates.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NonCaCertPolicy {
+    /// Leave the certificate out of the extracted trust anchors.
+    Skip,
+    /// Fail with [`Error::BundleCertNotCa`].
+    Reject,
+}
+
+// id-signedData: https://www.rfc-editor.org/rfc/rfc5652#section-5.1
+#[cfg(feature = "alloc")]
+const ID_SIGNED_DATA: [u8; 9] = oid!(1, 2, 0x86, 0x48, 0x86, 0xf7, 0x0d, 1, 7, 2);
+
 /// Parses a v1 certificate directly into a TrustAnchor.
 fn extract_trust_anchor_from_v1_cert_der(
     cert_der: untrusted::Input<'_>,
 ) -> Result<TrustAnchor<'_>, Error> {
-    // X.509 Certificate: https://tools.ietf.org/html/rfc5280#section-4.1.
-    cert_der.read_all(Error::BadDer, |cert_der| {
-        der::nested(
-            cert_der,
-            der::Tag::Sequence,
-            Error::TrailingData(DerTypeId::TrustAnchorV1),
-            |cert_der| {
-                let anchor = der::nested(
-                    cert_der,
-                    der::Tag::Sequence,
-                    Error::TrailingData(DerTypeId::TrustAnchorV1TbsCertificate),
-                    |tbs| {
-                        // The version number field does not appear in v1 certificates.
-                        lenient_certificate_serial_number(tbs)?;
-
-                        skip(tbs, der::Tag::Sequence)?; // signature.
-                        skip(tbs, der::Tag::Sequence)?; // issuer.
-                        skip(tbs, der::Tag::Sequence)?; // validity.
-                        let subject = der::expect_tag(tbs, der::Tag::Sequence)?;
-                        let spki = der::expect_tag(tbs, der::Tag::Sequence)?;
-
-                        Ok(TrustAnchor {
-                            subject: subject.as_slice_less_safe().into(),
-                            subject_public_key_info: spki.as_slice_less_safe().into(),
-                            name_constraints: None,
-                        })
-                    },
-                );
+    V1Cert::from_der(cert_der).map(|cert| cert.anchor)
+}
 
-                // read and discard signatureAlgorithm + signature
-                skip(cert_der, der::Tag::Sequence)?;
-                skip(cert_der, der::Tag::BitString)?;
+/// The parts of a v1 certificate used as a trust anchor.
+struct V1Cert<'a> {
+    anchor: TrustAnchor<'a>,
+    issuer: untrusted::Input<'a>,
+    /// The whole tbsCertificate, including its tag and length.
+    tbs: untrusted::Input<'a>,
+    signature_algorithm: untrusted::Input<'a>,
+    /// The whole signature BIT STRING, including its tag and length.
+    signature: untrusted::Input<'a>,
+}
 
-                anchor
-            },
-        )
-    })
+impl<'a> V1Cert<'a> {
+    fn from_der(cert_der: untrusted::Input<'a>) -> Result<Self, Error> {
+        // X.509 Certificate: https://tools.ietf.org/html/rfc5280#section-4.1.
+        cert_der.read_all(Error::BadDer, |cert_der| {
+            der::nested(
+                cert_der,
+                der::Tag::Sequence,
+                Error::TrailingData(DerTypeId::TrustAnchorV1),
+                |cert_der| {
+                    let (tbs, (anchor, issuer)) = cert_der.read_partial(|cert_der| {
+                        der::nested(
+                            cert_der,
+                            der::Tag::Sequence,
+                            Error::TrailingData(DerTypeId::TrustAnchorV1TbsCertificate),
+                            |tbs| {
+                                // The version number field does not appear in v1 certificates.
+                                lenient_certificate_serial_number(tbs)?;
+
+                                skip(tbs, der::Tag::Sequence)?; // signature.
+                                let issuer = der::expect_tag(tbs, der::Tag::Sequence)?;
+                                skip(tbs, der::Tag::Sequence)?; // validity.
+                                let subject = der::expect_tag(tbs, der::Tag::Sequence)?;
+                                let spki = der::expect_tag(tbs, der::Tag::Sequence)?;
+
+                                let anchor = TrustAnchor {
+                                    subject: subject.as_slice_less_safe().into(),
+                                    subject_public_key_info: spki.as_slice_less_safe().into(),
+                                    name_constraints: None,
+                                };
+                                Ok((anchor, issuer))
+                            },
+                        )
+                    })?;
+
+                    let signature_algorithm = der::expect_tag(cert_der, der::Tag::Sequence)?;
+                    let (signature, _) = cert_der
+                        .read_partial(|cert_der| der::expect_tag(cert_der, der::Tag::BitString))?;
+
+                    Ok(Self {
+                        anchor,
+                        issuer,
+                        tbs,
+                        signature_algorithm,
+                        signature,
+                    })
+                },
+            )
+        })
+    }
 }
 
 impl<'a> From<Cert<'a>> for TrustAnchor<'a> {
@@ -98,6 +393,450 @@ impl<'a> From<Cert<'a>> for TrustAnchor<'a> {
     }
 }
 
+/// A store of trust anchors that can be searched by subject.
+///
+/// Path building asks the source for the anchors that may have issued each certificate it
+/// considers, so a large store can be indexed, for example by a hash map keyed on the subject,
+/// instead of being scanned in full for every lookup. Use
+/// [`crate::EndEntityCert::verifier_with_anchor_source()`] to verify against one.
+///
+/// A slice of trust anchors is a source that returns every anchor for every subject, which is
+/// what [`crate::EndEntityCert::verifier()`] does.
+pub trait TrustAnchorSource {
+    /// Returns the trust anchors that may have the DER-encoded `subject`, in order of
+    /// preference.
+    ///
+    /// As with [`TrustAnchor::subject`], `subject` excludes the outer SEQUENCE tag and length.
+    /// Anchors with another subject may be returned too, and are ignored, but an anchor with
+    /// this subject that isn't returned won't be used.
+    fn anchors_for_subject(&self, subject: &[u8]) -> &[TrustAnchor<'_>];
+}
+
+impl TrustAnchorSource for &[TrustAnchor<'_>] {
+    fn anchors_for_subject(&self, _subject: &[u8]) -> &[TrustAnchor<'_>] {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TrustAnchorSource for TrustAnchorSet<'_> {
+    fn anchors_for_subject(&self, _subject: &[u8]) -> &[TrustAnchor<'_>] {
+        &self.anchors
+    }
+}
+
+/// The trust anchors supplied for verification: either a slice, or a [`TrustAnchorSource`].
+#[derive(Clone, Copy)]
+pub(crate) enum Anchors<'p> {
+    Slice(&'p [TrustAnchor<'p>]),
+    Source(&'p dyn TrustAnchorSource),
+}
+
+impl<'p> Anchors<'p> {
+    /// Returns the anchors that may have `subject`. See
+    /// `TrustAnchorSource::anchors_for_subject()`.
+    pub(crate) fn for_subject(self, subject: untrusted::Input<'_>) -> &'p [TrustAnchor<'p>] {
+        match self {
+            Self::Slice(anchors) => anchors,
+            Self::Source(source) => source.anchors_for_subject(subject.as_slice_less_safe()),
+        }
+    }
+}
+
+impl<'p> From<&'p [TrustAnchor<'p>]> for Anchors<'p> {
+    fn from(anchors: &'p [TrustAnchor<'p>]) -> Self {
+        Self::Slice(anchors)
+    }
+}
+
+/// A collection of trust anchors gathered from one or more sources.
+///
+/// Anchors are borrowed rather than copied from the slices they are added from, so a set can
+/// cheaply combine the `&'static [TrustAnchor<'static>]` provided by crates like `webpki-roots`
+/// with anchors extracted by [`anchor_from_trusted_cert()`].
+///
+/// Use [`TrustAnchorSet::as_slice()`] to pass the set to
+/// [`crate::EndEntityCert::verify_for_usage()`].
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct TrustAnchorSet<'a> {
+    anchors: Vec<TrustAnchor<'a>>,
+    generation: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> TrustAnchorSet<'a> {
+    /// Construct an empty set.
+    pub fn new() -> Self {
+        Self {
+            anchors: Vec::new(),
+            generation: crate::next_generation(),
+        }
+    }
+
+    /// Add all of the given trust anchors to the set, borrowing their contents.
+    pub fn extend_from_slice(&mut self, anchors: &'a [TrustAnchor<'_>]) {
+        self.generation = crate::next_generation();
+        self.anchors.extend(anchors.iter().map(|anchor| {
+            TrustAnchor {
+                subject: Der::from(anchor.subject.as_ref()),
+                subject_public_key_info: Der::from(anchor.subject_public_key_info.as_ref()),
+                name_constraints: anchor
+                    .name_constraints
+                    .as_ref()
+                    .map(|nc| Der::from(nc.as_ref())),
+            }
+        }));
+    }
+
+    /// Returns an iterator over the trust anchors in the set with the given DER-encoded subject.
+    ///
+    /// As with [`TrustAnchor::subject`], `subject` excludes the outer SEQUENCE tag and length.
+    pub fn find_by_subject<'s>(
+        &'s self,
+        subject: &'s [u8],
+    ) -> impl Iterator<Item = &'s TrustAnchor<'a>> + 's {
+        self.anchors
+            .iter()
+            .filter(move |anchor| anchor.subject.as_ref() == subject)
+    }
+
+    /// The trust anchors in the set.
+    pub fn as_slice(&self) -> &[TrustAnchor<'a>] {
+        &self.anchors
+    }
+
+    /// The number of trust anchors in the set.
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    /// Returns true if the set contains no trust anchors.
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    /// Identifies the current contents of the set.
+    ///
+    /// Every set is created with a generation distinct from that of any other set in the
+    /// process, and each change to its contents assigns it another. Clones share a
+    /// generation until either is changed.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Debug for TrustAnchorSet<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TrustAnchorSet")
+            .field("len", &self.anchors.len())
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for TrustAnchorSet<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a [TrustAnchor<'_>]> for TrustAnchorSet<'a> {
+    fn from(anchors: &'a [TrustAnchor<'_>]) -> Self {
+        let mut set = Self::new();
+        set.extend_from_slice(anchors);
+        set
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Extend<TrustAnchor<'a>> for TrustAnchorSet<'a> {
+    fn extend<T: IntoIterator<Item = TrustAnchor<'a>>>(&mut self, iter: T) {
+        self.generation = crate::next_generation();
+        self.anchors.extend(iter)
+    }
+}
+
 fn skip(input: &mut untrusted::Reader, tag: der::Tag) -> Result<(), Error> {
     der::expect_tag(input, tag).map(|_| ())
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{make_end_entity, make_issuer};
+
+    fn tlv(tag: u8, value: &[u8]) -> Vec<u8> {
+        let mut der = vec![tag];
+        match value.len() {
+            len @ 0..=0x7f => der.push(u8::try_from(len).unwrap()),
+            len => {
+                der.extend([0x82]);
+                der.extend(u16::try_from(len).unwrap().to_be_bytes());
+            }
+        }
+        der.extend_from_slice(value);
+        der
+    }
+
+    // A certs-only SignedData, as produced by `openssl crl2pkcs7 -nocrl`.
+    fn pkcs7(certs: &[&[u8]]) -> Vec<u8> {
+        let mut signed_data = tlv(0x02, &[1]);
+        signed_data.extend(tlv(0x31, &[]));
+        signed_data.extend(tlv(
+            0x30,
+            &tlv(0x06, &oid!(1, 2, 0x86, 0x48, 0x86, 0xf7, 0x0d, 1, 7, 1)),
+        ));
+        signed_data.extend(tlv(0xa0, &certs.concat()));
+        signed_data.extend(tlv(0x31, &[]));
+
+        let mut content_info = tlv(0x06, &ID_SIGNED_DATA);
+        content_info.extend(tlv(0xa0, &tlv(0x30, &signed_data)));
+        tlv(0x30, &content_info)
+    }
+
+    #[test]
+    fn extracts_ca_certificates() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let v1_der = CertificateDer::from(&include_bytes!("../tests/misc/v1_intermediate.der")[..]);
+        let ee_der = make_end_entity(&ca);
+        let bundle = pkcs7(&[&ca_der, &ee_der, &v1_der]);
+
+        let expected = [
+            anchor_from_trusted_cert(&ca_der).unwrap(),
+            anchor_from_trusted_cert(&v1_der).unwrap(),
+        ];
+        assert_eq!(
+            extract_trust_anchors_from_pkcs7(&bundle, NonCaCertPolicy::Skip).unwrap(),
+            expected
+        );
+        assert_eq!(
+            extract_trust_anchors_from_pkcs7(&bundle, NonCaCertPolicy::Reject),
+            Err(Error::BundleCertNotCa { index: 1 })
+        );
+        assert_eq!(
+            extract_trust_anchors_from_pkcs7(&pkcs7(&[&ca_der, &v1_der]), NonCaCertPolicy::Reject)
+                .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn reports_index_of_malformed_certificate() {
+        let ca_der = CertificateDer::from(make_issuer("Test CA").serialize_der().unwrap());
+        let truncated = tlv(0x30, &ca_der[4..ca_der.len() - 8]);
+        for policy in [NonCaCertPolicy::Skip, NonCaCertPolicy::Reject] {
+            assert_eq!(
+                extract_trust_anchors_from_pkcs7(&pkcs7(&[&ca_der, &truncated, &ca_der]), policy),
+                Err(Error::MalformedBundleCert { index: 1 })
+            );
+            // An entry that isn't a complete SEQUENCE.
+            assert_eq!(
+                extract_trust_anchors_from_pkcs7(&pkcs7(&[&ca_der, &[0x30, 0x05, 0x00]]), policy),
+                Err(Error::MalformedBundleCert { index: 1 })
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_other_structures() {
+        let ca_der = CertificateDer::from(make_issuer("Test CA").serialize_der().unwrap());
+        let policy = NonCaCertPolicy::Skip;
+        assert_eq!(
+            extract_trust_anchors_from_pkcs7(&pkcs7(&[]), policy),
+            Ok(Vec::new())
+        );
+        // A bare certificate, and a concatenation of them.
+        assert_eq!(
+            extract_trust_anchors_from_pkcs7(&ca_der, policy),
+            Err(Error::BadDer)
+        );
+        assert_eq!(
+            extract_trust_anchors_from_pkcs7(&[&ca_der[..], &ca_der[..]].concat(), policy),
+            Err(Error::BadDer)
+        );
+        // Trailing data after the bundle.
+        let mut bundle = pkcs7(&[&ca_der]);
+        bundle.push(0);
+        assert_eq!(
+            extract_trust_anchors_from_pkcs7(&bundle, policy),
+            Err(Error::BadDer)
+        );
+    }
+
+    #[test]
+    fn checked_anchors() {
+        use crate::test_utils::issuer_params;
+
+        let policy = AnchorPolicy::new();
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&ca_der, &policy),
+            anchor_from_trusted_cert(&ca_der)
+        );
+
+        let ee_der = make_end_entity(&ca);
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&ee_der, &policy),
+            Err(Error::TrustAnchorNotCa)
+        );
+
+        let mut params = issuer_params("Not CA");
+        params.is_ca = rcgen::IsCa::NoCa;
+        let not_ca = CertificateDer::from(
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der()
+                .unwrap(),
+        );
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&not_ca, &policy),
+            Err(Error::TrustAnchorNotCa)
+        );
+
+        let mut params = issuer_params("No KeyCertSign");
+        params.key_usages = vec![rcgen::KeyUsagePurpose::DigitalSignature];
+        let no_cert_sign = CertificateDer::from(
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der()
+                .unwrap(),
+        );
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&no_cert_sign, &policy),
+            Err(Error::TrustAnchorKeyUsageInvalid)
+        );
+
+        let intermediate = CertificateDer::from(
+            make_issuer("Intermediate")
+                .serialize_der_with_signer(&ca)
+                .unwrap(),
+        );
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&intermediate, &policy),
+            Err(Error::TrustAnchorNotSelfIssued)
+        );
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&intermediate, &policy.allow_not_self_issued()),
+            anchor_from_trusted_cert(&intermediate)
+        );
+    }
+
+    #[test]
+    fn checked_v1_anchors() {
+        let policy = AnchorPolicy::new();
+        let v1_root =
+            CertificateDer::from(&include_bytes!("../tests/trust_anchor/v1_root.der")[..]);
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&v1_root, &policy),
+            Err(Error::UnsupportedCertVersion)
+        );
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&v1_root, &policy.allow_v1()),
+            anchor_from_trusted_cert(&v1_root)
+        );
+
+        let v1_intermediate =
+            CertificateDer::from(&include_bytes!("../tests/misc/v1_intermediate.der")[..]);
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&v1_intermediate, &policy.allow_v1()),
+            Err(Error::TrustAnchorNotSelfIssued)
+        );
+        assert_eq!(
+            anchor_from_trusted_cert_checked(
+                &v1_intermediate,
+                &policy.allow_v1().allow_not_self_issued()
+            ),
+            anchor_from_trusted_cert(&v1_intermediate)
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    fn checked_anchor_self_signature() {
+        use crate::test_utils::issuer_params;
+
+        let policy = AnchorPolicy::new().require_self_signed(crate::ALL_VERIFICATION_ALGS);
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&ca_der, &policy),
+            anchor_from_trusted_cert(&ca_der)
+        );
+
+        // Self-issued, but signed by another key.
+        let rekeyed = CertificateDer::from(
+            rcgen::Certificate::from_params(issuer_params("Test CA"))
+                .unwrap()
+                .serialize_der_with_signer(&ca)
+                .unwrap(),
+        );
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&rekeyed, &policy),
+            Err(Error::TrustAnchorNotSelfSigned)
+        );
+
+        let v1_root =
+            CertificateDer::from(&include_bytes!("../tests/trust_anchor/v1_root.der")[..]);
+        assert_eq!(
+            anchor_from_trusted_cert_checked(&v1_root, &policy.allow_v1()),
+            anchor_from_trusted_cert(&v1_root)
+        );
+        let v1_intermediate =
+            CertificateDer::from(&include_bytes!("../tests/misc/v1_intermediate.der")[..]);
+        assert_eq!(
+            anchor_from_trusted_cert_checked(
+                &v1_intermediate,
+                &policy.allow_v1().allow_not_self_issued()
+            ),
+            Err(Error::TrustAnchorNotSelfSigned)
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    fn owned_anchors_outlive_source() {
+        use core::time::Duration;
+
+        use pki_types::UnixTime;
+
+        use crate::{EndEntityCert, KeyUsage};
+
+        let ca = make_issuer("Test CA");
+        let ee_der = make_end_entity(&ca);
+        let anchors: Vec<TrustAnchor<'static>> = {
+            let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+            let bundle = pkcs7(&[&ca_der]);
+            let mut anchors = vec![anchor_from_trusted_cert(&ca_der).unwrap().to_owned()];
+            anchors.extend(
+                extract_trust_anchors_from_pkcs7(&bundle, NonCaCertPolicy::Reject)
+                    .unwrap()
+                    .iter()
+                    .map(TrustAnchor::to_owned),
+            );
+            anchors
+        };
+        drop(ca);
+
+        let ee = EndEntityCert::try_from(&ee_der).unwrap();
+        for anchor in &anchors {
+            let path = ee
+                .verify_for_usage(
+                    crate::ALL_VERIFICATION_ALGS,
+                    core::slice::from_ref(anchor),
+                    &[],
+                    UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)),
+                    KeyUsage::server_auth(),
+                    None,
+                    None,
+                )
+                .unwrap();
+            assert!(core::ptr::eq(path.anchor(), anchor));
+        }
+    }
+}
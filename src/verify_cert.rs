@@ -12,26 +12,69 @@
 // ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
 // OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
+use core::cell::Cell;
 use core::default::Default;
+use core::fmt;
 use core::ops::ControlFlow;
+use core::time::Duration;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 use pki_types::{CertificateDer, SignatureVerificationAlgorithm, TrustAnchor, UnixTime};
 
-use crate::cert::Cert;
-use crate::crl::RevocationOptions;
+use crate::cert::{BasicConstraints, Cert};
+use crate::crl::{RevocationOptions, RevocationStatus};
+use crate::dangerous::{AcceptedPath, AllowedErrors};
 use crate::der::{self, FromDer};
 use crate::end_entity::EndEntityCert;
-use crate::error::Error;
-use crate::{public_values_eq, signed_data, subject_name};
+use crate::error::{CertRole, Error};
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+use crate::ocsp::{self, OcspCertStatus};
+#[cfg(feature = "alloc")]
+use crate::subject_name::{ConstraintSource, EnforcedNameConstraints};
+use crate::time::skewed;
+use crate::trust_anchor::Anchors;
+use crate::{constant_time, policy, public_values_eq, signed_data, subject_name};
 
 // Use `'a` for lifetimes that we don't care about, `'p` for lifetimes that become a part of
 // the `VerifiedPath`.
 pub(crate) struct ChainOptions<'a, 'p> {
     pub(crate) eku: KeyUsage,
     pub(crate) supported_sig_algs: &'a [&'a dyn SignatureVerificationAlgorithm],
-    pub(crate) trust_anchors: &'p [TrustAnchor<'p>],
-    pub(crate) intermediate_certs: &'p [CertificateDer<'p>],
+    pub(crate) trust_anchors: Anchors<'p>,
+    pub(crate) intermediate_certs: &'a SuppliedIntermediates<'p>,
     pub(crate) revocation: Option<RevocationOptions<'a>>,
+    pub(crate) policy: VerificationPolicy,
+    pub(crate) anchor_filter: Option<&'a dyn Fn(&TrustAnchor<'_>) -> bool>,
+    pub(crate) hook: Option<&'a dyn InspectionHook>,
+    /// The maximum number of intermediate certificates in a path, at most `MAX_SUB_CA_COUNT`.
+    pub(crate) max_sub_ca_count: usize,
+    pub(crate) budget: Budget,
+    /// The clock skew tolerated in each comparison with the verification time.
+    pub(crate) clock_skew: Duration,
+    /// The time revocation data must be fresh at, if not the verification time. See
+    /// `Verifier::freshness_time()`.
+    pub(crate) freshness_time: Option<UnixTime>,
+    /// The certificate policies the path must be valid for. See `policy::check_policies()`.
+    pub(crate) required_policies: &'a [&'a [u8]],
+    /// Critical extensions to accept without processing them. See
+    /// `Cert::check_critical_extensions()`.
+    pub(crate) ignored_extensions: &'a [&'a [u8]],
+    /// The errors to accept certificates despite. See `dangerous::AllowedErrors`.
+    pub(crate) allowed_errors: AllowedErrors,
+    /// Whether to accept a path whose topmost certificate has an unknown issuer. See
+    /// `Verifier::dangerous_verify_allowing_unknown_issuer()`.
+    pub(crate) allow_unknown_issuer: bool,
+    /// Whether to skip checking that certificates are valid at the verification time. See
+    /// `Verifier::dangerous_ignore_validity_periods()`.
+    pub(crate) ignore_validity_periods: bool,
+    /// Whether the end-entity certificate may be a v1 certificate. See
+    /// `Verifier::allow_v1_certificates()`.
+    pub(crate) allow_v1: bool,
+    /// The most specific error found so far, and the certificate it was found at. See
+    /// `ChainOptions::record()`.
+    pub(crate) failure: Cell<Option<(Error, FailedCert<'p>)>>,
 }
 
 impl<'a, 'p: 'a> ChainOptions<'a, 'p> {
@@ -40,12 +83,100 @@ impl<'a, 'p: 'a> ChainOptions<'a, 'p> {
         end_entity: &'p EndEntityCert<'p>,
         time: UnixTime,
         verify_path: Option<&dyn Fn(&VerifiedPath<'_>) -> Result<(), Error>>,
-    ) -> Result<VerifiedPath<'p>, Error> {
+    ) -> Result<AcceptedPath<'p>, DetailedError<'p>> {
+        if self.intermediate_certs.overflowed() {
+            return Err(DetailedError {
+                error: Error::MaximumPathDepthExceeded,
+                cert: None,
+            });
+        }
+
         let mut path = PartialPath::new(end_entity);
-        match self.build_chain_inner(&mut path, time, verify_path, 0, &mut Budget::default()) {
-            Ok(anchor) => Ok(VerifiedPath::new(end_entity, anchor, path)),
-            Err(ControlFlow::Break(err)) | Err(ControlFlow::Continue(err)) => Err(err),
+        let mut budget = self.budget;
+        let error = match self.build_chain_inner(&mut path, time, verify_path, 0, &mut budget) {
+            Ok(anchor) => {
+                let revocation = self.revocation_statuses(&path.node(), anchor);
+                let suppressed = match self.allowed_errors.is_empty() {
+                    true => Default::default(),
+                    false => validity_errors(
+                        end_entity,
+                        &path.intermediates[..path.used],
+                        time,
+                        self.clock_skew,
+                    ),
+                };
+                return Ok(match anchor {
+                    Some(anchor) => {
+                        let mut path = VerifiedPath::new(
+                            end_entity,
+                            anchor,
+                            self.trust_anchors,
+                            path,
+                            time,
+                            self.freshness_time(time),
+                        );
+                        path.revocation = revocation;
+                        path.suppressed = suppressed;
+                        AcceptedPath::Verified(path)
+                    }
+                    None => AcceptedPath::UnknownIssuer(UnanchoredPath {
+                        end_entity,
+                        intermediates: Intermediates::Owned {
+                            certs: path.intermediates,
+                            used: path.used,
+                        },
+                        revocation,
+                        suppressed,
+                        #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+                        ocsp: [None; MAX_SUB_CA_COUNT + 1],
+                        time,
+                    }),
+                });
+            }
+            Err(err @ ControlFlow::Continue(Error::UnknownIssuer)) => {
+                self.record(err, 0, &end_entity.der)
+            }
+            Err(err) => err,
+        };
+
+        let error = match error {
+            ControlFlow::Break(err) | ControlFlow::Continue(err) => err,
+        };
+        // Errors that can't be attributed to a certificate, such as those returned by
+        // `verify_path`, are never recorded, but may still be the most specific ones.
+        let cert = match self.failure.take() {
+            Some((recorded, cert)) if recorded == error => Some(cert),
+            _ => None,
+        };
+        Err(DetailedError { error, cert })
+    }
+
+    /// Remembers that `err` was found at the certificate at `depth`, unless a more specific
+    /// error has been found already.
+    ///
+    /// Among errors of the same rank the first is kept, so this selects the same error as
+    /// combining every error with `Error::most_specific()` does. Fatal errors are not
+    /// recorded: they concern the search as a whole, not one certificate.
+    fn record(
+        &self,
+        err: ControlFlow<Error, Error>,
+        depth: usize,
+        der: &CertificateDer<'p>,
+    ) -> ControlFlow<Error, Error> {
+        if let ControlFlow::Continue(error) = err {
+            let failure = self.failure.take();
+            self.failure.set(match failure {
+                Some((recorded, _)) if recorded.rank() >= error.rank() => failure,
+                _ => Some((
+                    error,
+                    FailedCert {
+                        depth,
+                        der: der.clone(),
+                    },
+                )),
+            });
         }
+        err
     }
 
     fn build_chain_inner(
@@ -55,43 +186,85 @@ impl<'a, 'p: 'a> ChainOptions<'a, 'p> {
         verify_path: Option<&dyn Fn(&VerifiedPath<'_>) -> Result<(), Error>>,
         sub_ca_count: usize,
         budget: &mut Budget,
-    ) -> Result<&'p TrustAnchor<'p>, ControlFlow<Error, Error>> {
+    ) -> Result<Option<&'p TrustAnchor<'p>>, ControlFlow<Error, Error>> {
         let role = path.node().role();
 
-        check_issuer_independent_properties(path.head(), time, role, sub_ca_count, self.eku.inner)?;
+        let node = path.node();
+        let check = |time| {
+            // Only an end-entity certificate can be a v1 certificate.
+            if node.cert.v1 && !self.allow_v1 {
+                return Err(Error::UnsupportedCertVersion);
+            }
+            check_issuer_independent_properties(
+                &node,
+                time,
+                self.clock_skew,
+                sub_ca_count,
+                self.eku.inner,
+                &self.policy,
+                self.ignored_extensions,
+            )
+        };
+        let result = check(match self.ignore_validity_periods {
+            true => None,
+            false => Some(time),
+        });
+        let suppress = |result| match result {
+            Err(err) if self.allowed_errors.allows(err) => check(None),
+            result => result,
+        };
+        match self.hook {
+            Some(hook) => match hook.on_certificate(node.index, node.cert, result) {
+                HookDecision::Continue => suppress(result),
+                // Only the validity period is checked before the overridable errors can
+                // arise, so re-run the remaining checks without it.
+                HookDecision::OverrideAccept => match result {
+                    Err(err) if overridable(err) => check(None),
+                    result => result,
+                },
+                HookDecision::Reject(err) => Err(err),
+            },
+            None => suppress(result),
+        }
+        .map_err(|err| self.record(err.into(), node.index, &path.head().der))?;
 
         // TODO: HPKP checks.
 
+        // Of the issuers named as the certificate's issuer, the one whose key its
+        // authorityKeyIdentifier identifies is tried first, as after a CA key rollover there
+        // may be several. The others are still tried after it: key identifiers in the wild are
+        // sometimes wrong, and trust anchors have none, so their keys are hashed to match.
+        let aki = path.head().authority_key_id().ok().flatten();
+        let anchors = self.trust_anchors.for_subject(path.head().issuer);
+        let preferred = aki.and_then(|aki| {
+            anchors.iter().position(|anchor| {
+                public_values_eq(
+                    path.head().issuer,
+                    untrusted::Input::from(anchor.subject.as_ref()),
+                ) && key_id_identifies(
+                    aki,
+                    untrusted::Input::from(anchor.subject_public_key_info.as_ref()),
+                )
+            })
+        });
+
         let result = loop_while_non_fatal_error(
             Error::UnknownIssuer,
-            self.trust_anchors,
-            |trust_anchor: &TrustAnchor| {
+            preferred_first(anchors.iter(), preferred),
+            |trust_anchor: &'p TrustAnchor<'p>| {
                 let trust_anchor_subject = untrusted::Input::from(trust_anchor.subject.as_ref());
                 if !public_values_eq(path.head().issuer, trust_anchor_subject) {
                     return Err(Error::UnknownIssuer.into());
                 }
+                if let Some(filter) = self.anchor_filter {
+                    if !filter(trust_anchor) {
+                        return Err(Error::UnknownIssuer.into());
+                    }
+                }
 
                 // TODO: check_distrust(trust_anchor_subject, trust_anchor_spki)?;
 
-                let node = path.node();
-                self.check_signed_chain(&node, trust_anchor, budget)?;
-                check_signed_chain_name_constraints(&node, trust_anchor, budget)?;
-
-                let verify = match verify_path {
-                    Some(verify) => verify,
-                    None => return Ok(trust_anchor),
-                };
-
-                let candidate = VerifiedPath {
-                    end_entity: path.end_entity,
-                    intermediates: Intermediates::Borrowed(&path.intermediates[..path.used]),
-                    anchor: trust_anchor,
-                };
-
-                match verify(&candidate) {
-                    Ok(()) => Ok(trust_anchor),
-                    Err(err) => Err(ControlFlow::Continue(err)),
-                }
+                self.check_path(path, Some(trust_anchor), time, verify_path, budget)
             },
         );
 
@@ -105,62 +278,257 @@ impl<'a, 'p: 'a> ChainOptions<'a, 'p> {
             Err(ControlFlow::Continue(err)) => err,
         };
 
-        loop_while_non_fatal_error(err, self.intermediate_certs, |cert_der| {
-            let potential_issuer = Cert::from_der(untrusted::Input::from(cert_der))?;
-            if !public_values_eq(potential_issuer.subject, path.head().issuer) {
-                return Err(Error::UnknownIssuer.into());
-            }
+        let intermediates = self.intermediate_certs;
+        let preferred = aki.and_then(|aki| {
+            (0..intermediates.len()).position(|i| match intermediates.cert(i) {
+                Ok(cert) => {
+                    cert.subject_key_identifier
+                        .map_or(false, |ski| public_values_eq(ski, aki))
+                        && public_values_eq(cert.subject, path.head().issuer)
+                }
+                Err(_) => false,
+            })
+        });
+        let result = loop_while_non_fatal_error(
+            err,
+            preferred_first(0..intermediates.len(), preferred),
+            |i| {
+                let cert_der = intermediates.der(i);
+                // Some peers send the same certificate more than once. A copy of an earlier
+                // candidate would only repeat the work already done for it. (A copy of a
+                // certificate already in the path, including the end-entity certificate, is
+                // rejected by the loop check below.)
+                if preferred_first(0..intermediates.len(), preferred)
+                    .take_while(|&prev| prev != i)
+                    .any(|prev| intermediates.der(prev).as_ref() == cert_der.as_ref())
+                {
+                    return Err(Error::UnknownIssuer.into());
+                }
+
+                // A v1 or v2 certificate can't carry basicConstraints, so it would be rejected as an
+                // issuer anyway. In strict mode, say why.
+                let potential_issuer = intermediates.cert(i).map_err(|err| {
+                    let err = match err {
+                        Error::UnsupportedCertVersion if self.policy.strict => {
+                            Error::UnsupportedCertVersionForCa
+                        }
+                        err => err,
+                    };
+                    self.record(err.into(), path.used + 1, cert_der)
+                })?;
+                if !public_values_eq(potential_issuer.subject, path.head().issuer) {
+                    return Err(Error::UnknownIssuer.into());
+                }
+
+                // A supplied copy of a trust anchor, such as a directly trusted intermediate sent
+                // along with the root above it, ends the path: the anchor was already tried as the
+                // issuer above. Whatever issued it is ignored, however invalid.
+                if self
+                    .trust_anchors
+                    .for_subject(potential_issuer.subject)
+                    .iter()
+                    .any(|anchor| {
+                        public_values_eq(
+                            potential_issuer.subject,
+                            untrusted::Input::from(anchor.subject.as_ref()),
+                        ) && public_values_eq(
+                            potential_issuer.spki,
+                            untrusted::Input::from(anchor.subject_public_key_info.as_ref()),
+                        ) && self.anchor_filter.map_or(true, |filter| filter(anchor))
+                    })
+                {
+                    return Err(Error::UnknownIssuer.into());
+                }
+
+                // A mismatched key identifier suggests this is the wrong generation of a CA that
+                // shares its name with others. AKI values in the wild are sometimes wrong, so this
+                // is only enforced in strict mode.
+                if self.policy.strict {
+                    if let (Some(aki), Some(ski)) = (
+                        path.head().authority_key_id()?,
+                        potential_issuer.subject_key_identifier,
+                    ) {
+                        if !constant_time::slices_eq(
+                            aki.as_slice_less_safe(),
+                            ski.as_slice_less_safe(),
+                        ) {
+                            return Err(Error::KeyIdentifierMismatch.into());
+                        }
+                    }
+                }
+
+                // Prevent loops; see RFC 4158 section 5.2.
+                if path.node().iter().any(|prev| {
+                    public_values_eq(potential_issuer.spki, prev.cert.spki)
+                        && public_values_eq(potential_issuer.subject, prev.cert.subject)
+                }) {
+                    return Err(Error::UnknownIssuer.into());
+                }
+
+                // https://www.rfc-editor.org/rfc/rfc5280#section-6.1.4: only intermediates that are
+                // not self-issued count towards the pathLenConstraint of the CAs above them.
+                let next_sub_ca_count = match role {
+                    Role::EndEntity => sub_ca_count,
+                    Role::Issuer if path.head().is_self_issued() => sub_ca_count,
+                    Role::Issuer => sub_ca_count + 1,
+                };
+
+                budget.consume_build_chain_call()?;
+                if path.used >= self.max_sub_ca_count {
+                    return Err(Error::MaximumPathDepthExceeded.into());
+                }
+                path.push(potential_issuer)?;
+                let result = self
+                    .build_chain_inner(path, time, verify_path, next_sub_ca_count, budget)
+                    .map_err(|err| match err {
+                        // Only now is it known that no issuer was found for `potential_issuer`.
+                        ControlFlow::Continue(Error::UnknownIssuer) => {
+                            self.record(err, path.used, &path.head().der)
+                        }
+                        err => err,
+                    });
+                if result.is_err() {
+                    path.pop();
+                }
 
-            // Prevent loops; see RFC 4158 section 5.2.
-            if path.node().iter().any(|prev| {
-                public_values_eq(potential_issuer.spki, prev.cert.spki)
-                    && public_values_eq(potential_issuer.subject, prev.cert.subject)
-            }) {
-                return Err(Error::UnknownIssuer.into());
+                result
+            },
+        );
+
+        match result {
+            // No issuer was found, rather than one that was rejected. See
+            // `Verifier::dangerous_verify_allowing_unknown_issuer()`.
+            Err(ControlFlow::Continue(Error::UnknownIssuer)) if self.allow_unknown_issuer => {
+                self.check_path(path, None, time, verify_path, budget)
             }
+            result => result,
+        }
+    }
 
-            let next_sub_ca_count = match role {
-                Role::EndEntity => sub_ca_count,
-                Role::Issuer => sub_ca_count + 1,
-            };
+    /// Checks the path ending at `path`, whose head is issued by `trust_anchor`, or by an
+    /// unknown issuer if that is `None`, and then calls `verify_path` with it if it ends at a
+    /// trust anchor. A path whose issuer is unknown is never a `VerifiedPath`.
+    fn check_path(
+        &self,
+        path: &PartialPath<'p>,
+        trust_anchor: Option<&'p TrustAnchor<'p>>,
+        time: UnixTime,
+        verify_path: Option<&dyn Fn(&VerifiedPath<'_>) -> Result<(), Error>>,
+        budget: &mut Budget,
+    ) -> Result<Option<&'p TrustAnchor<'p>>, ControlFlow<Error, Error>> {
+        let node = path.node();
+        self.check_signed_chain(&node, trust_anchor, time, budget)
+            .map_err(|(depth, err)| self.record(err, depth, &path.get(depth).der))?;
+        check_signed_chain_name_constraints(
+            &node,
+            trust_anchor,
+            self.policy.subject_name_constraints,
+            budget,
+        )
+        .map_err(|(depth, err)| self.record(err, depth, &path.get(depth).der))?;
+        policy::check_policies(&node, self.required_policies)
+            .map_err(|(depth, err)| self.record(err.into(), depth, &path.get(depth).der))?;
+
+        let (anchor, verify) = match (trust_anchor, verify_path) {
+            (Some(anchor), Some(verify)) => (anchor, verify),
+            _ => return Ok(trust_anchor),
+        };
+
+        let candidate = VerifiedPath {
+            end_entity: path.end_entity,
+            intermediates: Intermediates::Borrowed(&path.intermediates[..path.used]),
+            anchor,
+            anchors: self.trust_anchors,
+            revocation: self.revocation_statuses(&node, trust_anchor),
+            suppressed: Default::default(),
+            #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+            ocsp: [None; MAX_SUB_CA_COUNT + 1],
+            time,
+            freshness_time: self.freshness_time(time),
+        };
+
+        match verify(&candidate) {
+            Ok(()) => Ok(trust_anchor),
+            Err(err) => Err(ControlFlow::Continue(err)),
+        }
+    }
 
-            budget.consume_build_chain_call()?;
-            path.push(potential_issuer)?;
-            let result = self.build_chain_inner(path, time, verify_path, next_sub_ca_count, budget);
-            if result.is_err() {
-                path.pop();
+    /// The revocation status of each certificate in the path ending at `path`, once
+    /// `check_signed_chain()` has succeeded for it with `trust_anchor`.
+    fn revocation_statuses(
+        &self,
+        path: &PathNode<'_>,
+        trust_anchor: Option<&TrustAnchor<'_>>,
+    ) -> [RevocationStatus; MAX_SUB_CA_COUNT + 1] {
+        let mut statuses = [RevocationStatus::NotChecked; MAX_SUB_CA_COUNT + 1];
+        if let Some(revocation) = &self.revocation {
+            for node in path.iter() {
+                statuses[node.index] = match (trust_anchor, node.index == path.index) {
+                    (None, true) => revocation
+                        .status_without_issuer(&node)
+                        .unwrap_or(RevocationStatus::Unknown),
+                    _ => revocation.status(&node),
+                };
             }
+        }
+        statuses
+    }
 
-            result
-        })
+    /// The time revocation data must be fresh at, when verifying as of `time`.
+    pub(crate) fn freshness_time(&self, time: UnixTime) -> UnixTime {
+        self.freshness_time.unwrap_or(time)
     }
 
+    /// Checks the signature and revocation status of each certificate in the path ending at
+    /// `path`. Those of its head can't be checked if `trust_anchor` is `None`, as its issuer is
+    /// unknown.
     fn check_signed_chain(
         &self,
         path: &PathNode<'_>,
-        trust_anchor: &TrustAnchor,
+        trust_anchor: Option<&TrustAnchor>,
+        time: UnixTime,
         budget: &mut Budget,
-    ) -> Result<(), ControlFlow<Error, Error>> {
-        let mut spki_value = untrusted::Input::from(trust_anchor.subject_public_key_info.as_ref());
-        let mut issuer_subject = untrusted::Input::from(trust_anchor.subject.as_ref());
-        let mut issuer_key_usage = None; // TODO(XXX): Consider whether to track TrustAnchor KU.
-        for path in path.iter() {
+    ) -> Result<(), (usize, ControlFlow<Error, Error>)> {
+        let mut iter = path.iter();
+        let (mut spki_value, mut issuer_subject, mut issuer_key_usage) = match trust_anchor {
+            Some(trust_anchor) => (
+                untrusted::Input::from(trust_anchor.subject_public_key_info.as_ref()),
+                untrusted::Input::from(trust_anchor.subject.as_ref()),
+                None, // TODO(XXX): Consider whether to track TrustAnchor KU.
+            ),
+            None => {
+                let head = iter.next().unwrap();
+                if let Some(revocation_opts) = &self.revocation {
+                    revocation_opts
+                        .status_without_issuer(&head)
+                        .map_err(|err| (head.index, err.into()))?;
+                }
+                (head.cert.spki, head.cert.subject, head.cert.key_usage)
+            }
+        };
+        for path in iter {
             signed_data::verify_signed_data(
                 self.supported_sig_algs,
                 spki_value,
                 &path.cert.signed_data,
                 budget,
-            )?;
+            )
+            .map_err(|err| (path.index, err.into()))?;
 
             if let Some(revocation_opts) = &self.revocation {
-                revocation_opts.check(
-                    &path,
-                    issuer_subject,
-                    spki_value,
-                    issuer_key_usage,
-                    self.supported_sig_algs,
-                    budget,
-                )?;
+                revocation_opts
+                    .check(
+                        &path,
+                        issuer_subject,
+                        spki_value,
+                        issuer_key_usage,
+                        self.supported_sig_algs,
+                        time,
+                        self.freshness_time(time),
+                        self.clock_skew,
+                        budget,
+                    )
+                    .map_err(|err| (path.index, err.into()))?;
             }
 
             spki_value = path.cert.spki;
@@ -179,13 +547,32 @@ pub struct VerifiedPath<'p> {
     end_entity: &'p EndEntityCert<'p>,
     intermediates: Intermediates<'p>,
     anchor: &'p TrustAnchor<'p>,
+    /// All the trust anchors supplied for verification, of which `anchor` is one.
+    anchors: Anchors<'p>,
+    /// The revocation status of the certificate at each depth. See
+    /// `VerifiedPath::revocation_statuses()`.
+    revocation: [RevocationStatus; MAX_SUB_CA_COUNT + 1],
+    /// The errors suppressed for the certificate at each depth. See
+    /// `VerifiedPath::suppressed_errors()`.
+    suppressed: [Option<Error>; MAX_SUB_CA_COUNT + 1],
+    /// The status given by the OCSP response for the certificate at each depth. See
+    /// `VerifiedPath::ocsp_statuses()`.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    ocsp: [Option<OcspCertStatus>; MAX_SUB_CA_COUNT + 1],
+    /// The verification time. See `VerifiedPath::validation_time()`.
+    time: UnixTime,
+    /// See `VerifiedPath::freshness_time()`.
+    freshness_time: UnixTime,
 }
 
 impl<'p> VerifiedPath<'p> {
     fn new(
         end_entity: &'p EndEntityCert<'p>,
         anchor: &'p TrustAnchor<'p>,
+        anchors: Anchors<'p>,
         partial: PartialPath<'p>,
+        time: UnixTime,
+        freshness_time: UnixTime,
     ) -> Self {
         Self {
             end_entity,
@@ -194,9 +581,98 @@ impl<'p> VerifiedPath<'p> {
                 used: partial.used,
             },
             anchor,
+            anchors,
+            revocation: [RevocationStatus::NotChecked; MAX_SUB_CA_COUNT + 1],
+            suppressed: Default::default(),
+            #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+            ocsp: [None; MAX_SUB_CA_COUNT + 1],
+            time,
+            freshness_time,
         }
     }
 
+    /// The time the certificates in the path were verified as of: the time set by
+    /// [`crate::Verifier::at_time()`], or the current time.
+    pub fn validation_time(&self) -> UnixTime {
+        self.time
+    }
+
+    /// The time the revocation data consulted was required to be fresh at, as set by
+    /// [`crate::Verifier::freshness_time()`]. This is the [`Self::validation_time()`] unless
+    /// set otherwise.
+    pub fn freshness_time(&self) -> UnixTime {
+        self.freshness_time
+    }
+
+    /// Yields the revocation status of each certificate in the path: the end-entity
+    /// certificate first, then each intermediate certificate from the end-entity's issuer
+    /// upwards.
+    ///
+    /// With [`crate::UnknownStatusPolicy::Allow`], a certificate is accepted when no supplied
+    /// CRL is authoritative for it, for example because the CRL's issuer or issuing
+    /// distribution point doesn't match. Such certificates are reported as
+    /// [`RevocationStatus::Unknown`], so that a caller can decide to fail after all.
+    pub fn revocation_statuses(&self) -> impl Iterator<Item = RevocationStatus> + '_ {
+        self.revocation[..self.intermediates.as_ref().len() + 1]
+            .iter()
+            .copied()
+    }
+
+    /// Yields the errors suppressed as allowed by [`crate::Verifier::dangerous_allow_errors()`],
+    /// with the depth in the path of the certificate each was found at (0 for the end-entity).
+    ///
+    /// This is empty unless errors were allowed, and for the paths passed to `verify_path`.
+    pub fn suppressed_errors(&self) -> impl Iterator<Item = (usize, Error)> + '_ {
+        suppressed_errors(&self.suppressed)
+    }
+
+    /// Yields the status given by the OCSP response for each certificate in the path, in the
+    /// order of [`Self::revocation_statuses()`], as checked by
+    /// [`crate::Verifier::ocsp_responses()`].
+    ///
+    /// A certificate has no status if no response was given for it, as
+    /// [`crate::UnknownStatusPolicy::Allow`] accepts. None has for the paths passed to
+    /// `verify_path`, which are checked before the responses are.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub fn ocsp_statuses(&self) -> impl Iterator<Item = Option<OcspCertStatus>> + '_ {
+        self.ocsp[..self.intermediates.as_ref().len() + 1]
+            .iter()
+            .copied()
+    }
+
+    /// Verifies `responses` as described by [`crate::Verifier::ocsp_responses()`], recording
+    /// the status each gives.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub(crate) fn verify_ocsp_responses(
+        &mut self,
+        responses: OcspResponses<'_>,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        budget: &mut Budget,
+    ) -> Result<(), DetailedError<'p>> {
+        self.ocsp = responses.verify(
+            self.end_entity,
+            &self.intermediates,
+            Some(ocsp::Issuer::from(self.anchor)),
+            supported_sig_algs,
+            budget,
+        )?;
+        Ok(())
+    }
+
+    /// Yields the notBefore and notAfter times of each certificate in the path: the end-entity
+    /// certificate first, then each intermediate certificate from the end-entity's issuer
+    /// upwards.
+    ///
+    /// This is for evaluating them separately after verifying with
+    /// [`crate::Verifier::dangerous_ignore_validity_periods()`].
+    pub fn validity_periods(&self) -> impl Iterator<Item = (UnixTime, UnixTime)> + '_ {
+        let intermediates = self.intermediates.as_ref().iter().flatten();
+        // Path building read every validity period, so none is skipped.
+        core::iter::once(&**self.end_entity)
+            .chain(intermediates)
+            .filter_map(|cert| cert.validity().ok())
+    }
+
     /// Yields a (double-ended) iterator over the intermediate certificates in this path.
     pub fn intermediate_certificates(&'p self) -> IntermediateIterator<'p> {
         IntermediateIterator {
@@ -204,15 +680,368 @@ impl<'p> VerifiedPath<'p> {
         }
     }
 
+    /// The certificate that issued the end-entity certificate, unless that is the trust anchor.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub(crate) fn end_entity_issuer(&self) -> Option<&Cert<'p>> {
+        self.intermediates.as_ref().first().and_then(Option::as_ref)
+    }
+
     /// Yields the end-entity certificate for this path.
     pub fn end_entity(&self) -> &'p EndEntityCert<'p> {
         self.end_entity
     }
 
     /// Yields the trust anchor for this path.
+    ///
+    /// This is a reference to one of the trust anchors supplied for verification, so
+    /// `core::ptr::eq()` tells which one it is.
     pub fn anchor(&self) -> &'p TrustAnchor<'p> {
         self.anchor
     }
+
+    /// Returns whether the SHA-256 digest of the subjectPublicKeyInfo of any certificate in the
+    /// path, or of the trust anchor, is one of `pins`.
+    ///
+    /// As with HPKP (RFC 7469), a pin can be satisfied by any certificate in the path, so
+    /// pinning an intermediate or a root keeps working across end-entity certificate renewals.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub fn matches_spki_pins(&self, pins: &[[u8; 32]]) -> bool {
+        if self.end_entity.matches_spki_pins(pins)
+            || self
+                .intermediate_certificates()
+                .any(|cert| cert.matches_spki_pins(pins))
+        {
+            return true;
+        }
+
+        // The trust anchor only has the value of its subjectPublicKeyInfo, so restore the
+        // SEQUENCE tag and length.
+        let spki = self.anchor.subject_public_key_info.as_ref();
+        let len = spki.len().to_be_bytes();
+        let significant = &len[len.iter().take_while(|byte| **byte == 0).count()..];
+        let mut header = [der::Tag::Sequence.into(), 0, 0, 0, 0];
+        let header = match (spki.len(), significant) {
+            (0..=0x7f, _) => {
+                header[1] = len[len.len() - 1];
+                &header[..2]
+            }
+            (_, [one]) => {
+                header[1..3].copy_from_slice(&[0x81, *one]);
+                &header[..3]
+            }
+            (_, [one, two]) => {
+                header[1..4].copy_from_slice(&[0x82, *one, *two]);
+                &header[..4]
+            }
+            (_, [one, two, three]) => {
+                header[1..5].copy_from_slice(&[0x83, *one, *two, *three]);
+                &header[..5]
+            }
+            _ => return false,
+        };
+        crate::cert::spki_pin_matches(header, spki, pins)
+    }
+
+    /// Returns `true` if this path relied on a cross-signature, rather than terminating at the
+    /// trust anchor that would have been preferred.
+    ///
+    /// This is the case if either:
+    ///
+    /// * An intermediate certificate in the path has the same subject as one of the supplied
+    ///   trust anchors other than [`Self::anchor()`]. That intermediate is a cross-signed
+    ///   version of a root that was itself trusted, but could not terminate the path.
+    /// * A certificate in the path names one of the supplied trust anchors as its issuer, but
+    ///   the path continues beyond it, or terminates at a later trust anchor with the same
+    ///   subject. Trust anchors are preferred in the order they were supplied, and a nearer
+    ///   one is preferred over a more distant one.
+    ///
+    /// Only names are compared: a trust anchor that could not have terminated the path because
+    /// it has a different key, or imposes name constraints the path violates, still counts.
+    pub fn is_cross_signed(&self) -> bool {
+        let intermediates = self.intermediates.as_ref().iter().flatten();
+        let anchors_named = |name: untrusted::Input<'p>| {
+            self.anchors.for_subject(name).iter().filter(move |anchor| {
+                public_values_eq(untrusted::Input::from(anchor.subject.as_ref()), name)
+            })
+        };
+
+        if intermediates.clone().any(|cert| {
+            anchors_named(cert.subject).any(|anchor| !core::ptr::eq(anchor, self.anchor))
+        }) {
+            return true;
+        }
+
+        // Every certificate but the last could have been issued by the path's trust anchor.
+        let mut certs = core::iter::once(&**self.end_entity).chain(intermediates);
+        let top = certs.next_back();
+        if certs.any(|cert| anchors_named(cert.issuer).next().is_some()) {
+            return true;
+        }
+
+        let preferred = top.and_then(|top| anchors_named(top.issuer).next());
+        !matches!(preferred, Some(preferred) if core::ptr::eq(preferred, self.anchor))
+    }
+
+    /// Returns the name constraints that were enforced on this path: those of the trust
+    /// anchor and of every intermediate certificate.
+    #[cfg(feature = "alloc")]
+    pub fn name_constraints(&self) -> Result<EnforcedNameConstraints<'p>, Error> {
+        let mut enforced = EnforcedNameConstraints::default();
+        let anchor: &'p TrustAnchor<'p> = self.anchor;
+        if let Some(constraints) = &anchor.name_constraints {
+            enforced.extend(
+                untrusted::Input::from(constraints.as_ref()),
+                ConstraintSource::TrustAnchor,
+            )?;
+        }
+
+        for (i, cert) in self.intermediates.as_ref().iter().enumerate() {
+            if let Some(constraints) = cert.as_ref().and_then(|cert| cert.name_constraints) {
+                enforced.extend(constraints, ConstraintSource::Intermediate(i))?;
+            }
+        }
+
+        Ok(enforced)
+    }
+
+    /// Returns the revocation endpoints of each certificate in this path: the end-entity
+    /// certificate first, then each intermediate certificate from the end-entity's issuer
+    /// upwards. The trust anchor has none.
+    ///
+    /// Nothing is fetched: these are only the URIs found in each certificate. See
+    /// [`Cert::ocsp_uris()`] for which ones are skipped.
+    #[cfg(feature = "alloc")]
+    pub fn revocation_endpoints(&self) -> Vec<RevocationEndpoints<'p>> {
+        let intermediates = self.intermediates.as_ref().iter().flatten();
+        core::iter::once(&**self.end_entity)
+            .chain(intermediates)
+            .map(|cert| RevocationEndpoints {
+                ocsp: cert.ocsp_uris().collect(),
+                crl_distribution_points: cert.crl_distribution_point_uris().collect(),
+            })
+            .collect()
+    }
+}
+
+/// Path from the end-entity certificate to a certificate whose issuer is unknown, as accepted
+/// by [`crate::Verifier::dangerous_verify_allowing_unknown_issuer()`].
+///
+/// Every certificate in the path but the topmost is signed by the one above it, and satisfies
+/// the name constraints of those above it. Nothing vouches for the topmost certificate.
+pub struct UnanchoredPath<'p> {
+    end_entity: &'p EndEntityCert<'p>,
+    intermediates: Intermediates<'p>,
+    /// See `UnanchoredPath::revocation_statuses()`.
+    revocation: [RevocationStatus; MAX_SUB_CA_COUNT + 1],
+    /// See `UnanchoredPath::suppressed_errors()`.
+    suppressed: [Option<Error>; MAX_SUB_CA_COUNT + 1],
+    /// See `UnanchoredPath::ocsp_statuses()`.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    ocsp: [Option<OcspCertStatus>; MAX_SUB_CA_COUNT + 1],
+    time: UnixTime,
+}
+
+impl<'p> UnanchoredPath<'p> {
+    /// The time the certificates in the path were verified as of, as for
+    /// `VerifiedPath::validation_time()`.
+    pub fn validation_time(&self) -> UnixTime {
+        self.time
+    }
+
+    /// Yields the revocation status of each certificate in the path, as
+    /// `VerifiedPath::revocation_statuses()` does.
+    ///
+    /// No CRL can be verified for the topmost certificate, so its status is
+    /// [`RevocationStatus::Unknown`] if checking it was required and
+    /// [`crate::UnknownStatusPolicy::Allow`] is set.
+    pub fn revocation_statuses(&self) -> impl Iterator<Item = RevocationStatus> + '_ {
+        self.revocation[..self.intermediates.as_ref().len() + 1]
+            .iter()
+            .copied()
+    }
+
+    /// Yields the errors suppressed as allowed by [`crate::Verifier::dangerous_allow_errors()`],
+    /// as `VerifiedPath::suppressed_errors()` does, followed by
+    /// [`Error::UnknownIssuer`] at the topmost certificate.
+    pub fn suppressed_errors(&self) -> impl Iterator<Item = (usize, Error)> + '_ {
+        let top = self.intermediates.as_ref().len();
+        suppressed_errors(&self.suppressed).chain(core::iter::once((top, Error::UnknownIssuer)))
+    }
+
+    /// Yields the status given by the OCSP response for each certificate in the path, as
+    /// `VerifiedPath::ocsp_statuses()` does.
+    ///
+    /// No response can be verified for the topmost certificate, so it has no status.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub fn ocsp_statuses(&self) -> impl Iterator<Item = Option<OcspCertStatus>> + '_ {
+        self.ocsp[..self.intermediates.as_ref().len() + 1]
+            .iter()
+            .copied()
+    }
+
+    /// Verifies `responses` as `VerifiedPath::verify_ocsp_responses()` does.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub(crate) fn verify_ocsp_responses(
+        &mut self,
+        responses: OcspResponses<'_>,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        budget: &mut Budget,
+    ) -> Result<(), DetailedError<'p>> {
+        self.ocsp = responses.verify(
+            self.end_entity,
+            &self.intermediates,
+            None,
+            supported_sig_algs,
+            budget,
+        )?;
+        Ok(())
+    }
+
+    /// Yields a (double-ended) iterator over the intermediate certificates in this path.
+    pub fn intermediate_certificates(&'p self) -> IntermediateIterator<'p> {
+        IntermediateIterator {
+            intermediates: self.intermediates.as_ref(),
+        }
+    }
+
+    /// The certificate that issued the end-entity certificate, if that is known.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub(crate) fn end_entity_issuer(&self) -> Option<&Cert<'p>> {
+        self.intermediates.as_ref().first().and_then(Option::as_ref)
+    }
+
+    /// Yields the end-entity certificate for this path.
+    pub fn end_entity(&self) -> &'p EndEntityCert<'p> {
+        self.end_entity
+    }
+}
+
+/// The OCSP responses given to [`crate::Verifier::ocsp_responses()`], and the time and clock
+/// skew to check them with.
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+#[derive(Clone, Copy)]
+pub(crate) struct OcspResponses<'a> {
+    pub(crate) responses: &'a [&'a [u8]],
+    pub(crate) status_policy: crate::UnknownStatusPolicy,
+    pub(crate) time: UnixTime,
+    pub(crate) clock_skew: Duration,
+}
+
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+impl OcspResponses<'_> {
+    fn verify<'p>(
+        &self,
+        end_entity: &'p EndEntityCert<'p>,
+        intermediates: &Intermediates<'p>,
+        top_issuer: Option<ocsp::Issuer<'_>>,
+        supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+        budget: &mut Budget,
+    ) -> Result<[Option<OcspCertStatus>; MAX_SUB_CA_COUNT + 1], DetailedError<'p>> {
+        let intermediates = intermediates.as_ref();
+        let cert = |depth: usize| match depth {
+            0 => Some(&**end_entity),
+            _ => intermediates.get(depth - 1).and_then(Option::as_ref),
+        };
+        ocsp::verify_responses(
+            end_entity,
+            intermediates,
+            top_issuer,
+            self.responses,
+            self.status_policy,
+            self.time,
+            self.clock_skew,
+            supported_sig_algs,
+            budget,
+        )
+        .map_err(|(depth, error)| DetailedError {
+            error,
+            cert: depth.and_then(|depth| {
+                Some(FailedCert {
+                    depth,
+                    der: cert(depth)?.der(),
+                })
+            }),
+        })
+    }
+}
+
+/// The validity period error, if any, of each certificate in the path from `end_entity`
+/// through `intermediates` at `time`. Path building was allowed to suppress all of them.
+fn validity_errors(
+    end_entity: &Cert<'_>,
+    intermediates: &[Option<Cert<'_>>],
+    time: UnixTime,
+    clock_skew: Duration,
+) -> [Option<Error>; MAX_SUB_CA_COUNT + 1] {
+    let mut errors = [None; MAX_SUB_CA_COUNT + 1];
+    let certs = core::iter::once(end_entity).chain(intermediates.iter().flatten());
+    for (error, cert) in errors.iter_mut().zip(certs) {
+        *error = cert
+            .validity
+            .read_all(Error::BadDer, |value| {
+                check_validity(value, time, clock_skew)
+            })
+            .err();
+    }
+    errors
+}
+
+/// Yields each of `suppressed` with the depth of the certificate it was found at.
+fn suppressed_errors(
+    suppressed: &[Option<Error>; MAX_SUB_CA_COUNT + 1],
+) -> impl Iterator<Item = (usize, Error)> + '_ {
+    suppressed
+        .iter()
+        .enumerate()
+        .filter_map(|(depth, err)| err.map(|err| (depth, err)))
+}
+
+/// The revocation endpoints of one certificate in a verified path, as returned by
+/// `VerifiedPath::revocation_endpoints()`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RevocationEndpoints<'a> {
+    /// The OCSP responder URIs from the authorityInfoAccess extension.
+    pub ocsp: Vec<&'a str>,
+    /// The URIs from the cRLDistributionPoints extension.
+    pub crl_distribution_points: Vec<&'a str>,
+}
+
+/// A verification error, and the certificate in the path it was found at, as returned by
+/// [`crate::Verifier::verify_detailed()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetailedError<'p> {
+    /// The error, as [`crate::Verifier::verify()`] would have returned it.
+    pub error: Error,
+    /// The certificate the error was found at.
+    ///
+    /// This is `None` for errors that don't concern one certificate: those returned by a
+    /// `verify_path` callback, fatal errors such as [`Error::MaximumPathBuildCallsExceeded`],
+    /// [`Error::KeyIdentifierMismatch`], which concerns a pair of certificates, and
+    /// [`Error::SurplusOcspResponses`].
+    pub cert: Option<FailedCert<'p>>,
+}
+
+impl From<DetailedError<'_>> for Error {
+    fn from(err: DetailedError<'_>) -> Self {
+        err.error
+    }
+}
+
+/// The certificate a [`DetailedError`] was found at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailedCert<'p> {
+    /// The position of the certificate, where the end-entity certificate is at depth 0, its
+    /// issuer at depth 1, and so on.
+    ///
+    /// Path building may try several candidate paths, so the certificate is not necessarily
+    /// in the path that came closest to verifying; it is where the most specific error was
+    /// found. For [`Error::UnknownIssuer`] it is the certificate whose issuer could not be
+    /// found. The trust anchor is never reported: an error in a signature made by the trust
+    /// anchor is reported at the certificate it signed.
+    pub depth: usize,
+    /// The DER encoding of the certificate.
+    pub der: CertificateDer<'p>,
 }
 
 /// Iterator over a path's intermediate certificates.
@@ -267,20 +1096,26 @@ impl<'a> AsRef<[Option<Cert<'a>>]> for Intermediates<'a> {
     }
 }
 
+/// Checks the name constraints of the trust anchor and of every CA certificate in the path.
+///
+/// Each set of constraints applies to every certificate below it, the subjects of subordinate
+/// CAs as well as the end-entity certificate's names, so each certificate must satisfy the
+/// constraints of all of its issuers (RFC 5280 section 6.1.3).
 fn check_signed_chain_name_constraints(
     path: &PathNode<'_>,
-    trust_anchor: &TrustAnchor,
+    trust_anchor: Option<&TrustAnchor>,
+    subject_identities: bool,
     budget: &mut Budget,
-) -> Result<(), ControlFlow<Error, Error>> {
+) -> Result<(), (usize, ControlFlow<Error, Error>)> {
     let mut name_constraints = trust_anchor
-        .name_constraints
-        .as_ref()
+        .and_then(|anchor| anchor.name_constraints.as_ref())
         .map(|der| untrusted::Input::from(der.as_ref()));
 
     for path in path.iter() {
         untrusted::read_all_optional(name_constraints, Error::BadDer, |value| {
-            subject_name::check_name_constraints(value, &path, budget)
-        })?;
+            subject_name::check_name_constraints(value, &path, subject_identities, budget)
+        })
+        .map_err(|err| (path.index, err.into()))?;
 
         name_constraints = path.cert.name_constraints;
     }
@@ -288,10 +1123,11 @@ fn check_signed_chain_name_constraints(
     Ok(())
 }
 
+#[derive(Clone, Copy)]
 pub(crate) struct Budget {
-    signatures: usize,
-    build_chain_calls: usize,
-    name_constraint_comparisons: usize,
+    pub(crate) signatures: usize,
+    pub(crate) build_chain_calls: usize,
+    pub(crate) name_constraint_comparisons: usize,
 }
 
 impl Budget {
@@ -343,78 +1179,237 @@ impl Default for Budget {
     }
 }
 
+/// `time` is `None` if an [`InspectionHook`] overrode a validity period error, if one was
+/// suppressed, or if validity periods are ignored. The validity period must then only be
+/// well-formed.
 fn check_issuer_independent_properties(
-    cert: &Cert,
-    time: UnixTime,
-    role: Role,
+    node: &PathNode<'_>,
+    time: Option<UnixTime>,
+    clock_skew: Duration,
     sub_ca_count: usize,
     eku: ExtendedKeyUsage,
+    policy: &VerificationPolicy,
+    ignored_extensions: &[&[u8]],
 ) -> Result<(), Error> {
     // TODO: check_distrust(trust_anchor_subject, trust_anchor_spki)?;
     // TODO: Check signature algorithm like mozilla::pkix.
     // TODO: Check SPKI like mozilla::pkix.
     // TODO: check for active distrust like mozilla::pkix.
 
-    // For cert validation, we ignore the KeyUsage extension. For CA
-    // certificates, BasicConstraints.cA makes KeyUsage redundant. Firefox
-    // and other common browsers do not check KeyUsage for end-entities,
-    // though it would be kind of nice to ensure that a KeyUsage without
-    // the keyEncipherment bit could not be used for RSA key exchange.
+    let inputs = CertCheckInputs {
+        cert: node.cert,
+        role: node.role(),
+        depth: node.index,
+        time,
+        clock_skew,
+        sub_ca_count,
+        eku,
+        policy,
+        ignored_extensions,
+    };
+    CertCheck::ALL
+        .iter()
+        .try_for_each(|check| check.run(&inputs))
+}
 
-    cert.validity
-        .read_all(Error::BadDer, |value| check_validity(value, time))?;
-    untrusted::read_all_optional(cert.basic_constraints, Error::BadDer, |value| {
-        check_basic_constraints(value, role, sub_ca_count)
-    })?;
-    untrusted::read_all_optional(cert.eku, Error::BadDer, |value| eku.check(value))?;
+/// The results of those checks made by [`check_issuer_independent_properties()`] on an
+/// end-entity certificate that depend on neither the verification time nor its issuer.
+///
+/// Unlike verification, this doesn't stop at the first failure.
+#[cfg(feature = "alloc")]
+pub(crate) fn end_entity_structural_checks(
+    cert: &Cert<'_>,
+    usage: KeyUsage,
+    policy: &VerificationPolicy,
+) -> [Result<(), Error>; CertCheck::ALL.len()] {
+    let inputs = CertCheckInputs {
+        cert,
+        role: Role::EndEntity,
+        depth: 0,
+        time: None,
+        clock_skew: Duration::ZERO,
+        sub_ca_count: 0,
+        eku: usage.inner,
+        policy,
+        ignored_extensions: &[],
+    };
+    CertCheck::ALL.map(|check| check.run(&inputs))
+}
 
-    Ok(())
+/// What a [`CertCheck`] is made with.
+struct CertCheckInputs<'a> {
+    cert: &'a Cert<'a>,
+    role: Role,
+    /// The depth of `cert` in the path, 0 for the end-entity certificate.
+    depth: usize,
+    /// See `check_issuer_independent_properties()`.
+    time: Option<UnixTime>,
+    clock_skew: Duration,
+    sub_ca_count: usize,
+    eku: ExtendedKeyUsage,
+    policy: &'a VerificationPolicy,
+    ignored_extensions: &'a [&'a [u8]],
 }
 
-// https://tools.ietf.org/html/rfc5280#section-4.1.2.5
-fn check_validity(input: &mut untrusted::Reader, time: UnixTime) -> Result<(), Error> {
-    let not_before = UnixTime::from_der(input)?;
-    let not_after = UnixTime::from_der(input)?;
+/// One of the checks of a certificate's own properties, which depend on neither its issuer
+/// nor the rest of the path.
+#[derive(Clone, Copy)]
+enum CertCheck {
+    CriticalExtensions,
+    Validity,
+    MaxValidity,
+    SerialNumber,
+    BasicConstraints,
+    ExtendedKeyUsage,
+    EkuCriticality,
+    EndEntityKeyUsage,
+    IssuerKeyUsage,
+    EmptySubjectWithoutCriticalSan,
+    MalformedDnsNameInSan,
+    MalformedOtherNameInSan,
+    EmptySubjectAltName,
+    CaConstraintsCritical,
+    UniqueIds,
+}
 
-    if not_before > not_after {
-        return Err(Error::InvalidCertValidity);
+impl CertCheck {
+    /// Every check, in the order verification makes them.
+    const ALL: [Self; 15] = [
+        Self::CriticalExtensions,
+        Self::Validity,
+        Self::MaxValidity,
+        Self::SerialNumber,
+        Self::BasicConstraints,
+        Self::ExtendedKeyUsage,
+        Self::EkuCriticality,
+        Self::EndEntityKeyUsage,
+        Self::IssuerKeyUsage,
+        Self::EmptySubjectWithoutCriticalSan,
+        Self::MalformedDnsNameInSan,
+        Self::MalformedOtherNameInSan,
+        Self::EmptySubjectAltName,
+        Self::CaConstraintsCritical,
+        Self::UniqueIds,
+    ];
+
+    /// Makes this check of `inputs.cert`. Checks that don't apply to its role, or that only
+    /// `VerificationPolicy::strict` makes, pass otherwise.
+    fn run(self, inputs: &CertCheckInputs<'_>) -> Result<(), Error> {
+        let CertCheckInputs { cert, role, .. } = *inputs;
+        let policy = inputs.policy;
+        // Only parse what strict mode requires.
+        let strict = |failed: &dyn Fn() -> bool, err: Error| match policy.strict && failed() {
+            true => Err(err),
+            false => Ok(()),
+        };
+
+        match self {
+            Self::CriticalExtensions => cert.check_critical_extensions(inputs.ignored_extensions),
+            Self::Validity => cert
+                .validity
+                .read_all(Error::BadDer, |value| match inputs.time {
+                    Some(time) => check_validity(value, time, inputs.clock_skew),
+                    None => read_validity(value).map(|_| ()),
+                }),
+            Self::MaxValidity => policy.max_validity.check(cert.validity, inputs.depth),
+            Self::SerialNumber => policy.serial_number.check(cert.serial),
+            Self::BasicConstraints => {
+                check_basic_constraints(cert.basic_constraints()?, role, inputs.sub_ca_count)
+            }
+            Self::ExtendedKeyUsage => {
+                untrusted::read_all_optional(cert.eku, Error::BadDer, |value| {
+                    inputs.eku.check(value)
+                })
+            }
+            Self::EkuCriticality if role == Role::EndEntity => policy
+                .eku_criticality
+                .check(cert.extended_key_usage_critical()),
+            Self::EndEntityKeyUsage if role == Role::EndEntity => {
+                policy.end_entity_key_usage.check(cert.key_usage)
+            }
+            // https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.3: "If the keyUsage extension
+            // is present, then the subject public key MUST NOT be used to verify signatures on
+            // certificates or CRLs unless the corresponding keyCertSign or cRLSign bit is set."
+            Self::IssuerKeyUsage if role == Role::Issuer => {
+                KeyUsageMode::CertSign.check(cert.key_usage)
+            }
+            Self::EkuCriticality | Self::EndEntityKeyUsage | Self::IssuerKeyUsage => Ok(()),
+            Self::EmptySubjectWithoutCriticalSan => strict(
+                &|| cert.has_empty_subject_without_critical_san(),
+                Error::EmptySubjectWithoutCriticalSan,
+            ),
+            Self::MalformedDnsNameInSan => strict(
+                &|| cert.has_malformed_dns_name_in_san(),
+                Error::MalformedDnsNameInSan,
+            ),
+            Self::MalformedOtherNameInSan => strict(
+                &|| cert.has_malformed_other_name_in_san(),
+                Error::MalformedOtherNameInSan,
+            ),
+            Self::EmptySubjectAltName => strict(
+                &|| cert.has_empty_subject_alt_name(),
+                Error::EmptySubjectAltName,
+            ),
+            // The CA/Browser Forum Baseline Requirements (section 7.1.2) require that the
+            // basicConstraints extension of CA certificates be marked critical. Its presence with
+            // cA=TRUE has already been checked by `Self::BasicConstraints`.
+            Self::CaConstraintsCritical => strict(
+                &|| role == Role::Issuer && !cert.basic_constraints_critical,
+                Error::CaConstraintsNotCritical {
+                    depth: inputs.depth,
+                },
+            ),
+            // https://www.rfc-editor.org/rfc/rfc5280#section-4.1.2.8: "CAs conforming to this
+            // profile MUST NOT generate certificates with unique identifiers."
+            Self::UniqueIds => strict(
+                &|| cert.has_unique_ids(),
+                Error::DeprecatedUniqueIdentifiersPresent,
+            ),
+        }
     }
-    if time < not_before {
-        return Err(Error::CertNotValidYet);
+}
+
+// https://tools.ietf.org/html/rfc5280#section-4.1.2.5
+//
+// The validity period is widened by `clock_skew` at both ends.
+pub(crate) fn check_validity(
+    input: &mut untrusted::Reader,
+    time: UnixTime,
+    clock_skew: Duration,
+) -> Result<(), Error> {
+    let (not_before, not_after) = read_validity(input)?;
+    let (earliest, latest) = skewed(time, clock_skew);
+    if latest < not_before {
+        return Err(Error::CertNotValidYet { time, not_before });
     }
-    if time > not_after {
-        return Err(Error::CertExpired);
+    if earliest > not_after {
+        return Err(Error::CertExpired { time, not_after });
     }
 
-    // TODO: mozilla::pkix allows the TrustDomain to check not_before and
-    // not_after, to enforce things like a maximum validity period. We should
-    // do something similar.
-
     Ok(())
 }
 
+/// Reads the notBefore and notAfter times of a validity period, which must not be inverted.
+fn read_validity(input: &mut untrusted::Reader) -> Result<(UnixTime, UnixTime), Error> {
+    let not_before = UnixTime::from_der(input)?;
+    let not_after = UnixTime::from_der(input)?;
+    match not_before > not_after {
+        true => Err(Error::InvalidValidityPeriod),
+        false => Ok((not_before, not_after)),
+    }
+}
+
 // https://tools.ietf.org/html/rfc5280#section-4.2.1.9
 fn check_basic_constraints(
-    input: Option<&mut untrusted::Reader>,
+    constraints: Option<BasicConstraints>,
     role: Role,
     sub_ca_count: usize,
 ) -> Result<(), Error> {
-    let (is_ca, path_len_constraint) = match input {
-        Some(input) => {
-            let is_ca = bool::from_der(input)?;
-
-            // https://bugzilla.mozilla.org/show_bug.cgi?id=985025: RFC 5280
-            // says that a certificate must not have pathLenConstraint unless
-            // it is a CA certificate, but some real-world end-entity
-            // certificates have pathLenConstraint.
-            let path_len_constraint = if !input.at_end() {
-                Some(usize::from(u8::from_der(input)?))
-            } else {
-                None
-            };
-
-            (is_ca, path_len_constraint)
-        }
+    let (is_ca, path_len_constraint) = match constraints {
+        Some(constraints) => (
+            constraints.is_ca,
+            constraints.path_len_constraint.map(usize::from),
+        ),
         None => (false, None),
     };
 
@@ -428,6 +1423,373 @@ fn check_basic_constraints(
     }
 }
 
+// https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.3
+#[repr(u8)]
+#[derive(Clone, Copy)]
+pub(crate) enum KeyUsageMode {
+    DigitalSignature = 0,
+    // ContentCommitment = 1,
+    // KeyEncipherment = 2,
+    // DataEncipherment = 3,
+    // KeyAgreement = 4,
+    CertSign = 5,
+    CrlSign = 6,
+    // EncipherOnly = 7,
+    // DecipherOnly = 8,
+}
+
+impl KeyUsageMode {
+    // https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.3
+    pub(crate) fn check(self, input: Option<untrusted::Input>) -> Result<(), Error> {
+        let bit_string = match input {
+            Some(input) => {
+                der::expect_tag(&mut untrusted::Reader::new(input), der::Tag::BitString)?
+            }
+            // While RFC 5280 requires KeyUsage be present, historically the absence of a KeyUsage
+            // has been treated as "Any Usage". We follow that convention here and assume the absence
+            // of KeyUsage implies the required_ku_bit_if_present we're checking for.
+            None => return Ok(()),
+        };
+
+        let flags = der::bit_string_flags(bit_string)?;
+        #[allow(clippy::as_conversions)] // u8 always fits in usize.
+        match flags.bit_set(self as usize) {
+            true => Ok(()),
+            false => Err(match self {
+                Self::DigitalSignature => Error::EndEntityKeyUsageInvalid,
+                Self::CertSign => Error::IssuerKeyUsageInvalid,
+                Self::CrlSign => Error::IssuerNotCrlSigner,
+            }),
+        }
+    }
+}
+
+/// Additional checks to perform when building a path, beyond those required by RFC 5280.
+///
+/// The default policy performs no additional checks. A [`VerificationPolicy::strict()`] policy
+/// rejects certificates exhibiting any of the problems described by [`crate::Lint`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct VerificationPolicy {
+    strict: bool,
+    eku_criticality: EkuCriticality,
+    end_entity_key_usage: EndEntityKeyUsage,
+    max_validity: MaxValidity,
+    serial_number: SerialNumberPolicy,
+    subject_name_constraints: bool,
+}
+
+impl VerificationPolicy {
+    /// Construct a [`VerificationPolicy`] that rejects likely-misissued certificates.
+    ///
+    /// In addition to the default checks, each certificate in the path must satisfy:
+    ///
+    /// * If the subject is empty, the subjectAltName extension must be present and critical
+    ///   ([`Error::EmptySubjectWithoutCriticalSan`]).
+    /// * The subjectAltName extension must not contain empty or whitespace-only dNSNames
+    ///   ([`Error::MalformedDnsNameInSan`]).
+    /// * Each otherName in the subjectAltName extension must consist of a `type-id` and a
+    ///   single DER-encoded `value` ([`Error::MalformedOtherNameInSan`]).
+    /// * The subjectAltName extension, if present, must contain at least one name
+    ///   ([`Error::EmptySubjectAltName`]).
+    /// * If the certificate is used as a CA, its basicConstraints extension must be marked
+    ///   critical ([`Error::CaConstraintsNotCritical`]).
+    /// * Supplied intermediates must be version 3 certificates
+    ///   ([`Error::UnsupportedCertVersionForCa`]).
+    /// * The authorityKeyIdentifier keyIdentifier of each certificate, if present, must match
+    ///   the subjectKeyIdentifier of its issuer, if present ([`Error::KeyIdentifierMismatch`]).
+    /// * The deprecated issuerUniqueID and subjectUniqueID fields must be absent
+    ///   ([`Error::DeprecatedUniqueIdentifiersPresent`]).
+    pub const fn strict() -> Self {
+        Self {
+            strict: true,
+            eku_criticality: EkuCriticality::Any,
+            end_entity_key_usage: EndEntityKeyUsage::RequireDigitalSignature,
+            max_validity: MaxValidity::new(),
+            serial_number: SerialNumberPolicy::new(),
+            subject_name_constraints: false,
+        }
+    }
+
+    /// Require the extendedKeyUsage extension of the end-entity certificate, if present, to
+    /// have the given criticality.
+    pub const fn with_eku_criticality(mut self, eku_criticality: EkuCriticality) -> Self {
+        self.eku_criticality = eku_criticality;
+        self
+    }
+
+    /// Choose which bits the keyUsage extension of the end-entity certificate, if present,
+    /// must assert.
+    pub const fn with_end_entity_key_usage(mut self, key_usage: EndEntityKeyUsage) -> Self {
+        self.end_entity_key_usage = key_usage;
+        self
+    }
+
+    /// Limit the validity period of the certificates in the path, other than the trust
+    /// anchor, to `max_validity`.
+    pub const fn with_max_validity(mut self, max_validity: MaxValidity) -> Self {
+        self.max_validity = max_validity;
+        self
+    }
+
+    /// Constrain the serial numbers of the certificates in the path. By default, and in a
+    /// [`VerificationPolicy::strict()`] policy, any serial number is accepted.
+    pub const fn with_serial_number_policy(mut self, serial_number: SerialNumberPolicy) -> Self {
+        self.serial_number = serial_number;
+        self
+    }
+
+    /// Also apply name constraints to the names in the end-entity certificate's subject that
+    /// other validators might take for its identity, rejecting violations with
+    /// [`Error::NameConstraintViolation`].
+    ///
+    /// These are a subject commonName that is an IP address or looks like a DNS name, checked
+    /// against iPAddress and dNSName constraints, and each emailAddress attribute, checked
+    /// against rfc822Name constraints. webpki itself never matches a name against the subject
+    /// commonName, so by default only the subjectAltName extension and the subject as a
+    /// whole, as a directoryName, are constrained. This closes the gap for applications that
+    /// pass the certificate on to validators that do.
+    pub const fn with_subject_name_constraints(mut self, enforce: bool) -> Self {
+        self.subject_name_constraints = enforce;
+        self
+    }
+}
+
+/// Upper limits on the length of the validity period of the certificates in a path, by their
+/// position in it. See [`VerificationPolicy::with_max_validity()`].
+///
+/// The length of a validity period is the time from its notBefore to its notAfter, and a
+/// certificate exceeding its limit is rejected with [`Error::ValidityPeriodTooLong`]. By
+/// default nothing is limited.
+///
+/// There is no limit for trust anchors: a [`TrustAnchor`] has no validity period, even one
+/// made from a certificate by [`crate::anchor_from_trusted_cert()`], which discards it. To
+/// limit the lifetime of roots, check their certificates with [`Cert::validity()`] before
+/// making trust anchors of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MaxValidity {
+    end_entity: Option<Duration>,
+    intermediate: Option<Duration>,
+}
+
+impl MaxValidity {
+    /// Construct a [`MaxValidity`] that limits nothing.
+    pub const fn new() -> Self {
+        Self {
+            end_entity: None,
+            intermediate: None,
+        }
+    }
+
+    /// Limit the validity period of the end-entity certificate to `max`.
+    pub const fn with_end_entity(mut self, max: Duration) -> Self {
+        self.end_entity = Some(max);
+        self
+    }
+
+    /// Limit the validity period of each intermediate certificate to `max`.
+    pub const fn with_intermediate(mut self, max: Duration) -> Self {
+        self.intermediate = Some(max);
+        self
+    }
+
+    fn check(&self, validity: untrusted::Input<'_>, depth: usize) -> Result<(), Error> {
+        let (role, max) = match depth {
+            0 => (CertRole::EndEntity, self.end_entity),
+            _ => (CertRole::Intermediate, self.intermediate),
+        };
+        let max = match max {
+            Some(max) => max,
+            None => return Ok(()),
+        };
+
+        let (not_before, not_after) = validity.read_all(Error::BadDer, |value| {
+            Ok((UnixTime::from_der(value)?, UnixTime::from_der(value)?))
+        })?;
+        match not_after.as_secs().saturating_sub(not_before.as_secs()) > max.as_secs() {
+            true => Err(Error::ValidityPeriodTooLong { depth, role }),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Constraints on the serial numbers of the certificates in a path. See
+/// [`VerificationPolicy::with_serial_number_policy()`].
+///
+/// Certificates are parsed whatever their serial number, which is available from
+/// [`Cert::serial()`]. By default nothing is constrained: RFC 5280 section 4.1.2.2 asks
+/// certificate users to "gracefully handle" negative and zero serial numbers, and some
+/// issuers use serial numbers longer than the 20 octets it permits. Trust anchors have no
+/// serial number, so are not constrained.
+///
+/// The CA/Browser Forum Baseline Requirements (section 7.1) also require serial numbers to
+/// contain at least 64 bits of output from a CSPRNG. That can't be told from a single
+/// certificate, so isn't checked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SerialNumberPolicy {
+    require_positive: bool,
+    limit_length: bool,
+}
+
+impl SerialNumberPolicy {
+    /// Construct a [`SerialNumberPolicy`] that accepts any serial number.
+    pub const fn new() -> Self {
+        Self {
+            require_positive: false,
+            limit_length: false,
+        }
+    }
+
+    /// Construct a [`SerialNumberPolicy`] enforcing both the requirements of RFC 5280 and the
+    /// CA/Browser Forum Baseline Requirements that can be checked: serial numbers must be
+    /// positive and at most 20 octets long.
+    pub const fn baseline_requirements() -> Self {
+        Self::new().require_positive().limit_length()
+    }
+
+    /// Reject serial numbers that are negative or zero with [`Error::NonPositiveSerialNumber`].
+    pub const fn require_positive(mut self) -> Self {
+        self.require_positive = true;
+        self
+    }
+
+    /// Reject serial numbers longer than 20 octets with [`Error::SerialNumberTooLong`].
+    ///
+    /// The length is that of the INTEGER's DER encoding, including any leading zero octet, so
+    /// a positive serial number can have at most 159 significant bits.
+    pub const fn limit_length(mut self) -> Self {
+        self.limit_length = true;
+        self
+    }
+
+    fn check(&self, serial: untrusted::Input<'_>) -> Result<(), Error> {
+        let serial = serial.as_slice_less_safe();
+        if self.require_positive {
+            match serial.first() {
+                Some(first) if first & 0x80 != 0 => return Err(Error::NonPositiveSerialNumber),
+                _ if serial.iter().all(|byte| *byte == 0) => {
+                    return Err(Error::NonPositiveSerialNumber)
+                }
+                _ => {}
+            }
+        }
+        if self.limit_length && serial.len() > 20 {
+            return Err(Error::SerialNumberTooLong);
+        }
+        Ok(())
+    }
+}
+
+/// The criticality required of an end-entity certificate's extendedKeyUsage extension.
+///
+/// Certificates without an extendedKeyUsage extension are not affected. Violations are reported
+/// as [`Error::EkuCriticalityMismatch`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EkuCriticality {
+    /// The extension may be critical or non-critical. This is the default.
+    Any,
+    /// The extension must be marked critical.
+    RequireCritical,
+    /// The extension must not be marked critical.
+    RequireNonCritical,
+}
+
+impl EkuCriticality {
+    fn check(self, critical: Option<bool>) -> Result<(), Error> {
+        match (self, critical) {
+            (Self::RequireCritical, Some(false)) | (Self::RequireNonCritical, Some(true)) => {
+                Err(Error::EkuCriticalityMismatch)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for EkuCriticality {
+    fn default() -> Self {
+        Self::Any
+    }
+}
+
+/// The bits required of an end-entity certificate's keyUsage extension.
+///
+/// Certificates without a keyUsage extension are not affected. Every [`KeyUsage`] is for
+/// verifying signatures made with the end-entity key, so by default the digitalSignature bit
+/// is required. Violations are reported as [`Error::EndEntityKeyUsageInvalid`].
+///
+/// Certificates used as issuers must always assert keyCertSign, as required by
+/// <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.3>; violations are reported as
+/// [`Error::IssuerKeyUsageInvalid`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EndEntityKeyUsage {
+    /// The extension must assert digitalSignature. This is the default.
+    RequireDigitalSignature,
+    /// The extension is not checked, for legacy PKIs whose end-entity certificates assert
+    /// only keyEncipherment, say.
+    Ignore,
+}
+
+impl EndEntityKeyUsage {
+    fn check(self, key_usage: Option<untrusted::Input<'_>>) -> Result<(), Error> {
+        match self {
+            Self::RequireDigitalSignature => KeyUsageMode::DigitalSignature.check(key_usage),
+            Self::Ignore => Ok(()),
+        }
+    }
+}
+
+impl Default for EndEntityKeyUsage {
+    fn default() -> Self {
+        Self::RequireDigitalSignature
+    }
+}
+
+/// A callback invoked for each certificate considered during path building, with the result
+/// of checking the properties that don't depend on its issuer.
+///
+/// Like OpenSSL's verify callback, this can be used to log certificates as they are checked,
+/// and to accept a certificate despite an error. Only the errors listed on
+/// [`HookDecision::OverrideAccept`] can be overridden; a hook can never accept a certificate
+/// whose signature, name constraints or revocation status could not be verified.
+pub trait InspectionHook {
+    /// Called with the `depth` of `cert` in the candidate path (0 for the end-entity) and
+    /// the `result` of checking it.
+    ///
+    /// This may be called more than once for the same certificate, if it is considered in
+    /// more than one candidate path.
+    fn on_certificate(
+        &self,
+        depth: usize,
+        cert: &Cert<'_>,
+        result: Result<(), Error>,
+    ) -> HookDecision;
+}
+
+/// What an [`InspectionHook`] decided about a certificate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HookDecision {
+    /// Proceed with the original result.
+    Continue,
+    /// Accept the certificate despite an [`Error::CertExpired`] or [`Error::CertNotValidYet`]
+    /// error. The certificate's remaining checks are still performed.
+    ///
+    /// For any other result this is the same as [`HookDecision::Continue`].
+    OverrideAccept,
+    /// Reject the certificate with the given error. Path building continues with other
+    /// candidates unless the error is fatal.
+    Reject(Error),
+}
+
+/// Errors an [`InspectionHook`] may override.
+fn overridable(err: Error) -> bool {
+    matches!(
+        err,
+        Error::CertExpired { .. } | Error::CertNotValidYet { .. }
+    )
+}
+
 /// The expected key usage of a certificate.
 ///
 /// This type represents the expected key usage of an end entity certificate. Although for most
@@ -436,7 +1798,7 @@ fn check_basic_constraints(
 /// is present, the certificate MUST only be used for one of the purposes indicated.
 ///
 /// <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.12>
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct KeyUsage {
     inner: ExtendedKeyUsage,
 }
@@ -460,16 +1822,68 @@ impl KeyUsage {
         }
     }
 
+    /// Construct a new [`KeyUsage`] as appropriate for code signing.
+    ///
+    /// Like [`KeyUsage::server_auth()`], this does not require the certificate to specify the
+    /// eKU extension.
+    pub const fn code_signing() -> Self {
+        Self {
+            inner: ExtendedKeyUsage::RequiredIfPresent(EKU_CODE_SIGNING),
+        }
+    }
+
+    /// Construct a new [`KeyUsage`] as appropriate for email protection, such as S/MIME.
+    ///
+    /// Like [`KeyUsage::server_auth()`], this does not require the certificate to specify the
+    /// eKU extension, and it does not accept the TLS key purposes in its place.
+    pub const fn email_protection() -> Self {
+        Self {
+            inner: ExtendedKeyUsage::RequiredIfPresent(EKU_EMAIL_PROTECTION),
+        }
+    }
+
     /// Construct a new [`KeyUsage`] requiring a certificate to support the specified OID.
+    ///
+    /// `oid` is the DER-encoded value of the OBJECT IDENTIFIER, without its tag and length.
+    /// Certificates without the eKU extension are rejected. anyExtendedKeyUsage is given no
+    /// special meaning, for custom OIDs as for the built-in ones: a certificate listing only
+    /// anyExtendedKeyUsage does not satisfy the requirement.
     pub const fn required(oid: &'static [u8]) -> Self {
         Self {
             inner: ExtendedKeyUsage::Required(KeyPurposeId::new(oid)),
         }
     }
+
+    /// Construct a new [`KeyUsage`] requiring a certificate to support the specified OID if
+    /// it specifies the eKU extension.
+    ///
+    /// Like [`KeyUsage::required()`], but certificates without the eKU extension are accepted.
+    pub const fn required_if_present(oid: &'static [u8]) -> Self {
+        Self {
+            inner: ExtendedKeyUsage::RequiredIfPresent(KeyPurposeId::new(oid)),
+        }
+    }
+
+    /// Construct a new [`KeyUsage`] as appropriate for a delegated OCSP responder, which must
+    /// specify the eKU extension.
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc6960#section-4.2.2.2>
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub(crate) const fn ocsp_signing() -> Self {
+        Self {
+            inner: ExtendedKeyUsage::Required(EKU_OCSP_SIGNING),
+        }
+    }
+
+    /// Check the extended key usage of `cert` against this usage.
+    #[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+    pub(crate) fn check(&self, cert: &Cert<'_>) -> Result<(), Error> {
+        untrusted::read_all_optional(cert.eku, Error::BadDer, |value| self.inner.check(value))
+    }
 }
 
 /// Extended Key Usage (EKU) of a certificate.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 enum ExtendedKeyUsage {
     /// The certificate must contain the specified [`KeyPurposeId`] as EKU.
     Required(KeyPurposeId),
@@ -539,6 +1953,20 @@ impl PartialEq<Self> for KeyPurposeId {
 
 impl Eq for KeyPurposeId {}
 
+impl fmt::Debug for KeyPurposeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("KeyPurposeId")
+            .field(&self.oid_value.as_slice_less_safe())
+            .finish()
+    }
+}
+
+impl core::hash::Hash for KeyPurposeId {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.oid_value.as_slice_less_safe().hash(state)
+    }
+}
+
 // id-pkix            OBJECT IDENTIFIER ::= { 1 3 6 1 5 5 7 }
 // id-kp              OBJECT IDENTIFIER ::= { id-pkix 3 }
 
@@ -548,13 +1976,72 @@ const EKU_SERVER_AUTH: KeyPurposeId = KeyPurposeId::new(&oid!(1, 3, 6, 1, 5, 5,
 // id-kp-clientAuth   OBJECT IDENTIFIER ::= { id-kp 2 }
 const EKU_CLIENT_AUTH: KeyPurposeId = KeyPurposeId::new(&oid!(1, 3, 6, 1, 5, 5, 7, 3, 2));
 
-fn loop_while_non_fatal_error<'a, V: 'a>(
+// id-kp-codeSigning  OBJECT IDENTIFIER ::= { id-kp 3 }
+const EKU_CODE_SIGNING: KeyPurposeId = KeyPurposeId::new(&oid!(1, 3, 6, 1, 5, 5, 7, 3, 3));
+
+// id-kp-emailProtection  OBJECT IDENTIFIER ::= { id-kp 4 }
+const EKU_EMAIL_PROTECTION: KeyPurposeId = KeyPurposeId::new(&oid!(1, 3, 6, 1, 5, 5, 7, 3, 4));
+
+// id-kp-OCSPSigning  OBJECT IDENTIFIER ::= { id-kp 9 }
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+const EKU_OCSP_SIGNING: KeyPurposeId = KeyPurposeId::new(&oid!(1, 3, 6, 1, 5, 5, 7, 3, 9));
+
+/// Yields the item of `items` at `preferred`, if any, followed by the others in order.
+fn preferred_first<I: Iterator + Clone>(
+    items: I,
+    preferred: Option<usize>,
+) -> impl Iterator<Item = I::Item> {
+    let first = preferred.and_then(|i| items.clone().nth(i));
+    first.into_iter().chain(
+        items
+            .enumerate()
+            .filter(move |(i, _)| Some(*i) != preferred)
+            .map(|(_, item)| item),
+    )
+}
+
+/// Returns whether `key_id`, the keyIdentifier of an authorityKeyIdentifier, identifies the key
+/// of `spki`, the value of a subjectPublicKeyInfo, by one of the common methods: the SHA-1
+/// digest of the subjectPublicKey (RFC 5280 section 4.2.1.2), or its SHA-256 digest, whole or
+/// truncated to 160 bits (RFC 7093 section 2).
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+fn key_id_identifies(key_id: untrusted::Input<'_>, spki: untrusted::Input<'_>) -> bool {
+    #[cfg(all(feature = "aws_lc_rs", not(feature = "ring")))]
+    use aws_lc_rs::digest;
+    #[cfg(feature = "ring")]
+    use ring::digest;
+
+    let key = match spki.read_all(Error::BadDer, |spki| {
+        der::expect_tag(spki, der::Tag::Sequence)?; // algorithm
+        der::bit_string_with_no_unused_bits(spki)
+    }) {
+        Ok(key) => key.as_slice_less_safe(),
+        Err(_) => return false,
+    };
+
+    let key_id = key_id.as_slice_less_safe();
+    match key_id.len() {
+        20 => {
+            digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, key).as_ref() == key_id
+                || digest::digest(&digest::SHA256, key).as_ref()[..20] == *key_id
+        }
+        32 => digest::digest(&digest::SHA256, key).as_ref() == key_id,
+        _ => false,
+    }
+}
+
+#[cfg(not(any(feature = "ring", feature = "aws_lc_rs")))]
+fn key_id_identifies(_: untrusted::Input<'_>, _: untrusted::Input<'_>) -> bool {
+    false
+}
+
+fn loop_while_non_fatal_error<'a, V>(
     default_error: Error,
     values: V,
-    mut f: impl FnMut(V::Item) -> Result<&'a TrustAnchor<'a>, ControlFlow<Error, Error>>,
-) -> Result<&'a TrustAnchor<'a>, ControlFlow<Error, Error>>
+    mut f: impl FnMut(V::Item) -> Result<Option<&'a TrustAnchor<'a>>, ControlFlow<Error, Error>>,
+) -> Result<Option<&'a TrustAnchor<'a>>, ControlFlow<Error, Error>>
 where
-    V: IntoIterator,
+    V: IntoIterator + 'a,
 {
     let mut error = default_error;
     for v in values {
@@ -570,6 +2057,137 @@ where
     Err(error.into())
 }
 
+/// The intermediate certificates supplied for path building.
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+pub(crate) enum SuppliedIntermediates<'p> {
+    /// Parsed each time they're considered as an issuer.
+    Der(&'p [CertificateDer<'p>]),
+    /// Parsed by the caller.
+    Certs(&'p [Cert<'p>]),
+    /// Parsed once, when they were supplied. See `SuppliedIntermediates::parse()`.
+    #[cfg(feature = "alloc")]
+    Parsed(Vec<ParsedIntermediate<'p>>),
+    /// Parsed once, when they were supplied, without allocating. See
+    /// `SuppliedIntermediates::parse()`.
+    #[cfg(not(feature = "alloc"))]
+    Parsed {
+        certs: [Option<ParsedIntermediate<'p>>; MAX_SUB_CA_COUNT],
+        len: usize,
+        /// Whether more than `MAX_SUB_CA_COUNT` certificates were supplied.
+        overflowed: bool,
+    },
+}
+
+/// A supplied intermediate certificate, and the result of parsing it.
+type ParsedIntermediate<'p> = (&'p CertificateDer<'p>, Result<Cert<'p>, Error>);
+
+impl<'p> SuppliedIntermediates<'p> {
+    /// Parses each of `certs`, consuming the iterator once.
+    ///
+    /// All of them are kept, as for `SuppliedIntermediates::Der`: the work of path building
+    /// is bounded by its `Budget`, not by the number of certificates.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn parse(certs: impl IntoIterator<Item = &'p CertificateDer<'p>>) -> Self {
+        Self::Parsed(
+            certs
+                .into_iter()
+                .map(|cert_der| {
+                    (
+                        cert_der,
+                        Cert::from_der(untrusted::Input::from(cert_der.as_ref())),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Parses each of `certs`, consuming the iterator once.
+    ///
+    /// Without an allocator, at most `MAX_SUB_CA_COUNT` certificates are kept. If there are
+    /// more, path building fails with `Error::MaximumPathDepthExceeded`.
+    #[cfg(not(feature = "alloc"))]
+    pub(crate) fn parse(certs: impl IntoIterator<Item = &'p CertificateDer<'p>>) -> Self {
+        let mut parsed = <[_; MAX_SUB_CA_COUNT]>::default();
+        let mut len = 0;
+        let mut overflowed = false;
+        for cert_der in certs {
+            match parsed.get_mut(len) {
+                Some(slot) => {
+                    *slot = Some((
+                        cert_der,
+                        Cert::from_der(untrusted::Input::from(cert_der.as_ref())),
+                    ));
+                    len += 1;
+                }
+                None => {
+                    overflowed = true;
+                    break;
+                }
+            }
+        }
+
+        Self::Parsed {
+            certs: parsed,
+            len,
+            overflowed,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Der(certs) => certs.len(),
+            Self::Certs(certs) => certs.len(),
+            #[cfg(feature = "alloc")]
+            Self::Parsed(certs) => certs.len(),
+            #[cfg(not(feature = "alloc"))]
+            Self::Parsed { len, .. } => *len,
+        }
+    }
+
+    fn overflowed(&self) -> bool {
+        #[cfg(not(feature = "alloc"))]
+        if let Self::Parsed {
+            overflowed: true, ..
+        } = self
+        {
+            return true;
+        }
+
+        false
+    }
+
+    /// The certificate supplied at `idx` and the result of parsing it, if it was parsed when
+    /// supplied.
+    fn parsed(&self, idx: usize) -> Option<&ParsedIntermediate<'p>> {
+        match self {
+            Self::Der(_) | Self::Certs(_) => None,
+            #[cfg(feature = "alloc")]
+            Self::Parsed(certs) => Some(&certs[idx]),
+            #[cfg(not(feature = "alloc"))]
+            Self::Parsed { certs, .. } => certs[idx].as_ref(),
+        }
+    }
+
+    /// The DER encoding of the certificate at `idx`, which must be below `self.len()`.
+    fn der(&self, idx: usize) -> &'p CertificateDer<'p> {
+        match self {
+            Self::Der(certs) => &certs[idx],
+            Self::Certs(certs) => &certs[idx].der,
+            Self::Parsed { .. } => self.parsed(idx).unwrap().0,
+        }
+    }
+
+    /// The certificate at `idx`, which must be below `self.len()`.
+    fn cert(&self, idx: usize) -> Result<Cert<'p>, Error> {
+        match self {
+            Self::Der(certs) => Cert::from_der(untrusted::Input::from(certs[idx].as_ref())),
+            Self::Certs(certs) => Ok(certs[idx].clone()),
+            Self::Parsed { .. } => self.parsed(idx).unwrap().1.clone(),
+        }
+    }
+}
+
 /// A path for consideration in path building.
 ///
 /// This represents a partial path because it does not yet contain the trust anchor. It stores
@@ -641,13 +2259,13 @@ impl<'a> PartialPath<'a> {
     }
 }
 
-const MAX_SUB_CA_COUNT: usize = 6;
+pub(crate) const MAX_SUB_CA_COUNT: usize = 6;
 
 pub(crate) struct PathNode<'a> {
     /// The path we're iterating.
     path: &'a PartialPath<'a>,
     /// The index of the current node in the path (input for `path.get()`).
-    index: usize,
+    pub(crate) index: usize,
     /// The [`Cert`] at `index`.
     pub(crate) cert: &'a Cert<'a>,
 }
@@ -699,9 +2317,14 @@ pub(crate) enum Role {
 
 #[cfg(all(test, feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
 mod tests {
+    use pki_types::ServerName;
+
     use super::*;
-    use crate::test_utils::{issuer_params, make_end_entity, make_issuer};
-    use crate::trust_anchor::anchor_from_trusted_cert;
+    use crate::lint::Lint;
+    #[cfg(feature = "alloc")]
+    use crate::subject_name::{ConstraintBase, NameSubtree, SubtreeKind};
+    use crate::test_utils::{end_entity_params, issuer_params, make_end_entity, make_issuer};
+    use crate::trust_anchor::{anchor_from_trusted_cert, TrustAnchorSource};
 
     #[test]
     fn eku_key_purpose_id() {
@@ -709,6 +2332,39 @@ mod tests {
             .key_purpose_id_equals(EKU_SERVER_AUTH.oid_value))
     }
 
+    #[test]
+    fn key_usage_equality() {
+        static SERVER_AUTH: KeyUsage = KeyUsage::server_auth();
+        static CODE_SIGNING: KeyUsage = KeyUsage::required(&oid!(1, 3, 6, 1, 5, 5, 7, 3, 3));
+        const CODE_SIGNING_OID: &[u8] = &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x03];
+
+        assert_eq!(SERVER_AUTH, KeyUsage::server_auth());
+        assert_ne!(SERVER_AUTH, KeyUsage::client_auth());
+        // Usages are compared by OID value, not by address.
+        assert_eq!(CODE_SIGNING, KeyUsage::required(CODE_SIGNING_OID));
+        // Requiring an EKU is not the same as requiring it only if EKUs are present.
+        assert_ne!(
+            SERVER_AUTH,
+            KeyUsage::required(EKU_SERVER_AUTH.oid_value.as_slice_less_safe())
+        );
+
+        assert_eq!(
+            format!("{:?}", CODE_SIGNING),
+            "KeyUsage { inner: Required(KeyPurposeId([43, 6, 1, 5, 5, 7, 3, 3])) }"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn key_usage_as_map_key() {
+        let mut routes = std::collections::HashMap::new();
+        routes.insert(KeyUsage::server_auth(), "server");
+        routes.insert(KeyUsage::client_auth(), "client");
+        routes.insert(KeyUsage::server_auth(), "server again");
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[&KeyUsage::server_auth()], "server again");
+    }
+
     #[cfg(feature = "alloc")]
     enum ChainTrustAnchor {
         NotInChain,
@@ -776,7 +2432,7 @@ mod tests {
         let ca_cert = make_issuer(format!("Bogus Subject {chain_length}"));
         let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
         let anchor = anchor_from_trusted_cert(&ca_cert_der).unwrap();
-        let anchors = &[anchor.clone()];
+        let anchors = core::slice::from_ref(&anchor);
 
         let mut intermediates = Vec::with_capacity(chain_length);
         let mut issuer = ca_cert;
@@ -849,54 +2505,643 @@ mod tests {
     }
 
     #[test]
-    fn name_constraint_budget() {
-        // Issue a trust anchor that imposes name constraints. The constraint should match
-        // the end entity certificate SAN.
-        let mut ca_cert_params = issuer_params("Constrained Root");
-        ca_cert_params.name_constraints = Some(rcgen::NameConstraints {
-            permitted_subtrees: vec![rcgen::GeneralSubtree::DnsName(".com".into())],
-            excluded_subtrees: vec![],
-        });
-        let ca_cert = rcgen::Certificate::from_params(ca_cert_params).unwrap();
+    fn max_path_length() {
+        let ca_cert = make_issuer("Bogus Subject");
         let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
         let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
 
-        // Create a series of intermediate issuers. We'll only use one in the actual built path,
-        // helping demonstrate that the name constraint budget is not expended checking certificates
-        // that are not part of the path we compute.
-        const NUM_INTERMEDIATES: usize = 5;
-        let mut intermediates = Vec::with_capacity(NUM_INTERMEDIATES);
-        for i in 0..NUM_INTERMEDIATES {
-            intermediates.push(make_issuer(format!("Intermediate {i}")));
-        }
+        let intermediate = make_issuer("Bogus Intermediate");
+        let intermediates = &[CertificateDer::from(
+            intermediate.serialize_der_with_signer(&ca_cert).unwrap(),
+        )];
+        let ee_der = make_end_entity(&intermediate);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
 
-        // Each intermediate should be issued by the trust anchor.
-        let mut intermediates_der = Vec::with_capacity(NUM_INTERMEDIATES);
-        for intermediate in &intermediates {
-            intermediates_der.push(CertificateDer::from(
-                intermediate.serialize_der_with_signer(&ca_cert).unwrap(),
-            ));
-        }
+        let verifier = ee_cert
+            .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+            .intermediates(intermediates)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)));
+        assert!(verifier.clone().max_path_length(1).verify().is_ok());
+        assert!(matches!(
+            verifier.max_path_length(0).verify(),
+            Err(Error::MaximumPathDepthExceeded)
+        ));
+    }
 
-        // Create an end-entity cert that is issued by the last of the intermediates.
-        let ee_der = make_end_entity(intermediates.last().unwrap());
+    #[test]
+    fn max_signature_checks() {
+        let ca_cert = make_issuer("Bogus Subject");
+        let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
+
+        let intermediate = make_issuer("Bogus Intermediate");
+        let intermediates = &[CertificateDer::from(
+            intermediate.serialize_der_with_signer(&ca_cert).unwrap(),
+        )];
+        let ee_der = make_end_entity(&intermediate);
         let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
 
-        // We use a custom budget to make it easier to write a test, otherwise it is tricky to
-        // stuff enough names/constraints into the potential chains while staying within the path
-        // depth limit and the build chain call limit.
-        let passing_budget = Budget {
-            // One comparison against the intermediate's distinguished name.
-            // One comparison against the EE's distinguished name.
-            // One comparison against the EE's SAN.
-            //  = 3 total comparisons.
-            name_constraint_comparisons: 3,
-            ..Budget::default()
-        };
+        // The path needs two signatures verified.
+        let verifier = ee_cert
+            .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+            .intermediates(intermediates)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)));
+        assert!(verifier.clone().max_signature_checks(2).verify().is_ok());
+        assert!(matches!(
+            verifier.max_signature_checks(1).verify(),
+            Err(Error::MaximumSignatureChecksExceeded)
+        ));
+    }
 
-        // Validation should succeed with the name constraint comparison budget allocated above.
-        // This shows that we're not consuming budget on unused intermediates: we didn't budget
-        // enough comparisons for that to pass the overall chain building.
+    #[test]
+    fn duplicate_intermediates() {
+        let ca_cert = make_issuer("Bogus Subject");
+        let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
+
+        let intermediate = make_issuer("Bogus Intermediate");
+        let intermediate_der =
+            CertificateDer::from(intermediate.serialize_der_with_signer(&ca_cert).unwrap());
+        let ee_der = make_end_entity(&intermediate);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let mut intermediates = vec![intermediate_der; 5];
+        intermediates.push(ee_der.clone());
+
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+        let verifier = |anchors| {
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(&intermediates)
+                .at_time(time)
+                .max_signature_checks(2)
+        };
+        assert!(verifier(anchors).verify().is_ok());
+
+        // A trust anchor with the right name but the wrong key: each path fails after two
+        // signatures are verified, and only one path is tried.
+        let other_ca = make_issuer("Bogus Subject");
+        let other_ca_der = CertificateDer::from(other_ca.serialize_der().unwrap());
+        let other_anchors = &[anchor_from_trusted_cert(&other_ca_der).unwrap()];
+        assert_eq!(
+            verifier(other_anchors).verify().err(),
+            Some(Error::InvalidSignatureForPublicKey)
+        );
+
+        // Copies of the candidate that the authorityKeyIdentifier prefers, which is tried
+        // first, are skipped wherever they are. Both paths fail, after two signatures for the
+        // preferred intermediate and one for the other.
+        let other = make_issuer("Bogus Intermediate");
+        let other_der = CertificateDer::from(other.serialize_der_with_signer(&ca_cert).unwrap());
+        let mut params = end_entity_params(vec!["example.com".into()]);
+        params.use_authority_key_identifier_extension = true;
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der_with_signer(&intermediate)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let intermediate_der = &intermediates[0];
+        let intermediates = [
+            other_der.clone(),
+            intermediate_der.clone(),
+            other_der,
+            intermediate_der.clone(),
+            intermediate_der.clone(),
+        ];
+        assert_eq!(
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, other_anchors)
+                .intermediates(&intermediates)
+                .at_time(time)
+                .max_signature_checks(3)
+                .verify()
+                .err(),
+            Some(Error::InvalidSignatureForPublicKey)
+        );
+    }
+
+    #[test]
+    fn intermediates_from_iter() {
+        let ca_cert = make_issuer("Bogus Subject");
+        let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
+
+        let first = make_issuer("Bogus Intermediate 1");
+        let first_der = CertificateDer::from(first.serialize_der_with_signer(&ca_cert).unwrap());
+        let second = make_issuer("Bogus Intermediate 2");
+        let second_der = CertificateDer::from(second.serialize_der_with_signer(&first).unwrap());
+        let ee_der = make_end_entity(&second);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let malformed = CertificateDer::from(&[0x30, 0x00][..]);
+
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+        let consumed = Cell::new(0);
+        let intermediates = [&malformed, &second_der, &first_der]
+            .into_iter()
+            .inspect(|_| consumed.set(consumed.get() + 1));
+        let path = ee_cert
+            .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+            .intermediates_from_iter(intermediates)
+            .at_time(time)
+            .verify()
+            .unwrap();
+        assert_eq!(consumed.get(), 3);
+        assert_eq!(
+            path.intermediate_certificates()
+                .map(|cert| cert.der())
+                .collect::<Vec<_>>(),
+            [second_der.clone(), first_der.clone()]
+        );
+
+        // There may be more certificates than can be in a path.
+        let verifier = |copies| {
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates_from_iter(
+                    core::iter::repeat(&malformed)
+                        .take(copies)
+                        .chain([&second_der, &first_der]),
+                )
+                .at_time(time)
+                .verify()
+                .map(|_| ())
+        };
+        assert_eq!(verifier(MAX_SUB_CA_COUNT - 2), Ok(()));
+        assert_eq!(verifier(MAX_SUB_CA_COUNT * 4), Ok(()));
+    }
+
+    #[test]
+    fn intermediates_in_any_order() {
+        let ca_cert = make_issuer("Bogus Subject");
+        let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
+
+        let first = make_issuer("Bogus Intermediate 1");
+        let first_der = CertificateDer::from(first.serialize_der_with_signer(&ca_cert).unwrap());
+        let second = make_issuer("Bogus Intermediate 2");
+        let second_der = CertificateDer::from(second.serialize_der_with_signer(&first).unwrap());
+        let ee_der = make_end_entity(&second);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        let unrelated = make_issuer("Unrelated Intermediate");
+        let unrelated_der =
+            CertificateDer::from(unrelated.serialize_der_with_signer(&ca_cert).unwrap());
+        let malformed = CertificateDer::from(&[0x30, 0x00][..]);
+        // Valid CAs with the same name as the end-entity certificate's issuer, but other keys.
+        let decoys = (0..3)
+            .map(|_| {
+                CertificateDer::from(
+                    make_issuer("Bogus Intermediate 2")
+                        .serialize_der_with_signer(&first)
+                        .unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let verify = |intermediates: &[CertificateDer<'_>]| {
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(intermediates)
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+                .verify()
+                .map(|path| {
+                    path.intermediate_certificates()
+                        .map(|cert| cert.der().to_vec())
+                        .collect::<Vec<_>>()
+                })
+        };
+        let expected = Ok(vec![second_der.to_vec(), first_der.to_vec()]);
+
+        // Reversed.
+        assert_eq!(verify(&[first_der.clone(), second_der.clone()]), expected);
+        // With unrelated and malformed certificates in between.
+        assert_eq!(
+            verify(&[
+                second_der.clone(),
+                unrelated_der,
+                malformed,
+                first_der.clone()
+            ]),
+            expected
+        );
+        // After decoys for the end-entity certificate's issuer.
+        let mut intermediates = decoys;
+        intermediates.extend([second_der.clone(), first_der.clone()]);
+        assert_eq!(verify(&intermediates), expected);
+    }
+
+    #[test]
+    fn trusted_intermediate_ends_path() {
+        let mut root_params = issuer_params("Bogus Root");
+        root_params.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        let root = rcgen::Certificate::from_params(root_params).unwrap();
+        let root_der = CertificateDer::from(root.serialize_der().unwrap());
+
+        let chain = |int_b_params: rcgen::CertificateParams| {
+            let int_b = rcgen::Certificate::from_params(int_b_params).unwrap();
+            let int_b_der = CertificateDer::from(int_b.serialize_der_with_signer(&root).unwrap());
+            let int_a = make_issuer("Bogus Intermediate A");
+            let int_a_der = CertificateDer::from(int_a.serialize_der_with_signer(&int_b).unwrap());
+            (make_end_entity(&int_a), [int_a_der, int_b_der])
+        };
+        let verify = |ee_der: &CertificateDer<'_>, intermediates: &[CertificateDer<'_>]| {
+            let anchors = &[anchor_from_trusted_cert(&intermediates[1]).unwrap()];
+            let supplied = [intermediates, core::slice::from_ref(&root_der)].concat();
+            let ee_cert = EndEntityCert::try_from(ee_der).unwrap();
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(&supplied)
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+                .verify()
+                .map(|path| {
+                    path.intermediate_certificates()
+                        .map(|cert| cert.der().to_vec())
+                        .collect::<Vec<_>>()
+                })
+        };
+
+        // The path ends at the anchor, and the expired root it was sent with doesn't matter.
+        let (ee_der, intermediates) = chain(issuer_params("Bogus Intermediate B"));
+        assert_eq!(
+            verify(&ee_der, &intermediates),
+            Ok(vec![intermediates[0].to_vec()])
+        );
+
+        // Nor does the root matter when the path through the anchor is invalid: the error is
+        // the anchor's, rather than one found by building a path past it.
+        let mut constrained_params = issuer_params("Bogus Intermediate B");
+        constrained_params.name_constraints = Some(rcgen::NameConstraints {
+            permitted_subtrees: vec![rcgen::GeneralSubtree::DnsName("example.org".into())],
+            excluded_subtrees: vec![],
+        });
+        let (ee_der, intermediates) = chain(constrained_params);
+        assert_eq!(
+            verify(&ee_der, &intermediates),
+            Err(Error::NameConstraintViolation)
+        );
+    }
+
+    #[test]
+    fn dangerous_allow_errors() {
+        use crate::dangerous::AllowedErrors;
+
+        let ca_cert = make_issuer("Bogus Subject");
+        let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
+
+        let intermediate = make_issuer("Bogus Intermediate");
+        let intermediate_der =
+            CertificateDer::from(intermediate.serialize_der_with_signer(&ca_cert).unwrap());
+        let mut params = end_entity_params(vec!["example.com".into()]);
+        params.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der_with_signer(&intermediate)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+        let expired = Error::CertExpired {
+            time,
+            not_after: UnixTime::since_unix_epoch(Duration::from_secs(315_532_800)),
+        };
+        let verify = |anchors, allowed| {
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(core::slice::from_ref(&intermediate_der))
+                .at_time(time)
+                .dangerous_allow_errors(allowed)
+                .verify()
+        };
+
+        assert_eq!(verify(anchors, AllowedErrors::new()).err(), Some(expired));
+        assert_eq!(
+            verify(anchors, AllowedErrors::new().cert_not_valid_yet()).err(),
+            Some(expired)
+        );
+        let path = verify(anchors, AllowedErrors::new().cert_expired()).unwrap();
+        assert_eq!(path.suppressed_errors().collect::<Vec<_>>(), [(0, expired)]);
+
+        // Signatures are still verified.
+        let other_ca = make_issuer("Bogus Subject");
+        let other_ca_der = CertificateDer::from(other_ca.serialize_der().unwrap());
+        let other_anchors = &[anchor_from_trusted_cert(&other_ca_der).unwrap()];
+        assert_eq!(
+            verify(other_anchors, AllowedErrors::new().cert_expired()).err(),
+            Some(Error::InvalidSignatureForPublicKey)
+        );
+    }
+
+    #[test]
+    fn dangerous_allow_errors_prefers_valid_path() {
+        use crate::dangerous::AllowedErrors;
+
+        let ca_cert = make_issuer("Bogus Subject");
+        let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
+
+        // Two certificates for the same intermediate CA, the first of which has expired.
+        let intermediate = make_issuer("Bogus Intermediate");
+        let mut params = issuer_params("Bogus Intermediate");
+        params.key_pair =
+            Some(rcgen::KeyPair::from_der(&intermediate.serialize_private_key_der()).unwrap());
+        params.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        let expired = rcgen::Certificate::from_params(params).unwrap();
+        let intermediates = [
+            CertificateDer::from(expired.serialize_der_with_signer(&ca_cert).unwrap()),
+            CertificateDer::from(intermediate.serialize_der_with_signer(&ca_cert).unwrap()),
+        ];
+        let ee_der = make_end_entity(&intermediate);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        let path = ee_cert
+            .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+            .intermediates(&intermediates)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+            .dangerous_allow_errors(AllowedErrors::new().cert_expired())
+            .verify()
+            .unwrap();
+        assert_eq!(path.suppressed_errors().count(), 0);
+        assert_eq!(
+            path.intermediate_certificates().next().unwrap().der(),
+            intermediates[1]
+        );
+    }
+
+    #[test]
+    fn dangerous_verify_allowing_unknown_issuer() {
+        use crate::dangerous::{AcceptedPath, AllowedErrors};
+
+        let ca_cert = make_issuer("Bogus Subject");
+        let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
+
+        let intermediate = make_issuer("Bogus Intermediate");
+        let intermediate_der =
+            CertificateDer::from(intermediate.serialize_der_with_signer(&ca_cert).unwrap());
+        let mut params = end_entity_params(vec!["example.com".into()]);
+        params.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der_with_signer(&intermediate)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+        let expired = Error::CertExpired {
+            time,
+            not_after: UnixTime::since_unix_epoch(Duration::from_secs(315_532_800)),
+        };
+        let verifier = |anchors, intermediates| {
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(intermediates)
+                .at_time(time)
+                .dangerous_allow_errors(AllowedErrors::new().cert_expired())
+        };
+        let verify = |anchors, intermediates| {
+            verifier(anchors, intermediates)
+                .dangerous_verify_allowing_unknown_issuer()
+                .map_err(Error::from)
+        };
+        let unanchored = |anchors, intermediates| match verify(anchors, intermediates) {
+            Ok(AcceptedPath::UnknownIssuer(path)) => path,
+            _ => panic!("expected a path with an unknown issuer"),
+        };
+        let intermediates = core::slice::from_ref(&intermediate_der);
+
+        // Only this verification accepts an unknown issuer.
+        assert_eq!(
+            verifier(&[], intermediates).verify().err(),
+            Some(Error::UnknownIssuer)
+        );
+        assert_eq!(
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, &[])
+                .intermediates(intermediates)
+                .at_time(time)
+                .dangerous_verify_allowing_unknown_issuer()
+                .map(|_| ())
+                .map_err(Error::from),
+            Err(expired)
+        );
+
+        // The path goes as far as the intermediate, whose issuer is unknown.
+        let path = unanchored(&[], intermediates);
+        assert_eq!(
+            path.intermediate_certificates().next().unwrap().der(),
+            intermediate_der
+        );
+        assert_eq!(
+            path.suppressed_errors().collect::<Vec<_>>(),
+            [(0, expired), (1, Error::UnknownIssuer)]
+        );
+
+        // Without the intermediate, it's the end-entity certificate's issuer that is unknown.
+        let path = unanchored(&[], &[]);
+        assert_eq!(path.intermediate_certificates().count(), 0);
+        assert_eq!(
+            path.suppressed_errors().collect::<Vec<_>>(),
+            [(0, expired), (0, Error::UnknownIssuer)]
+        );
+
+        // A path ending at a trust anchor is preferred.
+        match verify(anchors, intermediates) {
+            Ok(AcceptedPath::Verified(path)) => {
+                assert!(core::ptr::eq(path.anchor(), &anchors[0]));
+                assert_eq!(path.suppressed_errors().collect::<Vec<_>>(), [(0, expired)]);
+            }
+            _ => panic!("expected a verified path"),
+        }
+
+        // A path whose issuer is unknown is never passed to `verify_path`.
+        let called = Cell::new(false);
+        let verify_path = |_: &VerifiedPath<'_>| {
+            called.set(true);
+            Ok(())
+        };
+        assert!(matches!(
+            verifier(&[], intermediates)
+                .verify_path(&verify_path)
+                .dangerous_verify_allowing_unknown_issuer(),
+            Ok(AcceptedPath::UnknownIssuer(_))
+        ));
+        assert!(!called.get());
+
+        // An issuer that is found but rejected isn't unknown.
+        let other_ca = make_issuer("Bogus Subject");
+        let other_ca_der = CertificateDer::from(other_ca.serialize_der().unwrap());
+        let other_anchors = &[anchor_from_trusted_cert(&other_ca_der).unwrap()];
+        assert_eq!(
+            verify(other_anchors, intermediates).err(),
+            Some(Error::InvalidSignatureForPublicKey)
+        );
+
+        // Signatures within the path are still verified.
+        let other_intermediate = make_issuer("Bogus Intermediate");
+        let other_intermediate_der = CertificateDer::from(
+            other_intermediate
+                .serialize_der_with_signer(&ca_cert)
+                .unwrap(),
+        );
+        assert_eq!(
+            verify(&[], core::slice::from_ref(&other_intermediate_der)).err(),
+            Some(Error::InvalidSignatureForPublicKey)
+        );
+    }
+
+    #[test]
+    fn dangerous_ignore_validity_periods() {
+        let ca_cert = make_issuer("Bogus Subject");
+        let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
+
+        let date = |year| {
+            let secs = rcgen::date_time_ymd(year, 1, 1).unix_timestamp();
+            UnixTime::since_unix_epoch(Duration::from_secs(u64::try_from(secs).unwrap()))
+        };
+        // An expired intermediate, and an end-entity certificate that isn't valid yet.
+        let mut params = issuer_params("Bogus Intermediate");
+        params.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        let intermediate = rcgen::Certificate::from_params(params).unwrap();
+        let intermediate_der =
+            CertificateDer::from(intermediate.serialize_der_with_signer(&ca_cert).unwrap());
+        let mut params = end_entity_params(vec!["example.com".into()]);
+        params.not_before = rcgen::date_time_ymd(1990, 1, 1);
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(params)
+                .unwrap()
+                .serialize_der_with_signer(&intermediate)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        let verifier = |anchors| {
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(core::slice::from_ref(&intermediate_der))
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+        };
+        assert_eq!(
+            verifier(anchors).verify().err(),
+            Some(Error::CertNotValidYet {
+                time: UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)),
+                not_before: date(1990),
+            })
+        );
+
+        let path = verifier(anchors)
+            .dangerous_ignore_validity_periods()
+            .verify()
+            .unwrap();
+        assert_eq!(path.suppressed_errors().count(), 0);
+        assert_eq!(
+            path.validity_periods().collect::<Vec<_>>(),
+            [(date(1990), date(4096)), (date(1975), date(1980))]
+        );
+
+        // Signatures are still verified.
+        let other_ca = make_issuer("Bogus Subject");
+        let other_ca_der = CertificateDer::from(other_ca.serialize_der().unwrap());
+        let other_anchors = &[anchor_from_trusted_cert(&other_ca_der).unwrap()];
+        assert_eq!(
+            verifier(other_anchors)
+                .dangerous_ignore_validity_periods()
+                .verify()
+                .err(),
+            Some(Error::InvalidSignatureForPublicKey)
+        );
+    }
+
+    #[test]
+    fn max_path_build_calls() {
+        // A mesh of intermediates, each cross-signed by every other, none of which lead to the
+        // trust anchor. Every path of up to six intermediates is a candidate.
+        const NUM_INTERMEDIATES: usize = 6;
+        let ca_cert = make_issuer("Bogus Subject");
+        let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
+
+        let issuers = (0..NUM_INTERMEDIATES)
+            .map(|i| make_issuer(format!("Bogus Intermediate {i}")))
+            .collect::<Vec<_>>();
+        let mut intermediates = Vec::new();
+        for (i, subject) in issuers.iter().enumerate() {
+            for (j, issuer) in issuers.iter().enumerate() {
+                if i != j {
+                    let der = subject.serialize_der_with_signer(issuer).unwrap();
+                    intermediates.push(CertificateDer::from(der));
+                }
+            }
+        }
+        let ee_der = make_end_entity(&issuers[0]);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        assert!(matches!(
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(&intermediates)
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+                .max_path_build_calls(1_000)
+                .verify(),
+            Err(Error::MaximumPathBuildCallsExceeded)
+        ));
+    }
+
+    #[test]
+    fn name_constraint_budget() {
+        // Issue a trust anchor that imposes name constraints. The constraint should match
+        // the end entity certificate SAN.
+        let mut ca_cert_params = issuer_params("Constrained Root");
+        ca_cert_params.name_constraints = Some(rcgen::NameConstraints {
+            permitted_subtrees: vec![rcgen::GeneralSubtree::DnsName(".com".into())],
+            excluded_subtrees: vec![],
+        });
+        let ca_cert = rcgen::Certificate::from_params(ca_cert_params).unwrap();
+        let ca_cert_der = CertificateDer::from(ca_cert.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_cert_der).unwrap()];
+
+        // Create a series of intermediate issuers. We'll only use one in the actual built path,
+        // helping demonstrate that the name constraint budget is not expended checking certificates
+        // that are not part of the path we compute.
+        const NUM_INTERMEDIATES: usize = 5;
+        let mut intermediates = Vec::with_capacity(NUM_INTERMEDIATES);
+        for i in 0..NUM_INTERMEDIATES {
+            intermediates.push(make_issuer(format!("Intermediate {i}")));
+        }
+
+        // Each intermediate should be issued by the trust anchor.
+        let mut intermediates_der = Vec::with_capacity(NUM_INTERMEDIATES);
+        for intermediate in &intermediates {
+            intermediates_der.push(CertificateDer::from(
+                intermediate.serialize_der_with_signer(&ca_cert).unwrap(),
+            ));
+        }
+
+        // Create an end-entity cert that is issued by the last of the intermediates.
+        let ee_der = make_end_entity(intermediates.last().unwrap());
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        // We use a custom budget to make it easier to write a test, otherwise it is tricky to
+        // stuff enough names/constraints into the potential chains while staying within the path
+        // depth limit and the build chain call limit.
+        let passing_budget = Budget {
+            // One comparison against the intermediate's distinguished name.
+            // One comparison against the EE's distinguished name.
+            // One comparison against the EE's SAN.
+            //  = 3 total comparisons.
+            name_constraint_comparisons: 3,
+            ..Budget::default()
+        };
+
+        // Validation should succeed with the name constraint comparison budget allocated above.
+        // This shows that we're not consuming budget on unused intermediates: we didn't budget
+        // enough comparisons for that to pass the overall chain building.
         let path = verify_chain(
             anchors,
             &intermediates_der,
@@ -907,165 +3152,2032 @@ mod tests {
         .unwrap();
         assert_eq!(path.anchor().subject, anchors.first().unwrap().subject);
 
-        let failing_budget = Budget {
-            // See passing_budget: 2 comparisons is not sufficient.
-            name_constraint_comparisons: 2,
-            ..Budget::default()
+        let failing_budget = Budget {
+            // See passing_budget: 2 comparisons is not sufficient.
+            name_constraint_comparisons: 2,
+            ..Budget::default()
+        };
+        // Validation should fail when the budget is smaller than the number of comparisons performed
+        // on the validated path. This demonstrates we properly fail path building when too many
+        // name constraint comparisons occur.
+        let result = verify_chain(
+            anchors,
+            &intermediates_der,
+            &ee_cert,
+            None,
+            Some(failing_budget),
+        );
+
+        assert!(matches!(
+            result,
+            Err(ControlFlow::Break(
+                Error::MaximumNameConstraintComparisonsExceeded
+            ))
+        ));
+    }
+
+    #[test]
+    fn max_name_constraint_comparisons() {
+        // An intermediate with 10,000 permitted subtrees, each of which is compared against
+        // each of the end-entity certificate's 20 names. The subtrees are as short as
+        // possible, to keep the intermediate within the size of certificate webpki accepts.
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        let mut int_params = issuer_params("Many Subtrees");
+        int_params.name_constraints = Some(rcgen::NameConstraints {
+            permitted_subtrees: vec![rcgen::GeneralSubtree::DnsName("a".into()); 10_000],
+            excluded_subtrees: vec![],
+        });
+        let int = rcgen::Certificate::from_params(int_params).unwrap();
+        let intermediates = [CertificateDer::from(
+            int.serialize_der_with_signer(&ca).unwrap(),
+        )];
+
+        let names = (0..20).map(|i| format!("host{i}.a")).collect();
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(end_entity_params(names))
+                .unwrap()
+                .serialize_der_with_signer(&int)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let verifier = ee_cert
+            .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+            .intermediates(&intermediates)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)));
+
+        assert!(verifier.clone().verify().is_ok());
+        assert!(matches!(
+            verifier.max_name_constraint_comparisons(1 << 16).verify(),
+            Err(Error::MaximumNameConstraintComparisonsExceeded)
+        ));
+    }
+
+    #[test]
+    fn empty_permitted_subtrees() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        // rcgen omits empty subtrees, so encode the extension ourselves:
+        // SEQUENCE { [0] {} }
+        let mut int_params = issuer_params("Empty Permitted Intermediate");
+        int_params.custom_extensions = vec![{
+            let mut ext = rcgen::CustomExtension::from_oid_content(
+                &[2, 5, 29, 30],
+                vec![0x30, 0x02, 0xa0, 0x00],
+            );
+            ext.set_criticality(true);
+            ext
+        }];
+        let int = rcgen::Certificate::from_params(int_params).unwrap();
+        let int_der = CertificateDer::from(int.serialize_der_with_signer(&ca).unwrap());
+
+        let ee_der = make_end_entity(&int);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        assert!(matches!(
+            verify_chain(
+                anchors,
+                core::slice::from_ref(&int_der),
+                &ee_cert,
+                None,
+                None
+            ),
+            Err(ControlFlow::Continue(Error::NameConstraintViolation))
+        ));
+
+        // A leaf without a subjectAltName still presents its subject, which is not permitted
+        // either.
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(end_entity_params(vec![]))
+                .unwrap()
+                .serialize_der_with_signer(&int)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        assert!(ee_cert.subject_alt_name.is_none());
+        assert!(matches!(
+            verify_chain(anchors, &[int_der], &ee_cert, None, None),
+            Err(ControlFlow::Continue(Error::NameConstraintViolation))
+        ));
+    }
+
+    #[test]
+    fn disjoint_permitted_subtrees() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        let constrained_issuer = |name: &str, permitted: &str, issuer: &rcgen::Certificate| {
+            let mut params = issuer_params(name);
+            params.name_constraints = Some(rcgen::NameConstraints {
+                permitted_subtrees: vec![rcgen::GeneralSubtree::DnsName(permitted.into())],
+                excluded_subtrees: vec![],
+            });
+            let cert = rcgen::Certificate::from_params(params).unwrap();
+            let der = CertificateDer::from(cert.serialize_der_with_signer(issuer).unwrap());
+            (cert, der)
+        };
+        let end_entity = |san: &str, issuer: &rcgen::Certificate| {
+            CertificateDer::from(
+                rcgen::Certificate::from_params(end_entity_params(vec![san.into()]))
+                    .unwrap()
+                    .serialize_der_with_signer(issuer)
+                    .unwrap(),
+            )
+        };
+
+        // The first intermediate only permits names under a.example.com, and the second,
+        // issued by the first, only permits names under b.example.com.
+        let (int_a, int_a_der) = constrained_issuer("Intermediate A", "a.example.com", &ca);
+        let (int_b, int_b_der) = constrained_issuer("Intermediate B", "b.example.com", &int_a);
+
+        // A leaf issued by the first intermediate only needs to satisfy its constraints.
+        let ee_der = end_entity("host.a.example.com", &int_a);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        assert!(verify_chain(
+            anchors,
+            core::slice::from_ref(&int_a_der),
+            &ee_cert,
+            None,
+            None
+        )
+        .is_ok());
+
+        // The intersection of the two permitted sets is empty, so no leaf issued by the
+        // second intermediate is acceptable, whichever of the two subtrees it is in.
+        let intermediates = &[int_a_der, int_b_der];
+        for san in ["host.a.example.com", "host.b.example.com"] {
+            let ee_der = end_entity(san, &int_b);
+            let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+            assert!(matches!(
+                verify_chain(anchors, intermediates, &ee_cert, None, None),
+                Err(ControlFlow::Continue(Error::NameConstraintViolation))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_reject_candidate_path() {
+        /*
+         This test builds a PKI like the following diagram depicts. We first verify
+         that we can build a path EE -> B -> A -> TA. Next we supply a custom path verification
+         function that rejects the B->A path, and verify that we build a path EE -> B -> C -> TA.
+
+               ┌───────────┐
+               │           │
+               │     TA    │
+               │           │
+               └───┬───┬───┘
+                   │   │
+                   │   │
+        ┌────────┐◄┘   └──►┌────────┐
+        │        │         │        │
+        │   A    │         │   C    │
+        │        │         │        │
+        └────┬───┘         └───┬────┘
+             │                 │
+             │                 │
+             │   ┌─────────┐   │
+             └──►│         │◄──┘
+                 │    B    │
+                 │         │
+                 └────┬────┘
+                      │
+                      │
+                      │
+                 ┌────▼────┐
+                 │         │
+                 │    EE   │
+                 │         │
+                 └─────────┘
+          */
+
+        // Create a trust anchor, and use it to issue two distinct intermediate certificates, each
+        // with a unique subject and keypair.
+        let trust_anchor = make_issuer("Trust Anchor");
+        let trust_anchor_der = CertificateDer::from(trust_anchor.serialize_der().unwrap());
+        let trust_anchor_cert =
+            Cert::from_der(untrusted::Input::from(trust_anchor_der.as_ref())).unwrap();
+        let trust_anchors = &[anchor_from_trusted_cert(&trust_anchor_der).unwrap()];
+
+        let intermediate_a = make_issuer("Intermediate A");
+        let intermediate_a_der = CertificateDer::from(
+            intermediate_a
+                .serialize_der_with_signer(&trust_anchor)
+                .unwrap(),
+        );
+        let intermediate_a_cert =
+            Cert::from_der(untrusted::Input::from(intermediate_a_der.as_ref())).unwrap();
+
+        let intermediate_c = make_issuer("Intermediate C");
+        let intermediate_c_der = CertificateDer::from(
+            intermediate_c
+                .serialize_der_with_signer(&trust_anchor)
+                .unwrap(),
+        );
+        let intermediate_c_cert =
+            Cert::from_der(untrusted::Input::from(intermediate_c_der.as_ref())).unwrap();
+
+        // Next, create an intermediate that is issued by both of the intermediates above.
+        // Both should share the same subject, and key pair, but will differ in the issuer.
+        let intermediate_b_key = rcgen::KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
+        let mut intermediate_b_params = issuer_params("Intermediate");
+        intermediate_b_params.key_pair = Some(intermediate_b_key);
+        let intermediate_b = rcgen::Certificate::from_params(intermediate_b_params).unwrap();
+
+        let intermediate_b_a_der = CertificateDer::from(
+            intermediate_b
+                .serialize_der_with_signer(&intermediate_a)
+                .unwrap(),
+        );
+        let intermediate_b_c_der = CertificateDer::from(
+            intermediate_b
+                .serialize_der_with_signer(&intermediate_c)
+                .unwrap(),
+        );
+
+        let intermediates = &[
+            intermediate_a_der.clone(),
+            intermediate_c_der.clone(),
+            intermediate_b_a_der.clone(),
+            intermediate_b_c_der.clone(),
+        ];
+
+        // Create an end entity certificate signed by the keypair of the intermediates created above.
+        let ee = make_end_entity(&intermediate_b);
+        let ee_cert = &EndEntityCert::try_from(&ee).unwrap();
+
+        // We should be able to create a valid path from EE to trust anchor.
+        let path = verify_chain(trust_anchors, intermediates, ee_cert, None, None).unwrap();
+        let path_intermediates = path.intermediate_certificates().collect::<Vec<_>>();
+
+        // We expect that without applying any additional constraints, that the path will be
+        // EE -> intermediate_b_a -> intermediate_a -> trust_anchor.
+        assert_eq!(path_intermediates.len(), 2);
+        assert_eq!(
+            path_intermediates[0].issuer(),
+            intermediate_a_cert.subject()
+        );
+        assert_eq!(path_intermediates[1].issuer(), trust_anchor_cert.subject());
+
+        // Now, we'll create a function that will reject the intermediate_b_a path.
+        let expected_chain = |path: &VerifiedPath<'_>| {
+            for intermediate in path.intermediate_certificates() {
+                // Reject any intermediates issued by intermediate A.
+                if intermediate.issuer() == intermediate_a_cert.subject() {
+                    return Err(Error::UnknownIssuer);
+                }
+            }
+
+            Ok(())
+        };
+
+        // We should still be able to build a valid path.
+        let path = verify_chain(
+            trust_anchors,
+            intermediates,
+            ee_cert,
+            Some(&expected_chain),
+            None,
+        )
+        .unwrap();
+        let path_intermediates = path.intermediate_certificates().collect::<Vec<_>>();
+
+        // We expect that the path will now be
+        // EE -> intermediate_b_c -> intermediate_c -> trust_anchor.
+        assert_eq!(path_intermediates.len(), 2);
+        assert_eq!(
+            path_intermediates[0].issuer(),
+            intermediate_c_cert.subject()
+        );
+        assert_eq!(path_intermediates[1].issuer(), trust_anchor_cert.subject());
+    }
+
+    #[test]
+    fn empty_issuer_rejected() {
+        let mut ca_params = issuer_params("unused");
+        ca_params.distinguished_name = rcgen::DistinguishedName::new();
+        let ca = rcgen::Certificate::from_params(ca_params).unwrap();
+
+        let ee_der = make_end_entity(&ca);
+        assert!(matches!(
+            EndEntityCert::try_from(&ee_der),
+            Err(Error::EmptyIssuer)
+        ));
+    }
+
+    #[test]
+    fn empty_subject_with_non_critical_san() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        let mut ee_params = end_entity_params(vec!["example.com".into()]);
+        ee_params.distinguished_name = rcgen::DistinguishedName::new();
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(ee_params)
+                .unwrap()
+                .serialize_der_with_signer(&ca)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        // By default, the problem is only reported as a lint.
+        assert_eq!(
+            ee_cert.lints().collect::<Vec<_>>(),
+            vec![Lint::EmptySubjectWithoutCriticalSan]
+        );
+        assert_eq!(
+            verify_with_policy(anchors, &[], &ee_cert, VerificationPolicy::default()),
+            Ok(())
+        );
+        assert_eq!(
+            verify_with_policy(anchors, &[], &ee_cert, VerificationPolicy::strict()),
+            Err(Error::EmptySubjectWithoutCriticalSan)
+        );
+    }
+
+    #[test]
+    fn empty_subject_alt_name() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        // SEQUENCE {}
+        let mut ee_params = end_entity_params(vec![]);
+        ee_params.custom_extensions = vec![rcgen::CustomExtension::from_oid_content(
+            &[2, 5, 29, 17],
+            vec![0x30, 0x00],
+        )];
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(ee_params)
+                .unwrap()
+                .serialize_der_with_signer(&ca)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        assert_eq!(
+            verify_with_policy(anchors, &[], &ee_cert, VerificationPolicy::default()),
+            Ok(())
+        );
+        assert_eq!(
+            verify_with_policy(anchors, &[], &ee_cert, VerificationPolicy::strict()),
+            Err(Error::EmptySubjectAltName)
+        );
+    }
+
+    #[test]
+    fn empty_subject_with_critical_san() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        // rcgen always marks the SAN extension non-critical, so encode it ourselves:
+        // SEQUENCE { [2] "example.com" }
+        let mut san = vec![0x30, 0x0d, 0x82, 0x0b];
+        san.extend_from_slice(b"example.com");
+        let mut san = rcgen::CustomExtension::from_oid_content(&[2, 5, 29, 17], san);
+        san.set_criticality(true);
+
+        let mut ee_params = end_entity_params(vec![]);
+        ee_params.distinguished_name = rcgen::DistinguishedName::new();
+        ee_params.custom_extensions = vec![san];
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(ee_params)
+                .unwrap()
+                .serialize_der_with_signer(&ca)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        assert_eq!(ee_cert.lints().next(), None);
+        assert_eq!(
+            verify_with_policy(anchors, &[], &ee_cert, VerificationPolicy::strict()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn malformed_dns_name_in_san() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+        let example = ServerName::try_from("example.com").unwrap();
+
+        for malformed in ["", " ", "\t "] {
+            let ee_params = end_entity_params(vec![malformed.into(), "example.com".into()]);
+            let ee_der = CertificateDer::from(
+                rcgen::Certificate::from_params(ee_params)
+                    .unwrap()
+                    .serialize_der_with_signer(&ca)
+                    .unwrap(),
+            );
+            let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+            // The malformed name is skipped for matching purposes, but reported as a lint.
+            assert_eq!(
+                ee_cert.valid_dns_names().collect::<Vec<_>>(),
+                ["example.com"]
+            );
+            assert_eq!(ee_cert.verify_is_valid_for_subject_name(&example), Ok(()));
+            assert_eq!(
+                ee_cert.lints().collect::<Vec<_>>(),
+                [Lint::MalformedDnsNameInSan]
+            );
+            assert_eq!(
+                verify_with_policy(anchors, &[], &ee_cert, VerificationPolicy::default()),
+                Ok(())
+            );
+            assert_eq!(
+                verify_with_policy(anchors, &[], &ee_cert, VerificationPolicy::strict()),
+                Err(Error::MalformedDnsNameInSan)
+            );
+        }
+
+        // A certificate with only an empty dNSName is valid for no name, and is reported as
+        // malformed.
+        let ee_params = end_entity_params(vec!["".into()]);
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(ee_params)
+                .unwrap()
+                .serialize_der_with_signer(&ca)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        assert_eq!(
+            ee_cert.verify_is_valid_for_subject_name(&example),
+            Err(Error::MalformedDnsIdentifier)
+        );
+    }
+
+    #[test]
+    fn malformed_other_name_in_san() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+        let issue = |san: Vec<u8>| {
+            let mut ee_params = end_entity_params(vec![]);
+            ee_params.custom_extensions = vec![rcgen::CustomExtension::from_oid_content(
+                &[2, 5, 29, 17],
+                san,
+            )];
+            CertificateDer::from(
+                rcgen::Certificate::from_params(ee_params)
+                    .unwrap()
+                    .serialize_der_with_signer(&ca)
+                    .unwrap(),
+            )
+        };
+
+        // [2] "example.com"
+        let mut dns_name = vec![0x82, 0x0b];
+        dns_name.extend_from_slice(b"example.com");
+        // A userPrincipalName otherName: [0] { OID 1.3.6.1.4.1.311.20.2.3, [0] { value } }
+        let other_name = |value: &[u8]| {
+            let mut other_name = vec![0x06, 0x0a, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37];
+            other_name.extend_from_slice(&[
+                0x14,
+                0x02,
+                0x03,
+                0xa0,
+                u8::try_from(value.len()).unwrap(),
+            ]);
+            other_name.extend_from_slice(value);
+            let mut encoded = vec![0xa0, u8::try_from(other_name.len()).unwrap()];
+            encoded.extend(other_name);
+            encoded
+        };
+        let san = |names: &[&[u8]]| {
+            let names = names.concat();
+            let mut san = vec![0x30, u8::try_from(names.len()).unwrap()];
+            san.extend(names);
+            san
+        };
+        let example = ServerName::try_from("example.com").unwrap();
+
+        let valid = issue(san(&[&dns_name, &other_name(b"\x0c\x05a@b.c")]));
+        let valid = EndEntityCert::try_from(&valid).unwrap();
+        assert_eq!(valid.lints().next(), None);
+        assert_eq!(
+            verify_with_policy(anchors, &[], &valid, VerificationPolicy::strict()),
+            Ok(())
+        );
+
+        // The value claims to be five bytes long, but is one.
+        let malformed = issue(san(&[&dns_name, &other_name(b"\x0c\x05a")]));
+        let malformed = EndEntityCert::try_from(&malformed).unwrap();
+        assert_eq!(
+            malformed.lints().collect::<Vec<_>>(),
+            [Lint::MalformedOtherNameInSan]
+        );
+        assert_eq!(
+            verify_with_policy(anchors, &[], &malformed, VerificationPolicy::default()),
+            Ok(())
+        );
+        assert_eq!(malformed.verify_is_valid_for_subject_name(&example), Ok(()));
+        assert_eq!(
+            verify_with_policy(anchors, &[], &malformed, VerificationPolicy::strict()),
+            Err(Error::MalformedOtherNameInSan)
+        );
+
+        // If the otherName can't even be delimited, no names after it can be matched.
+        let truncated = issue(san(&[&dns_name, &[0xa0, 0x20, 0x06]]));
+        let truncated = EndEntityCert::try_from(&truncated).unwrap();
+        assert_eq!(truncated.lints().next(), None);
+        assert_eq!(
+            verify_with_policy(anchors, &[], &truncated, VerificationPolicy::default()),
+            Ok(())
+        );
+        assert_eq!(truncated.verify_is_valid_for_subject_name(&example), Ok(()));
+        assert_eq!(
+            truncated
+                .verify_is_valid_for_subject_name(&ServerName::try_from("other.example").unwrap()),
+            Err(Error::BadDer)
+        );
+    }
+
+    #[test]
+    fn max_validity() {
+        const DAY: u64 = 24 * 60 * 60;
+
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        // Ten years, under a root that is valid for much longer.
+        let mut inter_params = issuer_params("Intermediate");
+        inter_params.not_before = rcgen::date_time_ymd(1980, 1, 1);
+        inter_params.not_after = rcgen::date_time_ymd(1990, 1, 1);
+        let inter = rcgen::Certificate::from_params(inter_params).unwrap();
+        let inter_der = CertificateDer::from(inter.serialize_der_with_signer(&ca).unwrap());
+
+        // One year.
+        let mut ee_params = end_entity_params(vec!["example.com".into()]);
+        ee_params.not_before = rcgen::date_time_ymd(1986, 6, 1);
+        ee_params.not_after = rcgen::date_time_ymd(1987, 6, 1);
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(ee_params)
+                .unwrap()
+                .serialize_der_with_signer(&inter)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let intermediates = &[inter_der];
+
+        let verify = |max_validity| {
+            let policy = VerificationPolicy::default().with_max_validity(max_validity);
+            verify_with_policy(anchors, intermediates, &ee_cert, policy)
+        };
+
+        assert_eq!(verify(MaxValidity::new()), Ok(()));
+        assert_eq!(
+            verify(MaxValidity::new().with_end_entity(Duration::from_secs(398 * DAY))),
+            Ok(())
+        );
+        assert_eq!(
+            verify(MaxValidity::new().with_end_entity(Duration::from_secs(90 * DAY))),
+            Err(Error::ValidityPeriodTooLong {
+                depth: 0,
+                role: CertRole::EndEntity,
+            })
+        );
+
+        // The intermediate is only rejected once its own limit is configured.
+        let leaf_only = MaxValidity::new().with_end_entity(Duration::from_secs(398 * DAY));
+        assert_eq!(verify(leaf_only), Ok(()));
+        assert_eq!(
+            verify(leaf_only.with_intermediate(Duration::from_secs(8 * 365 * DAY))),
+            Err(Error::ValidityPeriodTooLong {
+                depth: 1,
+                role: CertRole::Intermediate,
+            })
+        );
+        // The root's certificate is valid for far longer than either limit, but the trust
+        // anchor made from it has no validity period to limit.
+        assert_eq!(
+            verify(leaf_only.with_intermediate(Duration::from_secs(11 * 365 * DAY))),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn ca_constraints_not_critical() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        // An intermediate with a critical basicConstraints extension is acceptable in
+        // strict mode.
+        let critical = make_issuer("Critical BC Intermediate");
+        let critical_der = CertificateDer::from(critical.serialize_der_with_signer(&ca).unwrap());
+        let ee_der = make_end_entity(&critical);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        assert_eq!(
+            verify_with_policy(
+                anchors,
+                &[critical_der],
+                &ee_cert,
+                VerificationPolicy::strict()
+            ),
+            Ok(())
+        );
+
+        // rcgen always marks basicConstraints critical, so encode it ourselves:
+        // SEQUENCE { BOOLEAN TRUE }
+        let mut non_critical_params = issuer_params("Non-critical BC Intermediate");
+        non_critical_params.is_ca = rcgen::IsCa::NoCa;
+        non_critical_params.custom_extensions = vec![rcgen::CustomExtension::from_oid_content(
+            &[2, 5, 29, 19],
+            vec![0x30, 0x03, 0x01, 0x01, 0xff],
+        )];
+        let non_critical = rcgen::Certificate::from_params(non_critical_params).unwrap();
+        let non_critical_der =
+            CertificateDer::from(non_critical.serialize_der_with_signer(&ca).unwrap());
+        let ee_der = make_end_entity(&non_critical);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        let intermediates = &[non_critical_der];
+        assert_eq!(
+            verify_with_policy(
+                anchors,
+                intermediates,
+                &ee_cert,
+                VerificationPolicy::default()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            verify_with_policy(
+                anchors,
+                intermediates,
+                &ee_cert,
+                VerificationPolicy::strict()
+            ),
+            Err(Error::CaConstraintsNotCritical { depth: 1 })
+        );
+    }
+
+    #[test]
+    fn issuer_key_usage_without_key_cert_sign() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        // Two intermediates sharing a subject and key pair: the first only asserts
+        // digitalSignature in its KeyUsage, the second also asserts keyCertSign.
+        let key_pair = rcgen::KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
+        let mut bad_ku_params = issuer_params("Intermediate");
+        bad_ku_params.key_usages = vec![rcgen::KeyUsagePurpose::DigitalSignature];
+        bad_ku_params.key_pair = Some(rcgen::KeyPair::from_der(&key_pair.serialize_der()).unwrap());
+        let bad_ku = rcgen::Certificate::from_params(bad_ku_params).unwrap();
+        let bad_ku_der = CertificateDer::from(bad_ku.serialize_der_with_signer(&ca).unwrap());
+
+        let mut good_ku_params = issuer_params("Intermediate");
+        good_ku_params.key_pair = Some(key_pair);
+        let good_ku = rcgen::Certificate::from_params(good_ku_params).unwrap();
+        let good_ku_der = CertificateDer::from(good_ku.serialize_der_with_signer(&ca).unwrap());
+
+        let ee_der = make_end_entity(&bad_ku);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        // With only the intermediate lacking keyCertSign, no path can be built.
+        assert_eq!(
+            verify_with_policy(
+                anchors,
+                core::slice::from_ref(&bad_ku_der),
+                &ee_cert,
+                VerificationPolicy::default()
+            ),
+            Err(Error::IssuerKeyUsageInvalid)
+        );
+
+        // With the alternative intermediate available, path building falls back to it.
+        assert_eq!(
+            verify_with_policy(
+                anchors,
+                &[bad_ku_der, good_ku_der],
+                &ee_cert,
+                VerificationPolicy::default()
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn most_specific_error_across_candidates() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        // Two intermediates sharing a subject and key pair: the first has expired, the second
+        // has name constraints that exclude the end-entity certificate's name.
+        let key_pair = rcgen::KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
+        let mut expired_params = issuer_params("Intermediate");
+        expired_params.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        expired_params.key_pair =
+            Some(rcgen::KeyPair::from_der(&key_pair.serialize_der()).unwrap());
+        let expired = rcgen::Certificate::from_params(expired_params).unwrap();
+        let expired_der = CertificateDer::from(expired.serialize_der_with_signer(&ca).unwrap());
+
+        let mut constrained_params = issuer_params("Intermediate");
+        constrained_params.name_constraints = Some(rcgen::NameConstraints {
+            permitted_subtrees: vec![rcgen::GeneralSubtree::DnsName(".org".into())],
+            excluded_subtrees: vec![],
+        });
+        constrained_params.key_pair = Some(key_pair);
+        let constrained = rcgen::Certificate::from_params(constrained_params).unwrap();
+        let constrained_der =
+            CertificateDer::from(constrained.serialize_der_with_signer(&ca).unwrap());
+
+        let ee_der = make_end_entity(&expired);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        assert_eq!(
+            verify_with_policy(
+                anchors,
+                core::slice::from_ref(&constrained_der),
+                &ee_cert,
+                VerificationPolicy::default()
+            ),
+            Err(Error::NameConstraintViolation)
+        );
+
+        // Whichever candidate is tried first, the validity error is the most specific.
+        for intermediates in [
+            [expired_der.clone(), constrained_der.clone()],
+            [constrained_der, expired_der],
+        ] {
+            assert!(matches!(
+                verify_with_policy(
+                    anchors,
+                    &intermediates,
+                    &ee_cert,
+                    VerificationPolicy::default()
+                ),
+                Err(Error::CertExpired { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn ca_rollover() {
+        // A rolled-over root: two certificates with the same subject and key pair, the first
+        // of which has expired. Trust anchors carry no validity period, so both are usable.
+        let root_key = rcgen::KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
+        let mut old_root_params = issuer_params("Test CA");
+        old_root_params.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        old_root_params.key_pair =
+            Some(rcgen::KeyPair::from_der(&root_key.serialize_der()).unwrap());
+        let old_root = rcgen::Certificate::from_params(old_root_params).unwrap();
+        let old_root_der = CertificateDer::from(old_root.serialize_der().unwrap());
+        let mut new_root_params = issuer_params("Test CA");
+        new_root_params.key_pair = Some(root_key);
+        let new_root = rcgen::Certificate::from_params(new_root_params).unwrap();
+        let new_root_der = CertificateDer::from(new_root.serialize_der().unwrap());
+        let anchors = &[
+            anchor_from_trusted_cert(&old_root_der).unwrap(),
+            anchor_from_trusted_cert(&new_root_der).unwrap(),
+        ];
+
+        // The intermediate was rolled over along with it.
+        let int_key = rcgen::KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
+        let mut expired_params = issuer_params("Intermediate");
+        expired_params.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        expired_params.key_pair = Some(rcgen::KeyPair::from_der(&int_key.serialize_der()).unwrap());
+        let expired = rcgen::Certificate::from_params(expired_params).unwrap();
+        let expired_der =
+            CertificateDer::from(expired.serialize_der_with_signer(&old_root).unwrap());
+        let mut current_params = issuer_params("Intermediate");
+        current_params.key_pair = Some(int_key);
+        let current = rcgen::Certificate::from_params(current_params).unwrap();
+        let current_der =
+            CertificateDer::from(current.serialize_der_with_signer(&new_root).unwrap());
+
+        let ee_der = make_end_entity(&current);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        // Whichever variant is tried first, the path through the current one is found.
+        for intermediates in [
+            [expired_der.clone(), current_der.clone()],
+            [current_der.clone(), expired_der.clone()],
+        ] {
+            let path = verify_chain(anchors, &intermediates, &ee_cert, None, None)
+                .ok()
+                .unwrap();
+            assert_eq!(
+                path.intermediate_certificates()
+                    .map(|cert| cert.der())
+                    .collect::<Vec<_>>(),
+                vec![current_der.clone()]
+            );
+        }
+
+        // Without the current variant, the validity error is reported.
+        assert!(matches!(
+            verify_with_policy(
+                anchors,
+                core::slice::from_ref(&expired_der),
+                &ee_cert,
+                VerificationPolicy::default()
+            ),
+            Err(Error::CertExpired { .. })
+        ));
+    }
+
+    #[test]
+    fn spki_pins() {
+        #[cfg(all(feature = "aws_lc_rs", not(feature = "ring")))]
+        use aws_lc_rs::digest;
+        #[cfg(feature = "ring")]
+        use ring::digest;
+
+        let pin = |spki: &[u8]| -> [u8; 32] {
+            digest::digest(&digest::SHA256, spki)
+                .as_ref()
+                .try_into()
+                .unwrap()
+        };
+
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+        let ca_cert = Cert::from_der(untrusted::Input::from(&ca_der)).unwrap();
+
+        let intermediate = rcgen::Certificate::from_params(issuer_params("Intermediate")).unwrap();
+        let intermediate_der =
+            CertificateDer::from(intermediate.serialize_der_with_signer(&ca).unwrap());
+        let intermediate_cert = Cert::from_der(untrusted::Input::from(&intermediate_der)).unwrap();
+        let intermediates = core::slice::from_ref(&intermediate_der);
+
+        let ee_der = make_end_entity(&intermediate);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        // The whole subjectPublicKeyInfo is hashed, not just the key.
+        let spki = intermediate_cert.subject_public_key_info();
+        assert_eq!(spki[0], u8::from(der::Tag::Sequence));
+        assert!(intermediate_der
+            .as_ref()
+            .windows(spki.len())
+            .any(|w| w == spki));
+
+        let intermediate_pin = pin(spki);
+        let anchor_pin = pin(ca_cert.subject_public_key_info());
+        let other_pin = [0; 32];
+        assert!(intermediate_cert.matches_spki_pins(&[other_pin, intermediate_pin]));
+        assert!(!ee_cert.matches_spki_pins(&[intermediate_pin]));
+
+        let path = verify_chain(anchors, intermediates, &ee_cert, None, None)
+            .ok()
+            .unwrap();
+        assert!(path.matches_spki_pins(&[intermediate_pin]));
+        assert!(path.matches_spki_pins(&[other_pin, intermediate_pin]));
+        assert!(path.matches_spki_pins(&[pin(ee_cert.subject_public_key_info())]));
+        // The trust anchor's subjectPublicKeyInfo is hashed with its restored header.
+        assert!(path.matches_spki_pins(&[anchor_pin]));
+        assert!(!path.matches_spki_pins(&[other_pin]));
+        assert!(!path.matches_spki_pins(&[]));
+    }
+
+    #[test]
+    fn inverted_cert_validity_period() {
+        use core::time::Duration;
+
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        let mut ee_params = end_entity_params(vec!["example.com".into()]);
+        ee_params.not_before = rcgen::date_time_ymd(2030, 1, 1);
+        ee_params.not_after = rcgen::date_time_ymd(2020, 1, 1);
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(ee_params)
+                .unwrap()
+                .serialize_der_with_signer(&ca)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        // No verification time, whether before, between, or after the two bounds,
+        // makes the certificate valid.
+        for secs in [
+            0,
+            1_577_836_799, // one second before notAfter
+            1_577_836_800, // notAfter
+            1_735_689_600, // between notAfter and notBefore
+            1_893_456_000, // notBefore
+            1_893_456_001, // one second after notBefore
+            u64::from(u32::MAX),
+        ] {
+            let time = UnixTime::since_unix_epoch(Duration::from_secs(secs));
+            let result = ee_cert.verify_for_usage(
+                crate::ALL_VERIFICATION_ALGS,
+                anchors,
+                &[],
+                time,
+                KeyUsage::server_auth(),
+                None,
+                None,
+            );
+            assert!(
+                matches!(result, Err(Error::InvalidValidityPeriod)),
+                "unexpected result at {secs}"
+            );
+
+            // Neither does suppressing the errors of a validity period that merely excludes
+            // the verification time, nor skipping the comparison with it.
+            let verifier = || {
+                ee_cert
+                    .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                    .at_time(time)
+            };
+            let allowed = AllowedErrors::new().cert_expired().cert_not_valid_yet();
+            assert!(
+                matches!(
+                    verifier().dangerous_allow_errors(allowed).verify(),
+                    Err(Error::InvalidValidityPeriod)
+                ),
+                "unexpected result with allowed errors at {secs}"
+            );
+            assert!(
+                matches!(
+                    verifier()
+                        .allow_clock_skew(Duration::from_secs(u64::from(u32::MAX)))
+                        .verify(),
+                    Err(Error::InvalidValidityPeriod)
+                ),
+                "unexpected result with clock skew at {secs}"
+            );
+            assert!(
+                matches!(
+                    verifier().dangerous_ignore_validity_periods().verify(),
+                    Err(Error::InvalidValidityPeriod)
+                ),
+                "unexpected result with ignored validity periods at {secs}"
+            );
+        }
+    }
+
+    #[test]
+    fn clock_skew() {
+        use core::time::Duration;
+
+        const NOT_BEFORE: u64 = 1_735_689_600; // 2025-01-01T00:00:00Z
+        const NOT_AFTER: u64 = 1_767_225_600; // 2026-01-01T00:00:00Z
+        const SKEW: u64 = 300;
+
+        // The intermediate has the narrow validity period, to show that the skew applies to
+        // every certificate in the path and not just the end-entity certificate.
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+        let mut int_params = issuer_params("Test Intermediate");
+        int_params.not_before = rcgen::date_time_ymd(2025, 1, 1);
+        int_params.not_after = rcgen::date_time_ymd(2026, 1, 1);
+        let int = rcgen::Certificate::from_params(int_params).unwrap();
+        let int_der = CertificateDer::from(int.serialize_der_with_signer(&ca).unwrap());
+        let ee_der = make_end_entity(&int);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let intermediates = &[int_der];
+
+        let verify = |secs: u64, skew: Option<u64>| {
+            let verifier = ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(intermediates)
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(secs)));
+            match skew {
+                Some(skew) => verifier.allow_clock_skew(Duration::from_secs(skew)),
+                None => verifier,
+            }
+            .verify()
+            .map(|_| ())
+        };
+
+        let not_before = UnixTime::since_unix_epoch(Duration::from_secs(NOT_BEFORE));
+        let not_after = UnixTime::since_unix_epoch(Duration::from_secs(NOT_AFTER));
+        assert!(matches!(
+            verify(NOT_BEFORE - SKEW - 1, Some(SKEW)),
+            Err(Error::CertNotValidYet { not_before: nb, .. }) if nb == not_before
+        ));
+        assert_eq!(verify(NOT_BEFORE - SKEW, Some(SKEW)), Ok(()));
+        assert_eq!(verify(NOT_AFTER + SKEW, Some(SKEW)), Ok(()));
+        assert!(matches!(
+            verify(NOT_AFTER + SKEW + 1, Some(SKEW)),
+            Err(Error::CertExpired { not_after: na, .. }) if na == not_after
+        ));
+
+        // A zero skew is the same as not allowing any skew.
+        for secs in [
+            NOT_BEFORE - 1,
+            NOT_BEFORE,
+            NOT_AFTER,
+            NOT_AFTER + 1,
+            NOT_AFTER + SKEW,
+        ] {
+            assert_eq!(verify(secs, Some(0)), verify(secs, None), "{secs}");
+        }
+    }
+
+    #[test]
+    fn eku_criticality() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        // rcgen always writes extendedKeyUsage non-critical, so encode it ourselves:
+        // SEQUENCE { id-kp-serverAuth }
+        let make_ee = |critical: bool| {
+            let mut ext = rcgen::CustomExtension::from_oid_content(
+                &[2, 5, 29, 37],
+                vec![
+                    0x30, 0x0a, 0x06, 0x08, 0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x01,
+                ],
+            );
+            ext.set_criticality(critical);
+            let mut ee_params = end_entity_params(vec!["example.com".into()]);
+            ee_params.custom_extensions = vec![ext];
+            CertificateDer::from(
+                rcgen::Certificate::from_params(ee_params)
+                    .unwrap()
+                    .serialize_der_with_signer(&ca)
+                    .unwrap(),
+            )
+        };
+        let critical_der = make_ee(true);
+        let critical = EndEntityCert::try_from(&critical_der).unwrap();
+        let non_critical_der = make_ee(false);
+        let non_critical = EndEntityCert::try_from(&non_critical_der).unwrap();
+        let absent_der = make_end_entity(&ca);
+        let absent = EndEntityCert::try_from(&absent_der).unwrap();
+
+        assert_eq!(critical.extended_key_usage_critical(), Some(true));
+        assert_eq!(non_critical.extended_key_usage_critical(), Some(false));
+        assert_eq!(absent.extended_key_usage_critical(), None);
+
+        let verify = |ee_cert: &EndEntityCert<'_>, eku_criticality| {
+            let policy = VerificationPolicy::default().with_eku_criticality(eku_criticality);
+            verify_with_policy(anchors, &[], ee_cert, policy)
+        };
+        for ee_cert in [&critical, &non_critical, &absent] {
+            assert_eq!(verify(ee_cert, EkuCriticality::Any), Ok(()));
+        }
+        assert_eq!(verify(&critical, EkuCriticality::RequireCritical), Ok(()));
+        assert_eq!(
+            verify(&non_critical, EkuCriticality::RequireCritical),
+            Err(Error::EkuCriticalityMismatch)
+        );
+        assert_eq!(
+            verify(&critical, EkuCriticality::RequireNonCritical),
+            Err(Error::EkuCriticalityMismatch)
+        );
+        assert_eq!(
+            verify(&non_critical, EkuCriticality::RequireNonCritical),
+            Ok(())
+        );
+        // The policy only applies when the extension is present.
+        assert_eq!(verify(&absent, EkuCriticality::RequireCritical), Ok(()));
+        assert_eq!(verify(&absent, EkuCriticality::RequireNonCritical), Ok(()));
+    }
+
+    #[test]
+    fn custom_eku() {
+        // A private enterprise key purpose, 1.3.6.1.4.1.55555.1.
+        static CUSTOM: &[u8] = &oid!(1, 3, 6, 1, 4, 1, 0x83, 0xb2, 0x03, 1);
+
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        let make_ee = |ekus: &[&[u8]]| {
+            let mut content = vec![];
+            for eku in ekus {
+                content.extend([0x06, u8::try_from(eku.len()).unwrap()]);
+                content.extend(*eku);
+            }
+            let mut value = vec![0x30, u8::try_from(content.len()).unwrap()];
+            value.extend(content);
+            let mut ee_params = end_entity_params(vec!["example.com".into()]);
+            if !ekus.is_empty() {
+                ee_params.custom_extensions = vec![rcgen::CustomExtension::from_oid_content(
+                    &[2, 5, 29, 37],
+                    value,
+                )];
+            }
+            CertificateDer::from(
+                rcgen::Certificate::from_params(ee_params)
+                    .unwrap()
+                    .serialize_der_with_signer(&ca)
+                    .unwrap(),
+            )
+        };
+        let verify = |ee_der: &CertificateDer<'_>, usage| {
+            EndEntityCert::try_from(ee_der)
+                .unwrap()
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+                .key_usage(usage)
+                .verify()
+                .map(|_| ())
+        };
+
+        let custom = make_ee(&[EKU_SERVER_AUTH.oid_value.as_slice_less_safe(), CUSTOM]);
+        assert_eq!(verify(&custom, KeyUsage::required(CUSTOM)), Ok(()));
+        assert_eq!(
+            verify(&custom, KeyUsage::required_if_present(CUSTOM)),
+            Ok(())
+        );
+
+        let absent = make_ee(&[]);
+        assert_eq!(
+            verify(&absent, KeyUsage::required(CUSTOM)),
+            Err(Error::RequiredEkuNotFound)
+        );
+        assert_eq!(
+            verify(&absent, KeyUsage::required_if_present(CUSTOM)),
+            Ok(())
+        );
+
+        let other = make_ee(&[EKU_SERVER_AUTH.oid_value.as_slice_less_safe()]);
+        assert_eq!(
+            verify(&other, KeyUsage::required_if_present(CUSTOM)),
+            Err(Error::RequiredEkuNotFound)
+        );
+
+        // anyExtendedKeyUsage, 2.5.29.37.0, has no special meaning.
+        let any = make_ee(&[&oid!(2, 5, 29, 37, 0)]);
+        assert_eq!(
+            verify(&any, KeyUsage::required_if_present(CUSTOM)),
+            Err(Error::RequiredEkuNotFound)
+        );
+    }
+
+    #[test]
+    fn key_identifier_mismatch() {
+        let ca = make_issuer("Test CA");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        // A rolled-over intermediate: the old and new certificates share a subject and key
+        // pair, but derive different subjectKeyIdentifiers from it.
+        let key_pair = rcgen::KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
+        let mut old_params = issuer_params("Intermediate");
+        old_params.key_identifier_method = rcgen::KeyIdMethod::Sha256;
+        old_params.key_pair = Some(rcgen::KeyPair::from_der(&key_pair.serialize_der()).unwrap());
+        let old = rcgen::Certificate::from_params(old_params).unwrap();
+        let old_der = CertificateDer::from(old.serialize_der_with_signer(&ca).unwrap());
+
+        let mut new_params = issuer_params("Intermediate");
+        new_params.key_identifier_method = rcgen::KeyIdMethod::Sha384;
+        new_params.key_pair = Some(key_pair);
+        let new = rcgen::Certificate::from_params(new_params).unwrap();
+        let new_der = CertificateDer::from(new.serialize_der_with_signer(&ca).unwrap());
+
+        // The end-entity's authorityKeyIdentifier points at the old intermediate.
+        let mut ee_params = end_entity_params(vec!["example.com".into()]);
+        ee_params.use_authority_key_identifier_extension = true;
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(ee_params)
+                .unwrap()
+                .serialize_der_with_signer(&old)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        // By default, the key identifiers are not consulted.
+        assert_eq!(
+            verify_with_policy(
+                anchors,
+                core::slice::from_ref(&new_der),
+                &ee_cert,
+                VerificationPolicy::default()
+            ),
+            Ok(())
+        );
+
+        // In strict mode, only the new intermediate is not enough...
+        assert_eq!(
+            verify_with_policy(
+                anchors,
+                core::slice::from_ref(&new_der),
+                &ee_cert,
+                VerificationPolicy::strict()
+            ),
+            Err(Error::KeyIdentifierMismatch)
+        );
+
+        // ... but path building falls back to the old one when it is available.
+        assert_eq!(
+            verify_with_policy(
+                anchors,
+                &[new_der, old_der],
+                &ee_cert,
+                VerificationPolicy::strict()
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn authority_key_identifier_selects_issuer() {
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+        let ee_signed_by = |issuer: &rcgen::Certificate, aki: bool| {
+            let mut params = end_entity_params(vec!["example.com".into()]);
+            params.use_authority_key_identifier_extension = aki;
+            CertificateDer::from(
+                rcgen::Certificate::from_params(params)
+                    .unwrap()
+                    .serialize_der_with_signer(issuer)
+                    .unwrap(),
+            )
+        };
+        let verify = |anchors: &[TrustAnchor<'_>],
+                      intermediates: &[CertificateDer<'_>],
+                      ee: &CertificateDer<'_>,
+                      max_signature_checks: usize| {
+            EndEntityCert::try_from(ee)
+                .unwrap()
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(intermediates)
+                .at_time(time)
+                .max_signature_checks(max_signature_checks)
+                .verify()
+                .map(|path| path.anchor().subject_public_key_info.to_vec())
+        };
+
+        // Two trust anchors sharing a subject, with different keys, as after a key rollover.
+        let old = make_issuer("Rolled Over CA");
+        let new = make_issuer("Rolled Over CA");
+        let old_der = CertificateDer::from(old.serialize_der().unwrap());
+        let new_der = CertificateDer::from(new.serialize_der().unwrap());
+        let anchors = [
+            anchor_from_trusted_cert(&old_der).unwrap(),
+            anchor_from_trusted_cert(&new_der).unwrap(),
+        ];
+
+        // The anchor that the authorityKeyIdentifier identifies is tried first, so its
+        // signature is the only one checked...
+        let ee = ee_signed_by(&new, true);
+        assert_eq!(
+            verify(&anchors, &[], &ee, 1),
+            Ok(anchors[1].subject_public_key_info.to_vec())
+        );
+        // ... whereas without one, the anchors are tried in order.
+        let ee = ee_signed_by(&new, false);
+        assert_eq!(
+            verify(&anchors, &[], &ee, 1),
+            Err(Error::MaximumSignatureChecksExceeded)
+        );
+        assert_eq!(
+            verify(&anchors, &[], &ee, 2),
+            Ok(anchors[1].subject_public_key_info.to_vec())
+        );
+
+        // Likewise for two intermediates sharing a subject, by their subjectKeyIdentifiers.
+        let root = make_issuer("Root");
+        let root_der = CertificateDer::from(root.serialize_der().unwrap());
+        let anchors = [anchor_from_trusted_cert(&root_der).unwrap()];
+        let old = make_issuer("Rolled Over Intermediate");
+        let new = make_issuer("Rolled Over Intermediate");
+        let intermediates = [
+            CertificateDer::from(old.serialize_der_with_signer(&root).unwrap()),
+            CertificateDer::from(new.serialize_der_with_signer(&root).unwrap()),
+        ];
+        let ee = ee_signed_by(&new, true);
+        assert!(verify(&anchors, &intermediates, &ee, 2).is_ok());
+        let ee = ee_signed_by(&new, false);
+        assert_eq!(
+            verify(&anchors, &intermediates, &ee, 2),
+            Err(Error::MaximumSignatureChecksExceeded)
+        );
+    }
+
+    #[test]
+    fn key_usage_mode_crl_sign() {
+        // BIT STRING { digitalSignature, keyCertSign }
+        let ku_without_crl_sign = untrusted::Input::from(&[0x03, 0x02, 0x02, 0x84]);
+        // BIT STRING { digitalSignature, keyCertSign, cRLSign }
+        let ku_with_crl_sign = untrusted::Input::from(&[0x03, 0x02, 0x01, 0x86]);
+
+        assert_eq!(
+            KeyUsageMode::CrlSign.check(Some(ku_without_crl_sign)),
+            Err(Error::IssuerNotCrlSigner)
+        );
+        assert_eq!(KeyUsageMode::CrlSign.check(Some(ku_with_crl_sign)), Ok(()));
+        // Absent KeyUsage (e.g. for a trust anchor) is treated as permitting any usage.
+        assert_eq!(KeyUsageMode::CrlSign.check(None), Ok(()));
+    }
+
+    struct RecordingHook {
+        decision: HookDecision,
+        seen: core::cell::RefCell<Vec<(usize, Result<(), Error>)>>,
+    }
+
+    impl RecordingHook {
+        fn new(decision: HookDecision) -> Self {
+            Self {
+                decision,
+                seen: core::cell::RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl InspectionHook for RecordingHook {
+        fn on_certificate(
+            &self,
+            depth: usize,
+            _: &Cert<'_>,
+            result: Result<(), Error>,
+        ) -> HookDecision {
+            self.seen.borrow_mut().push((depth, result));
+            self.decision
+        }
+    }
+
+    #[test]
+    fn inspection_hook_sees_each_certificate() {
+        let ca = make_issuer("Root");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+        let intermediate = make_issuer("Intermediate");
+        let intermediates = &[CertificateDer::from(
+            intermediate.serialize_der_with_signer(&ca).unwrap(),
+        )];
+        let ee_der = make_end_entity(&intermediate);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        let hook = RecordingHook::new(HookDecision::Continue);
+        assert_eq!(
+            verify_with_hook(anchors, intermediates, &ee_cert, &hook),
+            Ok(())
+        );
+        assert_eq!(*hook.seen.borrow(), vec![(0, Ok(())), (1, Ok(()))]);
+    }
+
+    #[test]
+    fn inspection_hook_overrides_expiry() {
+        let ca = make_issuer("Root");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        let mut ee_params = end_entity_params(vec!["example.com".into()]);
+        ee_params.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(ee_params)
+                .unwrap()
+                .serialize_der_with_signer(&ca)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        let expired = Error::CertExpired {
+            time: UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)),
+            not_after: UnixTime::since_unix_epoch(Duration::from_secs(315_532_800)), // 1980-01-01
+        };
+        let logging = RecordingHook::new(HookDecision::Continue);
+        assert_eq!(
+            verify_with_hook(anchors, &[], &ee_cert, &logging),
+            Err(expired)
+        );
+        assert_eq!(*logging.seen.borrow(), vec![(0, Err(expired))]);
+
+        let accept = RecordingHook::new(HookDecision::OverrideAccept);
+        assert_eq!(verify_with_hook(anchors, &[], &ee_cert, &accept), Ok(()));
+
+        let reject = RecordingHook::new(HookDecision::Reject(Error::CertRevoked));
+        assert_eq!(
+            verify_with_hook(anchors, &[], &ee_cert, &reject),
+            Err(Error::CertRevoked)
+        );
+    }
+
+    #[test]
+    fn inspection_hook_cannot_override_other_errors() {
+        let ca = make_issuer("Root");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+        let accept = RecordingHook::new(HookDecision::OverrideAccept);
+
+        // An end-entity signed by a different key with the same name as the trust anchor.
+        let impostor = make_issuer("Root");
+        let ee_der = make_end_entity(&impostor);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        assert_eq!(
+            verify_with_hook(anchors, &[], &ee_cert, &accept),
+            Err(Error::InvalidSignatureForPublicKey)
+        );
+
+        // Overriding expiry doesn't skip the certificate's other checks.
+        let mut ee_params = end_entity_params(vec!["example.com".into()]);
+        ee_params.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        ee_params.extended_key_usages = vec![rcgen::ExtendedKeyUsagePurpose::ClientAuth];
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(ee_params)
+                .unwrap()
+                .serialize_der_with_signer(&ca)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        assert_eq!(
+            verify_with_hook(anchors, &[], &ee_cert, &accept),
+            Err(Error::RequiredEkuNotFound)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn enforced_name_constraints_from_anchor_fixture() {
+        let ca_der = CertificateDer::from(
+            &include_bytes!(
+                "../tests/tls_server_certs/ip4_address_san_allowed_if_outside_excluded_subtree.ca.der"
+            )[..],
+        );
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+        let ee_der = CertificateDer::from(
+            &include_bytes!(
+                "../tests/tls_server_certs/ip4_address_san_allowed_if_outside_excluded_subtree.ee.der"
+            )[..],
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        let path = verify_chain(anchors, &[], &ee_cert, None, None).unwrap();
+        let enforced = path.name_constraints().unwrap();
+        assert_eq!(enforced.permitted().count(), 0);
+        assert_eq!(
+            enforced.excluded().collect::<Vec<_>>(),
+            vec![&NameSubtree {
+                kind: SubtreeKind::Excluded,
+                source: ConstraintSource::TrustAnchor,
+                base: ConstraintBase::IpAddress {
+                    address: &[12, 34, 56, 252],
+                    mask: &[255, 255, 255, 252],
+                },
+                der: &[0x87, 8, 12, 34, 56, 252, 255, 255, 255, 252],
+            }]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn enforced_name_constraints_accumulate_along_path() {
+        let mut root_params = issuer_params("Root");
+        root_params.name_constraints = Some(rcgen::NameConstraints {
+            permitted_subtrees: vec![rcgen::GeneralSubtree::DnsName("corp.example".into())],
+            excluded_subtrees: vec![],
+        });
+        let root = rcgen::Certificate::from_params(root_params).unwrap();
+        let root_der = CertificateDer::from(root.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&root_der).unwrap()];
+
+        let mut inter_params = issuer_params("Intermediate");
+        inter_params.name_constraints = Some(rcgen::NameConstraints {
+            permitted_subtrees: vec![],
+            excluded_subtrees: vec![rcgen::GeneralSubtree::IpAddress(rcgen::CidrSubnet::V4(
+                [10, 0, 0, 0],
+                [255, 0, 0, 0],
+            ))],
+        });
+        let inter = rcgen::Certificate::from_params(inter_params).unwrap();
+        let inter_der = CertificateDer::from(inter.serialize_der_with_signer(&root).unwrap());
+
+        let ee =
+            rcgen::Certificate::from_params(end_entity_params(vec!["host.corp.example".into()]))
+                .unwrap();
+        let ee_der = CertificateDer::from(ee.serialize_der_with_signer(&inter).unwrap());
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+
+        let intermediates = [inter_der];
+        let path = verify_chain(anchors, &intermediates, &ee_cert, None, None).unwrap();
+        let enforced = path.name_constraints().unwrap();
+        let subtrees = enforced
+            .subtrees()
+            .iter()
+            .map(|subtree| (subtree.kind, subtree.source, subtree.base))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            subtrees,
+            vec![
+                (
+                    SubtreeKind::Permitted,
+                    ConstraintSource::TrustAnchor,
+                    ConstraintBase::DnsName(b"corp.example"),
+                ),
+                (
+                    SubtreeKind::Excluded,
+                    ConstraintSource::Intermediate(0),
+                    ConstraintBase::IpAddress {
+                        address: &[10, 0, 0, 0],
+                        mask: &[255, 0, 0, 0],
+                    },
+                ),
+            ]
+        );
+
+        // A path without any constraints enforces none.
+        let plain = make_issuer("Plain");
+        let plain_der = CertificateDer::from(plain.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&plain_der).unwrap()];
+        let ee_der = make_end_entity(&plain);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let path = verify_chain(anchors, &[], &ee_cert, None, None).unwrap();
+        assert!(path.name_constraints().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn cross_signed_paths() {
+        // Mirrors the ISRG Root X1 / DST Root CA X3 topology: "ISRG Root X1" is both a root
+        // and an intermediate cross-signed by "DST Root CA X3", with the same key.
+        let isrg_key = rcgen::KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
+        let isrg_params = || {
+            let mut params = issuer_params("ISRG Root X1");
+            params.key_pair = Some(rcgen::KeyPair::from_der(&isrg_key.serialize_der()).unwrap());
+            params
         };
-        // Validation should fail when the budget is smaller than the number of comparisons performed
-        // on the validated path. This demonstrates we properly fail path building when too many
-        // name constraint comparisons occur.
-        let result = verify_chain(
-            anchors,
-            &intermediates_der,
-            &ee_cert,
-            None,
-            Some(failing_budget),
+        let isrg = rcgen::Certificate::from_params(isrg_params()).unwrap();
+        let isrg_der = CertificateDer::from(isrg.serialize_der().unwrap());
+        let isrg_anchor = anchor_from_trusted_cert(&isrg_der).unwrap();
+
+        let dst = make_issuer("DST Root CA X3");
+        let dst_der = CertificateDer::from(dst.serialize_der().unwrap());
+        let dst_anchor = anchor_from_trusted_cert(&dst_der).unwrap();
+
+        let cross = rcgen::Certificate::from_params(isrg_params()).unwrap();
+        let cross_der = CertificateDer::from(cross.serialize_der_with_signer(&dst).unwrap());
+
+        // A root with the same name as ISRG Root X1, but a different key.
+        let rotated = make_issuer("ISRG Root X1");
+        let rotated_der = CertificateDer::from(rotated.serialize_der().unwrap());
+        let rotated_anchor = anchor_from_trusted_cert(&rotated_der).unwrap();
+
+        let r3 = make_issuer("R3");
+        let r3_der = CertificateDer::from(r3.serialize_der_with_signer(&isrg).unwrap());
+        let ee_der = make_end_entity(&r3);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let intermediates = [r3_der, cross_der];
+
+        // With ISRG Root X1 trusted, the path terminates there.
+        let anchors = [isrg_anchor.clone(), dst_anchor.clone()];
+        let path = verify_chain(&anchors, &intermediates, &ee_cert, None, None).unwrap();
+        assert_eq!(path.intermediate_certificates().count(), 1);
+        assert!(!path.is_cross_signed());
+        let anchors = [dst_anchor.clone(), isrg_anchor.clone()];
+        let path = verify_chain(&anchors, &intermediates, &ee_cert, None, None).unwrap();
+        assert!(!path.is_cross_signed());
+
+        // With ISRG Root X1 replaced by a root it cannot chain to, the cross-signature is used.
+        let anchors = [rotated_anchor.clone(), dst_anchor.clone()];
+        let path = verify_chain(&anchors, &intermediates, &ee_cert, None, None).unwrap();
+        assert_eq!(path.intermediate_certificates().count(), 2);
+        assert_eq!(path.anchor().subject, dst_anchor.subject);
+        assert!(path.is_cross_signed());
+
+        // Without any ISRG Root X1 anchor the cross-signature can't be distinguished from an
+        // ordinary intermediate.
+        let anchors = [dst_anchor];
+        let path = verify_chain(&anchors, &intermediates, &ee_cert, None, None).unwrap();
+        assert_eq!(path.intermediate_certificates().count(), 2);
+        assert!(!path.is_cross_signed());
+
+        // A later anchor with the same subject as the preferred one was used.
+        let anchors = [rotated_anchor, isrg_anchor];
+        let path = verify_chain(&anchors, &intermediates[..1], &ee_cert, None, None).unwrap();
+        assert!(path.is_cross_signed());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn trust_anchor_source() {
+        use std::collections::HashMap;
+
+        struct BySubject<'a>(HashMap<Vec<u8>, Vec<TrustAnchor<'a>>>);
+
+        impl TrustAnchorSource for BySubject<'_> {
+            fn anchors_for_subject(&self, subject: &[u8]) -> &[TrustAnchor<'_>] {
+                self.0.get(subject).map_or(&[], |anchors| anchors)
+            }
+        }
+
+        impl<'a> FromIterator<TrustAnchor<'a>> for BySubject<'a> {
+            fn from_iter<T: IntoIterator<Item = TrustAnchor<'a>>>(iter: T) -> Self {
+                let mut map = HashMap::<_, Vec<_>>::new();
+                for anchor in iter {
+                    map.entry(anchor.subject.to_vec()).or_default().push(anchor);
+                }
+                Self(map)
+            }
+        }
+
+        let root = make_issuer("Root");
+        let root_der = CertificateDer::from(root.serialize_der().unwrap());
+        let root_anchor = anchor_from_trusted_cert(&root_der).unwrap();
+        let other = make_issuer("Other Root");
+        let other_der = CertificateDer::from(other.serialize_der().unwrap());
+        let other_anchor = anchor_from_trusted_cert(&other_der).unwrap();
+
+        let int = make_issuer("Intermediate");
+        let int_der = CertificateDer::from(int.serialize_der_with_signer(&root).unwrap());
+        let ee_der = make_end_entity(&int);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let intermediates = [int_der];
+
+        let verify = |source: &dyn TrustAnchorSource| {
+            ee_cert
+                .verifier_with_anchor_source(crate::ALL_VERIFICATION_ALGS, source)
+                .intermediates(&intermediates)
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+                .verify()
+                .map(|path| (path.anchor().subject.to_vec(), path.is_cross_signed()))
+        };
+
+        let source = [other_anchor.clone(), root_anchor.clone()]
+            .into_iter()
+            .collect::<BySubject<'_>>();
+        assert_eq!(verify(&source), Ok((root_anchor.subject.to_vec(), false)));
+        // Slices are sources too.
+        let anchors = [other_anchor.clone(), root_anchor.clone()];
+        assert_eq!(
+            verify(&&anchors[..]),
+            Ok((root_anchor.subject.to_vec(), false))
         );
 
-        assert!(matches!(
-            result,
-            Err(ControlFlow::Break(
-                Error::MaximumNameConstraintComparisonsExceeded
-            ))
-        ));
+        let source = [other_anchor.clone()]
+            .into_iter()
+            .collect::<BySubject<'_>>();
+        assert_eq!(verify(&source), Err(Error::UnknownIssuer));
+
+        // An anchor with the intermediate's name, but another key, is found by looking up the
+        // intermediate's subject.
+        let rotated = TrustAnchor {
+            subject_public_key_info: other_anchor.subject_public_key_info,
+            ..anchor_from_trusted_cert(&intermediates[0]).unwrap()
+        };
+        let source = [rotated, root_anchor.clone()]
+            .into_iter()
+            .collect::<BySubject<'_>>();
+        assert_eq!(verify(&source), Ok((root_anchor.subject.to_vec(), true)));
     }
 
     #[test]
-    fn test_reject_candidate_path() {
-        /*
-         This test builds a PKI like the following diagram depicts. We first verify
-         that we can build a path EE -> B -> A -> TA. Next we supply a custom path verification
-         function that rejects the B->A path, and verify that we build a path EE -> B -> C -> TA.
+    fn trust_anchor_selection() {
+        use core::time::Duration;
 
-               ┌───────────┐
-               │           │
-               │     TA    │
-               │           │
-               └───┬───┬───┘
-                   │   │
-                   │   │
-        ┌────────┐◄┘   └──►┌────────┐
-        │        │         │        │
-        │   A    │         │   C    │
-        │        │         │        │
-        └────┬───┘         └───┬────┘
-             │                 │
-             │                 │
-             │   ┌─────────┐   │
-             └──►│         │◄──┘
-                 │    B    │
-                 │         │
-                 └────┬────┘
-                      │
-                      │
-                      │
-                 ┌────▼────┐
-                 │         │
-                 │    EE   │
-                 │         │
-                 └─────────┘
-          */
+        // "Internal Root" is trusted directly, and also cross-signed by "Public Root".
+        let internal_key = rcgen::KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
+        let internal_params = || {
+            let mut params = issuer_params("Internal Root");
+            params.key_pair =
+                Some(rcgen::KeyPair::from_der(&internal_key.serialize_der()).unwrap());
+            params
+        };
+        let internal = rcgen::Certificate::from_params(internal_params()).unwrap();
+        let internal_der = CertificateDer::from(internal.serialize_der().unwrap());
+        let internal_anchor = anchor_from_trusted_cert(&internal_der).unwrap();
+
+        let public = make_issuer("Public Root");
+        let public_der = CertificateDer::from(public.serialize_der().unwrap());
+        let public_anchor = anchor_from_trusted_cert(&public_der).unwrap();
+
+        let cross = rcgen::Certificate::from_params(internal_params()).unwrap();
+        let cross_der = CertificateDer::from(cross.serialize_der_with_signer(&public).unwrap());
+        // The cross-signed certificate also makes a trust anchor for "Internal Root".
+        let cross_anchor = anchor_from_trusted_cert(&cross_der).unwrap();
+
+        let issuing = make_issuer("Issuing CA");
+        let issuing_der =
+            CertificateDer::from(issuing.serialize_der_with_signer(&internal).unwrap());
+        let ee_der = make_end_entity(&issuing);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let intermediates = [issuing_der, cross_der.clone()];
+
+        fn verify<'a>(
+            ee_cert: &'a EndEntityCert<'a>,
+            anchors: &'a [TrustAnchor<'a>],
+            intermediates: &'a [CertificateDer<'a>],
+            filter: Option<&dyn Fn(&TrustAnchor<'_>) -> bool>,
+        ) -> Result<VerifiedPath<'a>, Error> {
+            let verifier = ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(intermediates)
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)));
+            match filter {
+                Some(filter) => verifier.anchor_filter(filter).verify(),
+                None => verifier.verify(),
+            }
+        }
 
-        // Create a trust anchor, and use it to issue two distinct intermediate certificates, each
-        // with a unique subject and keypair.
-        let trust_anchor = make_issuer("Trust Anchor");
-        let trust_anchor_der = CertificateDer::from(trust_anchor.serialize_der().unwrap());
-        let trust_anchor_cert =
-            Cert::from_der(untrusted::Input::from(trust_anchor_der.as_ref())).unwrap();
-        let trust_anchors = &[anchor_from_trusted_cert(&trust_anchor_der).unwrap()];
+        // The nearest trust anchor terminates the path, whatever the order.
+        for anchors in [
+            [internal_anchor.clone(), public_anchor.clone()],
+            [public_anchor.clone(), internal_anchor.clone()],
+        ] {
+            let path = verify(&ee_cert, &anchors, &intermediates, None).unwrap();
+            assert_eq!(path.anchor().subject, internal_anchor.subject);
+            assert_eq!(path.intermediate_certificates().count(), 1);
+        }
 
-        let intermediate_a = make_issuer("Intermediate A");
-        let intermediate_a_der = CertificateDer::from(
-            intermediate_a
-                .serialize_der_with_signer(&trust_anchor)
-                .unwrap(),
+        // Among trust anchors equally near, the earliest is used.
+        let anchors = [internal_anchor.clone(), cross_anchor.clone()];
+        assert!(core::ptr::eq(
+            verify(&ee_cert, &anchors, &intermediates, None)
+                .unwrap()
+                .anchor(),
+            &anchors[0]
+        ));
+        let anchors = [cross_anchor, internal_anchor.clone()];
+        assert!(core::ptr::eq(
+            verify(&ee_cert, &anchors, &intermediates, None)
+                .unwrap()
+                .anchor(),
+            &anchors[0]
+        ));
+
+        // Filtering out the nearest trust anchor makes path building use the cross-signature.
+        let anchors = [internal_anchor.clone(), public_anchor.clone()];
+        let not_internal = |anchor: &TrustAnchor<'_>| anchor.subject != internal_anchor.subject;
+        let path = verify(&ee_cert, &anchors, &intermediates, Some(&not_internal)).unwrap();
+        assert!(core::ptr::eq(path.anchor(), &anchors[1]));
+        assert_eq!(path.intermediate_certificates().count(), 2);
+
+        let none = |_: &TrustAnchor<'_>| false;
+        assert_eq!(
+            verify(&ee_cert, &anchors, &intermediates, Some(&none)).map(|_| ()),
+            Err(Error::UnknownIssuer)
         );
-        let intermediate_a_cert =
-            Cert::from_der(untrusted::Input::from(intermediate_a_der.as_ref())).unwrap();
+    }
 
-        let intermediate_c = make_issuer("Intermediate C");
-        let intermediate_c_der = CertificateDer::from(
-            intermediate_c
-                .serialize_der_with_signer(&trust_anchor)
-                .unwrap(),
+    #[test]
+    fn detailed_errors() {
+        use core::time::Duration;
+
+        let root = make_issuer("Root");
+        let root_der = CertificateDer::from(root.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&root_der).unwrap()];
+        // Has the same name as "Root", but a different key.
+        let impostor = make_issuer("Root");
+
+        let sign = |params: rcgen::CertificateParams, issuer: &rcgen::Certificate| {
+            let cert = rcgen::Certificate::from_params(params).unwrap();
+            let der = CertificateDer::from(cert.serialize_der_with_signer(issuer).unwrap());
+            (cert, der)
+        };
+        let (int, int_der) = sign(issuer_params("Intermediate"), &root);
+        let (_, bad_int_der) = sign(issuer_params("Intermediate"), &impostor);
+        let mut expired = issuer_params("Intermediate");
+        expired.not_after = rcgen::date_time_ymd(1980, 1, 1);
+        let (_, expired_int_der) = sign(expired, &root);
+        let (other_int, other_int_der) = sign(
+            issuer_params("Other Intermediate"),
+            &make_issuer("Other Root"),
         );
-        let intermediate_c_cert =
-            Cert::from_der(untrusted::Input::from(intermediate_c_der.as_ref())).unwrap();
 
-        // Next, create an intermediate that is issued by both of the intermediates above.
-        // Both should share the same subject, and key pair, but will differ in the issuer.
-        let intermediate_b_key = rcgen::KeyPair::generate(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
-        let mut intermediate_b_params = issuer_params("Intermediate");
-        intermediate_b_params.key_pair = Some(intermediate_b_key);
-        let intermediate_b = rcgen::Certificate::from_params(intermediate_b_params).unwrap();
+        let ee_der = make_end_entity(&int);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        // Names "Intermediate" as its issuer, but isn't signed by it.
+        let bad_ee_der = make_end_entity(&make_issuer("Intermediate"));
+        let bad_ee_cert = EndEntityCert::try_from(&bad_ee_der).unwrap();
+        let orphan_ee_der = make_end_entity(&other_int);
+        let orphan_ee_cert = EndEntityCert::try_from(&orphan_ee_der).unwrap();
+
+        let verify = |ee: &EndEntityCert<'_>, intermediates: &[CertificateDer<'_>]| {
+            ee.verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(intermediates)
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+                .verify_detailed()
+                .map(|_| ())
+                .map_err(|err| {
+                    (
+                        err.error,
+                        err.cert.map(|cert| (cert.depth, cert.der.to_vec())),
+                    )
+                })
+        };
 
-        let intermediate_b_a_der = CertificateDer::from(
-            intermediate_b
-                .serialize_der_with_signer(&intermediate_a)
-                .unwrap(),
+        assert_eq!(verify(&ee_cert, core::slice::from_ref(&int_der)), Ok(()));
+        assert_eq!(
+            verify(&bad_ee_cert, core::slice::from_ref(&int_der)),
+            Err((
+                Error::InvalidSignatureForPublicKey,
+                Some((0, bad_ee_der.to_vec()))
+            ))
         );
-        let intermediate_b_c_der = CertificateDer::from(
-            intermediate_b
-                .serialize_der_with_signer(&intermediate_c)
-                .unwrap(),
+        assert_eq!(
+            verify(&ee_cert, core::slice::from_ref(&bad_int_der)),
+            Err((
+                Error::InvalidSignatureForPublicKey,
+                Some((1, bad_int_der.to_vec()))
+            ))
+        );
+        assert!(matches!(
+            verify(&ee_cert, core::slice::from_ref(&expired_int_der)),
+            Err((Error::CertExpired { .. }, Some((1, der)))) if der == expired_int_der.as_ref()
+        ));
+        // No issuer is found for the intermediate.
+        assert_eq!(
+            verify(&orphan_ee_cert, core::slice::from_ref(&other_int_der)),
+            Err((Error::UnknownIssuer, Some((1, other_int_der.to_vec()))))
+        );
+        // No issuer is found for the end-entity certificate.
+        assert_eq!(
+            verify(&orphan_ee_cert, &[]),
+            Err((Error::UnknownIssuer, Some((0, orphan_ee_der.to_vec()))))
         );
 
-        let intermediates = &[
-            intermediate_a_der.clone(),
-            intermediate_c_der.clone(),
-            intermediate_b_a_der.clone(),
-            intermediate_b_c_der.clone(),
-        ];
+        // The plain error is unchanged.
+        assert_eq!(
+            ee_cert
+                .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+                .intermediates(&[bad_int_der])
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+                .verify()
+                .map(|_| ()),
+            Err(Error::InvalidSignatureForPublicKey)
+        );
 
-        // Create an end entity certificate signed by the keypair of the intermediates created above.
-        let ee = make_end_entity(&intermediate_b);
-        let ee_cert = &EndEntityCert::try_from(&ee).unwrap();
+        // An error from the `verify_path` callback isn't attributed to any certificate.
+        let reject = |_: &VerifiedPath<'_>| Err(Error::RequiredEkuNotFound);
+        let intermediates = [int_der];
+        let result = ee_cert
+            .verifier(crate::ALL_VERIFICATION_ALGS, anchors)
+            .intermediates(&intermediates)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+            .verify_path(&reject)
+            .verify_detailed();
+        assert_eq!(
+            result.map(|_| ()),
+            Err(DetailedError {
+                error: Error::RequiredEkuNotFound,
+                cert: None,
+            })
+        );
+    }
 
-        // We should be able to create a valid path from EE to trust anchor.
-        let path = verify_chain(trust_anchors, intermediates, ee_cert, None, None).unwrap();
-        let path_intermediates = path.intermediate_certificates().collect::<Vec<_>>();
+    #[test]
+    fn subject_matched_issuer_with_bad_signature() {
+        let ca = make_issuer("Test CA");
+        let ee_der = make_end_entity(&ca);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
 
-        // We expect that without applying any additional constraints, that the path will be
-        // EE -> intermediate_b_a -> intermediate_a -> trust_anchor.
-        assert_eq!(path_intermediates.len(), 2);
+        // A root with the issuer's name, but a different key.
+        let imposter = make_issuer("Test CA");
+        let imposter_der = CertificateDer::from(imposter.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&imposter_der).unwrap()];
         assert_eq!(
-            path_intermediates[0].issuer(),
-            intermediate_a_cert.subject()
+            verify_with_policy(anchors, &[], &ee_cert, VerificationPolicy::default()),
+            Err(Error::InvalidSignatureForPublicKey)
         );
-        assert_eq!(path_intermediates[1].issuer(), trust_anchor_cert.subject());
 
-        // Now, we'll create a function that will reject the intermediate_b_a path.
-        let expected_chain = |path: &VerifiedPath<'_>| {
-            for intermediate in path.intermediate_certificates() {
-                // Reject any intermediates issued by intermediate A.
-                if intermediate.issuer() == intermediate_a_cert.subject() {
-                    return Err(Error::UnknownIssuer);
-                }
-            }
+        // The same for an intermediate, issued by a trusted root.
+        let root = make_issuer("Root");
+        let root_der = CertificateDer::from(root.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&root_der).unwrap()];
+        let imposter_der = CertificateDer::from(imposter.serialize_der_with_signer(&root).unwrap());
+        assert_eq!(
+            verify_with_policy(
+                anchors,
+                &[imposter_der],
+                &ee_cert,
+                VerificationPolicy::default()
+            ),
+            Err(Error::InvalidSignatureForPublicKey)
+        );
 
-            Ok(())
+        // Without any candidate with the issuer's name, the issuer is unknown.
+        assert_eq!(
+            verify_with_policy(anchors, &[], &ee_cert, VerificationPolicy::default()),
+            Err(Error::UnknownIssuer)
+        );
+    }
+
+    #[test]
+    fn revocation_endpoints() {
+        fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+            let mut out = vec![tag, u8::try_from(contents.len()).unwrap()];
+            out.extend_from_slice(contents);
+            out
+        }
+
+        // SEQUENCE OF AccessDescription, with id-ad-ocsp (1) or id-ad-caIssuers (2) methods.
+        let aia = |descriptions: &[(u8, u8, &str)]| {
+            let descriptions = descriptions
+                .iter()
+                .map(|(method, tag, location)| {
+                    let mut description =
+                        tlv(0x06, &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, *method]);
+                    description.extend(tlv(*tag, location.as_bytes()));
+                    tlv(0x30, &description)
+                })
+                .collect::<Vec<_>>();
+            rcgen::CustomExtension::from_oid_content(
+                &[1, 3, 6, 1, 5, 5, 7, 1, 1],
+                tlv(0x30, &descriptions.concat()),
+            )
+        };
+        let crldp = |uri: &str| rcgen::CrlDistributionPoint {
+            uris: vec![uri.into()],
         };
 
-        // We should still be able to build a valid path.
-        let path = verify_chain(
-            trust_anchors,
-            intermediates,
-            ee_cert,
-            Some(&expected_chain),
-            None,
-        )
-        .unwrap();
-        let path_intermediates = path.intermediate_certificates().collect::<Vec<_>>();
+        let ca = make_issuer("Root");
+        let ca_der = CertificateDer::from(ca.serialize_der().unwrap());
+        let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+
+        let mut int_params = issuer_params("Intermediate");
+        int_params.crl_distribution_points = vec![crldp("http://root.example/crl")];
+        int_params.custom_extensions = vec![aia(&[
+            (1, 0x86, "http://ocsp.root.example"),
+            (2, 0x86, "http://root.example/ca"),
+        ])];
+        let int = rcgen::Certificate::from_params(int_params).unwrap();
+        let int_der = CertificateDer::from(int.serialize_der_with_signer(&ca).unwrap());
+
+        let mut ee_params = end_entity_params(vec!["example.com".into()]);
+        ee_params.crl_distribution_points = vec![
+            crldp("http://int.example/crl"),
+            crldp("ldap://int.example/crl"),
+        ];
+        ee_params.custom_extensions = vec![aia(&[
+            (1, 0x86, "http://ocsp.int.example"),
+            // A dNSName location is skipped.
+            (1, 0x82, "ocsp.int.example"),
+            (2, 0x86, "http://int.example/ca"),
+        ])];
+        let ee_der = CertificateDer::from(
+            rcgen::Certificate::from_params(ee_params)
+                .unwrap()
+                .serialize_der_with_signer(&int)
+                .unwrap(),
+        );
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        assert_eq!(
+            ee_cert.ca_issuers_uris().collect::<Vec<_>>(),
+            ["http://int.example/ca"]
+        );
 
-        // We expect that the path will now be
-        // EE -> intermediate_b_c -> intermediate_c -> trust_anchor.
-        assert_eq!(path_intermediates.len(), 2);
+        let intermediates = [int_der];
+        let path = verify_chain(anchors, &intermediates, &ee_cert, None, None).unwrap();
         assert_eq!(
-            path_intermediates[0].issuer(),
-            intermediate_c_cert.subject()
+            path.revocation_endpoints(),
+            [
+                RevocationEndpoints {
+                    ocsp: vec!["http://ocsp.int.example"],
+                    crl_distribution_points: vec![
+                        "http://int.example/crl",
+                        "ldap://int.example/crl",
+                    ],
+                },
+                RevocationEndpoints {
+                    ocsp: vec!["http://ocsp.root.example"],
+                    crl_distribution_points: vec!["http://root.example/crl"],
+                },
+            ]
         );
-        assert_eq!(path_intermediates[1].issuer(), trust_anchor_cert.subject());
+
+        // A certificate without either extension has no endpoints.
+        let ee_der = make_end_entity(&ca);
+        let ee_cert = EndEntityCert::try_from(&ee_der).unwrap();
+        let path = verify_chain(anchors, &[], &ee_cert, None, None).unwrap();
+        assert_eq!(
+            path.revocation_endpoints(),
+            [RevocationEndpoints::default()]
+        );
+    }
+
+    fn verify_with_hook(
+        trust_anchors: &[TrustAnchor<'_>],
+        intermediate_certs: &[CertificateDer<'_>],
+        ee_cert: &EndEntityCert<'_>,
+        hook: &dyn InspectionHook,
+    ) -> Result<(), Error> {
+        use core::time::Duration;
+
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+        ee_cert
+            .verifier(crate::ALL_VERIFICATION_ALGS, trust_anchors)
+            .intermediates(intermediate_certs)
+            .at_time(time)
+            .hook(hook)
+            .verify()
+            .map(|_| ())
+    }
+
+    fn verify_with_policy(
+        trust_anchors: &[TrustAnchor<'_>],
+        intermediate_certs: &[CertificateDer<'_>],
+        ee_cert: &EndEntityCert<'_>,
+        policy: VerificationPolicy,
+    ) -> Result<(), Error> {
+        use core::time::Duration;
+
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+        ee_cert
+            .verifier(crate::ALL_VERIFICATION_ALGS, trust_anchors)
+            .intermediates(intermediate_certs)
+            .at_time(time)
+            .policy(policy)
+            .verify()
+            .map(|_| ())
     }
 
     fn verify_chain<'a>(
@@ -1082,9 +5194,23 @@ mod tests {
         let opts = ChainOptions {
             eku: KeyUsage::server_auth(),
             supported_sig_algs: crate::ALL_VERIFICATION_ALGS,
-            trust_anchors,
-            intermediate_certs,
+            trust_anchors: trust_anchors.into(),
+            intermediate_certs: &SuppliedIntermediates::Der(intermediate_certs),
             revocation: None,
+            policy: VerificationPolicy::default(),
+            anchor_filter: None,
+            hook: None,
+            max_sub_ca_count: MAX_SUB_CA_COUNT,
+            budget: Budget::default(),
+            clock_skew: Duration::ZERO,
+            freshness_time: None,
+            required_policies: &[],
+            ignored_extensions: &[],
+            allowed_errors: AllowedErrors::new(),
+            allow_unknown_issuer: false,
+            ignore_validity_periods: false,
+            allow_v1: false,
+            failure: Cell::new(None),
         };
 
         match opts.build_chain_inner(
@@ -1094,7 +5220,16 @@ mod tests {
             0,
             &mut budget.unwrap_or_default(),
         ) {
-            Ok(anchor) => Ok(VerifiedPath::new(ee_cert, anchor, path)),
+            Ok(Some(anchor)) => Ok(VerifiedPath::new(
+                ee_cert,
+                anchor,
+                trust_anchors.into(),
+                path,
+                time,
+                time,
+            )),
+            // Only accepted with `allow_unknown_issuer`.
+            Ok(None) => Err(Error::UnknownIssuer.into()),
             Err(err) => Err(err),
         }
     }
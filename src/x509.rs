@@ -31,6 +31,70 @@ impl<'a> Extension<'a> {
     }
 }
 
+/// An extension of a certificate, as yielded by [`Extensions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RawExtension<'a> {
+    /// The extension's OID, as the value of the OBJECT IDENTIFIER, without its tag and length.
+    pub oid: &'a [u8],
+    /// Whether the extension is marked critical.
+    pub critical: bool,
+    /// The contents of the extnValue OCTET STRING: the DER encoding of the extension's value.
+    pub value: &'a [u8],
+}
+
+/// An iterator over the extensions of a certificate, in the order they appear, returned by
+/// `Cert::extensions()`.
+///
+/// A malformed extension yields an error, after which the iteration ends.
+#[derive(Clone, Debug)]
+pub struct Extensions<'a> {
+    // The extensions not yet yielded, or the error to yield next. `None` once iteration ends.
+    remaining: Option<Result<untrusted::Input<'a>, Error>>,
+}
+
+impl<'a> Extensions<'a> {
+    /// Iterates the extensions of `tagged`, the contents of the `[3] EXPLICIT` extensions field
+    /// of a TBSCertificate.
+    pub(crate) fn new(tagged: Option<untrusted::Input<'a>>) -> Self {
+        Self {
+            remaining: tagged.map(|tagged| {
+                tagged.read_all(
+                    Error::TrailingData(DerTypeId::CertificateExtensions),
+                    |tagged| der::expect_tag(tagged, der::Tag::Sequence),
+                )
+            }),
+        }
+    }
+}
+
+impl<'a> Iterator for Extensions<'a> {
+    type Item = Result<RawExtension<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = match self.remaining.take()? {
+            Ok(remaining) if remaining.is_empty() => return None,
+            Ok(remaining) => remaining,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let mut reader = untrusted::Reader::new(remaining);
+        let result = der::nested(
+            &mut reader,
+            der::Tag::Sequence,
+            Error::TrailingData(DerTypeId::Extension),
+            Extension::from_der,
+        );
+        Some(result.map(|extension| {
+            self.remaining = Some(Ok(reader.read_bytes_to_end()));
+            RawExtension {
+                oid: extension.id.as_slice_less_safe(),
+                critical: extension.critical,
+                value: extension.value.as_slice_less_safe(),
+            }
+        }))
+    }
+}
+
 impl<'a> FromDer<'a> for Extension<'a> {
     fn from_der(reader: &mut untrusted::Reader<'a>) -> Result<Self, Error> {
         let id = der::expect_tag(reader, der::Tag::OID)?;
@@ -86,7 +150,7 @@ pub(crate) fn remember_extension(
 /// [^1]: <https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.13>
 pub(crate) enum DistributionPointName<'a> {
     /// The distribution point name is a relative distinguished name, relative to the CRL issuer.
-    NameRelativeToCrlIssuer(untrusted::Input<'a>),
+    NameRelativeToCrlIssuer(#[allow(dead_code)] untrusted::Input<'a>),
     /// The distribution point name is a sequence of [GeneralName] items.
     FullName(DerIterator<'a, GeneralName<'a>>),
 }
@@ -111,3 +175,58 @@ impl<'a> FromDer<'a> for DistributionPointName<'a> {
 
     const TYPE_ID: DerTypeId = DerTypeId::DistributionPointName;
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn extensions() {
+        // SEQUENCE { SEQUENCE { OID 2.5.29.19, BOOLEAN TRUE, OCTET STRING { SEQUENCE {} } },
+        //            SEQUENCE { OID 1.2.3, OCTET STRING { NULL } } }
+        let tagged = [
+            0x30, 0x18, 0x30, 0x0c, 0x06, 0x03, 0x55, 0x1d, 0x13, 0x01, 0x01, 0xff, 0x04, 0x02,
+            0x30, 0x00, 0x30, 0x08, 0x06, 0x02, 0x2a, 0x03, 0x04, 0x02, 0x05, 0x00,
+        ];
+        let extensions = Extensions::new(Some(untrusted::Input::from(&tagged)));
+        assert_eq!(
+            extensions.collect::<Vec<_>>(),
+            [
+                Ok(RawExtension {
+                    oid: &[0x55, 0x1d, 0x13],
+                    critical: true,
+                    value: &[0x30, 0x00],
+                }),
+                Ok(RawExtension {
+                    oid: &[0x2a, 0x03],
+                    critical: false,
+                    value: &[0x05, 0x00],
+                }),
+            ]
+        );
+
+        assert_eq!(Extensions::new(None).next(), None);
+
+        // The second extension is missing its extnValue, ending the iteration.
+        let mut malformed = tagged;
+        malformed[1] -= 4;
+        malformed[17] -= 4;
+        let malformed = &malformed[..malformed.len() - 4];
+        let mut extensions = Extensions::new(Some(untrusted::Input::from(malformed)));
+        assert!(matches!(extensions.next(), Some(Ok(_))));
+        assert_eq!(extensions.next(), Some(Err(Error::BadDer)));
+        assert_eq!(extensions.next(), None);
+
+        // Trailing data after the SEQUENCE of extensions.
+        let mut trailing = tagged.to_vec();
+        trailing.push(0x00);
+        let mut extensions = Extensions::new(Some(untrusted::Input::from(&trailing)));
+        assert_eq!(
+            extensions.next(),
+            Some(Err(Error::TrailingData(DerTypeId::CertificateExtensions)))
+        );
+        assert_eq!(extensions.next(), None);
+    }
+}
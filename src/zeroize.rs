@@ -0,0 +1,89 @@
+//! Handling of owned buffers copied from certificates and CRLs.
+//!
+//! webpki only handles public material, but some deployments require that buffers derived from
+//! it are not left in freed memory, nor written to logs. So:
+//!
+//! * The `Debug` output of [`crate::OwnedCertRevocationList`], [`crate::TrustAnchorSet`],
+//!   [`crate::VerificationCache`] and the C API's anchor store includes the lengths of the
+//!   buffers they hold, but not their contents.
+//! * With the `zeroize` feature, the buffers held by an [`crate::OwnedCertRevocationList`],
+//!   including the cached SPKI of the issuer its signature was verified with, the keys of a
+//!   [`crate::VerificationCache`], and the anchors of the C API's anchor store are overwritten
+//!   with zeros when dropped.
+//!
+//! Copies of a buffer left behind when it grows while being built, for example by
+//! [`crate::OwnedCertRevocationList::from_reader()`], are not covered. Nor are the fields of
+//! [`crate::OwnedRevokedCert`], which are public.
+//!
+//! Nor are anchors made with `TrustAnchor::to_owned()`: the buffers are owned by
+//! `rustls-pki-types`, which gives no mutable access to them, so webpki can't overwrite them.
+//! A [`crate::TrustAnchorSet`] borrows the contents of the anchors added to it rather than
+//! copying them, so it holds no buffers of its own.
+
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use alloc::vec::Vec;
+
+/// `Debug`s as the length of the wrapped buffer.
+pub(crate) struct Redacted<'a>(pub(crate) &'a [u8]);
+
+impl fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} bytes]", self.0.len())
+    }
+}
+
+/// Overwrites the whole of `buf`'s allocation with zeros, including any spare capacity left by
+/// truncating it.
+#[cfg(feature = "zeroize")]
+pub(crate) fn zeroize(buf: &mut Vec<u8>) {
+    buf.resize(buf.capacity(), 0);
+    for byte in buf.iter_mut() {
+        // SAFETY: `byte` is a valid, aligned, exclusive reference. The volatile write keeps
+        // the compiler from eliding stores to memory that is about to be freed.
+        unsafe { core::ptr::write_volatile(byte, 0) };
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+
+    #[cfg(all(test, feature = "std"))]
+    ZEROIZED.with(|count| count.set(count.get() + 1));
+}
+
+#[cfg(all(test, feature = "zeroize", feature = "std"))]
+std::thread_local! {
+    /// The number of buffers `zeroize()` has overwritten on this thread.
+    static ZEROIZED: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+/// Returns the number of buffers zeroized while running `f`, so tests can check that the drop
+/// glue of the types holding them runs.
+#[cfg(all(test, feature = "zeroize", feature = "std"))]
+pub(crate) fn count_zeroized(f: impl FnOnce()) -> usize {
+    let before = ZEROIZED.with(|count| count.get());
+    f();
+    ZEROIZED.with(|count| count.get()) - before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted() {
+        assert_eq!(format!("{:?}", Redacted(b"secret")), "[6 bytes]");
+        assert_eq!(format!("{:?}", Some(Redacted(&[]))), "Some([0 bytes])");
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_covers_spare_capacity() {
+        let mut buf = Vec::with_capacity(16);
+        buf.extend_from_slice(&[0xa5; 16]);
+        buf.truncate(4);
+
+        zeroize(&mut buf);
+        assert_eq!(buf.len(), buf.capacity());
+        assert!(buf.iter().all(|byte| *byte == 0));
+    }
+}
@@ -0,0 +1,82 @@
+//! Reading the authorityInfoAccess extension, and using its caIssuers locations to complete
+//! a chain that is missing an intermediate. See `authority_info_access()` in
+//! tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{anchor_from_trusted_cert, Cert, EndEntityCert, Error};
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    #[cfg(feature = "ring")]
+    webpki::ring::ECDSA_P256_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    webpki::aws_lc_rs::ECDSA_P256_SHA256,
+];
+
+static ROOT: &[u8] = include_bytes!("aia/root.der");
+static INTERMEDIATE: &[u8] = include_bytes!("aia/intermediate.der");
+static EE: &[u8] = include_bytes!("aia/ee.der");
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+#[test]
+fn access_locations() {
+    let ee = CertificateDer::from(EE);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+
+    // The DNS name and directory name locations are skipped.
+    assert_eq!(
+        cert.ocsp_uris().collect::<Vec<_>>(),
+        ["http://ocsp.example.com", "http://ocsp-backup.example.com"]
+    );
+    assert_eq!(
+        cert.ca_issuers_uris().collect::<Vec<_>>(),
+        [
+            "http://aia.example.com/intermediate.der",
+            "ldap://ldap.example.com/cn=AIA%20Test%20Intermediate"
+        ]
+    );
+
+    let intermediate = CertificateDer::from(INTERMEDIATE);
+    let intermediate = Cert::try_from(&intermediate).unwrap();
+    assert_eq!(intermediate.ocsp_uris().next(), None);
+    assert_eq!(
+        intermediate.ca_issuers_uris().collect::<Vec<_>>(),
+        ["http://aia.example.com/root.der"]
+    );
+
+    let root = CertificateDer::from(ROOT);
+    let root = Cert::try_from(&root).unwrap();
+    assert_eq!(root.ocsp_uris().next(), None);
+    assert_eq!(root.ca_issuers_uris().next(), None);
+}
+
+#[test]
+fn complete_chain_from_ca_issuers() {
+    let root = CertificateDer::from(ROOT);
+    let anchors = &[anchor_from_trusted_cert(&root).unwrap()];
+    let ee = CertificateDer::from(EE);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let verify = |intermediates: &[CertificateDer<'_>]| {
+        cert.verifier(ALGS, anchors)
+            .intermediates(intermediates)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+            .verify()
+            .map(|_| ())
+    };
+
+    // The server left out the intermediate...
+    assert_eq!(verify(&[]), Err(Error::UnknownIssuer));
+
+    // ...so fetch it from the first caIssuers location, and retry with it appended.
+    let fetch = |uri: &str| match uri {
+        "http://aia.example.com/intermediate.der" => Some(CertificateDer::from(INTERMEDIATE)),
+        _ => None,
+    };
+    let fetched = cert.ca_issuers_uris().find_map(fetch).unwrap();
+    assert_eq!(verify(&[fetched]), Ok(()));
+}
@@ -0,0 +1,76 @@
+//! ECDSA signatures that are BER rather than DER encoded, accepted only by
+//! `webpki::dangerous::BerTolerantEcdsa`. See `ber_ecdsa_signatures()` in tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+mod common;
+
+use pki_types::SignatureVerificationAlgorithm;
+use webpki::dangerous::BerTolerantEcdsa;
+use webpki::Error;
+
+#[cfg(feature = "ring")]
+use webpki::ring::ECDSA_P256_SHA256;
+
+#[cfg(all(feature = "aws_lc_rs", not(feature = "ring")))]
+use webpki::aws_lc_rs::ECDSA_P256_SHA256;
+
+use common::Verification;
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+fn verify(
+    ee: &[u8],
+    supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+) -> Result<(), Error> {
+    Verification {
+        supported_sig_algs,
+        ..Verification::new(ee, TIME)
+    }
+    .verify(include_bytes!("ber_ecdsa/ca.der"))
+}
+
+#[test]
+fn der() {
+    let ee = include_bytes!("ber_ecdsa/ee.der");
+    assert_eq!(verify(ee, &[ECDSA_P256_SHA256]), Ok(()));
+    assert_eq!(
+        verify(ee, &[&BerTolerantEcdsa::new(ECDSA_P256_SHA256)]),
+        Ok(())
+    );
+}
+
+#[test]
+fn ber_accepted_if_tolerated() {
+    for ee in [
+        &include_bytes!("ber_ecdsa/ee_leading_zeros.der")[..],
+        &include_bytes!("ber_ecdsa/ee_long_lengths.der")[..],
+    ] {
+        assert_eq!(
+            verify(ee, &[ECDSA_P256_SHA256]),
+            Err(Error::InvalidSignatureForPublicKey)
+        );
+        assert_eq!(
+            verify(ee, &[&BerTolerantEcdsa::new(ECDSA_P256_SHA256)]),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+fn other_encodings_rejected() {
+    for ee in [
+        &include_bytes!("ber_ecdsa/ee_trailing_data.der")[..],
+        &include_bytes!("ber_ecdsa/ee_r_plus_n.der")[..],
+    ] {
+        assert_eq!(
+            verify(ee, &[ECDSA_P256_SHA256]),
+            Err(Error::InvalidSignatureForPublicKey)
+        );
+        assert_eq!(
+            verify(ee, &[&BerTolerantEcdsa::new(ECDSA_P256_SHA256)]),
+            Err(Error::InvalidSignatureForPublicKey)
+        );
+    }
+}
@@ -20,8 +20,10 @@ fn test_cert_v1_unsupported() {
     // to verify this is a correct version 1 certificate.
     let ca = CertificateDer::from(&include_bytes!("cert_v1.der")[..]);
 
+    // A v1 certificate is parsed, but isn't verified unless v1 certificates are allowed.
+    let cert = webpki::EndEntityCert::try_from(&ca).unwrap();
     assert_eq!(
         Some(webpki::Error::UnsupportedCertVersion),
-        webpki::EndEntityCert::try_from(&ca).err()
+        cert.verifier(&[], &[]).verify().err()
     );
 }
@@ -0,0 +1,102 @@
+//! Verification of a code-signing chain at the time of signing, after the end-entity
+//! certificate has expired and been revoked. See `code_signing()` in tests/generate.py.
+
+#![cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{
+    anchor_from_trusted_cert, verify_code_signing_cert, CertRevocationList, Error,
+    OwnedCertRevocationList, RevocationCheckDepth, RevocationDatePolicy, RevocationOptions,
+    RevocationOptionsBuilder,
+};
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    #[cfg(feature = "ring")]
+    webpki::ring::ECDSA_P256_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    webpki::aws_lc_rs::ECDSA_P256_SHA256,
+];
+
+static ROOT: &[u8] = include_bytes!("code_signing/root.der");
+static INTER: &[u8] = include_bytes!("code_signing/inter.der");
+static EE: &[u8] = include_bytes!("code_signing/ee.der");
+static EE_REVOKED_CRL: &[u8] = include_bytes!("code_signing/ee_revoked.crl.der");
+
+/// 2011-06-01T00:00:00Z, before the end-entity certificate was revoked.
+const BEFORE_REVOCATION: u64 = 1_306_886_400;
+/// 2012-09-01T00:00:00Z, after the end-entity certificate was revoked on 2012-06-01.
+const AFTER_REVOCATION: u64 = 1_346_457_600;
+
+fn check(signing_time: u64, revocation: Option<RevocationOptions<'_>>) -> Result<(), Error> {
+    let root = CertificateDer::from(ROOT);
+    let anchors = &[anchor_from_trusted_cert(&root).unwrap()];
+    verify_code_signing_cert(
+        &CertificateDer::from(EE),
+        &[CertificateDer::from(INTER)],
+        anchors,
+        UnixTime::since_unix_epoch(Duration::from_secs(signing_time)),
+        ALGS,
+        revocation,
+    )
+}
+
+fn crl() -> CertRevocationList<'static> {
+    OwnedCertRevocationList::from_der(EE_REVOKED_CRL)
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn expired_chain_valid_at_signing_time() {
+    assert_eq!(check(BEFORE_REVOCATION, None), Ok(()));
+    assert!(matches!(
+        check(AFTER_REVOCATION + 365 * 86_400, None),
+        Err(Error::CertExpired { .. })
+    ));
+}
+
+#[test]
+fn revoked_after_signing() {
+    let crl = crl();
+    let crls = &[&crl];
+    // Only the intermediate has published a CRL.
+    let builder = RevocationOptionsBuilder::new(crls)
+        .unwrap()
+        .with_depth(RevocationCheckDepth::EndEntity);
+
+    // By default, any listed certificate is revoked.
+    assert_eq!(
+        check(BEFORE_REVOCATION, Some(builder.build())),
+        Err(Error::CertRevoked)
+    );
+
+    // Enforcing the revocation date accepts signatures made before it.
+    let revocation = builder
+        .with_revocation_date_policy(RevocationDatePolicy::Enforce)
+        .build();
+    assert_eq!(check(BEFORE_REVOCATION, Some(revocation)), Ok(()));
+    assert_eq!(
+        check(AFTER_REVOCATION, Some(revocation)),
+        Err(Error::CertRevoked)
+    );
+}
+
+#[test]
+fn code_signing_eku_checked() {
+    // A TLS server certificate, verified against its own CA.
+    let ca = CertificateDer::from(&include_bytes!("historical/ca.der")[..]);
+    let anchors = &[anchor_from_trusted_cert(&ca).unwrap()];
+    assert_eq!(
+        verify_code_signing_cert(
+            &CertificateDer::from(&include_bytes!("historical/ee.der")[..]),
+            &[],
+            anchors,
+            UnixTime::since_unix_epoch(Duration::from_secs(1_011_052_800)),
+            ALGS,
+            None,
+        ),
+        Err(Error::RequiredEkuNotFound)
+    );
+}
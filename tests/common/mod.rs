@@ -0,0 +1,89 @@
+//! Verification of a test's certificates, shared by the test files that include this with
+//! `mod common;`. Each of those only uses some of it.
+
+#![allow(dead_code)]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, TrustAnchor, UnixTime};
+use webpki::{
+    anchor_from_trusted_cert, DetailedError, EndEntityCert, Error, KeyUsage, VerificationPolicy,
+};
+
+/// An end-entity certificate to verify, and the options to verify it with. The options are
+/// those of `Verification::new()` unless set otherwise.
+pub struct Verification<'a> {
+    pub ee: &'a [u8],
+    pub intermediates: &'a [CertificateDer<'a>],
+    /// The verification time, in seconds since the Unix epoch.
+    pub time: u64,
+    pub supported_sig_algs: &'a [&'a dyn SignatureVerificationAlgorithm],
+    pub usage: KeyUsage,
+    pub policy: VerificationPolicy,
+    #[cfg(feature = "alloc")]
+    pub required_policies: &'a [&'a [u8]],
+    pub ignored_extensions: &'a [&'a [u8]],
+    pub allow_v1: bool,
+}
+
+impl<'a> Verification<'a> {
+    /// Verifies `ee` at `time` with no intermediates, `webpki::ALL_VERIFICATION_ALGS`,
+    /// `KeyUsage::server_auth()` and the default policy, as `Verifier` does by default.
+    pub fn new(ee: &'a [u8], time: u64) -> Self {
+        Self {
+            ee,
+            intermediates: &[],
+            time,
+            supported_sig_algs: webpki::ALL_VERIFICATION_ALGS,
+            usage: KeyUsage::server_auth(),
+            policy: VerificationPolicy::default(),
+            #[cfg(feature = "alloc")]
+            required_policies: &[],
+            ignored_extensions: &[],
+            allow_v1: false,
+        }
+    }
+
+    /// Verifies with `root` as the only trust anchor.
+    pub fn verify(&self, root: &[u8]) -> Result<(), Error> {
+        self.verify_detailed(root).map_err(|(error, _)| error)
+    }
+
+    /// Verifies with `root` as the only trust anchor, returning the depth of the certificate
+    /// an error was found at along with the error.
+    pub fn verify_detailed(&self, root: &[u8]) -> Result<(), (Error, Option<usize>)> {
+        let root = CertificateDer::from(root);
+        self.verify_detailed_with(&[anchor_from_trusted_cert(&root).unwrap()])
+    }
+
+    /// Verifies with `anchors`.
+    pub fn verify_with(&self, anchors: &[TrustAnchor<'_>]) -> Result<(), Error> {
+        self.verify_detailed_with(anchors)
+            .map_err(|(error, _)| error)
+    }
+
+    fn verify_detailed_with(
+        &self,
+        anchors: &[TrustAnchor<'_>],
+    ) -> Result<(), (Error, Option<usize>)> {
+        let ee = CertificateDer::from(self.ee);
+        let cert = EndEntityCert::try_from(&ee).map_err(|error| (error, None))?;
+
+        let verifier = cert
+            .verifier(self.supported_sig_algs, anchors)
+            .intermediates(self.intermediates)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(self.time)))
+            .key_usage(self.usage)
+            .policy(self.policy)
+            .ignore_critical_extensions(self.ignored_extensions);
+        #[cfg(feature = "alloc")]
+        let verifier = verifier.required_policies(self.required_policies);
+        match self.allow_v1 {
+            true => verifier.allow_v1_certificates(),
+            false => verifier,
+        }
+        .verify_detailed()
+        .map(|_| ())
+        .map_err(|DetailedError { error, cert }| (error, cert.map(|cert| cert.depth)))
+    }
+}
@@ -66,6 +66,14 @@ fn parse_missing_next_update_crl() {
     assert!(matches!(res, Err(Error::TrailingData(DerTypeId::Time))));
 }
 
+#[test]
+fn parse_inverted_validity_crl() {
+    // Parsing a CRL with a next update time earlier than its this update time should error.
+    let crl = include_bytes!("crls/crl.inverted.validity.der");
+    let res = BorrowedCertRevocationList::from_der(&crl[..]);
+    assert!(matches!(res, Err(Error::InvalidCrlValidityPeriod)));
+}
+
 #[test]
 fn parse_wrong_version_crl() {
     // Parsing a CRL with an unsupported version should error.
@@ -84,10 +92,13 @@ fn parse_missing_exts_crl() {
 
 #[test]
 fn parse_delta_crl() {
-    // Parsing a CRL with an extension indicating its a delta CRL should error.
+    // Parsing a CRL with an extension indicating its a delta CRL should succeed, recording
+    // the number of its base CRL.
     let crl = include_bytes!("crls/crl.delta.der");
-    let res = BorrowedCertRevocationList::from_der(&crl[..]);
-    assert!(matches!(res, Err(Error::UnsupportedDeltaCrl)));
+    let crl = CertRevocationList::from(BorrowedCertRevocationList::from_der(&crl[..]).unwrap());
+    let number = 1_665_432_727_442_340_449u64.to_be_bytes();
+    assert_eq!(crl.crl_number(), Some(&number[..]));
+    assert_eq!(crl.base_crl_number(), Some(&number[..]));
 }
 
 #[test]
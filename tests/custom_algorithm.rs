@@ -0,0 +1,141 @@
+//! Verification using only a caller-provided `SignatureVerificationAlgorithm`, which works
+//! whether or not any of the crate's own algorithm implementations are built.
+
+use core::time::Duration;
+
+use pki_types::{
+    AlgorithmIdentifier, CertificateDer, InvalidSignature, SignatureVerificationAlgorithm, UnixTime,
+};
+use webpki::{alg_id, anchor_from_trusted_cert, EndEntityCert, Error, KeyUsage};
+
+/// A toy, insecure signature scheme using the Ed25519 algorithm identifiers: the signature is
+/// a checksum of the public key and the message, so anyone can forge it.
+#[derive(Debug)]
+struct ToyAlgorithm;
+
+impl ToyAlgorithm {
+    fn sign(public_key: &[u8], message: &[u8]) -> Vec<u8> {
+        (0u8..8)
+            .flat_map(|i| {
+                // FNV-1a.
+                let mut hash = 0xcbf2_9ce4_8422_2325u64;
+                for byte in [i].iter().chain(public_key).chain(message) {
+                    hash = (hash ^ u64::from(*byte)).wrapping_mul(0x100_0000_01b3);
+                }
+                hash.to_be_bytes()
+            })
+            .collect()
+    }
+}
+
+impl SignatureVerificationAlgorithm for ToyAlgorithm {
+    fn verify_signature(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), InvalidSignature> {
+        match Self::sign(public_key, message) == signature {
+            true => Ok(()),
+            false => Err(InvalidSignature),
+        }
+    }
+
+    fn public_key_alg_id(&self) -> AlgorithmIdentifier {
+        alg_id::ED25519
+    }
+
+    fn signature_alg_id(&self) -> AlgorithmIdentifier {
+        alg_id::ED25519
+    }
+}
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[&ToyAlgorithm];
+
+struct ToyKeyPair([u8; 32]);
+
+impl rcgen::RemoteKeyPair for ToyKeyPair {
+    fn public_key(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, rcgen::RcgenError> {
+        Ok(ToyAlgorithm::sign(&self.0, msg))
+    }
+
+    fn algorithm(&self) -> &'static rcgen::SignatureAlgorithm {
+        &rcgen::PKCS_ED25519
+    }
+}
+
+fn params(name: &str, ca: bool) -> rcgen::CertificateParams {
+    let mut params = rcgen::CertificateParams::new(vec![format!("{name}.example.com")]);
+    params.alg = &rcgen::PKCS_ED25519;
+    params.key_pair = Some(
+        rcgen::KeyPair::from_remote(Box::new(ToyKeyPair(
+            [u8::try_from(name.len()).unwrap(); 32],
+        )))
+        .unwrap(),
+    );
+    if ca {
+        params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+    }
+    params
+}
+
+#[test]
+fn chain_verified_with_custom_algorithm() {
+    let ca = rcgen::Certificate::from_params(params("ca", true)).unwrap();
+    let ca_der = ca.serialize_der().unwrap();
+    let ca_der = CertificateDer::from(&ca_der[..]);
+    let intermediate = rcgen::Certificate::from_params(params("inter", true)).unwrap();
+    let intermediate_der = intermediate.serialize_der_with_signer(&ca).unwrap();
+    let ee = rcgen::Certificate::from_params(params("end-entity", false)).unwrap();
+    let ee_der = ee.serialize_der_with_signer(&intermediate).unwrap();
+
+    let anchors = &[anchor_from_trusted_cert(&ca_der).unwrap()];
+    let ee_der = CertificateDer::from(ee_der.as_slice());
+    let cert = EndEntityCert::try_from(&ee_der).unwrap();
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(1_893_456_000)); // 2030-01-01
+    let verify = |algs, intermediates| {
+        cert.verify_for_usage(
+            algs,
+            anchors,
+            intermediates,
+            time,
+            KeyUsage::server_auth(),
+            None,
+            None,
+        )
+        .map(|_| ())
+    };
+
+    let intermediates = &[CertificateDer::from(&intermediate_der[..])];
+    assert_eq!(verify(ALGS, intermediates), Ok(()));
+
+    // Without the custom algorithm, no signature can be verified.
+    assert_eq!(
+        verify(&[], intermediates),
+        Err(Error::UnsupportedSignatureAlgorithm)
+    );
+
+    // The signature over the intermediate certificate is checked with the custom algorithm.
+    let mut tampered = intermediates[0].as_ref().to_vec();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert_eq!(
+        verify(ALGS, &[CertificateDer::from(&tampered[..])]),
+        Err(Error::InvalidSignatureForPublicKey)
+    );
+
+    // Signatures by the end-entity key, a 32-byte key of "end-entity".len().
+    let signature = ToyAlgorithm::sign(&[10; 32], b"message");
+    assert_eq!(
+        cert.verify_signature(&ToyAlgorithm, b"message", &signature),
+        Ok(())
+    );
+    assert_eq!(
+        cert.verify_signature(&ToyAlgorithm, b"other message", &signature),
+        Err(Error::InvalidSignatureForPublicKey)
+    );
+}
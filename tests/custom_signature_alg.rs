@@ -0,0 +1,90 @@
+//! A `SignatureVerificationAlgorithm` implemented outside webpki, for signature and public
+//! key algorithms webpki doesn't know, as for ML-DSA. See `custom_signature_alg()` in
+//! tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+mod common;
+
+use pki_types::{
+    AlgorithmIdentifier, CertificateDer, InvalidSignature, SignatureVerificationAlgorithm,
+};
+use webpki::{EndEntityCert, Error};
+
+#[cfg(feature = "ring")]
+use webpki::ring::ECDSA_P256_SHA256;
+
+#[cfg(all(feature = "aws_lc_rs", not(feature = "ring")))]
+use webpki::aws_lc_rs::ECDSA_P256_SHA256;
+
+use common::Verification;
+
+/// 2030-01-01T00:00:00Z, while both certificates are valid.
+const TIME: u64 = 1_893_456_000;
+
+/// An algorithm identified by OIDs under the arc reserved for documentation by RFC 5612,
+/// whose keys and signatures are those of ECDSA P-256 with SHA-256.
+#[derive(Debug)]
+struct ExampleAlgorithm;
+
+impl SignatureVerificationAlgorithm for ExampleAlgorithm {
+    fn verify_signature(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), InvalidSignature> {
+        ECDSA_P256_SHA256.verify_signature(public_key, message, signature)
+    }
+
+    fn public_key_alg_id(&self) -> AlgorithmIdentifier {
+        // 1.3.6.1.4.1.32473.2, without parameters.
+        AlgorithmIdentifier::from_slice(&[
+            0x06, 0x09, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x81, 0xfd, 0x59, 0x02,
+        ])
+    }
+
+    fn signature_alg_id(&self) -> AlgorithmIdentifier {
+        // 1.3.6.1.4.1.32473.1, without parameters.
+        AlgorithmIdentifier::from_slice(&[
+            0x06, 0x09, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x81, 0xfd, 0x59, 0x01,
+        ])
+    }
+}
+
+fn verify(supported_sig_algs: &[&dyn SignatureVerificationAlgorithm]) -> Result<(), Error> {
+    Verification {
+        supported_sig_algs,
+        ..Verification::new(include_bytes!("custom_signature_alg/ee.der"), TIME)
+    }
+    .verify(include_bytes!("custom_signature_alg/ca.der"))
+}
+
+#[test]
+fn custom_algorithm() {
+    assert_eq!(verify(&[&ExampleAlgorithm]), Ok(()));
+    assert_eq!(verify(&[ECDSA_P256_SHA256, &ExampleAlgorithm]), Ok(()));
+}
+
+#[test]
+fn unknown_algorithm() {
+    assert_eq!(
+        verify(webpki::ALL_VERIFICATION_ALGS),
+        Err(Error::UnsupportedSignatureAlgorithm)
+    );
+}
+
+#[test]
+fn custom_algorithm_signatures_are_checked() {
+    let ee = CertificateDer::from(&include_bytes!("custom_signature_alg/ee.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    assert_eq!(
+        cert.verify_signature(&ExampleAlgorithm, b"message", &[0x30, 0x00]),
+        Err(Error::InvalidSignatureForPublicKey)
+    );
+    // ECDSA keys are identified by another algorithm identifier.
+    assert_eq!(
+        cert.verify_signature(ECDSA_P256_SHA256, b"message", &[0x30, 0x00]),
+        Err(Error::UnsupportedSignatureAlgorithmForPublicKey)
+    );
+}
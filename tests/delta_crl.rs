@@ -0,0 +1,116 @@
+//! Merging a delta CRL into its base CRL, and checking revocation against the result. See
+//! `delta_crl()` in tests/generate.py.
+
+#![cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{
+    anchor_from_trusted_cert, BorrowedCertRevocationList, CertRevocationList, Error, KeyUsage,
+    OwnedCertRevocationList, RevocationOptionsBuilder,
+};
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    #[cfg(feature = "ring")]
+    webpki::ring::ECDSA_P256_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    webpki::aws_lc_rs::ECDSA_P256_SHA256,
+];
+
+static CA: &[u8] = include_bytes!("delta_crl/ca.der");
+static EE_A: &[u8] = include_bytes!("delta_crl/ee_a.der");
+static EE_B: &[u8] = include_bytes!("delta_crl/ee_b.der");
+static BASE: &[u8] = include_bytes!("delta_crl/base.crl.der");
+static DELTA: &[u8] = include_bytes!("delta_crl/delta.crl.der");
+static DELTA_NEWER_BASE: &[u8] = include_bytes!("delta_crl/delta_newer_base.crl.der");
+
+/// 2030-01-01T00:00:00Z, while every certificate and CRL is current.
+const TIME: u64 = 1_893_456_000;
+
+fn crl(der: &[u8]) -> CertRevocationList<'static> {
+    OwnedCertRevocationList::from_der(der).unwrap().into()
+}
+
+fn check_cert(ee: &[u8], crl: &CertRevocationList<'_>) -> Result<(), Error> {
+    let ca = CertificateDer::from(CA);
+    let anchors = &[anchor_from_trusted_cert(&ca).unwrap()];
+    let ee = CertificateDer::from(ee);
+    let cert = webpki::EndEntityCert::try_from(&ee).unwrap();
+
+    let crls = &[crl];
+    cert.verify_for_usage(
+        ALGS,
+        anchors,
+        &[],
+        UnixTime::since_unix_epoch(Duration::from_secs(TIME)),
+        KeyUsage::server_auth(),
+        Some(RevocationOptionsBuilder::new(crls).unwrap().build()),
+        None,
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn merged_crl_applies_delta() {
+    let base = crl(BASE);
+    assert_eq!(check_cert(EE_A, &base), Err(Error::CertRevoked));
+    assert_eq!(check_cert(EE_B, &base), Ok(()));
+
+    // The delta removes `EE_A` from the base CRL, and revokes `EE_B`.
+    let delta = crl(DELTA);
+    let merged = CertRevocationList::from(base.with_delta(&delta).unwrap());
+    assert_eq!(merged.crl_number(), delta.crl_number());
+    assert_eq!(merged.base_crl_number(), None);
+    assert_eq!(merged.next_update(), delta.next_update());
+    assert_eq!(check_cert(EE_A, &merged), Ok(()));
+    assert_eq!(check_cert(EE_B, &merged), Err(Error::CertRevoked));
+
+    // The borrowed representations merge in the same way.
+    let base = CertRevocationList::from(BorrowedCertRevocationList::from_der(BASE).unwrap());
+    let delta = CertRevocationList::from(BorrowedCertRevocationList::from_der(DELTA).unwrap());
+    let merged = CertRevocationList::from(base.with_delta(&delta).unwrap());
+    assert_eq!(check_cert(EE_A, &merged), Ok(()));
+    assert_eq!(check_cert(EE_B, &merged), Err(Error::CertRevoked));
+}
+
+#[test]
+fn delta_crl_alone_is_not_authoritative() {
+    assert_eq!(
+        check_cert(EE_B, &crl(DELTA)),
+        Err(Error::UnknownRevocationStatus)
+    );
+}
+
+#[test]
+fn mismatched_delta_crl() {
+    let base = crl(BASE);
+    let delta = crl(DELTA);
+
+    // The delta requires a newer base CRL.
+    assert!(matches!(
+        base.with_delta(&crl(DELTA_NEWER_BASE)),
+        Err(Error::DeltaCrlMismatch)
+    ));
+    // A complete CRL isn't a delta CRL, and a delta CRL isn't a base CRL.
+    assert!(matches!(
+        base.with_delta(&base),
+        Err(Error::DeltaCrlMismatch)
+    ));
+    assert!(matches!(
+        delta.with_delta(&delta),
+        Err(Error::UnsupportedDeltaCrl)
+    ));
+    // A delta CRL from another issuer.
+    let other = crl(include_bytes!("crls/crl.delta.der"));
+    assert!(matches!(
+        base.with_delta(&other),
+        Err(Error::DeltaCrlMismatch)
+    ));
+    // The merged CRL has the delta's number, so the same delta can't be applied again.
+    let merged = CertRevocationList::from(base.with_delta(&delta).unwrap());
+    assert!(matches!(
+        merged.with_delta(&delta),
+        Err(Error::DeltaCrlMismatch)
+    ));
+}
@@ -0,0 +1,120 @@
+//! Email protection (S/MIME) end-entity certificates, verified for the emailProtection
+//! key purpose and matched against email addresses. See `email_protection()` in
+//! tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+mod common;
+
+use pki_types::CertificateDer;
+use webpki::{EndEntityCert, Error, KeyUsage};
+
+use common::Verification;
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+fn verify(ee: &[u8], usage: KeyUsage) -> Result<(), Error> {
+    Verification {
+        usage,
+        ..Verification::new(ee, TIME)
+    }
+    .verify(include_bytes!("email_protection/ca.der"))
+}
+
+#[test]
+fn email_protection_eku() {
+    let ee = include_bytes!("email_protection/ee.der");
+    assert_eq!(verify(ee, KeyUsage::email_protection()), Ok(()));
+    assert_eq!(
+        verify(ee, KeyUsage::server_auth()),
+        Err(Error::RequiredEkuNotFound)
+    );
+    assert_eq!(
+        verify(
+            include_bytes!("email_protection/legacy_ee.der"),
+            KeyUsage::email_protection()
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn server_auth_eku_is_not_email_protection() {
+    let ee = include_bytes!("email_protection/server_auth_ee.der");
+    assert_eq!(
+        verify(ee, KeyUsage::email_protection()),
+        Err(Error::RequiredEkuNotFound)
+    );
+    assert_eq!(verify(ee, KeyUsage::server_auth()), Ok(()));
+}
+
+#[test]
+fn valid_for_email() {
+    let ee = CertificateDer::from(&include_bytes!("email_protection/ee.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+
+    for email in [
+        "joe@example.com",
+        "joe@EXAMPLE.COM",
+        "Joe.Bloggs@mail.example.com",
+    ] {
+        assert_eq!(cert.verify_is_valid_for_email(email), Ok(()), "{email}");
+        assert_eq!(
+            cert.verify_is_valid_for_legacy_email(email),
+            Ok(()),
+            "{email}"
+        );
+    }
+
+    for email in [
+        "Joe@example.com",
+        "joe.bloggs@mail.example.com",
+        "joe@mail.example.com",
+        "bob@example.com",
+    ] {
+        assert_eq!(
+            cert.verify_is_valid_for_email(email),
+            Err(Error::CertNotValidForName),
+            "{email}"
+        );
+    }
+}
+
+#[test]
+fn legacy_subject_email_address() {
+    let ee = CertificateDer::from(&include_bytes!("email_protection/legacy_ee.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+
+    assert_eq!(
+        cert.verify_is_valid_for_email("joe@legacy.example.com"),
+        Err(Error::CertNotValidForName)
+    );
+    assert_eq!(
+        cert.verify_is_valid_for_legacy_email("joe@Legacy.Example.com"),
+        Ok(())
+    );
+    assert_eq!(
+        cert.verify_is_valid_for_legacy_email("JOE@legacy.example.com"),
+        Err(Error::CertNotValidForName)
+    );
+}
+
+#[test]
+fn malformed_reference_email() {
+    let ee = CertificateDer::from(&include_bytes!("email_protection/ee.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+
+    for email in [
+        "example.com",
+        "@example.com",
+        "joe@",
+        "jo\u{e9}@example.com",
+    ] {
+        assert_eq!(
+            cert.verify_is_valid_for_email(email),
+            Err(Error::MalformedEmailIdentifier),
+            "{email}"
+        );
+    }
+}
@@ -16,8 +16,10 @@ from pathlib import Path
 from cryptography import x509
 from cryptography.hazmat.primitives import hashes
 from cryptography.hazmat.primitives.asymmetric import rsa, ec, ed25519, padding
-from cryptography.hazmat.primitives.serialization import Encoding
+from cryptography.hazmat.primitives.asymmetric.utils import decode_dss_signature
+from cryptography.hazmat.primitives.serialization import Encoding, PublicFormat
 from cryptography.hazmat.backends import default_backend
+from cryptography.x509 import ocsp
 from cryptography.x509.oid import NameOID, ExtendedKeyUsageOID
 import ipaddress
 import datetime
@@ -200,6 +202,8 @@ def generate_tls_server_cert_test(
     sans: Optional[Iterable[x509.GeneralName]] = None,
     permitted_subtrees: Optional[Iterable[x509.GeneralName]] = None,
     excluded_subtrees: Optional[Iterable[x509.GeneralName]] = None,
+    subject_name_constraints: bool = False,
+    subject_name_constraints_error: Optional[str] = None,
     force: bool = False,
 ) -> None:
     """
@@ -231,6 +235,10 @@ def generate_tls_server_cert_test(
       the `excludedSubtrees` contents of the `nameConstraints` extension.
       If this and `permitted_subtrees` are both empty/absent then the
       end-entity  certificate does not have a `nameConstraints` extension.
+    - `subject_name_constraints`: whether to also write a `<test_name>_subject_names`
+      test, which verifies with `VerificationPolicy::with_subject_name_constraints()`.
+    - `subject_name_constraints_error`: item in `webpki::Error` enum, expected error
+      from that test.  Leave absent to expect success.
     """
 
     if invalid_names is None:
@@ -301,6 +309,35 @@ fn %(test_name)s() {
         file=output,
     )
 
+    if not subject_name_constraints:
+        return
+
+    if subject_name_constraints_error is None:
+        expected = "Ok(())"
+    else:
+        expected = "Err(webpki::Error::" + subject_name_constraints_error + ")"
+
+    print(
+        """
+#[test]
+fn %(test_name)s_subject_names() {
+    let ee = include_bytes!("%(ee_cert_path)s");
+    let ca = include_bytes!("%(ca_cert_path)s");
+    assert_eq!(
+        check_cert_with_policy(
+            ee,
+            ca,
+            &[%(valid_names_str)s],
+            &[%(invalid_names_str)s],
+            VerificationPolicy::default().with_subject_name_constraints(true),
+        ),
+        %(expected)s
+    );
+}"""
+        % locals(),
+        file=output,
+    )
+
 
 def tls_server_certs(force: bool) -> None:
     with trim_top("tls_server_certs.rs") as output:
@@ -337,6 +374,49 @@ def tls_server_certs(force: bool) -> None:
             subject_common_name="allowed.example.com",
             invalid_names=["allowed.example.com"],
             permitted_subtrees=[x509.DNSName("allowed.example.com")],
+            subject_name_constraints=True,
+        )
+        # The subject common name is only constrained when asked to.
+        generate_tls_server_cert_test(
+            output,
+            "exclude_dns_name_subject_common_name",
+            subject_common_name="disallowed.example.com",
+            valid_names=["allowed.example.com"],
+            invalid_names=["disallowed.example.com"],
+            sans=[x509.DNSName("allowed.example.com")],
+            excluded_subtrees=[x509.DNSName("disallowed.example.com")],
+            subject_name_constraints=True,
+            subject_name_constraints_error="NameConstraintViolation",
+        )
+        generate_tls_server_cert_test(
+            output,
+            "permit_dns_name_subject_common_name_mismatch",
+            subject_common_name="other.example.org",
+            valid_names=["allowed.example.com"],
+            sans=[x509.DNSName("allowed.example.com")],
+            permitted_subtrees=[x509.DNSName("example.com")],
+            subject_name_constraints=True,
+            subject_name_constraints_error="NameConstraintViolation",
+        )
+        generate_tls_server_cert_test(
+            output,
+            "exclude_ip_address_subject_common_name",
+            subject_common_name="10.0.0.1",
+            valid_names=["allowed.example.com"],
+            sans=[x509.DNSName("allowed.example.com")],
+            excluded_subtrees=[x509.IPAddress(ipaddress.ip_network("10.0.0.0/8"))],
+            subject_name_constraints=True,
+            subject_name_constraints_error="NameConstraintViolation",
+        )
+        # A common name that doesn't look like a DNS name isn't constrained.
+        generate_tls_server_cert_test(
+            output,
+            "permit_dns_name_subject_common_name_not_dns_name",
+            subject_common_name="Example Subject",
+            valid_names=["allowed.example.com"],
+            sans=[x509.DNSName("allowed.example.com")],
+            permitted_subtrees=[x509.DNSName("example.com")],
+            subject_name_constraints=True,
         )
         generate_tls_server_cert_test(
             output,
@@ -375,7 +455,8 @@ def tls_server_certs(force: bool) -> None:
 
         # XXX: ideally this test case would be a negative one, because the name constraints
         # should apply to the subject name.
-        # however, because we don't look at email addresses in subjects, it is accepted.
+        # however, by default we don't look at email addresses in subjects, so it is
+        # accepted unless subject names are constrained.
         generate_tls_server_cert_test(
             output,
             "we_incorrectly_ignore_name_constraints_on_name_in_subject",
@@ -383,17 +464,90 @@ def tls_server_certs(force: bool) -> None:
                 x509.NameAttribute(NameOID.EMAIL_ADDRESS, "joe@notexample.com")
             ],
             permitted_subtrees=[x509.RFC822Name("example.com")],
+            subject_name_constraints=True,
+            subject_name_constraints_error="NameConstraintViolation",
         )
 
         # this does work, however, because we process all SANs
         generate_tls_server_cert_test(
             output,
-            "reject_constraints_on_unimplemented_names",
-            expected_error="NameConstraintViolation",
+            "permit_rfc822_name_host",
             sans=[x509.RFC822Name("joe@example.com")],
             permitted_subtrees=[x509.RFC822Name("example.com")],
         )
 
+        generate_tls_server_cert_test(
+            output,
+            "permit_rfc822_name_host_mismatch",
+            expected_error="NameConstraintViolation",
+            sans=[x509.RFC822Name("joe@sub.example.com")],
+            permitted_subtrees=[x509.RFC822Name("example.com")],
+        )
+
+        generate_tls_server_cert_test(
+            output,
+            "permit_rfc822_name_domain",
+            sans=[x509.RFC822Name("joe@sub.example.com")],
+            permitted_subtrees=[x509.RFC822Name(".example.com")],
+        )
+
+        generate_tls_server_cert_test(
+            output,
+            "exclude_rfc822_name_mailbox",
+            expected_error="NameConstraintViolation",
+            sans=[x509.RFC822Name("joe@EXAMPLE.com")],
+            excluded_subtrees=[x509.RFC822Name("joe@example.com")],
+        )
+
+        # the local part of a mailbox is case-sensitive
+        generate_tls_server_cert_test(
+            output,
+            "exclude_rfc822_name_mailbox_mismatch",
+            sans=[x509.RFC822Name("Joe@example.com")],
+            excluded_subtrees=[x509.RFC822Name("joe@example.com")],
+        )
+
+        generate_tls_server_cert_test(
+            output,
+            "permit_uri_host",
+            sans=[x509.UniformResourceIdentifier("https://example.com/path")],
+            permitted_subtrees=[x509.UniformResourceIdentifier("example.com")],
+        )
+
+        generate_tls_server_cert_test(
+            output,
+            "permit_uri_domain_mismatch",
+            expected_error="NameConstraintViolation",
+            sans=[x509.UniformResourceIdentifier("https://example.com/path")],
+            permitted_subtrees=[x509.UniformResourceIdentifier(".example.com")],
+        )
+
+        generate_tls_server_cert_test(
+            output,
+            "exclude_uri_domain",
+            expected_error="NameConstraintViolation",
+            sans=[x509.UniformResourceIdentifier("xmpp://chat.example.com:5222")],
+            excluded_subtrees=[x509.UniformResourceIdentifier(".example.com")],
+        )
+
+        # RFC5280 4.2.1.10: a URI without an authority component violates any URI
+        # constraint
+        generate_tls_server_cert_test(
+            output,
+            "exclude_uri_without_authority",
+            expected_error="NameConstraintViolation",
+            sans=[x509.UniformResourceIdentifier("sip:example.com")],
+            excluded_subtrees=[x509.UniformResourceIdentifier(".example.org")],
+        )
+
+        generate_tls_server_cert_test(
+            output,
+            "reject_constraints_on_unimplemented_names",
+            expected_error="NameConstraintViolation",
+            sans=[x509.RegisteredID(x509.ObjectIdentifier("1.2.3.4"))],
+            permitted_subtrees=[x509.RegisteredID(x509.ObjectIdentifier("1.2.3.4"))],
+        )
+
         # RFC5280 4.2.1.10:
         #   "If no name of the type is in the certificate,
         #    the certificate is acceptable."
@@ -423,6 +577,33 @@ def tls_server_certs(force: bool) -> None:
             excluded_subtrees=[x509.DNSName("example.com")],
         )
 
+        # a wildcard SAN is within a permitted subtree only if every name it stands for is
+        generate_tls_server_cert_test(
+            output,
+            "wildcard_san_accepted_if_in_subdomain_subtree",
+            sans=[x509.DNSName("*.example.com")],
+            valid_names=["bob.example.com"],
+            invalid_names=["example.com"],
+            permitted_subtrees=[x509.DNSName(".example.com")],
+        )
+
+        generate_tls_server_cert_test(
+            output,
+            "wildcard_san_rejected_if_subtree_is_narrower",
+            expected_error="NameConstraintViolation",
+            sans=[x509.DNSName("*.example.com")],
+            permitted_subtrees=[x509.DNSName("sub.example.com")],
+        )
+
+        # ... and is within an excluded subtree if any name it stands for is
+        generate_tls_server_cert_test(
+            output,
+            "wildcard_san_rejected_if_expansion_in_excluded_subtree",
+            expected_error="NameConstraintViolation",
+            sans=[x509.DNSName("*.example.com")],
+            excluded_subtrees=[x509.DNSName("secret.example.com")],
+        )
+
         generate_tls_server_cert_test(
             output,
             "ip4_address_san_rejected_if_in_excluded_subtree",
@@ -511,7 +692,23 @@ def tls_server_certs(force: bool) -> None:
 
         generate_tls_server_cert_test(
             output,
-            "permit_directory_name_not_implemented",
+            "permit_directory_name",
+            permitted_subtrees=[
+                x509.DirectoryName(
+                    x509.Name(
+                        [
+                            x509.NameAttribute(
+                                NameOID.ORGANIZATION_NAME, "permit_directory_name"
+                            )
+                        ]
+                    )
+                )
+            ],
+        )
+
+        generate_tls_server_cert_test(
+            output,
+            "permit_directory_name_mismatch",
             expected_error="NameConstraintViolation",
             permitted_subtrees=[
                 x509.DirectoryName(
@@ -522,8 +719,24 @@ def tls_server_certs(force: bool) -> None:
 
         generate_tls_server_cert_test(
             output,
-            "exclude_directory_name_not_implemented",
+            "exclude_directory_name",
             expected_error="NameConstraintViolation",
+            excluded_subtrees=[
+                x509.DirectoryName(
+                    x509.Name(
+                        [
+                            x509.NameAttribute(
+                                NameOID.ORGANIZATION_NAME, "exclude_directory_name"
+                            )
+                        ]
+                    )
+                )
+            ],
+        )
+
+        generate_tls_server_cert_test(
+            output,
+            "exclude_directory_name_mismatch",
             excluded_subtrees=[
                 x509.DirectoryName(
                     x509.Name([x509.NameAttribute(NameOID.COUNTRY_NAME, "CN")])
@@ -2095,31 +2308,2290 @@ def client_auth_revocation(force: bool) -> None:
         _ee_dp_invalid()
 
 
-if __name__ == "__main__":
-    parser = argparse.ArgumentParser()
-    parser.add_argument(
-        "--tls-server-certs",
-        action=argparse.BooleanOptionalAction,
-        default=True,
-        help="Generate TLS server certificate testcases",
+def historical(force: bool) -> None:
+    """
+    Generate a CA, end-entity certificate and CRLs that are only valid in the 2000s, for
+    testing verification at a past time.
+    """
+    output_dir: str = "historical"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _time(year: int, month: int = 1, day: int = 1) -> datetime.datetime:
+        return datetime.datetime(year, month, day, tzinfo=datetime.timezone.utc)
+
+    ca_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ca_name = x509.Name(
+        [x509.NameAttribute(NameOID.COMMON_NAME, "Historical Test CA")]
     )
-    parser.add_argument(
-        "--signatures",
-        action=argparse.BooleanOptionalAction,
-        default=True,
-        help="Generate signature testcases",
+    ca = (
+        x509.CertificateBuilder()
+        .subject_name(ca_name)
+        .issuer_name(ca_name)
+        .not_valid_before(_time(2000))
+        .not_valid_after(_time(2010))
+        .serial_number(x509.random_serial_number())
+        .public_key(ca_key.public_key())
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .add_extension(
+            x509.KeyUsage(
+                digital_signature=False,
+                content_commitment=False,
+                key_encipherment=False,
+                data_encipherment=False,
+                key_agreement=False,
+                key_cert_sign=True,
+                crl_sign=True,
+                encipher_only=False,
+                decipher_only=False,
+            ),
+            critical=True,
+        )
+        .sign(ca_key, hashes.SHA256(), default_backend())
     )
-    parser.add_argument(
-        "--client-auth",
-        action=argparse.BooleanOptionalAction,
-        default=True,
-        help="Generate client auth testcases",
+    write_der(os.path.join(output_dir, "ca.der"), ca.public_bytes(Encoding.DER), force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ee = (
+        x509.CertificateBuilder()
+        .subject_name(
+            x509.Name(
+                [x509.NameAttribute(NameOID.COMMON_NAME, "historical.example.com")]
+            )
+        )
+        .issuer_name(ca_name)
+        .not_valid_before(_time(2001))
+        .not_valid_after(_time(2004))
+        .serial_number(x509.random_serial_number())
+        .public_key(ee_key.public_key())
+        .add_extension(
+            x509.SubjectAlternativeName([x509.DNSName("historical.example.com")]),
+            critical=False,
+        )
+        .add_extension(
+            x509.ExtendedKeyUsage([ExtendedKeyUsageOID.SERVER_AUTH]), critical=False
+        )
+        .add_extension(x509.BasicConstraints(ca=False, path_length=None), critical=True)
+        .sign(ca_key, hashes.SHA256(), default_backend())
     )
-    parser.add_argument(
-        "--client-auth-revocation",
-        action=argparse.BooleanOptionalAction,
-        default=True,
-        help="Generate client auth revocation testcases",
+    write_der(os.path.join(output_dir, "ee.der"), ee.public_bytes(Encoding.DER), force)
+
+    # One CRL current during 2002 that doesn't list the end-entity, and one current during
+    # 2003 that does.
+    for year, crl_number, revoked in [(2002, 1, False), (2003, 2, True)]:
+        crl_builder = (
+            x509.CertificateRevocationListBuilder()
+            .issuer_name(ca_name)
+            .last_update(_time(year))
+            .next_update(_time(year, 2))
+            .add_extension(x509.CRLNumber(crl_number), critical=False)
+        )
+        if revoked:
+            crl_builder = crl_builder.add_revoked_certificate(
+                x509.RevokedCertificateBuilder()
+                .serial_number(ee.serial_number)
+                .revocation_date(_time(year))
+                .add_extension(
+                    x509.CRLReason(x509.ReasonFlags.key_compromise), critical=False
+                )
+                .build()
+            )
+        crl = crl_builder.sign(ca_key, hashes.SHA256(), default_backend())
+        write_der(
+            os.path.join(output_dir, f"crl_{year}.der"),
+            crl.public_bytes(Encoding.DER),
+            force,
+        )
+
+
+def code_signing(force: bool) -> None:
+    """
+    Generate a code-signing chain that was valid between 2010 and 2012, and a CRL revoking
+    its end-entity certificate in mid-2012, for testing verification at a signing time.
+    """
+    output_dir: str = "code_signing"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _time(year: int, month: int = 1, day: int = 1) -> datetime.datetime:
+        return datetime.datetime(year, month, day, tzinfo=datetime.timezone.utc)
+
+    def _ca_key_usage() -> x509.KeyUsage:
+        return x509.KeyUsage(
+            digital_signature=False,
+            content_commitment=False,
+            key_encipherment=False,
+            data_encipherment=False,
+            key_agreement=False,
+            key_cert_sign=True,
+            crl_sign=True,
+            encipher_only=False,
+            decipher_only=False,
+        )
+
+    root_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    root_name = x509.Name(
+        [x509.NameAttribute(NameOID.COMMON_NAME, "Code Signing Test Root")]
+    )
+    root = (
+        x509.CertificateBuilder()
+        .subject_name(root_name)
+        .issuer_name(root_name)
+        .not_valid_before(_time(2005))
+        .not_valid_after(_time(2020))
+        .serial_number(x509.random_serial_number())
+        .public_key(root_key.public_key())
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .add_extension(_ca_key_usage(), critical=True)
+        .sign(root_key, hashes.SHA256(), default_backend())
+    )
+    write_der(
+        os.path.join(output_dir, "root.der"), root.public_bytes(Encoding.DER), force
+    )
+
+    inter_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    inter_name = x509.Name(
+        [x509.NameAttribute(NameOID.COMMON_NAME, "Code Signing Test Intermediate")]
+    )
+    inter = (
+        x509.CertificateBuilder()
+        .subject_name(inter_name)
+        .issuer_name(root_name)
+        .not_valid_before(_time(2008))
+        .not_valid_after(_time(2015))
+        .serial_number(x509.random_serial_number())
+        .public_key(inter_key.public_key())
+        .add_extension(x509.BasicConstraints(ca=True, path_length=0), critical=True)
+        .add_extension(_ca_key_usage(), critical=True)
+        .sign(root_key, hashes.SHA256(), default_backend())
+    )
+    write_der(
+        os.path.join(output_dir, "inter.der"), inter.public_bytes(Encoding.DER), force
+    )
+
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ee = (
+        x509.CertificateBuilder()
+        .subject_name(
+            x509.Name(
+                [x509.NameAttribute(NameOID.COMMON_NAME, "Code Signing Test Publisher")]
+            )
+        )
+        .issuer_name(inter_name)
+        .not_valid_before(_time(2010))
+        .not_valid_after(_time(2013))
+        .serial_number(x509.random_serial_number())
+        .public_key(ee_key.public_key())
+        .add_extension(
+            x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CODE_SIGNING]), critical=False
+        )
+        .add_extension(x509.BasicConstraints(ca=False, path_length=None), critical=True)
+        .sign(inter_key, hashes.SHA256(), default_backend())
+    )
+    write_der(os.path.join(output_dir, "ee.der"), ee.public_bytes(Encoding.DER), force)
+
+    crl = (
+        x509.CertificateRevocationListBuilder()
+        .issuer_name(inter_name)
+        .last_update(_time(2012, 7))
+        .next_update(_time(2015))
+        .add_extension(x509.CRLNumber(1), critical=False)
+        .add_revoked_certificate(
+            x509.RevokedCertificateBuilder()
+            .serial_number(ee.serial_number)
+            .revocation_date(_time(2012, 6))
+            .build()
+        )
+        .sign(inter_key, hashes.SHA256(), default_backend())
+    )
+    write_der(
+        os.path.join(output_dir, "ee_revoked.crl.der"),
+        crl.public_bytes(Encoding.DER),
+        force,
+    )
+
+
+def delta_crl(force: bool) -> None:
+    """
+    Generate a CA, two end-entity certificates, a base CRL and delta CRLs for testing the
+    merging of a delta CRL into its base.
+    """
+    output_dir: str = "delta_crl"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _time(year: int, month: int = 1, day: int = 1) -> datetime.datetime:
+        return datetime.datetime(year, month, day, tzinfo=datetime.timezone.utc)
+
+    ca_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ca_name = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "Delta CRL Test CA")])
+    ca = (
+        x509.CertificateBuilder()
+        .subject_name(ca_name)
+        .issuer_name(ca_name)
+        .not_valid_before(_time(2020))
+        .not_valid_after(_time(2050))
+        .serial_number(x509.random_serial_number())
+        .public_key(ca_key.public_key())
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .add_extension(
+            x509.KeyUsage(
+                digital_signature=False,
+                content_commitment=False,
+                key_encipherment=False,
+                data_encipherment=False,
+                key_agreement=False,
+                key_cert_sign=True,
+                crl_sign=True,
+                encipher_only=False,
+                decipher_only=False,
+            ),
+            critical=True,
+        )
+        .sign(ca_key, hashes.SHA256(), default_backend())
+    )
+    write_der(os.path.join(output_dir, "ca.der"), ca.public_bytes(Encoding.DER), force)
+
+    ees: list[x509.Certificate] = []
+    for name in ["a", "b"]:
+        ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+        ee = (
+            x509.CertificateBuilder()
+            .subject_name(
+                x509.Name(
+                    [x509.NameAttribute(NameOID.COMMON_NAME, f"{name}.example.com")]
+                )
+            )
+            .issuer_name(ca_name)
+            .not_valid_before(_time(2020))
+            .not_valid_after(_time(2050))
+            .serial_number(x509.random_serial_number())
+            .public_key(ee_key.public_key())
+            .add_extension(
+                x509.SubjectAlternativeName([x509.DNSName(f"{name}.example.com")]),
+                critical=False,
+            )
+            .add_extension(
+                x509.ExtendedKeyUsage([ExtendedKeyUsageOID.SERVER_AUTH]),
+                critical=False,
+            )
+            .add_extension(
+                x509.BasicConstraints(ca=False, path_length=None), critical=True
+            )
+            .sign(ca_key, hashes.SHA256(), default_backend())
+        )
+        write_der(
+            os.path.join(output_dir, f"ee_{name}.der"),
+            ee.public_bytes(Encoding.DER),
+            force,
+        )
+        ees.append(ee)
+    ee_a, ee_b = ees
+
+    def _crl(
+        path: str,
+        crl_number: int,
+        base_crl_number: Optional[int],
+        revoked: list[tuple[x509.Certificate, x509.ReasonFlags]],
+    ) -> None:
+        crl_builder = (
+            x509.CertificateRevocationListBuilder()
+            .issuer_name(ca_name)
+            .last_update(_time(2024))
+            .next_update(_time(2045 if base_crl_number is None else 2040))
+            .add_extension(x509.CRLNumber(crl_number), critical=False)
+        )
+        if base_crl_number is not None:
+            crl_builder = crl_builder.add_extension(
+                x509.DeltaCRLIndicator(base_crl_number), critical=True
+            )
+        for cert, reason in revoked:
+            crl_builder = crl_builder.add_revoked_certificate(
+                x509.RevokedCertificateBuilder()
+                .serial_number(cert.serial_number)
+                .revocation_date(_time(2024))
+                .add_extension(x509.CRLReason(reason), critical=False)
+                .build()
+            )
+        crl = crl_builder.sign(ca_key, hashes.SHA256(), default_backend())
+        write_der(os.path.join(output_dir, path), crl.public_bytes(Encoding.DER), force)
+
+    # The base CRL puts `ee_a` on hold. The delta releases it, and revokes `ee_b`.
+    _crl("base.crl.der", 10, None, [(ee_a, x509.ReasonFlags.certificate_hold)])
+    _crl(
+        "delta.crl.der",
+        11,
+        10,
+        [
+            (ee_a, x509.ReasonFlags.remove_from_crl),
+            (ee_b, x509.ReasonFlags.key_compromise),
+        ],
+    )
+    # A delta that requires a later base CRL than `base.crl.der`.
+    _crl("delta_newer_base.crl.der", 13, 12, [])
+
+
+def ocsp_responses(force: bool) -> None:
+    """
+    Generate a CA, an end-entity certificate, delegated OCSP responder certificates and OCSP
+    responses for the end-entity certificate, for testing stapled OCSP response
+    verification.
+    """
+    output_dir: str = "ocsp"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _time(year: int, month: int = 1, day: int = 1) -> datetime.datetime:
+        return datetime.datetime(year, month, day, tzinfo=datetime.timezone.utc)
+
+    def _cert(
+        path: str,
+        subject_cn: str,
+        issuer: Optional[x509.Certificate],
+        issuer_key: Optional[ec.EllipticCurvePrivateKey],
+        ca: bool,
+        eku: Optional[list[x509.ObjectIdentifier]] = None,
+    ) -> tuple[x509.Certificate, ec.EllipticCurvePrivateKey]:
+        key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+        subject = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, subject_cn)])
+        builder = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(subject if issuer is None else issuer.subject)
+            .not_valid_before(_time(2020))
+            .not_valid_after(_time(2050))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+            .add_extension(
+                x509.BasicConstraints(ca=ca, path_length=None), critical=True
+            )
+        )
+        if eku is not None:
+            builder = builder.add_extension(x509.ExtendedKeyUsage(eku), critical=False)
+        cert = builder.sign(
+            key if issuer_key is None else issuer_key,
+            hashes.SHA256(),
+            default_backend(),
+        )
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+        return cert, key
+
+    ca, ca_key = _cert("ca.der", "OCSP Test CA", None, None, True)
+    ee, _ = _cert(
+        "ee.der",
+        "ocsp.example.com",
+        ca,
+        ca_key,
+        False,
+        [ExtendedKeyUsageOID.SERVER_AUTH],
+    )
+    other_ee, _ = _cert(
+        "other_ee.der",
+        "other.example.com",
+        ca,
+        ca_key,
+        False,
+        [ExtendedKeyUsageOID.SERVER_AUTH],
+    )
+    responder, responder_key = _cert(
+        "responder.der",
+        "OCSP Test Responder",
+        ca,
+        ca_key,
+        False,
+        [ExtendedKeyUsageOID.OCSP_SIGNING],
+    )
+    responder_no_eku, responder_no_eku_key = _cert(
+        "responder_no_eku.der", "OCSP Test Responder Without EKU", ca, ca_key, False
+    )
+
+    def _response(
+        path: str,
+        cert: x509.Certificate,
+        status: ocsp.OCSPCertStatus,
+        signer: x509.Certificate,
+        signer_key: ec.EllipticCurvePrivateKey,
+        encoding: ocsp.OCSPResponderEncoding = ocsp.OCSPResponderEncoding.HASH,
+        cert_id_hash: hashes.HashAlgorithm = hashes.SHA1(),
+    ) -> None:
+        revoked = status == ocsp.OCSPCertStatus.REVOKED
+        builder = (
+            ocsp.OCSPResponseBuilder()
+            .add_response(
+                cert=cert,
+                issuer=ca,
+                algorithm=cert_id_hash,
+                cert_status=status,
+                this_update=_time(2024),
+                next_update=_time(2024, 1, 8),
+                revocation_time=_time(2023, 6) if revoked else None,
+                revocation_reason=x509.ReasonFlags.key_compromise if revoked else None,
+            )
+            .responder_id(encoding, signer)
+        )
+        if signer is not ca:
+            builder = builder.certificates([signer])
+        response = builder.sign(signer_key, hashes.SHA256())
+        write_der(
+            os.path.join(output_dir, path), response.public_bytes(Encoding.DER), force
+        )
+
+    _response("good.der", ee, ocsp.OCSPCertStatus.GOOD, ca, ca_key)
+    _response(
+        "good_by_name_sha256.der",
+        ee,
+        ocsp.OCSPCertStatus.GOOD,
+        ca,
+        ca_key,
+        ocsp.OCSPResponderEncoding.NAME,
+        hashes.SHA256(),
+    )
+    _response("revoked.der", ee, ocsp.OCSPCertStatus.REVOKED, ca, ca_key)
+    _response("unknown.der", ee, ocsp.OCSPCertStatus.UNKNOWN, ca, ca_key)
+    _response(
+        "good_for_other_ee.der", other_ee, ocsp.OCSPCertStatus.GOOD, ca, ca_key
+    )
+    _response(
+        "delegated.der", ee, ocsp.OCSPCertStatus.GOOD, responder, responder_key
+    )
+    _response(
+        "delegated_no_eku.der",
+        ee,
+        ocsp.OCSPCertStatus.GOOD,
+        responder_no_eku,
+        responder_no_eku_key,
+    )
+
+    unsuccessful = ocsp.OCSPResponseBuilder.build_unsuccessful(
+        ocsp.OCSPResponseStatus.TRY_LATER
+    )
+    write_der(
+        os.path.join(output_dir, "try_later.der"),
+        unsuccessful.public_bytes(Encoding.DER),
+        force,
+    )
+
+
+def rsa_pss(force: bool) -> None:
+    """
+    Generate an RSA CA whose own certificate, and the end-entity certificates it
+    issues, are signed with RSASSA-PSS. Some end-entity certificates use PSS parameters
+    other than those of the supported profiles, where the salt length is the hash length.
+    """
+    output_dir: str = "rsa_pss"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _time(year: int) -> datetime.datetime:
+        return datetime.datetime(year, 1, 1, tzinfo=datetime.timezone.utc)
+
+    def _pss(
+        hash_alg: hashes.HashAlgorithm, mgf_hash: Optional[hashes.HashAlgorithm] = None
+    ) -> padding.PSS:
+        return padding.PSS(
+            mgf=padding.MGF1(hash_alg if mgf_hash is None else mgf_hash),
+            salt_length=padding.PSS.DIGEST_LENGTH,
+        )
+
+    def _cert(
+        path: str,
+        subject_cn: str,
+        key: rsa.RSAPrivateKey,
+        issuer: Optional[x509.Certificate],
+        issuer_key: rsa.RSAPrivateKey,
+        hash_alg: hashes.HashAlgorithm,
+        pss: padding.PSS,
+    ) -> x509.Certificate:
+        subject = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, subject_cn)])
+        cert = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(subject if issuer is None else issuer.subject)
+            .not_valid_before(_time(2020))
+            .not_valid_after(_time(2050))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+            .add_extension(
+                x509.BasicConstraints(ca=issuer is None, path_length=None),
+                critical=True,
+            )
+            .sign(issuer_key, hash_alg, default_backend(), rsa_padding=pss)
+        )
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+        return cert
+
+    ca_key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    ca = _cert(
+        "ca.der",
+        "RSA-PSS Test CA",
+        ca_key,
+        None,
+        ca_key,
+        hashes.SHA256(),
+        _pss(hashes.SHA256()),
+    )
+
+    ee_key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    for name, hash_alg, pss in [
+        ("ee_sha256.der", hashes.SHA256(), _pss(hashes.SHA256())),
+        ("ee_sha384.der", hashes.SHA384(), _pss(hashes.SHA384())),
+        ("ee_sha512.der", hashes.SHA512(), _pss(hashes.SHA512())),
+        (
+            "ee_sha256_salt20.der",
+            hashes.SHA256(),
+            padding.PSS(mgf=padding.MGF1(hashes.SHA256()), salt_length=20),
+        ),
+        (
+            "ee_sha256_mgf1_sha512.der",
+            hashes.SHA256(),
+            _pss(hashes.SHA256(), hashes.SHA512()),
+        ),
+    ]:
+        _cert(name, "rsa-pss.example.com", ee_key, ca, ca_key, hash_alg, pss)
+
+
+def key_usage(force: bool) -> None:
+    """
+    Generate a CA, an intermediate lacking keyCertSign, and end-entity certificates
+    whose keyUsage extensions do and do not assert digitalSignature.
+    """
+    output_dir: str = "key_usage"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _key_usage(
+        digital_signature: bool = False,
+        key_encipherment: bool = False,
+        key_cert_sign: bool = False,
+        crl_sign: bool = False,
+    ) -> x509.KeyUsage:
+        return x509.KeyUsage(
+            digital_signature=digital_signature,
+            content_commitment=False,
+            key_encipherment=key_encipherment,
+            data_encipherment=False,
+            key_agreement=False,
+            key_cert_sign=key_cert_sign,
+            crl_sign=crl_sign,
+            encipher_only=False,
+            decipher_only=False,
+        )
+
+    def _cert(
+        path: str,
+        subject_cn: str,
+        key: ec.EllipticCurvePrivateKey,
+        issuer: Optional[x509.Certificate],
+        issuer_key: ec.EllipticCurvePrivateKey,
+        ca: bool,
+        key_usage: x509.KeyUsage,
+    ) -> x509.Certificate:
+        subject = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, subject_cn)])
+        utc = datetime.timezone.utc
+        cert = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(subject if issuer is None else issuer.subject)
+            .not_valid_before(datetime.datetime(2020, 1, 1, tzinfo=utc))
+            .not_valid_after(datetime.datetime(2050, 1, 1, tzinfo=utc))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+            .add_extension(
+                x509.BasicConstraints(ca=ca, path_length=None), critical=True
+            )
+            .add_extension(key_usage, critical=True)
+            .sign(issuer_key, hashes.SHA256(), default_backend())
+        )
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+        return cert
+
+    ca_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ca = _cert(
+        "ca.der",
+        "Key Usage Test CA",
+        ca_key,
+        None,
+        ca_key,
+        True,
+        _key_usage(key_cert_sign=True, crl_sign=True),
+    )
+
+    inter_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    inter = _cert(
+        "inter_no_cert_sign.der",
+        "Key Usage Test Intermediate",
+        inter_key,
+        ca,
+        ca_key,
+        True,
+        _key_usage(digital_signature=True, crl_sign=True),
+    )
+
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    for path, issuer, issuer_key, key_usage in [
+        ("ee_digital_signature.der", ca, ca_key, _key_usage(digital_signature=True)),
+        ("ee_key_encipherment.der", ca, ca_key, _key_usage(key_encipherment=True)),
+        ("ee_cert_sign.der", ca, ca_key, _key_usage(key_cert_sign=True)),
+        ("ee_by_inter.der", inter, inter_key, _key_usage(digital_signature=True)),
+    ]:
+        _cert(
+            path, "key-usage.example.com", ee_key, issuer, issuer_key, False, key_usage
+        )
+
+
+def path_len(force: bool) -> None:
+    """
+    Generate chains below CAs with pathLenConstraints of 0 and 1, each with one further
+    intermediate, and a chain through a self-issued certificate for the rolled-over key
+    of the CA with pathLenConstraint 0.
+    """
+    output_dir: str = "path_len"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _name(cn: str) -> x509.Name:
+        return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, cn)])
+
+    def _cert(
+        path: str,
+        subject: x509.Name,
+        key: ec.EllipticCurvePrivateKey,
+        issuer: x509.Name,
+        issuer_key: ec.EllipticCurvePrivateKey,
+        ca: bool,
+        path_length: Optional[int] = None,
+    ) -> None:
+        utc = datetime.timezone.utc
+        cert = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(issuer)
+            .not_valid_before(datetime.datetime(2020, 1, 1, tzinfo=utc))
+            .not_valid_after(datetime.datetime(2050, 1, 1, tzinfo=utc))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+            .add_extension(
+                x509.BasicConstraints(ca=ca, path_length=path_length), critical=True
+            )
+            .sign(issuer_key, hashes.SHA256(), default_backend())
+        )
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+
+    def _key() -> ec.EllipticCurvePrivateKey:
+        return ec.generate_private_key(ec.SECP256R1(), default_backend())
+
+    root_name, root_key = _name("Path Length Test Root"), _key()
+    _cert("root.der", root_name, root_key, root_name, root_key, True)
+
+    for path_length in [0, 1]:
+        ca_name, ca_key = _name(f"pathLen {path_length} CA"), _key()
+        _cert(
+            f"len{path_length}.der",
+            ca_name,
+            ca_key,
+            root_name,
+            root_key,
+            True,
+            path_length,
+        )
+        sub_name, sub_key = _name(f"Sub CA of pathLen {path_length} CA"), _key()
+        _cert(f"len{path_length}_sub.der", sub_name, sub_key, ca_name, ca_key, True)
+        _cert(
+            f"len{path_length}_sub_ee.der",
+            _name("path-len.example.com"),
+            _key(),
+            sub_name,
+            sub_key,
+            False,
+        )
+
+        if path_length == 0:
+            rolled_over_key = _key()
+            _cert(
+                "len0_self_issued.der", ca_name, rolled_over_key, ca_name, ca_key, True
+            )
+            _cert(
+                "len0_self_issued_ee.der",
+                _name("path-len.example.com"),
+                _key(),
+                ca_name,
+                rolled_over_key,
+                False,
+            )
+
+
+def authority_info_access(force: bool) -> None:
+    """
+    Generate a root, an intermediate and an end-entity certificate whose
+    authorityInfoAccess extension lists OCSP responders and caIssuers locations, among
+    them access locations that are not URIs. The caIssuers locations are where the
+    intermediate, which a server might leave out of its chain, could be fetched from.
+    """
+    output_dir: str = "aia"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _name(cn: str) -> x509.Name:
+        return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, cn)])
+
+    def _cert(
+        path: str,
+        subject: x509.Name,
+        key: ec.EllipticCurvePrivateKey,
+        issuer: x509.Name,
+        issuer_key: ec.EllipticCurvePrivateKey,
+        ca: bool,
+        access: Optional[list[x509.AccessDescription]] = None,
+    ) -> None:
+        utc = datetime.timezone.utc
+        builder = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(issuer)
+            .not_valid_before(datetime.datetime(2020, 1, 1, tzinfo=utc))
+            .not_valid_after(datetime.datetime(2050, 1, 1, tzinfo=utc))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+            .add_extension(
+                x509.BasicConstraints(ca=ca, path_length=None), critical=True
+            )
+        )
+        if not ca:
+            builder = builder.add_extension(
+                x509.SubjectAlternativeName([x509.DNSName("aia.example.com")]),
+                critical=False,
+            )
+        if access is not None:
+            builder = builder.add_extension(
+                x509.AuthorityInformationAccess(access), critical=False
+            )
+        cert = builder.sign(issuer_key, hashes.SHA256(), default_backend())
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+
+    def _key() -> ec.EllipticCurvePrivateKey:
+        return ec.generate_private_key(ec.SECP256R1(), default_backend())
+
+    ocsp_method = x509.AuthorityInformationAccessOID.OCSP
+    ca_issuers_method = x509.AuthorityInformationAccessOID.CA_ISSUERS
+
+    root_name, root_key = _name("AIA Test Root"), _key()
+    _cert("root.der", root_name, root_key, root_name, root_key, True)
+    int_name, int_key = _name("AIA Test Intermediate"), _key()
+    _cert(
+        "intermediate.der",
+        int_name,
+        int_key,
+        root_name,
+        root_key,
+        True,
+        [
+            x509.AccessDescription(
+                ca_issuers_method,
+                x509.UniformResourceIdentifier("http://aia.example.com/root.der"),
+            )
+        ],
+    )
+    _cert(
+        "ee.der",
+        _name("aia.example.com"),
+        _key(),
+        int_name,
+        int_key,
+        False,
+        [
+            x509.AccessDescription(
+                ocsp_method, x509.UniformResourceIdentifier("http://ocsp.example.com")
+            ),
+            x509.AccessDescription(
+                ca_issuers_method,
+                x509.UniformResourceIdentifier(
+                    "http://aia.example.com/intermediate.der"
+                ),
+            ),
+            x509.AccessDescription(
+                ca_issuers_method, x509.DirectoryName(_name("AIA Directory"))
+            ),
+            x509.AccessDescription(ocsp_method, x509.DNSName("ocsp.example.com")),
+            x509.AccessDescription(
+                ca_issuers_method,
+                x509.UniformResourceIdentifier(
+                    "ldap://ldap.example.com/cn=AIA%20Test%20Intermediate"
+                ),
+            ),
+            x509.AccessDescription(
+                ocsp_method,
+                x509.UniformResourceIdentifier("http://ocsp-backup.example.com"),
+            ),
+        ],
+    )
+
+
+def certificate_policies(force: bool) -> None:
+    """
+    Generate chains from a common root whose certificates assert certificate policies,
+    for testing policy processing: with anyPolicy, with a policy mapped to another
+    one, and with each of the requireExplicitPolicy, inhibitPolicyMapping and
+    inhibitAnyPolicy constraints.
+    """
+    output_dir: str = "policies"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    any_policy = "2.5.29.32.0"
+    policy_a = "1.3.6.1.4.1.55555.1.1"
+    policy_b = "1.3.6.1.4.1.55555.1.2"
+
+    def _name(cn: str) -> x509.Name:
+        return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, cn)])
+
+    def _key() -> ec.EllipticCurvePrivateKey:
+        return ec.generate_private_key(ec.SECP256R1(), default_backend())
+
+    def _der(tag: int, value: bytes) -> bytes:
+        assert len(value) < 128
+        return bytes([tag, len(value)]) + value
+
+    def _oid(dotted: str) -> bytes:
+        arcs = [int(arc) for arc in dotted.split(".")]
+        value = bytes([40 * arcs[0] + arcs[1]])
+        for arc in arcs[2:]:
+            encoded = [arc & 0x7F]
+            while arc > 0x7F:
+                arc >>= 7
+                encoded.insert(0, 0x80 | (arc & 0x7F))
+            value += bytes(encoded)
+        return _der(0x06, value)
+
+    def _mappings(mappings: list[tuple[str, str]]) -> x509.ExtensionType:
+        # cryptography can't build a policyMappings extension.
+        value = b"".join(_der(0x30, _oid(i) + _oid(s)) for (i, s) in mappings)
+        return x509.UnrecognizedExtension(
+            x509.ObjectIdentifier("2.5.29.33"), _der(0x30, value)
+        )
+
+    root_name, root_key = _name("Policy Test Root"), _key()
+
+    def _cert(
+        path: str,
+        subject: x509.Name,
+        key: ec.EllipticCurvePrivateKey,
+        issuer: x509.Name,
+        issuer_key: ec.EllipticCurvePrivateKey,
+        ca: bool,
+        policies: Optional[list[str]] = None,
+        extensions: Optional[list[tuple[x509.ExtensionType, bool]]] = None,
+    ) -> None:
+        utc = datetime.timezone.utc
+        builder = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(issuer)
+            .not_valid_before(datetime.datetime(2020, 1, 1, tzinfo=utc))
+            .not_valid_after(datetime.datetime(2050, 1, 1, tzinfo=utc))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+            .add_extension(
+                x509.BasicConstraints(ca=ca, path_length=None), critical=True
+            )
+        )
+        if not ca:
+            builder = builder.add_extension(
+                x509.SubjectAlternativeName([x509.DNSName("policy.example.com")]),
+                critical=False,
+            )
+        if policies is not None:
+            info = [
+                x509.PolicyInformation(x509.ObjectIdentifier(policy), None)
+                for policy in policies
+            ]
+            builder = builder.add_extension(
+                x509.CertificatePolicies(info), critical=False
+            )
+        for extension, critical in extensions or []:
+            builder = builder.add_extension(extension, critical=critical)
+        cert = builder.sign(issuer_key, hashes.SHA256(), default_backend())
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+
+    def _chain(
+        name: str,
+        intermediates: list[
+            tuple[list[str], Optional[list[tuple[x509.ExtensionType, bool]]]]
+        ],
+        ee_policies: Optional[list[str]],
+    ) -> None:
+        # Intermediates are listed from the one issued by the root down.
+        issuer_name, issuer_key = root_name, root_key
+        for i, (policies, extensions) in enumerate(intermediates):
+            int_name, int_key = _name(f"Policy Test {name} {i + 1}"), _key()
+            _cert(
+                f"{name}.int{i + 1}.der",
+                int_name,
+                int_key,
+                issuer_name,
+                issuer_key,
+                True,
+                policies,
+                extensions,
+            )
+            issuer_name, issuer_key = int_name, int_key
+        _cert(
+            f"{name}.ee.der",
+            _name("policy.example.com"),
+            _key(),
+            issuer_name,
+            issuer_key,
+            False,
+            ee_policies,
+        )
+
+    _cert("root.der", root_name, root_key, root_name, root_key, True)
+
+    # Both certificates assert policy A.
+    _chain("plain", [([policy_a], None)], [policy_a])
+    # The end-entity certificate asserts no policies.
+    _chain("no_ee_policies", [([policy_a], None)], None)
+    # The intermediate asserts anyPolicy.
+    _chain("any_policy", [([any_policy], None)], [policy_a])
+    # The end-entity certificate asserts anyPolicy, which the intermediate inhibits.
+    _chain(
+        "inhibit_any_policy",
+        [([policy_a], [(x509.InhibitAnyPolicy(0), True)])],
+        [any_policy],
+    )
+    # The intermediate maps policy A to policy B.
+    _chain(
+        "mapped",
+        [([policy_a], [(_mappings([(policy_a, policy_b)]), True)])],
+        [policy_b],
+    )
+    # As above, but the first intermediate inhibits mapping by the second.
+    _chain(
+        "mapping_inhibited",
+        [
+            (
+                [policy_a],
+                [(x509.PolicyConstraints(None, inhibit_policy_mapping=0), True)],
+            ),
+            ([policy_a], [(_mappings([(policy_a, policy_b)]), True)]),
+        ],
+        [policy_b],
+    )
+    # The intermediate requires explicit policies, as RFC 5280 says it must mark
+    # critical.
+    _chain(
+        "require_explicit",
+        [
+            (
+                [policy_a],
+                [(x509.PolicyConstraints(0, inhibit_policy_mapping=None), True)],
+            )
+        ],
+        [policy_a],
+    )
+
+
+def unknown_critical_extensions(force: bool) -> None:
+    """
+    Generate two chains from a common root, in which the end-entity certificate of one
+    and the intermediate of the other have a critical extension that webpki doesn't
+    understand.
+    """
+    output_dir: str = "unknown_critical_extensions"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    # A private OID, with an ASN.1 NULL value.
+    unknown = x509.UnrecognizedExtension(
+        x509.ObjectIdentifier("1.3.6.1.4.1.55555.2.1"), bytes([0x05, 0x00])
+    )
+
+    def _name(cn: str) -> x509.Name:
+        return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, cn)])
+
+    def _key() -> ec.EllipticCurvePrivateKey:
+        return ec.generate_private_key(ec.SECP256R1(), default_backend())
+
+    def _cert(
+        path: str,
+        subject: x509.Name,
+        key: ec.EllipticCurvePrivateKey,
+        issuer: x509.Name,
+        issuer_key: ec.EllipticCurvePrivateKey,
+        ca: bool,
+        critical_unknown: bool = False,
+    ) -> None:
+        utc = datetime.timezone.utc
+        builder = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(issuer)
+            .not_valid_before(datetime.datetime(2020, 1, 1, tzinfo=utc))
+            .not_valid_after(datetime.datetime(2050, 1, 1, tzinfo=utc))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+            .add_extension(
+                x509.BasicConstraints(ca=ca, path_length=None), critical=True
+            )
+        )
+        if not ca:
+            builder = builder.add_extension(
+                x509.SubjectAlternativeName([x509.DNSName("unknown.example.com")]),
+                critical=False,
+            )
+        if critical_unknown:
+            builder = builder.add_extension(unknown, critical=True)
+        cert = builder.sign(issuer_key, hashes.SHA256(), default_backend())
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+
+    root_name, root_key = _name("Unknown Extension Test Root"), _key()
+    _cert("root.der", root_name, root_key, root_name, root_key, True)
+
+    int_name, int_key = _name("Unknown Extension Test Intermediate"), _key()
+    _cert("int.der", int_name, int_key, root_name, root_key, True)
+    ee_name = _name("unknown.example.com")
+    _cert("ee_unknown.der", ee_name, _key(), int_name, int_key, False, True)
+
+    int_name, int_key = _name("Unknown Extension Test Intermediate 2"), _key()
+    _cert("int_unknown.der", int_name, int_key, root_name, root_key, True, True)
+    _cert("ee.der", ee_name, _key(), int_name, int_key, False)
+
+def legacy_sha1(force: bool) -> None:
+    """
+    Generate an RSA root and an end-entity certificate it issues, signed with
+    sha1WithRSAEncryption.
+    """
+    output_dir: str = "legacy_sha1"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    # sha256WithRSAEncryption and sha1WithRSAEncryption, with NULL parameters.
+    sha256_alg_id = bytes.fromhex("300d06092a864886f70d01010b0500")
+    sha1_alg_id = bytes.fromhex("300d06092a864886f70d0101050500")
+
+    def _der(tag: int, content: bytes) -> bytes:
+        length = len(content)
+        if length < 0x80:
+            return bytes([tag, length]) + content
+        encoded = length.to_bytes((length.bit_length() + 7) // 8, "big")
+        return bytes([tag, 0x80 | len(encoded)]) + encoded + content
+
+    def _time(year: int) -> datetime.datetime:
+        return datetime.datetime(year, 1, 1, tzinfo=datetime.timezone.utc)
+
+    def _name(cn: str) -> x509.Name:
+        return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, cn)])
+
+    root_key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    root_name = _name("SHA-1 Test Root")
+    root = (
+        x509.CertificateBuilder()
+        .subject_name(root_name)
+        .issuer_name(root_name)
+        .not_valid_before(_time(2020))
+        .not_valid_after(_time(2050))
+        .serial_number(x509.random_serial_number())
+        .public_key(root_key.public_key())
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .sign(root_key, hashes.SHA256(), default_backend())
+    )
+    write_der(
+        os.path.join(output_dir, "root.der"), root.public_bytes(Encoding.DER), force
+    )
+
+    ee_key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    ee = (
+        x509.CertificateBuilder()
+        .subject_name(_name("sha1.example.com"))
+        .issuer_name(root_name)
+        .not_valid_before(_time(2020))
+        .not_valid_after(_time(2050))
+        .serial_number(x509.random_serial_number())
+        .public_key(ee_key.public_key())
+        .add_extension(
+            x509.SubjectAlternativeName([x509.DNSName("sha1.example.com")]),
+            critical=False,
+        )
+        .sign(root_key, hashes.SHA256(), default_backend())
+    )
+
+    # The x509 builder refuses to sign with SHA-1, so swap the signature algorithm in
+    # the TBSCertificate and sign it separately.
+    tbs = ee.tbs_certificate_bytes.replace(sha256_alg_id, sha1_alg_id)
+    signature = root_key.sign(tbs, padding.PKCS1v15(), hashes.SHA1())
+    der = _der(0x30, tbs + sha1_alg_id + _der(0x03, b"\x00" + signature))
+    write_der(os.path.join(output_dir, "ee.der"), der, force)
+
+def p521(force: bool) -> None:
+    """
+    Generate a P-521 root issuing a P-256 end-entity certificate, and a P-256 root
+    issuing a P-521 end-entity certificate, with a message signed by the latter.
+    """
+    output_dir: str = "p521"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _name(cn: str) -> x509.Name:
+        return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, cn)])
+
+    def _cert(
+        path: str,
+        subject: x509.Name,
+        key: ec.EllipticCurvePrivateKey,
+        issuer: x509.Name,
+        issuer_key: ec.EllipticCurvePrivateKey,
+        hash_alg: hashes.HashAlgorithm,
+    ) -> None:
+        utc = datetime.timezone.utc
+        ca = subject == issuer
+        builder = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(issuer)
+            .not_valid_before(datetime.datetime(2020, 1, 1, tzinfo=utc))
+            .not_valid_after(datetime.datetime(2050, 1, 1, tzinfo=utc))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+            .add_extension(
+                x509.BasicConstraints(ca=ca, path_length=None), critical=True
+            )
+        )
+        if not ca:
+            builder = builder.add_extension(
+                x509.SubjectAlternativeName([x509.DNSName("p521.example.com")]),
+                critical=False,
+            )
+        cert = builder.sign(issuer_key, hash_alg, default_backend())
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+
+    p521_key = ec.generate_private_key(ec.SECP521R1(), default_backend())
+    p256_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ee_name = _name("p521.example.com")
+
+    p521_root = _name("P-521 Test Root")
+    _cert("p521_root.der", p521_root, p521_key, p521_root, p521_key, hashes.SHA512())
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    _cert("p256_ee.der", ee_name, ee_key, p521_root, p521_key, hashes.SHA512())
+
+    p256_root = _name("P-256 Test Root")
+    _cert("p256_root.der", p256_root, p256_key, p256_root, p256_key, hashes.SHA256())
+    ee_key = ec.generate_private_key(ec.SECP521R1(), default_backend())
+    _cert("p521_ee.der", ee_name, ee_key, p256_root, p256_key, hashes.SHA256())
+
+    message = b"hello world!"
+    write_der(os.path.join(output_dir, "message.bin"), message, force)
+    signature = ee_key.sign(message, ec.ECDSA(hashes.SHA512()))
+    write_der(os.path.join(output_dir, "p521_ee_sha512.sig.bin"), signature, force)
+
+
+def email_protection(force: bool) -> None:
+    """
+    Generate a root issuing email protection end-entity certificates: one with
+    rfc822Name subject alternative names, one with only a legacy subject
+    emailAddress, and one with the serverAuth EKU only.
+    """
+    output_dir: str = "email_protection"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    utc = datetime.timezone.utc
+    ca_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ca_name = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "Email Test Root")])
+
+    def _cert(
+        path: str,
+        subject: x509.Name,
+        key: ec.EllipticCurvePrivateKey,
+        extensions: List[x509.ExtensionType],
+    ) -> None:
+        builder = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(ca_name)
+            .not_valid_before(datetime.datetime(2020, 1, 1, tzinfo=utc))
+            .not_valid_after(datetime.datetime(2050, 1, 1, tzinfo=utc))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+        )
+        for extension in extensions:
+            builder = builder.add_extension(extension, critical=False)
+        cert = builder.sign(ca_key, hashes.SHA256(), default_backend())
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+
+    _cert(
+        "ca.der",
+        ca_name,
+        ca_key,
+        [x509.BasicConstraints(ca=True, path_length=None)],
+    )
+
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    email_eku = x509.ExtendedKeyUsage([ExtendedKeyUsageOID.EMAIL_PROTECTION])
+    _cert(
+        "ee.der",
+        x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "Joe")]),
+        ee_key,
+        [
+            email_eku,
+            x509.SubjectAlternativeName(
+                [
+                    x509.RFC822Name("joe@example.com"),
+                    x509.RFC822Name("Joe.Bloggs@Mail.Example.com"),
+                ]
+            ),
+        ],
+    )
+    _cert(
+        "legacy_ee.der",
+        x509.Name(
+            [
+                x509.NameAttribute(NameOID.COMMON_NAME, "Joe"),
+                x509.NameAttribute(NameOID.EMAIL_ADDRESS, "joe@legacy.example.com"),
+            ]
+        ),
+        ee_key,
+        [email_eku],
+    )
+    _cert(
+        "server_auth_ee.der",
+        x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "Joe")]),
+        ee_key,
+        [
+            x509.ExtendedKeyUsage([ExtendedKeyUsageOID.SERVER_AUTH]),
+            x509.SubjectAlternativeName([x509.RFC822Name("joe@example.com")]),
+        ],
+    )
+
+
+def v1_root(force: bool) -> None:
+    """
+    Generate a self-signed v1 certificate, which cryptography can't build.
+    """
+    output_dir: str = "trust_anchor"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    # ecdsa-with-SHA256.
+    alg_id = bytes.fromhex("300a06082a8648ce3d040302")
+
+    def _der(tag: int, content: bytes) -> bytes:
+        if len(content) < 0x80:
+            return bytes([tag, len(content)]) + content
+        if len(content) < 0x100:
+            return bytes([tag, 0x81, len(content)]) + content
+        return bytes([tag, 0x82]) + len(content).to_bytes(2, "big") + content
+
+    key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    name = x509.Name(
+        [x509.NameAttribute(NameOID.COMMON_NAME, "v1 Root")]
+    ).public_bytes()
+    validity = _der(
+        0x30, _der(0x17, b"200101000000Z") + _der(0x18, b"20500101000000Z")
+    )
+    spki = key.public_key().public_bytes(
+        Encoding.DER, PublicFormat.SubjectPublicKeyInfo
+    )
+    # There is no version field in a v1 certificate.
+    tbs = _der(0x30, _der(0x02, b"\x01") + alg_id + name + validity + name + spki)
+    signature = key.sign(tbs, ec.ECDSA(hashes.SHA256()))
+    cert = _der(0x30, tbs + alg_id + _der(0x03, b"\x00" + signature))
+    write_der(os.path.join(output_dir, "v1_root.der"), cert, force)
+
+
+def custom_signature_alg(force: bool) -> None:
+    """
+    Generate a CA and an end-entity certificate whose signature and public key algorithms
+    have OIDs webpki doesn't know, like those of ML-DSA. The keys and signatures are ECDSA
+    P-256 with SHA-256, so the algorithm implemented in tests/custom_signature_alg.rs can
+    delegate to webpki's.
+    """
+    output_dir: str = "custom_signature_alg"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _der(tag: int, content: bytes) -> bytes:
+        if len(content) < 0x80:
+            return bytes([tag, len(content)]) + content
+        if len(content) < 0x100:
+            return bytes([tag, 0x81, len(content)]) + content
+        return bytes([tag, 0x82]) + len(content).to_bytes(2, "big") + content
+
+    def _oid(dotted: str) -> bytes:
+        arcs = [int(arc) for arc in dotted.split(".")]
+        encoded = bytes([40 * arcs[0] + arcs[1]])
+        for arc in arcs[2:]:
+            chunk = [arc & 0x7F]
+            while arc > 0x7F:
+                arc >>= 7
+                chunk.insert(0, 0x80 | (arc & 0x7F))
+            encoded += bytes(chunk)
+        return _der(0x06, encoded)
+
+    # Under the arc reserved for documentation by RFC 5612. As for ML-DSA, the parameters
+    # are absent.
+    signature_alg_id = _der(0x30, _oid("1.3.6.1.4.1.32473.1"))
+    public_key_alg_id = _der(0x30, _oid("1.3.6.1.4.1.32473.2"))
+
+    def _spki(key: ec.EllipticCurvePrivateKey) -> bytes:
+        point = key.public_key().public_bytes(Encoding.X962, PublicFormat.UncompressedPoint)
+        return _der(0x30, public_key_alg_id + _der(0x03, b"\x00" + point))
+
+    def _name(common_name: str) -> bytes:
+        return x509.Name(
+            [x509.NameAttribute(NameOID.COMMON_NAME, common_name)]
+        ).public_bytes()
+
+    def _cert(
+        serial: int,
+        issuer: str,
+        subject: str,
+        key: ec.EllipticCurvePrivateKey,
+        signer: ec.EllipticCurvePrivateKey,
+        extensions: bytes,
+    ) -> bytes:
+        validity = _der(
+            0x30, _der(0x17, b"200101000000Z") + _der(0x18, b"20500101000000Z")
+        )
+        tbs = _der(
+            0x30,
+            _der(0xA0, _der(0x02, b"\x02"))
+            + _der(0x02, bytes([serial]))
+            + signature_alg_id
+            + _name(issuer)
+            + validity
+            + _name(subject)
+            + _spki(key)
+            + _der(0xA3, _der(0x30, extensions)),
+        )
+        signature = signer.sign(tbs, ec.ECDSA(hashes.SHA256()))
+        return _der(0x30, tbs + signature_alg_id + _der(0x03, b"\x00" + signature))
+
+    def _extension(oid: str, critical: bool, value: bytes) -> bytes:
+        critical_field = _der(0x01, b"\xff") if critical else b""
+        return _der(0x30, _oid(oid) + critical_field + _der(0x04, value))
+
+    ca_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ca = _cert(
+        1,
+        "Custom Algorithm CA",
+        "Custom Algorithm CA",
+        ca_key,
+        ca_key,
+        _extension("2.5.29.19", True, _der(0x30, _der(0x01, b"\xff"))),
+    )
+    write_der(os.path.join(output_dir, "ca.der"), ca, force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ee = _cert(
+        2,
+        "Custom Algorithm CA",
+        "example.com",
+        ee_key,
+        ca_key,
+        _extension("2.5.29.19", True, _der(0x30, b""))
+        + _extension("2.5.29.17", False, _der(0x30, _der(0x82, b"example.com")))
+        + _extension("2.5.29.37", False, _der(0x30, _oid("1.3.6.1.5.5.7.3.1"))),
+    )
+    write_der(os.path.join(output_dir, "ee.der"), ee, force)
+
+
+def smartcard_logon(force: bool) -> None:
+    """
+    Generate a root issuing a smartcard logon end-entity certificate, whose subject
+    alternative names are otherNames: a Microsoft user principal name (UPN), as issued
+    by Active Directory Certificate Services, and one of an unknown type whose value has
+    nested context-specific tags. Also generate a root with a name constraint on UPNs,
+    issuing the same end-entity certificate.
+    """
+    output_dir: str = "smartcard_logon"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    utc = datetime.timezone.utc
+    upn_oid = x509.ObjectIdentifier("1.3.6.1.4.1.311.20.2.3")
+    smartcard_logon_oid = x509.ObjectIdentifier("1.3.6.1.4.1.311.20.2.2")
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+
+    def _chain(prefix: str, ca_extensions: List[x509.ExtensionType]) -> None:
+        ca_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+        ca_name = x509.Name(
+            [x509.NameAttribute(NameOID.COMMON_NAME, "Smartcard Logon Root")]
+        )
+
+        def _cert(
+            path: str,
+            subject: x509.Name,
+            key: ec.EllipticCurvePrivateKey,
+            extensions: List[x509.ExtensionType],
+        ) -> None:
+            builder = (
+                x509.CertificateBuilder()
+                .subject_name(subject)
+                .issuer_name(ca_name)
+                .not_valid_before(datetime.datetime(2020, 1, 1, tzinfo=utc))
+                .not_valid_after(datetime.datetime(2050, 1, 1, tzinfo=utc))
+                .serial_number(x509.random_serial_number())
+                .public_key(key.public_key())
+            )
+            for extension in extensions:
+                builder = builder.add_extension(extension, critical=False)
+            cert = builder.sign(ca_key, hashes.SHA256(), default_backend())
+            write_der(
+                os.path.join(output_dir, prefix + path),
+                cert.public_bytes(Encoding.DER),
+                force,
+            )
+
+        _cert(
+            "ca.der",
+            ca_name,
+            ca_key,
+            [x509.BasicConstraints(ca=True, path_length=None)] + ca_extensions,
+        )
+        _cert(
+            "ee.der",
+            x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "Joe")]),
+            ee_key,
+            [
+                x509.ExtendedKeyUsage(
+                    [ExtendedKeyUsageOID.CLIENT_AUTH, smartcard_logon_oid]
+                ),
+                x509.SubjectAlternativeName(
+                    [
+                        # A UTF8String.
+                        x509.OtherName(upn_oid, b"\x0c\x14joe@corp.example.com"),
+                        # [1] { INTEGER 5 }
+                        x509.OtherName(
+                            x509.ObjectIdentifier("1.3.6.1.4.1.32473.3"),
+                            b"\xa1\x03\x02\x01\x05",
+                        ),
+                    ]
+                ),
+            ],
+        )
+
+    _chain("", [])
+    _chain(
+        "constrained_",
+        [
+            x509.NameConstraints(
+                permitted_subtrees=[
+                    x509.OtherName(upn_oid, b"\x0c\x10corp.example.com")
+                ],
+                excluded_subtrees=None,
+            )
+        ],
+    )
+
+
+def serial_numbers(force: bool) -> None:
+    """
+    Generate a root issuing end-entity certificates whose serial numbers violate RFC 5280
+    or the CA/Browser Forum Baseline Requirements, but which are commonly issued anyway:
+    a negative one, zero, and one that is 21 octets long. Also generate one whose serial
+    number is the longest permitted, 20 octets. The certificates are encoded by hand, as
+    the cryptography package refuses such serial numbers.
+    """
+    output_dir: str = "serial_numbers"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _der(tag: int, content: bytes) -> bytes:
+        if len(content) < 0x80:
+            return bytes([tag, len(content)]) + content
+        if len(content) < 0x100:
+            return bytes([tag, 0x81, len(content)]) + content
+        return bytes([tag, 0x82]) + len(content).to_bytes(2, "big") + content
+
+    # ecdsa-with-SHA256, 1.2.840.10045.4.3.2.
+    signature_alg_id = _der(0x30, _der(0x06, bytes.fromhex("2a8648ce3d040302")))
+
+    def _name(common_name: str) -> bytes:
+        return x509.Name(
+            [x509.NameAttribute(NameOID.COMMON_NAME, common_name)]
+        ).public_bytes()
+
+    def _cert(
+        serial: bytes,
+        subject: str,
+        key: ec.EllipticCurvePrivateKey,
+        extensions: bytes,
+    ) -> bytes:
+        validity = _der(
+            0x30, _der(0x17, b"200101000000Z") + _der(0x18, b"20500101000000Z")
+        )
+        spki = key.public_key().public_bytes(
+            Encoding.DER, PublicFormat.SubjectPublicKeyInfo
+        )
+        tbs = _der(
+            0x30,
+            _der(0xA0, _der(0x02, b"\x02"))
+            + _der(0x02, serial)
+            + signature_alg_id
+            + _name("Serial Numbers Root")
+            + validity
+            + _name(subject)
+            + spki
+            + _der(0xA3, _der(0x30, extensions)),
+        )
+        signature = ca_key.sign(tbs, ec.ECDSA(hashes.SHA256()))
+        return _der(0x30, tbs + signature_alg_id + _der(0x03, b"\x00" + signature))
+
+    # basicConstraints, 2.5.29.19, critical.
+    def _basic_constraints(ca: bool) -> bytes:
+        value = _der(0x30, _der(0x01, b"\xff") if ca else b"")
+        return _der(
+            0x30,
+            _der(0x06, bytes.fromhex("551d13"))
+            + _der(0x01, b"\xff")
+            + _der(0x04, value),
+        )
+
+    ca_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ca = _cert(b"\x01", "Serial Numbers Root", ca_key, _basic_constraints(True))
+    write_der(os.path.join(output_dir, "ca.der"), ca, force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    for name, serial in [
+        ("negative", bytes.fromhex("deadbeef")),
+        ("zero", b"\x00"),
+        # A leading zero octet and 20 octets with the most significant bit set.
+        ("21_octets", b"\x00\x80" + os.urandom(19)),
+        ("20_octets", b"\x7f" + os.urandom(19)),
+    ]:
+        ee = _cert(serial, "example.com", ee_key, _basic_constraints(False))
+        write_der(os.path.join(output_dir, "ee_" + name + ".der"), ee, force)
+
+
+def ocsp_requests(force: bool) -> None:
+    """
+    Generate OCSP requests for the end-entity certificate generated by ocsp_responses(),
+    using `openssl ocsp`, to compare webpki's encoding with.
+    """
+    output_dir: str = "ocsp"
+    for path, args in [
+        ("request_sha1.der", ["-no_nonce"]),
+        ("request_sha256.der", ["-sha256", "-no_nonce"]),
+        # With a random 16 octet nonce.
+        ("request_nonce.der", ["-nonce"]),
+    ]:
+        out_path = os.path.join(output_dir, path)
+        if os.path.exists(out_path) and not force:
+            continue
+        subprocess.run(
+            ["openssl", "ocsp", "-issuer", os.path.join(output_dir, "ca.der")]
+            + args
+            + ["-cert", os.path.join(output_dir, "ee.der"), "-reqout", out_path],
+            check=True,
+        )
+
+
+
+def ocsp_response_lists(force: bool) -> None:
+    """
+    Generate a root, an intermediate and an end-entity certificate, and OCSP responses for
+    the end-entity and intermediate certificates, each signed by its issuer, for testing
+    verification of a list of stapled responses aligned with the path.
+    """
+    output_dir: str = "ocsp_chain"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _time(year: int, month: int = 1, day: int = 1) -> datetime.datetime:
+        return datetime.datetime(year, month, day, tzinfo=datetime.timezone.utc)
+
+    def _cert(
+        path: str,
+        subject_cn: str,
+        issuer: Optional[x509.Certificate],
+        issuer_key: Optional[ec.EllipticCurvePrivateKey],
+        ca: bool,
+    ) -> tuple[x509.Certificate, ec.EllipticCurvePrivateKey]:
+        key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+        subject = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, subject_cn)])
+        builder = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(subject if issuer is None else issuer.subject)
+            .not_valid_before(_time(2020))
+            .not_valid_after(_time(2050))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+            .add_extension(
+                x509.BasicConstraints(ca=ca, path_length=None), critical=True
+            )
+        )
+        if not ca:
+            builder = builder.add_extension(
+                x509.ExtendedKeyUsage([ExtendedKeyUsageOID.SERVER_AUTH]),
+                critical=False,
+            )
+        cert = builder.sign(
+            key if issuer_key is None else issuer_key,
+            hashes.SHA256(),
+            default_backend(),
+        )
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+        return cert, key
+
+    root, root_key = _cert("root.der", "OCSP Chain Test Root", None, None, True)
+    inter, inter_key = _cert(
+        "inter.der", "OCSP Chain Test Intermediate", root, root_key, True
+    )
+    ee, _ = _cert("ee.der", "ocsp-chain.example.com", inter, inter_key, False)
+
+    def _response(
+        path: str,
+        cert: x509.Certificate,
+        status: ocsp.OCSPCertStatus,
+        issuer: x509.Certificate,
+        issuer_key: ec.EllipticCurvePrivateKey,
+    ) -> None:
+        revoked = status == ocsp.OCSPCertStatus.REVOKED
+        response = (
+            ocsp.OCSPResponseBuilder()
+            .add_response(
+                cert=cert,
+                issuer=issuer,
+                algorithm=hashes.SHA1(),
+                cert_status=status,
+                this_update=_time(2024),
+                next_update=_time(2024, 1, 8),
+                revocation_time=_time(2023, 6) if revoked else None,
+                revocation_reason=x509.ReasonFlags.key_compromise if revoked else None,
+            )
+            .responder_id(ocsp.OCSPResponderEncoding.HASH, issuer)
+            .sign(issuer_key, hashes.SHA256())
+        )
+        write_der(
+            os.path.join(output_dir, path), response.public_bytes(Encoding.DER), force
+        )
+
+    _response("ee_good.der", ee, ocsp.OCSPCertStatus.GOOD, inter, inter_key)
+    _response("ee_unknown.der", ee, ocsp.OCSPCertStatus.UNKNOWN, inter, inter_key)
+    _response("inter_good.der", inter, ocsp.OCSPCertStatus.GOOD, root, root_key)
+    _response(
+        "inter_revoked.der", inter, ocsp.OCSPCertStatus.REVOKED, root, root_key
+    )
+
+def v1_certificates(force: bool) -> None:
+    """
+    Generate a v1 root and three v1 end-entity certificates it issued, identified only by
+    their subject commonName: one for a DNS name, one for a wildcard, and one for the DNS
+    name but with a v3 extension, which a v1 certificate can't have. Also generate a v3
+    root with the same name and key, so that the v1 end-entity certificates can be verified
+    against either.
+    """
+    output_dir: str = "v1_certificates"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    # ecdsa-with-SHA256.
+    alg_id = bytes.fromhex("300a06082a8648ce3d040302")
+
+    def _der(tag: int, content: bytes) -> bytes:
+        if len(content) < 0x80:
+            return bytes([tag, len(content)]) + content
+        if len(content) < 0x100:
+            return bytes([tag, 0x81, len(content)]) + content
+        return bytes([tag, 0x82]) + len(content).to_bytes(2, "big") + content
+
+    def _name(common_name: str) -> bytes:
+        return x509.Name(
+            [x509.NameAttribute(NameOID.COMMON_NAME, common_name)]
+        ).public_bytes()
+
+    def _cert(
+        serial: int,
+        subject: str,
+        key: ec.EllipticCurvePrivateKey,
+        version: bytes = b"",
+        extensions: bytes = b"",
+    ) -> bytes:
+        validity = _der(
+            0x30, _der(0x17, b"200101000000Z") + _der(0x18, b"20500101000000Z")
+        )
+        spki = key.public_key().public_bytes(
+            Encoding.DER, PublicFormat.SubjectPublicKeyInfo
+        )
+        tbs = _der(
+            0x30,
+            version
+            + _der(0x02, bytes([serial]))
+            + alg_id
+            + _name("v1 Root")
+            + validity
+            + _name(subject)
+            + spki
+            + extensions,
+        )
+        signature = root_key.sign(tbs, ec.ECDSA(hashes.SHA256()))
+        return _der(0x30, tbs + alg_id + _der(0x03, b"\x00" + signature))
+
+    root_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    # There is no version field in a v1 certificate.
+    write_der(
+        os.path.join(output_dir, "root.der"), _cert(1, "v1 Root", root_key), force
+    )
+    # basicConstraints, critical, with cA set.
+    basic_constraints = bytes.fromhex("300f0603551d130101ff040530030101ff")
+    write_der(
+        os.path.join(output_dir, "v3_root.der"),
+        _cert(
+            2,
+            "v1 Root",
+            root_key,
+            _der(0xA0, _der(0x02, b"\x02")),
+            _der(0xA3, _der(0x30, basic_constraints)),
+        ),
+        force,
+    )
+
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    write_der(
+        os.path.join(output_dir, "ee.der"),
+        _cert(3, "device.example.com", ee_key),
+        force,
+    )
+    write_der(
+        os.path.join(output_dir, "wildcard_ee.der"),
+        _cert(4, "*.example.com", ee_key),
+        force,
+    )
+    write_der(
+        os.path.join(output_dir, "ee_with_extensions.der"),
+        _cert(
+            5,
+            "device.example.com",
+            ee_key,
+            extensions=_der(0xA3, _der(0x30, basic_constraints)),
+        ),
+        force,
+    )
+
+
+def ber_ecdsa_signatures(force: bool) -> None:
+    """
+    Generate a P-256 CA and end-entity certificates it signed, whose signature values are
+    re-encoded from the same valid signature: as DER, with unnecessary leading zeros in
+    the INTEGERs, with long form lengths, with data after the SEQUENCE, and with r + n in
+    place of r, which is out of range.
+    """
+    output_dir: str = "ber_ecdsa"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    # ecdsa-with-SHA256.
+    alg_id = bytes.fromhex("300a06082a8648ce3d040302")
+    # The order of the P-256 group.
+    order = 0xFFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551
+
+    def _der(tag: int, content: bytes) -> bytes:
+        if len(content) < 0x80:
+            return bytes([tag, len(content)]) + content
+        if len(content) < 0x100:
+            return bytes([tag, 0x81, len(content)]) + content
+        return bytes([tag, 0x82]) + len(content).to_bytes(2, "big") + content
+
+    def _integer(value: int) -> bytes:
+        # The minimal encoding of a positive value, with a sign octet if needed.
+        return value.to_bytes(value.bit_length() // 8 + 1, "big")
+
+    utc = datetime.timezone.utc
+    ca_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ca_name = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "BER ECDSA CA")])
+
+    def _builder(subject: x509.Name, key: ec.EllipticCurvePrivateKey) -> Any:
+        return (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(ca_name)
+            .not_valid_before(datetime.datetime(2020, 1, 1, tzinfo=utc))
+            .not_valid_after(datetime.datetime(2050, 1, 1, tzinfo=utc))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+        )
+
+    ca = (
+        _builder(ca_name, ca_key)
+        .add_extension(x509.BasicConstraints(ca=True, path_length=None), critical=True)
+        .sign(ca_key, hashes.SHA256(), default_backend())
+    )
+    write_der(os.path.join(output_dir, "ca.der"), ca.public_bytes(Encoding.DER), force)
+
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+    ee = (
+        _builder(
+            x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "example.com")]), ee_key
+        )
+        .add_extension(
+            x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False
+        )
+        .sign(ca_key, hashes.SHA256(), default_backend())
+    )
+    r, s = decode_dss_signature(ee.signature)
+
+    signatures = {
+        "ee.der": _der(0x30, _der(0x02, _integer(r)) + _der(0x02, _integer(s))),
+        "ee_leading_zeros.der": _der(
+            0x30,
+            _der(0x02, b"\x00" + _integer(r)) + _der(0x02, b"\x00\x00" + _integer(s)),
+        ),
+        "ee_long_lengths.der": b"\x30\x82"
+        + (len(_integer(r)) + len(_integer(s)) + 7).to_bytes(2, "big")
+        + b"\x02\x81"
+        + bytes([len(_integer(r))])
+        + _integer(r)
+        + b"\x02\x82"
+        + len(_integer(s)).to_bytes(2, "big")
+        + _integer(s),
+        "ee_trailing_data.der": _der(
+            0x30, _der(0x02, _integer(r)) + _der(0x02, _integer(s))
+        )
+        + b"\x00",
+        "ee_r_plus_n.der": _der(
+            0x30, _der(0x02, _integer(r + order)) + _der(0x02, _integer(s))
+        ),
+    }
+    for path, signature in signatures.items():
+        cert = _der(
+            0x30,
+            ee.tbs_certificate_bytes + alg_id + _der(0x03, b"\x00" + signature),
+        )
+        write_der(os.path.join(output_dir, path), cert, force)
+    assert signatures["ee.der"] == ee.signature
+
+
+def intermediate_name_constraints(force: bool) -> None:
+    """
+    Generate chains from a root through an intermediate A, which permits only DNS names in
+    example.com and directory names in O=Example, and then through an intermediate B below
+    A, to end-entity certificates. B is either unconstrained, excludes bad.example.com, or
+    has a subject outside of A's permitted directory names.
+    """
+    output_dir: str = "intermediate_name_constraints"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _name(cn: str, org: str = "Example") -> x509.Name:
+        return x509.Name(
+            [
+                x509.NameAttribute(NameOID.ORGANIZATION_NAME, org),
+                x509.NameAttribute(NameOID.COMMON_NAME, cn),
+            ]
+        )
+
+    def _key() -> ec.EllipticCurvePrivateKey:
+        return ec.generate_private_key(ec.SECP256R1(), default_backend())
+
+    def _write(path: str, cert: x509.Certificate) -> None:
+        write_der(
+            os.path.join(output_dir, path), cert.public_bytes(Encoding.DER), force
+        )
+
+    root_name, root_key = _name("Intermediate Name Constraints Root", "Root"), _key()
+    _write("root.der", ca_cert(subject_name=root_name, subject_key=root_key))
+
+    a_name, a_key = _name("Intermediate A"), _key()
+    _write(
+        "int_a.der",
+        ca_cert(
+            subject_name=a_name,
+            subject_key=a_key,
+            issuer_name=root_name,
+            issuer_key=root_key,
+            permitted_subtrees=[
+                x509.DNSName("example.com"),
+                x509.DirectoryName(
+                    x509.Name([x509.NameAttribute(NameOID.ORGANIZATION_NAME, "Example")])
+                ),
+            ],
+        ),
+    )
+
+    # Each B, with its excluded subtrees and the DNS names of the end-entities it issues.
+    intermediates = {
+        "int_b": (
+            _name("Intermediate B"),
+            None,
+            {"permitted": "www.example.com", "violating": "www.example.org"},
+        ),
+        "int_b_excluding": (
+            _name("Intermediate B excluding bad.example.com"),
+            [x509.DNSName("bad.example.com")],
+            {"permitted": "www.example.com", "excluded": "www.bad.example.com"},
+        ),
+        "int_b_bad_subject": (
+            _name("Intermediate B", "Other"),
+            None,
+            {"permitted": "www.example.com"},
+        ),
+    }
+    for path, (b_name, excluded, ees) in intermediates.items():
+        b_key = _key()
+        _write(
+            f"{path}.der",
+            ca_cert(
+                subject_name=b_name,
+                subject_key=b_key,
+                issuer_name=a_name,
+                issuer_key=a_key,
+                excluded_subtrees=excluded,
+            ),
+        )
+        for ee_path, dns_name in ees.items():
+            ee = end_entity_cert(
+                subject_name=_name(dns_name),
+                issuer_name=b_name,
+                issuer_key=b_key,
+                sans=[x509.DNSName(dns_name)],
+            )
+            _write(f"{path}_ee_{ee_path}.der", ee)
+
+
+def verification_algs(force: bool) -> None:
+    """
+    Generate, for each signature verification algorithm webpki provides, a CA with a key
+    of the algorithm's type and an end-entity certificate signed by it with the algorithm.
+    """
+    output_dir: str = "verification_algs"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _time(year: int) -> datetime.datetime:
+        return datetime.datetime(year, 1, 1, tzinfo=datetime.timezone.utc)
+
+    def _pss(hash_alg: hashes.HashAlgorithm) -> padding.PSS:
+        return padding.PSS(
+            mgf=padding.MGF1(hash_alg), salt_length=padding.PSS.DIGEST_LENGTH
+        )
+
+    def _rsa(key_size: int) -> rsa.RSAPrivateKey:
+        return rsa.generate_private_key(public_exponent=65537, key_size=key_size)
+
+    def _ec(curve: ec.EllipticCurve) -> ec.EllipticCurvePrivateKey:
+        return ec.generate_private_key(curve, default_backend())
+
+    ee_key = _ec(ec.SECP256R1())
+    p256, p384, p521 = _ec(ec.SECP256R1()), _ec(ec.SECP384R1()), _ec(ec.SECP521R1())
+    rsa_2048, rsa_3072 = _rsa(2048), _rsa(3072)
+
+    # The CA key, hash and RSA padding of each algorithm.
+    chains = {
+        "ecdsa_p256_sha256": (p256, hashes.SHA256(), None),
+        "ecdsa_p256_sha384": (p256, hashes.SHA384(), None),
+        "ecdsa_p384_sha256": (p384, hashes.SHA256(), None),
+        "ecdsa_p384_sha384": (p384, hashes.SHA384(), None),
+        "ecdsa_p521_sha256": (p521, hashes.SHA256(), None),
+        "ecdsa_p521_sha384": (p521, hashes.SHA384(), None),
+        "ecdsa_p521_sha512": (p521, hashes.SHA512(), None),
+        "ed25519": (ed25519.Ed25519PrivateKey.generate(), None, None),
+        "rsa_pkcs1_2048_8192_sha256": (rsa_2048, hashes.SHA256(), None),
+        "rsa_pkcs1_2048_8192_sha384": (rsa_2048, hashes.SHA384(), None),
+        "rsa_pkcs1_2048_8192_sha512": (rsa_2048, hashes.SHA512(), None),
+        "rsa_pkcs1_3072_8192_sha384": (rsa_3072, hashes.SHA384(), None),
+        "rsa_pss_2048_8192_sha256_legacy_key": (
+            rsa_2048,
+            hashes.SHA256(),
+            _pss(hashes.SHA256()),
+        ),
+        "rsa_pss_2048_8192_sha384_legacy_key": (
+            rsa_2048,
+            hashes.SHA384(),
+            _pss(hashes.SHA384()),
+        ),
+        "rsa_pss_2048_8192_sha512_legacy_key": (
+            rsa_2048,
+            hashes.SHA512(),
+            _pss(hashes.SHA512()),
+        ),
+    }
+    for name, (ca_key, hash_alg, pss) in chains.items():
+        ca_name = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, f"{name} CA")])
+        ee_name = x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "example.com")])
+        for suffix, subject, key, ca in [
+            ("ca", ca_name, ca_key, True),
+            ("ee", ee_name, ee_key, False),
+        ]:
+            cert = (
+                x509.CertificateBuilder()
+                .subject_name(subject)
+                .issuer_name(ca_name)
+                .not_valid_before(_time(2020))
+                .not_valid_after(_time(2050))
+                .serial_number(x509.random_serial_number())
+                .public_key(key.public_key())
+                .add_extension(
+                    x509.BasicConstraints(ca=ca, path_length=None), critical=True
+                )
+                .sign(ca_key, hash_alg, default_backend(), rsa_padding=pss)
+            )
+            write_der(
+                os.path.join(output_dir, f"{name}.{suffix}.der"),
+                cert.public_bytes(Encoding.DER),
+                force,
+            )
+
+
+def mixed_algorithms(force: bool) -> None:
+    """
+    Generate, for every pairing of RSA PKCS#1, ECDSA P-256, ECDSA P-384 and Ed25519, a
+    chain whose root and intermediate have keys of the two algorithms, so that the
+    intermediate is signed with the first and the end-entity certificate with the second.
+    """
+    output_dir: str = "mixed_algorithms"
+    if not os.path.isdir(output_dir):
+        os.mkdir(output_dir)
+
+    def _time(year: int) -> datetime.datetime:
+        return datetime.datetime(year, 1, 1, tzinfo=datetime.timezone.utc)
+
+    def _name(common_name: str) -> x509.Name:
+        return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, common_name)])
+
+    def _cert(
+        subject: x509.Name,
+        issuer: x509.Name,
+        key: ANY_PRIV_KEY,
+        issuer_key: ANY_PRIV_KEY,
+        hash_alg: Optional[hashes.HashAlgorithm],
+        ca: bool,
+    ) -> x509.Certificate:
+        builder = (
+            x509.CertificateBuilder()
+            .subject_name(subject)
+            .issuer_name(issuer)
+            .not_valid_before(_time(2020))
+            .not_valid_after(_time(2050))
+            .serial_number(x509.random_serial_number())
+            .public_key(key.public_key())
+            .add_extension(x509.BasicConstraints(ca=ca, path_length=None), critical=True)
+        )
+        if not ca:
+            builder = builder.add_extension(
+                x509.SubjectAlternativeName([x509.DNSName("example.com")]),
+                critical=False,
+            )
+        return builder.sign(issuer_key, hash_alg, default_backend())
+
+    # The key of each algorithm, and the hash its signatures are made with.
+    algs = {
+        "rsa": (
+            rsa.generate_private_key(public_exponent=65537, key_size=2048),
+            hashes.SHA256(),
+        ),
+        "p256": (ec.generate_private_key(ec.SECP256R1(), default_backend()), hashes.SHA256()),
+        "p384": (ec.generate_private_key(ec.SECP384R1(), default_backend()), hashes.SHA384()),
+        "ed25519": (ed25519.Ed25519PrivateKey.generate(), None),
+    }
+    ee_key = ec.generate_private_key(ec.SECP256R1(), default_backend())
+
+    for root_alg, (root_key, root_hash) in algs.items():
+        root_name = _name(f"{root_alg} root")
+        root = _cert(root_name, root_name, root_key, root_key, root_hash, True)
+        write_der(
+            os.path.join(output_dir, f"{root_alg}.root.der"),
+            root.public_bytes(Encoding.DER),
+            force,
+        )
+
+        for int_alg, (int_key, int_hash) in algs.items():
+            int_name = _name(f"{int_alg} intermediate under {root_alg} root")
+            intermediate = _cert(
+                int_name, root_name, int_key, root_key, root_hash, True
+            )
+            ee = _cert(
+                _name("example.com"),
+                int_name,
+                ee_key,
+                int_key,
+                int_hash,
+                False,
+            )
+            for suffix, cert in [("int", intermediate), ("ee", ee)]:
+                write_der(
+                    os.path.join(output_dir, f"{root_alg}_{int_alg}.{suffix}.der"),
+                    cert.public_bytes(Encoding.DER),
+                    force,
+                )
+
+
+if __name__ == "__main__":
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--tls-server-certs",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate TLS server certificate testcases",
+    )
+    parser.add_argument(
+        "--signatures",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate signature testcases",
+    )
+    parser.add_argument(
+        "--client-auth",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate client auth testcases",
+    )
+    parser.add_argument(
+        "--client-auth-revocation",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate client auth revocation testcases",
+    )
+    parser.add_argument(
+        "--historical",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate historical validation test data",
+    )
+    parser.add_argument(
+        "--code-signing",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate code signing test data",
+    )
+    parser.add_argument(
+        "--delta-crl",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate delta CRL test data",
+    )
+    parser.add_argument(
+        "--ocsp",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate OCSP request and response test data",
+    )
+    parser.add_argument(
+        "--rsa-pss",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate RSA-PSS certificate test data",
+    )
+    parser.add_argument(
+        "--key-usage",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate keyUsage test data",
+    )
+    parser.add_argument(
+        "--path-len",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate pathLenConstraint test data",
+    )
+    parser.add_argument(
+        "--aia",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate authorityInfoAccess test data",
+    )
+    parser.add_argument(
+        "--policies",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate certificate policy test data",
+    )
+    parser.add_argument(
+        "--unknown-critical-extensions",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate unknown critical extension test data",
+    )
+    parser.add_argument(
+        "--legacy-sha1",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate SHA-1 signed certificate test data",
+    )
+    parser.add_argument(
+        "--p521",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate P-521 certificate test data",
+    )
+    parser.add_argument(
+        "--email-protection",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate email protection certificate test data",
+    )
+    parser.add_argument(
+        "--v1-root",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate a self-signed v1 certificate",
+    )
+    parser.add_argument(
+        "--custom-signature-alg",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate certificates using signature algorithms webpki doesn't know",
+    )
+    parser.add_argument(
+        "--smartcard-logon",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate smartcard logon certificates with otherName subject alternative names",
+    )
+    parser.add_argument(
+        "--serial-numbers",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate certificates with nonconforming serial numbers",
+    )
+    parser.add_argument(
+        "--v1-certificates",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate v1 end-entity certificates and the roots issuing them",
+    )
+    parser.add_argument(
+        "--ber-ecdsa-signatures",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate certificates with BER-encoded ECDSA signatures",
+    )
+    parser.add_argument(
+        "--intermediate-name-constraints",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate chains with name constraints on an intermediate below the root",
+    )
+    parser.add_argument(
+        "--verification-algs",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate a chain signed with each signature verification algorithm",
+    )
+    parser.add_argument(
+        "--mixed-algorithms",
+        action=argparse.BooleanOptionalAction,
+        default=True,
+        help="Generate chains whose hops are signed with different algorithms",
     )
     parser.add_argument(
         "--format",
@@ -2149,6 +4621,62 @@ if __name__ == "__main__":
         client_auth(args.force)
     if args.client_auth_revocation:
         client_auth_revocation(args.force)
+    if args.historical:
+        historical(args.force)
+    if args.code_signing:
+        code_signing(args.force)
+    if args.delta_crl:
+        delta_crl(args.force)
+    if args.ocsp:
+        ocsp_responses(args.force)
+        ocsp_requests(args.force)
+        ocsp_response_lists(args.force)
+    if args.rsa_pss:
+        rsa_pss(args.force)
+    if args.key_usage:
+        key_usage(args.force)
+    if args.path_len:
+        path_len(args.force)
+    if args.aia:
+        authority_info_access(args.force)
+    if args.policies:
+        certificate_policies(args.force)
+    if args.unknown_critical_extensions:
+        unknown_critical_extensions(args.force)
+    if args.legacy_sha1:
+        legacy_sha1(args.force)
+    if args.p521:
+        p521(args.force)
+
+    if args.email_protection:
+        email_protection(args.force)
+
+    if args.v1_root:
+        v1_root(args.force)
+
+    if args.custom_signature_alg:
+        custom_signature_alg(args.force)
+
+    if args.smartcard_logon:
+        smartcard_logon(args.force)
+
+    if args.serial_numbers:
+        serial_numbers(args.force)
+
+    if args.v1_certificates:
+        v1_certificates(args.force)
+
+    if args.ber_ecdsa_signatures:
+        ber_ecdsa_signatures(args.force)
+
+    if args.intermediate_name_constraints:
+        intermediate_name_constraints(args.force)
+
+    if args.verification_algs:
+        verification_algs(args.force)
+
+    if args.mixed_algorithms:
+        mixed_algorithms(args.force)
 
     if args.format:
         subprocess.run("cargo fmt", shell=True, check=True)
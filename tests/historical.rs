@@ -0,0 +1,201 @@
+//! Verification at a past time, against a CA, end-entity certificate and CRLs that were only
+//! valid in the 2000s. See `historical()` in tests/generate.py.
+
+#![cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{
+    anchor_from_trusted_cert, CertRevocationList, Error, ExpirationPolicy, KeyUsage,
+    OwnedCertRevocationList, RevocationOptions, RevocationOptionsBuilder,
+};
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    #[cfg(feature = "ring")]
+    webpki::ring::ECDSA_P256_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    webpki::aws_lc_rs::ECDSA_P256_SHA256,
+];
+
+static CA: &[u8] = include_bytes!("historical/ca.der");
+static EE: &[u8] = include_bytes!("historical/ee.der");
+static CRL_2002: &[u8] = include_bytes!("historical/crl_2002.der");
+static CRL_2003: &[u8] = include_bytes!("historical/crl_2003.der");
+
+/// 2002-01-15T00:00:00Z, while `CRL_2002` was current.
+const JANUARY_2002: u64 = 1_011_052_800;
+/// 2003-01-15T00:00:00Z, while `CRL_2003` was current.
+const JANUARY_2003: u64 = 1_042_588_800;
+
+fn crl(der: &[u8]) -> CertRevocationList<'static> {
+    OwnedCertRevocationList::from_der(der).unwrap().into()
+}
+
+fn check_cert(time: u64, revocation: Option<RevocationOptions<'_>>) -> Result<(), Error> {
+    let ca = CertificateDer::from(CA);
+    let anchors = &[anchor_from_trusted_cert(&ca).unwrap()];
+    let ee = CertificateDer::from(EE);
+    let cert = webpki::EndEntityCert::try_from(&ee).unwrap();
+
+    cert.verify_for_usage(
+        ALGS,
+        anchors,
+        &[],
+        UnixTime::since_unix_epoch(Duration::from_secs(time)),
+        KeyUsage::server_auth(),
+        revocation,
+        None,
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn crl_current_at_verification_time() {
+    let crl = crl(CRL_2002);
+    let crls = &[&crl];
+    let revocation = RevocationOptionsBuilder::new(crls)
+        .unwrap()
+        .with_expiration_policy(ExpirationPolicy::Enforce)
+        .build();
+    assert_eq!(check_cert(JANUARY_2002, Some(revocation)), Ok(()));
+}
+
+#[test]
+fn crl_expired_at_verification_time() {
+    let crl = crl(CRL_2002);
+    let crls = &[&crl];
+    let builder = RevocationOptionsBuilder::new(crls).unwrap();
+
+    let revocation = builder
+        .with_expiration_policy(ExpirationPolicy::Enforce)
+        .build();
+    assert_eq!(
+        check_cert(JANUARY_2003, Some(revocation)),
+        Err(Error::CrlExpired)
+    );
+
+    // An expired CRL is used as-is by default.
+    assert_eq!(check_cert(JANUARY_2003, Some(builder.build())), Ok(()));
+}
+
+#[test]
+fn revoked_by_later_crl() {
+    let crl = crl(CRL_2003);
+    let crls = &[&crl];
+    let revocation = RevocationOptionsBuilder::new(crls)
+        .unwrap()
+        .with_expiration_policy(ExpirationPolicy::Enforce)
+        .build();
+    assert_eq!(
+        check_cert(JANUARY_2003, Some(revocation)),
+        Err(Error::CertRevoked)
+    );
+}
+
+#[test]
+fn crl_stale_at_freshness_time() {
+    let ca = CertificateDer::from(CA);
+    let anchors = &[anchor_from_trusted_cert(&ca).unwrap()];
+    let ee = CertificateDer::from(EE);
+    let cert = webpki::EndEntityCert::try_from(&ee).unwrap();
+    let crl = crl(CRL_2002);
+    let crls = &[&crl];
+    let builder = RevocationOptionsBuilder::new(crls).unwrap();
+
+    // The certificate was valid in January 2002, and checked against the CRL current then, but
+    // the CRL is stale by January 2003.
+    let verify = |revocation, freshness_time| {
+        cert.verifier(ALGS, anchors)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(
+                JANUARY_2002,
+            )))
+            .freshness_time(UnixTime::since_unix_epoch(Duration::from_secs(
+                freshness_time,
+            )))
+            .revocation(revocation)
+            .verify()
+    };
+
+    let enforce = builder
+        .with_expiration_policy(ExpirationPolicy::Enforce)
+        .build();
+    assert!(verify(enforce, JANUARY_2002).is_ok());
+    assert!(matches!(
+        verify(enforce, JANUARY_2003),
+        Err(Error::CrlExpired)
+    ));
+
+    let ignore = builder
+        .with_expiration_policy(ExpirationPolicy::Ignore)
+        .build();
+    let path = verify(ignore, JANUARY_2003).unwrap();
+    assert_eq!(path.validation_time().as_secs(), JANUARY_2002);
+    assert_eq!(path.freshness_time().as_secs(), JANUARY_2003);
+
+    // Without a freshness time, both are the verification time.
+    let path = cert
+        .verifier(ALGS, anchors)
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(
+            JANUARY_2002,
+        )))
+        .revocation(enforce)
+        .verify()
+        .unwrap();
+    assert_eq!(path.validation_time().as_secs(), JANUARY_2002);
+    assert_eq!(path.freshness_time().as_secs(), JANUARY_2002);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn expired_at_present_time() {
+    let now = UnixTime::now().as_secs();
+    assert!(matches!(
+        check_cert(now, None),
+        Err(Error::CertExpired { not_after, .. }) if not_after.as_secs() < now
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn report_records_inputs() {
+    use webpki::report::{verify_with_report, CrlInput};
+    use webpki::{
+        RevocationCheckDepth, RevocationDatePolicy, UnknownStatusPolicy, VerificationPolicy,
+    };
+
+    let crl = crl(CRL_2002);
+    let crls = &[&crl];
+    let revocation = RevocationOptionsBuilder::new(crls)
+        .unwrap()
+        .with_expiration_policy(ExpirationPolicy::Enforce)
+        .build();
+
+    let ca = CertificateDer::from(CA);
+    let anchors = &[anchor_from_trusted_cert(&ca).unwrap()];
+    let report = verify_with_report(
+        &CertificateDer::from(EE),
+        ALGS,
+        anchors,
+        &[],
+        UnixTime::since_unix_epoch(Duration::from_secs(JANUARY_2002)),
+        KeyUsage::server_auth(),
+        Some(revocation),
+        VerificationPolicy::default(),
+    );
+    assert!(report.is_ok());
+    assert_eq!(report.time, JANUARY_2002);
+
+    let inputs = report.inputs.revocation.unwrap();
+    assert_eq!(
+        inputs.crls,
+        [CrlInput {
+            issuer: report.inputs.trust_anchors[0].clone(),
+            next_update: 1_012_521_600, // 2002-02-01T00:00:00Z
+        }]
+    );
+    assert_eq!(inputs.depth, RevocationCheckDepth::Chain);
+    assert_eq!(inputs.unknown_status, UnknownStatusPolicy::Deny);
+    assert_eq!(inputs.expiration, ExpirationPolicy::Enforce);
+    assert_eq!(inputs.revocation_date, RevocationDatePolicy::Ignore);
+}
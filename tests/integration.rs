@@ -17,6 +17,8 @@
 use core::time::Duration;
 
 use pki_types::{CertificateDer, UnixTime};
+#[cfg(feature = "alloc")]
+use webpki::VerificationPolicy;
 use webpki::{anchor_from_trusted_cert, KeyUsage};
 
 /* Checks we can verify netflix's cert chain.  This is notable
@@ -47,6 +49,132 @@ fn netflix() {
         .is_ok());
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn netflix_spki_pins() {
+    let ee: &[u8] = include_bytes!("netflix/ee.der");
+    let inter = CertificateDer::from(&include_bytes!("netflix/inter.der")[..]);
+    let ca = CertificateDer::from(&include_bytes!("netflix/ca.der")[..]);
+
+    let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+    let intermediates = [inter];
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(1_492_441_716)); // 2017-04-17T15:08:36Z
+
+    // The SHA-256 digests of the subjectPublicKeyInfos of the root and intermediate.
+    let ca_pin = [
+        0x49, 0x5a, 0x96, 0xba, 0x6b, 0xad, 0x78, 0x24, 0x07, 0xbd, 0x52, 0x1a, 0x00, 0xba, 0xce,
+        0x65, 0x7b, 0xb3, 0x55, 0x55, 0x5e, 0x4b, 0xb7, 0xf8, 0x14, 0x6c, 0x71, 0xbb, 0xa5, 0x7e,
+        0x7a, 0xce,
+    ];
+    let inter_pin = [
+        0xf6, 0x7d, 0x22, 0xcd, 0x39, 0xd2, 0x44, 0x5f, 0x96, 0xe1, 0x6e, 0x09, 0x4e, 0xae, 0x75,
+        0x6a, 0xf4, 0x97, 0x91, 0x68, 0x50, 0x07, 0xc7, 0x6e, 0x4b, 0x66, 0xf1, 0x54, 0xb7, 0xf3,
+        0x5e, 0xc6,
+    ];
+
+    let ee = CertificateDer::from(ee);
+    let cert = webpki::EndEntityCert::try_from(&ee).unwrap();
+    let path = cert
+        .verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+        .intermediates(&intermediates)
+        .at_time(time)
+        .verify()
+        .unwrap();
+    assert!(path.matches_spki_pins(&[inter_pin]));
+    assert!(path.matches_spki_pins(&[ca_pin]));
+    assert!(!cert.matches_spki_pins(&[inter_pin, ca_pin]));
+}
+
+#[test]
+fn netflix_signed_certificate_timestamps() {
+    let ee = CertificateDer::from(&include_bytes!("netflix/ee.der")[..]);
+    let cert = webpki::EndEntityCert::try_from(&ee).unwrap();
+
+    let scts = cert
+        .signed_certificate_timestamps()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let log_ids = scts
+        .iter()
+        .map(|sct| sct.log_id[..4].to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        log_ids,
+        [
+            [0xdd, 0xeb, 0x1d, 0x2b],
+            [0x68, 0xf6, 0x98, 0xf8],
+            [0xee, 0x4b, 0xbd, 0xb7]
+        ]
+    );
+    // 2016-11-08T18:53:39.836Z, .898Z and .910Z.
+    let timestamps = scts.iter().map(|sct| sct.timestamp).collect::<Vec<_>>();
+    assert_eq!(
+        timestamps,
+        [1_478_631_219_836, 1_478_631_219_898, 1_478_631_219_910]
+    );
+    for sct in &scts {
+        assert_eq!(sct.version, 0);
+        assert!(sct.extensions.is_empty());
+        // ecdsa-with-SHA256
+        assert_eq!((sct.hash_algorithm, sct.signature_algorithm), (4, 3));
+        assert_eq!(sct.signature[0], 0x30);
+    }
+
+    #[cfg(feature = "alloc")]
+    {
+        // The SCT list is the only difference between the certificate's tbsCertificate and
+        // the precertificate's.
+        let precert = cert.precert_tbs_certificate().unwrap();
+        let sct_list = cert.sct_list().unwrap();
+        assert!(precert.len() < ee.len() - sct_list.len());
+        assert!(!precert
+            .windows(sct_list.len())
+            .any(|window| window == sct_list));
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn netflix_concatenated_der() {
+    let ee: &[u8] = include_bytes!("netflix/ee.der");
+    let inter: &[u8] = include_bytes!("netflix/inter.der");
+    let ca: &[u8] = include_bytes!("netflix/ca.der");
+    let bundle = [ee, inter, ca].concat();
+
+    let certs = webpki::split_der_certificates(&bundle)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        certs.iter().map(|cert| cert.as_ref()).collect::<Vec<_>>(),
+        [ee, inter, ca]
+    );
+
+    let anchors = [anchor_from_trusted_cert(&certs[2]).unwrap()];
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(1_492_441_716)); // 2017-04-17T15:08:36Z
+    let cert = webpki::EndEntityCert::try_from(&certs[0]).unwrap();
+    assert!(cert
+        .verify_for_usage(
+            webpki::ALL_VERIFICATION_ALGS,
+            &anchors,
+            &certs[1..2],
+            time,
+            KeyUsage::server_auth(),
+            None,
+            None,
+        )
+        .is_ok());
+
+    // Trailing garbage and truncated certificates are reported, after any complete certificates.
+    for bundle in [[ee, &[0x00]].concat(), [ee, &inter[..100]].concat()] {
+        let mut certs = webpki::split_der_certificates(&bundle);
+        assert_eq!(certs.next().unwrap().unwrap().as_ref(), ee);
+        assert_eq!(certs.next().unwrap(), Err(webpki::Error::BadDer));
+        assert!(certs.next().is_none());
+    }
+
+    assert!(webpki::split_der_certificates(&[]).next().is_none());
+}
+
 /* This is notable because it is a popular use of IP address subjectAltNames. */
 #[cfg(feature = "alloc")]
 #[test]
@@ -187,7 +315,17 @@ fn critical_extensions() {
     );
     assert!(
         matches!(
-            webpki::EndEntityCert::try_from(&ee),
+            webpki::EndEntityCert::try_from(&ee)
+                .unwrap()
+                .verify_for_usage(
+                    webpki::ALL_VERIFICATION_ALGS,
+                    &anchors,
+                    &intermediates,
+                    time,
+                    KeyUsage::server_auth(),
+                    None,
+                    None,
+                ),
             Err(webpki::Error::UnsupportedCriticalExtension)
         ),
         "reject critical unknown extension"
@@ -230,6 +368,63 @@ fn read_ee_with_neg_serial() {
         .is_ok());
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn read_ee_with_unique_ids() {
+    let ca = CertificateDer::from(&include_bytes!("misc/unique_ids_ca.der")[..]);
+    let ee = CertificateDer::from(&include_bytes!("misc/unique_ids_ee.der")[..]);
+
+    let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(1_700_000_000)); // 2023-11-14T22:13:20Z
+
+    let cert = webpki::EndEntityCert::try_from(&ee).unwrap();
+    assert!(cert.has_unique_ids());
+
+    let verify = |policy| {
+        cert.verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+            .at_time(time)
+            .policy(policy)
+            .verify()
+            .map(|_| ())
+    };
+    assert_eq!(verify(VerificationPolicy::default()), Ok(()));
+    assert_eq!(
+        verify(VerificationPolicy::strict()),
+        Err(webpki::Error::DeprecatedUniqueIdentifiersPresent)
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn v1_intermediate_rejected() {
+    let ca = CertificateDer::from(&include_bytes!("misc/v1_intermediate.ca.der")[..]);
+    let intermediate = CertificateDer::from(&include_bytes!("misc/v1_intermediate.der")[..]);
+    let ee = CertificateDer::from(&include_bytes!("misc/v1_intermediate.ee.der")[..]);
+
+    let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(1_700_000_000)); // 2023-11-14T22:13:20Z
+
+    let cert = webpki::EndEntityCert::try_from(&ee).unwrap();
+    let verify = |policy| {
+        cert.verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+            .intermediates(core::slice::from_ref(&intermediate))
+            .at_time(time)
+            .policy(policy)
+            .verify()
+            .map(|_| ())
+    };
+    assert_eq!(
+        verify(VerificationPolicy::default()),
+        Err(webpki::Error::UnsupportedCertVersion)
+    );
+    assert_eq!(
+        verify(VerificationPolicy::strict()),
+        Err(webpki::Error::UnsupportedCertVersionForCa)
+    );
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn read_ee_with_large_pos_serial() {
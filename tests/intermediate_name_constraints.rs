@@ -0,0 +1,83 @@
+//! Name constraints of an intermediate that is neither the trust anchor nor the end-entity's
+//! issuer. See `intermediate_name_constraints()` in tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, UnixTime};
+use webpki::{anchor_from_trusted_cert, EndEntityCert, Error, KeyUsage};
+
+static ROOT: &[u8] = include_bytes!("intermediate_name_constraints/root.der");
+static INT_A: &[u8] = include_bytes!("intermediate_name_constraints/int_a.der");
+
+fn check(ee: &[u8], int_b: &'static [u8]) -> Result<(), Error> {
+    let root = CertificateDer::from(ROOT);
+    let anchors = &[anchor_from_trusted_cert(&root).unwrap()];
+    let intermediates = [CertificateDer::from(int_b), CertificateDer::from(INT_A)];
+    let ee = CertificateDer::from(ee);
+    EndEntityCert::try_from(&ee)
+        .unwrap()
+        .verifier(webpki::ALL_VERIFICATION_ALGS, anchors)
+        .intermediates(&intermediates)
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+        .key_usage(KeyUsage::server_auth())
+        .verify()
+        .map(|_| ())
+}
+
+#[test]
+fn permitted_through_unconstrained_intermediate() {
+    assert_eq!(
+        check(
+            include_bytes!("intermediate_name_constraints/int_b_ee_permitted.der"),
+            include_bytes!("intermediate_name_constraints/int_b.der"),
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn violating_through_unconstrained_intermediate() {
+    // Intermediate B adds no constraints, but those of intermediate A above it still apply.
+    assert_eq!(
+        check(
+            include_bytes!("intermediate_name_constraints/int_b_ee_violating.der"),
+            include_bytes!("intermediate_name_constraints/int_b.der"),
+        ),
+        Err(Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn constraints_of_every_intermediate_apply() {
+    // The end-entity must satisfy both the permitted subtrees of A and the excluded
+    // subtrees of B.
+    assert_eq!(
+        check(
+            include_bytes!("intermediate_name_constraints/int_b_excluding_ee_permitted.der"),
+            include_bytes!("intermediate_name_constraints/int_b_excluding.der"),
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        check(
+            include_bytes!("intermediate_name_constraints/int_b_excluding_ee_excluded.der"),
+            include_bytes!("intermediate_name_constraints/int_b_excluding.der"),
+        ),
+        Err(Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn subordinate_ca_subject_is_constrained() {
+    // Intermediate B's subject is outside A's permitted directoryNames, though the
+    // end-entity's names are all permitted.
+    assert_eq!(
+        check(
+            include_bytes!("intermediate_name_constraints/int_b_bad_subject_ee_permitted.der"),
+            include_bytes!("intermediate_name_constraints/int_b_bad_subject.der"),
+        ),
+        Err(Error::NameConstraintViolation)
+    );
+}
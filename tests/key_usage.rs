@@ -0,0 +1,81 @@
+//! Enforcement of the keyUsage extension of issuers and end-entity certificates. See
+//! `key_usage()` in tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{
+    anchor_from_trusted_cert, EndEntityCert, EndEntityKeyUsage, Error, KeyUsage, VerificationPolicy,
+};
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    #[cfg(feature = "ring")]
+    webpki::ring::ECDSA_P256_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    webpki::aws_lc_rs::ECDSA_P256_SHA256,
+];
+
+static CA: &[u8] = include_bytes!("key_usage/ca.der");
+static INTER_NO_CERT_SIGN: &[u8] = include_bytes!("key_usage/inter_no_cert_sign.der");
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+fn check(ee: &[u8], key_usage: EndEntityKeyUsage) -> Result<(), Error> {
+    let ca = CertificateDer::from(CA);
+    let anchors = &[anchor_from_trusted_cert(&ca).unwrap()];
+    let ee = CertificateDer::from(ee);
+    EndEntityCert::try_from(&ee)
+        .unwrap()
+        .verifier(ALGS, anchors)
+        .intermediates(&[CertificateDer::from(INTER_NO_CERT_SIGN)])
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+        .key_usage(KeyUsage::server_auth())
+        .policy(VerificationPolicy::default().with_end_entity_key_usage(key_usage))
+        .verify()
+        .map(|_| ())
+}
+
+#[test]
+fn end_entity_requires_digital_signature() {
+    let default = EndEntityKeyUsage::default();
+    assert_eq!(
+        check(
+            include_bytes!("key_usage/ee_digital_signature.der"),
+            default
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        check(include_bytes!("key_usage/ee_key_encipherment.der"), default),
+        Err(Error::EndEntityKeyUsageInvalid)
+    );
+    assert_eq!(
+        check(include_bytes!("key_usage/ee_cert_sign.der"), default),
+        Err(Error::EndEntityKeyUsageInvalid)
+    );
+}
+
+#[test]
+fn end_entity_check_disabled() {
+    for ee in [
+        &include_bytes!("key_usage/ee_digital_signature.der")[..],
+        include_bytes!("key_usage/ee_key_encipherment.der"),
+        include_bytes!("key_usage/ee_cert_sign.der"),
+    ] {
+        assert_eq!(check(ee, EndEntityKeyUsage::Ignore), Ok(()));
+    }
+}
+
+#[test]
+fn issuer_requires_cert_sign() {
+    // Ignoring the end-entity keyUsage doesn't affect the intermediate's.
+    for key_usage in [EndEntityKeyUsage::default(), EndEntityKeyUsage::Ignore] {
+        assert_eq!(
+            check(include_bytes!("key_usage/ee_by_inter.der"), key_usage),
+            Err(Error::IssuerKeyUsageInvalid)
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! SHA-1 signed certificates, which are only accepted by the algorithms of the
+//! `legacy_sha1` feature. See `legacy_sha1()` in tests/generate.py.
+
+#![cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+
+mod common;
+
+use pki_types::SignatureVerificationAlgorithm;
+use webpki::Error;
+
+use common::Verification;
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+fn verify(supported_sig_algs: &[&dyn SignatureVerificationAlgorithm]) -> Result<(), Error> {
+    Verification {
+        supported_sig_algs,
+        ..Verification::new(include_bytes!("legacy_sha1/ee.der"), TIME)
+    }
+    .verify(include_bytes!("legacy_sha1/root.der"))
+}
+
+#[test]
+fn rejected_by_default() {
+    assert_eq!(
+        verify(webpki::ALL_VERIFICATION_ALGS),
+        Err(Error::UnsupportedSignatureAlgorithm)
+    );
+}
+
+#[test]
+#[cfg(feature = "legacy_sha1")]
+fn accepted_with_legacy_algorithm() {
+    #[cfg(all(not(feature = "ring"), feature = "aws_lc_rs"))]
+    use webpki::aws_lc_rs::RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY;
+    #[cfg(feature = "ring")]
+    use webpki::ring::RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY;
+
+    assert_eq!(
+        verify(&[RSA_PKCS1_2048_8192_SHA1_FOR_LEGACY_USE_ONLY]),
+        Ok(())
+    );
+}
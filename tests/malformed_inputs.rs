@@ -0,0 +1,123 @@
+//! No input makes parsing or verification panic. These are deterministic counterparts to the
+//! fuzz targets in fuzz/, run over mutations of real certificates and CRLs.
+
+use pki_types::{CertificateDer, IpAddr, ServerName};
+use webpki::{anchor_from_trusted_cert, BorrowedCertRevocationList, EndEntityCert};
+
+static EE: &[u8] = include_bytes!("netflix/ee.der");
+static CRL: &[u8] = include_bytes!("crls/crl.idp.indirect_crl.der");
+
+/// Encodes `depth` SEQUENCEs, each containing the next, around an empty one.
+fn nested_sequences(depth: usize) -> Vec<u8> {
+    let mut der = vec![0x30, 0x00];
+    for _ in 0..depth {
+        let len = der.len();
+        let mut outer = match len {
+            0..=0x7f => vec![0x30, len as u8],
+            0x80..=0xff => vec![0x30, 0x81, len as u8],
+            _ => vec![0x30, 0x82, (len >> 8) as u8, len as u8],
+        };
+        outer.extend_from_slice(&der);
+        der = outer;
+    }
+    der
+}
+
+/// Yields every truncation of `der`, and `der` with each of its bytes inverted.
+fn mutations(der: &[u8]) -> impl Iterator<Item = Vec<u8>> + '_ {
+    let truncated = (0..der.len()).map(|len| der[..len].to_vec());
+    let inverted = (0..der.len()).map(|i| {
+        let mut der = der.to_vec();
+        der[i] = !der[i];
+        der
+    });
+    truncated.chain(inverted)
+}
+
+fn parse_and_match_names(der: &[u8]) {
+    let der = CertificateDer::from(der);
+    if let Ok(cert) = EndEntityCert::try_from(&der) {
+        let names = [
+            ServerName::try_from("www.netflix.com").unwrap(),
+            ServerName::IpAddress(IpAddr::try_from("1.2.3.4").unwrap()),
+        ];
+        for name in &names {
+            let _ = cert.verify_is_valid_for_subject_name(name);
+        }
+        let _ = cert.valid_dns_names().count();
+    }
+    let _ = anchor_from_trusted_cert(&der);
+}
+
+#[test]
+fn deeply_nested_sequences() {
+    // The parser doesn't recurse, so the nesting depth of the input is limited only by its
+    // length.
+    for depth in [1, 10, 100, 1_000, 10_000] {
+        let der = nested_sequences(depth);
+        parse_and_match_names(&der);
+        assert!(BorrowedCertRevocationList::from_der(&der).is_err());
+        #[cfg(feature = "alloc")]
+        assert!(webpki::split_der_certificates(&der).all(|cert| {
+            parse_and_match_names(cert.as_ref().map(|cert| cert.as_ref()).unwrap_or_default());
+            true
+        }));
+    }
+}
+
+#[test]
+fn mutated_end_entity_certificate() {
+    for der in mutations(EE) {
+        parse_and_match_names(&der);
+    }
+}
+
+#[test]
+fn mutated_crl() {
+    for der in mutations(CRL) {
+        if let Ok(crl) = BorrowedCertRevocationList::from_der(&der) {
+            for revoked_cert in &crl {
+                let _ = revoked_cert;
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+#[test]
+fn mutated_chain() {
+    use core::time::Duration;
+
+    use pki_types::UnixTime;
+
+    static INTER: &[u8] = include_bytes!("netflix/inter.der");
+    static CA: &[u8] = include_bytes!("netflix/ca.der");
+
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(1_492_441_716)); // 2017-04-17T15:08:36Z
+    let ca = CertificateDer::from(CA);
+    let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+
+    let verify = |ee: &[u8], inter: &[u8]| {
+        let ee = CertificateDer::from(ee);
+        let intermediates = [CertificateDer::from(inter)];
+        let cert = match EndEntityCert::try_from(&ee) {
+            Ok(cert) => cert,
+            Err(_) => return false,
+        };
+        cert.verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+            .intermediates(&intermediates)
+            .at_time(time)
+            .verify()
+            .is_ok()
+    };
+
+    assert!(verify(EE, INTER));
+    // Only changes to the unsigned parts of a certificate could go unnoticed, and inverting
+    // a byte there breaks the encoding.
+    for ee in mutations(EE) {
+        assert!(!verify(&ee, INTER));
+    }
+    for inter in mutations(INTER) {
+        assert!(!verify(EE, &inter));
+    }
+}
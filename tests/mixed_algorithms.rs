@@ -0,0 +1,158 @@
+//! Chains whose hops are signed with different algorithms. See `mixed_algorithms()` in
+//! tests/generate.py.
+
+#![cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+
+mod common;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm};
+use webpki::Error;
+
+#[cfg(feature = "ring")]
+use webpki::ring::{
+    ECDSA_P256_SHA256, ECDSA_P384_SHA256, ECDSA_P384_SHA384, ED25519, RSA_PKCS1_2048_8192_SHA256,
+};
+
+#[cfg(all(feature = "aws_lc_rs", not(feature = "ring")))]
+use webpki::aws_lc_rs::{
+    ECDSA_P256_SHA256, ECDSA_P384_SHA256, ECDSA_P384_SHA384, ED25519, RSA_PKCS1_2048_8192_SHA256,
+};
+
+use common::Verification;
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+/// The algorithm of each key, as named by tests/generate.py.
+static ALGS: &[(&str, &dyn SignatureVerificationAlgorithm)] = &[
+    ("rsa", RSA_PKCS1_2048_8192_SHA256),
+    ("p256", ECDSA_P256_SHA256),
+    ("p384", ECDSA_P384_SHA384),
+    ("ed25519", ED25519),
+];
+
+/// The root, intermediate and end-entity certificates of the chain whose root has a key of
+/// the first algorithm and whose intermediate has a key of the second.
+macro_rules! chain {
+    ($root:literal, $int:literal) => {
+        (
+            $root,
+            $int,
+            include_bytes!(concat!("mixed_algorithms/", $root, ".root.der")).as_slice(),
+            include_bytes!(concat!("mixed_algorithms/", $root, "_", $int, ".int.der")).as_slice(),
+            include_bytes!(concat!("mixed_algorithms/", $root, "_", $int, ".ee.der")).as_slice(),
+        )
+    };
+}
+
+type Chain = (
+    &'static str,
+    &'static str,
+    &'static [u8],
+    &'static [u8],
+    &'static [u8],
+);
+
+static CHAINS: &[Chain] = &[
+    chain!("rsa", "rsa"),
+    chain!("rsa", "p256"),
+    chain!("rsa", "p384"),
+    chain!("rsa", "ed25519"),
+    chain!("p256", "rsa"),
+    chain!("p256", "p256"),
+    chain!("p256", "p384"),
+    chain!("p256", "ed25519"),
+    chain!("p384", "rsa"),
+    chain!("p384", "p256"),
+    chain!("p384", "p384"),
+    chain!("p384", "ed25519"),
+    chain!("ed25519", "rsa"),
+    chain!("ed25519", "p256"),
+    chain!("ed25519", "p384"),
+    chain!("ed25519", "ed25519"),
+];
+
+/// Verifies `chain` with `supported_sig_algs`, returning the depth of the certificate an
+/// error was found at along with the error.
+fn verify(
+    chain: &Chain,
+    supported_sig_algs: &[&dyn SignatureVerificationAlgorithm],
+) -> Result<(), (Error, Option<usize>)> {
+    let (_, _, root, int, ee) = chain;
+    Verification {
+        intermediates: &[CertificateDer::from(*int)],
+        supported_sig_algs,
+        ..Verification::new(ee, TIME)
+    }
+    .verify_detailed(root)
+}
+
+/// The algorithms of `ALGS`, except `missing`.
+fn algs_without(missing: &str) -> Vec<&'static dyn SignatureVerificationAlgorithm> {
+    ALGS.iter()
+        .filter(|(name, _)| *name != missing)
+        .map(|(_, alg)| *alg)
+        .collect()
+}
+
+#[test]
+fn every_pairing_verifies() {
+    let algs = ALGS.iter().map(|(_, alg)| *alg).collect::<Vec<_>>();
+    for chain in CHAINS {
+        assert_eq!(verify(chain, &algs), Ok(()), "{} {}", chain.0, chain.1);
+        assert_eq!(
+            verify(chain, webpki::ALL_VERIFICATION_ALGS),
+            Ok(()),
+            "{} {}",
+            chain.0,
+            chain.1
+        );
+    }
+}
+
+#[test]
+fn root_algorithm_unsupported() {
+    // The intermediate is signed by the root, so it's where the chain fails.
+    for chain in CHAINS {
+        assert_eq!(
+            verify(chain, &algs_without(chain.0)),
+            Err((Error::UnsupportedSignatureAlgorithm, Some(1))),
+            "{} {}",
+            chain.0,
+            chain.1
+        );
+    }
+}
+
+#[test]
+fn intermediate_algorithm_unsupported() {
+    for chain in CHAINS.iter().filter(|(root, int, ..)| root != int) {
+        assert_eq!(
+            verify(chain, &algs_without(chain.1)),
+            Err((Error::UnsupportedSignatureAlgorithm, Some(0))),
+            "{} {}",
+            chain.0,
+            chain.1
+        );
+    }
+}
+
+#[test]
+fn algorithm_unsupported_for_public_key() {
+    // ecdsa-with-SHA256 is supported, but only for P-384 keys, and the intermediate's key
+    // is a P-256 key.
+    let mut algs = algs_without("p256");
+    algs.push(ECDSA_P384_SHA256);
+    for chain in CHAINS
+        .iter()
+        .filter(|(root, int, ..)| *root != "p256" && *int == "p256")
+    {
+        assert_eq!(
+            verify(chain, &algs),
+            Err((Error::UnsupportedSignatureAlgorithmForPublicKey, Some(0))),
+            "{} {}",
+            chain.0,
+            chain.1
+        );
+    }
+}
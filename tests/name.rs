@@ -0,0 +1,46 @@
+#![cfg(feature = "alloc")]
+
+use pki_types::CertificateDer;
+use webpki::name::format_dn;
+
+/// Expected values are the output of `openssl x509 -noout -subject -issuer -nameopt RFC2253`.
+#[test]
+fn matches_openssl() {
+    for (der, subject, issuer) in [
+        (
+            &include_bytes!("netflix/ee.der")[..],
+            "CN=www.netflix.com,OU=Operations,O=Netflix\\, Inc.,L=los gatos,ST=California,C=US",
+            "CN=Symantec Class 3 Secure Server CA - G4,OU=Symantec Trust Network,O=Symantec Corporation,C=US",
+        ),
+        (
+            include_bytes!("netflix/inter.der"),
+            "CN=Symantec Class 3 Secure Server CA - G4,OU=Symantec Trust Network,O=Symantec Corporation,C=US",
+            "CN=VeriSign Class 3 Public Primary Certification Authority - G3,OU=(c) 1999 VeriSign\\, Inc. - For authorized use only,OU=VeriSign Trust Network,O=VeriSign\\, Inc.,C=US",
+        ),
+        (
+            include_bytes!("cloudflare_dns/ee.der"),
+            "CN=cloudflare-dns.com,O=Cloudflare\\, Inc.,L=San Francisco,ST=California,C=US",
+            "CN=DigiCert TLS Hybrid ECC SHA384 2020 CA1,O=DigiCert Inc,C=US",
+        ),
+        (
+            include_bytes!("misc/serial_neg.der"),
+            "CN=EC-ACC,OU=Jerarquia Entitats de Certificacio Catalanes,OU=Vegeu https://www.catcert.net/verarrel (c)03,OU=Serveis Publics de Certificacio,O=Agencia Catalana de Certificacio (NIF Q-0801176-I),C=ES",
+            "CN=EC-ACC,OU=Jerarquia Entitats de Certificacio Catalanes,OU=Vegeu https://www.catcert.net/verarrel (c)03,OU=Serveis Publics de Certificacio,O=Agencia Catalana de Certificacio (NIF Q-0801176-I),C=ES",
+        ),
+        (
+            include_bytes!("misc/mdoc_eku.ee.der"),
+            "C=US,CN=utopia ds",
+            "C=US,CN=utopia iaca",
+        ),
+        (
+            include_bytes!("misc/empty_sequence_common_name.der"),
+            "",
+            "OU=None",
+        ),
+    ] {
+        let der = CertificateDer::from(der);
+        let cert = webpki::EndEntityCert::try_from(&der).unwrap();
+        assert_eq!(format_dn(cert.subject()).unwrap(), subject);
+        assert_eq!(format_dn(cert.issuer()).unwrap(), issuer);
+    }
+}
@@ -0,0 +1,115 @@
+//! Verification of a certificate chain without touching the heap. The basic flow of parsing
+//! an end-entity certificate, building and verifying a path through a slice of intermediates,
+//! and checking the subject name must not need the `alloc` feature, so that webpki works in
+//! environments without an allocator. Run with
+//! `cargo test --no-default-features --features ring --test no_alloc` to check that it builds
+//! that way too.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+use core::time::Duration;
+use std::alloc::System;
+
+use pki_types::{CertificateDer, ServerName, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{anchor_from_trusted_cert, EndEntityCert, Error, KeyUsage};
+
+/// Counts each thread's allocations while it is tracking them, so that allocations by the
+/// test harness and by tests running on other threads are not counted.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get().map(|n| n + 1)));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    #[cfg(feature = "ring")]
+    webpki::ring::ECDSA_P256_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    webpki::aws_lc_rs::ECDSA_P256_SHA256,
+];
+
+static ROOT: &[u8] = include_bytes!("client_auth_revocation/dp_chain.root.ca.der");
+static INT_B: &[u8] = include_bytes!("client_auth_revocation/dp_chain.int.b.ca.der");
+static INT_A: &[u8] = include_bytes!("client_auth_revocation/dp_chain.int.a.ca.der");
+static EE: &[u8] = include_bytes!("client_auth_revocation/dp_chain.ee.der");
+
+/// Returns the result of `f` and the number of allocations it made.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    ALLOCATIONS.with(|count| count.set(Some(0)));
+    let result = f();
+    let count = ALLOCATIONS.with(|count| count.take()).unwrap();
+    (result, count)
+}
+
+#[test]
+fn path_through_intermediates() {
+    let root = CertificateDer::from(ROOT);
+    let intermediates = [CertificateDer::from(INT_A), CertificateDer::from(INT_B)];
+    let ee = CertificateDer::from(EE);
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
+
+    let (intermediate_count, allocations) = count_allocations(|| {
+        let anchors = [anchor_from_trusted_cert(&root).unwrap()];
+        EndEntityCert::try_from(&ee)
+            .unwrap()
+            .verify_for_usage(
+                ALGS,
+                &anchors,
+                &intermediates,
+                time,
+                KeyUsage::client_auth(),
+                None,
+                None,
+            )
+            .map(|path| path.intermediate_certificates().count())
+    });
+    assert_eq!(intermediate_count, Ok(2));
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn subject_name() {
+    let ca = CertificateDer::from(&include_bytes!("delta_crl/ca.der")[..]);
+    let ee = CertificateDer::from(&include_bytes!("delta_crl/ee_a.der")[..]);
+    let name = ServerName::try_from("a.example.com").unwrap();
+    let other_name = ServerName::try_from("b.example.com").unwrap();
+    // 2030-01-01T00:00:00Z, while both certificates are valid.
+    let time = UnixTime::since_unix_epoch(Duration::from_secs(1_893_456_000));
+
+    let (results, allocations) = count_allocations(|| {
+        let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+        let cert = EndEntityCert::try_from(&ee).unwrap();
+        (
+            cert.verify_for_usage(
+                ALGS,
+                &anchors,
+                &[],
+                time,
+                KeyUsage::server_auth(),
+                None,
+                None,
+            )
+            .map(|_| ()),
+            cert.verify_is_valid_for_subject_name(&name),
+            cert.verify_is_valid_for_subject_name(&other_name),
+        )
+    });
+    assert_eq!(results, (Ok(()), Ok(()), Err(Error::CertNotValidForName)));
+    assert_eq!(allocations, 0);
+}
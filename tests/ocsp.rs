@@ -0,0 +1,443 @@
+//! Verification of OCSP responses for an end-entity certificate, and encoding of requests for
+//! them. See `ocsp_responses()` and `ocsp_requests()` in tests/generate.py, and
+//! `ocsp_response_lists()` for the responses aligned with a path.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{
+    anchor_from_trusted_cert, Cert, DetailedError, EndEntityCert, Error, OcspCertStatus,
+    RevocationReason, UnknownStatusPolicy,
+};
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    #[cfg(feature = "ring")]
+    webpki::ring::ECDSA_P256_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    webpki::aws_lc_rs::ECDSA_P256_SHA256,
+];
+
+static CA: &[u8] = include_bytes!("ocsp/ca.der");
+static EE: &[u8] = include_bytes!("ocsp/ee.der");
+
+/// 2024-01-02T00:00:00Z, while every response is current.
+const TIME: u64 = 1_704_153_600;
+
+fn check(response: &[u8], time: u64) -> Result<OcspCertStatus, Error> {
+    let ca = CertificateDer::from(CA);
+    let ee = CertificateDer::from(EE);
+    EndEntityCert::try_from(&ee)
+        .unwrap()
+        .verify_ocsp_stapled_response(
+            &Cert::try_from(&ca).unwrap(),
+            response,
+            UnixTime::since_unix_epoch(Duration::from_secs(time)),
+            ALGS,
+        )
+}
+
+#[cfg(feature = "alloc")]
+fn request(hash: webpki::OcspHash, nonce: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+    let ca = CertificateDer::from(CA);
+    let ee = CertificateDer::from(EE);
+    let ee = EndEntityCert::try_from(&ee).unwrap();
+    let ca = Cert::try_from(&ca).unwrap();
+    match nonce {
+        Some(nonce) => ee.ocsp_request_with_nonce_for(&ca, hash, nonce),
+        None => ee.ocsp_request_for(&ca, hash),
+    }
+}
+
+#[test]
+fn signed_by_issuer() {
+    assert_eq!(
+        check(include_bytes!("ocsp/good.der"), TIME),
+        Ok(OcspCertStatus::Good)
+    );
+    assert_eq!(
+        check(include_bytes!("ocsp/good_by_name_sha256.der"), TIME),
+        Ok(OcspCertStatus::Good)
+    );
+    assert_eq!(
+        check(include_bytes!("ocsp/revoked.der"), TIME),
+        Ok(OcspCertStatus::Revoked {
+            time: UnixTime::since_unix_epoch(Duration::from_secs(1_685_577_600)), // 2023-06-01
+            reason: Some(RevocationReason::KeyCompromise),
+        })
+    );
+    assert_eq!(
+        check(include_bytes!("ocsp/unknown.der"), TIME),
+        Ok(OcspCertStatus::Unknown)
+    );
+}
+
+#[test]
+fn signed_by_delegated_responder() {
+    assert_eq!(
+        check(include_bytes!("ocsp/delegated.der"), TIME),
+        Ok(OcspCertStatus::Good)
+    );
+    // The responder certificate lacks the id-kp-OCSPSigning EKU.
+    assert_eq!(
+        check(include_bytes!("ocsp/delegated_no_eku.der"), TIME),
+        Err(Error::OcspResponderNotAuthorized)
+    );
+}
+
+#[test]
+fn response_validity_period() {
+    let response = include_bytes!("ocsp/good.der");
+    assert_eq!(
+        check(response, 1_704_067_200 - 1), // 2023-12-31T23:59:59Z
+        Err(Error::OcspResponseNotValidYet)
+    );
+    assert_eq!(
+        check(response, 1_704_672_000 + 1), // 2024-01-08T00:00:01Z
+        Err(Error::OcspResponseExpired)
+    );
+}
+
+#[test]
+fn response_for_other_cert() {
+    assert_eq!(
+        check(include_bytes!("ocsp/good_for_other_ee.der"), TIME),
+        Err(Error::OcspResponseCertMismatch)
+    );
+}
+
+#[test]
+fn tampered_response() {
+    let mut response = include_bytes!("ocsp/good.der").to_vec();
+    let last = response.len() - 1;
+    response[last] ^= 1;
+    assert_eq!(
+        check(&response, TIME),
+        Err(Error::InvalidSignatureForPublicKey)
+    );
+}
+
+#[test]
+fn unusable_responses() {
+    assert_eq!(
+        check(include_bytes!("ocsp/try_later.der"), TIME),
+        Err(Error::UnsuccessfulOcspResponse)
+    );
+
+    let good = include_bytes!("ocsp/good.der");
+    assert_eq!(
+        check(&good[..good.len() - 1], TIME),
+        Err(Error::MalformedOcspResponse)
+    );
+    assert_eq!(check(&[], TIME), Err(Error::MalformedOcspResponse));
+
+    // A successful response of an unknown type, 1.2.3.4.
+    let response = [
+        0x30, 0x0e, // OCSPResponse
+        0x0a, 0x01, 0x00, // responseStatus
+        0xa0, 0x09, 0x30, 0x07, // responseBytes
+        0x06, 0x03, 0x2a, 0x03, 0x04, // responseType
+        0x04, 0x00, // response
+    ];
+    assert_eq!(
+        check(&response, TIME),
+        Err(Error::UnsupportedOcspResponseType)
+    );
+}
+
+#[test]
+fn required_by_verifier() {
+    let ca = CertificateDer::from(CA);
+    let anchors = &[anchor_from_trusted_cert(&ca).unwrap()];
+    let ee = CertificateDer::from(EE);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let verify = |response: &[u8]| {
+        cert.verifier(ALGS, anchors)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+            .ocsp_response(response)
+            .verify()
+            .map(|_| ())
+    };
+
+    assert_eq!(verify(include_bytes!("ocsp/good.der")), Ok(()));
+    assert_eq!(
+        verify(include_bytes!("ocsp/revoked.der")),
+        Err(Error::CertRevoked)
+    );
+    assert_eq!(
+        verify(include_bytes!("ocsp/unknown.der")),
+        Err(Error::UnknownRevocationStatus)
+    );
+    assert_eq!(
+        verify(include_bytes!("ocsp/good_for_other_ee.der")),
+        Err(Error::OcspResponseCertMismatch)
+    );
+}
+
+#[test]
+fn signature_checks_limited_by_verifier() {
+    let ca = CertificateDer::from(CA);
+    let anchors = &[anchor_from_trusted_cert(&ca).unwrap()];
+    let ee = CertificateDer::from(EE);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let verify = |response: &[u8]| {
+        cert.verifier(ALGS, anchors)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+            .max_signature_checks(1)
+            .ocsp_response(response)
+            .verify()
+            .map(|_| ())
+    };
+
+    assert_eq!(verify(include_bytes!("ocsp/good.der")), Ok(()));
+    // Both the delegated responder's certificate and the response must be checked.
+    assert_eq!(
+        verify(include_bytes!("ocsp/delegated.der")),
+        Err(Error::MaximumSignatureChecksExceeded)
+    );
+}
+
+#[test]
+fn unknown_issuer() {
+    let ee = CertificateDer::from(EE);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+
+    // Without the issuer's key, not even a good response can be verified.
+    assert_eq!(
+        cert.verifier(ALGS, &[])
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+            .ocsp_response(include_bytes!("ocsp/good.der"))
+            .dangerous_verify_allowing_unknown_issuer()
+            .map(|_| ())
+            .map_err(Error::from),
+        Err(Error::UnknownRevocationStatus)
+    );
+}
+
+#[test]
+fn clock_skew() {
+    let ca = CertificateDer::from(CA);
+    let anchors = &[anchor_from_trusted_cert(&ca).unwrap()];
+    let ee = CertificateDer::from(EE);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let verify = |time: u64, skew: u64| {
+        cert.verifier(ALGS, anchors)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(time)))
+            .allow_clock_skew(Duration::from_secs(skew))
+            .ocsp_response(include_bytes!("ocsp/good.der"))
+            .verify()
+            .map(|_| ())
+    };
+
+    // The response is current from 2024-01-01T00:00:00Z to 2024-01-08T00:00:00Z.
+    assert_eq!(
+        verify(1_704_067_200 - 61, 60),
+        Err(Error::OcspResponseNotValidYet)
+    );
+    assert_eq!(verify(1_704_067_200 - 60, 60), Ok(()));
+    assert_eq!(verify(1_704_672_000 + 60, 60), Ok(()));
+    assert_eq!(
+        verify(1_704_672_000 + 61, 60),
+        Err(Error::OcspResponseExpired)
+    );
+    assert_eq!(
+        verify(1_704_672_000 + 1, 0),
+        Err(Error::OcspResponseExpired)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn requests() {
+    use webpki::OcspHash;
+
+    // Generated by `openssl ocsp -reqout`, so these check the encoding byte-for-byte.
+    assert_eq!(
+        request(OcspHash::Sha1, None).as_deref(),
+        Ok(&include_bytes!("ocsp/request_sha1.der")[..])
+    );
+    assert_eq!(
+        request(OcspHash::Sha256, None).as_deref(),
+        Ok(&include_bytes!("ocsp/request_sha256.der")[..])
+    );
+    // OpenSSL chooses a random 16 octet nonce, which ends the request.
+    let with_nonce = include_bytes!("ocsp/request_nonce.der");
+    let nonce = &with_nonce[with_nonce.len() - 16..];
+    assert_eq!(
+        request(OcspHash::Sha1, Some(nonce)).as_deref(),
+        Ok(&with_nonce[..])
+    );
+
+    // RFC 8954 nonces are from 1 to 32 octets long.
+    assert!(request(OcspHash::Sha256, Some(&[0xa5; 32])).is_ok());
+    assert_eq!(
+        request(OcspHash::Sha256, Some(&[])),
+        Err(Error::ExtensionValueInvalid)
+    );
+    assert_eq!(
+        request(OcspHash::Sha256, Some(&[0xa5; 33])),
+        Err(Error::ExtensionValueInvalid)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn request_for_wrong_issuer() {
+    let ee = CertificateDer::from(EE);
+    assert_eq!(
+        EndEntityCert::try_from(&ee)
+            .unwrap()
+            .ocsp_request_for(&Cert::try_from(&ee).unwrap(), webpki::OcspHash::Sha1),
+        Err(Error::UnknownIssuer)
+    );
+}
+
+mod response_list {
+    use super::*;
+
+    static ROOT: &[u8] = include_bytes!("ocsp_chain/root.der");
+    static INTER: &[u8] = include_bytes!("ocsp_chain/inter.der");
+    static EE: &[u8] = include_bytes!("ocsp_chain/ee.der");
+    static EE_GOOD: &[u8] = include_bytes!("ocsp_chain/ee_good.der");
+    static EE_UNKNOWN: &[u8] = include_bytes!("ocsp_chain/ee_unknown.der");
+    static INTER_GOOD: &[u8] = include_bytes!("ocsp_chain/inter_good.der");
+    static INTER_REVOKED: &[u8] = include_bytes!("ocsp_chain/inter_revoked.der");
+
+    /// Verifies the chain with `responses`, returning the status of each certificate, or the
+    /// error and the depth it was found at.
+    fn verify(
+        responses: &[&[u8]],
+        status_policy: UnknownStatusPolicy,
+    ) -> Result<Vec<Option<OcspCertStatus>>, (Error, Option<usize>)> {
+        let root = CertificateDer::from(ROOT);
+        let anchors = &[anchor_from_trusted_cert(&root).unwrap()];
+        let intermediates = &[CertificateDer::from(INTER)];
+        let ee = CertificateDer::from(EE);
+        let cert = EndEntityCert::try_from(&ee).unwrap();
+        cert.verifier(ALGS, anchors)
+            .intermediates(intermediates)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+            .ocsp_responses(responses, status_policy)
+            .verify_detailed()
+            .map(|path| path.ocsp_statuses().collect())
+            .map_err(|DetailedError { error, cert }| (error, cert.map(|cert| cert.depth)))
+    }
+
+    #[test]
+    fn aligned() {
+        let good = Some(OcspCertStatus::Good);
+        assert_eq!(
+            verify(&[EE_GOOD, INTER_GOOD], UnknownStatusPolicy::Deny),
+            Ok(vec![good, good])
+        );
+        assert_eq!(
+            verify(&[EE_GOOD, INTER_REVOKED], UnknownStatusPolicy::Allow),
+            Err((Error::CertRevoked, Some(1)))
+        );
+    }
+
+    #[test]
+    fn missing_responses() {
+        let good = Some(OcspCertStatus::Good);
+        assert_eq!(
+            verify(&[EE_GOOD], UnknownStatusPolicy::Allow),
+            Ok(vec![good, None])
+        );
+        assert_eq!(
+            verify(&[EE_GOOD], UnknownStatusPolicy::Deny),
+            Err((Error::UnknownRevocationStatus, Some(1)))
+        );
+        // An empty response stands for a missing one.
+        assert_eq!(
+            verify(&[&[], INTER_GOOD], UnknownStatusPolicy::Allow),
+            Ok(vec![None, good])
+        );
+        assert_eq!(
+            verify(&[&[], INTER_GOOD], UnknownStatusPolicy::Deny),
+            Err((Error::UnknownRevocationStatus, Some(0)))
+        );
+        assert_eq!(
+            verify(&[], UnknownStatusPolicy::Allow),
+            Ok(vec![None, None])
+        );
+    }
+
+    #[test]
+    fn unknown_status() {
+        assert_eq!(
+            verify(&[EE_UNKNOWN, INTER_GOOD], UnknownStatusPolicy::Allow),
+            Ok(vec![
+                Some(OcspCertStatus::Unknown),
+                Some(OcspCertStatus::Good)
+            ])
+        );
+        assert_eq!(
+            verify(&[EE_UNKNOWN, INTER_GOOD], UnknownStatusPolicy::Deny),
+            Err((Error::UnknownRevocationStatus, Some(0)))
+        );
+    }
+
+    #[test]
+    fn misaligned() {
+        assert_eq!(
+            verify(&[INTER_GOOD, EE_GOOD], UnknownStatusPolicy::Allow),
+            Err((Error::MisalignedOcspResponse { depth: 0 }, Some(0)))
+        );
+        assert_eq!(
+            verify(&[&[], EE_GOOD], UnknownStatusPolicy::Allow),
+            Err((Error::MisalignedOcspResponse { depth: 1 }, Some(1)))
+        );
+        // A response for a certificate outside the path is only for the wrong certificate.
+        assert_eq!(
+            verify(
+                &[include_bytes!("ocsp/good.der"), INTER_GOOD],
+                UnknownStatusPolicy::Allow
+            ),
+            Err((Error::OcspResponseCertMismatch, Some(0)))
+        );
+    }
+
+    #[test]
+    fn surplus() {
+        assert_eq!(
+            verify(
+                &[EE_GOOD, INTER_GOOD, INTER_GOOD],
+                UnknownStatusPolicy::Allow
+            ),
+            Err((Error::SurplusOcspResponses, None))
+        );
+        assert_eq!(
+            verify(&[EE_GOOD, INTER_GOOD, &[]], UnknownStatusPolicy::Allow),
+            Err((Error::SurplusOcspResponses, None))
+        );
+    }
+
+    #[test]
+    fn unknown_issuer() {
+        let ee = CertificateDer::from(EE);
+        let cert = EndEntityCert::try_from(&ee).unwrap();
+        let intermediates = &[CertificateDer::from(INTER)];
+        let verify = |status_policy| {
+            cert.verifier(ALGS, &[])
+                .intermediates(intermediates)
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+                .ocsp_responses(&[EE_GOOD, INTER_GOOD], status_policy)
+                .dangerous_verify_allowing_unknown_issuer()
+                .map_err(|DetailedError { error, cert }| (error, cert.map(|cert| cert.depth)))
+        };
+
+        // The intermediate's issuer is unknown, so its response can't be verified.
+        match verify(UnknownStatusPolicy::Allow) {
+            Ok(webpki::dangerous::AcceptedPath::UnknownIssuer(path)) => assert_eq!(
+                path.ocsp_statuses().collect::<Vec<_>>(),
+                vec![Some(OcspCertStatus::Good), None]
+            ),
+            _ => panic!("expected an unanchored path"),
+        }
+        assert!(matches!(
+            verify(UnknownStatusPolicy::Deny),
+            Err((Error::UnknownRevocationStatus, Some(1)))
+        ));
+    }
+}
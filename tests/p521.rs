@@ -0,0 +1,77 @@
+//! ECDSA P-521 certificates, whose signatures are only verified with the aws-lc-rs
+//! backend. See `p521()` in tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+mod common;
+
+use webpki::Error;
+
+use common::Verification;
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+fn verify(root: &[u8], ee: &[u8]) -> Result<(), Error> {
+    Verification::new(ee, TIME).verify(root)
+}
+
+#[test]
+fn p521_root_issues_p256_end_entity() {
+    let result = verify(
+        include_bytes!("p521/p521_root.der"),
+        include_bytes!("p521/p256_ee.der"),
+    );
+    match cfg!(feature = "aws_lc_rs") {
+        true => assert_eq!(result, Ok(())),
+        // ecdsa-with-SHA512 is not supported by the *ring* algorithms.
+        false => assert_eq!(result, Err(Error::UnsupportedSignatureAlgorithm)),
+    }
+}
+
+#[test]
+fn p256_root_issues_p521_end_entity() {
+    // The P-521 key isn't used to verify the path.
+    assert_eq!(
+        verify(
+            include_bytes!("p521/p256_root.der"),
+            include_bytes!("p521/p521_ee.der")
+        ),
+        Ok(())
+    );
+}
+
+#[cfg(feature = "aws_lc_rs")]
+#[test]
+fn p521_end_entity_signature() {
+    use pki_types::CertificateDer;
+    use webpki::aws_lc_rs::{
+        ECDSA_P256_SHA256, ECDSA_P521_SHA256, ECDSA_P521_SHA384, ECDSA_P521_SHA512,
+    };
+    use webpki::EndEntityCert;
+
+    let ee = CertificateDer::from(&include_bytes!("p521/p521_ee.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let message = include_bytes!("p521/message.bin");
+    let signature = include_bytes!("p521/p521_ee_sha512.sig.bin");
+
+    assert_eq!(
+        cert.verify_signature(ECDSA_P521_SHA512, message, signature),
+        Ok(())
+    );
+    assert_eq!(
+        cert.verify_signature(ECDSA_P521_SHA512, b"hello world?", signature),
+        Err(Error::InvalidSignatureForPublicKey)
+    );
+    // The right key, but the wrong digest.
+    for alg in [ECDSA_P521_SHA256, ECDSA_P521_SHA384] {
+        assert_eq!(
+            cert.verify_signature(alg, message, signature),
+            Err(Error::InvalidSignatureForPublicKey)
+        );
+    }
+    assert_eq!(
+        cert.verify_signature(ECDSA_P256_SHA256, message, signature),
+        Err(Error::UnsupportedSignatureAlgorithmForPublicKey)
+    );
+}
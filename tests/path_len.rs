@@ -0,0 +1,122 @@
+//! Enforcement of the pathLenConstraint of CA certificates. See `path_len()` in
+//! tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{anchor_from_trusted_cert, BasicConstraints, Cert, EndEntityCert, Error};
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    #[cfg(feature = "ring")]
+    webpki::ring::ECDSA_P256_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    webpki::aws_lc_rs::ECDSA_P256_SHA256,
+];
+
+static ROOT: &[u8] = include_bytes!("path_len/root.der");
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+fn check(ee: &[u8], intermediates: &[&'static [u8]]) -> Result<(), Error> {
+    let root = CertificateDer::from(ROOT);
+    let anchors = &[anchor_from_trusted_cert(&root).unwrap()];
+    let intermediates = intermediates
+        .iter()
+        .map(|der| CertificateDer::from(*der))
+        .collect::<Vec<_>>();
+    let ee = CertificateDer::from(ee);
+    EndEntityCert::try_from(&ee)
+        .unwrap()
+        .verifier(ALGS, anchors)
+        .intermediates(&intermediates)
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+        .verify()
+        .map(|_| ())
+}
+
+#[test]
+fn path_len_zero_with_intermediate() {
+    assert_eq!(
+        check(
+            include_bytes!("path_len/len0_sub_ee.der"),
+            &[
+                include_bytes!("path_len/len0_sub.der"),
+                include_bytes!("path_len/len0.der"),
+            ],
+        ),
+        Err(Error::PathLenConstraintViolated)
+    );
+}
+
+#[test]
+fn path_len_one_with_intermediate() {
+    assert_eq!(
+        check(
+            include_bytes!("path_len/len1_sub_ee.der"),
+            &[
+                include_bytes!("path_len/len1_sub.der"),
+                include_bytes!("path_len/len1.der"),
+            ],
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn self_issued_intermediate_not_counted() {
+    assert_eq!(
+        check(
+            include_bytes!("path_len/len0_self_issued_ee.der"),
+            &[
+                include_bytes!("path_len/len0_self_issued.der"),
+                include_bytes!("path_len/len0.der"),
+            ],
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn parsed_intermediates() {
+    let root = CertificateDer::from(ROOT);
+    let anchors = &[anchor_from_trusted_cert(&root).unwrap()];
+    let ders = [
+        CertificateDer::from(&include_bytes!("path_len/len0_sub.der")[..]),
+        CertificateDer::from(&include_bytes!("path_len/len0.der")[..]),
+        CertificateDer::from(&include_bytes!("path_len/len0_self_issued.der")[..]),
+    ];
+    let intermediates = ders
+        .iter()
+        .map(|der| Cert::try_from(der).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        intermediates[1].basic_constraints(),
+        Ok(Some(BasicConstraints {
+            is_ca: true,
+            path_len_constraint: Some(0),
+            critical: true,
+        }))
+    );
+
+    let verify = |ee: &'static [u8]| {
+        let ee = CertificateDer::from(ee);
+        EndEntityCert::try_from(&ee)
+            .unwrap()
+            .verifier(ALGS, anchors)
+            .parsed_intermediates(&intermediates)
+            .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+            .verify()
+            .map(|_| ())
+    };
+    assert_eq!(
+        verify(include_bytes!("path_len/len0_sub_ee.der")),
+        Err(Error::PathLenConstraintViolated)
+    );
+    assert_eq!(
+        verify(include_bytes!("path_len/len0_self_issued_ee.der")),
+        Ok(())
+    );
+}
@@ -0,0 +1,152 @@
+//! Certificate policy processing with `Verifier::required_policies()`. See
+//! `certificate_policies()` in tests/generate.py.
+
+#![cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+
+mod common;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm};
+use webpki::Error;
+
+use common::Verification;
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    #[cfg(feature = "ring")]
+    webpki::ring::ECDSA_P256_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    webpki::aws_lc_rs::ECDSA_P256_SHA256,
+];
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+/// 1.3.6.1.4.1.55555.1.1
+static POLICY_A: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0xb2, 0x03, 0x01, 0x01];
+/// 1.3.6.1.4.1.55555.1.2
+static POLICY_B: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0xb2, 0x03, 0x01, 0x02];
+
+fn verify(ee: &[u8], intermediates: &[&[u8]], required: &[&[u8]]) -> Result<(), Error> {
+    let intermediates = intermediates
+        .iter()
+        .map(|der| CertificateDer::from(*der))
+        .collect::<Vec<_>>();
+    Verification {
+        intermediates: &intermediates,
+        supported_sig_algs: ALGS,
+        required_policies: required,
+        ..Verification::new(ee, TIME)
+    }
+    .verify(include_bytes!("policies/root.der"))
+}
+
+#[test]
+fn plain() {
+    let ee = include_bytes!("policies/plain.ee.der");
+    let int = &include_bytes!("policies/plain.int1.der")[..];
+    assert_eq!(verify(ee, &[int], &[]), Ok(()));
+    assert_eq!(verify(ee, &[int], &[POLICY_A]), Ok(()));
+    assert_eq!(
+        verify(ee, &[int], &[POLICY_B]),
+        Err(Error::RequiredPolicyNotMet)
+    );
+    assert_eq!(
+        verify(ee, &[int], &[POLICY_A, POLICY_B]),
+        Err(Error::RequiredPolicyNotMet)
+    );
+}
+
+#[test]
+fn no_end_entity_policies() {
+    let ee = include_bytes!("policies/no_ee_policies.ee.der");
+    let int = &include_bytes!("policies/no_ee_policies.int1.der")[..];
+    assert_eq!(verify(ee, &[int], &[]), Ok(()));
+    assert_eq!(
+        verify(ee, &[int], &[POLICY_A]),
+        Err(Error::RequiredPolicyNotMet)
+    );
+}
+
+#[test]
+fn any_policy() {
+    // anyPolicy in the intermediate lets the end-entity certificate assert any policy.
+    let ee = include_bytes!("policies/any_policy.ee.der");
+    let int = &include_bytes!("policies/any_policy.int1.der")[..];
+    assert_eq!(verify(ee, &[int], &[POLICY_A]), Ok(()));
+    assert_eq!(
+        verify(ee, &[int], &[POLICY_B]),
+        Err(Error::RequiredPolicyNotMet)
+    );
+}
+
+#[test]
+fn inhibit_any_policy() {
+    // The end-entity certificate's anyPolicy would stand for policy A, but the intermediate
+    // inhibits it.
+    let ee = include_bytes!("policies/inhibit_any_policy.ee.der");
+    let int = &include_bytes!("policies/inhibit_any_policy.int1.der")[..];
+    assert_eq!(
+        verify(ee, &[int], &[POLICY_A]),
+        Err(Error::RequiredPolicyNotMet)
+    );
+    // The critical inhibitAnyPolicy extension isn't understood without required policies.
+    assert_eq!(
+        verify(ee, &[int], &[]),
+        Err(Error::UnsupportedCriticalExtension)
+    );
+}
+
+#[test]
+fn mapped() {
+    // The end-entity certificate's policy B is policy A in the trust anchor's domain.
+    let ee = include_bytes!("policies/mapped.ee.der");
+    let int = &include_bytes!("policies/mapped.int1.der")[..];
+    assert_eq!(verify(ee, &[int], &[POLICY_A]), Ok(()));
+    assert_eq!(
+        verify(ee, &[int], &[POLICY_B]),
+        Err(Error::RequiredPolicyNotMet)
+    );
+}
+
+#[test]
+fn mapping_inhibited() {
+    let ee = include_bytes!("policies/mapping_inhibited.ee.der");
+    let int1 = &include_bytes!("policies/mapping_inhibited.int1.der")[..];
+    let int2 = &include_bytes!("policies/mapping_inhibited.int2.der")[..];
+    for required in [POLICY_A, POLICY_B] {
+        assert_eq!(
+            verify(ee, &[int2, int1], &[required]),
+            Err(Error::RequiredPolicyNotMet)
+        );
+    }
+}
+
+#[test]
+fn require_explicit() {
+    let ee = include_bytes!("policies/require_explicit.ee.der");
+    let int = &include_bytes!("policies/require_explicit.int1.der")[..];
+    assert_eq!(verify(ee, &[int], &[POLICY_A]), Ok(()));
+    assert_eq!(
+        verify(ee, &[int], &[POLICY_B]),
+        Err(Error::RequiredPolicyNotMet)
+    );
+    assert_eq!(
+        verify(ee, &[int], &[]),
+        Err(Error::UnsupportedCriticalExtension)
+    );
+}
+
+#[test]
+fn detailed_error() {
+    let verification = Verification {
+        intermediates: &[CertificateDer::from(
+            &include_bytes!("policies/plain.int1.der")[..],
+        )],
+        supported_sig_algs: ALGS,
+        required_policies: &[POLICY_B],
+        ..Verification::new(include_bytes!("policies/plain.ee.der"), TIME)
+    };
+    assert_eq!(
+        verification.verify_detailed(include_bytes!("policies/root.der")),
+        Err((Error::RequiredPolicyNotMet, Some(0)))
+    );
+}
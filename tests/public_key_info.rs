@@ -0,0 +1,90 @@
+//! The algorithm and size of certificates' public keys, for enforcing key policies.
+
+use pki_types::CertificateDer;
+use webpki::{EcCurve, EndEntityCert, PublicKeyInfo};
+
+fn check(der: &[u8], expected: PublicKeyInfo<'_>) {
+    let ee = CertificateDer::from(der);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    assert_eq!(cert.public_key_info(), Ok(expected));
+}
+
+#[test]
+fn rsa_modulus_bits() {
+    // Each modulus has its top bit set, so is encoded with a leading zero byte.
+    for (der, modulus_bits) in [
+        (
+            &include_bytes!("signatures/rsa_1024_not_supported.ee.der")[..],
+            1024,
+        ),
+        (include_bytes!("signatures/rsa_2048.ee.der"), 2048),
+        (include_bytes!("signatures/rsa_3072.ee.der"), 3072),
+        (include_bytes!("signatures/rsa_4096.ee.der"), 4096),
+    ] {
+        check(der, PublicKeyInfo::Rsa { modulus_bits });
+    }
+}
+
+#[test]
+fn ec_curves() {
+    for (der, curve) in [
+        (
+            &include_bytes!("signatures/ecdsa_p256.ee.der")[..],
+            EcCurve::P256,
+        ),
+        (
+            include_bytes!("signatures/ecdsa_p384.ee.der"),
+            EcCurve::P384,
+        ),
+        (
+            include_bytes!("signatures/ecdsa_p521_not_supported.ee.der"),
+            EcCurve::P521,
+        ),
+    ] {
+        check(der, PublicKeyInfo::Ec { curve });
+    }
+}
+
+#[test]
+fn ed25519() {
+    check(
+        include_bytes!("signatures/ed25519.ee.der"),
+        PublicKeyInfo::Ed25519,
+    );
+}
+#[cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+#[cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+#[test]
+fn verified_path_keys() {
+    use core::time::Duration;
+
+    use pki_types::UnixTime;
+    use webpki::{anchor_from_trusted_cert, KeyUsage};
+
+    let ee = CertificateDer::from(&include_bytes!("netflix/ee.der")[..]);
+    let intermediates = [CertificateDer::from(
+        &include_bytes!("netflix/inter.der")[..],
+    )];
+    let ca = CertificateDer::from(&include_bytes!("netflix/ca.der")[..]);
+    let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let path = cert
+        .verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+        .intermediates(&intermediates)
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(
+            1_492_441_716,
+        )))
+        .key_usage(KeyUsage::server_auth())
+        .verify()
+        .unwrap();
+
+    let keys = core::iter::once(path.end_entity().public_key_info())
+        .chain(
+            path.intermediate_certificates()
+                .map(|c| c.public_key_info()),
+        )
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(keys, [PublicKeyInfo::Rsa { modulus_bits: 2048 }; 2]);
+}
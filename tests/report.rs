@@ -0,0 +1,128 @@
+#![cfg(all(feature = "serde", feature = "ring"))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, UnixTime};
+use serde_json::json;
+use webpki::report::verify_with_report;
+use webpki::{
+    anchor_from_trusted_cert, lint_chain, EndEntityCert, Error, KeyUsage, VerificationPolicy,
+};
+
+fn netflix_report(intermediates: &[CertificateDer<'_>]) -> String {
+    let ee = CertificateDer::from(&include_bytes!("netflix/ee.der")[..]);
+    let ca = CertificateDer::from(&include_bytes!("netflix/ca.der")[..]);
+    let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+
+    let report = verify_with_report(
+        &ee,
+        webpki::ALL_VERIFICATION_ALGS,
+        &anchors,
+        intermediates,
+        UnixTime::since_unix_epoch(Duration::from_secs(1_492_441_716)), // 2017-04-17T15:08:36Z
+        KeyUsage::server_auth(),
+        None,
+        VerificationPolicy::default(),
+    );
+    serde_json::to_string_pretty(&report).unwrap() + "\n"
+}
+
+#[test]
+fn report_for_verified_path() {
+    let inter = CertificateDer::from(&include_bytes!("netflix/inter.der")[..]);
+    assert_eq!(
+        netflix_report(&[inter]),
+        include_str!("report/netflix.ok.json")
+    );
+}
+
+#[test]
+fn report_for_unknown_issuer() {
+    assert_eq!(
+        netflix_report(&[]),
+        include_str!("report/netflix.unknown_issuer.json")
+    );
+}
+
+#[test]
+fn verified_path_serializes_as_report_chain() {
+    let ee = CertificateDer::from(&include_bytes!("netflix/ee.der")[..]);
+    let inter = CertificateDer::from(&include_bytes!("netflix/inter.der")[..]);
+    let ca = CertificateDer::from(&include_bytes!("netflix/ca.der")[..]);
+    let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+    let intermediates = [inter];
+
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let path = cert
+        .verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+        .intermediates(&intermediates)
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(
+            1_492_441_716,
+        )))
+        .verify()
+        .unwrap();
+
+    let report: serde_json::Value =
+        serde_json::from_str(include_str!("report/netflix.ok.json")).unwrap();
+    assert_eq!(serde_json::to_value(&path).unwrap(), report["chain"]);
+}
+
+#[test]
+fn inspection_types() {
+    let ee = CertificateDer::from(&include_bytes!("smartcard_logon/ee.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let sans = cert
+        .subject_alternative_names()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(sans).unwrap(),
+        json!([
+            {
+                "other_name": {
+                    "type_id": "1.3.6.1.4.1.311.20.2.3",
+                    "value": "0c146a6f6540636f72702e6578616d706c652e636f6d",
+                }
+            },
+            {
+                "other_name": {
+                    "type_id": "1.3.6.1.4.1.32473.3",
+                    "value": "a103020105",
+                }
+            },
+        ])
+    );
+    assert_eq!(
+        serde_json::to_value(cert.public_key_info().unwrap()).unwrap(),
+        json!({ "ec": { "curve": "p256" } })
+    );
+
+    assert_eq!(
+        serde_json::to_value(Error::CertExpired {
+            time: UnixTime::since_unix_epoch(Duration::from_secs(20)),
+            not_after: UnixTime::since_unix_epoch(Duration::from_secs(10)),
+        })
+        .unwrap(),
+        json!({ "cert_expired": { "time": 20, "not_after": 10 } })
+    );
+    assert_eq!(
+        serde_json::to_value(Error::UnknownIssuer).unwrap(),
+        json!("unknown_issuer")
+    );
+
+    let ca = CertificateDer::from(&include_bytes!("netflix/ca.der")[..]);
+    let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+    let ee = CertificateDer::from(&include_bytes!("netflix/ee.der")[..]);
+    let report = lint_chain(
+        &ee,
+        &[],
+        &anchors,
+        UnixTime::since_unix_epoch(Duration::from_secs(1_492_441_716)),
+    );
+    assert_eq!(
+        serde_json::to_value(report).unwrap(),
+        json!({
+            "findings": [{ "severity": "error", "index": 0, "error": "unknown_issuer" }]
+        })
+    );
+}
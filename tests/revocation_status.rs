@@ -0,0 +1,198 @@
+//! The revocation status reported for each certificate in a verified path, using the CRLs
+//! generated for tests/client_auth_revocation.rs.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, UnixTime};
+use webpki::{
+    anchor_from_trusted_cert, BorrowedCertRevocationList, Cert, CertRevocationList, EndEntityCert,
+    Error, KeyUsage, RevocationCheckDepth, RevocationOptionsBuilder, RevocationStatus,
+    UnknownStatusPolicy,
+};
+
+use RevocationStatus::*;
+
+/// The end-entity, intermediate and root certificates of `chain`.
+macro_rules! certs {
+    ($chain:literal) => {
+        [
+            include_bytes!(concat!("client_auth_revocation/", $chain, ".ee.der")).as_slice(),
+            include_bytes!(concat!("client_auth_revocation/", $chain, ".int.a.ca.der")).as_slice(),
+            include_bytes!(concat!("client_auth_revocation/", $chain, ".int.b.ca.der")).as_slice(),
+            include_bytes!(concat!("client_auth_revocation/", $chain, ".root.ca.der")).as_slice(),
+        ]
+        .map(CertificateDer::from)
+    };
+}
+
+fn statuses(
+    certs: [CertificateDer<'_>; 4],
+    crl: &[u8],
+    depth: RevocationCheckDepth,
+) -> Result<Vec<RevocationStatus>, Error> {
+    statuses_with(certs, &[crl], depth, UnknownStatusPolicy::Allow)
+}
+
+fn statuses_with(
+    [ee, int_a, int_b, root]: [CertificateDer<'_>; 4],
+    crls: &[&[u8]],
+    depth: RevocationCheckDepth,
+    status_policy: UnknownStatusPolicy,
+) -> Result<Vec<RevocationStatus>, Error> {
+    let anchors = [anchor_from_trusted_cert(&root).unwrap()];
+    let intermediates = [int_a, int_b];
+    let crls = crls
+        .iter()
+        .map(|crl| CertRevocationList::Borrowed(BorrowedCertRevocationList::from_der(crl).unwrap()))
+        .collect::<Vec<_>>();
+    let crls = crls.iter().collect::<Vec<_>>();
+    let revocation = RevocationOptionsBuilder::new(&crls)
+        .unwrap()
+        .with_depth(depth)
+        .with_status_policy(status_policy)
+        .build();
+
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let path = cert
+        .verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+        .intermediates(&intermediates)
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+        .key_usage(KeyUsage::client_auth())
+        .revocation(revocation)
+        .verify()?;
+    Ok(path.revocation_statuses().collect())
+}
+
+#[test]
+fn crl_with_matching_idp() {
+    let crl = include_bytes!("client_auth_revocation/ee_dp_idp_match.crl.der");
+    assert_eq!(
+        statuses(certs!("dp_chain"), crl, RevocationCheckDepth::EndEntity),
+        Ok(vec![NotRevoked, NotChecked, NotChecked])
+    );
+    // The CRL is only authoritative for the end-entity certificate's issuer.
+    assert_eq!(
+        statuses(certs!("dp_chain"), crl, RevocationCheckDepth::Chain),
+        Ok(vec![NotRevoked, Unknown, Unknown])
+    );
+}
+
+#[test]
+fn crl_without_idp_for_cert_with_dp() {
+    // A CRL without an issuing distribution point only covers certificates without
+    // distribution points.
+    let crl = include_bytes!("client_auth_revocation/ee_crl_no_idp_unknown_status.crl.der");
+    assert_eq!(
+        statuses(certs!("dp_chain"), crl, RevocationCheckDepth::EndEntity),
+        Ok(vec![Unknown, NotChecked, NotChecked])
+    );
+}
+
+#[test]
+fn crl_with_mismatched_idp() {
+    let crl = include_bytes!("client_auth_revocation/ee_crl_mismatched_idp_unknown_status.crl.der");
+    assert_eq!(
+        statuses(certs!("dp_chain"), crl, RevocationCheckDepth::EndEntity),
+        Ok(vec![Unknown, NotChecked, NotChecked])
+    );
+}
+
+#[test]
+fn crl_with_mismatched_issuer() {
+    let crl =
+        include_bytes!("client_auth_revocation/no_relevant_crl_ee_depth_allow_unknown.crl.der");
+    assert_eq!(
+        statuses(certs!("no_ku_chain"), crl, RevocationCheckDepth::EndEntity),
+        Ok(vec![Unknown, NotChecked, NotChecked])
+    );
+}
+
+#[test]
+fn crl_without_idp_for_cert_without_dp() {
+    let crl = include_bytes!("client_auth_revocation/ee_not_revoked_chain_depth.crl.der");
+    assert_eq!(
+        statuses(certs!("no_ku_chain"), crl, RevocationCheckDepth::EndEntity),
+        Ok(vec![NotRevoked, NotChecked, NotChecked])
+    );
+}
+
+#[test]
+fn intermediates_covered_but_not_end_entity() {
+    // CRLs from the issuers of both intermediates, but not from the end-entity's issuer.
+    let int_a_crl =
+        include_bytes!("client_auth_revocation/int_not_revoked_chain_depth_forbid_unknown.crl.der");
+    let int_b_crl = include_bytes!(
+        "client_auth_revocation/int_not_revoked_chain_depth_forbid_unknown_b.crl.der"
+    );
+    for crls in [[&int_a_crl[..], int_b_crl], [int_b_crl, int_a_crl]] {
+        assert_eq!(
+            statuses_with(
+                certs!("no_ku_chain"),
+                &crls,
+                RevocationCheckDepth::Chain,
+                UnknownStatusPolicy::Deny
+            ),
+            Err(Error::UnknownRevocationStatus)
+        );
+        assert_eq!(
+            statuses_with(
+                certs!("no_ku_chain"),
+                &crls,
+                RevocationCheckDepth::Chain,
+                UnknownStatusPolicy::Allow
+            ),
+            Ok(vec![Unknown, NotRevoked, NotRevoked])
+        );
+    }
+
+    let ee_crl = include_bytes!(
+        "client_auth_revocation/int_not_revoked_chain_depth_forbid_unknown_ee.crl.der"
+    );
+    assert_eq!(
+        statuses_with(
+            certs!("no_ku_chain"),
+            &[ee_crl, int_a_crl, int_b_crl],
+            RevocationCheckDepth::Chain,
+            UnknownStatusPolicy::Deny
+        ),
+        Ok(vec![NotRevoked, NotRevoked, NotRevoked])
+    );
+}
+
+#[test]
+fn not_checked_without_revocation_options() {
+    let [ee, int_a, int_b, root] = certs!("dp_chain");
+    let anchors = [anchor_from_trusted_cert(&root).unwrap()];
+    let intermediates = [int_a, int_b];
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let path = cert
+        .verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+        .intermediates(&intermediates)
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)))
+        .key_usage(KeyUsage::client_auth())
+        .verify()
+        .unwrap();
+    assert_eq!(
+        path.revocation_statuses().collect::<Vec<_>>(),
+        [NotChecked, NotChecked, NotChecked]
+    );
+}
+
+#[test]
+fn crl_distribution_point_uris() {
+    let [ee, ..] = certs!("dp_chain");
+    let cert = Cert::try_from(&ee).unwrap();
+    assert_eq!(
+        cert.crl_distribution_point_uris().collect::<Vec<_>>(),
+        [
+            "http://example.com/another.crl",
+            "http://example.com/valid.crl"
+        ]
+    );
+
+    let [ee, ..] = certs!("no_ku_chain");
+    let cert = Cert::try_from(&ee).unwrap();
+    assert_eq!(cert.crl_distribution_point_uris().count(), 0);
+}
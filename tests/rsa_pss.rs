@@ -0,0 +1,69 @@
+//! Verification of certificates signed with RSASSA-PSS. See `rsa_pss()` in tests/generate.py.
+
+#![cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{anchor_from_trusted_cert, EndEntityCert, Error, KeyUsage};
+
+#[cfg(feature = "ring")]
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    webpki::ring::RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
+    webpki::ring::RSA_PSS_2048_8192_SHA384_LEGACY_KEY,
+    webpki::ring::RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
+];
+#[cfg(all(feature = "aws_lc_rs", not(feature = "ring")))]
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    webpki::aws_lc_rs::RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
+    webpki::aws_lc_rs::RSA_PSS_2048_8192_SHA384_LEGACY_KEY,
+    webpki::aws_lc_rs::RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
+];
+
+static CA: &[u8] = include_bytes!("rsa_pss/ca.der");
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+fn check(ee: &[u8], algs: &[&dyn SignatureVerificationAlgorithm]) -> Result<(), Error> {
+    let ca = CertificateDer::from(CA);
+    let anchors = &[anchor_from_trusted_cert(&ca).unwrap()];
+    let ee = CertificateDer::from(ee);
+    EndEntityCert::try_from(&ee)
+        .unwrap()
+        .verifier(algs, anchors)
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+        .key_usage(KeyUsage::server_auth())
+        .verify()
+        .map(|_| ())
+}
+
+#[test]
+fn signed_with_pss() {
+    for ee in [
+        &include_bytes!("rsa_pss/ee_sha256.der")[..],
+        include_bytes!("rsa_pss/ee_sha384.der"),
+        include_bytes!("rsa_pss/ee_sha512.der"),
+    ] {
+        assert_eq!(check(ee, ALGS), Ok(()));
+    }
+}
+
+#[test]
+fn pss_algorithm_not_supported() {
+    // Only the algorithm for the hash used is accepted.
+    assert_eq!(
+        check(include_bytes!("rsa_pss/ee_sha384.der"), &ALGS[..1]),
+        Err(Error::UnsupportedSignatureAlgorithm)
+    );
+}
+
+#[test]
+fn unsupported_pss_parameters() {
+    for ee in [
+        &include_bytes!("rsa_pss/ee_sha256_salt20.der")[..],
+        include_bytes!("rsa_pss/ee_sha256_mgf1_sha512.der"),
+    ] {
+        assert_eq!(check(ee, ALGS), Err(Error::UnsupportedSignatureAlgorithm));
+    }
+}
@@ -0,0 +1,105 @@
+//! Certificates whose serial numbers are negative, zero, or longer than 20 octets, which
+//! webpki accepts unless a `SerialNumberPolicy` says otherwise. See `serial_numbers()` in
+//! tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+mod common;
+
+use pki_types::CertificateDer;
+use webpki::{EndEntityCert, Error, SerialNumberPolicy, VerificationPolicy};
+
+use common::Verification;
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+fn verify(ee: &[u8], serial_number: SerialNumberPolicy) -> Result<(), Error> {
+    Verification {
+        policy: VerificationPolicy::default().with_serial_number_policy(serial_number),
+        ..Verification::new(ee, TIME)
+    }
+    .verify(include_bytes!("serial_numbers/ca.der"))
+}
+
+#[test]
+fn serials_are_exposed() {
+    for (ee, serial) in [
+        (
+            &include_bytes!("serial_numbers/ee_negative.der")[..],
+            &[0xde, 0xad, 0xbe, 0xef][..],
+        ),
+        (include_bytes!("serial_numbers/ee_zero.der"), &[0x00]),
+    ] {
+        let ee = CertificateDer::from(ee);
+        assert_eq!(EndEntityCert::try_from(&ee).unwrap().serial(), serial);
+    }
+
+    let ee = CertificateDer::from(&include_bytes!("serial_numbers/ee_21_octets.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    assert_eq!(cert.serial().len(), 21);
+    assert_eq!(cert.serial()[..2], [0x00, 0x80]);
+}
+
+#[test]
+fn any_serial_by_default() {
+    for ee in [
+        &include_bytes!("serial_numbers/ee_negative.der")[..],
+        include_bytes!("serial_numbers/ee_zero.der"),
+        include_bytes!("serial_numbers/ee_21_octets.der"),
+        include_bytes!("serial_numbers/ee_20_octets.der"),
+    ] {
+        assert_eq!(verify(ee, SerialNumberPolicy::default()), Ok(()));
+    }
+}
+
+#[test]
+fn require_positive() {
+    let policy = SerialNumberPolicy::new().require_positive();
+    assert_eq!(
+        verify(include_bytes!("serial_numbers/ee_negative.der"), policy),
+        Err(Error::NonPositiveSerialNumber)
+    );
+    assert_eq!(
+        verify(include_bytes!("serial_numbers/ee_zero.der"), policy),
+        Err(Error::NonPositiveSerialNumber)
+    );
+    assert_eq!(
+        verify(include_bytes!("serial_numbers/ee_21_octets.der"), policy),
+        Ok(())
+    );
+}
+
+#[test]
+fn limit_length() {
+    let policy = SerialNumberPolicy::new().limit_length();
+    assert_eq!(
+        verify(include_bytes!("serial_numbers/ee_21_octets.der"), policy),
+        Err(Error::SerialNumberTooLong)
+    );
+    assert_eq!(
+        verify(include_bytes!("serial_numbers/ee_20_octets.der"), policy),
+        Ok(())
+    );
+    assert_eq!(
+        verify(include_bytes!("serial_numbers/ee_negative.der"), policy),
+        Ok(())
+    );
+}
+
+#[test]
+fn baseline_requirements() {
+    let policy = SerialNumberPolicy::baseline_requirements();
+    assert_eq!(
+        verify(include_bytes!("serial_numbers/ee_negative.der"), policy),
+        Err(Error::NonPositiveSerialNumber)
+    );
+    assert_eq!(
+        verify(include_bytes!("serial_numbers/ee_21_octets.der"), policy),
+        Err(Error::SerialNumberTooLong)
+    );
+    assert_eq!(
+        verify(include_bytes!("serial_numbers/ee_20_octets.der"), policy),
+        Ok(())
+    );
+}
@@ -46,6 +46,58 @@ fn check_sig(
     cert.verify_signature(alg, message, signature)
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn signature_over_other_message_rejected() {
+    let message = include_bytes!("signatures/message.bin");
+    let mut other = message.to_vec();
+    other[0] ^= 1;
+    let cases: &[(&[u8], &dyn SignatureVerificationAlgorithm, &[u8])] = &[
+        (
+            include_bytes!("signatures/ed25519.ee.der"),
+            ED25519,
+            include_bytes!("signatures/ed25519_key_and_ed25519_good_signature.sig.bin"),
+        ),
+        (
+            include_bytes!("signatures/ecdsa_p256.ee.der"),
+            ECDSA_P256_SHA256,
+            include_bytes!(
+                "signatures/ecdsa_p256_key_and_ecdsa_p256_sha256_good_signature.sig.bin"
+            ),
+        ),
+        (
+            include_bytes!("signatures/ecdsa_p384.ee.der"),
+            ECDSA_P384_SHA384,
+            include_bytes!(
+                "signatures/ecdsa_p384_key_and_ecdsa_p384_sha384_good_signature.sig.bin"
+            ),
+        ),
+        (
+            include_bytes!("signatures/rsa_2048.ee.der"),
+            RSA_PKCS1_2048_8192_SHA256,
+            include_bytes!(
+                "signatures/rsa_2048_key_and_rsa_pkcs1_2048_8192_sha256_good_signature.sig.bin"
+            ),
+        ),
+    ];
+
+    for (ee, alg, signature) in cases {
+        assert_eq!(check_sig(ee, *alg, message, signature), Ok(()));
+        assert_eq!(
+            check_sig(ee, *alg, &other, signature),
+            Err(webpki::Error::InvalidSignatureForPublicKey)
+        );
+        assert_eq!(
+            check_sig(ee, *alg, message, &signature[..signature.len() - 1]),
+            Err(webpki::Error::InvalidSignatureForPublicKey)
+        );
+        assert_eq!(
+            check_sig(ee, *alg, message, &[]),
+            Err(webpki::Error::InvalidSignatureForPublicKey)
+        );
+    }
+}
+
 // DO NOT EDIT BELOW: generated by tests/generate.py
 
 #[test]
@@ -0,0 +1,73 @@
+//! Smartcard logon certificates, whose subject alternative names are otherNames such as a
+//! Microsoft user principal name. See `smartcard_logon()` in tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+mod common;
+
+use pki_types::CertificateDer;
+use webpki::{EndEntityCert, Error, KeyUsage, SubjectAltName};
+
+use common::Verification;
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+fn verify(ca: &[u8], ee: &[u8]) -> Result<(), Error> {
+    Verification {
+        usage: KeyUsage::client_auth(),
+        ..Verification::new(ee, TIME)
+    }
+    .verify(ca)
+}
+
+#[test]
+fn microsoft_upn() {
+    let ee = CertificateDer::from(&include_bytes!("smartcard_logon/ee.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    assert_eq!(cert.microsoft_upn(), Ok(Some("joe@corp.example.com")));
+
+    assert_eq!(
+        cert.subject_alternative_names()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
+        [
+            SubjectAltName::OtherName {
+                type_id: &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x14, 0x02, 0x03],
+                value: b"\x0c\x14joe@corp.example.com",
+            },
+            SubjectAltName::OtherName {
+                type_id: &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x81, 0xfd, 0x59, 0x03],
+                value: &[0xa1, 0x03, 0x02, 0x01, 0x05],
+            },
+        ]
+    );
+
+    let ca = CertificateDer::from(&include_bytes!("smartcard_logon/ca.der")[..]);
+    let cert = EndEntityCert::try_from(&ca).unwrap();
+    assert_eq!(cert.microsoft_upn(), Ok(None));
+}
+
+#[test]
+fn verify_smartcard_logon() {
+    assert_eq!(
+        verify(
+            include_bytes!("smartcard_logon/ca.der"),
+            include_bytes!("smartcard_logon/ee.der")
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn other_name_constraints_fail_closed() {
+    // Constraints on otherNames can't be evaluated, so a certificate with an otherName of
+    // the constrained type is rejected.
+    assert_eq!(
+        verify(
+            include_bytes!("smartcard_logon/constrained_ca.der"),
+            include_bytes!("smartcard_logon/constrained_ee.der")
+        ),
+        Err(Error::NameConstraintViolation)
+    );
+}
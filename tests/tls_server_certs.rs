@@ -16,13 +16,29 @@
 use core::time::Duration;
 
 use pki_types::{CertificateDer, ServerName, UnixTime};
-use webpki::{anchor_from_trusted_cert, KeyUsage};
+use webpki::{anchor_from_trusted_cert, KeyUsage, VerificationPolicy};
 
 fn check_cert(
     ee: &[u8],
     ca: &[u8],
     valid_names: &[&str],
     invalid_names: &[&str],
+) -> Result<(), webpki::Error> {
+    check_cert_with_policy(
+        ee,
+        ca,
+        valid_names,
+        invalid_names,
+        VerificationPolicy::default(),
+    )
+}
+
+fn check_cert_with_policy(
+    ee: &[u8],
+    ca: &[u8],
+    valid_names: &[&str],
+    invalid_names: &[&str],
+    policy: VerificationPolicy,
 ) -> Result<(), webpki::Error> {
     let ca_cert_der = CertificateDer::from(ca);
     let anchors = [anchor_from_trusted_cert(&ca_cert_der).unwrap()];
@@ -30,15 +46,11 @@ fn check_cert(
     let ee_der = CertificateDer::from(ee);
     let time = UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d));
     let cert = webpki::EndEntityCert::try_from(&ee_der).unwrap();
-    cert.verify_for_usage(
-        webpki::ALL_VERIFICATION_ALGS,
-        &anchors,
-        &[],
-        time,
-        KeyUsage::server_auth(),
-        None,
-        None,
-    )?;
+    cert.verifier(webpki::ALL_VERIFICATION_ALGS, &anchors)
+        .at_time(time)
+        .key_usage(KeyUsage::server_auth())
+        .policy(policy)
+        .verify()?;
 
     for valid in valid_names {
         let name = ServerName::try_from(*valid).unwrap();
@@ -100,6 +112,126 @@ fn allow_subject_common_name() {
     assert_eq!(check_cert(ee, ca, &[], &["allowed.example.com"]), Ok(()));
 }
 
+#[test]
+fn allow_subject_common_name_subject_names() {
+    let ee = include_bytes!("tls_server_certs/allow_subject_common_name.ee.der");
+    let ca = include_bytes!("tls_server_certs/allow_subject_common_name.ca.der");
+    assert_eq!(
+        check_cert_with_policy(
+            ee,
+            ca,
+            &[],
+            &["allowed.example.com"],
+            VerificationPolicy::default().with_subject_name_constraints(true),
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn exclude_dns_name_subject_common_name() {
+    let ee = include_bytes!("tls_server_certs/exclude_dns_name_subject_common_name.ee.der");
+    let ca = include_bytes!("tls_server_certs/exclude_dns_name_subject_common_name.ca.der");
+    assert_eq!(
+        check_cert(
+            ee,
+            ca,
+            &["allowed.example.com"],
+            &["disallowed.example.com"]
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn exclude_dns_name_subject_common_name_subject_names() {
+    let ee = include_bytes!("tls_server_certs/exclude_dns_name_subject_common_name.ee.der");
+    let ca = include_bytes!("tls_server_certs/exclude_dns_name_subject_common_name.ca.der");
+    assert_eq!(
+        check_cert_with_policy(
+            ee,
+            ca,
+            &["allowed.example.com"],
+            &["disallowed.example.com"],
+            VerificationPolicy::default().with_subject_name_constraints(true),
+        ),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn permit_dns_name_subject_common_name_mismatch() {
+    let ee = include_bytes!("tls_server_certs/permit_dns_name_subject_common_name_mismatch.ee.der");
+    let ca = include_bytes!("tls_server_certs/permit_dns_name_subject_common_name_mismatch.ca.der");
+    assert_eq!(check_cert(ee, ca, &["allowed.example.com"], &[]), Ok(()));
+}
+
+#[test]
+fn permit_dns_name_subject_common_name_mismatch_subject_names() {
+    let ee = include_bytes!("tls_server_certs/permit_dns_name_subject_common_name_mismatch.ee.der");
+    let ca = include_bytes!("tls_server_certs/permit_dns_name_subject_common_name_mismatch.ca.der");
+    assert_eq!(
+        check_cert_with_policy(
+            ee,
+            ca,
+            &["allowed.example.com"],
+            &[],
+            VerificationPolicy::default().with_subject_name_constraints(true),
+        ),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn exclude_ip_address_subject_common_name() {
+    let ee = include_bytes!("tls_server_certs/exclude_ip_address_subject_common_name.ee.der");
+    let ca = include_bytes!("tls_server_certs/exclude_ip_address_subject_common_name.ca.der");
+    assert_eq!(check_cert(ee, ca, &["allowed.example.com"], &[]), Ok(()));
+}
+
+#[test]
+fn exclude_ip_address_subject_common_name_subject_names() {
+    let ee = include_bytes!("tls_server_certs/exclude_ip_address_subject_common_name.ee.der");
+    let ca = include_bytes!("tls_server_certs/exclude_ip_address_subject_common_name.ca.der");
+    assert_eq!(
+        check_cert_with_policy(
+            ee,
+            ca,
+            &["allowed.example.com"],
+            &[],
+            VerificationPolicy::default().with_subject_name_constraints(true),
+        ),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn permit_dns_name_subject_common_name_not_dns_name() {
+    let ee =
+        include_bytes!("tls_server_certs/permit_dns_name_subject_common_name_not_dns_name.ee.der");
+    let ca =
+        include_bytes!("tls_server_certs/permit_dns_name_subject_common_name_not_dns_name.ca.der");
+    assert_eq!(check_cert(ee, ca, &["allowed.example.com"], &[]), Ok(()));
+}
+
+#[test]
+fn permit_dns_name_subject_common_name_not_dns_name_subject_names() {
+    let ee =
+        include_bytes!("tls_server_certs/permit_dns_name_subject_common_name_not_dns_name.ee.der");
+    let ca =
+        include_bytes!("tls_server_certs/permit_dns_name_subject_common_name_not_dns_name.ca.der");
+    assert_eq!(
+        check_cert_with_policy(
+            ee,
+            ca,
+            &["allowed.example.com"],
+            &[],
+            VerificationPolicy::default().with_subject_name_constraints(true),
+        ),
+        Ok(())
+    );
+}
+
 #[test]
 fn allow_dns_san() {
     let ee = include_bytes!("tls_server_certs/allow_dns_san.ee.der");
@@ -145,6 +277,104 @@ fn we_incorrectly_ignore_name_constraints_on_name_in_subject() {
     assert_eq!(check_cert(ee, ca, &[], &[]), Ok(()));
 }
 
+#[test]
+fn we_incorrectly_ignore_name_constraints_on_name_in_subject_subject_names() {
+    let ee = include_bytes!(
+        "tls_server_certs/we_incorrectly_ignore_name_constraints_on_name_in_subject.ee.der"
+    );
+    let ca = include_bytes!(
+        "tls_server_certs/we_incorrectly_ignore_name_constraints_on_name_in_subject.ca.der"
+    );
+    assert_eq!(
+        check_cert_with_policy(
+            ee,
+            ca,
+            &[],
+            &[],
+            VerificationPolicy::default().with_subject_name_constraints(true),
+        ),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn permit_rfc822_name_host() {
+    let ee = include_bytes!("tls_server_certs/permit_rfc822_name_host.ee.der");
+    let ca = include_bytes!("tls_server_certs/permit_rfc822_name_host.ca.der");
+    assert_eq!(check_cert(ee, ca, &[], &[]), Ok(()));
+}
+
+#[test]
+fn permit_rfc822_name_host_mismatch() {
+    let ee = include_bytes!("tls_server_certs/permit_rfc822_name_host_mismatch.ee.der");
+    let ca = include_bytes!("tls_server_certs/permit_rfc822_name_host_mismatch.ca.der");
+    assert_eq!(
+        check_cert(ee, ca, &[], &[]),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn permit_rfc822_name_domain() {
+    let ee = include_bytes!("tls_server_certs/permit_rfc822_name_domain.ee.der");
+    let ca = include_bytes!("tls_server_certs/permit_rfc822_name_domain.ca.der");
+    assert_eq!(check_cert(ee, ca, &[], &[]), Ok(()));
+}
+
+#[test]
+fn exclude_rfc822_name_mailbox() {
+    let ee = include_bytes!("tls_server_certs/exclude_rfc822_name_mailbox.ee.der");
+    let ca = include_bytes!("tls_server_certs/exclude_rfc822_name_mailbox.ca.der");
+    assert_eq!(
+        check_cert(ee, ca, &[], &[]),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn exclude_rfc822_name_mailbox_mismatch() {
+    let ee = include_bytes!("tls_server_certs/exclude_rfc822_name_mailbox_mismatch.ee.der");
+    let ca = include_bytes!("tls_server_certs/exclude_rfc822_name_mailbox_mismatch.ca.der");
+    assert_eq!(check_cert(ee, ca, &[], &[]), Ok(()));
+}
+
+#[test]
+fn permit_uri_host() {
+    let ee = include_bytes!("tls_server_certs/permit_uri_host.ee.der");
+    let ca = include_bytes!("tls_server_certs/permit_uri_host.ca.der");
+    assert_eq!(check_cert(ee, ca, &[], &[]), Ok(()));
+}
+
+#[test]
+fn permit_uri_domain_mismatch() {
+    let ee = include_bytes!("tls_server_certs/permit_uri_domain_mismatch.ee.der");
+    let ca = include_bytes!("tls_server_certs/permit_uri_domain_mismatch.ca.der");
+    assert_eq!(
+        check_cert(ee, ca, &[], &[]),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn exclude_uri_domain() {
+    let ee = include_bytes!("tls_server_certs/exclude_uri_domain.ee.der");
+    let ca = include_bytes!("tls_server_certs/exclude_uri_domain.ca.der");
+    assert_eq!(
+        check_cert(ee, ca, &[], &[]),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn exclude_uri_without_authority() {
+    let ee = include_bytes!("tls_server_certs/exclude_uri_without_authority.ee.der");
+    let ca = include_bytes!("tls_server_certs/exclude_uri_without_authority.ca.der");
+    assert_eq!(
+        check_cert(ee, ca, &[], &[]),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
 #[test]
 fn reject_constraints_on_unimplemented_names() {
     let ee = include_bytes!("tls_server_certs/reject_constraints_on_unimplemented_names.ee.der");
@@ -194,6 +424,42 @@ fn wildcard_san_rejected_if_in_excluded_subtree() {
     );
 }
 
+#[test]
+fn wildcard_san_accepted_if_in_subdomain_subtree() {
+    let ee =
+        include_bytes!("tls_server_certs/wildcard_san_accepted_if_in_subdomain_subtree.ee.der");
+    let ca =
+        include_bytes!("tls_server_certs/wildcard_san_accepted_if_in_subdomain_subtree.ca.der");
+    assert_eq!(
+        check_cert(ee, ca, &["bob.example.com"], &["example.com"]),
+        Ok(())
+    );
+}
+
+#[test]
+fn wildcard_san_rejected_if_subtree_is_narrower() {
+    let ee = include_bytes!("tls_server_certs/wildcard_san_rejected_if_subtree_is_narrower.ee.der");
+    let ca = include_bytes!("tls_server_certs/wildcard_san_rejected_if_subtree_is_narrower.ca.der");
+    assert_eq!(
+        check_cert(ee, ca, &[], &[]),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
+#[test]
+fn wildcard_san_rejected_if_expansion_in_excluded_subtree() {
+    let ee = include_bytes!(
+        "tls_server_certs/wildcard_san_rejected_if_expansion_in_excluded_subtree.ee.der"
+    );
+    let ca = include_bytes!(
+        "tls_server_certs/wildcard_san_rejected_if_expansion_in_excluded_subtree.ca.der"
+    );
+    assert_eq!(
+        check_cert(ee, ca, &[], &[]),
+        Err(webpki::Error::NameConstraintViolation)
+    );
+}
+
 #[test]
 fn ip4_address_san_rejected_if_in_excluded_subtree() {
     let ee =
@@ -311,9 +577,16 @@ fn ip46_mixed_address_san_allowed() {
 }
 
 #[test]
-fn permit_directory_name_not_implemented() {
-    let ee = include_bytes!("tls_server_certs/permit_directory_name_not_implemented.ee.der");
-    let ca = include_bytes!("tls_server_certs/permit_directory_name_not_implemented.ca.der");
+fn permit_directory_name() {
+    let ee = include_bytes!("tls_server_certs/permit_directory_name.ee.der");
+    let ca = include_bytes!("tls_server_certs/permit_directory_name.ca.der");
+    assert_eq!(check_cert(ee, ca, &[], &[]), Ok(()));
+}
+
+#[test]
+fn permit_directory_name_mismatch() {
+    let ee = include_bytes!("tls_server_certs/permit_directory_name_mismatch.ee.der");
+    let ca = include_bytes!("tls_server_certs/permit_directory_name_mismatch.ca.der");
     assert_eq!(
         check_cert(ee, ca, &[], &[]),
         Err(webpki::Error::NameConstraintViolation)
@@ -321,15 +594,22 @@ fn permit_directory_name_not_implemented() {
 }
 
 #[test]
-fn exclude_directory_name_not_implemented() {
-    let ee = include_bytes!("tls_server_certs/exclude_directory_name_not_implemented.ee.der");
-    let ca = include_bytes!("tls_server_certs/exclude_directory_name_not_implemented.ca.der");
+fn exclude_directory_name() {
+    let ee = include_bytes!("tls_server_certs/exclude_directory_name.ee.der");
+    let ca = include_bytes!("tls_server_certs/exclude_directory_name.ca.der");
     assert_eq!(
         check_cert(ee, ca, &[], &[]),
         Err(webpki::Error::NameConstraintViolation)
     );
 }
 
+#[test]
+fn exclude_directory_name_mismatch() {
+    let ee = include_bytes!("tls_server_certs/exclude_directory_name_mismatch.ee.der");
+    let ca = include_bytes!("tls_server_certs/exclude_directory_name_mismatch.ca.der");
+    assert_eq!(check_cert(ee, ca, &[], &[]), Ok(()));
+}
+
 #[test]
 fn invalid_dns_name_matching() {
     let ee = include_bytes!("tls_server_certs/invalid_dns_name_matching.ee.der");
@@ -0,0 +1,84 @@
+#![cfg(all(feature = "alloc", any(feature = "ring", feature = "aws_lc_rs")))]
+
+mod common;
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, Der, TrustAnchor, UnixTime};
+use webpki::{anchor_from_trusted_cert, KeyUsage, TrustAnchorSet};
+
+use common::Verification;
+
+// Laid out like the anchors compiled into webpki-roots. This is tests/misc/mdoc_eku.ca.der.
+static STATIC_ANCHORS: &[TrustAnchor<'static>] = &[TrustAnchor {
+    subject: Der::from_slice(
+        b"\x31\x14\x30\x12\x06\x03\x55\x04\x03\x0c\x0b\x75\x74\x6f\x70\x69\x61\x20\x69\x61\x63\
+          \x61\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x55\x53",
+    ),
+    subject_public_key_info: Der::from_slice(
+        b"\x30\x13\x06\x07\x2a\x86\x48\xce\x3d\x02\x01\x06\x08\x2a\x86\x48\xce\x3d\x03\x01\x07\
+          \x03\x42\x00\x04\x2c\x3e\x10\x3d\xbc\x07\xb2\x5c\x5a\x77\x0a\xee\xdf\xa5\xd8\xbd\x15\
+          \x41\x7e\x3e\x67\x61\x42\x46\x1a\x78\x75\xe3\xb4\x18\x8a\x22\x21\xe6\x42\x35\x99\xd1\
+          \xdb\x19\xaa\xef\x66\xf9\x23\xd3\x94\xb6\x17\x09\x54\x9b\xce\xc2\xea\x6f\xf6\x0e\xc7\
+          \x52\x68\xf2\xe0\x94",
+    ),
+    name_constraints: None,
+}];
+
+fn verify(anchors: &TrustAnchorSet<'_>, ee: &[u8], eku: KeyUsage, time: UnixTime) {
+    Verification {
+        usage: eku,
+        ..Verification::new(ee, time.as_secs())
+    }
+    .verify_with(anchors.as_slice())
+    .unwrap();
+}
+
+#[test]
+fn static_and_private_anchors() {
+    let private_ca = CertificateDer::from(
+        &include_bytes!("client_auth/cert_with_no_eku_accepted_for_client_auth.ca.der")[..],
+    );
+    let private_anchors = [anchor_from_trusted_cert(&private_ca).unwrap()];
+
+    let mut anchors = TrustAnchorSet::from(STATIC_ANCHORS);
+    anchors.extend_from_slice(&private_anchors);
+    assert_eq!(anchors.len(), 2);
+
+    // Both anchors remain discoverable by subject.
+    let static_anchor = anchors
+        .find_by_subject(STATIC_ANCHORS[0].subject.as_ref())
+        .collect::<Vec<_>>();
+    assert_eq!(static_anchor, [&STATIC_ANCHORS[0]]);
+    let private_anchor = anchors
+        .find_by_subject(private_anchors[0].subject.as_ref())
+        .collect::<Vec<_>>();
+    assert_eq!(private_anchor, [&private_anchors[0]]);
+    assert_eq!(anchors.find_by_subject(b"").count(), 0);
+
+    // Chains to either anchor verify against the combined set.
+    verify(
+        &anchors,
+        include_bytes!("misc/mdoc_eku.ee.der"),
+        KeyUsage::required(&[40, 129, 140, 93, 5, 1, 2]),
+        UnixTime::since_unix_epoch(Duration::from_secs(1_609_459_200)), // 2021-01-01T00:00:00Z
+    );
+    verify(
+        &anchors,
+        include_bytes!("client_auth/cert_with_no_eku_accepted_for_client_auth.ee.der"),
+        KeyUsage::client_auth(),
+        UnixTime::since_unix_epoch(Duration::from_secs(0x1fed_f00d)),
+    );
+}
+
+#[test]
+fn debug_omits_anchor_contents() {
+    let anchors = TrustAnchorSet::from(STATIC_ANCHORS);
+    assert_eq!(
+        format!("{:?}", anchors),
+        format!(
+            "TrustAnchorSet {{ len: 1, generation: {} }}",
+            anchors.generation()
+        )
+    );
+}
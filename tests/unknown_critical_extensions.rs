@@ -0,0 +1,133 @@
+//! Rejecting certificates with critical extensions that webpki doesn't understand, unless
+//! `Verifier::ignore_critical_extensions()` names them. See `unknown_critical_extensions()`
+//! in tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+mod common;
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{anchor_from_trusted_cert, EndEntityCert, Error, RawExtension};
+
+use common::Verification;
+
+static ALGS: &[&dyn SignatureVerificationAlgorithm] = &[
+    #[cfg(feature = "ring")]
+    webpki::ring::ECDSA_P256_SHA256,
+    #[cfg(feature = "aws_lc_rs")]
+    webpki::aws_lc_rs::ECDSA_P256_SHA256,
+];
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+/// 1.3.6.1.4.1.55555.2.1, the OID of the unknown extension.
+static UNKNOWN: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0xb2, 0x03, 0x02, 0x01];
+/// 1.3.6.1.4.1.55555.2.2
+static OTHER: &[u8] = &[0x2b, 0x06, 0x01, 0x04, 0x01, 0x83, 0xb2, 0x03, 0x02, 0x02];
+
+fn verify(ee: &[u8], intermediate: &[u8], ignored: &[&[u8]]) -> Result<(), Error> {
+    Verification {
+        intermediates: &[CertificateDer::from(intermediate)],
+        supported_sig_algs: ALGS,
+        ignored_extensions: ignored,
+        ..Verification::new(ee, TIME)
+    }
+    .verify(include_bytes!("unknown_critical_extensions/root.der"))
+}
+
+#[test]
+fn end_entity() {
+    let ee = include_bytes!("unknown_critical_extensions/ee_unknown.der");
+    let int = include_bytes!("unknown_critical_extensions/int.der");
+    assert_eq!(
+        verify(ee, int, &[]),
+        Err(Error::UnsupportedCriticalExtension)
+    );
+    assert_eq!(
+        verify(ee, int, &[OTHER]),
+        Err(Error::UnsupportedCriticalExtension)
+    );
+    assert_eq!(verify(ee, int, &[UNKNOWN]), Ok(()));
+    assert_eq!(verify(ee, int, &[OTHER, UNKNOWN]), Ok(()));
+}
+
+#[test]
+fn intermediate() {
+    let ee = include_bytes!("unknown_critical_extensions/ee.der");
+    let int = include_bytes!("unknown_critical_extensions/int_unknown.der");
+    assert_eq!(
+        verify(ee, int, &[]),
+        Err(Error::UnsupportedCriticalExtension)
+    );
+    assert_eq!(
+        verify(ee, int, &[OTHER]),
+        Err(Error::UnsupportedCriticalExtension)
+    );
+    assert_eq!(verify(ee, int, &[UNKNOWN]), Ok(()));
+}
+
+#[test]
+fn detailed_error() {
+    let root = CertificateDer::from(&include_bytes!("unknown_critical_extensions/root.der")[..]);
+    let anchors = [anchor_from_trusted_cert(&root).unwrap()];
+    let intermediates = [CertificateDer::from(
+        &include_bytes!("unknown_critical_extensions/int_unknown.der")[..],
+    )];
+    let ee = CertificateDer::from(&include_bytes!("unknown_critical_extensions/ee.der")[..]);
+
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let err = cert
+        .verifier(ALGS, &anchors)
+        .intermediates(&intermediates)
+        .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+        .verify_detailed()
+        .err()
+        .unwrap();
+    assert_eq!(err.error, Error::UnsupportedCriticalExtension);
+    assert_eq!(err.cert.map(|cert| cert.depth), Some(1));
+}
+
+#[test]
+fn trust_anchor() {
+    // A trust anchor is not in the path, and can't be given ignored extensions.
+    let int =
+        CertificateDer::from(&include_bytes!("unknown_critical_extensions/int_unknown.der")[..]);
+    assert_eq!(
+        anchor_from_trusted_cert(&int).err(),
+        Some(Error::UnsupportedCriticalExtension)
+    );
+}
+
+#[test]
+fn extensions() {
+    let ee =
+        CertificateDer::from(&include_bytes!("unknown_critical_extensions/ee_unknown.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    let extensions = cert.extensions().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(
+        extensions
+            .iter()
+            .map(|extension| (extension.oid, extension.critical))
+            .collect::<Vec<_>>(),
+        [
+            (&[0x55, 0x1d, 0x13][..], true),  // basicConstraints
+            (&[0x55, 0x1d, 0x11][..], false), // subjectAltName
+            (UNKNOWN, true),
+        ]
+    );
+    assert_eq!(
+        extensions[2],
+        RawExtension {
+            oid: UNKNOWN,
+            critical: true,
+            value: &[0x05, 0x00],
+        }
+    );
+
+    // An application that handles the extension itself can have verification accept it.
+    let int = include_bytes!("unknown_critical_extensions/int.der");
+    assert_eq!(verify(&ee, int, &[extensions[2].oid]), Ok(()));
+}
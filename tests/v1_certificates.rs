@@ -0,0 +1,104 @@
+//! v1 end-entity certificates, which have no extensions and are identified by their subject
+//! commonName. See `v1_certificates()` in tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+mod common;
+
+use pki_types::{CertificateDer, IpAddr, ServerName};
+use webpki::{EndEntityCert, Error};
+
+use common::Verification;
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+fn verify(root: &[u8], ee: &[u8], allow_v1: bool) -> Result<(), Error> {
+    Verification {
+        allow_v1,
+        ..Verification::new(ee, TIME)
+    }
+    .verify(root)
+}
+
+#[test]
+fn rejected_by_default() {
+    assert_eq!(
+        verify(
+            include_bytes!("v1_certificates/v3_root.der"),
+            include_bytes!("v1_certificates/ee.der"),
+            false
+        ),
+        Err(Error::UnsupportedCertVersion)
+    );
+}
+
+#[test]
+fn allowed() {
+    for root in [
+        &include_bytes!("v1_certificates/root.der")[..],
+        &include_bytes!("v1_certificates/v3_root.der")[..],
+    ] {
+        assert_eq!(
+            verify(root, include_bytes!("v1_certificates/ee.der"), true),
+            Ok(())
+        );
+    }
+}
+
+#[test]
+fn extensions_rejected() {
+    let ee = CertificateDer::from(&include_bytes!("v1_certificates/ee_with_extensions.der")[..]);
+    assert_eq!(
+        EndEntityCert::try_from(&ee).err(),
+        Some(Error::UnsupportedCertVersion)
+    );
+}
+
+#[test]
+fn subject_name_from_common_name() {
+    let ee = CertificateDer::from(&include_bytes!("v1_certificates/ee.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    for name in [
+        "device.example.com",
+        "DEVICE.Example.com",
+        "device.example.com.",
+    ] {
+        assert_eq!(
+            cert.verify_is_valid_for_subject_name(&ServerName::try_from(name).unwrap()),
+            Ok(()),
+            "{name}"
+        );
+    }
+    for name in ["example.com", "other.example.com", "sub.device.example.com"] {
+        assert_eq!(
+            cert.verify_is_valid_for_subject_name(&ServerName::try_from(name).unwrap()),
+            Err(Error::CertNotValidForName),
+            "{name}"
+        );
+    }
+    assert_eq!(
+        cert.verify_is_valid_for_subject_name(&ServerName::IpAddress(
+            IpAddr::try_from("192.0.2.1").unwrap()
+        )),
+        Err(Error::CertNotValidForName)
+    );
+}
+
+#[test]
+fn wildcard_common_name_not_expanded() {
+    let ee = CertificateDer::from(&include_bytes!("v1_certificates/wildcard_ee.der")[..]);
+    let cert = EndEntityCert::try_from(&ee).unwrap();
+    assert_eq!(
+        verify(
+            include_bytes!("v1_certificates/root.der"),
+            include_bytes!("v1_certificates/wildcard_ee.der"),
+            true
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        cert.verify_is_valid_for_subject_name(&ServerName::try_from("device.example.com").unwrap()),
+        Err(Error::CertNotValidForName)
+    );
+}
@@ -0,0 +1,47 @@
+#![cfg(all(feature = "testing", any(feature = "ring", feature = "aws_lc_rs")))]
+
+use std::fs;
+
+use webpki::testing::{run_vector, VectorOutcome};
+
+#[test]
+fn run_all_vectors() {
+    let mut paths = fs::read_dir("tests/vectors")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert!(!paths.is_empty());
+
+    for path in paths {
+        let json = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            run_vector(&json),
+            VectorOutcome::Passed,
+            "{}",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn invalid_vectors() {
+    assert!(matches!(run_vector("{"), VectorOutcome::Invalid(_)));
+    assert!(matches!(
+        run_vector(r#"{"end_entity": "", "anchors": ["!"], "time": 0, "expected_error": null}"#),
+        VectorOutcome::Invalid(_)
+    ));
+}
+
+#[test]
+fn mismatched_vector() {
+    let json = fs::read_to_string("tests/vectors/name_constraints.disallow_dns_san.json").unwrap();
+    let json = json.replace("\"NameConstraintViolation\"", "null");
+    assert_eq!(
+        run_vector(&json),
+        VectorOutcome::Mismatch {
+            expected: None,
+            actual: Some("NameConstraintViolation".to_string()),
+        }
+    );
+}
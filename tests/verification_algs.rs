@@ -0,0 +1,182 @@
+//! The per-backend lists of signature verification algorithms. See `verification_algs()` in
+//! tests/generate.py.
+
+#![cfg(any(feature = "ring", feature = "aws_lc_rs"))]
+
+use core::time::Duration;
+
+use pki_types::{CertificateDer, SignatureVerificationAlgorithm, UnixTime};
+use webpki::{anchor_from_trusted_cert, EndEntityCert};
+
+/// 2030-01-01T00:00:00Z, while every certificate is valid.
+const TIME: u64 = 1_893_456_000;
+
+/// The name, CA and end-entity certificate of a chain signed with each algorithm.
+static CHAINS: &[(&str, &[u8], &[u8])] = &[
+    (
+        "ecdsa_p256_sha256",
+        include_bytes!("verification_algs/ecdsa_p256_sha256.ca.der"),
+        include_bytes!("verification_algs/ecdsa_p256_sha256.ee.der"),
+    ),
+    (
+        "ecdsa_p256_sha384",
+        include_bytes!("verification_algs/ecdsa_p256_sha384.ca.der"),
+        include_bytes!("verification_algs/ecdsa_p256_sha384.ee.der"),
+    ),
+    (
+        "ecdsa_p384_sha256",
+        include_bytes!("verification_algs/ecdsa_p384_sha256.ca.der"),
+        include_bytes!("verification_algs/ecdsa_p384_sha256.ee.der"),
+    ),
+    (
+        "ecdsa_p384_sha384",
+        include_bytes!("verification_algs/ecdsa_p384_sha384.ca.der"),
+        include_bytes!("verification_algs/ecdsa_p384_sha384.ee.der"),
+    ),
+    (
+        "ecdsa_p521_sha256",
+        include_bytes!("verification_algs/ecdsa_p521_sha256.ca.der"),
+        include_bytes!("verification_algs/ecdsa_p521_sha256.ee.der"),
+    ),
+    (
+        "ecdsa_p521_sha384",
+        include_bytes!("verification_algs/ecdsa_p521_sha384.ca.der"),
+        include_bytes!("verification_algs/ecdsa_p521_sha384.ee.der"),
+    ),
+    (
+        "ecdsa_p521_sha512",
+        include_bytes!("verification_algs/ecdsa_p521_sha512.ca.der"),
+        include_bytes!("verification_algs/ecdsa_p521_sha512.ee.der"),
+    ),
+    (
+        "ed25519",
+        include_bytes!("verification_algs/ed25519.ca.der"),
+        include_bytes!("verification_algs/ed25519.ee.der"),
+    ),
+    (
+        "rsa_pkcs1_2048_8192_sha256",
+        include_bytes!("verification_algs/rsa_pkcs1_2048_8192_sha256.ca.der"),
+        include_bytes!("verification_algs/rsa_pkcs1_2048_8192_sha256.ee.der"),
+    ),
+    (
+        "rsa_pkcs1_2048_8192_sha384",
+        include_bytes!("verification_algs/rsa_pkcs1_2048_8192_sha384.ca.der"),
+        include_bytes!("verification_algs/rsa_pkcs1_2048_8192_sha384.ee.der"),
+    ),
+    (
+        "rsa_pkcs1_2048_8192_sha512",
+        include_bytes!("verification_algs/rsa_pkcs1_2048_8192_sha512.ca.der"),
+        include_bytes!("verification_algs/rsa_pkcs1_2048_8192_sha512.ee.der"),
+    ),
+    (
+        "rsa_pkcs1_3072_8192_sha384",
+        include_bytes!("verification_algs/rsa_pkcs1_3072_8192_sha384.ca.der"),
+        include_bytes!("verification_algs/rsa_pkcs1_3072_8192_sha384.ee.der"),
+    ),
+    (
+        "rsa_pss_2048_8192_sha256_legacy_key",
+        include_bytes!("verification_algs/rsa_pss_2048_8192_sha256_legacy_key.ca.der"),
+        include_bytes!("verification_algs/rsa_pss_2048_8192_sha256_legacy_key.ee.der"),
+    ),
+    (
+        "rsa_pss_2048_8192_sha384_legacy_key",
+        include_bytes!("verification_algs/rsa_pss_2048_8192_sha384_legacy_key.ca.der"),
+        include_bytes!("verification_algs/rsa_pss_2048_8192_sha384_legacy_key.ee.der"),
+    ),
+    (
+        "rsa_pss_2048_8192_sha512_legacy_key",
+        include_bytes!("verification_algs/rsa_pss_2048_8192_sha512_legacy_key.ca.der"),
+        include_bytes!("verification_algs/rsa_pss_2048_8192_sha512_legacy_key.ee.der"),
+    ),
+];
+
+/// The names of the chains that verify with `alg` alone.
+fn verified_by(alg: &'static dyn SignatureVerificationAlgorithm) -> Vec<&'static str> {
+    CHAINS
+        .iter()
+        .filter(|(_, ca, ee)| {
+            let ca = CertificateDer::from(*ca);
+            let anchors = [anchor_from_trusted_cert(&ca).unwrap()];
+            let ee = CertificateDer::from(*ee);
+            EndEntityCert::try_from(&ee)
+                .unwrap()
+                .verifier(&[alg], &anchors)
+                .at_time(UnixTime::since_unix_epoch(Duration::from_secs(TIME)))
+                .verify()
+                .is_ok()
+        })
+        .map(|(name, _, _)| *name)
+        .collect()
+}
+
+/// Asserts that each algorithm of `algs` verifies the chain of the same position in `names`.
+fn assert_verifies(algs: &[&'static dyn SignatureVerificationAlgorithm], names: &[&str]) {
+    assert_eq!(algs.len(), names.len());
+    for (alg, name) in algs.iter().zip(names) {
+        assert!(
+            verified_by(*alg).contains(name),
+            "{alg:?} doesn't verify {name}"
+        );
+    }
+}
+
+#[cfg(feature = "ring")]
+#[test]
+fn ring_algs() {
+    #[cfg_attr(not(feature = "alloc"), allow(unused_mut))]
+    let mut expected = vec![
+        "ecdsa_p256_sha256",
+        "ecdsa_p256_sha384",
+        "ecdsa_p384_sha256",
+        "ecdsa_p384_sha384",
+        "ed25519",
+    ];
+    #[cfg(feature = "alloc")]
+    expected.extend([
+        "rsa_pkcs1_2048_8192_sha256",
+        "rsa_pkcs1_2048_8192_sha384",
+        "rsa_pkcs1_2048_8192_sha512",
+        "rsa_pkcs1_3072_8192_sha384",
+        "rsa_pss_2048_8192_sha256_legacy_key",
+        "rsa_pss_2048_8192_sha384_legacy_key",
+        "rsa_pss_2048_8192_sha512_legacy_key",
+    ]);
+    assert_verifies(webpki::ring::ALL_VERIFICATION_ALGS, &expected);
+}
+
+#[cfg(feature = "aws_lc_rs")]
+#[test]
+fn aws_lc_rs_algs() {
+    let names = CHAINS.iter().map(|(name, _, _)| *name).collect::<Vec<_>>();
+    assert_verifies(webpki::aws_lc_rs::ALL_VERIFICATION_ALGS, &names);
+
+    let fips = names
+        .into_iter()
+        .filter(|name| *name != "ed25519")
+        .collect::<Vec<_>>();
+    assert_verifies(webpki::aws_lc_rs::FIPS_VERIFICATION_ALGS, &fips);
+    assert_eq!(
+        webpki::aws_lc_rs::FIPS_VERIFICATION_ALGS
+            .iter()
+            .flat_map(|alg| verified_by(*alg))
+            .find(|name| *name == "ed25519"),
+        None
+    );
+}
+
+#[test]
+fn all_verification_algs() {
+    // The crate-wide list is the concatenation of those of the enabled backends.
+    let mut expected = Vec::new();
+    #[cfg(feature = "ring")]
+    expected.extend_from_slice(webpki::ring::ALL_VERIFICATION_ALGS);
+    #[cfg(feature = "aws_lc_rs")]
+    expected.extend_from_slice(webpki::aws_lc_rs::ALL_VERIFICATION_ALGS);
+
+    let ids = |algs: &[&dyn SignatureVerificationAlgorithm]| {
+        algs.iter()
+            .map(|alg| (alg.public_key_alg_id(), alg.signature_alg_id()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(webpki::ALL_VERIFICATION_ALGS), ids(&expected));
+}